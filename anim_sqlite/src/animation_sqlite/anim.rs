@@ -1,5 +1,6 @@
 use super::*;
 use super::super::error::*;
+use super::super::result::Result;
 
 use flo_animation::*;
 
@@ -23,6 +24,30 @@ impl SqliteAnimation {
     pub fn panic_on_error(&self) {
         self.retrieve_and_clear_error().map(|erm| panic!("{:?}", erm));
     }
+
+    ///
+    /// Discards any cached drawings and rebuilds the file so that it uses as little space as possible
+    ///
+    pub fn compact_storage(&self) -> Result<()> {
+        self.db.compact_storage()
+    }
+
+    ///
+    /// Removes old edits from the edit log according to a policy along with any data that's no longer in use, then
+    /// rebuilds the file so it uses as little space as possible. Returns the number of edits that were removed.
+    ///
+    /// The animation itself is unchanged: the keyframes and elements are already stored separately from the edit log.
+    ///
+    pub fn compact(&self, policy: CompactionPolicy) -> Result<usize> {
+        self.db.compact(policy)
+    }
+
+    ///
+    /// Checks the file for corruption, returning a description of each problem that was found
+    ///
+    pub fn check_integrity(&self) -> Result<Vec<String>> {
+        self.db.check_integrity()
+    }
 }
 
 impl Animation for SqliteAnimation {
@@ -45,16 +70,7 @@ impl Animation for SqliteAnimation {
     }
 
     fn get_layer_with_id<'a>(&'a self, layer_id: u64) -> Option<Arc<dyn Layer>> {
-        // Try to retrieve the layer from the editor
-        let layer = self.db.get_layer_with_id(layer_id);
-
-        // Turn into a reader if it exists
-        let layer = layer.map(|layer| {
-            let layer_ref: Arc<dyn Layer> = Arc::new(layer);
-            layer_ref
-        });
-
-        layer
+        self.db.get_layer_with_id(layer_id)
     }
 
     fn get_num_edits(&self) -> usize {
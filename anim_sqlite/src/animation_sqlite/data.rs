@@ -1,4 +1,5 @@
 use super::*;
+use super::super::result::Result;
 
 use rusqlite::*;
 use std::path::Path;
@@ -31,6 +32,8 @@ impl SqliteAnimation {
     ///
     pub fn open_file<P: AsRef<Path>>(path: P) -> Result<SqliteAnimation> {
         let connection  = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+        AnimationDb::check_version(&connection)?;
+
         let db          = AnimationDb::from_connection(connection);
 
         Ok(SqliteAnimation {
@@ -38,6 +41,24 @@ impl SqliteAnimation {
         })
     }
 
+    ///
+    /// Returns the names of the upgrades that will be applied to an existing file when it's next opened
+    ///
+    pub fn pending_upgrades<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
+        let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+
+        AnimationDb::pending_upgrades(&connection)
+    }
+
+    ///
+    /// Upgrades an existing file to the latest version of the file format, without opening it as an animation
+    ///
+    pub fn upgrade_file<P: AsRef<Path>>(path: P) -> Result<()> {
+        let mut connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_WRITE)?;
+
+        AnimationDb::upgrade_connection(&mut connection)
+    }
+
     ///
     /// Takes an existing SQLite connection and creates a new animation in it
     ///
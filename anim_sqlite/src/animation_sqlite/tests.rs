@@ -1,4 +1,5 @@
 use super::*;
+use super::super::error::*;
 
 use flo_canvas::*;
 use flo_animation::*;
@@ -67,6 +68,63 @@ fn size_changes_after_being_set() {
     anim.panic_on_error();
 }
 
+#[test]
+fn duration_changes_after_being_set() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetDuration(Duration::from_millis(95_500))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.duration() == Duration::from_millis(95_500));
+}
+
+#[test]
+fn frame_length_changes_after_being_set() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetFrameLength(Duration::new(0, 41_666_667))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.frame_length() == Duration::new(0, 41_666_667));
+}
+
+#[test]
+fn zero_frame_length_is_clamped() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetFrameLength(Duration::from_millis(0))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.frame_length() == Duration::from_nanos(1));
+}
+
+#[test]
+fn read_duration_and_frame_length_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::SetDuration(Duration::from_millis(95_500)),
+        AnimationEdit::SetFrameLength(Duration::new(0, 41_666_667))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(0..2);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::SetDuration(Duration::from_millis(95_500)),
+        AnimationEdit::SetFrameLength(Duration::new(0, 41_666_667))
+    ]);
+}
+
 #[test]
 fn add_layer() {
     let anim = SqliteAnimation::new_in_memory();
@@ -171,6 +229,48 @@ fn retrieve_layer_ids() {
     anim.panic_on_error();
 }
 
+#[test]
+fn set_layer_ordering() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4)
+    ]);
+    assert!(anim.get_layer_ids() == vec![2, 3, 4]);
+
+    anim.perform_edits(vec![AnimationEdit::Layer(4, LayerEdit::SetOrdering(0))]);
+    assert!(anim.get_layer_ids() == vec![4, 2, 3]);
+
+    // Indexes past the end of the list move the layer to the top
+    anim.perform_edits(vec![AnimationEdit::Layer(2, LayerEdit::SetOrdering(10))]);
+    assert!(anim.get_layer_ids() == vec![4, 3, 2]);
+
+    // New layers are added on top of the existing ones
+    anim.perform_edits(vec![AnimationEdit::AddNewLayer(1)]);
+    assert!(anim.get_layer_ids() == vec![4, 3, 2, 1]);
+
+    anim.panic_on_error();
+}
+
+#[test]
+fn child_layers_follow_layer_ordering() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetOrdering(1))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.get_layer_with_id(1).unwrap().as_group_layer().unwrap().child_layer_ids() == vec![3, 2]);
+}
+
 #[test]
 fn retrieve_layer() {
     let anim = SqliteAnimation::new_in_memory();
@@ -200,6 +300,259 @@ fn non_existent_layer() {
     anim.panic_on_error();
 }
 
+#[test]
+fn layer_properties_default_to_visible_and_unlocked() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2)
+    ]);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+    assert!(layer.is_visible());
+    assert!(!layer.is_locked());
+    assert!(layer.opacity() == 1.0);
+    assert!(layer.blend_mode() == BlendMode::SourceOver);
+
+    anim.panic_on_error();
+}
+
+#[test]
+fn set_layer_properties() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetVisible(false)),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.25)),
+        AnimationEdit::Layer(2, LayerEdit::SetBlendMode(BlendMode::Multiply))
+    ]);
+    anim.panic_on_error();
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+    assert!(!layer.is_visible());
+    assert!(layer.is_locked());
+    assert!(layer.opacity() == 0.25);
+    assert!(layer.blend_mode() == BlendMode::Multiply);
+}
+
+#[test]
+fn remove_layer_with_properties() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetVisible(false)),
+        AnimationEdit::RemoveLayer(2),
+        AnimationEdit::AddNewLayer(3)
+    ]);
+    anim.panic_on_error();
+
+    let layer = anim.get_layer_with_id(3).unwrap();
+    assert!(layer.is_visible());
+    assert!(anim.get_layer_with_id(2).is_none());
+}
+
+#[test]
+fn read_layer_properties_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetVisible(false)),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.25)),
+        AnimationEdit::Layer(2, LayerEdit::SetBlendMode(BlendMode::Screen))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(1..5);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Layer(2, LayerEdit::SetVisible(false)),
+        AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.25)),
+        AnimationEdit::Layer(2, LayerEdit::SetBlendMode(BlendMode::Screen))
+    ]);
+}
+
+#[test]
+fn add_layers_to_group() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetParentGroup(Some(1)))
+    ]);
+    anim.panic_on_error();
+
+    let group = anim.get_layer_with_id(1).unwrap();
+    assert!(group.supported_edit_types() == vec![LayerEditType::Group]);
+    assert!(group.as_vector_layer().is_none());
+    assert!(group.as_group_layer().unwrap().child_layer_ids() == vec![2, 3]);
+    assert!(group.parent_group() == None);
+
+    let layer = anim.get_layer_with_id(2).unwrap();
+    assert!(layer.supported_edit_types() == vec![LayerEditType::Vector]);
+    assert!(layer.as_group_layer().is_none());
+    assert!(layer.parent_group() == Some(1));
+    assert!(anim.get_layer_with_id(4).unwrap().parent_group() == None);
+}
+
+#[test]
+fn remove_layer_from_group() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(None))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.get_layer_with_id(2).unwrap().parent_group() == None);
+    assert!(anim.get_layer_with_id(1).unwrap().as_group_layer().unwrap().child_layer_ids().len() == 0);
+}
+
+#[test]
+fn cannot_add_layer_to_vector_layer_or_make_group_loops() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewGroupLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(1, LayerEdit::SetParentGroup(Some(2))),
+        AnimationEdit::Layer(1, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(4, LayerEdit::SetParentGroup(Some(3))),
+        AnimationEdit::Layer(4, LayerEdit::SetParentGroup(Some(5)))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.get_layer_with_id(1).unwrap().parent_group() == None);
+    assert!(anim.get_layer_with_id(2).unwrap().parent_group() == Some(1));
+    assert!(anim.get_layer_with_id(4).unwrap().parent_group() == None);
+}
+
+#[test]
+fn cannot_add_keyframes_to_group_layer() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+
+    match anim.retrieve_and_clear_error() {
+        Some(SqliteAnimationError::UnexpectedLayerType(_))  => { },
+        other                                               => panic!("Unexpected result: {:?}", other)
+    }
+}
+
+#[test]
+fn removing_group_moves_layers_to_top_level() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::RemoveLayer(1)
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.get_layer_ids() == vec![2]);
+    assert!(anim.get_layer_with_id(2).unwrap().parent_group() == None);
+}
+
+#[test]
+fn layers_share_group_visibility_and_opacity() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewGroupLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetParentGroup(Some(2))),
+        AnimationEdit::Layer(1, LayerEdit::SetOpacity(0.5)),
+        AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.5)),
+        AnimationEdit::Layer(2, LayerEdit::SetBlendMode(BlendMode::Multiply)),
+        AnimationEdit::Layer(3, LayerEdit::SetOpacity(0.5))
+    ]);
+    anim.panic_on_error();
+
+    // Layer 3 is drawn as part of group 1, so it uses the opacity and blend mode of that group
+    let layer = anim.get_layer_with_id(3).unwrap();
+    assert!(composited_layer_id(&anim, &*layer) == 1);
+    assert!(composited_layer_appearance(&anim, &*layer) == (true, 0.5, BlendMode::SourceOver));
+
+    let layer = anim.get_layer_with_id(4).unwrap();
+    assert!(composited_layer_id(&anim, &*layer) == 4);
+    assert!(composited_layer_appearance(&anim, &*layer) == (true, 1.0, BlendMode::SourceOver));
+
+    anim.perform_edits(vec![
+        AnimationEdit::Layer(1, LayerEdit::SetVisible(false))
+    ]);
+
+    let layer = anim.get_layer_with_id(3).unwrap();
+    assert!(composited_layer_appearance(&anim, &*layer).0 == false);
+}
+
+#[test]
+fn layers_follow_their_group() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::AddNewGroupLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::AddNewLayer(4),
+        AnimationEdit::Layer(1, LayerEdit::SetParentGroup(Some(2))),
+        AnimationEdit::Layer(4, LayerEdit::SetParentGroup(Some(2)))
+    ]);
+    anim.panic_on_error();
+
+    assert!(layer_ids_in_group_order(&anim) == vec![2, 1, 4, 3]);
+}
+
+#[test]
+fn read_layer_groups_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(None))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(0..4);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(None))
+    ]);
+}
+
 #[test]
 fn add_keyframe() {
     let anim = SqliteAnimation::new_in_memory();
@@ -477,7 +830,7 @@ fn read_brush_strokes_from_edit_log() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -513,7 +866,7 @@ fn read_brush_strokes_from_edit_log() {
     assert!(match edits[3] {
         AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::
             BrushProperties(ElementId::Assigned(_element_id), ref brush_properties)))
-                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 },
+                => brush_properties == &BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None },
             _ => false
     });
     assert!(match edits[6] {
@@ -527,65 +880,200 @@ fn read_brush_strokes_from_edit_log() {
 }
 
 #[test]
-fn will_assign_element_ids() {
-    let animation = SqliteAnimation::new_in_memory();;
+fn read_gradient_brush_properties_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
 
-    // Perform some edits on the animation with an unassigned element ID
-    animation.perform_edits(vec![
-        AnimationEdit::AddNewLayer(0),
-        AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
-                ElementId::Unassigned,
-                BrushDefinition::Ink(InkDefinition::default()),
-                BrushDrawingStyle::Draw
-            )
-        )),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
-        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
-                RawPoint::from((10.0, 10.0)),
-                RawPoint::from((20.0, 5.0))
-            ]))))
+    let linear = BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 1.0, gradient: Some(BrushGradient::Linear(Color::Rgba(0.1, 0.9, 0.3, 1.0), 90.0)) };
+    let radial = BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 0.5, size: 1.0, gradient: Some(BrushGradient::Radial(Color::Rgba(1.0, 1.0, 1.0, 1.0))) };
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, linear))),
+        AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, radial)))
     ]);
+    anim.panic_on_error();
 
-    // Element ID should be assigned if we read the log back
-    let edit_log        = animation.read_edit_log(4..5);
+    let edit_log        = anim.read_edit_log(2..4);
     let edit_log        = edit_log.collect();
     let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
 
-    let paint_edit = edit_log.wait_future().unwrap();
-
-    // Should be able to find the paint edit here
-    assert!(match &paint_edit[0] { &AnimationEdit::Layer(0, LayerEdit::Paint(_, _)) => true, _ => false });
-
-    // Element ID should be assigned
-    assert!(match &paint_edit[0] { &AnimationEdit::Layer(0, LayerEdit::Paint(_, PaintEdit::BrushStroke(ElementId::Assigned(_), _))) => true, _ => false });
+    assert!(edits.len() == 2);
+    assert!(match edits[0] {
+        AnimationEdit::Layer(2, LayerEdit::Path(_, PathEdit::BrushProperties(_, ref brush_properties))) => brush_properties == &linear,
+        _ => false
+    });
+    assert!(match edits[1] {
+        AnimationEdit::Layer(2, LayerEdit::Path(_, PathEdit::BrushProperties(_, ref brush_properties))) => brush_properties == &radial,
+        _ => false
+    });
 }
 
 #[test]
-fn fetch_element_by_id() {
-    let anim = SqliteAnimation::new_in_memory();
+fn read_calligraphy_brush_from_edit_log_and_frame() {
+    let anim        = SqliteAnimation::new_in_memory();
+    let calligraphy = CalligraphyDefinition { min_width: 0.5, max_width: 12.0, nib_angle: 30.0, nib_aspect_ratio: 0.25, tilt_influence: 0.75 };
 
     anim.perform_edits(vec![
         AnimationEdit::AddNewLayer(2),
-        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(50))),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
                 ElementId::Unassigned,
-                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDefinition::Calligraphy(calligraphy.clone()),
                 BrushDrawingStyle::Draw
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
-                    RawPoint::from((10.0, 10.0)),
-                    RawPoint::from((20.0, 5.0))
-                ])))),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(127), Arc::new(vec![
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 12.0, gradient: None }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
-                    RawPoint::from((20.0, 5.0))
+                    RawPoint::from((20.0, 5.0)),
+                    RawPoint::from((30.0, 10.0)),
+                    RawPoint::from((40.0, 5.0))
                 ])))),
-        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(128), Arc::new(vec![
-                    RawPoint::from((10.0, 10.0)),
+    ]);
+    anim.panic_on_error();
+
+    // The brush definition should be read back from the edit log
+    let edit_log        = anim.read_edit_log(2..3);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(match edits[0] {
+        AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::SelectBrush(
+                ElementId::Assigned(_element_id),
+                BrushDefinition::Calligraphy(ref calligraphy_defn),
+                BrushDrawingStyle::Draw
+            )
+        ))  => calligraphy_defn == &calligraphy,
+        _   => false
+    });
+
+    // ... and should be attached to the brush stroke in the frame
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(442));
+    let element     = frame.element_with_id(ElementId::Assigned(100)).unwrap();
+    let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+
+    assert!(properties.brush.to_definition() == (BrushDefinition::Calligraphy(calligraphy), BrushDrawingStyle::Draw));
+}
+
+#[test]
+fn read_ink_brush_pressure_curve_from_edit_log_and_frame() {
+    let anim        = SqliteAnimation::new_in_memory();
+    let ink         = InkDefinition {
+        pressure_curve:     PressureCurve::Bezier((0.25, 0.0), (0.5, 0.75)),
+        pressure_source:    PressureSource::Velocity,
+        ..InkDefinition::default()
+    };
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(ink.clone()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 12.0, gradient: None }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0)),
+                    RawPoint::from((30.0, 10.0)),
+                    RawPoint::from((40.0, 5.0))
+                ])))),
+    ]);
+    anim.panic_on_error();
+
+    // The pressure settings should be read back from the edit log
+    let edit_log        = anim.read_edit_log(2..3);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(match edits[0] {
+        AnimationEdit::Layer(2, LayerEdit::Paint(_when, PaintEdit::SelectBrush(
+                ElementId::Assigned(_element_id),
+                BrushDefinition::Ink(ref ink_defn),
+                BrushDrawingStyle::Draw
+            )
+        ))  => ink_defn == &ink,
+        _   => false
+    });
+
+    // ... and should be attached to the brush stroke in the frame
+    let layer       = anim.get_layer_with_id(2).unwrap();
+    let frame       = layer.get_frame_at_time(Duration::from_millis(442));
+    let element     = frame.element_with_id(ElementId::Assigned(100)).unwrap();
+    let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+
+    assert!(properties.brush.to_definition() == (BrushDefinition::Ink(ink), BrushDrawingStyle::Draw));
+}
+
+#[test]
+fn will_assign_element_ids() {
+    let animation = SqliteAnimation::new_in_memory();;
+
+    // Perform some edits on the animation with an unassigned element ID
+    animation.perform_edits(vec![
+        AnimationEdit::AddNewLayer(0),
+        AnimationEdit::Layer(0, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
+        AnimationEdit::Layer(0, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0))
+            ]))))
+    ]);
+
+    // Element ID should be assigned if we read the log back
+    let edit_log        = animation.read_edit_log(4..5);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+
+    let paint_edit = edit_log.wait_future().unwrap();
+
+    // Should be able to find the paint edit here
+    assert!(match &paint_edit[0] { &AnimationEdit::Layer(0, LayerEdit::Paint(_, _)) => true, _ => false });
+
+    // Element ID should be assigned
+    assert!(match &paint_edit[0] { &AnimationEdit::Layer(0, LayerEdit::Paint(_, PaintEdit::BrushStroke(ElementId::Assigned(_), _))) => true, _ => false });
+}
+
+#[test]
+fn fetch_element_by_id() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(50))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(127), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(128), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
                 ])))),
     ]);
@@ -626,7 +1114,7 @@ fn read_frame_after_edits() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -684,6 +1172,60 @@ fn read_frame_after_edits() {
     }
 }
 
+#[test]
+fn create_brush_stroke_from_brush_points() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let brush_points = vec![
+        BrushPoint { position: (10.0, 10.0), cp1: (10.0, 10.0), cp2: (10.0, 10.0), width: 0.0 },
+        BrushPoint { position: (20.0, 5.0), cp1: (13.0, 8.0), cp2: (17.0, 6.0), width: 4.5 }
+    ];
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            )
+        )),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
+        AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::CreateBrushStroke(ElementId::Unassigned, Arc::new(brush_points.clone()))))
+    ]);
+    anim.panic_on_error();
+
+    // The brush stroke should have exactly the points that were passed in, with the brush attached
+    let layer               = anim.get_layer_with_id(2).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(0));
+    let elements: Vec<_>    = frame.vector_elements().unwrap().collect();
+
+    assert!(elements.len() == 1);
+
+    let brush_stroke = match &elements[0] {
+        &Vector::BrushStroke(ref brush_stroke)  => brush_stroke.clone(),
+        _                                       => { assert!(false); unimplemented!() }
+    };
+
+    assert!(*brush_stroke.points() == brush_points);
+    assert!(frame.attached_elements(brush_stroke.id()).len() == 2);
+
+    // Points should round-trip via the edit log too
+    let edit_log        = anim.read_edit_log(4..5);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+
+    let paint_edit = edit_log.wait_future().unwrap();
+
+    assert!(match &paint_edit[0] {
+        &AnimationEdit::Layer(2, LayerEdit::Paint(_, PaintEdit::CreateBrushStroke(ElementId::Assigned(_), ref points))) => {
+            points.len() == 2 && (points[1].cp1.0-13.0).abs() < 0.01 && (points[1].width-4.5).abs() < 0.01
+        },
+        _ => false
+    });
+}
+
 #[test]
 fn delete_element() {
     let anim = SqliteAnimation::new_in_memory();
@@ -698,7 +1240,7 @@ fn delete_element() {
             )
         )),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::
-            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0 }))),
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
         AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(442), PaintEdit::BrushStroke(ElementId::Assigned(126), Arc::new(vec![
                     RawPoint::from((10.0, 10.0)),
                     RawPoint::from((20.0, 5.0))
@@ -804,6 +1346,138 @@ fn move_existing_element() {
     }
 }
 
+#[test]
+fn create_transform_motion() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let when        = Duration::from_millis(442);
+    let translate   = TimeCurve::new(TimePoint::new(200.0, 200.0, when), TimePoint::new(200.0, 200.0, when));
+    let rotate      = TimeCurve::new(TimePoint::new(90.0, 0.0, when), TimePoint::new(90.0, 0.0, when));
+    let scale       = TimeCurve::new(TimePoint::new(2.0, 0.5, when), TimePoint::new(2.0, 0.5, when));
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Transform)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(50.0, 60.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(translate.clone())),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetRotation(rotate.clone())),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetScale(scale.clone()))
+    ]);
+    anim.panic_on_error();
+
+    let motion = anim.get_motion(ElementId::Assigned(100));
+    assert!(motion.as_ref().unwrap().motion_type() == MotionType::Transform);
+
+    if let Some(Motion::Transform(transform)) = motion {
+        assert!(transform.origin == (50.0, 60.0));
+        assert!(transform.translate == translate);
+        assert!(transform.rotate == rotate);
+        assert!(transform.scale == scale);
+    } else {
+        assert!(false)
+    }
+}
+
+#[test]
+fn read_motion_rotation_and_scale_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let when        = Duration::from_millis(442);
+    let rotate      = TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(90.0, 0.0, when));
+    let scale       = TimeCurve::new(TimePoint::new(1.0, 1.0, Duration::from_millis(0)), TimePoint::new(2.0, 0.5, when));
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Transform)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetRotation(rotate.clone())),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetScale(scale.clone()))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(2..4);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetRotation(rotate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetScale(scale))
+    ]);
+}
+
+#[test]
+fn read_motion_edits_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let path        = TimeCurve::new(TimePoint::new(10.0, 20.0, Duration::from_millis(0)), TimePoint::new(500.0, 400.0, Duration::from_millis(2000)));
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(30.0, 40.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(path.clone())),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Delete)
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(0..5);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(30.0, 40.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(path)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Delete)
+    ]);
+}
+
+#[test]
+fn create_rotate_motion() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let when        = Duration::from_millis(442);
+    let rotate      = TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(180.0, 0.0, when));
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Rotate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(50.0, 60.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetRotation(rotate.clone()))
+    ]);
+    anim.panic_on_error();
+
+    let motion = anim.get_motion(ElementId::Assigned(100));
+
+    if let Some(Motion::Rotate(rotation)) = motion {
+        assert!(rotation.origin == (50.0, 60.0));
+        assert!(rotation.rotate == rotate);
+    } else {
+        assert!(false)
+    }
+}
+
+#[test]
+fn eased_motion_path_round_trips() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let path = TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 50.0, Duration::from_millis(1000)))
+        .set_section_easing(0, Easing::Bounce);
+
+    anim.perform_edits(vec![
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::Create),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetType(MotionType::Translate)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetOrigin(0.0, 0.0)),
+        AnimationEdit::Motion(ElementId::Assigned(100), MotionEdit::SetPath(path.clone()))
+    ]);
+    anim.panic_on_error();
+
+    let motion = anim.get_motion(ElementId::Assigned(100));
+    assert!(motion.as_ref().and_then(|motion| motion.path()) == Some(&path));
+}
+
 #[test]
 fn read_path_element() {
     use self::LayerEdit::*;
@@ -1042,3 +1716,541 @@ fn retrieve_cached_onionskin_from_different_time() {
 
     assert!(cached_drawing == None);
 }
+
+#[test]
+fn group_paths_then_ungroup() {
+    use self::LayerEdit::*;
+
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, flo_animation::BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 50.0)),
+                PathComponent::Line(PathPoint::new(10.0, 50.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(30.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 70.0)),
+                PathComponent::Line(PathPoint::new(30.0, 70.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(102), GroupType::Subtracted))
+    ]);
+
+    {
+        let layer               = anim.get_layer_with_id(24).unwrap();
+        let frame               = layer.get_frame_at_time(Duration::from_millis(300));
+        let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+        assert!(elements.len() == 1);
+        assert!(elements[0].id() == ElementId::Assigned(102));
+
+        match &elements[0] {
+            Vector::Group(group) => {
+                assert!(group.group_type() == GroupType::Subtracted);
+
+                let member_ids = group.elements()
+                    .filter(|member| match member { Vector::BrushDefinition(_) | Vector::BrushProperties(_) => false, _ => true })
+                    .map(|member| member.id())
+                    .collect::<Vec<_>>();
+                assert!(member_ids == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+            },
+
+            _ => assert!(false)
+        }
+    }
+
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Ungroup)
+    ]);
+
+    {
+        let layer               = anim.get_layer_with_id(24).unwrap();
+        let frame               = layer.get_frame_at_time(Duration::from_millis(300));
+        let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+        assert!(elements.len() == 2);
+        assert!(elements[0].id() == ElementId::Assigned(100));
+        assert!(elements[1].id() == ElementId::Assigned(101));
+    }
+}
+
+#[test]
+fn read_group_and_ungroup_from_edit_log() {
+    use self::LayerEdit::*;
+
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, flo_animation::BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 10.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(30.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 30.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(102), GroupType::Added)),
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Ungroup)
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(6..8);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(102), GroupType::Added)),
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Ungroup)
+    ]);
+}
+
+#[test]
+fn read_element_edits_from_edit_log() {
+    use self::LayerEdit::*;
+
+    let anim = SqliteAnimation::new_in_memory();
+
+    let new_path = Arc::new(vec![
+        PathComponent::Move(PathPoint::new(15.0, 15.0)),
+        PathComponent::Line(PathPoint::new(55.0, 15.0)),
+        PathComponent::Bezier(PathPoint::new(55.0, 55.0), PathPoint::new(60.0, 20.0), PathPoint::new(60.0, 50.0)),
+        PathComponent::Close
+    ]);
+
+    let element_edits = vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetControlPoints(vec![(10.0, 20.0), (50.0, 20.0), (50.0, 60.0)])),
+        AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::SetPath(new_path)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Order(ElementOrdering::InFront)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Order(ElementOrdering::Behind)),
+        AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Order(ElementOrdering::ToBottom)),
+        AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Order(ElementOrdering::ToTop)),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::AddAttachment(ElementId::Assigned(101))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::RemoveAttachment(ElementId::Assigned(101))),
+        AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::DetachFromFrame),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)
+    ];
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, flo_animation::BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 50.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(30.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 70.0)),
+                PathComponent::Close
+            ]))))
+    ].into_iter().chain(element_edits.iter().cloned()).collect());
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(6..16);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == element_edits);
+}
+
+#[test]
+fn delete_grouped_paths() {
+    use self::LayerEdit::*;
+
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, flo_animation::BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 50.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(30.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 70.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(102), GroupType::Masked)),
+        AnimationEdit::Element(vec![ElementId::Assigned(102)], ElementEdit::Delete)
+    ]);
+
+    let layer               = anim.get_layer_with_id(24).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(300));
+    let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 0);
+}
+
+#[test]
+fn create_text_element() {
+    use self::LayerEdit::*;
+
+    let anim = SqliteAnimation::new_in_memory();
+
+    let mut style   = TextStyle::new();
+    style.font_family   = "serif".to_string();
+    style.font_size     = 32.0;
+    style.color         = Color::Rgba(1.0, 0.0, 0.0, 1.0);
+    style.alignment     = TextAlignment::Center;
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(24, Text(Duration::from_millis(300),
+            TextEdit::CreateText(ElementId::Assigned(100), style.clone(), (100.0, 200.0), "Hello\nWorld".to_string())))
+    ]);
+    anim.panic_on_error();
+
+    let layer               = anim.get_layer_with_id(24).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(300));
+    let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 1);
+    assert!(elements[0].id() == ElementId::Assigned(100));
+
+    match &elements[0] {
+        Vector::Text(text) => {
+            assert!(text.text() == "Hello\nWorld");
+            assert!(text.position() == (100.0, 200.0));
+            assert!(text.style() == &style);
+        },
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn read_text_element_from_edit_log() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    let mut style   = TextStyle::new();
+    style.font_family   = "monospace".to_string();
+    style.font_size     = 12.0;
+    style.color         = Color::Rgba(0.0, 0.0, 1.0, 1.0);
+    style.alignment     = TextAlignment::Right;
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Text(Duration::from_millis(0),
+            TextEdit::CreateText(ElementId::Assigned(100), style.clone(), (20.0, 30.0), "Speech bubble".to_string())))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(2..3);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Layer(24, LayerEdit::Text(Duration::from_millis(0),
+            TextEdit::CreateText(ElementId::Assigned(100), style, (20.0, 30.0), "Speech bubble".to_string())))
+    ]);
+}
+
+#[test]
+fn move_text_element_with_control_points() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Text(Duration::from_millis(0),
+            TextEdit::CreateText(ElementId::Assigned(100), TextStyle::new(), (20.0, 30.0), "Moved".to_string()))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetControlPoints(vec![(50.0, 70.0)]))
+    ]);
+    anim.panic_on_error();
+
+    let layer               = anim.get_layer_with_id(24).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(0));
+    let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 1);
+
+    match &elements[0] {
+        Vector::Text(text) => {
+            assert!(text.text() == "Moved");
+            assert!(text.position() == (50.0, 70.0));
+        },
+
+        _ => assert!(false)
+    }
+}
+
+///
+/// Creates the header of a PNG file of the specified size (enough to be loaded as a canvas image)
+///
+fn png_header(width: u8, height: u8) -> CanvasImage {
+    let data = vec![
+        137, 80, 78, 71, 13, 10, 26, 10,
+        0, 0, 0, 13, b'I', b'H', b'D', b'R',
+        0, 0, 0, width,
+        0, 0, 0, height,
+        8, 6, 0, 0, 0
+    ];
+
+    CanvasImage::from_data(data).unwrap()
+}
+
+#[test]
+fn create_image_element() {
+    use self::LayerEdit::*;
+
+    let anim        = SqliteAnimation::new_in_memory();
+    let image       = png_header(3, 2);
+    let transform   = Transform2D::translate(100.0, 200.0) * Transform2D::scale(30.0, 20.0);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(100))),
+        AnimationEdit::Layer(24, Image(Duration::from_millis(300),
+            ImageEdit::CreateImage(ElementId::Assigned(100), image.clone(), transform)))
+    ]);
+    anim.panic_on_error();
+
+    let layer               = anim.get_layer_with_id(24).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(300));
+    let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 1);
+    assert!(elements[0].id() == ElementId::Assigned(100));
+
+    match &elements[0] {
+        Vector::Image(image_element) => {
+            assert!(image_element.image() == &image);
+            assert!(image_element.transform() == transform);
+        },
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn read_image_element_from_edit_log() {
+    let anim        = SqliteAnimation::new_in_memory();
+    let image       = png_header(16, 8);
+    let transform   = Transform2D::translate(20.0, 30.0) * Transform2D::scale(160.0, 80.0);
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Image(Duration::from_millis(0),
+            ImageEdit::CreateImage(ElementId::Assigned(100), image.clone(), transform)))
+    ]);
+    anim.panic_on_error();
+
+    let edit_log        = anim.read_edit_log(2..3);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Layer(24, LayerEdit::Image(Duration::from_millis(0),
+            ImageEdit::CreateImage(ElementId::Assigned(100), image, transform)))
+    ]);
+}
+
+#[test]
+fn transform_image_element_with_control_points() {
+    let anim        = SqliteAnimation::new_in_memory();
+    let image       = png_header(3, 2);
+    let transform   = Transform2D::translate(100.0, 200.0) * Transform2D::scale(30.0, 20.0);
+
+    // Move the image and double its width (the top-right corner is ignored as images are always parallelograms)
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(24, LayerEdit::Image(Duration::from_millis(0),
+            ImageEdit::CreateImage(ElementId::Assigned(100), image.clone(), transform))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetControlPoints(vec![(110.0, 190.0), (170.0, 190.0), (0.0, 0.0), (110.0, 210.0)]))
+    ]);
+    anim.panic_on_error();
+
+    let layer               = anim.get_layer_with_id(24).unwrap();
+    let frame               = layer.get_frame_at_time(Duration::from_millis(0));
+    let elements            = frame.vector_elements().unwrap().collect::<Vec<_>>();
+
+    assert!(elements.len() == 1);
+
+    match &elements[0] {
+        Vector::Image(image_element) => {
+            assert!(image_element.image() == &image);
+            assert!(image_element.corners() == [(110.0, 190.0), (170.0, 190.0), (170.0, 210.0), (110.0, 210.0)]);
+        },
+
+        _ => assert!(false)
+    }
+}
+
+#[test]
+fn new_animation_passes_integrity_check() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+    ]);
+    anim.panic_on_error();
+
+    assert!(anim.check_integrity().unwrap().len() == 0);
+}
+
+#[test]
+fn compacting_storage_preserves_edits() {
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::SetSize(800.0, 600.0)
+    ]);
+    anim.panic_on_error();
+
+    anim.compact_storage().unwrap();
+
+    assert!(anim.get_num_edits() == 3);
+    assert!(anim.size() == (800.0, 600.0));
+    assert!(anim.get_layer_ids() == vec![2]);
+}
+
+#[test]
+fn compacting_edit_log_preserves_rendering() {
+    use self::LayerEdit::*;
+
+    let anim = SqliteAnimation::new_in_memory();
+
+    anim.perform_edits(vec![
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(300))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::BrushProperties(ElementId::Unassigned, flo_animation::BrushProperties::new()))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 50.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(24, Path(Duration::from_millis(300),
+            PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(30.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 30.0)),
+                PathComponent::Line(PathPoint::new(70.0, 70.0)),
+                PathComponent::Close
+            ])))),
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300), PaintEdit::SelectBrush(
+                ElementId::Unassigned,
+                BrushDefinition::Ink(InkDefinition::default()),
+                BrushDrawingStyle::Draw
+            ))),
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300), PaintEdit::
+            BrushProperties(ElementId::Unassigned, BrushProperties { color: Color::Rgba(0.5, 0.2, 0.7, 1.0), opacity: 1.0, size: 32.0, gradient: None }))),
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300), PaintEdit::BrushStroke(ElementId::Assigned(102), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0))
+            ])))),
+        AnimationEdit::Layer(24, Paint(Duration::from_millis(300), PaintEdit::BrushStroke(ElementId::Assigned(103), Arc::new(vec![
+                RawPoint::from((40.0, 10.0)),
+                RawPoint::from((60.0, 25.0))
+            ]))))
+    ]);
+
+    // Nudge the control points and delete an element so there's some history that no longer affects the animation
+    anim.perform_edits(vec![
+        AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetPath(Arc::new(vec![
+            PathComponent::Move(PathPoint::new(15.0, 10.0)),
+            PathComponent::Line(PathPoint::new(55.0, 10.0)),
+            PathComponent::Line(PathPoint::new(55.0, 50.0)),
+            PathComponent::Close
+        ]))),
+        AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::SetControlPoints(vec![(35.0, 30.0), (75.0, 30.0), (75.0, 70.0)])),
+        AnimationEdit::Element(vec![ElementId::Assigned(103)], ElementEdit::Delete),
+        AnimationEdit::Layer(24, SetName("Compacted".to_string())),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(600)))
+    ]);
+    anim.panic_on_error();
+
+    let render_frame = |anim: &SqliteAnimation| {
+        let layer                   = anim.get_layer_with_id(24).unwrap();
+        let frame                   = layer.get_frame_at_time(Duration::from_millis(300));
+        let mut drawing: Vec<Draw>  = vec![];
+
+        frame.render_to(&mut drawing);
+        drawing
+    };
+
+    let before_compaction = render_frame(&anim);
+    assert!(anim.get_num_edits() == 15);
+
+    // Keep only the last two edits
+    assert!(anim.compact(CompactionPolicy::KeepLastEdits(2)).unwrap() == 13);
+    anim.panic_on_error();
+
+    let after_compaction = render_frame(&anim);
+
+    assert!(before_compaction.len() > 0);
+    assert!(before_compaction == after_compaction);
+    assert!(anim.get_num_edits() == 2);
+    assert!(anim.check_integrity().unwrap().len() == 0);
+
+    // The edits that were kept can still be read back
+    let edit_log        = anim.read_edit_log(0..2);
+    let edit_log        = edit_log.collect();
+    let mut edit_log    = executor::spawn(edit_log);
+    let edits           = edit_log.wait_future().unwrap();
+
+    assert!(edits == vec![
+        AnimationEdit::Layer(24, SetName("Compacted".to_string())),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(600)))
+    ]);
+
+    // Compacting again while keeping all the edits leaves the animation as it was
+    assert!(anim.compact(CompactionPolicy::KeepAllEdits).unwrap() == 0);
+    assert!(render_frame(&anim) == before_compaction);
+    assert!(anim.get_num_edits() == 2);
+}
@@ -1,5 +1,7 @@
 use super::*;
+use super::db_enum::*;
 use super::flo_query::*;
+use super::super::result::Result;
 
 use std::time::Duration;
 
@@ -40,6 +42,23 @@ impl AnimationDb {
         }).unwrap()
     }
 
+    ///
+    /// Retrieves a layer for a particular ID
+    ///
+    pub fn get_layer_with_id(&self, assigned_id: u64) -> Option<Arc<dyn Layer>> {
+        // Group layers and vector layers are represented by different types
+        let layer_type: Result<_> = self.core.sync(|core| {
+            let (layer_id, _name) = core.db.query_layer_id_for_assigned_id(assigned_id)?;
+            core.db.query_layer_type(layer_id)
+        });
+
+        match layer_type {
+            Ok(LayerType::Vector)   => SqliteVectorLayer::from_assigned_id(&self.core, assigned_id).map(|layer| { let layer: Arc<dyn Layer> = Arc::new(layer); layer }),
+            Ok(LayerType::Group)    => SqliteGroupLayer::from_assigned_id(&self.core, assigned_id).map(|layer| { let layer: Arc<dyn Layer> = Arc::new(layer); layer }),
+            Err(_)                  => None
+        }
+    }
+
     ///
     /// Assigns an unique element ID
     ///
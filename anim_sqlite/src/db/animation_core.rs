@@ -96,6 +96,9 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             Layer(layer_id, Paint(when, BrushStroke(ElementId::Unassigned, points))) =>
                 Layer(layer_id, Paint(when, BrushStroke(ElementId::Assigned(self.next_element_id()), points))),
 
+            Layer(layer_id, Paint(when, CreateBrushStroke(ElementId::Unassigned, points))) =>
+                Layer(layer_id, Paint(when, CreateBrushStroke(ElementId::Assigned(self.next_element_id()), points))),
+
             Layer(layer_id, Path(when, PathEdit::CreatePath(ElementId::Unassigned, points))) =>
                 Layer(layer_id, Path(when, PathEdit::CreatePath(ElementId::Assigned(self.next_element_id()), points))),
 
@@ -105,6 +108,15 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             Layer(layer_id, Path(when, PathEdit::BrushProperties(ElementId::Unassigned, properties))) =>
                 Layer(layer_id, Path(when, PathEdit::BrushProperties(ElementId::Assigned(self.next_element_id()), properties))),
 
+            Layer(layer_id, Text(when, TextEdit::CreateText(ElementId::Unassigned, style, position, text))) =>
+                Layer(layer_id, Text(when, TextEdit::CreateText(ElementId::Assigned(self.next_element_id()), style, position, text))),
+
+            Layer(layer_id, Image(when, ImageEdit::CreateImage(ElementId::Unassigned, image, transform))) =>
+                Layer(layer_id, Image(when, ImageEdit::CreateImage(ElementId::Assigned(self.next_element_id()), image, transform))),
+
+            Element(element_ids, ElementEdit::Group(ElementId::Unassigned, group_type)) =>
+                Element(element_ids, ElementEdit::Group(ElementId::Assigned(self.next_element_id()), group_type)),
+
             other => other
         }
     }
@@ -248,6 +260,52 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
         Ok(())
     }
 
+    ///
+    /// Attaches the brush properties that are currently active on a layer to the element on top of the stack
+    ///
+    fn attach_active_brush_properties(&mut self, layer_id: i64) -> Result<()> {
+        let property_elements   = self.brush_properties_for_layer.get(&layer_id)
+            .map(|properties| properties.property_of_type.values().filter_map(|elem| elem.id()))
+            .map(|assigned_ids| assigned_ids.map(|assigned_id| DatabaseUpdate::PushElementIdForAssignedId(assigned_id)))
+            .map(|push_ids| push_ids.collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![]);
+        let num_properties      = property_elements.len();
+
+        if num_properties > 0 {
+            // Push all of the assigned IDs for the properties, followed by attaching them to the brush element we're building
+            self.db.update(property_elements.into_iter()
+                .chain(vec![DatabaseUpdate::PushAttachElements(num_properties)]))?;
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Stops attaching an element to new brush strokes and paths (called when the element is deleted)
+    ///
+    fn forget_brush_element(&mut self, element_id: ElementId) {
+        let mut changed_layers = vec![];
+
+        for (layer_id, properties) in self.brush_properties_for_layer.iter_mut() {
+            let num_properties = properties.property_of_type.len();
+            properties.property_of_type.retain(|_property_type, property_id| *property_id != element_id);
+
+            if properties.property_of_type.len() != num_properties {
+                changed_layers.push(*layer_id);
+            }
+        }
+
+        for path_properties in self.path_properties_for_layer.values_mut() {
+            if path_properties.brush_id == element_id       { path_properties.brush_id = ElementId::Unassigned; }
+            if path_properties.properties_id == element_id  { path_properties.properties_id = ElementId::Unassigned; }
+        }
+
+        // The brush for a layer is cached, so needs to be looked up again if its definition has gone
+        for layer_id in changed_layers {
+            self.active_brush_for_layer.remove(&layer_id);
+        }
+    }
+
     ///
     /// Adds a new vector element to a vector layer
     ///
@@ -271,18 +329,7 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 Self::create_new_element(&mut self.db, layer_id, when, id, VectorElementType::BrushStroke)?;
 
                 // Attach the properties for this brush stroke
-                let property_elements   = self.brush_properties_for_layer.get(&layer_id)
-                    .map(|properties| properties.property_of_type.values().filter_map(|elem| elem.id()))
-                    .map(|assigned_ids| assigned_ids.map(|assigned_id| DatabaseUpdate::PushElementIdForAssignedId(assigned_id)))
-                    .map(|push_ids| push_ids.collect::<Vec<_>>())
-                    .unwrap_or_else(|| vec![]);
-                let num_properties      = property_elements.len();
-
-                if num_properties > 0 {
-                    // Push all of the assigned IDs for the properties, followed by attaching them to the brush element we're building
-                    self.db.update(property_elements.into_iter()
-                        .chain(vec![DatabaseUpdate::PushAttachElements(num_properties)]))?;
-                }
+                self.attach_active_brush_properties(layer_id)?;
 
                 // Create the brush stroke (popping the element ID)
                 self.create_brush_stroke(layer_id, when, brush_stroke)?;
@@ -291,6 +338,19 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.db.update(vec![DatabaseUpdate::Pop, DatabaseUpdate::Pop])?;
             },
 
+            CreateBrushStroke(id, brush_points)                 => {
+                // New brush stroke element, with the same properties as a brush stroke drawn with the current brush
+                Self::create_new_element(&mut self.db, layer_id, when, id, VectorElementType::BrushStroke)?;
+                self.attach_active_brush_properties(layer_id)?;
+
+                // The points have already been processed by the brush, so they can be stored directly (popping the element ID, the frame ID and the time)
+                self.db.update(vec![
+                    DatabaseUpdate::PopBrushPoints(brush_points),
+                    DatabaseUpdate::Pop,
+                    DatabaseUpdate::Pop
+                ])?;
+            },
+
             SelectBrush(id, brush_definition, drawing_style)    => {
                 // Create a new brush definition to use with the future brush strokes
                 Self::create_unattached_element(&mut self.db, VectorElementType::BrushDefinition, id)?;
@@ -377,6 +437,50 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
         Ok(())
     }
 
+    ///
+    /// Adds a text element to a vector layer
+    ///
+    fn text_vector_layer(&mut self, layer_id: i64, when: Duration, new_element: TextEdit) -> Result<()> {
+        use self::TextEdit::*;
+
+        match new_element {
+            CreateText(element_id, style, position, text)               => {
+                // Need the stack to be color_id, element_id to create a text element
+                Self::create_new_element(&mut self.db, layer_id, when, element_id, VectorElementType::Text)?;
+                Self::insert_color(&mut self.db, &style.color)?;
+                self.db.update(vec![
+                    DatabaseUpdate::PopVectorTextElement(style.font_family, style.font_size, TextAlignmentType::from(&style.alignment), position, text),
+                    DatabaseUpdate::Pop,
+                    DatabaseUpdate::Pop
+                ])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Adds an image element to a vector layer
+    ///
+    fn image_vector_layer(&mut self, layer_id: i64, when: Duration, new_element: ImageEdit) -> Result<()> {
+        use self::ImageEdit::*;
+
+        match new_element {
+            CreateImage(element_id, image, transform)                   => {
+                // Need the stack to be image_id, element_id to create an image element
+                Self::create_new_element(&mut self.db, layer_id, when, element_id, VectorElementType::Image)?;
+                self.db.update(vec![
+                    DatabaseUpdate::PushImage(image.data),
+                    DatabaseUpdate::PopVectorImageElement(transform),
+                    DatabaseUpdate::Pop,
+                    DatabaseUpdate::Pop
+                ])?;
+            }
+        }
+
+        Ok(())
+    }
+
     ///
     /// Performs an editing action on a motion
     ///
@@ -425,6 +529,24 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                     // Turn into a motion path
                     self.db.update(vec![DatabaseUpdate::SetMotionPath(motion_id, MotionPathType::Position, time_path.points.len()*3)])?;
                 },
+
+                SetRotation(time_path) => {
+                    self.db.update(time_path.points
+                        .iter()
+                        .flat_map(|control_point| vec![&control_point.point, &control_point.past, &control_point.future])
+                        .map(|&TimePoint(ref x, ref y, ref millis)| DatabaseUpdate::PushTimePoint(*x, *y, *millis)))?;
+
+                    self.db.update(vec![DatabaseUpdate::SetMotionPath(motion_id, MotionPathType::Rotation, time_path.points.len()*3)])?;
+                },
+
+                SetScale(time_path) => {
+                    self.db.update(time_path.points
+                        .iter()
+                        .flat_map(|control_point| vec![&control_point.point, &control_point.past, &control_point.future])
+                        .map(|&TimePoint(ref x, ref y, ref millis)| DatabaseUpdate::PushTimePoint(*x, *y, *millis)))?;
+
+                    self.db.update(vec![DatabaseUpdate::SetMotionPath(motion_id, MotionPathType::Scale, time_path.points.len()*3)])?;
+                },
             }
         }
 
@@ -468,6 +590,28 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                         ])?;
                     },
 
+                    (VectorElementType::Text, ElementEdit::SetControlPoints(points)) => {
+                        // Text has a single control point at the start of its baseline
+                        if let Some((x, y)) = points.into_iter().next() {
+                            self.db.update(vec![
+                                DatabaseUpdate::PushElementIdForAssignedId(assigned_id),
+                                DatabaseUpdate::UpdateTextPosition(x, y)
+                            ])?;
+                        }
+                    },
+
+                    (VectorElementType::Image, ElementEdit::SetControlPoints(points)) => {
+                        // The control points are the corners of the image, which is always a parallelogram
+                        if points.len() == 4 {
+                            let transform = ImageElement::transform_from_axes(points[0], points[1], points[3]);
+
+                            self.db.update(vec![
+                                DatabaseUpdate::PushElementIdForAssignedId(assigned_id),
+                                DatabaseUpdate::UpdateImageTransform(transform)
+                            ])?;
+                        }
+                    },
+
                     (VectorElementType::Path, ElementEdit::SetPath(components)) => {
                         // Count the number of points in this path before the update
                         let element_id          = self.db.query_vector_element_id(&ElementId::Assigned(assigned_id))?
@@ -532,6 +676,8 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                             DatabaseUpdate::PushElementIdForAssignedId(assigned_id),
                             DatabaseUpdate::PopDeleteVectorElement
                         ])?;
+
+                        self.forget_brush_element(ElementId::Assigned(assigned_id));
                     },
 
                     (_any_type, ElementEdit::DetachFromFrame) => {
@@ -541,6 +687,16 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                         ])?;
                     },
 
+                    (VectorElementType::Group, ElementEdit::Ungroup) => {
+                        // Only groups that are in a frame can be ungrouped
+                        if Self::is_in_frame(&mut self.db, element_id)? {
+                            self.db.update(vec![
+                                DatabaseUpdate::PushElementIdForAssignedId(assigned_id),
+                                DatabaseUpdate::PopUngroupVectorElement
+                            ])?;
+                        }
+                    },
+
                     // Other types have no action
                     _ => ()
                 }
@@ -552,6 +708,46 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
         Ok(())
     }
 
+    ///
+    /// Returns true if the element with the specified ID is part of a keyframe
+    ///
+    fn is_in_frame(db: &mut TFile, element_id: ElementId) -> Result<bool> {
+        if let Some(element_id) = db.query_vector_element_id(&element_id)? {
+            Ok(db.query_vector_element(element_id)?.when.is_some())
+        } else {
+            Ok(false)
+        }
+    }
+
+    ///
+    /// Combines a set of elements into a new group element
+    ///
+    fn group_elements(&mut self, element_ids: Vec<ElementId>, group_id: ElementId, group_type: GroupType) -> Result<()> {
+        // Only elements that are currently in a frame can be added to the group
+        let mut member_ids = vec![];
+        for element_id in element_ids {
+            if let ElementId::Assigned(assigned_id) = element_id {
+                if !member_ids.contains(&assigned_id) && Self::is_in_frame(&mut self.db, element_id)? {
+                    member_ids.push(assigned_id);
+                }
+            }
+        }
+
+        if member_ids.len() == 0 {
+            return Ok(());
+        }
+
+        // Create the group element, then move the elements into it
+        let num_members = member_ids.len();
+
+        Self::create_unattached_element(&mut self.db, VectorElementType::Group, group_id)?;
+        self.db.update(member_ids.into_iter()
+            .map(|assigned_id| DatabaseUpdate::PushElementIdForAssignedId(assigned_id))
+            .chain(vec![DatabaseUpdate::PopVectorGroupElement(group_type, num_members)]))?;
+
+        Ok(())
+    }
+
     ///
     /// Sends an editing operation to many elements at once
     ///
@@ -564,6 +760,35 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
         Ok(())
     }
 
+    ///
+    /// Finds the real ID of the group layer with the specified assigned ID, or None if there's no group with that ID that the
+    /// layer with the specified real ID can be moved into
+    ///
+    fn group_layer_for_assigned_id(&mut self, layer_id: i64, assigned_group_id: u64) -> Result<Option<i64>> {
+        let group_layer_id = match self.db.query_layer_id_for_assigned_id(assigned_group_id) {
+            Ok((group_layer_id, _name))                     => group_layer_id,
+            Err(SqliteAnimationError::QueryReturnedNoRows)  => { return Ok(None); },
+            Err(other)                                      => { return Err(other); }
+        };
+
+        // Layers can only be moved into group layers
+        if self.db.query_layer_type(group_layer_id)? != LayerType::Group {
+            return Ok(None);
+        }
+
+        // A group can't be moved into itself or into any of the groups it contains
+        let mut ancestor_id = Some(group_layer_id);
+        while let Some(group_id) = ancestor_id {
+            if group_id == layer_id {
+                return Ok(None);
+            }
+
+            ancestor_id = self.db.query_parent_layer_group(group_id)?.map(|(parent_id, _assigned_id)| parent_id);
+        }
+
+        Ok(Some(group_layer_id))
+    }
+
     ///
     /// Performs a layer edit to a vector layer
     ///
@@ -572,6 +797,17 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
 
         // Note that we can't access the core at this point (the database implies that the core is already in use)
 
+        // Group layers have no content of their own, so edits that change the keyframes or elements of a layer can't be applied to them
+        match edit {
+            AddKeyFrame(_) | RemoveKeyFrame(_) | Paint(_, _) | Path(_, _) | Text(_, _) | Image(_, _) => {
+                if let Ok(LayerType::Group) = self.db.query_layer_type(layer_id) {
+                    return Err(SqliteAnimationError::UnexpectedLayerType(layer_id));
+                }
+            },
+
+            _ => { }
+        }
+
         match edit {
             AddKeyFrame(when) => {
                 self.db.update(vec![
@@ -613,6 +849,22 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 ])?;
             }
 
+            Text(when, edit) => {
+                self.text_vector_layer(layer_id, when, edit)?;
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopDeleteLayerCache(when, CacheType::OnionSkinLayer)
+                ])?;
+            }
+
+            Image(when, edit) => {
+                self.image_vector_layer(layer_id, when, edit)?;
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopDeleteLayerCache(when, CacheType::OnionSkinLayer)
+                ])?;
+            }
+
             SetName(new_name) => {
                 self.db.update(vec![
                     DatabaseUpdate::PushLayerId(layer_id),
@@ -621,7 +873,55 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             },
 
             SetOrdering(at_index) => {
-                unimplemented!("Layer ordering not implemented yet")
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopLayerOrdering(at_index)
+                ])?;
+            }
+
+            SetVisible(visible) => {
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopLayerVisible(visible)
+                ])?;
+            },
+
+            SetLocked(locked) => {
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopLayerLocked(locked)
+                ])?;
+            },
+
+            SetOpacity(opacity) => {
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopLayerOpacity(opacity.max(0.0).min(1.0))
+                ])?;
+            },
+
+            SetBlendMode(blend_mode) => {
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopLayerBlendMode(LayerBlendType::from(&blend_mode))
+                ])?;
+            },
+
+            SetParentGroup(None) => {
+                self.db.update(vec![
+                    DatabaseUpdate::PushLayerId(layer_id),
+                    DatabaseUpdate::PopLayerParentGroup(None)
+                ])?;
+            },
+
+            SetParentGroup(Some(assigned_group_id)) => {
+                // Edits that would move the layer into something that's not a group or create a loop of groups are ignored
+                if let Some(group_layer_id) = self.group_layer_for_assigned_id(layer_id, assigned_group_id)? {
+                    self.db.update(vec![
+                        DatabaseUpdate::PushLayerId(layer_id),
+                        DatabaseUpdate::PopLayerParentGroup(Some(group_layer_id))
+                    ])?;
+                }
             }
         }
 
@@ -642,8 +942,22 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                     ])?;
                 },
 
+                SetDuration(duration) => {
+                    self.db.update(vec![
+                        DatabaseUpdate::UpdateDuration(duration)
+                    ])?;
+                },
+
+                SetFrameLength(frame_length) => {
+                    // Frames can't have a zero length (frame numbers are found by dividing by the frame length)
+                    self.db.update(vec![
+                        DatabaseUpdate::UpdateFrameLength(frame_length.max(Duration::from_nanos(1)))
+                    ])?;
+                },
+
                 AddNewLayer(new_layer_id) => {
                     // Create a layer with the new ID
+                    self.layer_id_for_assigned_id.remove(&new_layer_id);
                     self.db.update(vec![
                         DatabaseUpdate::PushLayerType(LayerType::Vector),
                         DatabaseUpdate::PushAssignLayer(new_layer_id),
@@ -651,8 +965,19 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                     ])?;
                 },
 
+                AddNewGroupLayer(new_layer_id) => {
+                    // Create a group layer with the new ID
+                    self.layer_id_for_assigned_id.remove(&new_layer_id);
+                    self.db.update(vec![
+                        DatabaseUpdate::PushLayerType(LayerType::Group),
+                        DatabaseUpdate::PushAssignLayer(new_layer_id),
+                        DatabaseUpdate::Pop
+                    ])?;
+                },
+
                 RemoveLayer(old_layer_id) => {
-                    // Delete this layer
+                    // Delete this layer (a layer added later on with the same ID will have a different real ID)
+                    self.layer_id_for_assigned_id.remove(&old_layer_id);
                     self.db.update(vec![
                         DatabaseUpdate::PushLayerForAssignedId(old_layer_id),
                         DatabaseUpdate::PopDeleteLayer
@@ -674,6 +999,10 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                     self.edit_vector_layer(layer_id, layer_edit)?;
                 },
 
+                Element(element_ids, ElementEdit::Group(group_id, group_type)) => {
+                    self.group_elements(element_ids, group_id, group_type)?;
+                },
+
                 Element(element_ids, element_edit) => {
                     self.edit_many_elements(element_ids, element_edit)?;
                 },
@@ -20,6 +20,24 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 db.update(vec![
                     PushBrushType(BrushDefinitionType::from(brush_definition)),
                     PushInkBrush(ink_defn.min_width, ink_defn.max_width, ink_defn.scale_up_distance)
+                ])?;
+
+                // Brushes with no pressure settings use the device pressure with a linear curve, so we only need to store the settings for other brushes
+                if ink_defn.pressure_curve != PressureCurve::Linear || ink_defn.pressure_source != PressureSource::Device {
+                    let (cp1, cp2) = ink_defn.pressure_curve.control_points();
+
+                    db.update(vec![
+                        PushInkPressure(PressureSourceType::from(&ink_defn.pressure_source), PressureCurveType::from(&ink_defn.pressure_curve), cp1, cp2)
+                    ])?;
+                }
+
+                Ok(())
+            },
+
+            &BrushDefinition::Calligraphy(ref calligraphy_defn) => {
+                db.update(vec![
+                    PushBrushType(BrushDefinitionType::from(brush_definition)),
+                    PushCalligraphyBrush(calligraphy_defn.min_width, calligraphy_defn.max_width, calligraphy_defn.nib_angle, calligraphy_defn.nib_aspect_ratio, calligraphy_defn.tilt_influence)
                 ])
             }
         }
@@ -37,6 +55,19 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             PushBrushProperties(brush_properties.size, brush_properties.opacity)
         ])?;
 
+        if let Some(gradient) = brush_properties.gradient {
+            let angle = match gradient {
+                BrushGradient::Linear(_, angle) => angle,
+                BrushGradient::Radial(_)        => 0.0
+            };
+
+            Self::insert_color(db, &gradient.end_color())?;
+
+            db.update(vec![
+                PushBrushGradient(BrushGradientType::from(&gradient), angle)
+            ])?;
+        }
+
         Ok(())
     }
 
@@ -56,8 +87,36 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 let max_width           = max_width as f32;
                 let scale_up_distance   = scale_up_distance as f32;
 
+                let (pressure_source, pressure_curve) = match brush_entry.ink_pressure {
+                    Some((pressure_source, curve_type, (cp1_x, cp1_y), (cp2_x, cp2_y))) => {
+                        let pressure_curve = match curve_type {
+                            PressureCurveType::Linear   => PressureCurve::Linear,
+                            PressureCurveType::Soft     => PressureCurve::Soft,
+                            PressureCurveType::Firm     => PressureCurve::Firm,
+                            PressureCurveType::Bezier   => PressureCurve::Bezier((cp1_x as f32, cp1_y as f32), (cp2_x as f32, cp2_y as f32))
+                        };
+
+                        (pressure_source.into(), pressure_curve)
+                    },
+
+                    None => (PressureSource::Device, PressureCurve::Linear)
+                };
+
                 Ok(BrushDefinition::Ink(InkDefinition {
-                    min_width, max_width, scale_up_distance
+                    min_width, max_width, scale_up_distance, pressure_curve, pressure_source
+                }))
+            },
+
+            Calligraphy => {
+                let (min_width, max_width, nib_angle, nib_aspect_ratio, tilt_influence) = brush_entry.calligraphy_defn.unwrap_or((0.0, 0.0, 0.0, 0.0, 0.0));
+                let min_width           = min_width as f32;
+                let max_width           = max_width as f32;
+                let nib_angle           = nib_angle as f32;
+                let nib_aspect_ratio    = nib_aspect_ratio as f32;
+                let tilt_influence      = tilt_influence as f32;
+
+                Ok(BrushDefinition::Calligraphy(CalligraphyDefinition {
+                    min_width, max_width, nib_angle, nib_aspect_ratio, tilt_influence
                 }))
             }
         }
@@ -69,11 +128,17 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
     pub fn get_brush_properties(db: &mut TFile, brush_properties_id: i64) -> Result<BrushProperties> {
         let brush_properties_entry  = db.query_brush_properties(brush_properties_id)?;
         let color                   = Self::get_color(db, brush_properties_entry.color_id)?;
+        let gradient                = match brush_properties_entry.gradient {
+            Some((BrushGradientType::Linear, end_color_id, angle))  => Some(BrushGradient::Linear(Self::get_color(db, end_color_id)?, angle as f32)),
+            Some((BrushGradientType::Radial, end_color_id, _))      => Some(BrushGradient::Radial(Self::get_color(db, end_color_id)?)),
+            None                                                    => None
+        };
 
         Ok(BrushProperties {
             size:       brush_properties_entry.size as f32,
             opacity:    brush_properties_entry.opacity as f32,
-            color:      color
+            color:      color,
+            gradient:   gradient
         })
     }
 }
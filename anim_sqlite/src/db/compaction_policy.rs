@@ -0,0 +1,29 @@
+///
+/// Describes how much of the edit log is kept when an animation is compacted
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CompactionPolicy {
+    /// Keep every edit in the edit log (compacting will only remove data that's no longer in use)
+    KeepAllEdits,
+
+    /// Keep only the specified number of the most recent edits
+    KeepLastEdits(usize)
+}
+
+impl CompactionPolicy {
+    ///
+    /// Returns the number of edits this policy keeps in the edit log, or None if the whole edit log is kept
+    ///
+    pub fn edits_to_keep(&self) -> Option<usize> {
+        match self {
+            CompactionPolicy::KeepAllEdits              => None,
+            CompactionPolicy::KeepLastEdits(num_edits)  => Some(*num_edits)
+        }
+    }
+}
+
+impl Default for CompactionPolicy {
+    fn default() -> CompactionPolicy {
+        CompactionPolicy::KeepAllEdits
+    }
+}
@@ -1,6 +1,7 @@
 use super::motion_path_type::*;
 
 use flo_animation::*;
+use flo_canvas::{BlendMode, TextAlignment};
 
 /// Provides the enum type and name for a database enum value
 pub struct DbEnumName(pub &'static str, pub &'static str);
@@ -11,27 +12,41 @@ pub struct DbEnumName(pub &'static str, pub &'static str);
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum EditLogType {
     SetSize,
+    SetDuration,
+    SetFrameLength,
     AddNewLayer,
+    AddNewGroupLayer,
     RemoveLayer,
 
     LayerAddKeyFrame,
     LayerRemoveKeyFrame,
     LayerSetName,
+    LayerSetVisible,
+    LayerSetLocked,
+    LayerSetOpacity,
+    LayerSetBlendMode,
+    LayerSetParentGroup,
 
     LayerPaintSelectBrush,
     LayerPaintBrushProperties,
     LayerPaintBrushStroke,
+    LayerPaintCreateBrushStroke,
 
     LayerPathCreatePath,
     LayerPathSelectBrush,
     LayerPathBrushProperties,
     LayerSetOrdering,
 
+    LayerTextCreateText,
+    LayerImageCreateImage,
+
     MotionCreate,
     MotionDelete,
     MotionSetType,
     MotionSetOrigin,
     MotionSetPath,
+    MotionSetRotation,
+    MotionSetScale,
 
     ElementAddAttachment,
     ElementRemoveAttachment,
@@ -43,7 +58,9 @@ pub enum EditLogType {
     ElementOrderToBottom,
     ElementOrderBefore,
     ElementDelete,
-    ElementDetachFromFrame
+    ElementDetachFromFrame,
+    ElementGroup,
+    ElementUngroup
 }
 
 ///
@@ -61,7 +78,8 @@ pub enum DrawingStyleType {
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum BrushDefinitionType {
     Simple,
-    Ink
+    Ink,
+    Calligraphy
 }
 
 ///
@@ -78,7 +96,66 @@ pub enum ColorType {
 ///
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum LayerType {
-    Vector
+    Vector,
+    Group
+}
+
+///
+/// Blend modes that can be applied to a layer
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum LayerBlendType {
+    SourceOver,
+    SourceIn,
+    SourceOut,
+    DestinationOver,
+    DestinationIn,
+    DestinationOut,
+    SourceAtop,
+    DestinationAtop,
+    Multiply,
+    Screen,
+    Darken,
+    Lighten
+}
+
+///
+/// How the text in a text element is aligned
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TextAlignmentType {
+    Left,
+    Center,
+    Right
+}
+
+///
+/// The kinds of gradient that brush properties can fill with
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum BrushGradientType {
+    Linear,
+    Radial
+}
+
+///
+/// The pressure curves that an ink brush can use
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PressureCurveType {
+    Linear,
+    Soft,
+    Firm,
+    Bezier
+}
+
+///
+/// Where an ink brush can get its pressure from
+///
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PressureSourceType {
+    Device,
+    Velocity
 }
 
 ///
@@ -90,7 +167,10 @@ pub enum VectorElementType {
     BrushProperties,
     BrushStroke,
     Path,
-    Motion
+    Motion,
+    Group,
+    Text,
+    Image
 }
 
 impl Into<VectorType> for VectorElementType {
@@ -100,7 +180,10 @@ impl Into<VectorType> for VectorElementType {
             VectorElementType::BrushProperties  => VectorType::BrushProperties,
             VectorElementType::BrushStroke      => VectorType::BrushStroke,
             VectorElementType::Path             => VectorType::Path,
-            VectorElementType::Motion           => VectorType::Motion
+            VectorElementType::Motion           => VectorType::Motion,
+            VectorElementType::Group            => VectorType::Group,
+            VectorElementType::Text             => VectorType::Text,
+            VectorElementType::Image            => VectorType::Image
         }
     }
 }
@@ -127,11 +210,17 @@ pub enum DbEnum {
     BrushDefinition(BrushDefinitionType),
     Color(ColorType),
     Layer(LayerType),
+    LayerBlend(LayerBlendType),
     MotionType(MotionType),
     MotionPathType(MotionPathType),
     VectorElement(VectorElementType),
     PathPoint(PathPointType),
-    CacheType(CacheType)
+    CacheType(CacheType),
+    GroupType(GroupType),
+    TextAlignment(TextAlignmentType),
+    BrushGradient(BrushGradientType),
+    PressureCurve(PressureCurveType),
+    PressureSource(PressureSourceType)
 }
 
 impl DbEnum {
@@ -175,6 +264,14 @@ impl DbEnum {
         }
     }
 
+    /// Returns the LayerBlend value for this enum (if there is one)
+    pub fn layer_blend(self) -> Option<LayerBlendType> {
+        match self {
+            DbEnum::LayerBlend(res) => Some(res),
+            _                       => None
+        }
+    }
+
     /// Returns the VectorElement value for this enum (if there is one)
     pub fn vector_element(self) -> Option<VectorElementType> {
         match self {
@@ -197,6 +294,46 @@ impl DbEnum {
             _                       => None
         }
     }
+
+    /// Returns the GroupType value for this enum (if there is one)
+    pub fn group_type(self) -> Option<GroupType> {
+        match self {
+            DbEnum::GroupType(res)  => Some(res),
+            _                       => None
+        }
+    }
+
+    /// Returns the TextAlignment value for this enum (if there is one)
+    pub fn text_alignment(self) -> Option<TextAlignmentType> {
+        match self {
+            DbEnum::TextAlignment(res)  => Some(res),
+            _                           => None
+        }
+    }
+
+    /// Returns the BrushGradient value for this enum (if there is one)
+    pub fn brush_gradient(self) -> Option<BrushGradientType> {
+        match self {
+            DbEnum::BrushGradient(res)  => Some(res),
+            _                           => None
+        }
+    }
+
+    /// Returns the PressureCurve value for this enum (if there is one)
+    pub fn pressure_curve(self) -> Option<PressureCurveType> {
+        match self {
+            DbEnum::PressureCurve(res)  => Some(res),
+            _                           => None
+        }
+    }
+
+    /// Returns the PressureSource value for this enum (if there is one)
+    pub fn pressure_source(self) -> Option<PressureSourceType> {
+        match self {
+            DbEnum::PressureSource(res) => Some(res),
+            _                           => None
+        }
+    }
 }
 
 ///
@@ -209,10 +346,16 @@ pub enum DbEnumType {
     BrushDefinition,
     Color,
     Layer,
+    LayerBlend,
     VectorElement,
     MotionType,
     PathPoint,
-    CacheType
+    CacheType,
+    GroupType,
+    TextAlignment,
+    BrushGradient,
+    PressureCurve,
+    PressureSource
 }
 
 impl From<DbEnumType> for Vec<DbEnum> {
@@ -224,27 +367,41 @@ impl From<DbEnumType> for Vec<DbEnum> {
                 use self::EditLogType::*;
                 vec![
                     DbEnum::EditLog(SetSize),
+                    DbEnum::EditLog(SetDuration),
+                    DbEnum::EditLog(SetFrameLength),
                     DbEnum::EditLog(AddNewLayer),
+                    DbEnum::EditLog(AddNewGroupLayer),
                     DbEnum::EditLog(RemoveLayer),
 
                     DbEnum::EditLog(LayerAddKeyFrame),
                     DbEnum::EditLog(LayerRemoveKeyFrame),
                     DbEnum::EditLog(LayerSetName),
                     DbEnum::EditLog(LayerSetOrdering),
+                    DbEnum::EditLog(LayerSetVisible),
+                    DbEnum::EditLog(LayerSetLocked),
+                    DbEnum::EditLog(LayerSetOpacity),
+                    DbEnum::EditLog(LayerSetBlendMode),
+                    DbEnum::EditLog(LayerSetParentGroup),
 
                     DbEnum::EditLog(LayerPaintSelectBrush),
                     DbEnum::EditLog(LayerPaintBrushProperties),
                     DbEnum::EditLog(LayerPaintBrushStroke),
+                    DbEnum::EditLog(LayerPaintCreateBrushStroke),
 
                     DbEnum::EditLog(LayerPathCreatePath),
                     DbEnum::EditLog(LayerPathSelectBrush),
                     DbEnum::EditLog(LayerPathBrushProperties),
 
+                    DbEnum::EditLog(LayerTextCreateText),
+                    DbEnum::EditLog(LayerImageCreateImage),
+
                     DbEnum::EditLog(MotionCreate),
                     DbEnum::EditLog(MotionDelete),
                     DbEnum::EditLog(MotionSetType),
                     DbEnum::EditLog(MotionSetOrigin),
                     DbEnum::EditLog(MotionSetPath),
+                    DbEnum::EditLog(MotionSetRotation),
+                    DbEnum::EditLog(MotionSetScale),
 
                     DbEnum::EditLog(ElementAddAttachment),
                     DbEnum::EditLog(ElementRemoveAttachment),
@@ -256,7 +413,9 @@ impl From<DbEnumType> for Vec<DbEnum> {
                     DbEnum::EditLog(ElementOrderToBottom),
                     DbEnum::EditLog(ElementOrderBefore),
                     DbEnum::EditLog(ElementDelete),
-                    DbEnum::EditLog(ElementDetachFromFrame)
+                    DbEnum::EditLog(ElementDetachFromFrame),
+                    DbEnum::EditLog(ElementGroup),
+                    DbEnum::EditLog(ElementUngroup)
                 ]
             },
 
@@ -272,7 +431,8 @@ impl From<DbEnumType> for Vec<DbEnum> {
                 use self::BrushDefinitionType::*;
                 vec![
                     DbEnum::BrushDefinition(Simple),
-                    DbEnum::BrushDefinition(Ink)
+                    DbEnum::BrushDefinition(Ink),
+                    DbEnum::BrushDefinition(Calligraphy)
                 ]
             },
 
@@ -287,7 +447,26 @@ impl From<DbEnumType> for Vec<DbEnum> {
             Layer => {
                 use self::LayerType::*;
                 vec![
-                    DbEnum::Layer(Vector)
+                    DbEnum::Layer(Vector),
+                    DbEnum::Layer(Group)
+                ]
+            },
+
+            LayerBlend => {
+                use self::LayerBlendType::*;
+                vec![
+                    DbEnum::LayerBlend(SourceOver),
+                    DbEnum::LayerBlend(SourceIn),
+                    DbEnum::LayerBlend(SourceOut),
+                    DbEnum::LayerBlend(DestinationOver),
+                    DbEnum::LayerBlend(DestinationIn),
+                    DbEnum::LayerBlend(DestinationOut),
+                    DbEnum::LayerBlend(SourceAtop),
+                    DbEnum::LayerBlend(DestinationAtop),
+                    DbEnum::LayerBlend(Multiply),
+                    DbEnum::LayerBlend(Screen),
+                    DbEnum::LayerBlend(Darken),
+                    DbEnum::LayerBlend(Lighten)
                 ]
             },
 
@@ -298,7 +477,10 @@ impl From<DbEnumType> for Vec<DbEnum> {
                     DbEnum::VectorElement(BrushProperties),
                     DbEnum::VectorElement(BrushStroke),
                     DbEnum::VectorElement(Path),
-                    DbEnum::VectorElement(Motion)
+                    DbEnum::VectorElement(Motion),
+                    DbEnum::VectorElement(Group),
+                    DbEnum::VectorElement(Text),
+                    DbEnum::VectorElement(Image)
                 ]
             },
 
@@ -307,7 +489,10 @@ impl From<DbEnumType> for Vec<DbEnum> {
 
                 vec![
                     DbEnum::MotionType(None),
-                    DbEnum::MotionType(Translate)
+                    DbEnum::MotionType(Translate),
+                    DbEnum::MotionType(Rotate),
+                    DbEnum::MotionType(Scale),
+                    DbEnum::MotionType(Transform)
                 ]
             },
 
@@ -329,6 +514,57 @@ impl From<DbEnumType> for Vec<DbEnum> {
                 vec![
                     DbEnum::CacheType(OnionSkinLayer)
                 ]
+            },
+
+            GroupType => {
+                use self::GroupType::*;
+
+                vec![
+                    DbEnum::GroupType(Normal),
+                    DbEnum::GroupType(Added),
+                    DbEnum::GroupType(Subtracted),
+                    DbEnum::GroupType(Masked),
+                    DbEnum::GroupType(InvertedMask)
+                ]
+            },
+
+            TextAlignment => {
+                use self::TextAlignmentType::*;
+
+                vec![
+                    DbEnum::TextAlignment(Left),
+                    DbEnum::TextAlignment(Center),
+                    DbEnum::TextAlignment(Right)
+                ]
+            },
+
+            BrushGradient => {
+                use self::BrushGradientType::*;
+
+                vec![
+                    DbEnum::BrushGradient(Linear),
+                    DbEnum::BrushGradient(Radial)
+                ]
+            },
+
+            PressureCurve => {
+                use self::PressureCurveType::*;
+
+                vec![
+                    DbEnum::PressureCurve(Linear),
+                    DbEnum::PressureCurve(Soft),
+                    DbEnum::PressureCurve(Firm),
+                    DbEnum::PressureCurve(Bezier)
+                ]
+            },
+
+            PressureSource => {
+                use self::PressureSourceType::*;
+
+                vec![
+                    DbEnum::PressureSource(Device),
+                    DbEnum::PressureSource(Velocity)
+                ]
             }
         }
     }
@@ -346,7 +582,10 @@ impl<'a> From<&'a AnimationEdit> for EditLogType {
 
         match t {
             SetSize(_, _)                                       => EditLogType::SetSize,
+            SetDuration(_)                                      => EditLogType::SetDuration,
+            SetFrameLength(_)                                   => EditLogType::SetFrameLength,
             AddNewLayer(_)                                      => EditLogType::AddNewLayer,
+            AddNewGroupLayer(_)                                 => EditLogType::AddNewGroupLayer,
             RemoveLayer(_)                                      => EditLogType::RemoveLayer,
 
             Layer(_, AddKeyFrame(_))                            => EditLogType::LayerAddKeyFrame,
@@ -355,17 +594,28 @@ impl<'a> From<&'a AnimationEdit> for EditLogType {
             Layer(_, Paint(_, SelectBrush(_, _, _)))            => EditLogType::LayerPaintSelectBrush,
             Layer(_, Paint(_, BrushProperties(_, _)))           => EditLogType::LayerPaintBrushProperties,
             Layer(_, Paint(_, BrushStroke(_,_)))                => EditLogType::LayerPaintBrushStroke,
+            Layer(_, Paint(_, CreateBrushStroke(_,_)))          => EditLogType::LayerPaintCreateBrushStroke,
             Layer(_, SetOrdering(_))                            => EditLogType::LayerSetOrdering,
+            Layer(_, SetVisible(_))                             => EditLogType::LayerSetVisible,
+            Layer(_, SetLocked(_))                              => EditLogType::LayerSetLocked,
+            Layer(_, SetOpacity(_))                             => EditLogType::LayerSetOpacity,
+            Layer(_, SetBlendMode(_))                           => EditLogType::LayerSetBlendMode,
+            Layer(_, SetParentGroup(_))                         => EditLogType::LayerSetParentGroup,
 
             Layer(_, Path(_, CreatePath(_, _)))                 => EditLogType::LayerPathCreatePath,
             Layer(_, Path(_, PathEdit::SelectBrush(_, _, _)))   => EditLogType::LayerPathSelectBrush,
             Layer(_, Path(_, PathEdit::BrushProperties(_, _)))  => EditLogType::LayerPathBrushProperties,
 
+            Layer(_, Text(_, TextEdit::CreateText(_, _, _, _))) => EditLogType::LayerTextCreateText,
+            Layer(_, Image(_, ImageEdit::CreateImage(_, _, _))) => EditLogType::LayerImageCreateImage,
+
             Motion(_, Create)                                   => EditLogType::MotionCreate,
             Motion(_, MotionEdit::Delete)                       => EditLogType::MotionDelete,
             Motion(_, SetType(_))                               => EditLogType::MotionSetType,
             Motion(_, SetOrigin(_, _))                          => EditLogType::MotionSetOrigin,
             Motion(_, MotionEdit::SetPath(_))                   => EditLogType::MotionSetPath,
+            Motion(_, SetRotation(_))                           => EditLogType::MotionSetRotation,
+            Motion(_, SetScale(_))                              => EditLogType::MotionSetScale,
 
             Element(_, AddAttachment(_))                        => EditLogType::ElementAddAttachment,
             Element(_, RemoveAttachment(_))                     => EditLogType::ElementRemoveAttachment,
@@ -377,7 +627,9 @@ impl<'a> From<&'a AnimationEdit> for EditLogType {
             Element(_, Order(ToBottom))                         => EditLogType::ElementOrderToBottom,
             Element(_, Order(Before(_)))                        => EditLogType::ElementOrderBefore,
             Element(_, ElementEdit::Delete)                     => EditLogType::ElementDelete,
-            Element(_, DetachFromFrame)                         => EditLogType::ElementDetachFromFrame
+            Element(_, DetachFromFrame)                         => EditLogType::ElementDetachFromFrame,
+            Element(_, Group(_, _))                             => EditLogType::ElementGroup,
+            Element(_, Ungroup)                                 => EditLogType::ElementUngroup
         }
     }
 }
@@ -402,14 +654,113 @@ impl Into<BrushDrawingStyle> for DrawingStyleType {
     }
 }
 
+impl<'a> From<&'a BlendMode> for LayerBlendType {
+    fn from(t: &BlendMode) -> LayerBlendType {
+        use self::BlendMode::*;
+
+        match t {
+            &SourceOver         => LayerBlendType::SourceOver,
+            &SourceIn           => LayerBlendType::SourceIn,
+            &SourceOut          => LayerBlendType::SourceOut,
+            &DestinationOver    => LayerBlendType::DestinationOver,
+            &DestinationIn      => LayerBlendType::DestinationIn,
+            &DestinationOut     => LayerBlendType::DestinationOut,
+            &SourceAtop         => LayerBlendType::SourceAtop,
+            &DestinationAtop    => LayerBlendType::DestinationAtop,
+            &Multiply           => LayerBlendType::Multiply,
+            &Screen             => LayerBlendType::Screen,
+            &Darken             => LayerBlendType::Darken,
+            &Lighten            => LayerBlendType::Lighten
+        }
+    }
+}
+
+impl Into<BlendMode> for LayerBlendType {
+    fn into(self) -> BlendMode {
+        match self {
+            LayerBlendType::SourceOver          => BlendMode::SourceOver,
+            LayerBlendType::SourceIn            => BlendMode::SourceIn,
+            LayerBlendType::SourceOut           => BlendMode::SourceOut,
+            LayerBlendType::DestinationOver     => BlendMode::DestinationOver,
+            LayerBlendType::DestinationIn       => BlendMode::DestinationIn,
+            LayerBlendType::DestinationOut      => BlendMode::DestinationOut,
+            LayerBlendType::SourceAtop          => BlendMode::SourceAtop,
+            LayerBlendType::DestinationAtop     => BlendMode::DestinationAtop,
+            LayerBlendType::Multiply            => BlendMode::Multiply,
+            LayerBlendType::Screen              => BlendMode::Screen,
+            LayerBlendType::Darken              => BlendMode::Darken,
+            LayerBlendType::Lighten             => BlendMode::Lighten
+        }
+    }
+}
+
+impl<'a> From<&'a TextAlignment> for TextAlignmentType {
+    fn from(t: &TextAlignment) -> TextAlignmentType {
+        match t {
+            TextAlignment::Left     => TextAlignmentType::Left,
+            TextAlignment::Center   => TextAlignmentType::Center,
+            TextAlignment::Right    => TextAlignmentType::Right
+        }
+    }
+}
+
+impl<'a> From<&'a BrushGradient> for BrushGradientType {
+    fn from(t: &BrushGradient) -> BrushGradientType {
+        match t {
+            BrushGradient::Linear(_, _) => BrushGradientType::Linear,
+            BrushGradient::Radial(_)    => BrushGradientType::Radial
+        }
+    }
+}
+
+impl<'a> From<&'a PressureCurve> for PressureCurveType {
+    fn from(t: &PressureCurve) -> PressureCurveType {
+        match t {
+            PressureCurve::Linear       => PressureCurveType::Linear,
+            PressureCurve::Soft         => PressureCurveType::Soft,
+            PressureCurve::Firm         => PressureCurveType::Firm,
+            PressureCurve::Bezier(_, _) => PressureCurveType::Bezier
+        }
+    }
+}
+
+impl<'a> From<&'a PressureSource> for PressureSourceType {
+    fn from(t: &PressureSource) -> PressureSourceType {
+        match t {
+            PressureSource::Device      => PressureSourceType::Device,
+            PressureSource::Velocity    => PressureSourceType::Velocity
+        }
+    }
+}
+
+impl Into<PressureSource> for PressureSourceType {
+    fn into(self) -> PressureSource {
+        match self {
+            PressureSourceType::Device      => PressureSource::Device,
+            PressureSourceType::Velocity    => PressureSource::Velocity
+        }
+    }
+}
+
+impl Into<TextAlignment> for TextAlignmentType {
+    fn into(self) -> TextAlignment {
+        match self {
+            TextAlignmentType::Left     => TextAlignment::Left,
+            TextAlignmentType::Center   => TextAlignment::Center,
+            TextAlignmentType::Right    => TextAlignment::Right
+        }
+    }
+}
+
 impl<'a> From<&'a PaintEdit> for VectorElementType {
     fn from(t: &PaintEdit) -> VectorElementType {
         use self::PaintEdit::*;
 
         match t {
-            SelectBrush(_, _, _)    => VectorElementType::BrushDefinition,
-            BrushProperties(_, _)   => VectorElementType::BrushProperties,
-            BrushStroke(_, _)       => VectorElementType::BrushStroke
+            SelectBrush(_, _, _)        => VectorElementType::BrushDefinition,
+            BrushProperties(_, _)       => VectorElementType::BrushProperties,
+            BrushStroke(_, _)           => VectorElementType::BrushStroke,
+            CreateBrushStroke(_, _)     => VectorElementType::BrushStroke
         }
     }
 }
@@ -419,8 +770,9 @@ impl<'a> From<&'a BrushDefinition> for BrushDefinitionType {
         use self::BrushDefinition::*;
 
         match t {
-            &Simple     => BrushDefinitionType::Simple,
-            &Ink(_)     => BrushDefinitionType::Ink
+            &Simple             => BrushDefinitionType::Simple,
+            &Ink(_)             => BrushDefinitionType::Ink,
+            &Calligraphy(_)     => BrushDefinitionType::Calligraphy
         }
     }
 }
@@ -432,27 +784,41 @@ impl From<EditLogType> for DbEnumName {
 
         match t {
             SetSize                     => DbEnumName("Edit", "SetSize"),
+            SetDuration                 => DbEnumName("Edit", "SetDuration"),
+            SetFrameLength              => DbEnumName("Edit", "SetFrameLength"),
             AddNewLayer                 => DbEnumName("Edit", "AddNewLayer"),
+            AddNewGroupLayer            => DbEnumName("Edit", "AddNewGroupLayer"),
             RemoveLayer                 => DbEnumName("Edit", "RemoveLayer"),
 
             LayerAddKeyFrame            => DbEnumName("Edit", "Layer::AddKeyFrame"),
             LayerRemoveKeyFrame         => DbEnumName("Edit", "Layer::RemoveKeyFrame"),
             LayerSetName                => DbEnumName("Edit", "Layer::SetName"),
             LayerSetOrdering            => DbEnumName("Edit", "Layer::SetOrdering"),
+            LayerSetVisible             => DbEnumName("Edit", "Layer::SetVisible"),
+            LayerSetLocked              => DbEnumName("Edit", "Layer::SetLocked"),
+            LayerSetOpacity             => DbEnumName("Edit", "Layer::SetOpacity"),
+            LayerSetBlendMode           => DbEnumName("Edit", "Layer::SetBlendMode"),
+            LayerSetParentGroup         => DbEnumName("Edit", "Layer::SetParentGroup"),
 
             LayerPaintSelectBrush       => DbEnumName("Edit", "Layer::Paint::SelectBrush"),
             LayerPaintBrushProperties   => DbEnumName("Edit", "Layer::Paint::BrushProperties"),
             LayerPaintBrushStroke       => DbEnumName("Edit", "Layer::Paint::BrushStroke"),
+            LayerPaintCreateBrushStroke => DbEnumName("Edit", "Layer::Paint::CreateBrushStroke"),
 
             LayerPathCreatePath         => DbEnumName("Edit", "Layer::Path::CreatePath"),
             LayerPathSelectBrush        => DbEnumName("Edit", "Layer::Path::SelectBrush"),
             LayerPathBrushProperties    => DbEnumName("Edit", "Layer::Path::BrushProperties"),
 
+            LayerTextCreateText         => DbEnumName("Edit", "Layer::Text::CreateText"),
+            LayerImageCreateImage       => DbEnumName("Edit", "Layer::Image::CreateImage"),
+
             MotionCreate                => DbEnumName("Edit", "Motion::Create"),
             MotionDelete                => DbEnumName("Edit", "Motion::Delete"),
             MotionSetType               => DbEnumName("Edit", "Motion::SetType"),
             MotionSetOrigin             => DbEnumName("Edit", "Motion::SetOrigin"),
             MotionSetPath               => DbEnumName("Edit", "Motion::SetPath"),
+            MotionSetRotation           => DbEnumName("Edit", "Motion::SetRotation"),
+            MotionSetScale              => DbEnumName("Edit", "Motion::SetScale"),
 
             ElementAddAttachment        => DbEnumName("Edit", "Element::AddAttachment"),
             ElementRemoveAttachment     => DbEnumName("Edit", "Element::RemoveAttachment"),
@@ -464,7 +830,9 @@ impl From<EditLogType> for DbEnumName {
             ElementOrderToBottom        => DbEnumName("Edit", "Element::OrderToBottom"),
             ElementOrderBefore          => DbEnumName("Edit", "Element::OrderBefore"),
             ElementDelete               => DbEnumName("Edit", "Element::Delete"),
-            ElementDetachFromFrame      => DbEnumName("Edit", "Element::DetachFromFrame")
+            ElementDetachFromFrame      => DbEnumName("Edit", "Element::DetachFromFrame"),
+            ElementGroup                => DbEnumName("Edit", "Element::Group"),
+            ElementUngroup              => DbEnumName("Edit", "Element::Ungroup")
         }
     }
 }
@@ -485,8 +853,9 @@ impl From<BrushDefinitionType> for DbEnumName {
         use self::BrushDefinitionType::*;
 
         match t {
-            Simple  => DbEnumName("BrushType", "Simple"),
-            Ink     => DbEnumName("BrushType", "Ink")
+            Simple      => DbEnumName("BrushType", "Simple"),
+            Ink         => DbEnumName("BrushType", "Ink"),
+            Calligraphy => DbEnumName("BrushType", "Calligraphy")
         }
     }
 }
@@ -507,7 +876,29 @@ impl From<LayerType> for DbEnumName {
         use self::LayerType::*;
 
         match t {
-            Vector  => DbEnumName("LayerType", "Vector")
+            Vector  => DbEnumName("LayerType", "Vector"),
+            Group   => DbEnumName("LayerType", "Group")
+        }
+    }
+}
+
+impl From<LayerBlendType> for DbEnumName {
+    fn from(t: LayerBlendType) -> DbEnumName {
+        use self::LayerBlendType::*;
+
+        match t {
+            SourceOver          => DbEnumName("LayerBlendMode", "SourceOver"),
+            SourceIn            => DbEnumName("LayerBlendMode", "SourceIn"),
+            SourceOut           => DbEnumName("LayerBlendMode", "SourceOut"),
+            DestinationOver     => DbEnumName("LayerBlendMode", "DestinationOver"),
+            DestinationIn       => DbEnumName("LayerBlendMode", "DestinationIn"),
+            DestinationOut      => DbEnumName("LayerBlendMode", "DestinationOut"),
+            SourceAtop          => DbEnumName("LayerBlendMode", "SourceAtop"),
+            DestinationAtop     => DbEnumName("LayerBlendMode", "DestinationAtop"),
+            Multiply            => DbEnumName("LayerBlendMode", "Multiply"),
+            Screen              => DbEnumName("LayerBlendMode", "Screen"),
+            Darken              => DbEnumName("LayerBlendMode", "Darken"),
+            Lighten             => DbEnumName("LayerBlendMode", "Lighten")
         }
     }
 }
@@ -521,7 +912,10 @@ impl From<VectorElementType> for DbEnumName {
             BrushProperties     => DbEnumName("VectorElementType", "BrushProperties"),
             BrushStroke         => DbEnumName("VectorElementType", "BrushStroke"),
             Path                => DbEnumName("VectorElementType", "Path"),
-            Motion              => DbEnumName("VectorElementType", "Motion")
+            Motion              => DbEnumName("VectorElementType", "Motion"),
+            Group               => DbEnumName("VectorElementType", "Group"),
+            Text                => DbEnumName("VectorElementType", "Text"),
+            Image               => DbEnumName("VectorElementType", "Image")
         }
     }
 }
@@ -533,7 +927,10 @@ impl From<MotionType> for DbEnumName {
         match t {
             None        => DbEnumName("MotionType", "None"),
             Reverse     => DbEnumName("MotionType", "Reverse"),
-            Translate   => DbEnumName("MotionType", "Translate")
+            Translate   => DbEnumName("MotionType", "Translate"),
+            Rotate      => DbEnumName("MotionType", "Rotate"),
+            Scale       => DbEnumName("MotionType", "Scale"),
+            Transform   => DbEnumName("MotionType", "Transform")
         }
     }
 }
@@ -544,6 +941,8 @@ impl From<MotionPathType> for DbEnumName {
 
         match t {
             Position    => DbEnumName("MotionPathType", "Position"),
+            Rotation    => DbEnumName("MotionPathType", "Rotation"),
+            Scale       => DbEnumName("MotionPathType", "Scale"),
         }
     }
 }
@@ -572,6 +971,67 @@ impl From<CacheType> for DbEnumName {
     }
 }
 
+impl From<GroupType> for DbEnumName {
+    fn from(t: GroupType) -> DbEnumName {
+        use self::GroupType::*;
+
+        match t {
+            Normal          => DbEnumName("GroupType", "Normal"),
+            Added           => DbEnumName("GroupType", "Added"),
+            Subtracted      => DbEnumName("GroupType", "Subtracted"),
+            Masked          => DbEnumName("GroupType", "Masked"),
+            InvertedMask    => DbEnumName("GroupType", "InvertedMask")
+        }
+    }
+}
+
+impl From<TextAlignmentType> for DbEnumName {
+    fn from(t: TextAlignmentType) -> DbEnumName {
+        use self::TextAlignmentType::*;
+
+        match t {
+            Left        => DbEnumName("TextAlignment", "Left"),
+            Center      => DbEnumName("TextAlignment", "Center"),
+            Right       => DbEnumName("TextAlignment", "Right")
+        }
+    }
+}
+
+impl From<BrushGradientType> for DbEnumName {
+    fn from(t: BrushGradientType) -> DbEnumName {
+        use self::BrushGradientType::*;
+
+        match t {
+            Linear      => DbEnumName("BrushGradient", "Linear"),
+            Radial      => DbEnumName("BrushGradient", "Radial")
+        }
+    }
+}
+
+impl From<PressureCurveType> for DbEnumName {
+    fn from(t: PressureCurveType) -> DbEnumName {
+        use self::PressureCurveType::*;
+
+        match t {
+            Linear      => DbEnumName("PressureCurve", "Linear"),
+            Soft        => DbEnumName("PressureCurve", "Soft"),
+            Firm        => DbEnumName("PressureCurve", "Firm"),
+            Bezier      => DbEnumName("PressureCurve", "Bezier")
+        }
+    }
+}
+
+impl From<PressureSourceType> for DbEnumName {
+    fn from(t: PressureSourceType) -> DbEnumName {
+        use self::PressureSourceType::*;
+
+        match t {
+            Device      => DbEnumName("PressureSource", "Device"),
+            Velocity    => DbEnumName("PressureSource", "Velocity")
+        }
+    }
+}
+
 impl From<DbEnum> for DbEnumName {
     fn from(t: DbEnum) -> DbEnumName {
         use self::DbEnum::*;
@@ -582,11 +1042,17 @@ impl From<DbEnum> for DbEnumName {
             BrushDefinition(bdt)    => DbEnumName::from(bdt),
             Color(ct)               => DbEnumName::from(ct),
             Layer(lt)               => DbEnumName::from(lt),
+            LayerBlend(lbt)         => DbEnumName::from(lbt),
             VectorElement(vet)      => DbEnumName::from(vet),
             MotionType(mot)         => DbEnumName::from(mot),
             MotionPathType(mpt)     => DbEnumName::from(mpt),
             PathPoint(ppt)          => DbEnumName::from(ppt),
-            CacheType(ct)           => DbEnumName::from(ct)
+            CacheType(ct)           => DbEnumName::from(ct),
+            GroupType(gt)           => DbEnumName::from(gt),
+            TextAlignment(ta)       => DbEnumName::from(ta),
+            BrushGradient(bgt)      => DbEnumName::from(bgt),
+            PressureCurve(pct)      => DbEnumName::from(pct),
+            PressureSource(pst)     => DbEnumName::from(pst)
         }
     }
 }
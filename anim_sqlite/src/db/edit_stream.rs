@@ -1,7 +1,9 @@
 use super::*;
 use super::db_enum::*;
 use super::flo_query::*;
+use super::time_path::*;
 
+use flo_canvas::{CanvasImage, ImageFormat, Transform2D};
 use futures::task;
 
 use std::ops::Range;
@@ -64,6 +66,14 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
         AnimationEdit::SetSize(width, height)
     }
 
+    ///
+    /// Retrieves the duration stored as the first integer parameter of an entry
+    ///
+    fn duration_for_entry(core: &mut AnimationDbCore<TFile>, entry: &EditLogEntry) -> Duration {
+        let nanos = core.db.query_edit_log_int(entry.edit_id, 0).unwrap_or(0);
+        Duration::from_nanos(nanos as u64)
+    }
+
     ///
     /// Generates a SelectBrush entry
     ///
@@ -115,6 +125,15 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
         Arc::new(points)
     }
 
+    ///
+    /// Retrieves the processed brush points associated with an entry
+    ///
+    fn brush_points_for_entry(core: &mut AnimationDbCore<TFile>, edit_id: i64) -> Arc<Vec<BrushPoint>> {
+        let points = core.db.query_edit_log_brush_points(edit_id).unwrap_or_else(|_err| vec![]);
+
+        Arc::new(points)
+    }
+
     ///
     /// Retrieves the path components associated with a particular edit log ID
     ///
@@ -143,6 +162,17 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
         LayerEdit::Paint(when, PaintEdit::BrushStroke(element_id, points))
     }
 
+    ///
+    /// Decodes a 'create brush stroke' entry
+    ///
+    fn create_brush_stroke_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> LayerEdit {
+        let points      = Self::brush_points_for_entry(core, entry.edit_id);
+        let when        = entry.when.unwrap_or(Duration::from_millis(0));
+        let element_id  = ElementId::from(entry.element_id);
+
+        LayerEdit::Paint(when, PaintEdit::CreateBrushStroke(element_id, points))
+    }
+
     ///
     /// Decodes a 'create path' entry
     ///
@@ -184,6 +214,145 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
         LayerEdit::Path(when, PathEdit::SelectBrush(element_id, brush, drawing_style.into()))
     }
 
+    ///
+    /// Decodes a 'create text' entry
+    ///
+    fn create_text_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> LayerEdit {
+        let edit_id     = entry.edit_id;
+        let when        = entry.when.unwrap_or(Duration::from_millis(0));
+        let element_id  = ElementId::from(entry.element_id);
+
+        // Decode the style of the text
+        let mut style   = TextStyle::new();
+
+        if let Ok(color_id) = core.db.query_edit_log_color_id(edit_id) {
+            style.color = AnimationDbCore::get_color(&mut core.db, color_id).unwrap_or(style.color);
+        }
+        style.font_family   = core.db.query_edit_log_string(edit_id, 0).unwrap_or(style.font_family);
+        style.font_size     = core.db.query_edit_log_float(edit_id, 0).map(|size| size as f32).unwrap_or(style.font_size);
+        style.alignment     = core.db.query_edit_log_text_alignment(edit_id, 0).map(|alignment| alignment.into()).unwrap_or(style.alignment);
+
+        // Position and text
+        let x           = core.db.query_edit_log_float(edit_id, 1).unwrap_or(0.0) as f32;
+        let y           = core.db.query_edit_log_float(edit_id, 2).unwrap_or(0.0) as f32;
+        let text        = core.db.query_edit_log_string(edit_id, 1).unwrap_or_else(|_err| String::new());
+
+        LayerEdit::Text(when, TextEdit::CreateText(element_id, style, (x, y), text))
+    }
+
+    ///
+    /// Decodes a 'create image' entry
+    ///
+    fn create_image_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> LayerEdit {
+        let edit_id     = entry.edit_id;
+        let when        = entry.when.unwrap_or(Duration::from_millis(0));
+        let element_id  = ElementId::from(entry.element_id);
+
+        // The format and size of the image are read from its data
+        let data        = core.db.query_edit_log_image(edit_id).unwrap_or_else(|_err| vec![]);
+        let image       = CanvasImage::from_data(data)
+            .unwrap_or_else(|| CanvasImage { format: ImageFormat::Png, width: 0, height: 0, data: Arc::new(vec![]) });
+
+        // Transform is stored as the top two rows of the matrix
+        let matrix      = (0..6)
+            .map(|index| core.db.query_edit_log_float(edit_id, index).unwrap_or(0.0) as f32)
+            .collect::<Vec<_>>();
+        let transform   = Transform2D((matrix[0], matrix[1], matrix[2]), (matrix[3], matrix[4], matrix[5]), (0.0, 0.0, 1.0));
+
+        LayerEdit::Image(when, ImageEdit::CreateImage(element_id, image, transform))
+    }
+
+    ///
+    /// Retrieves the element IDs associated with an entry
+    ///
+    fn element_ids_for_entry(core: &mut AnimationDbCore<TFile>, entry: &EditLogEntry) -> Vec<ElementId> {
+        core.db.query_edit_log_element_ids(entry.edit_id)
+            .unwrap_or_else(|_err| vec![])
+            .into_iter()
+            .map(|element_id| ElementId::Assigned(element_id))
+            .collect()
+    }
+
+    ///
+    /// Decodes a 'group' entry
+    ///
+    fn group_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        // The group ID is stored first, followed by the IDs of the elements in the group
+        let mut element_ids = Self::element_ids_for_entry(core, &entry);
+        let group_id        = if element_ids.len() > 0 { element_ids.remove(0) } else { ElementId::Unassigned };
+        let group_type      = core.db.query_edit_log_group_type(entry.edit_id, 0).unwrap_or(GroupType::Normal);
+
+        AnimationEdit::Element(element_ids, ElementEdit::Group(group_id, group_type))
+    }
+
+    ///
+    /// Decodes an 'add attachment' or 'remove attachment' entry
+    ///
+    fn attachment_for_entry<EditFn: FnOnce(ElementId) -> ElementEdit>(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry, edit: EditFn) -> AnimationEdit {
+        // The attached element ID is stored first, followed by the IDs of the elements it's attached to
+        let mut element_ids = Self::element_ids_for_entry(core, &entry);
+        let attachment_id   = if element_ids.len() > 0 { element_ids.remove(0) } else { ElementId::Unassigned };
+
+        AnimationEdit::Element(element_ids, edit(attachment_id))
+    }
+
+    ///
+    /// Decodes a 'set control points' entry
+    ///
+    fn control_points_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let points = core.db.query_edit_log_path_id(entry.edit_id)
+            .and_then(|path_id| core.db.query_path_points(path_id))
+            .unwrap_or_else(|_err| vec![]);
+
+        AnimationEdit::Element(Self::element_ids_for_entry(core, &entry), ElementEdit::SetControlPoints(points))
+    }
+
+    ///
+    /// Decodes a 'set path' entry for an element
+    ///
+    fn element_path_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let components = Self::path_components_for_entry(core, entry.edit_id).unwrap_or_else(|_err| vec![]);
+
+        AnimationEdit::Element(Self::element_ids_for_entry(core, &entry), ElementEdit::SetPath(Arc::new(components)))
+    }
+
+    ///
+    /// Decodes an entry that changes the ordering of some elements
+    ///
+    fn order_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry, ordering: ElementOrdering) -> AnimationEdit {
+        AnimationEdit::Element(Self::element_ids_for_entry(core, &entry), ElementEdit::Order(ordering))
+    }
+
+    ///
+    /// Decodes an entry that orders some elements before another element
+    ///
+    fn order_before_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        // The element to order before is stored as an integer (and is missing if it was unassigned)
+        let before = core.db.query_edit_log_int(entry.edit_id, 0)
+            .map(|element_id| ElementId::Assigned(element_id))
+            .unwrap_or(ElementId::Unassigned);
+
+        Self::order_for_entry(core, entry, ElementOrdering::Before(before))
+    }
+
+    ///
+    /// Decodes a 'set origin' entry for a motion
+    ///
+    fn motion_origin_for_entry(core: &mut AnimationDbCore<TFile>, entry: EditLogEntry) -> AnimationEdit {
+        let (x, y) = core.db.query_edit_log_motion_origin(entry.edit_id).unwrap_or((0.0, 0.0));
+
+        AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::SetOrigin(x, y))
+    }
+
+    ///
+    /// Retrieves the motion curve associated with an entry
+    ///
+    fn time_curve_for_entry(core: &mut AnimationDbCore<TFile>, entry: &EditLogEntry) -> TimeCurve {
+        let points = core.db.query_edit_log_motion_timepoints(entry.edit_id).unwrap_or_else(|_err| vec![]);
+
+        time_curve_from_time_points(points)
+    }
+
     ///
     /// Turns an edit log entry into an animation edit
     ///
@@ -192,39 +361,55 @@ impl<TFile: FloFile+Send> EditStream<TFile> {
 
         match entry.edit_type {
             SetSize                     => Self::set_size_for_entry(core, entry),
+            SetDuration                 => AnimationEdit::SetDuration(Self::duration_for_entry(core, &entry)),
+            SetFrameLength              => AnimationEdit::SetFrameLength(Self::duration_for_entry(core, &entry)),
             AddNewLayer                 => AnimationEdit::AddNewLayer(entry.layer_id.unwrap_or(INVALID_LAYER)),
+            AddNewGroupLayer            => AnimationEdit::AddNewGroupLayer(entry.layer_id.unwrap_or(INVALID_LAYER)),
             RemoveLayer                 => AnimationEdit::RemoveLayer(entry.layer_id.unwrap_or(INVALID_LAYER)),
 
             LayerAddKeyFrame            => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::AddKeyFrame(entry.when.unwrap_or(Duration::from_millis(0)))),
             LayerRemoveKeyFrame         => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::RemoveKeyFrame(entry.when.unwrap_or(Duration::from_millis(0)))),
             LayerSetName                => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetName(core.db.query_edit_log_string(entry.edit_id, 0).unwrap())),
-            LayerSetOrdering            => unimplemented!(),
+            LayerSetOrdering            => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetOrdering(core.db.query_edit_log_int(entry.edit_id, 0).unwrap_or(0) as u32)),
+            LayerSetVisible             => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetVisible(core.db.query_edit_log_int(entry.edit_id, 0).unwrap() != 0)),
+            LayerSetLocked              => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetLocked(core.db.query_edit_log_int(entry.edit_id, 0).unwrap() != 0)),
+            LayerSetOpacity             => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetOpacity(core.db.query_edit_log_float(entry.edit_id, 0).unwrap())),
+            LayerSetBlendMode           => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetBlendMode(core.db.query_edit_log_layer_blend(entry.edit_id, 0).unwrap().into())),
+            LayerSetParentGroup         => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), LayerEdit::SetParentGroup(core.db.query_edit_log_int(entry.edit_id, 0).ok().map(|parent_id| parent_id as u64))),
 
             LayerPaintSelectBrush       => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::select_brush_for_entry(core, entry)),
             LayerPaintBrushProperties   => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::brush_properties_for_entry(core, entry)),
             LayerPaintBrushStroke       => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::brush_stroke_for_entry(core, entry)),
+            LayerPaintCreateBrushStroke => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::create_brush_stroke_for_entry(core, entry)),
 
             LayerPathCreatePath         => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::create_path_for_entry(core, entry)),
             LayerPathSelectBrush        => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::path_brush_for_entry(core, entry)),
             LayerPathBrushProperties    => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::path_properties_for_entry(core, entry)),
 
-            MotionCreate                => unimplemented!(),
-            MotionDelete                => unimplemented!(),
-            MotionSetType               => unimplemented!(),
-            MotionSetOrigin             => unimplemented!(),
-            MotionSetPath               => unimplemented!(),
-
-            ElementAddAttachment        => unimplemented!(),
-            ElementRemoveAttachment     => unimplemented!(),
-            ElementSetControlPoints     => unimplemented!(),
-            ElementSetPath              => unimplemented!(),
-            ElementOrderInFront         => unimplemented!(),
-            ElementOrderBehind          => unimplemented!(),
-            ElementOrderToTop           => unimplemented!(),
-            ElementOrderToBottom        => unimplemented!(),
-            ElementOrderBefore          => unimplemented!(),
-            ElementDelete               => unimplemented!(),
-            ElementDetachFromFrame      => unimplemented!()
+            LayerTextCreateText         => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::create_text_for_entry(core, entry)),
+            LayerImageCreateImage       => AnimationEdit::Layer(entry.layer_id.unwrap_or(INVALID_LAYER), Self::create_image_for_entry(core, entry)),
+
+            MotionCreate                => AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::Create),
+            MotionDelete                => AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::Delete),
+            MotionSetType               => AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::SetType(core.db.query_edit_log_motion_type(entry.edit_id).unwrap_or(MotionType::None))),
+            MotionSetOrigin             => Self::motion_origin_for_entry(core, entry),
+            MotionSetPath               => AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::SetPath(Self::time_curve_for_entry(core, &entry))),
+            MotionSetRotation           => AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::SetRotation(Self::time_curve_for_entry(core, &entry))),
+            MotionSetScale              => AnimationEdit::Motion(ElementId::from(entry.element_id), MotionEdit::SetScale(Self::time_curve_for_entry(core, &entry))),
+
+            ElementAddAttachment        => Self::attachment_for_entry(core, entry, |attachment_id| ElementEdit::AddAttachment(attachment_id)),
+            ElementRemoveAttachment     => Self::attachment_for_entry(core, entry, |attachment_id| ElementEdit::RemoveAttachment(attachment_id)),
+            ElementSetControlPoints     => Self::control_points_for_entry(core, entry),
+            ElementSetPath              => Self::element_path_for_entry(core, entry),
+            ElementOrderInFront         => Self::order_for_entry(core, entry, ElementOrdering::InFront),
+            ElementOrderBehind          => Self::order_for_entry(core, entry, ElementOrdering::Behind),
+            ElementOrderToTop           => Self::order_for_entry(core, entry, ElementOrdering::ToTop),
+            ElementOrderToBottom        => Self::order_for_entry(core, entry, ElementOrdering::ToBottom),
+            ElementOrderBefore          => Self::order_before_for_entry(core, entry),
+            ElementDelete               => AnimationEdit::Element(Self::element_ids_for_entry(core, &entry), ElementEdit::Delete),
+            ElementDetachFromFrame      => AnimationEdit::Element(Self::element_ids_for_entry(core, &entry), ElementEdit::DetachFromFrame),
+            ElementGroup                => Self::group_for_entry(core, entry),
+            ElementUngroup              => AnimationEdit::Element(Self::element_ids_for_entry(core, &entry), ElementEdit::Ungroup)
         }
     }
 
@@ -34,8 +34,10 @@ pub struct ColorEntry {
 /// Entry read from the brush table
 ///
 pub struct BrushEntry {
-    pub brush_type: BrushDefinitionType,
-    pub ink_defn:   Option<(f64, f64, f64)>
+    pub brush_type:         BrushDefinitionType,
+    pub ink_defn:           Option<(f64, f64, f64)>,
+    pub calligraphy_defn:   Option<(f64, f64, f64, f64, f64)>,
+    pub ink_pressure:       Option<(PressureSourceType, PressureCurveType, (f64, f64), (f64, f64))>
 }
 
 ///
@@ -44,7 +46,10 @@ pub struct BrushEntry {
 pub struct BrushPropertiesEntry {
     pub size:       f64,
     pub opacity:    f64,
-    pub color_id:   i64
+    pub color_id:   i64,
+
+    /// The gradient type, end colour ID and angle, if these brush properties fill with a gradient
+    pub gradient:   Option<(BrushGradientType, i64, f64)>
 }
 
 ///
@@ -76,6 +81,37 @@ pub struct PathElementEntry {
     pub brush_properties_id:    i64
 }
 
+///
+/// Entry read from the group element tables
+///
+pub struct GroupElementEntry {
+    pub element_id:             i64,
+    pub group_type:             GroupType,
+    pub member_ids:             Vec<i64>
+}
+
+///
+/// Entry read from the text element table
+///
+pub struct TextElementEntry {
+    pub element_id:             i64,
+    pub color_id:               i64,
+    pub font_family:            String,
+    pub font_size:              f32,
+    pub alignment:              TextAlignmentType,
+    pub position:               (f32, f32),
+    pub text:                   String
+}
+
+///
+/// Entry read from the image element table
+///
+pub struct ImageElementEntry {
+    pub element_id:             i64,
+    pub data:                   Vec<u8>,
+    pub transform:              Transform2D
+}
+
 ///
 /// Entry read from the time point table
 ///
@@ -95,6 +131,17 @@ pub struct MotionEntry {
     pub origin:         Option<(f32, f32)>,
 }
 
+///
+/// Entry read from the layer properties table
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct LayerPropertiesEntry {
+    pub visible:        bool,
+    pub locked:         bool,
+    pub opacity:        f64,
+    pub blend_mode:     LayerBlendType
+}
+
 ///
 /// Trait implemented by objects that can query an underlying store for FlowBetween
 ///
@@ -104,6 +151,26 @@ pub trait FloQuery {
     ///
     fn query_layer_id_for_assigned_id(&mut self, assigned_id: u64) -> Result<(i64, Option<String>), SqliteAnimationError>;
 
+    ///
+    /// Retrieves the visibility, locked state, opacity and blend mode for the layer with the specified (real) ID
+    ///
+    fn query_layer_properties(&mut self, layer_id: i64) -> Result<LayerPropertiesEntry, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the type of the layer with the specified (real) ID
+    ///
+    fn query_layer_type(&mut self, layer_id: i64) -> Result<LayerType, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the real and assigned IDs of the group layer containing the layer with the specified (real) ID, if it's in a group
+    ///
+    fn query_parent_layer_group(&mut self, layer_id: i64) -> Result<Option<(i64, u64)>, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the assigned IDs of the layers that are directly contained in the group layer with the specified (real) ID
+    ///
+    fn query_child_layer_ids(&mut self, layer_id: i64) -> Result<Vec<u64>, SqliteAnimationError>;
+
     ///
     /// Returns an iterator over the key frame times for a particular layer ID
     ///
@@ -144,6 +211,11 @@ pub trait FloQuery {
     ///
     fn query_edit_log_length(&mut self) -> Result<i64, SqliteAnimationError>;
 
+    ///
+    /// Retrieves the number of edits that have been removed from the edit log by compacting the animation
+    ///
+    fn query_discarded_edit_count(&mut self) -> Result<i64, SqliteAnimationError>;
+
     ///
     /// Retrieves a set of values from the edit log
     ///
@@ -159,16 +231,76 @@ pub trait FloQuery {
     ///
     fn query_edit_log_raw_points(&mut self, edit_id: i64) -> Result<Vec<RawPoint>, SqliteAnimationError>;
 
+    ///
+    /// Retrieves the processed brush points associated with a particular edit ID
+    ///
+    fn query_edit_log_brush_points(&mut self, edit_id: i64) -> Result<Vec<BrushPoint>, SqliteAnimationError>;
+
     ///
     /// Retrieves the ID of the path associated with the specified edit ID
     ///
     fn query_edit_log_path_id(&mut self, edit_id: i64) -> Result<i64, SqliteAnimationError>;
 
+    ///
+    /// Retrieves the element IDs associated with the specified edit ID, in index order
+    ///
+    fn query_edit_log_element_ids(&mut self, edit_id: i64) -> Result<Vec<i64>, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the time points of the motion curve associated with the specified edit ID
+    ///
+    fn query_edit_log_motion_timepoints(&mut self, edit_id: i64) -> Result<Vec<TimePointEntry>, SqliteAnimationError>;
+
     ///
     /// Retrieves the string associated with a specific edit ID
     ///
     fn query_edit_log_string(&mut self, edit_id: i64, string_index: u32) -> Result<String, SqliteAnimationError>;
 
+    ///
+    /// Retrieves the integer associated with a specific edit ID
+    ///
+    fn query_edit_log_int(&mut self, edit_id: i64, int_index: u32) -> Result<i64, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the floating point value associated with a specific edit ID
+    ///
+    fn query_edit_log_float(&mut self, edit_id: i64, float_index: u32) -> Result<f64, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the layer blend mode stored as an integer value for a specific edit ID
+    ///
+    fn query_edit_log_layer_blend(&mut self, edit_id: i64, int_index: u32) -> Result<LayerBlendType, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the group type stored as an integer value for a specific edit ID
+    ///
+    fn query_edit_log_group_type(&mut self, edit_id: i64, int_index: u32) -> Result<GroupType, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the text alignment stored as an integer value for a specific edit ID
+    ///
+    fn query_edit_log_text_alignment(&mut self, edit_id: i64, int_index: u32) -> Result<TextAlignmentType, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the ID of the colour associated with a specific edit ID
+    ///
+    fn query_edit_log_color_id(&mut self, edit_id: i64) -> Result<i64, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the encoded image data associated with a specific edit ID
+    ///
+    fn query_edit_log_image(&mut self, edit_id: i64) -> Result<Vec<u8>, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the motion type associated with a specific edit ID
+    ///
+    fn query_edit_log_motion_type(&mut self, edit_id: i64) -> Result<MotionType, SqliteAnimationError>;
+
+    ///
+    /// Retrieves the motion origin associated with a specific edit ID
+    ///
+    fn query_edit_log_motion_origin(&mut self, edit_id: i64) -> Result<(f32, f32), SqliteAnimationError>;
+
     ///
     /// Retrieves a colour with the specified ID
     ///
@@ -239,11 +371,31 @@ pub trait FloQuery {
     ///
     fn query_path_element(&mut self, element_id: i64) -> Result<Option<PathElementEntry>, SqliteAnimationError>;
 
+    ///
+    /// Queries a group element and the IDs of the elements it contains (in order)
+    ///
+    fn query_group_element(&mut self, element_id: i64) -> Result<Option<GroupElementEntry>, SqliteAnimationError>;
+
+    ///
+    /// Queries a text element
+    ///
+    fn query_text_element(&mut self, element_id: i64) -> Result<Option<TextElementEntry>, SqliteAnimationError>;
+
+    ///
+    /// Queries an image element
+    ///
+    fn query_image_element(&mut self, element_id: i64) -> Result<Option<ImageElementEntry>, SqliteAnimationError>;
+
     ///
     /// Queries the path components associated with a vector element
     ///
     fn query_path_components(&mut self, path_id: i64) -> Result<Vec<PathComponent>, SqliteAnimationError>;
 
+    ///
+    /// Queries the control points that make up a path
+    ///
+    fn query_path_points(&mut self, path_id: i64) -> Result<Vec<(f32, f32)>, SqliteAnimationError>;
+
     ///
     /// Queries the motion associated with a particular motion ID
     ///
@@ -20,6 +20,8 @@ pub use self::store::*;
 
 const V1_V2_UPGRADE: &[u8]          = include_bytes!["../../../sql/historical/flo_v1_to_v2.sqlite"];
 const V3_DEFINITION: &[u8]          = include_bytes!["../../../sql/flo_v3.sqlite"];
+const DISCARD_EDITS: &[u8]          = include_bytes!["../../../sql/compaction/discard_edits.sqlite"];
+const COLLECT_GARBAGE: &[u8]        = include_bytes!["../../../sql/compaction/collect_garbage.sqlite"];
 const PACKAGE_NAME: &str            = env!("CARGO_PKG_NAME");
 const PACKAGE_VERSION: &str         = env!("CARGO_PKG_VERSION");
 
@@ -27,7 +29,18 @@ lazy_static! {
     static ref V3_PATCHES: Vec<(&'static str, &'static [u8])> = vec![
         ("attached_elements", include_bytes!["../../../sql/v3_patches/attached_elements.sqlite"]),
         ("cached_drawing", include_bytes!["../../../sql/v3_patches/cached_drawing.sqlite"]),
-        ("layer_cache", include_bytes!["../../../sql/v3_patches/layer_cache.sqlite"])
+        ("layer_cache", include_bytes!["../../../sql/v3_patches/layer_cache.sqlite"]),
+        ("layer_properties", include_bytes!["../../../sql/v3_patches/layer_properties.sqlite"]),
+        ("groups", include_bytes!["../../../sql/v3_patches/groups.sqlite"]),
+        ("text", include_bytes!["../../../sql/v3_patches/text.sqlite"]),
+        ("images", include_bytes!["../../../sql/v3_patches/images.sqlite"]),
+        ("brush_gradients", include_bytes!["../../../sql/v3_patches/brush_gradients.sqlite"]),
+        ("edit_log_compaction", include_bytes!["../../../sql/v3_patches/edit_log_compaction.sqlite"]),
+        ("layer_groups", include_bytes!["../../../sql/v3_patches/layer_groups.sqlite"]),
+        ("brush_stroke_points", include_bytes!["../../../sql/v3_patches/brush_stroke_points.sqlite"]),
+        ("calligraphy_brush", include_bytes!["../../../sql/v3_patches/calligraphy_brush.sqlite"]),
+        ("ink_pressure", include_bytes!["../../../sql/v3_patches/ink_pressure.sqlite"]),
+        ("layer_ordering", include_bytes!["../../../sql/v3_patches/layer_ordering.sqlite"])
     ];
 }
 
@@ -71,7 +84,13 @@ enum FloStatement {
     SelectEnumValue,
     SelectLayerId,
     SelectLayerIdAndName,
+    SelectLayerProperties,
+    SelectLayerType,
+    SelectParentLayerGroup,
+    SelectChildLayers,
+    SelectLayerOrdering,
     SelectElementKeyFrame,
+    SelectElementKeyFrameAndTime,
     SelectNearestKeyFrame,
     SelectPreviousKeyFrame,
     SelectNextKeyFrame,
@@ -81,11 +100,20 @@ enum FloStatement {
     SelectAnimationFrameLength,
     SelectAssignedLayerIds,
     SelectEditLogLength,
+    SelectDiscardedEditCount,
     SelectEditLogValues,
     SelectEditLogSize,
     SelectEditLogRawPoints,
+    SelectEditLogBrushPoints,
     SelectEditLogPathId,
+    SelectEditLogElementIds,
+    SelectEditLogMotionTimePoints,
     SelectEditLogString,
+    SelectEditLogInt,
+    SelectEditLogFloat,
+    SelectEditLogColor,
+    SelectEditLogMotionType,
+    SelectEditLogMotionOrigin,
     SelectColor,
     SelectBrushDefinition,
     SelectBrushProperties,
@@ -105,14 +133,28 @@ enum FloStatement {
     SelectZIndexBeforeZIndexForKeyFrame,
     SelectZIndexAfterZIndexForKeyFrame,
     SelectMaxZIndexForKeyFrame,
+    SelectGroupType,
+    SelectGroupMembers,
+    SelectTextElement,
+    SelectImageElement,
+    SelectEditLogImage,
     SelectPathElement,
     SelectPathPointsWithTypes,
+    SelectPathPoints,
     SelectLayerCacheDrawing,
 
     UpdateAnimationSize,
+    UpdateAnimationDuration,
+    UpdateAnimationFrameLength,
+    UpdateLayerVisible,
+    UpdateLayerLocked,
+    UpdateLayerOpacity,
+    UpdateLayerBlendMode,
     UpdateMotionType,
     UpdateBrushPoint,
     UpdatePathPoint,
+    UpdateTextPosition,
+    UpdateImageTransform,
     UpdateMoveZIndexUpwards,
     UpdateMoveZIndexDownwards,
     UpdatePathPointIndicesAfter,
@@ -127,6 +169,7 @@ enum FloStatement {
     InsertELBrushProperties,
     InsertELElementId,
     InsertELRawPoints,
+    InsertELBrushPoints,
     InsertELMotionOrigin,
     InsertELMotionType,
     InsertELMotionElement,
@@ -135,19 +178,28 @@ enum FloStatement {
     InsertELString,
     InsertELInt,
     InsertELFloat,
+    InsertELColor,
+    InsertELImage,
+    InsertImage,
     InsertPath,
     InsertPathPoint,
     InsertPathPointType,
     InsertTimePoint,
     InsertBrushType,
     InsertInkBrush,
+    InsertCalligraphyBrush,
+    InsertInkPressure,
     InsertBrushProperties,
+    InsertBrushGradient,
     InsertColorType,
     InsertRgb,
     InsertHsluv,
     InsertLayerType,
     InsertAssignLayer,
     InsertOrReplaceLayerName,
+    InsertOrIgnoreLayerProperties,
+    InsertOrReplaceLayerGroup,
+    InsertOrReplaceLayerOrdering,
     InsertKeyFrame,
     InsertVectorElementType,
     InsertOrReplaceVectorElementTime,
@@ -163,9 +215,14 @@ enum FloStatement {
     InsertMotionPathPoint,
     InsertNewCachedDrawing,
     InsertOrReplaceLayerCache,
+    InsertGroupElement,
+    InsertGroupMember,
+    InsertTextElement,
+    InsertImageElement,
 
     DeleteKeyFrame,
     DeleteLayer,
+    DeleteLayerGroup,
     DeleteElementZIndex,
     DeleteElementAttachment,
     DeleteMotion,
@@ -174,7 +231,8 @@ enum FloStatement {
     DeletePathPointRange,
     DeletePathPointTypeRange,
     DeleteVectorElement,
-    DeleteVectorElementTime
+    DeleteVectorElementTime,
+    DeleteGroupMembers
 }
 
 impl FloSqlite {
@@ -198,10 +256,43 @@ impl FloSqlite {
         }
     }
 
+    ///
+    /// Checks that a connection contains an animation in a version of the file format that can be opened, returning the version number
+    ///
+    pub fn check_version(sqlite: &Connection) -> Result<i64, SqliteAnimationError> {
+        let animation_version: i64 = sqlite.query_row("SELECT DataVersion FROM FlowBetween", NO_PARAMS, |row| row.get(0))?;
+
+        match animation_version {
+            1 | 3   => Ok(animation_version),
+            2       => Err(SqliteAnimationError::CannotUpgradeVersionTooOld(animation_version)),
+            _       => Err(SqliteAnimationError::UnsupportedVersionNumber(animation_version))
+        }
+    }
+
+    ///
+    /// Returns the names of the patches that will be applied when a connection is upgraded
+    ///
+    pub fn pending_patches(sqlite: &Connection) -> Result<Vec<String>, SqliteAnimationError> {
+        if Self::check_version(sqlite)? == 1 {
+            // Version 1 files are converted to the version 2 format
+            return Ok(vec![String::from("v1_to_v2")]);
+        }
+
+        let mut applied_patches = sqlite.prepare("SELECT PatchName FROM Flo_AppliedPatches")?;
+        let applied_patches     = applied_patches
+            .query_map(NO_PARAMS, |row| row.get::<_, String>(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        Ok(V3_PATCHES.iter()
+            .map(|(patch_name, _patch_sql)| String::from(*patch_name))
+            .filter(|patch_name| !applied_patches.contains(patch_name))
+            .collect())
+    }
+
     ///
     /// Upgrades a connection so that it conforms to the latest version
     ///
-    fn upgrade(sqlite: &mut Connection) -> Result<(), SqliteAnimationError> {
+    pub fn upgrade(sqlite: &mut Connection) -> Result<(), SqliteAnimationError> {
         let animation_version: i64 = sqlite.query_row("SELECT DataVersion FROM FlowBetween", NO_PARAMS, |row| row.get(0))?;
 
         if animation_version == 1 {
@@ -298,6 +389,74 @@ impl FloSqlite {
         Ok(())
     }
 
+    ///
+    /// Removes the cached drawings and rebuilds the database file so that it takes up as little space as possible
+    ///
+    pub fn compact_storage(&mut self) -> Result<(), SqliteAnimationError> {
+        self.flush_pending()?;
+
+        // Cached drawings can always be regenerated from the animation, so there's no need to keep them in a compacted file
+        self.sqlite.execute_batch("DELETE FROM Flo_LayerCache; DELETE FROM Flo_CachedDrawings; VACUUM;")?;
+
+        Ok(())
+    }
+
+    ///
+    /// Removes all but the most recent `keep_edits` entries from the edit log (the whole log is kept if this is None),
+    /// then deletes any data that's no longer used by either the animation or the edit log.
+    ///
+    /// Returns the number of edits that were removed from the edit log.
+    ///
+    pub fn compact_edit_log(&mut self, keep_edits: Option<usize>) -> Result<usize, SqliteAnimationError> {
+        self.flush_pending()?;
+
+        let num_edits       = self.query_edit_log_length()? as usize;
+        let num_discarded   = keep_edits.map(|keep_edits| num_edits.saturating_sub(keep_edits)).unwrap_or(0);
+
+        let transaction     = self.sqlite.transaction()?;
+
+        if num_discarded > 0 {
+            // The oldest edits are removed from the log: the state they produced is already in the keyframe and element tables
+            transaction.execute("CREATE TEMP TABLE Flo_DiscardedEdits AS SELECT Id FROM Flo_EditLog ORDER BY Id ASC LIMIT ?", &[&(num_discarded as i64)])?;
+            transaction.execute_batch(&String::from_utf8_lossy(DISCARD_EDITS))?;
+        }
+
+        transaction.execute_batch(&String::from_utf8_lossy(COLLECT_GARBAGE))?;
+        transaction.commit()?;
+
+        Ok(num_discarded)
+    }
+
+    ///
+    /// Checks the database for corruption, returning a description of each problem that was found
+    ///
+    pub fn check_integrity(&mut self) -> Result<Vec<String>, SqliteAnimationError> {
+        self.flush_pending()?;
+
+        // SQLite's own consistency checks report 'ok' if there are no problems
+        let mut integrity_check = self.sqlite.prepare("PRAGMA integrity_check")?;
+        let mut problems        = integrity_check
+            .query_map(NO_PARAMS, |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|result| result != "ok")
+            .collect::<Vec<_>>();
+
+        // Check for references to rows that don't exist
+        let mut foreign_key_check   = self.sqlite.prepare("PRAGMA foreign_key_check")?;
+        let missing_references      = foreign_key_check
+            .query_map(NO_PARAMS, |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<i64>>(1)?, row.get::<_, String>(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        problems.extend(missing_references.into_iter()
+            .map(|(table, row_id, parent)| match row_id {
+                Some(row_id)    => format!("{} (row {}) refers to a missing entry in {}", table, row_id, parent),
+                None            => format!("{} refers to a missing entry in {}", table, parent)
+            }));
+
+        Ok(problems)
+    }
+
     ///
     /// Turns a microsecond count into a duration
     ///
@@ -322,6 +481,16 @@ impl FloSqlite {
         (secs * 1_000_000) + (nanos / 1_000)
     }
 
+    ///
+    /// Retrieves nanoseconds from a duration
+    ///
+    fn get_nanos(when: &Duration) -> i64 {
+        let secs:i64    = when.as_secs() as i64;
+        let nanos:i64   = when.subsec_nanos() as i64;
+
+        (secs * 1_000_000_000) + nanos
+    }
+
     ///
     /// Returns the text of the query for a particular statements
     ///
@@ -337,16 +506,38 @@ impl FloSqlite {
                                                         INNER JOIN Flo_LayerType AS Layer ON Layer.LayerId = Anim.LayerId \
                                                         LEFT OUTER JOIN Flo_LayerName AS LayerName ON Layer.LayerId = LayerName.LayerId \
                                                         WHERE Anim.AnimationId = ? AND Anim.AssignedLayerId = ?",
+            SelectLayerProperties               => "SELECT IFNULL(Props.Visible, 1), IFNULL(Props.Locked, 0), IFNULL(Props.Opacity, 1.0), Props.BlendMode \
+                                                        FROM Flo_LayerType AS Layer \
+                                                        LEFT OUTER JOIN Flo_LayerProperties AS Props ON Layer.LayerId = Props.LayerId \
+                                                        WHERE Layer.LayerId = ?",
+            SelectLayerType                     => "SELECT LayerType FROM Flo_LayerType WHERE LayerId = ?",
+            SelectParentLayerGroup              => "SELECT Grp.ParentLayerId, Anim.AssignedLayerId FROM Flo_LayerGroup AS Grp \
+                                                        INNER JOIN Flo_AnimationLayers AS Anim ON Anim.LayerId = Grp.ParentLayerId \
+                                                        WHERE Grp.LayerId = ?",
+            SelectChildLayers                   => "SELECT Anim.AssignedLayerId FROM Flo_LayerGroup AS Grp \
+                                                        INNER JOIN Flo_AnimationLayers AS Anim ON Anim.LayerId = Grp.LayerId \
+                                                        LEFT OUTER JOIN Flo_LayerOrdering AS Ord ON Ord.LayerId = Grp.LayerId \
+                                                        WHERE Grp.ParentLayerId = ? \
+                                                        ORDER BY IFNULL(Ord.OrderIndex, Grp.LayerId) ASC, Grp.LayerId ASC",
+            SelectLayerOrdering                 => "SELECT Anim.LayerId FROM Flo_AnimationLayers AS Anim \
+                                                        LEFT OUTER JOIN Flo_LayerOrdering AS Ord ON Ord.LayerId = Anim.LayerId \
+                                                        WHERE Anim.AnimationId = ? \
+                                                        ORDER BY IFNULL(Ord.OrderIndex, Anim.LayerId) ASC, Anim.LayerId ASC",
             SelectNearestKeyFrame               => "SELECT KeyFrameId, AtTime FROM Flo_LayerKeyFrame WHERE LayerId = ? AND AtTime <= ? ORDER BY AtTime DESC LIMIT 1",
             SelectElementKeyFrame               => "SELECT KeyFrameId FROM Flo_VectorElementTime WHERE ElementId = ?",
+            SelectElementKeyFrameAndTime        => "SELECT KeyFrameId, AtTime FROM Flo_VectorElementTime WHERE ElementId = ?",
             SelectPreviousKeyFrame              => "SELECT KeyFrameId, AtTime FROM Flo_LayerKeyFrame WHERE LayerId = ? AND AtTime < ? ORDER BY AtTime DESC LIMIT 1",
             SelectNextKeyFrame                  => "SELECT KeyFrameId, AtTime FROM Flo_LayerKeyFrame WHERE LayerId = ? AND AtTime > ? ORDER BY AtTime ASC LIMIT 1",
             SelectKeyFrameTimes                 => "SELECT AtTime FROM Flo_LayerKeyFrame WHERE LayerId = ? AND AtTime >= ? AND AtTime < ?",
             SelectAnimationSize                 => "SELECT SizeX, SizeY FROM Flo_Animation WHERE AnimationId = ?",
             SelectAnimationDuration             => "SELECT Duration FROM Flo_Animation WHERE AnimationId = ?",
             SelectAnimationFrameLength          => "SELECT Frame_Length_ns FROM Flo_Animation WHERE AnimationId = ?",
-            SelectAssignedLayerIds              => "SELECT AssignedLayerId FROM Flo_AnimationLayers WHERE AnimationId = ?",
+            SelectAssignedLayerIds              => "SELECT Anim.AssignedLayerId FROM Flo_AnimationLayers AS Anim \
+                                                        LEFT OUTER JOIN Flo_LayerOrdering AS Ord ON Ord.LayerId = Anim.LayerId \
+                                                        WHERE Anim.AnimationId = ? \
+                                                        ORDER BY IFNULL(Ord.OrderIndex, Anim.LayerId) ASC, Anim.LayerId ASC",
             SelectEditLogLength                 => "SELECT COUNT(Id) FROM Flo_EditLog",
+            SelectDiscardedEditCount            => "SELECT IFNULL(SUM(DiscardedEdits), 0) FROM Flo_EditLogCompaction",
             SelectEditLogValues                 => "SELECT EL.Id, EL.Edit, Layers.Layer, Time.AtTime, Brush.DrawingStyle, Brush.Brush, BrushProps.BrushProperties, ElementId.ElementId FROM Flo_EditLog AS EL \
                                                         LEFT OUTER JOIN Flo_EL_Layer           AS Layers        ON EL.Id = Layers.EditId \
                                                         LEFT OUTER JOIN Flo_EL_When            AS Time          ON EL.Id = Time.EditId \
@@ -357,14 +548,30 @@ impl FloSqlite {
                                                         LIMIT ? OFFSET ?",
             SelectEditLogSize                   => "SELECT X, Y FROM Flo_EL_Size WHERE EditId = ?",
             SelectEditLogRawPoints              => "SELECT Points FROM Flo_EL_RawPoints WHERE EditId = ?",
+            SelectEditLogBrushPoints            => "SELECT Points FROM Flo_EL_BrushPoints WHERE EditId = ?",
             SelectEditLogPathId                 => "SELECT PathId FROM Flo_EL_Path WHERE EditId = ?",
+            SelectEditLogElementIds             => "SELECT ElementId FROM Flo_EL_ElementIds WHERE EditId = ? ORDER BY ElementIndex ASC",
+            SelectEditLogMotionTimePoints       => "SELECT Point.X, Point.Y, Point.Milliseconds \
+                                                        FROM Flo_EL_MotionPath      AS Path \
+                                                        INNER JOIN Flo_TimePoint    AS Point ON Path.TimePointId = Point.PointId \
+                                                        WHERE Path.EditId = ? \
+                                                        ORDER BY Path.PointIndex ASC",
             SelectEditLogString                 => "SELECT String FROM Flo_EL_StringParameters WHERE EditId = ? AND StringIndex = ?",
+            SelectEditLogInt                    => "SELECT Value FROM Flo_EL_IntParameters WHERE EditId = ? AND IntIndex = ?",
+            SelectEditLogFloat                  => "SELECT Value FROM Flo_EL_FloatParameters WHERE EditId = ? AND FloatIndex = ?",
+            SelectEditLogColor                  => "SELECT Color FROM Flo_EL_Color WHERE EditId = ?",
+            SelectEditLogMotionType             => "SELECT MotionType FROM Flo_EL_MotionType WHERE EditId = ?",
+            SelectEditLogMotionOrigin           => "SELECT X, Y FROM Flo_EL_MotionOrigin WHERE EditId = ?",
             SelectColor                         => "SELECT Col.ColorType, Rgb.R, Rgb.G, Rgb.B, Hsluv.H, Hsluv.S, Hsluv.L FROM Flo_Color_Type AS Col \
                                                         LEFT OUTER JOIN Flo_Color_Rgb   AS Rgb      ON Col.Color = Rgb.Color \
                                                         LEFT OUTER JOIN Flo_Color_Hsluv AS Hsluv    ON Col.Color = Hsluv.Color \
                                                         WHERE Col.Color = ?",
-            SelectBrushDefinition               => "SELECT Brush.BrushType, Ink.MinWidth, Ink.MaxWidth, Ink.ScaleUpDistance FROM Flo_Brush_Type AS Brush \
-                                                        LEFT OUTER JOIN Flo_Brush_Ink AS Ink ON Brush.Brush = Ink.Brush \
+            SelectBrushDefinition               => "SELECT Brush.BrushType, Ink.MinWidth, Ink.MaxWidth, Ink.ScaleUpDistance, \
+                                                            Cal.MinWidth, Cal.MaxWidth, Cal.NibAngle, Cal.NibAspectRatio, Cal.TiltInfluence, \
+                                                            Pressure.PressureSource, Pressure.CurveType, Pressure.Cp1X, Pressure.Cp1Y, Pressure.Cp2X, Pressure.Cp2Y FROM Flo_Brush_Type AS Brush \
+                                                        LEFT OUTER JOIN Flo_Brush_Ink           AS Ink      ON Brush.Brush = Ink.Brush \
+                                                        LEFT OUTER JOIN Flo_Brush_Calligraphy   AS Cal      ON Brush.Brush = Cal.Brush \
+                                                        LEFT OUTER JOIN Flo_Brush_InkPressure   AS Pressure ON Brush.Brush = Pressure.Brush \
                                                         WHERE Brush.Brush = ?",
             SelectAttachmentsForElementId       => "SELECT Attch.AttachedElementId, Elem.VectorElementType, Assgn.AssignedId FROM Flo_ElementAttachments AS Attch \
                                                         INNER JOIN Flo_VectorElement            AS Elem     ON Elem.ElementId = Attch.AttachedElementId \
@@ -374,7 +581,9 @@ impl FloSqlite {
                                                         INNER JOIN Flo_VectorElement            AS Elem     ON Elem.ElementId = Attch.ElementId \
                                                         LEFT OUTER JOIN Flo_AssignedElementId   AS Assgn    ON Elem.ElementId = Assgn.ElementId \
                                                         WHERE Attch.AttachedElementId = ?;",
-            SelectBrushProperties               => "SELECT Size, Opacity, Color FROM Flo_BrushProperties WHERE BrushProperties = ?",
+            SelectBrushProperties               => "SELECT Props.Size, Props.Opacity, Props.Color, Grad.GradientType, Grad.EndColor, Grad.Angle FROM Flo_BrushProperties AS Props \
+                                                        LEFT OUTER JOIN Flo_BrushGradient AS Grad ON Props.BrushProperties = Grad.BrushProperties \
+                                                        WHERE Props.BrushProperties = ?",
             SelectVectorElementWithId           => "SELECT Elem.ElementId, Elem.VectorElementType, Time.AtTime, Brush.Brush, Brush.DrawingStyle, Props.BrushProperties, Assgn.AssignedId
                                                         FROM Flo_VectorElement                      AS Elem \
                                                         LEFT OUTER JOIN Flo_VectorElementTime       AS Time  ON Elem.ElementId = Time.ElementId \
@@ -441,6 +650,15 @@ impl FloSqlite {
             SelectZIndexBeforeZIndexForKeyFrame => "SELECT IFNULL(MAX(ZIndex), 0) FROM Flo_VectorElementOrdering WHERE KeyFrameId = ? AND ZIndex < ?",
             SelectZIndexAfterZIndexForKeyFrame  => "SELECT IFNULL(MIN(ZIndex), 0) FROM Flo_VectorElementOrdering WHERE KeyFrameId = ? AND ZIndex > ?",
             SelectMaxZIndexForKeyFrame          => "SELECT IFNULL(MAX(ZIndex), 0) FROM Flo_VectorElementOrdering WHERE KeyFrameId = ?",
+            SelectGroupType                     => "SELECT GroupType FROM Flo_GroupElement WHERE ElementId = ?",
+            SelectGroupMembers                  => "SELECT ElementId FROM Flo_GroupMember WHERE GroupElementId = ? ORDER BY MemberIndex ASC",
+            SelectTextElement                   => "SELECT Color, FontFamily, FontSize, Alignment, X, Y, Text FROM Flo_TextElement WHERE ElementId = ?",
+            SelectImageElement                  => "SELECT Img.Data, Elem.A, Elem.B, Elem.C, Elem.D, Elem.E, Elem.F FROM Flo_ImageElement AS Elem \
+                                                        INNER JOIN Flo_Image AS Img ON Img.ImageId = Elem.ImageId \
+                                                        WHERE Elem.ElementId = ?",
+            SelectEditLogImage                  => "SELECT Img.Data FROM Flo_EL_Image AS Edit \
+                                                        INNER JOIN Flo_Image AS Img ON Img.ImageId = Edit.ImageId \
+                                                        WHERE Edit.EditId = ?",
             SelectPathElement                   => "SELECT Elem.PathId \
                                                         FROM Flo_PathElement    AS Elem \
                                                         WHERE Elem.ElementId = ?",
@@ -448,14 +666,23 @@ impl FloSqlite {
                                                         LEFT OUTER JOIN Flo_PathPoints AS Path ON (Path.PathId = Types.PathId AND Types.PointIndex = Path.PointIndex) \
                                                         WHERE Types.PathId = ? \
                                                         ORDER BY Types.PointIndex ASC",
+            SelectPathPoints                    => "SELECT X, Y FROM Flo_PathPoints WHERE PathId = ? ORDER BY PointIndex ASC",
             SelectLayerCacheDrawing             => "SELECT Draw.Drawing FROM Flo_LayerCache AS Cache \
                                                         INNER JOIN Flo_CachedDrawings AS Draw ON Cache.CacheId = Draw.CacheId \
                                                         WHERE Cache.CacheType = ? AND Cache.LayerId = ? AND Cache.CacheTime = ?;",
 
             UpdateAnimationSize                 => "UPDATE Flo_Animation SET SizeX = ?, SizeY = ? WHERE AnimationId = ?",
+            UpdateAnimationDuration             => "UPDATE Flo_Animation SET Duration = ? WHERE AnimationId = ?",
+            UpdateAnimationFrameLength          => "UPDATE Flo_Animation SET Frame_Length_ns = ? WHERE AnimationId = ?",
+            UpdateLayerVisible                  => "UPDATE Flo_LayerProperties SET Visible = ? WHERE LayerId = ?",
+            UpdateLayerLocked                   => "UPDATE Flo_LayerProperties SET Locked = ? WHERE LayerId = ?",
+            UpdateLayerOpacity                  => "UPDATE Flo_LayerProperties SET Opacity = ? WHERE LayerId = ?",
+            UpdateLayerBlendMode                => "UPDATE Flo_LayerProperties SET BlendMode = ? WHERE LayerId = ?",
             UpdateMotionType                    => "UPDATE Flo_Motion SET MotionType = ? WHERE MotionId = ?",
             UpdateBrushPoint                    => "UPDATE Flo_BrushPoint SET X1 = ?, Y1 = ?, X2 = ?, Y2 = ?, X3 = ?, Y3 = ? WHERE ElementId = ? AND PointId = ?",
             UpdatePathPoint                     => "UPDATE Flo_PathPoints SET X = ?, Y = ? WHERE PathId = ? AND PointIndex = ?",
+            UpdateTextPosition                  => "UPDATE Flo_TextElement SET X = ?, Y = ? WHERE ElementId = ?",
+            UpdateImageTransform                => "UPDATE Flo_ImageElement SET A = ?, B = ?, C = ?, D = ?, E = ?, F = ? WHERE ElementId = ?",
             UpdateMoveZIndexUpwards             => "UPDATE Flo_VectorElementOrdering SET ZIndex = ZIndex + 1 WHERE KeyFrameId = ? AND ZIndex >= ?",
             UpdateMoveZIndexDownwards           => "UPDATE Flo_VectorElementOrdering SET ZIndex = ZIndex - 1 WHERE KeyFrameId = ? AND ZIndex >= ?",
             UpdatePathPointIndicesAfter         => "UPDATE Flo_PathPoints SET PointIndex = PointIndex + ? WHERE PathId = ? AND PointIndex >= ?",
@@ -470,6 +697,7 @@ impl FloSqlite {
             InsertELBrushProperties             => "INSERT INTO Flo_EL_BrushProperties (EditId, BrushProperties) VALUES (?, ?)",
             InsertELElementId                   => "INSERT INTO Flo_EL_ElementIds (EditId, ElementIndex, ElementId) VALUES (?, ?, ?)",
             InsertELRawPoints                   => "INSERT INTO Flo_EL_RawPoints (EditId, Points) VALUES (?, ?)",
+            InsertELBrushPoints                 => "INSERT INTO Flo_EL_BrushPoints (EditId, Points) VALUES (?, ?)",
             InsertELMotionOrigin                => "INSERT INTO Flo_EL_MotionOrigin (EditId, X, Y) VALUES (?, ?, ?)",
             InsertELMotionType                  => "INSERT INTO Flo_EL_MotionType (EditId, MotionType) VALUES (?, ?)",
             InsertELMotionElement               => "INSERT INTO Flo_EL_MotionAttach (EditId, AttachedElement) VALUES (?, ?)",
@@ -478,19 +706,28 @@ impl FloSqlite {
             InsertELString                      => "INSERT INTO Flo_EL_StringParameters (EditId, StringIndex, String) VALUES (?, ?, ?)",
             InsertELInt                         => "INSERT INTO Flo_EL_IntParameters (EditId, IntIndex, Value) VALUES (?, ?, ?)",
             InsertELFloat                       => "INSERT INTO Flo_EL_FloatParameters (EditId, FloatIndex, Value) VALUES (?, ?, ?)",
+            InsertELColor                       => "INSERT INTO Flo_EL_Color (EditId, Color) VALUES (?, ?)",
+            InsertELImage                       => "INSERT INTO Flo_EL_Image (EditId, ImageId) VALUES (?, ?)",
+            InsertImage                         => "INSERT INTO Flo_Image (ImageId, Data) VALUES (NULL, ?)",
             InsertPath                          => "INSERT INTO Flo_Path (PathId) VALUES (NULL)",
             InsertPathPoint                     => "INSERT INTO Flo_PathPoints (PathId, PointIndex, X, Y) VALUES (?, ?, ?, ?)",
             InsertPathPointType                 => "INSERT INTO Flo_PathPointType (PathId, PointIndex, Type) VALUES (?, ?, ?)",
             InsertTimePoint                     => "INSERT INTO Flo_TimePoint (X, Y, Milliseconds) VALUES (?, ?, ?)",
             InsertBrushType                     => "INSERT INTO Flo_Brush_Type (BrushType) VALUES (?)",
             InsertInkBrush                      => "INSERT INTO Flo_Brush_Ink (Brush, MinWidth, MaxWidth, ScaleUpDistance) VALUES (?, ?, ?, ?)",
+            InsertCalligraphyBrush              => "INSERT INTO Flo_Brush_Calligraphy (Brush, MinWidth, MaxWidth, NibAngle, NibAspectRatio, TiltInfluence) VALUES (?, ?, ?, ?, ?, ?)",
+            InsertInkPressure                   => "INSERT INTO Flo_Brush_InkPressure (Brush, PressureSource, CurveType, Cp1X, Cp1Y, Cp2X, Cp2Y) VALUES (?, ?, ?, ?, ?, ?, ?)",
             InsertBrushProperties               => "INSERT INTO Flo_BrushProperties (Size, Opacity, Color) VALUES (?, ?, ?)",
+            InsertBrushGradient                 => "INSERT INTO Flo_BrushGradient (BrushProperties, GradientType, EndColor, Angle) VALUES (?, ?, ?, ?)",
             InsertColorType                     => "INSERT INTO Flo_Color_Type (ColorType) VALUES (?)",
             InsertRgb                           => "INSERT INTO Flo_Color_Rgb (Color, R, G, B) VALUES (?, ?, ?, ?)",
             InsertHsluv                         => "INSERT INTO Flo_Color_Hsluv (Color, H, S, L) VALUES (?, ?, ?, ?)",
             InsertLayerType                     => "INSERT INTO Flo_LayerType (LayerType) VALUES (?)",
             InsertAssignLayer                   => "INSERT INTO Flo_AnimationLayers (AnimationId, LayerId, AssignedLayerId) VALUES (?, ?, ?)",
             InsertOrReplaceLayerName            => "INSERT OR REPLACE INTO Flo_LayerName (LayerId, Name) VALUES (?, ?)",
+            InsertOrIgnoreLayerProperties       => "INSERT OR IGNORE INTO Flo_LayerProperties (LayerId) VALUES (?)",
+            InsertOrReplaceLayerGroup           => "INSERT OR REPLACE INTO Flo_LayerGroup (LayerId, ParentLayerId) VALUES (?, ?)",
+            InsertOrReplaceLayerOrdering        => "INSERT OR REPLACE INTO Flo_LayerOrdering (LayerId, OrderIndex) VALUES (?, ?)",
             InsertKeyFrame                      => "INSERT INTO Flo_LayerKeyFrame (LayerId, AtTime) VALUES (?, ?)",
             InsertVectorElementType             => "INSERT INTO Flo_VectorElement (VectorElementType) VALUES (?)",
             InsertOrReplaceVectorElementTime    => "INSERT OR REPLACE INTO Flo_VectorElementTime (ElementId, KeyFrameId, AtTime) VALUES (?, ?, ?)",
@@ -506,9 +743,14 @@ impl FloSqlite {
             InsertMotionPathPoint               => "INSERT INTO Flo_MotionPath (MotionId, PathType, PointIndex, PointId) VALUES (?, ?, ?, ?)",
             InsertNewCachedDrawing              => "INSERT INTO Flo_CachedDrawings (Drawing) VALUES (?)",
             InsertOrReplaceLayerCache           => "INSERT OR REPLACE INTO Flo_LayerCache (CacheType, LayerId, CacheTime, CacheId) VALUES (?, ?, ?, ?)",
+            InsertGroupElement                  => "INSERT INTO Flo_GroupElement (ElementId, GroupType) VALUES (?, ?)",
+            InsertGroupMember                   => "INSERT INTO Flo_GroupMember (GroupElementId, MemberIndex, ElementId) VALUES (?, ?, ?)",
+            InsertTextElement                   => "INSERT INTO Flo_TextElement (ElementId, Color, FontFamily, FontSize, Alignment, X, Y, Text) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            InsertImageElement                  => "INSERT INTO Flo_ImageElement (ElementId, ImageId, A, B, C, D, E, F) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
 
             DeleteKeyFrame                      => "DELETE FROM Flo_LayerKeyFrame WHERE LayerId = ? AND AtTime = ?",
             DeleteLayer                         => "DELETE FROM Flo_LayerType WHERE LayerId = ?",
+            DeleteLayerGroup                    => "DELETE FROM Flo_LayerGroup WHERE LayerId = ?",
             DeleteElementZIndex                 => "DELETE FROM Flo_VectorElementOrdering WHERE ElementId = ?",
             DeleteElementAttachment             => "DELETE FROM Flo_ElementAttachments WHERE ElementId = ? AND AttachedElementId = ?",
             DeleteMotion                        => "DELETE FROM Flo_Motion WHERE MotionId = ?",
@@ -517,7 +759,8 @@ impl FloSqlite {
             DeletePathPointRange                => "DELETE FROM Flo_PathPoints WHERE PathId = ? AND PointIndex >= ? AND PointIndex < ?",
             DeletePathPointTypeRange            => "DELETE FROM Flo_PathPointType WHERE PathId = ? AND PointIndex >= ? AND PointIndex < ?",
             DeleteVectorElement                 => "DELETE FROM Flo_VectorElement WHERE ElementId = ?",
-            DeleteVectorElementTime             => "DELETE FROM Flo_VectorElementTime WHERE ElementId = ?"
+            DeleteVectorElementTime             => "DELETE FROM Flo_VectorElementTime WHERE ElementId = ?",
+            DeleteGroupMembers                  => "DELETE FROM Flo_GroupMember WHERE GroupElementId = ?"
         }
     }
 
@@ -43,6 +43,56 @@ impl FloQuery for FloSqlite {
         self.query_row(FloStatement::SelectLayerIdAndName, &[&animation_id, &(assigned_id as i64)], |row| Ok((row.get(0)?, row.get(2)?)))
     }
 
+    ///
+    /// Retrieves the visibility, locked state, opacity and blend mode for the layer with the specified (real) ID
+    ///
+    fn query_layer_properties(&mut self, layer_id: i64) -> Result<LayerPropertiesEntry, SqliteAnimationError> {
+        self.query_row(FloStatement::SelectLayerProperties, &[&layer_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .map(|(visible, locked, opacity, blend_mode)| {
+                let blend_mode: Option<i64> = blend_mode;
+                let blend_mode              = self.value_for_enum(DbEnumType::LayerBlend, blend_mode).and_then(|blend_mode| blend_mode.layer_blend());
+
+                LayerPropertiesEntry {
+                    visible:    visible,
+                    locked:     locked,
+                    opacity:    opacity,
+                    blend_mode: blend_mode.unwrap_or(LayerBlendType::SourceOver)
+                }
+            })
+    }
+
+    ///
+    /// Retrieves the type of the layer with the specified (real) ID
+    ///
+    fn query_layer_type(&mut self, layer_id: i64) -> Result<LayerType, SqliteAnimationError> {
+        let layer_type: i64 = self.query_row(FloStatement::SelectLayerType, &[&layer_id], |row| row.get(0))?;
+        let layer_type      = self.value_for_enum(DbEnumType::Layer, Some(layer_type)).and_then(|layer_type| layer_type.layer());
+
+        Ok(layer_type.unwrap_or(LayerType::Vector))
+    }
+
+    ///
+    /// Retrieves the real and assigned IDs of the group layer containing the layer with the specified (real) ID, if it's in a group
+    ///
+    fn query_parent_layer_group(&mut self, layer_id: i64) -> Result<Option<(i64, u64)>, SqliteAnimationError> {
+        let parent = self.query_row(FloStatement::SelectParentLayerGroup, &[&layer_id], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as u64)));
+
+        match parent {
+            Err(SqliteAnimationError::QueryReturnedNoRows)  => Ok(None),
+            other                                           => Ok(Some(other?))
+        }
+    }
+
+    ///
+    /// Retrieves the assigned IDs of the layers that are directly contained in the group layer with the specified (real) ID
+    ///
+    fn query_child_layer_ids(&mut self, layer_id: i64) -> Result<Vec<u64>, SqliteAnimationError> {
+        let rows    = self.query_map(FloStatement::SelectChildLayers, &[&layer_id], |row| { Ok(row.get::<_, i64>(0)? as u64) })?;
+        let rows    = rows.map(|row| row.unwrap());
+
+        Ok(rows.collect())
+    }
+
     ///
     /// Returns an iterator over the key frame times for a particular layer ID
     ///
@@ -144,6 +194,13 @@ impl FloQuery for FloSqlite {
         self.query_row(FloStatement::SelectEditLogLength, &[], |row| row.get(0))
     }
 
+    ///
+    /// Retrieves the number of edits that have been removed from the edit log by compacting the animation
+    ///
+    fn query_discarded_edit_count(&mut self) -> Result<i64, SqliteAnimationError> {
+        self.query_row(FloStatement::SelectDiscardedEditCount, &[], |row| row.get(0))
+    }
+
     ///
     /// Retrieves a set of values from the edit log
     ///
@@ -216,6 +273,19 @@ impl FloQuery for FloSqlite {
         Ok(read_raw_points(&mut point_bytes).unwrap())
     }
 
+    ///
+    /// Retrieves the processed brush points associated with a particular edit ID
+    ///
+    fn query_edit_log_brush_points(&mut self, edit_id: i64) -> Result<Vec<BrushPoint>, SqliteAnimationError> {
+        let point_bytes = self.query_row(FloStatement::SelectEditLogBrushPoints, &[&edit_id], |row| {
+            let point_bytes: Vec<_> = row.get(0)?;
+            Ok(point_bytes)
+        })?;
+
+        let mut point_bytes: &[u8]  = &point_bytes;
+        Ok(read_brush_points(&mut point_bytes).unwrap())
+    }
+
     ///
     /// Retrieves the ID of the path associated with the specified edit ID
     ///
@@ -225,6 +295,35 @@ impl FloQuery for FloSqlite {
         })
     }
 
+    ///
+    /// Retrieves the element IDs associated with the specified edit ID, in index order
+    ///
+    fn query_edit_log_element_ids(&mut self, edit_id: i64) -> Result<Vec<i64>, SqliteAnimationError> {
+        let element_ids = self.query_map(FloStatement::SelectEditLogElementIds, &[&edit_id], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(element_ids)
+    }
+
+    ///
+    /// Retrieves the time points of the motion curve associated with the specified edit ID
+    ///
+    fn query_edit_log_motion_timepoints(&mut self, edit_id: i64) -> Result<Vec<TimePointEntry>, SqliteAnimationError> {
+        let result = self.query_map(FloStatement::SelectEditLogMotionTimePoints, &[&edit_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .map(|row_with_error| row_with_error.unwrap())
+            .map(|(x, y, millis): (f64, f64, f64)| {
+                TimePointEntry {
+                    x:              x as f32,
+                    y:              y as f32,
+                    milliseconds:   millis as f32
+                }
+            })
+            .collect();
+
+        Ok(result)
+    }
+
     ///
     /// Retrieves the string associated with a specific edit ID
     ///
@@ -235,6 +334,88 @@ impl FloQuery for FloSqlite {
         })
     }
 
+    ///
+    /// Retrieves the integer associated with a specific edit ID
+    ///
+    fn query_edit_log_int(&mut self, edit_id: i64, int_index: u32) -> Result<i64, SqliteAnimationError> {
+        let int_index = int_index as i64;
+        self.query_row(FloStatement::SelectEditLogInt, &[&edit_id, &int_index], |row| {
+            row.get(0)
+        })
+    }
+
+    ///
+    /// Retrieves the floating point value associated with a specific edit ID
+    ///
+    fn query_edit_log_float(&mut self, edit_id: i64, float_index: u32) -> Result<f64, SqliteAnimationError> {
+        let float_index = float_index as i64;
+        self.query_row(FloStatement::SelectEditLogFloat, &[&edit_id, &float_index], |row| {
+            row.get(0)
+        })
+    }
+
+    ///
+    /// Retrieves the layer blend mode stored as an integer value for a specific edit ID
+    ///
+    fn query_edit_log_layer_blend(&mut self, edit_id: i64, int_index: u32) -> Result<LayerBlendType, SqliteAnimationError> {
+        let blend_mode = self.query_edit_log_int(edit_id, int_index)?;
+        let blend_mode = self.value_for_enum(DbEnumType::LayerBlend, Some(blend_mode)).and_then(|blend_mode| blend_mode.layer_blend());
+
+        Ok(blend_mode.unwrap_or(LayerBlendType::SourceOver))
+    }
+
+    ///
+    /// Retrieves the group type stored as an integer value for a specific edit ID
+    ///
+    fn query_edit_log_group_type(&mut self, edit_id: i64, int_index: u32) -> Result<GroupType, SqliteAnimationError> {
+        let group_type = self.query_edit_log_int(edit_id, int_index)?;
+        let group_type = self.value_for_enum(DbEnumType::GroupType, Some(group_type)).and_then(|group_type| group_type.group_type());
+
+        Ok(group_type.unwrap_or(GroupType::Normal))
+    }
+
+    ///
+    /// Retrieves the text alignment stored as an integer value for a specific edit ID
+    ///
+    fn query_edit_log_text_alignment(&mut self, edit_id: i64, int_index: u32) -> Result<TextAlignmentType, SqliteAnimationError> {
+        let alignment = self.query_edit_log_int(edit_id, int_index)?;
+        let alignment = self.value_for_enum(DbEnumType::TextAlignment, Some(alignment)).and_then(|alignment| alignment.text_alignment());
+
+        Ok(alignment.unwrap_or(TextAlignmentType::Left))
+    }
+
+    ///
+    /// Retrieves the ID of the colour associated with a specific edit ID
+    ///
+    fn query_edit_log_color_id(&mut self, edit_id: i64) -> Result<i64, SqliteAnimationError> {
+        self.query_row(FloStatement::SelectEditLogColor, &[&edit_id], |row| row.get(0))
+    }
+
+    ///
+    /// Retrieves the encoded image data associated with a specific edit ID
+    ///
+    fn query_edit_log_image(&mut self, edit_id: i64) -> Result<Vec<u8>, SqliteAnimationError> {
+        self.query_row(FloStatement::SelectEditLogImage, &[&edit_id], |row| row.get(0))
+    }
+
+    ///
+    /// Retrieves the motion type associated with a specific edit ID
+    ///
+    fn query_edit_log_motion_type(&mut self, edit_id: i64) -> Result<MotionType, SqliteAnimationError> {
+        let motion_type = self.query_row(FloStatement::SelectEditLogMotionType, &[&edit_id], |row| row.get::<_, i64>(0))?;
+        let motion_type = self.value_for_enum(DbEnumType::MotionType, Some(motion_type)).and_then(|motion_type| motion_type.motion_type());
+
+        Ok(motion_type.unwrap_or(MotionType::None))
+    }
+
+    ///
+    /// Retrieves the motion origin associated with a specific edit ID
+    ///
+    fn query_edit_log_motion_origin(&mut self, edit_id: i64) -> Result<(f32, f32), SqliteAnimationError> {
+        self.query_row(FloStatement::SelectEditLogMotionOrigin, &[&edit_id], |row| Ok((row.get::<_, f64>(0)?, row.get::<_, f64>(1)?)))
+            .map(|(x, y)| (x as f32, y as f32))
+    }
+
     ///
     /// Retrieves a colour with the specified ID
     ///
@@ -262,16 +443,41 @@ impl FloQuery for FloSqlite {
     /// Retrieves the brush with the specified ID
     ///
     fn query_brush(&mut self, brush_id: i64) -> Result<BrushEntry, SqliteAnimationError> {
-        self.query_row(FloStatement::SelectBrushDefinition, &[&brush_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
-            .map(|(brush_type, min_width, max_width, scale_up_distance)| {
+        self.query_row(FloStatement::SelectBrushDefinition, &[&brush_id], |row| Ok(((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?), (row.get(4)?, row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?), (row.get(9)?, row.get(10)?, row.get(11)?, row.get(12)?, row.get(13)?, row.get(14)?))))
+            .map(|((brush_type, min_width, max_width, scale_up_distance), (cal_min_width, cal_max_width, nib_angle, nib_aspect_ratio, tilt_influence), (pressure_source, curve_type, cp1_x, cp1_y, cp2_x, cp2_y))| {
                 let min_width: Option<f64>          = min_width;
                 let max_width: Option<f64>          = max_width;
                 let scale_up_distance: Option<f64>  = scale_up_distance;
+                let cal_min_width: Option<f64>      = cal_min_width;
+                let cal_max_width: Option<f64>      = cal_max_width;
+                let nib_angle: Option<f64>          = nib_angle;
+                let nib_aspect_ratio: Option<f64>   = nib_aspect_ratio;
+                let tilt_influence: Option<f64>     = tilt_influence;
+                let pressure_source: Option<i64>    = pressure_source;
+                let curve_type: Option<i64>         = curve_type;
+                let cp1_x: Option<f64>              = cp1_x;
+                let cp1_y: Option<f64>              = cp1_y;
+                let cp2_x: Option<f64>              = cp2_x;
+                let cp2_y: Option<f64>              = cp2_y;
                 let brush_type                      = self.value_for_enum(DbEnumType::BrushDefinition, Some(brush_type)).and_then(|brush_type| brush_type.brush_definition());
 
+                let calligraphy_defn = match (cal_min_width, cal_max_width, nib_angle, nib_aspect_ratio, tilt_influence) {
+                    (Some(min_width), Some(max_width), Some(nib_angle), Some(nib_aspect_ratio), Some(tilt_influence))   => Some((min_width, max_width, nib_angle, nib_aspect_ratio, tilt_influence)),
+                    _                                                                                                   => None
+                };
+
+                let pressure_source = self.value_for_enum(DbEnumType::PressureSource, pressure_source).and_then(|pressure_source| pressure_source.pressure_source());
+                let curve_type      = self.value_for_enum(DbEnumType::PressureCurve, curve_type).and_then(|curve_type| curve_type.pressure_curve());
+                let ink_pressure    = match (pressure_source, curve_type, cp1_x, cp1_y, cp2_x, cp2_y) {
+                    (Some(pressure_source), Some(curve_type), Some(cp1_x), Some(cp1_y), Some(cp2_x), Some(cp2_y))   => Some((pressure_source, curve_type, (cp1_x, cp1_y), (cp2_x, cp2_y))),
+                    _                                                                                               => None
+                };
+
                 BrushEntry {
-                    brush_type: brush_type.unwrap(),
-                    ink_defn:   min_width.and_then(|min_width| max_width.map(|max_width| (min_width, max_width))).and_then(|(min_width, max_width)| scale_up_distance.map(|scale_up| (min_width, max_width, scale_up)))
+                    brush_type:         brush_type.unwrap(),
+                    ink_defn:           min_width.and_then(|min_width| max_width.map(|max_width| (min_width, max_width))).and_then(|(min_width, max_width)| scale_up_distance.map(|scale_up| (min_width, max_width, scale_up))),
+                    calligraphy_defn:   calligraphy_defn,
+                    ink_pressure:       ink_pressure
                 }
             })
     }
@@ -280,12 +486,21 @@ impl FloQuery for FloSqlite {
     /// Retrieves the brush properties with the specified ID
     ///
     fn query_brush_properties(&mut self, brush_properties_id: i64) -> Result<BrushPropertiesEntry, SqliteAnimationError> {
-        self.query_row(FloStatement::SelectBrushProperties, &[&brush_properties_id], |row| {
-            Ok(BrushPropertiesEntry {
-                size:       row.get(0)?,
-                opacity:    row.get(1)?,
-                color_id:   row.get(2)?
-            })
+        let (size, opacity, color_id, gradient_type, end_color_id, angle) = self.query_row(FloStatement::SelectBrushProperties, &[&brush_properties_id], |row| {
+            Ok((row.get::<_, f64>(0)?, row.get::<_, f64>(1)?, row.get::<_, i64>(2)?, row.get::<_, Option<i64>>(3)?, row.get::<_, Option<i64>>(4)?, row.get::<_, Option<f64>>(5)?))
+        })?;
+
+        let gradient_type   = self.value_for_enum(DbEnumType::BrushGradient, gradient_type).and_then(|gradient_type| gradient_type.brush_gradient());
+        let gradient        = match (gradient_type, end_color_id, angle) {
+            (Some(gradient_type), Some(end_color_id), Some(angle))  => Some((gradient_type, end_color_id, angle)),
+            _                                                       => None
+        };
+
+        Ok(BrushPropertiesEntry {
+            size:       size,
+            opacity:    opacity,
+            color_id:   color_id,
+            gradient:   gradient
         })
     }
 
@@ -508,6 +723,80 @@ impl FloQuery for FloSqlite {
             .collect())
     }
 
+    ///
+    /// Queries a group element and the IDs of the elements it contains (in order)
+    ///
+    fn query_group_element(&mut self, element_id: i64) -> Result<Option<GroupElementEntry>, SqliteAnimationError> {
+        // Elements without a group type are not groups
+        let group_type = match self.query_row(FloStatement::SelectGroupType, &[&element_id], |row| row.get::<_, i64>(0)) {
+            Ok(group_type)                                  => group_type,
+            Err(SqliteAnimationError::QueryReturnedNoRows)  => { return Ok(None); },
+            Err(err)                                        => { return Err(err); }
+        };
+        let group_type = self.value_for_enum(DbEnumType::GroupType, Some(group_type)).and_then(|group_type| group_type.group_type());
+
+        // Fetch the members of the group
+        let member_ids = self.query_map(FloStatement::SelectGroupMembers, &[&element_id], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(group_type.map(|group_type| GroupElementEntry {
+            element_id,
+            group_type,
+            member_ids
+        }))
+    }
+
+    ///
+    /// Queries a text element
+    ///
+    fn query_text_element(&mut self, element_id: i64) -> Result<Option<TextElementEntry>, SqliteAnimationError> {
+        let entry = self.query_row(FloStatement::SelectTextElement, &[&element_id],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, f64>(2)?, row.get::<_, i64>(3)?, row.get::<_, f64>(4)?, row.get::<_, f64>(5)?, row.get::<_, String>(6)?)));
+
+        // Elements without an entry in the text table are not text elements
+        let entry = match entry {
+            Ok(entry)                                       => entry,
+            Err(SqliteAnimationError::QueryReturnedNoRows)  => { return Ok(None); },
+            Err(err)                                        => { return Err(err); }
+        };
+
+        let (color_id, font_family, font_size, alignment, x, y, text) = entry;
+        let alignment = self.value_for_enum(DbEnumType::TextAlignment, Some(alignment)).and_then(|alignment| alignment.text_alignment());
+
+        Ok(Some(TextElementEntry {
+            element_id,
+            color_id,
+            font_family,
+            font_size:  font_size as f32,
+            alignment:  alignment.unwrap_or(TextAlignmentType::Left),
+            position:   (x as f32, y as f32),
+            text
+        }))
+    }
+
+    ///
+    /// Queries an image element
+    ///
+    fn query_image_element(&mut self, element_id: i64) -> Result<Option<ImageElementEntry>, SqliteAnimationError> {
+        let entry = self.query_row(FloStatement::SelectImageElement, &[&element_id],
+            |row| Ok((row.get::<_, Vec<u8>>(0)?, row.get::<_, f64>(1)?, row.get::<_, f64>(2)?, row.get::<_, f64>(3)?, row.get::<_, f64>(4)?, row.get::<_, f64>(5)?, row.get::<_, f64>(6)?)));
+
+        // Elements without an entry in the image table are not image elements
+        let entry = match entry {
+            Ok(entry)                                       => entry,
+            Err(SqliteAnimationError::QueryReturnedNoRows)  => { return Ok(None); },
+            Err(err)                                        => { return Err(err); }
+        };
+
+        let (data, a, b, c, d, e, f) = entry;
+
+        Ok(Some(ImageElementEntry {
+            element_id,
+            data,
+            transform:  Transform2D((a as f32, b as f32, c as f32), (d as f32, e as f32, f as f32), (0.0, 0.0, 1.0))
+        }))
+    }
+
     ///
     /// Queries a path element
     ///
@@ -592,6 +881,17 @@ impl FloQuery for FloSqlite {
         Ok(components)
     }
 
+    ///
+    /// Queries the control points that make up a path
+    ///
+    fn query_path_points(&mut self, path_id: i64) -> Result<Vec<(f32, f32)>, SqliteAnimationError> {
+        let points = self.query_map(FloStatement::SelectPathPoints, &[&path_id], |row| Ok((row.get::<_, f64>(0)?, row.get::<_, f64>(1)?)))?
+            .map(|row| row.map(|(x, y)| (x as f32, y as f32)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(points)
+    }
+
     ///
     /// Queries the motion associated with a particular motion ID
     ///
@@ -1,8 +1,22 @@
 use super::*;
 
 use flo_animation::*;
+use flo_canvas::Transform2D;
 
 impl FloSqlite {
+    ///
+    /// Sets one of the values in the properties table for a layer, creating the properties for that layer if they don't already exist
+    ///
+    fn update_layer_property(&mut self, layer_id: i64, statement: FloStatement, value: &dyn ToSql) -> Result<(), SqliteAnimationError> {
+        let mut insert_properties   = Self::prepare(&self.sqlite, FloStatement::InsertOrIgnoreLayerProperties)?;
+        insert_properties.execute::<&[&dyn ToSql]>(&[&layer_id])?;
+
+        let mut update_property     = Self::prepare(&self.sqlite, statement)?;
+        update_property.execute::<&[&dyn ToSql]>(&[value, &layer_id])?;
+
+        Ok(())
+    }
+
     ///
     /// Executes a particular database update
     ///
@@ -31,6 +45,16 @@ impl FloSqlite {
                 update_size.execute::<&[&dyn ToSql]>(&[&width, &height, &self.animation_id])?;
             },
 
+            UpdateDuration(duration)                                        => {
+                let mut update_duration = Self::prepare(&self.sqlite, FloStatement::UpdateAnimationDuration)?;
+                update_duration.execute::<&[&dyn ToSql]>(&[&Self::get_micros(duration), &self.animation_id])?;
+            },
+
+            UpdateFrameLength(frame_length)                                 => {
+                let mut update_frame_length = Self::prepare(&self.sqlite, FloStatement::UpdateAnimationFrameLength)?;
+                update_frame_length.execute::<&[&dyn ToSql]>(&[&Self::get_nanos(frame_length), &self.animation_id])?;
+            },
+
             PushEditType(edit_log_type)                                     => {
                 let edit_log_type   = self.enum_value(DbEnum::EditLog(*edit_log_type));
                 let edit_log_id     = Self::prepare(&self.sqlite, FloStatement::InsertEditType)?.insert::<&[&dyn ToSql]>(&[&edit_log_type])?;
@@ -72,6 +96,13 @@ impl FloSqlite {
                 insert_edit_string.insert::<&[&dyn ToSql]>(&[&edit_log_id, &index, string])?;
             },
 
+            PushEditLogString(index, string)                                => {
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut insert_edit_string  = Self::prepare(&self.sqlite, FloStatement::InsertELString)?;
+                let index                   = *index as i64;
+                insert_edit_string.insert::<&[&dyn ToSql]>(&[edit_log_id, &index, string])?;
+            },
+
             PushEditLogInt(index, value)                                    => {
                 let edit_log_id             = self.stack.last().unwrap();
                 let mut insert_edit_int     = Self::prepare(&self.sqlite, FloStatement::InsertELInt)?;
@@ -86,6 +117,44 @@ impl FloSqlite {
                 insert_edit_int.insert::<&[&dyn ToSql]>(&[&edit_log_id, &index, value])?;
             },
 
+            PushEditLogLayerBlend(index, blend_mode)                        => {
+                let blend_mode              = self.enum_value(DbEnum::LayerBlend(*blend_mode));
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut insert_edit_int     = Self::prepare(&self.sqlite, FloStatement::InsertELInt)?;
+                let index                   = *index as i64;
+                insert_edit_int.insert::<&[&dyn ToSql]>(&[&edit_log_id, &index, &blend_mode])?;
+            },
+
+            PushEditLogGroupType(index, group_type)                         => {
+                let group_type              = self.enum_value(DbEnum::GroupType(*group_type));
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut insert_edit_int     = Self::prepare(&self.sqlite, FloStatement::InsertELInt)?;
+                let index                   = *index as i64;
+                insert_edit_int.insert::<&[&dyn ToSql]>(&[&edit_log_id, &index, &group_type])?;
+            },
+
+            PushEditLogTextAlignment(index, alignment)                      => {
+                let alignment               = self.enum_value(DbEnum::TextAlignment(*alignment));
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut insert_edit_int     = Self::prepare(&self.sqlite, FloStatement::InsertELInt)?;
+                let index                   = *index as i64;
+                insert_edit_int.insert::<&[&dyn ToSql]>(&[&edit_log_id, &index, &alignment])?;
+            },
+
+            PushEditLogColor                                                => {
+                let color_id                = self.stack.pop().unwrap();
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut insert_edit_color   = Self::prepare(&self.sqlite, FloStatement::InsertELColor)?;
+                insert_edit_color.insert::<&[&dyn ToSql]>(&[edit_log_id, &color_id])?;
+            },
+
+            PushEditLogImage                                                => {
+                let image_id                = self.stack.pop().unwrap();
+                let edit_log_id             = self.stack.last().unwrap();
+                let mut insert_edit_image   = Self::prepare(&self.sqlite, FloStatement::InsertELImage)?;
+                insert_edit_image.insert::<&[&dyn ToSql]>(&[edit_log_id, &image_id])?;
+            },
+
             PopEditLogBrushProperties                                       => {
                 let brush_props_id      = self.stack.pop().unwrap();
                 let edit_log_id         = self.stack.pop().unwrap();
@@ -110,6 +179,15 @@ impl FloSqlite {
                 add_raw_point.insert::<&[&dyn ToSql]>(&[edit_log_id, &point_bytes])?;
             },
 
+            PushEditLogBrushPoints(points)                                  => {
+                let edit_log_id         = self.stack.last().unwrap();
+                let mut add_points      = Self::prepare(&self.sqlite, FloStatement::InsertELBrushPoints)?;
+                let mut point_bytes     = vec![];
+
+                write_brush_points(&mut point_bytes, &*points).unwrap();
+                add_points.insert::<&[&dyn ToSql]>(&[edit_log_id, &point_bytes])?;
+            },
+
             PushEditLogMotionOrigin(x, y) => {
                 let (x, y)          = (*x as f64, *y as f64);
                 let edit_log_id     = self.stack.last().unwrap();
@@ -179,6 +257,13 @@ impl FloSqlite {
                 self.stack.push(path_id);
             },
 
+            PushImage(data) => {
+                let mut insert_image    = Self::prepare(&self.sqlite, FloStatement::InsertImage)?;
+                let image_id            = insert_image.insert::<&[&dyn ToSql]>(&[&**data])?;
+
+                self.stack.push(image_id as i64);
+            },
+
             PushPathComponents(components) => {
                 let point_move_to       = self.enum_value(DbEnum::PathPoint(PathPointType::MoveTo));
                 let point_line_to       = self.enum_value(DbEnum::PathPoint(PathPointType::LineTo));
@@ -356,6 +441,20 @@ impl FloSqlite {
                 insert_ink_brush.insert::<&[&dyn ToSql]>(&[brush_id, &(*min_width as f64), &(*max_width as f64), &(*scale_up_distance as f64)])?;
             },
 
+            PushCalligraphyBrush(min_width, max_width, nib_angle, nib_aspect_ratio, tilt_influence) => {
+                let brush_id                        = self.stack.last().unwrap();
+                let mut insert_calligraphy_brush    = Self::prepare(&self.sqlite, FloStatement::InsertCalligraphyBrush)?;
+                insert_calligraphy_brush.insert::<&[&dyn ToSql]>(&[brush_id, &(*min_width as f64), &(*max_width as f64), &(*nib_angle as f64), &(*nib_aspect_ratio as f64), &(*tilt_influence as f64)])?;
+            },
+
+            PushInkPressure(pressure_source, curve_type, (cp1_x, cp1_y), (cp2_x, cp2_y)) => {
+                let brush_id                = self.stack.last().unwrap();
+                let pressure_source         = self.enum_value(DbEnum::PressureSource(*pressure_source));
+                let curve_type              = self.enum_value(DbEnum::PressureCurve(*curve_type));
+                let mut insert_ink_pressure = Self::prepare(&self.sqlite, FloStatement::InsertInkPressure)?;
+                insert_ink_pressure.insert::<&[&dyn ToSql]>(&[brush_id, &pressure_source, &curve_type, &(*cp1_x as f64), &(*cp1_y as f64), &(*cp2_x as f64), &(*cp2_y as f64)])?;
+            },
+
             PushBrushProperties(size, opacity)                              => {
                 let color_id                    = self.stack.pop().unwrap();
                 let mut insert_brush_properties = Self::prepare(&self.sqlite, FloStatement::InsertBrushProperties)?;
@@ -363,6 +462,14 @@ impl FloSqlite {
                 self.stack.push(brush_props_id);
             },
 
+            PushBrushGradient(gradient_type, angle)                         => {
+                let end_color_id                = self.stack.pop().unwrap();
+                let brush_props_id              = self.stack.last().unwrap();
+                let gradient_type               = self.enum_value(DbEnum::BrushGradient(*gradient_type));
+                let mut insert_brush_gradient   = Self::prepare(&self.sqlite, FloStatement::InsertBrushGradient)?;
+                insert_brush_gradient.insert::<&[&dyn ToSql]>(&[brush_props_id, &gradient_type, &end_color_id, &(*angle as f64)])?;
+            },
+
             PushColorType(color_type)                                       => {
                 let color_type              = self.enum_value(DbEnum::Color(*color_type));
                 let mut insert_color_type   = Self::prepare(&self.sqlite, FloStatement::InsertColorType)?;
@@ -411,6 +518,59 @@ impl FloSqlite {
                 insert_or_replace_name.insert::<&[&dyn ToSql]>(&[&layer_id, name])?;
             },
 
+            PopLayerVisible(visible)                                        => {
+                let layer_id                    = self.stack.pop().unwrap();
+                self.update_layer_property(layer_id, FloStatement::UpdateLayerVisible, visible)?;
+            },
+
+            PopLayerLocked(locked)                                          => {
+                let layer_id                    = self.stack.pop().unwrap();
+                self.update_layer_property(layer_id, FloStatement::UpdateLayerLocked, locked)?;
+            },
+
+            PopLayerOpacity(opacity)                                        => {
+                let layer_id                    = self.stack.pop().unwrap();
+                self.update_layer_property(layer_id, FloStatement::UpdateLayerOpacity, opacity)?;
+            },
+
+            PopLayerBlendMode(blend_mode)                                   => {
+                let layer_id                    = self.stack.pop().unwrap();
+                let blend_mode                  = self.enum_value(DbEnum::LayerBlend(*blend_mode));
+                self.update_layer_property(layer_id, FloStatement::UpdateLayerBlendMode, &blend_mode)?;
+            },
+
+            PopLayerParentGroup(Some(parent_layer_id))                      => {
+                let layer_id                    = self.stack.pop().unwrap();
+                let mut insert_layer_group      = Self::prepare(&self.sqlite, FloStatement::InsertOrReplaceLayerGroup)?;
+                insert_layer_group.insert::<&[&dyn ToSql]>(&[&layer_id, parent_layer_id])?;
+            },
+
+            PopLayerParentGroup(None)                                       => {
+                let layer_id                    = self.stack.pop().unwrap();
+                let mut delete_layer_group      = Self::prepare(&self.sqlite, FloStatement::DeleteLayerGroup)?;
+                delete_layer_group.execute::<&[&dyn ToSql]>(&[&layer_id])?;
+            },
+
+            PopLayerOrdering(at_index)                                      => {
+                let layer_id                    = self.stack.pop().unwrap();
+
+                // Fetch the layers in their current order
+                let mut select_ordering         = Self::prepare(&self.sqlite, FloStatement::SelectLayerOrdering)?;
+                let mut layer_ids               = select_ordering.query_map(&[&self.animation_id], |row| row.get::<_, i64>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // Move the layer to its new position (or the end of the list if the index is past the end)
+                layer_ids.retain(|other_id| *other_id != layer_id);
+                let at_index                    = (*at_index as usize).min(layer_ids.len());
+                layer_ids.insert(at_index, layer_id);
+
+                // Store the new ordering
+                let mut insert_ordering         = Self::prepare(&self.sqlite, FloStatement::InsertOrReplaceLayerOrdering)?;
+                for (index, layer_id) in layer_ids.iter().enumerate() {
+                    insert_ordering.execute::<&[&dyn ToSql]>(&[layer_id, &(index as i64)])?;
+                }
+            },
+
             PushLayerForAssignedId(assigned_id)                             => {
                 let mut select_layer_id = Self::prepare(&self.sqlite, FloStatement::SelectLayerId)?;
                 let layer_id            = select_layer_id.query_row(&[&self.animation_id, &(*assigned_id as i64)], |row| row.get(0))?;
@@ -613,6 +773,21 @@ impl FloSqlite {
                 }
             },
 
+            UpdateTextPosition(x, y)                                        => {
+                let element_id              = self.stack.pop().unwrap();
+                let mut update_text         = Self::prepare(&self.sqlite, FloStatement::UpdateTextPosition)?;
+
+                update_text.execute::<&[&dyn ToSql]>(&[&(*x as f64), &(*y as f64), &element_id])?;
+            },
+
+            UpdateImageTransform(transform)                                 => {
+                let element_id              = self.stack.pop().unwrap();
+                let Transform2D((a, b, c), (d, e, f), _) = *transform;
+                let mut update_image        = Self::prepare(&self.sqlite, FloStatement::UpdateImageTransform)?;
+
+                update_image.execute::<&[&dyn ToSql]>(&[&(a as f64), &(b as f64), &(c as f64), &(d as f64), &(e as f64), &(f as f64), &element_id])?;
+            },
+
             PopVectorElementMove(move_direction)                            => {
                 // The stack contains the element ID and the keyframe ID
                 let element_id          = self.stack.pop().unwrap();
@@ -717,6 +892,111 @@ impl FloSqlite {
                 let element_id                      = self.stack.pop().unwrap();
                 let mut delete_element_from_frame   = Self::prepare(&self.sqlite, FloStatement::DeleteVectorElementTime)?;
                 delete_element_from_frame.execute(&[&element_id])?;
+            },
+
+            PopVectorGroupElement(group_type, num_elements)                 => {
+                // The grouped elements are on the stack in reverse order, followed by the group ID
+                let mut member_ids = vec![];
+                for _ in 0..*num_elements {
+                    member_ids.push(self.stack.pop().unwrap());
+                }
+                member_ids.reverse();
+                let group_id                = self.stack.pop().unwrap();
+
+                // Store the group type and the elements in the group
+                let group_type              = self.enum_value(DbEnum::GroupType(*group_type));
+                let mut insert_group        = Self::prepare(&self.sqlite, FloStatement::InsertGroupElement)?;
+                let mut insert_member       = Self::prepare(&self.sqlite, FloStatement::InsertGroupMember)?;
+
+                insert_group.insert::<&[&dyn ToSql]>(&[&group_id, &group_type])?;
+                for (index, member_id) in member_ids.iter().enumerate() {
+                    insert_member.insert::<&[&dyn ToSql]>(&[&group_id, &(index as i64), member_id])?;
+                }
+
+                // The group takes the place of the frontmost element, and appears once all of the elements have been drawn
+                let mut select_time         = Self::prepare(&self.sqlite, FloStatement::SelectElementKeyFrameAndTime)?;
+                let mut select_z_index      = Self::prepare(&self.sqlite, FloStatement::SelectZIndexForElement)?;
+                let mut delete_time         = Self::prepare(&self.sqlite, FloStatement::DeleteVectorElementTime)?;
+                let mut delete_z_index      = Self::prepare(&self.sqlite, FloStatement::DeleteElementZIndex)?;
+                let mut group_position      = None;
+                let mut group_time          = 0;
+
+                for member_id in member_ids.iter() {
+                    let (keyframe_id, when) = select_time.query_row(&[member_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+                    let z_index             = select_z_index.query_row(&[member_id], |row| row.get::<_, i64>(0))?;
+
+                    group_time              = group_time.max(when);
+                    group_position          = match group_position {
+                        Some((_, group_z_index)) if group_z_index > z_index => group_position,
+                        _                                                   => Some((keyframe_id, z_index))
+                    };
+
+                    // The element is no longer part of the frame
+                    delete_time.execute(&[member_id])?;
+                    delete_z_index.execute(&[member_id])?;
+                }
+
+                if let Some((keyframe_id, z_index)) = group_position {
+                    let mut insert_time     = Self::prepare(&self.sqlite, FloStatement::InsertOrReplaceVectorElementTime)?;
+                    let mut insert_z_index  = Self::prepare(&self.sqlite, FloStatement::InsertOrReplaceZIndex)?;
+
+                    insert_time.execute(&[&group_id, &keyframe_id, &group_time])?;
+                    insert_z_index.execute(&[&group_id, &keyframe_id, &z_index])?;
+                }
+            },
+
+            PopUngroupVectorElement                                         => {
+                let group_id                = self.stack.pop().unwrap();
+
+                // Fetch the elements in this group
+                let mut select_members      = Self::prepare(&self.sqlite, FloStatement::SelectGroupMembers)?;
+                let member_ids              = select_members.query_map(&[&group_id], |row| row.get::<_, i64>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                // The elements take the place of the group in its keyframe
+                let mut select_time         = Self::prepare(&self.sqlite, FloStatement::SelectElementKeyFrameAndTime)?;
+                let mut select_z_index      = Self::prepare(&self.sqlite, FloStatement::SelectZIndexForElement)?;
+                let (keyframe_id, when)     = select_time.query_row(&[&group_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?;
+                let z_index                 = select_z_index.query_row(&[&group_id], |row| row.get::<_, i64>(0))?;
+
+                let mut delete_z_index      = Self::prepare(&self.sqlite, FloStatement::DeleteElementZIndex)?;
+                let mut move_z_index_up     = Self::prepare(&self.sqlite, FloStatement::UpdateMoveZIndexUpwards)?;
+                let mut insert_time         = Self::prepare(&self.sqlite, FloStatement::InsertOrReplaceVectorElementTime)?;
+                let mut insert_z_index      = Self::prepare(&self.sqlite, FloStatement::InsertOrReplaceZIndex)?;
+
+                delete_z_index.execute(&[&group_id])?;
+                for (index, member_id) in member_ids.iter().enumerate() {
+                    let member_z_index = z_index + (index as i64);
+
+                    move_z_index_up.execute(&[&keyframe_id, &member_z_index])?;
+                    insert_time.execute(&[member_id, &keyframe_id, &when])?;
+                    insert_z_index.execute(&[member_id, &keyframe_id, &member_z_index])?;
+                }
+
+                // Remove the group (its elements are no longer members so they are left intact)
+                let mut delete_members      = Self::prepare(&self.sqlite, FloStatement::DeleteGroupMembers)?;
+                let mut delete_group        = Self::prepare(&self.sqlite, FloStatement::DeleteVectorElement)?;
+
+                delete_members.execute(&[&group_id])?;
+                delete_group.execute(&[&group_id])?;
+            },
+
+            PopVectorTextElement(font_family, font_size, alignment, (x, y), text) => {
+                let color_id                = self.stack.pop().unwrap();
+                let element_id              = self.stack.pop().unwrap();
+                let alignment               = self.enum_value(DbEnum::TextAlignment(*alignment));
+                let mut insert_text         = Self::prepare(&self.sqlite, FloStatement::InsertTextElement)?;
+
+                insert_text.insert::<&[&dyn ToSql]>(&[&element_id, &color_id, font_family, &(*font_size as f64), &alignment, &(*x as f64), &(*y as f64), text])?;
+            },
+
+            PopVectorImageElement(transform) => {
+                let image_id                = self.stack.pop().unwrap();
+                let element_id              = self.stack.pop().unwrap();
+                let Transform2D((a, b, c), (d, e, f), _) = *transform;
+                let mut insert_image        = Self::prepare(&self.sqlite, FloStatement::InsertImageElement)?;
+
+                insert_image.insert::<&[&dyn ToSql]>(&[&element_id, &image_id, &(a as f64), &(b as f64), &(c as f64), &(d as f64), &(e as f64), &(f as f64)])?;
             }
         }
 
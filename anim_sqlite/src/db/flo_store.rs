@@ -4,6 +4,7 @@ use super::motion_path_type::*;
 use super::super::error::*;
 
 use flo_animation::*;
+use flo_canvas::Transform2D;
 use std::sync::*;
 use std::ops::Range;
 use std::time::Duration;
@@ -39,6 +40,12 @@ pub enum DatabaseUpdate {
     /// Updates the canvas size of the animation
     UpdateCanvasSize(f64, f64),
 
+    /// Updates the duration of the animation
+    UpdateDuration(Duration),
+
+    /// Updates the length of a frame in the animation
+    UpdateFrameLength(Duration),
+
     /// Inserts a new edit log and pushes its ID
     PushEditType(EditLogType),
 
@@ -60,18 +67,39 @@ pub enum DatabaseUpdate {
     /// Pops an edit ID and associates a string value with it
     PopEditLogString(u32, String),
 
+    /// Uses the edit ID on top of the stack and associates a string value with it
+    PushEditLogString(u32, String),
+
     /// Uses the edit ID on top of the stack and sets an integer value (the parameters to this are the value index and the value itself). Edit log items can have arbitrary numbers of such parameters, the index generally counts from 0.
     PushEditLogInt(u32, i64),
 
     /// Uses the edit ID on top of the stack and sets a floating-point value (the parameters to this are the value index and the value itself)
     PushEditLogFloat(u32, f64),
 
+    /// Uses the edit ID on top of the stack and stores a layer blend mode as an integer value (the parameters to this are the value index and the blend mode)
+    PushEditLogLayerBlend(u32, LayerBlendType),
+
+    /// Uses the edit ID on top of the stack and stores a group type as an integer value (the parameters to this are the value index and the group type)
+    PushEditLogGroupType(u32, GroupType),
+
+    /// Uses the edit ID on top of the stack and stores a text alignment as an integer value (the parameters to this are the value index and the alignment)
+    PushEditLogTextAlignment(u32, TextAlignmentType),
+
+    /// Pops a colour ID and associates it with the edit ID that's on top of the stack after that
+    PushEditLogColor,
+
+    /// Pops an image ID and associates it with the edit ID that's on top of the stack after that
+    PushEditLogImage,
+
     /// Pops two things from the stack: a brush properties ID and an edit ID and inserts a brush properties edit
     PopEditLogBrushProperties,
 
     /// Uses the edit ID on top of the stack and inserts a raw point for it (index, position, pressure, tilt)
     PushRawPoints(Arc<Vec<RawPoint>>),
 
+    /// Uses the edit ID on top of the stack and associates a set of processed brush points with it
+    PushEditLogBrushPoints(Arc<Vec<BrushPoint>>),
+
     /// Uses the edit ID on top of the stack and associates a motion origin with it
     PushEditLogMotionOrigin(f32, f32),
 
@@ -90,6 +118,9 @@ pub enum DatabaseUpdate {
     /// Creates a new path from the specified points and pushes the ID
     PushPath(Vec<(f32, f32)>),
 
+    /// Stores the encoded data for an image and pushes its ID
+    PushImage(Arc<Vec<u8>>),
+
     /// Creates a path from the points in the list of path components and pushes the ID
     PushPathComponents(Arc<Vec<PathComponent>>),
 
@@ -113,9 +144,18 @@ pub enum DatabaseUpdate {
     /// Inserts an ink brush, leaving the brush ID on the stack
     PushInkBrush(f32, f32, f32),
 
+    /// Inserts a calligraphy brush (min width, max width, nib angle, nib aspect ratio, tilt influence), leaving the brush ID on the stack
+    PushCalligraphyBrush(f32, f32, f32, f32, f32),
+
+    /// Sets the pressure source and curve (with its control points) for the ink brush on top of the stack, leaving the brush ID on the stack
+    PushInkPressure(PressureSourceType, PressureCurveType, (f32, f32), (f32, f32)),
+
     /// Pops a colour ID and pushes brush properties with that colour and the specified size and opacity
     PushBrushProperties(f32, f32),
 
+    /// Pops a colour ID and uses it as the end colour of a gradient for the brush properties on top of the stack (with the specified angle)
+    PushBrushGradient(BrushGradientType, f32),
+
     /// Pushes a colour ID of the specified type
     PushColorType(ColorType),
 
@@ -143,6 +183,24 @@ pub enum DatabaseUpdate {
     /// Pops a layer ID and sets the name to the specified value for that layer
     PopLayerName(String),
 
+    /// Pops a layer ID and sets whether or not that layer is visible
+    PopLayerVisible(bool),
+
+    /// Pops a layer ID and sets whether or not that layer is locked
+    PopLayerLocked(bool),
+
+    /// Pops a layer ID and sets the opacity of that layer
+    PopLayerOpacity(f64),
+
+    /// Pops a layer ID and sets the blend mode for that layer
+    PopLayerBlendMode(LayerBlendType),
+
+    /// Pops a layer ID and moves it into the group layer with the specified (real) ID, or out of its group if the ID is None
+    PopLayerParentGroup(Option<i64>),
+
+    /// Pops a layer ID and moves it to the specified index in the ordering of the layers in the animation
+    PopLayerOrdering(u32),
+
     /// Adds a key frame to the layer with the ID on top of the stack
     PopAddKeyFrame(Duration),
 
@@ -201,6 +259,12 @@ pub enum DatabaseUpdate {
     /// Pops a path ID and updates the coordinates associated with it
     UpdatePathPointCoords(Arc<Vec<(f32, f32)>>),
 
+    /// Pops an element ID and moves the text element with that ID to a new position
+    UpdateTextPosition(f32, f32),
+
+    /// Pops an element ID and changes the transform used to draw the image element with that ID
+    UpdateImageTransform(Transform2D),
+
     /// Pops a path ID, a brush properties ID, a brush ID and a vector element ID and creates a path element from them
     PopVectorPathElement,
 
@@ -226,7 +290,22 @@ pub enum DatabaseUpdate {
     PopDeleteVectorElement,
 
     /// Removes the attachment between a vector element and its frame
-    PopDetachVectorElementFromFrame
+    PopDetachVectorElementFromFrame,
+
+    /// Pops the specified number of element IDs and then a group element ID. Makes the group element contain the popped
+    /// elements (in the order they were pushed) and replaces them in their keyframe with the group. The group is placed
+    /// at the position of the frontmost element.
+    PopVectorGroupElement(GroupType, usize),
+
+    /// Pops a group element ID, replaces it in its keyframe with the elements it contains and deletes the group
+    PopUngroupVectorElement,
+
+    /// Pops a colour ID and a vector element ID and creates a text element from them (the parameters are the font family,
+    /// font size, alignment, position and text)
+    PopVectorTextElement(String, f32, TextAlignmentType, (f32, f32), String),
+
+    /// Pops an image ID and a vector element ID and creates an image element from them, drawn using the specified transform
+    PopVectorImageElement(Transform2D)
 }
 
 ///
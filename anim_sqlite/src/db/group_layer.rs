@@ -0,0 +1,136 @@
+use super::*;
+use super::layer_cache::*;
+use super::vector_frame::*;
+use super::super::result::Result;
+
+use flo_canvas::BlendMode;
+
+use std::ops::{Range, Deref};
+use std::time::Duration;
+
+///
+/// Represents a group layer in a SQLite database
+///
+#[derive(Clone)]
+pub struct SqliteGroupLayer<TFile: FloFile+Send> {
+    /// The ID that was assigned to this layer
+    assigned_id: u64,
+
+    /// The ID of this layer
+    layer_id: i64,
+
+    /// The name of this layer, if it has one
+    name: Option<String>,
+
+    /// The visibility, locked state, opacity and blend mode for this layer
+    properties: LayerPropertiesEntry,
+
+    /// The assigned ID of the group containing this group, if there is one
+    parent_group: Option<u64>,
+
+    /// Database core
+    core: Arc<Desync<AnimationDbCore<TFile>>>
+}
+
+impl<TFile: FloFile+Send+'static> SqliteGroupLayer<TFile> {
+    ///
+    /// Retrieves a group layer for a particular ID
+    ///
+    pub fn from_assigned_id(core: &Arc<Desync<AnimationDbCore<TFile>>>, assigned_id: u64) -> Option<SqliteGroupLayer<TFile>> {
+        let layer: Result<_> = core.sync(|core| {
+            let (layer_id, name)    = core.db.query_layer_id_for_assigned_id(assigned_id)?;
+            let properties          = core.db.query_layer_properties(layer_id)?;
+            let parent_group        = core.db.query_parent_layer_group(layer_id)?;
+
+            Ok((layer_id, name, properties, parent_group))
+        });
+
+        layer.ok()
+            .map(|(layer_id, name, properties, parent_group)| {
+                SqliteGroupLayer {
+                    assigned_id:    assigned_id,
+                    layer_id:       layer_id,
+                    name:           name,
+                    properties:     properties,
+                    parent_group:   parent_group.map(|(_parent_id, assigned_parent_id)| assigned_parent_id),
+                    core:           Arc::clone(core)
+                }
+            })
+    }
+}
+
+impl<TFile: FloFile+Send+'static> Layer for SqliteGroupLayer<TFile> {
+    fn id(&self) -> u64 {
+        self.assigned_id
+    }
+
+    fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+
+    fn supported_edit_types(&self) -> Vec<LayerEditType> {
+        vec![LayerEditType::Group]
+    }
+
+    fn is_visible(&self) -> bool {
+        self.properties.visible
+    }
+
+    fn is_locked(&self) -> bool {
+        self.properties.locked
+    }
+
+    fn opacity(&self) -> f64 {
+        self.properties.opacity
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.properties.blend_mode.into()
+    }
+
+    fn parent_group(&self) -> Option<u64> {
+        self.parent_group
+    }
+
+    fn get_key_frames_during_time(&self, _when: Range<Duration>) -> Box<dyn Iterator<Item=Duration>> {
+        // Group layers have no content of their own, so they have no keyframes
+        Box::new(Vec::<Duration>::new().into_iter())
+    }
+
+    fn as_vector_layer<'a>(&'a self) -> Option<Box<dyn 'a+Deref<Target=dyn 'a+VectorLayer>>> {
+        None
+    }
+
+    fn as_group_layer<'a>(&'a self) -> Option<Box<dyn 'a+Deref<Target=dyn 'a+GroupLayer>>> {
+        let group_layer = self as &dyn GroupLayer;
+
+        Some(Box::new(group_layer))
+    }
+
+    fn get_frame_at_time(&self, time_index: Duration) -> Arc<dyn Frame> {
+        // As there are no keyframes, this will always be an empty frame
+        let frame: Result<Arc<dyn Frame>>   = self.core.sync(|core| {
+            let frame                       = VectorFrame::frame_at_time(&mut core.db, self.layer_id, time_index)?;
+            let frame: Arc<dyn Frame>       = Arc::new(frame);
+
+            Ok(frame)
+        });
+
+        frame.unwrap()
+    }
+
+    fn previous_and_next_key_frame(&self, _when: Duration) -> (Option<Duration>, Option<Duration>) {
+        (None, None)
+    }
+
+    fn get_canvas_cache_at_time(&self, time_index: Duration) -> Arc<dyn CanvasCache> {
+        Arc::new(LayerCanvasCache::cache_with_time(Arc::clone(&self.core), self.layer_id, time_index))
+    }
+}
+
+impl<TFile: FloFile+Send+'static> GroupLayer for SqliteGroupLayer<TFile> {
+    fn child_layer_ids(&self) -> Vec<u64> {
+        let layer_id = self.layer_id;
+        self.core.sync(|core| core.db.query_child_layer_ids(layer_id)).unwrap_or_else(|_| vec![])
+    }
+}
@@ -2,7 +2,10 @@ use super::*;
 use super::db_enum::*;
 use super::flo_store::*;
 
+use flo_canvas::Transform2D;
+
 use std::iter;
+use std::time::Duration;
 
 use self::DatabaseUpdate::*;
 
@@ -54,6 +57,13 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
         Ok(())
     }
 
+    ///
+    /// Converts a duration to nanoseconds for storing as an integer parameter
+    ///
+    fn nanos(duration: Duration) -> i64 {
+        (duration.as_secs() as i64) * 1_000_000_000 + (duration.subsec_nanos() as i64)
+    }
+
     ///
     /// Inserts a single AnimationEdit into the edit log
     ///
@@ -83,10 +93,22 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.db.update(vec![PopEditLogSetSize(width as f32, height as f32)])?;
             },
 
+            &SetDuration(duration)                          => {
+                self.db.update(vec![PushEditLogInt(0, Self::nanos(duration)), Pop])?;
+            },
+
+            &SetFrameLength(frame_length)                   => {
+                self.db.update(vec![PushEditLogInt(0, Self::nanos(frame_length)), Pop])?;
+            },
+
             &AddNewLayer(layer_id)                          => {
                 self.db.update(vec![PushEditLogLayer(layer_id), Pop])?;
             },
 
+            &AddNewGroupLayer(layer_id)                     => {
+                self.db.update(vec![PushEditLogLayer(layer_id), Pop])?;
+            },
+
             &RemoveLayer(layer_id)                          => {
                 self.db.update(vec![PushEditLogLayer(layer_id), Pop])?;
             },
@@ -103,6 +125,12 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.db.update(vec![Pop])?;
             },
 
+            &Element(ref element_ids, ElementEdit::Group(group_id, group_type))  => {
+                // The group ID appears at the start of the list, followed by the elements that are being grouped
+                Self::insert_element_id_list(&mut self.db, &(iter::once(group_id).chain(element_ids.iter().cloned()).collect()))?;
+                self.db.update(vec![PushEditLogGroupType(0, group_type), Pop])?;
+            },
+
             &Element(ref element_ids, ref element_edit)     => {
                 Self::insert_element_id_list(&mut self.db, element_ids)?;
                 self.insert_element_edit(element_edit)?;
@@ -156,6 +184,8 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             Order(_ordering)                            => { self.db.update(vec![Pop])?; }
             Delete                                      => { self.db.update(vec![Pop])?; }
             DetachFromFrame                             => { self.db.update(vec![Pop])?; }
+            Group(_group_id, group_type)                => { self.db.update(vec![PushEditLogGroupType(0, *group_type), Pop])?; }
+            Ungroup                                     => { self.db.update(vec![Pop])?; }
         }
 
         Ok(())
@@ -184,14 +214,14 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.db.update(vec![PushEditLogMotionOrigin(*x, *y), Pop])?;
             },
 
-            SetPath(curve)          => {
+            SetPath(curve) | SetRotation(curve) | SetScale(curve) => {
                 // Create the points in the curve
                 self.db.update(curve.points
                     .iter()
                     .flat_map(|control_point| vec![&control_point.point, &control_point.past, &control_point.future])
                     .map(|&TimePoint(ref x, ref y, ref millis)| PushTimePoint(*x, *y, *millis)))?;
 
-                // Turn into an edit log path
+                // Turn into an edit log path (the edit type distinguishes which curve this is)
                 self.db.update(vec![PushEditLogMotionPath(curve.points.len()*3), Pop])?;
             },
         }
@@ -216,6 +246,16 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
                 self.insert_path_edit(path_edit)?;
             }
 
+            Text(when, text_edit)           => {
+                self.db.update(vec![PushEditLogWhen(*when)])?;
+                self.insert_text_edit(text_edit)?;
+            }
+
+            Image(when, image_edit)         => {
+                self.db.update(vec![PushEditLogWhen(*when)])?;
+                self.insert_image_edit(image_edit)?;
+            }
+
             AddKeyFrame(when)              => {
                 self.db.update(vec![PushEditLogWhen(*when), Pop])?;
             }
@@ -231,6 +271,30 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             SetOrdering(at_index)           => {
                 self.db.update(vec![PushEditLogInt(0, *at_index as i64), Pop])?;
             }
+
+            SetVisible(visible)             => {
+                self.db.update(vec![PushEditLogInt(0, if *visible { 1 } else { 0 }), Pop])?;
+            }
+
+            SetLocked(locked)               => {
+                self.db.update(vec![PushEditLogInt(0, if *locked { 1 } else { 0 }), Pop])?;
+            }
+
+            SetOpacity(opacity)             => {
+                self.db.update(vec![PushEditLogFloat(0, *opacity), Pop])?;
+            }
+
+            SetBlendMode(blend_mode)        => {
+                self.db.update(vec![PushEditLogLayerBlend(0, LayerBlendType::from(blend_mode)), Pop])?;
+            }
+
+            SetParentGroup(Some(parent_id)) => {
+                self.db.update(vec![PushEditLogInt(0, *parent_id as i64), Pop])?;
+            }
+
+            SetParentGroup(None)            => {
+                self.db.update(vec![Pop])?;
+            }
         }
 
         Ok(())
@@ -258,6 +322,11 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
             &BrushStroke(ref id, ref points)                        => {
                 Self::insert_element_id(&mut self.db, id)?;
                 self.db.update(vec![PushRawPoints(Arc::clone(points)), Pop])?;
+            },
+
+            &CreateBrushStroke(ref id, ref points)                  => {
+                Self::insert_element_id(&mut self.db, id)?;
+                self.db.update(vec![PushEditLogBrushPoints(Arc::clone(points)), Pop])?;
             }
         }
 
@@ -295,4 +364,57 @@ impl<TFile: FloFile+Send> AnimationDbCore<TFile> {
 
         Ok(())
     }
+
+    ///
+    /// Inserts the values for a TextEdit into the edit log (db must have an edit ID + a when value pushed)
+    ///
+    fn insert_text_edit<'a>(&mut self, edit: &TextEdit) -> Result<()> {
+        use self::TextEdit::*;
+
+        match edit {
+            CreateText(id, style, (x, y), text)         => {
+                Self::insert_element_id(&mut self.db, id)?;
+                Self::insert_color(&mut self.db, &style.color)?;
+                self.db.update(vec![
+                    PushEditLogColor,
+                    PushEditLogFloat(0, style.font_size as f64),
+                    PushEditLogFloat(1, *x as f64),
+                    PushEditLogFloat(2, *y as f64),
+                    PushEditLogTextAlignment(0, TextAlignmentType::from(&style.alignment)),
+                    PushEditLogString(0, style.font_family.clone()),
+                    PopEditLogString(1, text.clone())
+                ])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Inserts the values for an ImageEdit into the edit log (db must have an edit ID + a when value pushed)
+    ///
+    fn insert_image_edit<'a>(&mut self, edit: &ImageEdit) -> Result<()> {
+        use self::ImageEdit::*;
+
+        match edit {
+            CreateImage(id, image, transform)           => {
+                let Transform2D((a, b, c), (d, e, f), _) = *transform;
+
+                Self::insert_element_id(&mut self.db, id)?;
+                self.db.update(vec![
+                    PushImage(Arc::clone(&image.data)),
+                    PushEditLogImage,
+                    PushEditLogFloat(0, a as f64),
+                    PushEditLogFloat(1, b as f64),
+                    PushEditLogFloat(2, c as f64),
+                    PushEditLogFloat(3, d as f64),
+                    PushEditLogFloat(4, e as f64),
+                    PushEditLogFloat(5, f as f64),
+                    Pop
+                ])?;
+            }
+        }
+
+        Ok(())
+    }
 }
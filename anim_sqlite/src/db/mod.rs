@@ -30,13 +30,17 @@ mod brush;
 mod motion;
 mod time_path;
 mod vector_layer;
+mod group_layer;
 mod motion_path_type;
 mod layer_cache;
+mod compaction_policy;
 pub mod vector_frame;
 
 pub use self::animation::*;
+pub use self::compaction_policy::*;
 pub use self::insert_editlog::*;
 pub use self::vector_layer::*;
+pub use self::group_layer::*;
 use self::animation_core::*;
 use self::flo_sqlite::*;
 use self::flo_store::*;
@@ -88,6 +92,27 @@ impl AnimationDb {
         db
     }
 
+    ///
+    /// Checks that a connection contains an animation that can be opened, returning the version of the file format it uses
+    ///
+    pub fn check_version(connection: &Connection) -> Result<i64> {
+        FloSqlite::check_version(connection)
+    }
+
+    ///
+    /// Returns the names of the patches that need to be applied to bring the animation in a connection up to date
+    ///
+    pub fn pending_upgrades(connection: &Connection) -> Result<Vec<String>> {
+        FloSqlite::pending_patches(connection)
+    }
+
+    ///
+    /// Upgrades the animation in a connection to the latest version of the file format
+    ///
+    pub fn upgrade_connection(connection: &mut Connection) -> Result<()> {
+        FloSqlite::upgrade(connection)
+    }
+
     ///
     /// If there has been an error, retrieves what it is and clears the condition
     ///
@@ -98,6 +123,36 @@ impl AnimationDb {
         })
     }
 
+    ///
+    /// Discards any cached data and rebuilds the database so it uses as little space as possible
+    ///
+    pub fn compact_storage(&self) -> Result<()> {
+        self.core.sync(|core| core.db.compact_storage())
+    }
+
+    ///
+    /// Compacts the animation: old entries are removed from the edit log according to the policy, anything that is no
+    /// longer used by the animation is deleted, and the database is rebuilt so that it uses as little space as possible.
+    ///
+    /// The keyframe and element tables already contain the state produced by the removed edits, so the animation is
+    /// unchanged. Returns the number of edits that were removed from the edit log.
+    ///
+    pub fn compact(&self, policy: CompactionPolicy) -> Result<usize> {
+        self.core.sync(move |core| {
+            let num_discarded = core.db.compact_edit_log(policy.edits_to_keep())?;
+            core.db.compact_storage()?;
+
+            Ok(num_discarded)
+        })
+    }
+
+    ///
+    /// Checks the database for corruption, returning a description of any problems that were found
+    ///
+    pub fn check_integrity(&self) -> Result<Vec<String>> {
+        self.core.sync(|core| core.db.check_integrity())
+    }
+
     ///
     /// Retrieves the number of edits in the animation
     ///
@@ -130,8 +185,8 @@ impl AnimationDbCore<FloSqlite> {
         // Query the database to warm up our cached values
         let mut db = FloSqlite::new(connection);
 
-        // We begin assigning element IDs at the current length of the edit log
-        let initial_element_id = db.query_edit_log_length().unwrap() as i64;
+        // We begin assigning element IDs at the number of edits that have been made (including any that were removed by compacting the edit log)
+        let initial_element_id = db.query_edit_log_length().unwrap() + db.query_discarded_edit_count().unwrap();
 
         // Generate the core
         let core = AnimationDbCore {
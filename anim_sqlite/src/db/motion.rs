@@ -32,6 +32,50 @@ impl AnimationDb {
         }))
     }
 
+    ///
+    /// Interprets a motion entry as a rotate motion
+    ///
+    fn get_rotate_motion<TFile: FloFile>(core: &mut TFile, motion_id: i64, entry: MotionEntry) -> Result<Motion> {
+        let origin      = entry.origin.unwrap_or((0.0, 0.0));
+        let rotation    = Self::get_motion_path(core, motion_id, MotionPathType::Rotation)?;
+
+        Ok(Motion::Rotate(RotateMotion {
+            origin: origin,
+            rotate: rotation
+        }))
+    }
+
+    ///
+    /// Interprets a motion entry as a scale motion
+    ///
+    fn get_scale_motion<TFile: FloFile>(core: &mut TFile, motion_id: i64, entry: MotionEntry) -> Result<Motion> {
+        let origin      = entry.origin.unwrap_or((0.0, 0.0));
+        let scale       = Self::get_motion_path(core, motion_id, MotionPathType::Scale)?;
+
+        Ok(Motion::Scale(ScaleMotion {
+            origin: origin,
+            scale:  scale
+        }))
+    }
+
+    ///
+    /// Interprets a motion entry as a general transform motion
+    ///
+    fn get_transform_motion<TFile: FloFile>(core: &mut TFile, motion_id: i64, entry: MotionEntry) -> Result<Motion> {
+        // Transforms use all three of the motion paths
+        let origin      = entry.origin.unwrap_or((0.0, 0.0));
+        let translate   = Self::get_motion_path(core, motion_id, MotionPathType::Position)?;
+        let rotation    = Self::get_motion_path(core, motion_id, MotionPathType::Rotation)?;
+        let scale       = Self::get_motion_path(core, motion_id, MotionPathType::Scale)?;
+
+        Ok(Motion::Transform(TransformMotion {
+            origin:     origin,
+            translate:  translate,
+            rotate:     rotation,
+            scale:      scale
+        }))
+    }
+
     ///
     /// Turns a motion entry into a motion
     ///
@@ -40,7 +84,10 @@ impl AnimationDb {
             MotionType::None        => Ok(Motion::None),
             MotionType::Reverse     => unimplemented!(), /* TODO: These cannot be represented in the database at the moment */
 
-            MotionType::Translate   => Ok(Self::get_translate_motion(core, motion_id, motion_entry)?)
+            MotionType::Translate   => Ok(Self::get_translate_motion(core, motion_id, motion_entry)?),
+            MotionType::Rotate      => Ok(Self::get_rotate_motion(core, motion_id, motion_entry)?),
+            MotionType::Scale       => Ok(Self::get_scale_motion(core, motion_id, motion_entry)?),
+            MotionType::Transform   => Ok(Self::get_transform_motion(core, motion_id, motion_entry)?)
         }
     }
 
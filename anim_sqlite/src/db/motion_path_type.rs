@@ -4,5 +4,11 @@
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum MotionPathType {
     /// A path giving a position at a time
-    Position
+    Position,
+
+    /// A path giving an angle of rotation in degrees at a time (in the x coordinate)
+    Rotation,
+
+    /// A path giving the horizontal and vertical scale factors at a time
+    Scale
 }
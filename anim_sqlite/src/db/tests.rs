@@ -169,3 +169,22 @@ fn delete_translate_motion() {
         AnimationEdit::Motion(ElementId::Assigned(1), MotionEdit::Delete)
     ]).unwrap();
 }
+
+#[test]
+fn compacting_edit_log_counts_discarded_edits() {
+    let mut core = core();
+
+    core.insert_edits(&[
+        AnimationEdit::SetSize(1980.0, 1080.0),
+        AnimationEdit::AddNewLayer(24),
+        AnimationEdit::Layer(24, AddKeyFrame(Duration::from_millis(300)))
+    ]).unwrap();
+
+    assert!(core.db.compact_edit_log(Some(1)).unwrap() == 2);
+    assert!(core.db.query_edit_log_length().unwrap() == 1);
+    assert!(core.db.query_discarded_edit_count().unwrap() == 2);
+
+    // Keeping more edits than there are in the log leaves it unchanged
+    assert!(core.db.compact_edit_log(Some(10)).unwrap() == 0);
+    assert!(core.db.query_discarded_edit_count().unwrap() == 2);
+}
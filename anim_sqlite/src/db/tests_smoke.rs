@@ -90,6 +90,16 @@ fn smoke_push_layer_order() {
     ]);
 }
 
+#[test]
+fn smoke_push_layer_blend() {
+    test_updates(vec![
+        DatabaseUpdate::PushEditType(EditLogType::LayerSetBlendMode),
+        DatabaseUpdate::PushEditLogLayer(1),
+        DatabaseUpdate::PushEditLogLayerBlend(0, LayerBlendType::Multiply),
+        DatabaseUpdate::Pop
+    ]);
+}
+
 #[test]
 fn smoke_push_element_delete() {
     test_updates(vec![
@@ -383,6 +393,19 @@ fn smoke_brush_properties() {
     ])
 }
 
+#[test]
+fn smoke_brush_gradient() {
+    test_updates(vec![
+        DatabaseUpdate::PushColorType(ColorType::Rgb),
+        DatabaseUpdate::PushRgb(0.0, 0.0, 0.0),
+        DatabaseUpdate::PushBrushProperties(100.0, 1.0),
+        DatabaseUpdate::PushColorType(ColorType::Rgb),
+        DatabaseUpdate::PushRgb(1.0, 1.0, 1.0),
+        DatabaseUpdate::PushBrushGradient(BrushGradientType::Linear, 45.0),
+        DatabaseUpdate::Pop
+    ])
+}
+
 #[test]
 fn smoke_editlog_brush_properties() {
     test_updates(vec![
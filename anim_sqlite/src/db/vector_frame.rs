@@ -109,6 +109,63 @@ impl VectorFrame {
         Ok(MotionElement::new(entry.assigned_id, motion))
     }
 
+    ///
+    /// Returns the group element associated with a particular entry
+    ///
+    fn group_for_entry<TFile: FloFile+Send>(db: &mut TFile, entry: VectorElementEntry) -> Result<GroupElement> {
+        let group_entry         = db.query_group_element(entry.element_id)?
+            .ok_or(SqliteAnimationError::UnexpectedElementType(entry.assigned_id))?;
+        let mut grouped         = vec![];
+
+        for member_id in group_entry.member_ids {
+            // Elements in a group are detached from the frame, so their brush attachments are added to the group ahead of them
+            for (attachment_id, _assigned_id, attachment_type) in db.query_attached_elements(member_id)? {
+                match attachment_type {
+                    VectorElementType::BrushDefinition | VectorElementType::BrushProperties => {
+                        let attachment_entry = db.query_vector_element(attachment_id)?;
+                        grouped.push(Self::vector_for_entry(db, attachment_entry)?);
+                    },
+
+                    _ => { }
+                }
+            }
+
+            let member_entry    = db.query_vector_element(member_id)?;
+            grouped.push(Self::vector_for_entry(db, member_entry)?);
+        }
+
+        Ok(GroupElement::new(entry.assigned_id, group_entry.group_type, Arc::new(grouped)))
+    }
+
+    ///
+    /// Returns the text element associated with a particular entry
+    ///
+    fn text_for_entry<TFile: FloFile+Send>(db: &mut TFile, entry: VectorElementEntry) -> Result<TextElement> {
+        let text_entry          = db.query_text_element(entry.element_id)?
+            .ok_or(SqliteAnimationError::UnexpectedElementType(entry.assigned_id))?;
+
+        let style               = TextStyle {
+            font_family:    text_entry.font_family,
+            font_size:      text_entry.font_size,
+            color:          AnimationDbCore::get_color(db, text_entry.color_id)?,
+            alignment:      text_entry.alignment.into()
+        };
+
+        Ok(TextElement::new(entry.assigned_id, text_entry.position, text_entry.text, style))
+    }
+
+    ///
+    /// Returns the image element associated with a particular entry
+    ///
+    fn image_for_entry<TFile: FloFile+Send>(db: &mut TFile, entry: VectorElementEntry) -> Result<ImageElement> {
+        let image_entry         = db.query_image_element(entry.element_id)?
+            .ok_or(SqliteAnimationError::UnexpectedElementType(entry.assigned_id))?;
+        let image               = CanvasImage::from_data(image_entry.data)
+            .ok_or(SqliteAnimationError::UnexpectedElementType(entry.assigned_id))?;
+
+        Ok(ImageElement::new(entry.assigned_id, image, image_entry.transform))
+    }
+
     ///
     /// Tries to turn a vector element entry into a Vector object
     ///
@@ -118,7 +175,10 @@ impl VectorFrame {
             VectorElementType::BrushProperties      => Ok(Vector::BrushProperties(Self::properties_for_entry(db, entry)?)),
             VectorElementType::BrushStroke          => Ok(Vector::BrushStroke(Self::brush_stroke_for_entry(db, entry)?)),
             VectorElementType::Path                 => Ok(Vector::Path(Self::path_for_entry(db, entry)?)),
-            VectorElementType::Motion               => Ok(Vector::Motion(Self::motion_for_entry(db, entry)?))
+            VectorElementType::Motion               => Ok(Vector::Motion(Self::motion_for_entry(db, entry)?)),
+            VectorElementType::Group                => Ok(Vector::Group(Self::group_for_entry(db, entry)?)),
+            VectorElementType::Text                 => Ok(Vector::Text(Self::text_for_entry(db, entry)?)),
+            VectorElementType::Image                => Ok(Vector::Image(Self::image_for_entry(db, entry)?))
         }
     }
 
@@ -4,6 +4,8 @@ use super::layer_cache::*;
 use super::vector_frame::*;
 use super::super::result::Result;
 
+use flo_canvas::BlendMode;
+
 use std::ops::{Range, Deref};
 use std::time::Duration;
 use std::collections::HashMap;
@@ -22,6 +24,12 @@ pub struct SqliteVectorLayer<TFile: FloFile+Send> {
     /// The name of this layer, if it has one
     name: Option<String>,
 
+    /// The visibility, locked state, opacity and blend mode for this layer
+    properties: LayerPropertiesEntry,
+
+    /// The assigned ID of the group containing this layer, if there is one
+    parent_group: Option<u64>,
+
     /// The currently active brush for this layer (or none if we need to fetch this from the database)
     /// The active brush is the brush most recently added to the keyframe at the specified point in time
     active_brush: Option<(Duration, Arc<dyn Brush>)>,
@@ -33,32 +41,29 @@ pub struct SqliteVectorLayer<TFile: FloFile+Send> {
     core: Arc<Desync<AnimationDbCore<TFile>>>
 }
 
-impl AnimationDb {
-    ///
-    /// Retrieves a layer for a particular ID
-    ///
-    pub fn get_layer_with_id(&self, assigned_id: u64) -> Option<SqliteVectorLayer<FloSqlite>> {
-        SqliteVectorLayer::from_assigned_id(&self.core, assigned_id)
-    }
-}
-
 impl<TFile: FloFile+Send+'static> SqliteVectorLayer<TFile> {
     ///
     /// Retrieves a layer for a particular ID
     ///
     pub fn from_assigned_id(core: &Arc<Desync<AnimationDbCore<TFile>>>, assigned_id: u64) -> Option<SqliteVectorLayer<TFile>> {
         // Query for the 'real' layer ID
-        let layer = core.sync(|core| {
+        let layer: Result<_> = core.sync(|core| {
             // Fetch the layer data (we need the 'real' ID here)
-            core.db.query_layer_id_for_assigned_id(assigned_id)
+            let (layer_id, name)    = core.db.query_layer_id_for_assigned_id(assigned_id)?;
+            let properties          = core.db.query_layer_properties(layer_id)?;
+            let parent_group        = core.db.query_parent_layer_group(layer_id)?;
+
+            Ok((layer_id, name, properties, parent_group))
         });
 
         // If the layer exists, create a SqliteVectorLayer
         layer.ok()
-            .map(|(layer_id, name)| {
+            .map(|(layer_id, name, properties, parent_group)| {
                 SqliteVectorLayer {
                     assigned_id:    assigned_id,
                     name:           name,
+                    properties:     properties,
+                    parent_group:   parent_group.map(|(_parent_id, assigned_parent_id)| assigned_parent_id),
                     layer_id:       layer_id,
                     active_brush:   None,
                     core:           Arc::clone(core),
@@ -81,6 +86,26 @@ impl<TFile: FloFile+Send+'static> Layer for SqliteVectorLayer<TFile> {
         vec![LayerEditType::Vector]
     }
 
+    fn is_visible(&self) -> bool {
+        self.properties.visible
+    }
+
+    fn is_locked(&self) -> bool {
+        self.properties.locked
+    }
+
+    fn opacity(&self) -> f64 {
+        self.properties.opacity
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.properties.blend_mode.into()
+    }
+
+    fn parent_group(&self) -> Option<u64> {
+        self.parent_group
+    }
+
     fn get_key_frames_during_time(&self, when: Range<Duration>) -> Box<dyn Iterator<Item=Duration>> {
         let from        = when.start;
         let until       = when.end;
@@ -100,6 +125,10 @@ impl<TFile: FloFile+Send+'static> Layer for SqliteVectorLayer<TFile> {
         Some(Box::new(vector_layer))
     }
 
+    fn as_group_layer<'a>(&'a self) -> Option<Box<dyn 'a+Deref<Target=dyn 'a+GroupLayer>>> {
+        None
+    }
+
     fn get_frame_at_time(&self, time_index: Duration) -> Arc<dyn Frame> {
         let core: Result<Arc<dyn Frame>>    = self.core.sync(|core| {
             // TODO: this call is returning a 'QueryReturnedNoRows' error sometimes (which isn't too helpful as we don't know what's failing)
@@ -28,6 +28,9 @@ pub enum SqliteAnimationError {
 
     /// An element with the specified ID was not of the correct type
     UnexpectedElementType(ElementId),
+
+    /// A layer edit was sent to a layer of the wrong type (eg, a vector edit to a group layer)
+    UnexpectedLayerType(i64),
 }
 
 impl From<rusqlite::Error> for SqliteAnimationError {
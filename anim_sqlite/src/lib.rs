@@ -18,3 +18,4 @@ mod animation_sqlite;
 
 pub use self::animation_sqlite::*;
 pub use self::error::*;
+pub use self::db::CompactionPolicy;
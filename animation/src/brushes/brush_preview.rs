@@ -15,7 +15,8 @@ pub struct BrushPreview {
     current_brush:          Arc<dyn Brush>,
     brush_properties:       BrushProperties,
     points:                 Vec<RawPoint>,
-    combined_element:       Option<Vector>
+    combined_element:       Option<Vector>,
+    stabiliser:             Stabiliser
 }
 
 impl BrushPreview {
@@ -24,7 +25,8 @@ impl BrushPreview {
             current_brush:      create_brush_from_definition(&BrushDefinition::Simple, BrushDrawingStyle::Draw),
             brush_properties:   BrushProperties::new(),
             points:             vec![],
-            combined_element:   None
+            combined_element:   None,
+            stabiliser:         Stabiliser::new(StabiliserSettings::default())
         }
     }
 
@@ -45,12 +47,30 @@ impl BrushPreview {
         self.brush_properties = *properties;
     }
 
+    ///
+    /// Sets how the points in the brush stroke are stabilised
+    ///
+    /// (The stabiliser is only reset if the settings change)
+    ///
+    pub fn set_stabiliser(&mut self, settings: StabiliserSettings) {
+        if self.stabiliser.settings() != settings {
+            self.stabiliser = Stabiliser::new(settings);
+        }
+    }
+
     ///
     /// Continues the current brush stroke
     ///
     pub fn continue_brush_stroke(&mut self, point: RawPoint) {
-        // Add points to the active brush stroke
-        self.points.push(point);
+        // Add the stabilised points to the active brush stroke
+        self.points.extend(self.stabiliser.add_point(point));
+    }
+
+    ///
+    /// Finishes the current brush stroke, letting the stabiliser catch up with the last point
+    ///
+    pub fn finish_brush_stroke(&mut self) {
+        self.points.extend(self.stabiliser.finish());
     }
 
     ///
@@ -59,6 +79,7 @@ impl BrushPreview {
     pub fn cancel_brush_stroke(&mut self) {
         self.points             = vec![];
         self.combined_element   = None;
+        self.stabiliser         = Stabiliser::new(self.stabiliser.settings());
     }
 
     ///
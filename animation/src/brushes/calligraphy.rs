@@ -0,0 +1,214 @@
+use super::ink::*;
+use super::super::traits::*;
+
+use flo_canvas::*;
+
+use std::sync::*;
+
+// Distance to scale up at the start of the brush stroke (calligraphy nibs reach their full width almost immediately)
+const SCALE_UP_DISTANCE: f32 = 4.0;
+
+// The tilt (in degrees away from vertical) at which the tilt of the pen has its full effect on the nib angle
+const FULL_TILT: f32 = 45.0;
+
+///
+/// The calligraphy brush draws a line whose width depends on the direction it's moving in relative to the angle of a flat nib
+///
+/// The nib is treated as an ellipse: moving across the nib draws a line with the full width, and moving along it draws a line
+/// that's only as wide as the nib is thick. When the tilt of the pen is available, it can be used to turn the nib.
+///
+pub struct CalligraphyBrush {
+    /// The definition for this brush
+    definition: CalligraphyDefinition,
+
+    /// The ink brush that renders the strokes generated by this brush
+    ink: InkBrush
+}
+
+impl CalligraphyBrush {
+    ///
+    /// Creates a new calligraphy brush
+    ///
+    pub fn new(definition: &CalligraphyDefinition, drawing_style: BrushDrawingStyle) -> Self {
+        let ink_definition = InkDefinition {
+            min_width:          definition.min_width,
+            max_width:          definition.max_width,
+            scale_up_distance:  SCALE_UP_DISTANCE,
+            pressure_curve:     PressureCurve::Linear,
+            pressure_source:    PressureSource::Device
+        };
+
+        Self {
+            definition: definition.clone(),
+            ink:        InkBrush::new(&ink_definition, drawing_style)
+        }
+    }
+
+    ///
+    /// Works out the angle of the nib (in degrees) for a point with the specified tilt
+    ///
+    fn nib_angle_for_tilt(&self, tilt: (f32, f32)) -> f32 {
+        let (tilt_x, tilt_y)    = tilt;
+        let tilt_amount         = (tilt_x*tilt_x + tilt_y*tilt_y).sqrt();
+
+        if tilt_amount <= 0.0 || self.definition.tilt_influence <= 0.0 {
+            // No tilt information (or the tilt is being ignored)
+            self.definition.nib_angle
+        } else {
+            // The edge of the nib lies across the direction that the pen is leaning in
+            let lean_angle  = tilt_y.atan2(tilt_x).to_degrees();
+            let pen_angle   = lean_angle + 90.0;
+
+            // Turn the nib towards the pen angle by an amount that depends on how far the pen is tilted
+            let tilt_weight = (tilt_amount / FULL_TILT).min(1.0) * self.definition.tilt_influence.min(1.0);
+
+            self.definition.nib_angle + wrap_nib_angle(pen_angle - self.definition.nib_angle) * tilt_weight
+        }
+    }
+}
+
+///
+/// Wraps an angle between two nib directions into the range -90 to 90 degrees (a nib looks the same when it's turned by 180 degrees)
+///
+fn wrap_nib_angle(angle: f32) -> f32 {
+    let angle = angle % 180.0;
+
+    if angle > 90.0 {
+        angle - 180.0
+    } else if angle <= -90.0 {
+        angle + 180.0
+    } else {
+        angle
+    }
+}
+
+///
+/// Returns the width of the line drawn by an elliptical nib (as a proportion of its full width) when it's moved in a particular direction
+///
+/// The angles are both in degrees.
+///
+fn nib_width(direction: f32, nib_angle: f32, aspect_ratio: f32) -> f32 {
+    let relative_angle  = (direction - nib_angle).to_radians();
+    let across          = relative_angle.sin();
+    let along           = relative_angle.cos() * aspect_ratio;
+
+    (across*across + along*along).sqrt()
+}
+
+impl Brush for CalligraphyBrush {
+    fn brush_points_for_raw_points(&self, points: &[RawPoint]) -> Vec<BrushPoint> {
+        // Nothing to draw if there are no points in the brush stroke (or only one point)
+        if points.len() <= 2 {
+            return vec![];
+        }
+
+        let aspect_ratio    = self.definition.nib_aspect_ratio.max(0.0).min(1.0);
+        let mut direction   = None;
+
+        // Scale the pressure of each point by the width of the nib in the direction that it's moving
+        let nib_points = (0..points.len())
+            .map(|index| {
+                // Use the neighbouring points to work out which way the pen is moving
+                let before  = points[if index > 0 { index-1 } else { 0 }].position;
+                let after   = points[(index+1).min(points.len()-1)].position;
+                let (dx, dy) = (after.0-before.0, after.1-before.1);
+
+                if dx != 0.0 || dy != 0.0 {
+                    direction = Some(dy.atan2(dx).to_degrees());
+                }
+
+                // Points where the pen is not moving use the last known direction (or move across the nib if the pen has not moved yet)
+                let nib_angle   = self.nib_angle_for_tilt(points[index].tilt);
+                let direction   = direction.unwrap_or(nib_angle + 90.0);
+                let width       = nib_width(direction, nib_angle, aspect_ratio);
+
+                RawPoint {
+                    position:   points[index].position,
+                    pressure:   points[index].pressure * width,
+                    tilt:       points[index].tilt
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // The ink brush can fit and render these points
+        self.ink.brush_points_for_raw_points(&nib_points)
+    }
+
+    fn prepare_to_render<'a>(&'a self, properties: &'a BrushProperties) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        self.ink.prepare_to_render(properties)
+    }
+
+    fn render_brush<'a>(&'a self, properties: &'a BrushProperties, points: &'a Vec<BrushPoint>) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        self.ink.render_brush(properties, points)
+    }
+
+    ///
+    /// Retrieves the definition for this brush
+    ///
+    fn to_definition(&self) -> (BrushDefinition, BrushDrawingStyle) {
+        let (_, drawing_style) = self.ink.to_definition();
+
+        (BrushDefinition::Calligraphy(self.definition.clone()), drawing_style)
+    }
+
+    ///
+    /// Attempts to combine this brush stroke with the specified vector element. Returns the combined element if successful
+    ///
+    fn combine_with(&self, element: &Vector, points: Arc<Vec<BrushPoint>>, brush_properties: &VectorProperties, element_properties: &VectorProperties, combined_element: Option<Vector>) -> CombineResult {
+        // Calligraphy strokes are filled outlines just like ink strokes, so they combine in the same way
+        self.ink.combine_with(element, points, brush_properties, element_properties, combined_element)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn full_width_across_nib() {
+        assert!((nib_width(90.0, 0.0, 0.2) - 1.0).abs() < 0.001);
+        assert!((nib_width(-45.0, 45.0, 0.2) - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn aspect_ratio_width_along_nib() {
+        assert!((nib_width(0.0, 0.0, 0.2) - 0.2).abs() < 0.001);
+        assert!((nib_width(225.0, 45.0, 0.2) - 0.2).abs() < 0.001);
+    }
+
+    #[test]
+    fn wrap_angles() {
+        assert!((wrap_nib_angle(135.0) - -45.0).abs() < 0.001);
+        assert!((wrap_nib_angle(-135.0) - 45.0).abs() < 0.001);
+        assert!((wrap_nib_angle(270.0) - 90.0).abs() < 0.001);
+        assert!((wrap_nib_angle(30.0) - 30.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn no_tilt_uses_nib_angle() {
+        let brush = CalligraphyBrush::new(&CalligraphyDefinition::default(), BrushDrawingStyle::Draw);
+
+        assert!((brush.nib_angle_for_tilt((0.0, 0.0)) - 45.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn tilt_turns_nib() {
+        let mut definition          = CalligraphyDefinition::default();
+        definition.nib_angle        = 0.0;
+        definition.tilt_influence   = 1.0;
+        let brush                   = CalligraphyBrush::new(&definition, BrushDrawingStyle::Draw);
+
+        // Leaning along the x axis puts the edge of the nib along the y axis
+        assert!((brush.nib_angle_for_tilt((60.0, 0.0)).abs() - 90.0).abs() < 0.001);
+
+        // Half the full tilt only turns the nib half of the way
+        assert!((brush.nib_angle_for_tilt((22.5, 0.0)) - 45.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn to_definition_round_trips() {
+        let brush = CalligraphyBrush::new(&CalligraphyDefinition::default(), BrushDrawingStyle::Erase);
+
+        assert!(brush.to_definition() == (BrushDefinition::Calligraphy(CalligraphyDefinition::default()), BrushDrawingStyle::Erase));
+    }
+}
@@ -30,7 +30,13 @@ pub struct InkBrush {
     max_width: f32,
 
     // Distance to scale up at the start of the brush stroke
-    scale_up_distance: f32
+    scale_up_distance: f32,
+
+    /// How the pressure is mapped between the minimum and maximum widths
+    pressure_curve: PressureCurve,
+
+    /// Where the pressure for each point comes from
+    pressure_source: PressureSource
 }
 
 impl InkBrush {
@@ -49,7 +55,9 @@ impl InkBrush {
             blend_mode,
             min_width:          definition.min_width,
             max_width:          definition.max_width,
-            scale_up_distance:  definition.scale_up_distance
+            scale_up_distance:  definition.scale_up_distance,
+            pressure_curve:     definition.pressure_curve,
+            pressure_source:    definition.pressure_source
         }
     }
 }
@@ -274,8 +282,12 @@ impl Brush for InkBrush {
             return vec![];
         }
 
-        // Convert points to ink points
-        let ink_points: Vec<_> = points.iter().map(|point| InkCoord::from(point)).collect();
+        // Convert points to ink points, using the pressure curve to map the pressure of each point
+        let pressures           = self.pressure_source.pressure_for_points(points);
+        let ink_points: Vec<_>  = points.iter()
+            .zip(pressures)
+            .map(|(point, pressure)| InkCoord::from(&RawPoint { pressure: self.pressure_curve.pressure(pressure), ..*point }))
+            .collect();
 
         // Average points that are very close together so we don't overdo
         // the curve fitting
@@ -454,7 +466,9 @@ impl Brush for InkBrush {
         let definition = BrushDefinition::Ink(InkDefinition {
             min_width:          self.min_width,
             max_width:          self.max_width,
-            scale_up_distance:  self.scale_up_distance
+            scale_up_distance:  self.scale_up_distance,
+            pressure_curve:     self.pressure_curve,
+            pressure_source:    self.pressure_source
         });
 
         let drawing_style = match self.blend_mode {
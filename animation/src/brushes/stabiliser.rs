@@ -0,0 +1,289 @@
+use super::super::traits::*;
+
+use std::collections::VecDeque;
+
+// Distance between the points generated when the stabiliser catches up with the end of a brush stroke
+const CATCH_UP_STEP: f32 = 2.0;
+
+///
+/// The ways that the stabiliser can smooth out a brush stroke
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StabiliserMode {
+    /// Points are passed through unchanged
+    Off,
+
+    /// The brush is pulled behind the pointer on a string of the specified length (in canvas units), so it only moves when the string is taut
+    PulledString(f32),
+
+    /// Each point is the average of the specified number of the most recent input points
+    MovingAverage(usize)
+}
+
+///
+/// Settings for the brush stroke stabiliser
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct StabiliserSettings {
+    /// How the stabiliser smooths the input points
+    pub mode: StabiliserMode,
+
+    /// True if the brush stroke should be extended to the last input point when it's finished
+    pub catch_up_on_finish: bool
+}
+
+impl StabiliserSettings {
+    ///
+    /// Creates the default stabiliser settings (which leave the input unchanged)
+    ///
+    pub fn default() -> StabiliserSettings {
+        StabiliserSettings {
+            mode:               StabiliserMode::Off,
+            catch_up_on_finish: true
+        }
+    }
+}
+
+///
+/// The stabiliser smooths the raw points from an input device before they're turned into a brush stroke
+///
+/// A new stabiliser should be created for each brush stroke.
+///
+pub struct Stabiliser {
+    /// The settings for this stabiliser
+    settings: StabiliserSettings,
+
+    /// The most recent input point
+    last_input: Option<RawPoint>,
+
+    /// Where the brush is for the pulled string mode
+    brush_position: Option<(f32, f32)>,
+
+    /// The most recent input points for the moving average mode
+    window: VecDeque<RawPoint>
+}
+
+impl Stabiliser {
+    ///
+    /// Creates a new stabiliser with the specified settings
+    ///
+    pub fn new(settings: StabiliserSettings) -> Stabiliser {
+        Stabiliser {
+            settings:       settings,
+            last_input:     None,
+            brush_position: None,
+            window:         VecDeque::new()
+        }
+    }
+
+    ///
+    /// Retrieves the settings for this stabiliser
+    ///
+    pub fn settings(&self) -> StabiliserSettings {
+        self.settings
+    }
+
+    ///
+    /// Adds a point from the input device, returning the stabilised points that should be added to the brush stroke
+    ///
+    pub fn add_point(&mut self, point: RawPoint) -> Vec<RawPoint> {
+        use self::StabiliserMode::*;
+
+        self.last_input = Some(point);
+
+        match self.settings.mode {
+            Off                     => vec![point],
+            PulledString(length)    => self.pull_string(point, length),
+            MovingAverage(samples)  => {
+                self.window.push_back(point);
+                while self.window.len() > samples.max(1) {
+                    self.window.pop_front();
+                }
+
+                vec![self.window_average()]
+            }
+        }
+    }
+
+    ///
+    /// Finishes the brush stroke, returning any points needed to catch up with the last input point
+    ///
+    pub fn finish(&mut self) -> Vec<RawPoint> {
+        use self::StabiliserMode::*;
+
+        let last_input = self.last_input.take();
+
+        if !self.settings.catch_up_on_finish {
+            self.brush_position = None;
+            self.window.clear();
+            return vec![];
+        }
+
+        match (self.settings.mode, last_input) {
+            (PulledString(_), Some(last_input)) => {
+                // Move the brush along the string to the last point
+                let mut points  = vec![];
+                let (x1, y1)    = self.brush_position.take().unwrap_or(last_input.position);
+                let (x2, y2)    = last_input.position;
+                let distance    = ((x2-x1)*(x2-x1) + (y2-y1)*(y2-y1)).sqrt();
+                let num_steps   = (distance / CATCH_UP_STEP).ceil() as usize;
+
+                for step in 1..(num_steps+1) {
+                    let t = (step as f32) / (num_steps as f32);
+
+                    points.push(RawPoint {
+                        position:   (x1 + (x2-x1)*t, y1 + (y2-y1)*t),
+                        pressure:   last_input.pressure,
+                        tilt:       last_input.tilt
+                    });
+                }
+
+                points
+            },
+
+            (MovingAverage(_), _) => {
+                // Shrink the window until only the last point is left
+                let mut points = vec![];
+
+                while self.window.len() > 1 {
+                    self.window.pop_front();
+                    points.push(self.window_average());
+                }
+
+                self.window.clear();
+                points
+            },
+
+            _ => vec![]
+        }
+    }
+
+    ///
+    /// Moves the brush towards a point when it's further away than the length of the string
+    ///
+    fn pull_string(&mut self, point: RawPoint, length: f32) -> Vec<RawPoint> {
+        match self.brush_position {
+            None                => {
+                // The brush starts where the pointer starts
+                self.brush_position = Some(point.position);
+                vec![point]
+            },
+
+            Some((x, y))        => {
+                let (dx, dy)    = (point.position.0-x, point.position.1-y);
+                let distance    = (dx*dx + dy*dy).sqrt();
+
+                if distance <= length {
+                    // String is slack: the brush does not move
+                    vec![]
+                } else {
+                    // Pull the brush until it's the length of the string away from the pointer
+                    let ratio           = (distance-length)/distance;
+                    let new_position    = (x + dx*ratio, y + dy*ratio);
+
+                    self.brush_position = Some(new_position);
+
+                    vec![RawPoint {
+                        position:   new_position,
+                        pressure:   point.pressure,
+                        tilt:       point.tilt
+                    }]
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the average of the points in the moving average window
+    ///
+    fn window_average(&self) -> RawPoint {
+        let count               = self.window.len() as f32;
+        let (mut x, mut y)      = (0.0, 0.0);
+        let mut pressure        = 0.0;
+        let (mut tx, mut ty)    = (0.0, 0.0);
+
+        for point in self.window.iter() {
+            x           += point.position.0;
+            y           += point.position.1;
+            pressure    += point.pressure;
+            tx          += point.tilt.0;
+            ty          += point.tilt.1;
+        }
+
+        RawPoint {
+            position:   (x/count, y/count),
+            pressure:   pressure/count,
+            tilt:       (tx/count, ty/count)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn settings(mode: StabiliserMode, catch_up_on_finish: bool) -> StabiliserSettings {
+        StabiliserSettings { mode, catch_up_on_finish }
+    }
+
+    #[test]
+    fn off_passes_points_through() {
+        let mut stabiliser  = Stabiliser::new(StabiliserSettings::default());
+        let point           = RawPoint::from((10.0, 20.0));
+
+        assert!(stabiliser.add_point(point) == vec![point]);
+        assert!(stabiliser.finish() == vec![]);
+    }
+
+    #[test]
+    fn pulled_string_waits_until_taut() {
+        let mut stabiliser  = Stabiliser::new(settings(StabiliserMode::PulledString(10.0), true));
+
+        assert!(stabiliser.add_point(RawPoint::from((0.0, 0.0))) == vec![RawPoint::from((0.0, 0.0))]);
+        assert!(stabiliser.add_point(RawPoint::from((5.0, 0.0))) == vec![]);
+        assert!(stabiliser.add_point(RawPoint::from((15.0, 0.0))) == vec![RawPoint::from((5.0, 0.0))]);
+    }
+
+    #[test]
+    fn pulled_string_catches_up() {
+        let mut stabiliser  = Stabiliser::new(settings(StabiliserMode::PulledString(10.0), true));
+
+        stabiliser.add_point(RawPoint::from((0.0, 0.0)));
+        stabiliser.add_point(RawPoint::from((15.0, 0.0)));
+
+        let catch_up = stabiliser.finish();
+
+        assert!(catch_up.len() == 5);
+        assert!(catch_up.last() == Some(&RawPoint::from((15.0, 0.0))));
+    }
+
+    #[test]
+    fn pulled_string_without_catch_up() {
+        let mut stabiliser  = Stabiliser::new(settings(StabiliserMode::PulledString(10.0), false));
+
+        stabiliser.add_point(RawPoint::from((0.0, 0.0)));
+        stabiliser.add_point(RawPoint::from((15.0, 0.0)));
+
+        assert!(stabiliser.finish() == vec![]);
+    }
+
+    #[test]
+    fn moving_average_smooths_points() {
+        let mut stabiliser  = Stabiliser::new(settings(StabiliserMode::MovingAverage(2), true));
+
+        assert!(stabiliser.add_point(RawPoint::from((0.0, 0.0))) == vec![RawPoint::from((0.0, 0.0))]);
+        assert!(stabiliser.add_point(RawPoint::from((10.0, 0.0))) == vec![RawPoint::from((5.0, 0.0))]);
+        assert!(stabiliser.add_point(RawPoint::from((10.0, 10.0))) == vec![RawPoint::from((10.0, 5.0))]);
+    }
+
+    #[test]
+    fn moving_average_catches_up() {
+        let mut stabiliser  = Stabiliser::new(settings(StabiliserMode::MovingAverage(3), true));
+
+        stabiliser.add_point(RawPoint::from((0.0, 0.0)));
+        stabiliser.add_point(RawPoint::from((10.0, 0.0)));
+        stabiliser.add_point(RawPoint::from((20.0, 0.0)));
+
+        assert!(stabiliser.finish() == vec![RawPoint::from((15.0, 0.0)), RawPoint::from((20.0, 0.0))]);
+    }
+}
@@ -42,7 +42,9 @@ impl RaycastEdge {
             Vector::Transformed(transform)      => { Self::from_transformed(transform, properties) }
             Vector::BrushStroke(brush_stroke)   => { Self::from_brush_stroke(brush_stroke, properties) }
             Vector::Path(path)                  => { Box::new(Self::from_path_element(path)) }
-            Vector::Group(group_element)        => { Box::new(Self::from_group(group_element, properties)) }
+            Vector::Group(group_element)        => { Self::from_group(group_element, properties) }
+            Vector::Text(text)                  => { Self::from_text(text, properties) }
+            Vector::Image(image)                => { Self::from_image(image, properties) }
         }
     }
 
@@ -63,9 +65,47 @@ impl RaycastEdge {
     ///
     /// Retrieves the edges corresponding to a group element
     ///
-    pub fn from_group<'a>(group: &'a GroupElement, properties: Arc<VectorProperties>) -> impl 'a+Iterator<Item=Self> {
-        group.elements()
-            .flat_map(move |element| Self::from_vector(element, properties.clone()))
+    pub fn from_group<'a>(group: &'a GroupElement, properties: Arc<VectorProperties>) -> Box<dyn 'a+Iterator<Item=Self>> {
+        match group.group_type() {
+            GroupType::Normal   => {
+                Box::new(group.elements()
+                    .flat_map(move |element| Self::from_vector(element, properties.clone())))
+            }
+
+            _                   => {
+                // Groups that combine paths generate the edges from the combined path
+                let paths = group.to_path(&*properties).unwrap_or_else(|| vec![]);
+                let edges = paths.iter()
+                    .flat_map(|path| Self::from_path(path, RaycastEdgeKind::Solid))
+                    .collect::<Vec<_>>();
+
+                Box::new(edges.into_iter())
+            }
+        }
+    }
+
+    ///
+    /// Retrieves the edges corresponding to a text element (the outline of the text)
+    ///
+    pub fn from_text<'a>(text: &'a TextElement, properties: Arc<VectorProperties>) -> Box<dyn 'a+Iterator<Item=Self>> {
+        let paths = text.to_path(&*properties).unwrap_or_else(|| vec![]);
+        let edges = paths.iter()
+            .flat_map(|path| Self::from_path(path, RaycastEdgeKind::Solid))
+            .collect::<Vec<_>>();
+
+        Box::new(edges.into_iter())
+    }
+
+    ///
+    /// Retrieves the edges corresponding to an image element (the outline of the image)
+    ///
+    pub fn from_image<'a>(image: &'a ImageElement, properties: Arc<VectorProperties>) -> Box<dyn 'a+Iterator<Item=Self>> {
+        let paths = image.to_path(&*properties).unwrap_or_else(|| vec![]);
+        let edges = paths.iter()
+            .flat_map(|path| Self::from_path(path, RaycastEdgeKind::Solid))
+            .collect::<Vec<_>>();
+
+        Box::new(edges.into_iter())
     }
 
     ///
@@ -27,7 +27,14 @@ pub enum MotionEditAction {
     ///
     /// If a translation that is being updated is attached to an element outside of the set
     /// that is being changed, the attached translation is changed to a new ID.
-    MoveElements(Vec<ElementId>, Duration, (f32, f32), (f32, f32))
+    MoveElements(Vec<ElementId>, Duration, (f32, f32), (f32, f32)),
+
+    /// Applies an easing to the motions attached to a set of elements
+    ///
+    /// Every curve in the motions attached to the elements has the easing applied to its section that
+    /// contains the specified time. Motions are shared, so this also affects any other elements that the
+    /// motions are attached to.
+    SetEasing(Vec<ElementId>, Duration, Easing)
 }
 
 impl EditAction for MotionEditAction {
@@ -38,7 +45,8 @@ impl EditAction for MotionEditAction {
         use self::MotionEditAction::*;
 
         match self {
-            MoveElements(elements, when, from, to)  => move_elements_edit(animation, elements, when, from, to)
+            MoveElements(elements, when, from, to)  => move_elements_edit(animation, elements, when, from, to),
+            SetEasing(elements, when, easing)       => set_easing_edit(animation, elements, when, *easing)
         }
     }
 }
@@ -176,6 +184,43 @@ fn move_elements_edit<Anim: Animation>(animation: &Anim, elements: &Vec<ElementI
         .collect()
 }
 
+///
+/// Generates the edits to apply an easing to the motions attached to a set of elements
+///
+fn set_easing_edit<Anim: Animation>(animation: &Anim, elements: &Vec<ElementId>, when: &Duration, easing: Easing) -> Vec<AnimationEdit> {
+    let when_millis = to_millis(*when) as f32;
+
+    // Find the motions attached to the elements (each motion is only edited once)
+    let mut motion_ids  = vec![];
+    let mut seen        = HashSet::new();
+
+    for element_id in elements.iter() {
+        for motion_id in animation.motion().get_motions_for_element(*element_id) {
+            if seen.insert(motion_id) {
+                motion_ids.push(motion_id);
+            }
+        }
+    }
+
+    // Ease the section of each curve at the specified time
+    let ease = |curve: &TimeCurve| curve.section_at_time(when_millis).map(|section| curve.set_section_easing(section, easing));
+
+    motion_ids.into_iter()
+        .filter_map(|motion_id| animation.motion().get_motion(motion_id).map(|motion| (motion_id, motion)))
+        .flat_map(|(motion_id, motion)| {
+            let path        = motion.path().and_then(&ease).map(|curve| MotionEdit::SetPath(curve));
+            let rotation    = motion.rotation().and_then(&ease).map(|curve| MotionEdit::SetRotation(curve));
+            let scale       = motion.scale().and_then(&ease).map(|curve| MotionEdit::SetScale(curve));
+
+            path.into_iter()
+                .chain(rotation)
+                .chain(scale)
+                .map(move |motion_edit| AnimationEdit::Motion(motion_id, motion_edit))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -285,4 +330,52 @@ mod test {
 
         assert!(dynamic_move.len() == 1);
     }
+
+    #[test]
+    fn ease_moving_element() {
+        // Test animation with a single translate motion attached to every element
+        struct TestAnimation;
+
+        impl Animation for TestAnimation {
+            fn size(&self) -> (f64, f64) { unimplemented!() }
+            fn duration(&self) -> Duration { unimplemented!() }
+            fn frame_length(&self) -> Duration { unimplemented!() }
+            fn get_layer_ids(&self) -> Vec<u64> { unimplemented!() }
+            fn get_layer_with_id<'a>(&'a self, _layer_id: u64) -> Option<Arc<dyn Layer>> { unimplemented!() }
+            fn get_num_edits(&self) -> usize { unimplemented!() }
+            fn read_edit_log<'a>(&'a self, _range: Range<usize>) -> Box<dyn 'a+Stream<Item=AnimationEdit, Error=()>> { unimplemented!() }
+            fn motion<'a>(&'a self) -> &'a dyn AnimationMotion { self }
+        }
+
+        impl AnimationMotion for TestAnimation {
+            fn assign_element_id(&self) -> ElementId {
+                ElementId::Assigned(43)
+            }
+
+            fn get_motions_for_element(&self, _element_id: ElementId) -> Vec<ElementId> {
+                vec![ElementId::Assigned(42)]
+            }
+
+            fn get_elements_for_motion(&self, _motion_id: ElementId) -> Vec<ElementId> {
+                vec![ElementId::Assigned(1), ElementId::Assigned(2)]
+            }
+
+            fn get_motion(&self, _motion_id: ElementId) -> Option<Motion> {
+                Some(Motion::Translate(TranslateMotion {
+                    origin:     (0.0, 0.0),
+                    translate:  TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 0.0, Duration::from_millis(1000)))
+                }))
+            }
+        }
+
+        // Both elements share the same motion, so it should only be edited once
+        let animation   = TestAnimation;
+        let ease        = MotionEditAction::SetEasing(vec![ElementId::Assigned(1), ElementId::Assigned(2)], Duration::from_millis(500), Easing::EaseIn)
+            .to_animation_edits(&animation);
+
+        let original    = TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 0.0, Duration::from_millis(1000)));
+
+        assert!(ease.len() == 1);
+        assert!(ease[0] == AnimationEdit::Motion(ElementId::Assigned(42), MotionEdit::SetPath(original.set_section_easing(0, Easing::EaseIn))));
+    }
 }
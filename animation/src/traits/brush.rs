@@ -7,9 +7,11 @@ use super::brush_definition::*;
 use super::brush_drawing_style::*;
 
 use flo_canvas::*;
+use flo_float_encoder::*;
 
 use std::iter;
 use std::sync::*;
+use std::io::{Read, Write, Error, ErrorKind};
 
 ///
 /// Represents a segment of a brush stroke
@@ -29,6 +31,57 @@ pub struct BrushPoint {
     pub width: f32
 }
 
+///
+/// Writes a set of brush points to a stream
+///
+pub fn write_brush_points<Target: Write>(tgt: &mut Target, points: &[BrushPoint]) -> Result<(), Error> {
+    let mut last_point = BrushPoint { position: (0.0, 0.0), cp1: (0.0, 0.0), cp2: (0.0, 0.0), width: 0.0 };
+
+    for point in points {
+        // Brush points are squished in the same way as raw points (each value is stored relative to the value in the previous point)
+        squish_float(tgt, last_point.position.0 as f64, point.position.0 as f64)?;
+        squish_float(tgt, last_point.position.1 as f64, point.position.1 as f64)?;
+        squish_float(tgt, last_point.cp1.0 as f64, point.cp1.0 as f64)?;
+        squish_float(tgt, last_point.cp1.1 as f64, point.cp1.1 as f64)?;
+        squish_float(tgt, last_point.cp2.0 as f64, point.cp2.0 as f64)?;
+        squish_float(tgt, last_point.cp2.1 as f64, point.cp2.1 as f64)?;
+        squish_float(tgt, last_point.width as f64, point.width as f64)?;
+
+        last_point = *point;
+    }
+
+    Ok(())
+}
+
+///
+/// Reads a set of brush points from a stream
+///
+pub fn read_brush_points<Source: Read>(src: &mut Source) -> Result<Vec<BrushPoint>, Error> {
+    let mut last_point  = BrushPoint { position: (0.0, 0.0), cp1: (0.0, 0.0), cp2: (0.0, 0.0), width: 0.0 };
+    let mut result      = vec![];
+
+    loop {
+        // Stop if we reach the EOF while reading the X position
+        let x_pos = unsquish_float(src, last_point.position.0 as f64);
+        if x_pos.as_ref().err().map(|err| err.kind()) == Some(ErrorKind::UnexpectedEof) {
+            break;
+        }
+
+        // Read the rest of the point if we have an x-pos
+        let point = BrushPoint {
+            position:   (x_pos? as f32, unsquish_float(src, last_point.position.1 as f64)? as f32),
+            cp1:        (unsquish_float(src, last_point.cp1.0 as f64)? as f32, unsquish_float(src, last_point.cp1.1 as f64)? as f32),
+            cp2:        (unsquish_float(src, last_point.cp2.0 as f64)? as f32, unsquish_float(src, last_point.cp2.1 as f64)? as f32),
+            width:      unsquish_float(src, last_point.width as f64)? as f32
+        };
+
+        result.push(point);
+        last_point = point;
+    }
+
+    Ok(result)
+}
+
 ///
 /// Trait implemented by things that can draw brush strokes
 ///
@@ -52,9 +105,14 @@ pub trait Brush : Send+Sync {
     ///
     /// Renders a path using this brush's style
     ///
-    fn render_path<'a>(&'a self, _properties: &'a BrushProperties, path: &'a Path) -> Box<dyn 'a+Iterator<Item=Draw>> {
+    /// If the brush properties specify a gradient, the path is filled with a gradient that covers its bounds
+    ///
+    fn render_path<'a>(&'a self, properties: &'a BrushProperties, path: &'a Path) -> Box<dyn 'a+Iterator<Item=Draw>> {
+        let gradient = properties.fill_gradient(&Rect::from(path));
+
         Box::new(iter::once(Draw::NewPath)
             .chain(path.to_drawing())
+            .chain(gradient.map(|gradient| Draw::FillGradient(gradient)))
             .chain(iter::once(Draw::Fill)))
     }
 
@@ -77,3 +135,28 @@ pub trait Brush : Send+Sync {
         CombineResult::UnableToCombineFurther
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encode_decode_brush_points() {
+        let mut tgt = vec![];
+
+        write_brush_points(&mut tgt, &vec![
+            BrushPoint { position: (2.0, 2.0), cp1: (0.0, 0.0), cp2: (0.0, 0.0), width: 0.0 },
+            BrushPoint { position: (4.0, 2.4), cp1: (2.5, 2.1), cp2: (3.5, 2.3), width: 1.5 },
+            BrushPoint { position: (6.7, 4.2), cp1: (4.5, 2.5), cp2: (6.0, 3.8), width: 2.25 }
+        ]).unwrap();
+
+        let mut src: &[u8] = &tgt;
+        let read_points = read_brush_points(&mut src).unwrap();
+        assert!(read_points.len() == 3);
+
+        assert!((read_points[1].position.1-2.4).abs() < 0.01);
+        assert!((read_points[1].cp1.0-2.5).abs() < 0.01);
+        assert!((read_points[2].cp2.1-3.8).abs() < 0.01);
+        assert!((read_points[2].width-2.25).abs() < 0.01);
+    }
+}
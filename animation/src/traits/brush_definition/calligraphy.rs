@@ -0,0 +1,37 @@
+///
+/// Calligraphy brushes draw with a flat nib: the width of the line depends on the direction the
+/// nib is moving in relative to its angle. The actual behaviour is implemented by the `CalligraphyBrush`
+/// structure.
+///
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub struct CalligraphyDefinition {
+    /// Width at pressure 0%
+    pub min_width: f32,
+
+    /// Width at pressure 100% when moving across the nib
+    pub max_width: f32,
+
+    /// The angle of the nib, in degrees from the x axis
+    pub nib_angle: f32,
+
+    /// The thickness of the nib relative to its width (1.0 is a round nib, 0.0 is a perfectly flat nib)
+    pub nib_aspect_ratio: f32,
+
+    /// How much the tilt of the pen turns the nib (0.0 ignores the tilt, 1.0 follows the pen entirely)
+    pub tilt_influence: f32
+}
+
+impl CalligraphyDefinition {
+    ///
+    /// Creates the default calligraphy definition
+    ///
+    pub fn default() -> CalligraphyDefinition {
+        CalligraphyDefinition {
+            min_width:          0.25,
+            max_width:          8.0,
+            nib_angle:          45.0,
+            nib_aspect_ratio:   0.15,
+            tilt_influence:     0.5
+        }
+    }
+}
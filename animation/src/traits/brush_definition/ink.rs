@@ -1,3 +1,4 @@
+use super::pressure::*;
 
 ///
 /// Ink brushes are solid lines of varying width. This defines how they behave.
@@ -12,7 +13,15 @@ pub struct InkDefinition {
     pub max_width: f32,
 
     // Distance to scale up at the start of the brush stroke
-    pub scale_up_distance: f32
+    pub scale_up_distance: f32,
+
+    /// How the pressure of the brush stroke is mapped between the minimum and maximum widths
+    #[serde(default)]
+    pub pressure_curve: PressureCurve,
+
+    /// Where the pressure for the brush stroke comes from
+    #[serde(default)]
+    pub pressure_source: PressureSource
 }
 
 impl InkDefinition {
@@ -23,7 +32,9 @@ impl InkDefinition {
         InkDefinition {
             min_width:          0.25,
             max_width:          5.0,
-            scale_up_distance:  40.0
+            scale_up_distance:  40.0,
+            pressure_curve:     PressureCurve::Linear,
+            pressure_source:    PressureSource::Device
         }
     }
 
@@ -34,7 +45,9 @@ impl InkDefinition {
         InkDefinition {
             min_width:          3.0,
             max_width:          20.0,
-            scale_up_distance:  5.0
+            scale_up_distance:  5.0,
+            pressure_curve:     PressureCurve::Linear,
+            pressure_source:    PressureSource::Device
         }
     }
 }
@@ -1,6 +1,10 @@
 mod ink;
+mod calligraphy;
+mod pressure;
 
 pub use self::ink::*;
+pub use self::calligraphy::*;
+pub use self::pressure::*;
 
 ///
 /// Stores the definition of a particular brush
@@ -11,5 +15,8 @@ pub enum BrushDefinition {
     Simple,
 
     /// An ink brush with a particular definition
-    Ink(InkDefinition)
+    Ink(InkDefinition),
+
+    /// A calligraphy brush with a particular definition
+    Calligraphy(CalligraphyDefinition)
 }
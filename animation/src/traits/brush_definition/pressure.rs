@@ -0,0 +1,189 @@
+use super::super::raw_point::*;
+
+// Speed (in canvas units between input points) at which the simulated pressure is halved
+const VELOCITY_HALF_PRESSURE: f32 = 12.0;
+
+// The lowest pressure that can be simulated from the velocity of a brush stroke
+const VELOCITY_MIN_PRESSURE: f32 = 0.1;
+
+///
+/// Describes how the pressure from an input device is mapped to the width of a brush stroke
+///
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Debug)]
+pub enum PressureCurve {
+    /// The width follows the pressure directly
+    Linear,
+
+    /// Light pressure produces wider lines
+    Soft,
+
+    /// More pressure is needed to produce wide lines
+    Firm,
+
+    /// A bezier curve from (0, 0) to (1, 1) with the specified control points (x is the input pressure and y is the resulting pressure)
+    Bezier((f32, f32), (f32, f32))
+}
+
+///
+/// Describes where the pressure for a brush stroke comes from
+///
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Debug)]
+pub enum PressureSource {
+    /// Use the pressure reported by the input device
+    Device,
+
+    /// Simulate the pressure from the speed of the brush stroke (faster strokes are thinner), for devices such as mice that don't report pressure
+    Velocity
+}
+
+impl Default for PressureCurve {
+    fn default() -> PressureCurve { PressureCurve::Linear }
+}
+
+impl Default for PressureSource {
+    fn default() -> PressureSource { PressureSource::Device }
+}
+
+impl PressureCurve {
+    ///
+    /// Returns the control points of the bezier curve for this pressure curve
+    ///
+    pub fn control_points(&self) -> ((f32, f32), (f32, f32)) {
+        match self {
+            PressureCurve::Linear           => ((1.0/3.0, 1.0/3.0), (2.0/3.0, 2.0/3.0)),
+            PressureCurve::Soft             => ((0.0, 0.5), (0.5, 1.0)),
+            PressureCurve::Firm             => ((0.5, 0.0), (1.0, 0.5)),
+            PressureCurve::Bezier(cp1, cp2) => (*cp1, *cp2)
+        }
+    }
+
+    ///
+    /// Maps an input pressure (0-1) to the pressure that should be used for the brush stroke
+    ///
+    pub fn pressure(&self, input: f32) -> f32 {
+        let input = input.max(0.0).min(1.0);
+
+        match self {
+            PressureCurve::Linear   => input,
+            _                       => {
+                // Control points are clamped so the curve always moves forward along the x axis
+                let ((x1, y1), (x2, y2))    = self.control_points();
+                let (x1, x2)                = (x1.max(0.0).min(1.0), x2.max(0.0).min(1.0));
+
+                // Find the position on the curve for the input pressure
+                let mut min_t   = 0.0;
+                let mut max_t   = 1.0;
+                for _ in 0..24 {
+                    let t = (min_t + max_t) / 2.0;
+
+                    if cubic_bezier(t, 0.0, x1, x2, 1.0) < input {
+                        min_t = t;
+                    } else {
+                        max_t = t;
+                    }
+                }
+
+                cubic_bezier((min_t + max_t) / 2.0, 0.0, y1, y2, 1.0).max(0.0).min(1.0)
+            }
+        }
+    }
+}
+
+impl PressureSource {
+    ///
+    /// Returns the pressure to use for each of the points in a brush stroke
+    ///
+    pub fn pressure_for_points(&self, points: &[RawPoint]) -> Vec<f32> {
+        match self {
+            PressureSource::Device      => points.iter().map(|point| point.pressure).collect(),
+            PressureSource::Velocity    => {
+                (0..points.len())
+                    .map(|index| {
+                        // Use the distance between the neighbouring points as the speed
+                        let before      = points[index.saturating_sub(1)].position;
+                        let after       = points[(index+1).min(points.len()-1)].position;
+                        let num_steps   = if index > 0 && index+1 < points.len() { 2.0 } else { 1.0 };
+                        let (dx, dy)    = (after.0-before.0, after.1-before.1);
+                        let speed       = (dx*dx + dy*dy).sqrt() / num_steps;
+
+                        // Pressure falls off as the speed increases
+                        let pressure    = 1.0 / (1.0 + speed/VELOCITY_HALF_PRESSURE);
+
+                        VELOCITY_MIN_PRESSURE + pressure * (1.0-VELOCITY_MIN_PRESSURE)
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+///
+/// Evaluates a one-dimensional cubic bezier curve at the position t
+///
+fn cubic_bezier(t: f32, w1: f32, w2: f32, w3: f32, w4: f32) -> f32 {
+    let one_minus_t = 1.0 - t;
+
+    one_minus_t*one_minus_t*one_minus_t*w1
+        + 3.0*one_minus_t*one_minus_t*t*w2
+        + 3.0*one_minus_t*t*t*w3
+        + t*t*t*w4
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn linear_curve_is_unchanged() {
+        assert!(PressureCurve::Linear.pressure(0.25) == 0.25);
+        assert!(PressureCurve::Linear.pressure(0.75) == 0.75);
+    }
+
+    #[test]
+    fn bezier_with_linear_control_points_is_linear() {
+        let curve = PressureCurve::Bezier((1.0/3.0, 1.0/3.0), (2.0/3.0, 2.0/3.0));
+
+        assert!((curve.pressure(0.25) - 0.25).abs() < 0.001);
+        assert!((curve.pressure(0.8) - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn curves_pass_through_end_points() {
+        for curve in vec![PressureCurve::Soft, PressureCurve::Firm, PressureCurve::Bezier((0.9, 0.1), (0.1, 0.9))] {
+            assert!(curve.pressure(0.0).abs() < 0.001);
+            assert!((curve.pressure(1.0) - 1.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn soft_and_firm_curves() {
+        assert!(PressureCurve::Soft.pressure(0.5) > 0.5);
+        assert!(PressureCurve::Firm.pressure(0.5) < 0.5);
+    }
+
+    #[test]
+    fn device_pressure_is_unchanged() {
+        let points = vec![
+            RawPoint { position: (0.0, 0.0), pressure: 0.2, tilt: (0.0, 0.0) },
+            RawPoint { position: (10.0, 0.0), pressure: 0.7, tilt: (0.0, 0.0) }
+        ];
+
+        assert!(PressureSource::Device.pressure_for_points(&points) == vec![0.2, 0.7]);
+    }
+
+    #[test]
+    fn faster_strokes_have_less_pressure() {
+        let points = vec![
+            RawPoint::from((0.0, 0.0)),
+            RawPoint::from((1.0, 0.0)),
+            RawPoint::from((2.0, 0.0)),
+            RawPoint::from((22.0, 0.0)),
+            RawPoint::from((42.0, 0.0))
+        ];
+        let pressure = PressureSource::Velocity.pressure_for_points(&points);
+
+        assert!(pressure.len() == 5);
+        assert!(pressure[1] > pressure[3]);
+        assert!(pressure.iter().all(|pressure| *pressure >= VELOCITY_MIN_PRESSURE && *pressure <= 1.0));
+    }
+}
@@ -1,12 +1,29 @@
+use super::path::*;
+
 use flo_canvas::*;
 
+///
+/// Describes a gradient used to fill the shapes drawn by a brush
+///
+/// Gradients start at the brush colour and are positioned relative to the bounds of the shape
+/// being filled, so the same gradient can be used for shapes of any size.
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum BrushGradient {
+    /// Linear gradient across the shape to the specified colour, at an angle in degrees (0 is left to right, 90 is bottom to top)
+    Linear(Color, f32),
+
+    /// Radial gradient from the center of the shape out to the specified colour at its corners
+    Radial(Color)
+}
+
 ///
 /// Standard properties for a brush stroke
 ///
 /// These are the properties that are independent of the brush type.
 /// Properties that define a brush can be found in brush_definition.
 ///
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub struct BrushProperties {
     /// The size of the brush stroke
     pub size: f32,
@@ -15,7 +32,52 @@ pub struct BrushProperties {
     pub opacity: f32,
 
     /// The colour of the brush stroke
-    pub color: Color
+    pub color: Color,
+
+    /// The gradient used when filling shapes with this brush (or None to fill with a flat colour)
+    #[serde(default)]
+    pub gradient: Option<BrushGradient>
+}
+
+impl BrushGradient {
+    ///
+    /// The colour at the end of this gradient
+    ///
+    pub fn end_color(&self) -> Color {
+        match self {
+            BrushGradient::Linear(color, _) => *color,
+            BrushGradient::Radial(color)    => *color
+        }
+    }
+
+    ///
+    /// Creates the canvas gradient that covers a particular set of bounds, starting at the specified colour
+    ///
+    pub fn to_gradient(&self, start_color: Color, bounds: &Rect) -> Gradient {
+        let center          = ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0);
+        let (width, height) = ((bounds.x2-bounds.x1).abs(), (bounds.y2-bounds.y1).abs());
+        let stops           = vec![
+            GradientStop::new(0.0, start_color),
+            GradientStop::new(1.0, self.end_color())
+        ];
+
+        match self {
+            BrushGradient::Linear(_, angle) => {
+                // The gradient runs along a line through the center of the shape, long enough to reach its corners
+                let angle       = angle.to_radians();
+                let (dx, dy)    = (angle.cos(), angle.sin());
+                let extent      = (width/2.0)*dx.abs() + (height/2.0)*dy.abs();
+
+                Gradient::Linear((center.0 - dx*extent, center.1 - dy*extent), (center.0 + dx*extent, center.1 + dy*extent), stops)
+            },
+
+            BrushGradient::Radial(_) => {
+                let radius      = (width*width + height*height).sqrt()/2.0;
+
+                Gradient::Radial(center, radius, stops)
+            }
+        }
+    }
 }
 
 impl BrushProperties {
@@ -26,7 +88,72 @@ impl BrushProperties {
         BrushProperties {
             size:       5.0,
             opacity:    1.0,
-            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0)
+            color:      Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            gradient:   None
+        }
+    }
+
+    ///
+    /// Returns the canvas gradient that these properties will use to fill a shape with the specified bounds
+    ///
+    /// The brush opacity is applied to both ends of the gradient.
+    ///
+    pub fn fill_gradient(&self, bounds: &Rect) -> Option<Gradient> {
+        let opacity = self.opacity;
+        let apply   = |color: Color| color.with_alpha(color.to_rgba_components().3 * opacity);
+
+        self.gradient.map(|gradient| {
+            let gradient = match gradient {
+                BrushGradient::Linear(end_color, angle) => BrushGradient::Linear(apply(end_color), angle),
+                BrushGradient::Radial(end_color)        => BrushGradient::Radial(apply(end_color))
+            };
+
+            gradient.to_gradient(apply(self.color), bounds)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn linear_gradient_spans_bounds() {
+        let gradient = BrushGradient::Linear(Color::Rgba(1.0, 1.0, 1.0, 1.0), 0.0);
+        let gradient = gradient.to_gradient(Color::Rgba(0.0, 0.0, 0.0, 1.0), &Rect::with_points(10.0, 20.0, 50.0, 40.0));
+
+        match gradient {
+            Gradient::Linear((x1, y1), (x2, y2), stops) => {
+                assert!((x1-10.0).abs() < 0.01);
+                assert!((y1-30.0).abs() < 0.01);
+                assert!((x2-50.0).abs() < 0.01);
+                assert!((y2-30.0).abs() < 0.01);
+                assert!(stops.len() == 2);
+                assert!(stops[1].color == Color::Rgba(1.0, 1.0, 1.0, 1.0));
+            },
+
+            _ => panic!("Expected a linear gradient")
         }
     }
+
+    #[test]
+    fn radial_gradient_reaches_corners() {
+        let gradient = BrushGradient::Radial(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+        let gradient = gradient.to_gradient(Color::Rgba(0.0, 0.0, 0.0, 1.0), &Rect::with_points(0.0, 0.0, 30.0, 40.0));
+
+        match gradient {
+            Gradient::Radial((x, y), radius, _) => {
+                assert!((x-15.0).abs() < 0.01);
+                assert!((y-20.0).abs() < 0.01);
+                assert!((radius-25.0).abs() < 0.01);
+            },
+
+            _ => panic!("Expected a radial gradient")
+        }
+    }
+
+    #[test]
+    fn flat_brush_has_no_fill_gradient() {
+        assert!(BrushProperties::new().fill_gradient(&Rect::with_points(0.0, 0.0, 10.0, 10.0)).is_none());
+    }
 }
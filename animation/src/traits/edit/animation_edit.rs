@@ -3,10 +3,12 @@ use super::layer_edit::*;
 use super::motion_edit::*;
 use super::element_edit::*;
 
+use std::time::Duration;
+
 ///
 /// Represents an edit to an animation object
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum AnimationEdit {
     /// Edit to an existing layer
     Layer(u64, LayerEdit),
@@ -21,10 +23,20 @@ pub enum AnimationEdit {
     /// Sets the canvas size for this animation
     SetSize(f64, f64),
 
+    /// Sets the length of this animation
+    SetDuration(Duration),
+
+    /// Sets the length of a single frame in this animation (ie, changes the frame rate)
+    SetFrameLength(Duration),
+
     /// Adds a new layer and assigns it the specified ID
     /// Has no effect if a layer with that ID already exists
     AddNewLayer(u64),
 
+    /// Adds a new group layer (a layer that contains other layers) and assigns it the specified ID
+    /// Has no effect if a layer with that ID already exists
+    AddNewGroupLayer(u64),
+
     /// Removes the layer with the specified ID
     RemoveLayer(u64)
 }
@@ -38,8 +50,9 @@ impl AnimationEdit {
         use self::AnimationEdit::*;
 
         match self {
-            Layer(layer_id, layer_edit) => Layer(layer_id, layer_edit.assign_element_id(assign_element_id)),
-            other                       => other
+            Layer(layer_id, layer_edit)         => Layer(layer_id, layer_edit.assign_element_id(assign_element_id)),
+            Element(element_ids, element_edit)  => Element(element_ids, element_edit.assign_element_id(assign_element_id)),
+            other                               => other
         }
     }
 }
@@ -1,12 +1,13 @@
 use super::element_id::*;
 use super::super::path::*;
+use super::super::vector::*;
 
 use std::sync::*;
 
 ///
 /// Possible element ordering operations
 ///
-#[derive(Clone, PartialEq, Debug, Copy)]
+#[derive(Clone, PartialEq, Debug, Copy, Serialize, Deserialize)]
 pub enum ElementOrdering {
     InFront,
     Behind,
@@ -18,7 +19,7 @@ pub enum ElementOrdering {
 ///
 /// Represents an edit to an element within a frame
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum ElementEdit {
     /// Adds an attachment to this element
     AddAttachment(ElementId),
@@ -40,5 +41,28 @@ pub enum ElementEdit {
     Delete,
 
     /// Detaches elements from the frame
-    DetachFromFrame
+    DetachFromFrame,
+
+    /// Combines the elements into a new group element with the specified ID. The elements are removed from the frame
+    /// and become part of the group, in the order that they are supplied (so the first element is the one that other
+    /// elements are subtracted from or masked by)
+    Group(ElementId, GroupType),
+
+    /// Replaces group elements with the elements that they contain
+    Ungroup
+}
+
+impl ElementEdit {
+    ///
+    /// If this edit contains an unassigned element ID, calls the specified function to supply a new
+    /// element ID. If the edit already has an ID, leaves it unchanged.
+    ///
+    pub fn assign_element_id<AssignFn: FnOnce() -> i64>(self, assign_element_id: AssignFn) -> ElementEdit {
+        use self::ElementEdit::*;
+
+        match self {
+            Group(ElementId::Unassigned, group_type)    => Group(ElementId::Assigned(assign_element_id()), group_type),
+            other                                       => other
+        }
+    }
 }
@@ -1,17 +1,21 @@
 use super::element_id::*;
 
 use super::super::path::*;
+use super::super::brush::*;
 use super::super::raw_point::*;
 use super::super::brush_properties::*;
 use super::super::brush_definition::*;
 use super::super::brush_drawing_style::*;
+use super::super::text_style::*;
+
+use flo_canvas::{CanvasImage, Transform2D};
 
 use std::sync::*;
 
 ///
 /// Represents an edit to a path
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum PathEdit {
     /// Creates a new path consisting of the specified path components
     CreatePath(ElementId, Arc<Vec<PathComponent>>),
@@ -23,10 +27,42 @@ pub enum PathEdit {
     BrushProperties(ElementId, BrushProperties),
 }
 
+impl PathEdit {
+    ///
+    /// The element ID for this edit
+    ///
+    pub fn id(&self) -> ElementId {
+        use self::PathEdit::*;
+
+        match self {
+            CreatePath(id, _)       => *id,
+            SelectBrush(id, _, _)   => *id,
+            BrushProperties(id, _)  => *id
+        }
+    }
+
+    ///
+    /// If this edit contains an unassigned element ID, calls the specified function to supply a new
+    /// element ID. If the edit already has an ID, leaves it unchanged.
+    ///
+    pub fn assign_element_id<AssignFn: FnOnce() -> i64>(self, assign_element_id: AssignFn) -> PathEdit {
+        use self::PathEdit::*;
+        use self::ElementId::*;
+
+        match self {
+            CreatePath(Unassigned, components)              => CreatePath(Assigned(assign_element_id()), components),
+            SelectBrush(Unassigned, brush_def, brush_style) => SelectBrush(Assigned(assign_element_id()), brush_def, brush_style),
+            BrushProperties(Unassigned, brush_props)        => BrushProperties(Assigned(assign_element_id()), brush_props),
+
+            assigned => assigned
+        }
+    }
+}
+
 ///
 /// Represents an edit involving painting
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum PaintEdit {
     /// Selects the brush with the specified definition for painting
     SelectBrush(ElementId, BrushDefinition, BrushDrawingStyle),
@@ -35,7 +71,13 @@ pub enum PaintEdit {
     BrushProperties(ElementId, BrushProperties),
 
     /// Draws a brush stroke using the current brush and the specified set of input points
-    BrushStroke(ElementId, Arc<Vec<RawPoint>>)
+    BrushStroke(ElementId, Arc<Vec<RawPoint>>),
+
+    /// Draws a brush stroke using the current brush from points that have already been processed by a brush
+    ///
+    /// This is used to re-create a brush stroke exactly (for example, when pasting a brush stroke that was copied
+    /// from another frame)
+    CreateBrushStroke(ElementId, Arc<Vec<BrushPoint>>)
 }
 
 impl PaintEdit {
@@ -46,9 +88,10 @@ impl PaintEdit {
         use self::PaintEdit::*;
 
         match self {
-            SelectBrush(id, _, _)   => *id,
-            BrushProperties(id, _)  => *id,
-            BrushStroke(id, _)      => *id
+            SelectBrush(id, _, _)       => *id,
+            BrushProperties(id, _)      => *id,
+            BrushStroke(id, _)          => *id,
+            CreateBrushStroke(id, _)    => *id
         }
     }
 
@@ -64,6 +107,81 @@ impl PaintEdit {
             SelectBrush(Unassigned, brush_def, brush_style) => SelectBrush(Assigned(assign_element_id()), brush_def, brush_style),
             BrushProperties(Unassigned, brush_props)        => BrushProperties(Assigned(assign_element_id()), brush_props),
             BrushStroke(Unassigned, points)                 => BrushStroke(Assigned(assign_element_id()), points),
+            CreateBrushStroke(Unassigned, points)           => CreateBrushStroke(Assigned(assign_element_id()), points),
+
+            assigned => assigned
+        }
+    }
+}
+
+///
+/// Represents an edit involving text
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TextEdit {
+    /// Creates a new text element with its first baseline starting at the specified position
+    CreateText(ElementId, TextStyle, (f32, f32), String)
+}
+
+impl TextEdit {
+    ///
+    /// The element ID for this edit
+    ///
+    pub fn id(&self) -> ElementId {
+        use self::TextEdit::*;
+
+        match self {
+            CreateText(id, _, _, _) => *id
+        }
+    }
+
+    ///
+    /// If this edit contains an unassigned element ID, calls the specified function to supply a new
+    /// element ID. If the edit already has an ID, leaves it unchanged.
+    ///
+    pub fn assign_element_id<AssignFn: FnOnce() -> i64>(self, assign_element_id: AssignFn) -> TextEdit {
+        use self::TextEdit::*;
+        use self::ElementId::*;
+
+        match self {
+            CreateText(Unassigned, style, position, text)   => CreateText(Assigned(assign_element_id()), style, position, text),
+
+            assigned => assigned
+        }
+    }
+}
+
+///
+/// Represents an edit involving a bitmap image
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ImageEdit {
+    /// Creates a new image element, drawn into the unit square mapped by the specified transform
+    CreateImage(ElementId, CanvasImage, Transform2D)
+}
+
+impl ImageEdit {
+    ///
+    /// The element ID for this edit
+    ///
+    pub fn id(&self) -> ElementId {
+        use self::ImageEdit::*;
+
+        match self {
+            CreateImage(id, _, _) => *id
+        }
+    }
+
+    ///
+    /// If this edit contains an unassigned element ID, calls the specified function to supply a new
+    /// element ID. If the edit already has an ID, leaves it unchanged.
+    ///
+    pub fn assign_element_id<AssignFn: FnOnce() -> i64>(self, assign_element_id: AssignFn) -> ImageEdit {
+        use self::ImageEdit::*;
+        use self::ElementId::*;
+
+        match self {
+            CreateImage(Unassigned, image, transform)   => CreateImage(Assigned(assign_element_id()), image, transform),
 
             assigned => assigned
         }
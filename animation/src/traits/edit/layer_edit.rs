@@ -1,5 +1,7 @@
 use super::frame_edit::*;
 
+use flo_canvas::BlendMode;
+
 use std::time::Duration;
 
 ///
@@ -11,13 +13,14 @@ use std::time::Duration;
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum LayerEditType {
-    Vector
+    Vector,
+    Group
 }
 
 ///
 /// Represents an edit to a layer
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum LayerEdit {
     /// Edit to a frame at a specific time
     Paint(Duration, PaintEdit),
@@ -25,6 +28,12 @@ pub enum LayerEdit {
     /// Edit to a path at a specific time
     Path(Duration, PathEdit),
 
+    /// Edit to the text at a specific time
+    Text(Duration, TextEdit),
+
+    /// Edit to the images at a specific time
+    Image(Duration, ImageEdit),
+
     /// Adds a keyframe at a particular point in time
     ///
     /// Edits don't have to correspond to a keyframe - instead, keyframes
@@ -40,7 +49,24 @@ pub enum LayerEdit {
     /// Sets this layer so that it is at a particular index in the ordering (moving the existing layers out of the way)
     ///
     /// If the ordering is greater than the number of layers,
-    SetOrdering(u32)
+    SetOrdering(u32),
+
+    /// Shows or hides this layer
+    SetVisible(bool),
+
+    /// Locks or unlocks this layer (locked layers should not be edited by the drawing tools)
+    SetLocked(bool),
+
+    /// Sets the opacity of this layer (0.0 is fully transparent, 1.0 fully opaque)
+    SetOpacity(f64),
+
+    /// Sets the mode used to blend this layer with the layers underneath it
+    SetBlendMode(BlendMode),
+
+    /// Moves this layer into the group layer with the specified ID, or out of any group if the ID is None
+    ///
+    /// Has no effect if the parent is not a group layer or if it would make a group contain itself
+    SetParentGroup(Option<u64>)
 }
 
 impl LayerEdit {
@@ -53,6 +79,9 @@ impl LayerEdit {
 
         match self {
             Paint(when, paint_edit) => Paint(when, paint_edit.assign_element_id(assign_element_id)),
+            Path(when, path_edit)   => Path(when, path_edit.assign_element_id(assign_element_id)),
+            Text(when, text_edit)   => Text(when, text_edit.assign_element_id(assign_element_id)),
+            Image(when, image_edit) => Image(when, image_edit.assign_element_id(assign_element_id)),
             other                   => other
         }
     }
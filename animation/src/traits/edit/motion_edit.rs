@@ -4,7 +4,7 @@ use super::super::time_path::*;
 ///
 /// Represents an edit that creates a motion description on a layer
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum MotionEdit {
     /// Creates a new motion with this element ID
     ///
@@ -20,6 +20,12 @@ pub enum MotionEdit {
     /// Changes the origin point for this motion
     SetOrigin(f32, f32),
 
-    /// Sets the time curve for this motion
+    /// Sets the time curve for this motion (the translation for `Translate` and `Transform` motions)
     SetPath(TimeCurve),
+
+    /// Sets the rotation curve for a `Rotate` or `Transform` motion (the x coordinate is the angle in degrees)
+    SetRotation(TimeCurve),
+
+    /// Sets the scale curve for a `Scale` or `Transform` motion (the x and y coordinates are the scale factors)
+    SetScale(TimeCurve),
 }
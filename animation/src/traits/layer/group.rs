@@ -0,0 +1,130 @@
+use super::layer::*;
+use super::super::animation::*;
+
+use flo_canvas::BlendMode;
+
+use std::collections::{HashMap, HashSet};
+
+///
+/// Represents a layer that contains other layers
+///
+/// Group layers have no content of their own. The layers in a group share its visibility and opacity: hiding
+/// the group hides every layer in it, and the layers are composited as a unit, so the opacity and blend mode
+/// of the group are applied once to the combined image of all of its layers.
+///
+pub trait GroupLayer : Send {
+    ///
+    /// The IDs of the layers that are directly contained in this group
+    ///
+    fn child_layer_ids(&self) -> Vec<u64>;
+}
+
+///
+/// Returns the IDs of the layers in an animation ordered so that the layers in each group immediately follow
+/// the group that contains them
+///
+/// This is the order that layers should be displayed and rendered in: layers that are not in a group keep
+/// the order they have in `get_layer_ids()`.
+///
+pub fn layer_ids_in_group_order(animation: &dyn Animation) -> Vec<u64> {
+    let layer_ids       = animation.get_layer_ids();
+    let known_layers    = layer_ids.iter().cloned().collect::<HashSet<_>>();
+
+    // Find the children of each group (layers whose parent no longer exists are treated as being at the top level)
+    let mut top_level   = vec![];
+    let mut children    = HashMap::new();
+
+    for layer_id in layer_ids.iter() {
+        let parent = animation.get_layer_with_id(*layer_id).and_then(|layer| layer.parent_group());
+
+        match parent {
+            Some(parent_id) if known_layers.contains(&parent_id)    => children.entry(parent_id).or_insert_with(|| vec![]).push(*layer_id),
+            _                                                       => top_level.push(*layer_id)
+        }
+    }
+
+    // Visit the layers depth-first, starting from the top level
+    let mut result      = vec![];
+    let mut visited     = HashSet::new();
+    let mut to_visit    = top_level.into_iter().rev().collect::<Vec<_>>();
+
+    while let Some(layer_id) = to_visit.pop() {
+        if !visited.insert(layer_id) { continue; }
+
+        result.push(layer_id);
+
+        if let Some(child_ids) = children.get(&layer_id) {
+            to_visit.extend(child_ids.iter().rev().cloned());
+        }
+    }
+
+    // Layers that are only reachable through a loop of groups are added at the end so they're not lost
+    result.extend(layer_ids.into_iter().filter(|layer_id| !visited.contains(layer_id)));
+
+    result
+}
+
+///
+/// Returns the ID of the outermost group that contains a layer, or the ID of the layer itself if it's not in a group
+///
+/// Layers are composited as a unit with the other layers that share this ID: they should all be drawn into the
+/// same canvas layer, in the order returned by `layer_ids_in_group_order()`.
+///
+pub fn composited_layer_id(animation: &dyn Animation, layer: &dyn Layer) -> u64 {
+    let mut composited_id   = layer.id();
+
+    let mut visited         = HashSet::new();
+    let mut parent          = layer.parent_group();
+    visited.insert(layer.id());
+
+    while let Some(parent_id) = parent {
+        // Stop if the groups form a loop or the parent has been removed
+        if !visited.insert(parent_id) { break; }
+
+        let group = match animation.get_layer_with_id(parent_id) {
+            Some(group) => group,
+            None        => break
+        };
+
+        composited_id   = parent_id;
+        parent          = group.parent_group();
+    }
+
+    composited_id
+}
+
+///
+/// Returns the visibility of a layer and the opacity and blend mode to use for the canvas layer it's drawn into
+///
+/// A layer is only visible if all of the groups that contain it are visible. Layers in a group are drawn into a
+/// single canvas layer (see `composited_layer_id()`), which is composited using the opacity and blend mode of the
+/// outermost group. Canvas layers can't be nested, so the opacity and blend mode of layers and groups inside a
+/// group are not applied separately.
+///
+pub fn composited_layer_appearance(animation: &dyn Animation, layer: &dyn Layer) -> (bool, f64, BlendMode) {
+    let mut visible     = layer.is_visible();
+    let mut opacity     = layer.opacity();
+    let mut blend_mode  = layer.blend_mode();
+
+    let mut visited     = HashSet::new();
+    let mut parent      = layer.parent_group();
+    visited.insert(layer.id());
+
+    while let Some(parent_id) = parent {
+        // Stop if the groups form a loop or the parent has been removed
+        if !visited.insert(parent_id) { break; }
+
+        let group = match animation.get_layer_with_id(parent_id) {
+            Some(group) => group,
+            None        => break
+        };
+
+        visible     = visible && group.is_visible();
+        opacity     = group.opacity();
+        blend_mode  = group.blend_mode();
+
+        parent      = group.parent_group();
+    }
+
+    (visible, opacity, blend_mode)
+}
@@ -1,8 +1,11 @@
 use super::vector::*;
+use super::group::*;
 use super::super::edit::*;
 use super::super::frame::*;
 use super::super::cache::*;
 
+use flo_canvas::BlendMode;
+
 use std::u32;
 use std::sync::*;
 use std::time::Duration;
@@ -27,6 +30,31 @@ pub trait Layer : Send+Sync {
     ///
     fn supported_edit_types(&self) -> Vec<LayerEditType>;
 
+    ///
+    /// True if this layer should be rendered
+    ///
+    fn is_visible(&self) -> bool { true }
+
+    ///
+    /// True if this layer is locked and should not be edited by the drawing tools
+    ///
+    fn is_locked(&self) -> bool { false }
+
+    ///
+    /// The opacity of this layer, between 0.0 and 1.0
+    ///
+    fn opacity(&self) -> f64 { 1.0 }
+
+    ///
+    /// The blend mode to use when rendering this layer on top of the layers underneath it
+    ///
+    fn blend_mode(&self) -> BlendMode { BlendMode::SourceOver }
+
+    ///
+    /// The ID of the group layer that contains this layer, or None if this layer is not in a group
+    ///
+    fn parent_group(&self) -> Option<u64> { None }
+
     ///
     /// Retrieves a frame from this layer with the specified parameters
     ///
@@ -54,6 +82,11 @@ pub trait Layer : Send+Sync {
     ///
     fn as_vector_layer<'a>(&'a self) -> Option<Box<dyn 'a+Deref<Target=dyn 'a+VectorLayer>>>;
 
+    ///
+    /// Retrieves the definition of this layer as a group layer
+    ///
+    fn as_group_layer<'a>(&'a self) -> Option<Box<dyn 'a+Deref<Target=dyn 'a+GroupLayer>>>;
+
     ///
     /// Retrieves the canvas cache at the specified time
     ///
@@ -1,5 +1,7 @@
 mod layer;
 mod vector;
+mod group;
 
 pub use self::layer::*;
 pub use self::vector::*;
+pub use self::group::*;
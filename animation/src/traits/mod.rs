@@ -10,6 +10,7 @@ mod brush;
 mod brush_properties;
 mod brush_definition;
 mod brush_drawing_style;
+mod text_style;
 mod vector;
 mod path;
 mod time_path;
@@ -29,6 +30,7 @@ pub use self::brush::*;
 pub use self::brush_properties::*;
 pub use self::brush_definition::*;
 pub use self::brush_drawing_style::*;
+pub use self::text_style::*;
 pub use self::vector::*;
 pub use self::path::*;
 pub use self::time_path::*;
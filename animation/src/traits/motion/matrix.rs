@@ -0,0 +1,219 @@
+use super::super::path::*;
+use super::super::brush::*;
+use super::super::time_path::*;
+
+use std::ops::Range;
+use std::f32::consts::PI;
+use std::time::Duration;
+
+///
+/// A 2D affine transformation matrix used to implement the rotate, scale and transform motions
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub (super) struct MotionMatrix(pub [[f32; 3]; 2]);
+
+impl MotionMatrix {
+    ///
+    /// The identity transformation
+    ///
+    pub fn identity() -> MotionMatrix {
+        MotionMatrix([
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0]
+        ])
+    }
+
+    ///
+    /// A matrix that translates by the specified offset
+    ///
+    pub fn translate(dx: f32, dy: f32) -> MotionMatrix {
+        MotionMatrix([
+            [1.0, 0.0, dx],
+            [0.0, 1.0, dy]
+        ])
+    }
+
+    ///
+    /// A matrix that rotates anticlockwise by the specified number of degrees about the origin
+    ///
+    pub fn rotate(degrees: f32) -> MotionMatrix {
+        let radians = degrees * PI / 180.0;
+        let (s, c)  = radians.sin_cos();
+
+        MotionMatrix([
+            [c, -s, 0.0],
+            [s,  c, 0.0]
+        ])
+    }
+
+    ///
+    /// A matrix that scales by the specified factors about the origin
+    ///
+    pub fn scale(sx: f32, sy: f32) -> MotionMatrix {
+        MotionMatrix([
+            [sx, 0.0, 0.0],
+            [0.0, sy, 0.0]
+        ])
+    }
+
+    ///
+    /// Returns the matrix that applies `self` followed by `next`
+    ///
+    pub fn then(&self, next: &MotionMatrix) -> MotionMatrix {
+        let MotionMatrix(a) = next;
+        let MotionMatrix(b) = self;
+
+        MotionMatrix([
+            [a[0][0]*b[0][0] + a[0][1]*b[1][0], a[0][0]*b[0][1] + a[0][1]*b[1][1], a[0][0]*b[0][2] + a[0][1]*b[1][2] + a[0][2]],
+            [a[1][0]*b[0][0] + a[1][1]*b[1][0], a[1][0]*b[0][1] + a[1][1]*b[1][1], a[1][0]*b[0][2] + a[1][1]*b[1][2] + a[1][2]]
+        ])
+    }
+
+    ///
+    /// Returns a matrix that performs this transformation about a pivot point instead of the origin
+    ///
+    pub fn about(&self, pivot: (f32, f32)) -> MotionMatrix {
+        MotionMatrix::translate(-pivot.0, -pivot.1)
+            .then(self)
+            .then(&MotionMatrix::translate(pivot.0, pivot.1))
+    }
+
+    ///
+    /// Returns the inverse of this matrix, or None if it cannot be inverted (eg, because it scales by 0)
+    ///
+    pub fn invert(&self) -> Option<MotionMatrix> {
+        let MotionMatrix(m) = self;
+        let det             = m[0][0]*m[1][1] - m[0][1]*m[1][0];
+
+        if det.abs() < 1e-6 {
+            None
+        } else {
+            let a =  m[1][1] / det;
+            let b = -m[0][1] / det;
+            let c = -m[1][0] / det;
+            let d =  m[0][0] / det;
+
+            Some(MotionMatrix([
+                [a, b, -(a*m[0][2] + b*m[1][2])],
+                [c, d, -(c*m[0][2] + d*m[1][2])]
+            ]))
+        }
+    }
+
+    ///
+    /// Transforms a single coordinate by this matrix
+    ///
+    #[inline]
+    pub fn transform_point(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let MotionMatrix(m) = self;
+
+        (m[0][0]*x + m[0][1]*y + m[0][2], m[1][0]*x + m[1][1]*y + m[1][2])
+    }
+
+    ///
+    /// The factor that widths are multiplied by when transformed by this matrix
+    ///
+    #[inline]
+    pub fn width_factor(&self) -> f32 {
+        let MotionMatrix(m) = self;
+
+        (m[0][0]*m[1][1] - m[0][1]*m[1][0]).abs().sqrt()
+    }
+
+    ///
+    /// Transforms a set of brush points by this matrix
+    ///
+    pub fn transform_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(self, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        let width_factor = self.width_factor();
+
+        Box::new(points.map(move |point| {
+            BrushPoint {
+                position:   self.transform_point(point.position),
+                cp1:        self.transform_point(point.cp1),
+                cp2:        self.transform_point(point.cp2),
+                width:      point.width * width_factor
+            }
+        }))
+    }
+
+    ///
+    /// Transforms a set of path points by this matrix
+    ///
+    pub fn transform_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(self, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        Box::new(points.map(move |point| {
+            let (x, y) = self.transform_point((point.x(), point.y()));
+            PathPoint::new(x, y)
+        }))
+    }
+}
+
+///
+/// Converts a time to milliseconds, as used by the time curves in a motion
+///
+#[inline]
+pub (super) fn time_to_millis(time: Duration) -> f32 {
+    ((time.as_secs() as f32) * 1_000.0) + ((time.subsec_nanos() as f32) / 1_000_000.0)
+}
+
+///
+/// Finds the point on a time curve at the specified time, or None if the curve doesn't cover that time
+///
+pub (super) fn curve_point_at_time(curve: &TimeCurve, millis: f32) -> Option<TimePoint> {
+    if curve.points.len() == 0 {
+        // Curves with no points (eg, ones that have never been set in the database) have no effect
+        None
+    } else {
+        curve.point_at_time(millis)
+    }
+}
+
+///
+/// The range of times covered by a time curve, in milliseconds
+///
+pub (super) fn curve_range_millis(curve: &TimeCurve) -> Range<f32> {
+    if curve.points.len() == 0 {
+        0.0..0.0
+    } else {
+        let start   = curve.points[0].point.milliseconds();
+        let end     = curve.points.last().unwrap().point.milliseconds();
+
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0-b.0).abs() < 0.001 && (a.1-b.1).abs() < 0.001
+    }
+
+    #[test]
+    fn rotate_about_pivot() {
+        let matrix = MotionMatrix::rotate(90.0).about((10.0, 10.0));
+
+        assert!(close(matrix.transform_point((20.0, 10.0)), (10.0, 20.0)));
+        assert!(close(matrix.transform_point((10.0, 10.0)), (10.0, 10.0)));
+    }
+
+    #[test]
+    fn scale_about_pivot() {
+        let matrix = MotionMatrix::scale(2.0, 3.0).about((10.0, 10.0));
+
+        assert!(close(matrix.transform_point((20.0, 20.0)), (30.0, 40.0)));
+    }
+
+    #[test]
+    fn invert_undoes_transform() {
+        let matrix  = MotionMatrix::scale(2.0, 0.5).then(&MotionMatrix::rotate(30.0)).then(&MotionMatrix::translate(5.0, -7.0)).about((3.0, 4.0));
+        let inverse = matrix.invert().unwrap();
+
+        assert!(close(inverse.transform_point(matrix.transform_point((12.0, -8.0))), (12.0, -8.0)));
+    }
+
+    #[test]
+    fn cannot_invert_zero_scale() {
+        assert!(MotionMatrix::scale(0.0, 1.0).invert().is_none());
+    }
+}
@@ -1,9 +1,16 @@
 mod transform;
 mod motion;
+mod matrix;
 mod translate;
+mod rotate;
+mod scale;
+mod transform_motion;
 mod motion_type;
 
 pub use self::transform::*;
 pub use self::motion::*;
 pub use self::translate::*;
+pub use self::rotate::*;
+pub use self::scale::*;
+pub use self::transform_motion::*;
 pub use self::motion_type::*;
@@ -1,4 +1,7 @@
+use super::rotate::*;
+use super::scale::*;
 use super::translate::*;
+use super::transform_motion::*;
 use super::transform::*;
 use super::motion_type::*;
 use super::super::path::*;
@@ -23,7 +26,16 @@ pub enum Motion {
     Reverse(Arc<Motion>),
 
     /// Describes how an element is translated over time
-    Translate(TranslateMotion)
+    Translate(TranslateMotion),
+
+    /// Describes how an element is rotated around a pivot over time
+    Rotate(RotateMotion),
+
+    /// Describes how an element is scaled around a pivot over time
+    Scale(ScaleMotion),
+
+    /// Describes how an element is scaled, rotated and translated over time
+    Transform(TransformMotion)
 }
 
 impl Motion {
@@ -36,7 +48,10 @@ impl Motion {
         match self {
             None            => MotionType::None,
             Reverse(_)      => MotionType::Reverse,
-            Translate(_)    => MotionType::Translate
+            Translate(_)    => MotionType::Translate,
+            Rotate(_)       => MotionType::Rotate,
+            Scale(_)        => MotionType::Scale,
+            Transform(_)    => MotionType::Transform
         }
     }
 
@@ -50,6 +65,9 @@ impl Motion {
             None        => { *self = Motion::None; },
             Reverse     => { *self = Motion::Reverse(Arc::new(Motion::None)); }
             Translate   => { *self = Motion::Translate(TranslateMotion::default()); }
+            Rotate      => { *self = Motion::Rotate(RotateMotion::default()); }
+            Scale       => { *self = Motion::Scale(ScaleMotion::default()); }
+            Transform   => { *self = Motion::Transform(TransformMotion::default()); }
         }
     }
 
@@ -63,6 +81,9 @@ impl Motion {
             None                    => { },
             Reverse(_)              => { },
             Translate(translate)    => { translate.set_origin(new_origin); }
+            Rotate(rotate)          => { rotate.set_origin(new_origin); }
+            Scale(scale)            => { scale.set_origin(new_origin); }
+            Transform(transform)    => { transform.set_origin(new_origin); }
         }
     }
 
@@ -76,6 +97,84 @@ impl Motion {
             None                    => { },
             Reverse(_)              => { },
             Translate(translate)    => { translate.set_path(new_path); }
+            Rotate(_)               => { },
+            Scale(_)                => { },
+            Transform(transform)    => { transform.set_path(new_path); }
+        }
+    }
+
+    ///
+    /// Sets the rotation curve of this motion
+    ///
+    pub fn set_rotation(&mut self, new_rotation: TimeCurve) {
+        use self::Motion::*;
+
+        match self {
+            Rotate(rotate)          => { rotate.set_rotation(new_rotation); }
+            Transform(transform)    => { transform.set_rotation(new_rotation); }
+            _                       => { }
+        }
+    }
+
+    ///
+    /// Sets the scale curve of this motion
+    ///
+    pub fn set_scale(&mut self, new_scale: TimeCurve) {
+        use self::Motion::*;
+
+        match self {
+            Scale(scale)            => { scale.set_scale(new_scale); }
+            Transform(transform)    => { transform.set_scale(new_scale); }
+            _                       => { }
+        }
+    }
+
+    ///
+    /// Retrieves the origin of this motion, if it has one
+    ///
+    pub fn origin(&self) -> Option<(f32, f32)> {
+        use self::Motion::*;
+
+        match self {
+            None                    => Option::None,
+            Reverse(_)              => Option::None,
+            Translate(translate)    => Some(translate.origin),
+            Rotate(rotate)          => Some(rotate.origin),
+            Scale(scale)            => Some(scale.origin),
+            Transform(transform)    => Some(transform.origin)
+        }
+    }
+
+    ///
+    /// Retrieves the path of this motion, if it has one
+    ///
+    pub fn path(&self) -> Option<&TimeCurve> {
+        match self {
+            Motion::Translate(translate)    => Some(&translate.translate),
+            Motion::Transform(transform)    => Some(&transform.translate),
+            _                               => None
+        }
+    }
+
+    ///
+    /// Retrieves the rotation curve of this motion, if it has one
+    ///
+    pub fn rotation(&self) -> Option<&TimeCurve> {
+        match self {
+            Motion::Rotate(rotate)          => Some(&rotate.rotate),
+            Motion::Transform(transform)    => Some(&transform.rotate),
+            _                               => None
+        }
+    }
+
+    ///
+    /// Retrieves the scale curve of this motion, if it has one
+    ///
+    pub fn scale(&self) -> Option<&TimeCurve> {
+        match self {
+            Motion::Scale(scale)            => Some(&scale.scale),
+            Motion::Transform(transform)    => Some(&transform.scale),
+            _                               => None
         }
     }
 
@@ -97,7 +196,10 @@ impl MotionTransform for Motion {
         match self {
             None                    => 0.0..0.0,
             Reverse(motion)         => motion.range_millis(),
-            Translate(translate)    => translate.range_millis()
+            Translate(translate)    => translate.range_millis(),
+            Rotate(rotate)          => rotate.range_millis(),
+            Scale(scale)            => scale.range_millis(),
+            Transform(transform)    => transform.range_millis()
         }
     }
 
@@ -108,7 +210,10 @@ impl MotionTransform for Motion {
             None                    => Box::new(points.cloned()),
             Reverse(motion)         => motion.reverse_brush_points(time, points),
 
-            Translate(translate)    => translate.transform_brush_points(time, points)
+            Translate(translate)    => translate.transform_brush_points(time, points),
+            Rotate(rotate)          => rotate.transform_brush_points(time, points),
+            Scale(scale)            => scale.transform_brush_points(time, points),
+            Transform(transform)    => transform.transform_brush_points(time, points)
         }
     }
 
@@ -119,7 +224,10 @@ impl MotionTransform for Motion {
             None                    => Box::new(points.cloned()),
             Reverse(motion)         => motion.transform_brush_points(time, points),
 
-            Translate(translate)    => translate.reverse_brush_points(time, points)
+            Translate(translate)    => translate.reverse_brush_points(time, points),
+            Rotate(rotate)          => rotate.reverse_brush_points(time, points),
+            Scale(scale)            => scale.reverse_brush_points(time, points),
+            Transform(transform)    => transform.reverse_brush_points(time, points)
         }
     }
 
@@ -130,7 +238,10 @@ impl MotionTransform for Motion {
             None                    => Box::new(points.cloned()),
             Reverse(motion)         => motion.reverse_path_points(time, points),
 
-            Translate(translate)    => translate.transform_path_points(time, points)
+            Translate(translate)    => translate.transform_path_points(time, points),
+            Rotate(rotate)          => rotate.transform_path_points(time, points),
+            Scale(scale)            => scale.transform_path_points(time, points),
+            Transform(transform)    => transform.transform_path_points(time, points)
         }
     }
 
@@ -141,7 +252,10 @@ impl MotionTransform for Motion {
             None                    => Box::new(points.cloned()),
             Reverse(motion)         => motion.transform_path_points(time, points),
 
-            Translate(translate)    => translate.reverse_path_points(time, points)
+            Translate(translate)    => translate.reverse_path_points(time, points),
+            Rotate(rotate)          => rotate.reverse_path_points(time, points),
+            Scale(scale)            => scale.reverse_path_points(time, points),
+            Transform(transform)    => transform.reverse_path_points(time, points)
         }
     }
 }
@@ -1,9 +1,12 @@
 ///
 /// The possible types of motion
 ///
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum MotionType {
     None,
     Reverse,
-    Translate
+    Translate,
+    Rotate,
+    Scale,
+    Transform
 }
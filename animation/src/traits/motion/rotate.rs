@@ -0,0 +1,130 @@
+use super::matrix::*;
+use super::transform::*;
+use super::super::path::*;
+use super::super::brush::*;
+use super::super::time_path::*;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how an element is rotated around a pivot point over time
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct RotateMotion {
+    /// The point about which this rotation is taking place
+    pub origin: (f32, f32),
+
+    /// Curve describing the angle of rotation (the x coordinate is the anticlockwise angle in degrees, the y coordinate is ignored)
+    pub rotate: TimeCurve
+}
+
+impl RotateMotion {
+    ///
+    /// Creates a rotate motion that instantaneously rotates something about a pivot point
+    ///
+    pub fn rotate_to(when: Duration, origin: (f32, f32), degrees: f32) -> RotateMotion {
+        let angle = TimePoint::new(degrees, 0.0, when);
+
+        RotateMotion {
+            origin: origin,
+            rotate: TimeCurve::new(angle, angle)
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the rotation curve for this motion
+    ///
+    #[inline]
+    pub fn set_rotation(&mut self, new_rotation: TimeCurve) {
+        self.rotate = new_rotation;
+    }
+
+    ///
+    /// Returns the transformation matrix for this motion at a particular time
+    ///
+    fn matrix_at_time(&self, time: Duration) -> Option<MotionMatrix> {
+        curve_point_at_time(&self.rotate, time_to_millis(time))
+            .map(|angle| MotionMatrix::rotate(angle.0).about(self.origin))
+    }
+}
+
+impl Default for RotateMotion {
+    ///
+    /// Creates a default rotate motion
+    ///
+    fn default() -> RotateMotion {
+        RotateMotion {
+            origin: (0.0, 0.0),
+            rotate: TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(0.0, 0.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for RotateMotion {
+    fn range_millis(&self) -> Range<f32> {
+        curve_range_millis(&self.rotate)
+    }
+
+    fn transform_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        match self.matrix_at_time(time) {
+            Some(matrix)    => matrix.transform_brush_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn reverse_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        match self.matrix_at_time(time).and_then(|matrix| matrix.invert()) {
+            Some(matrix)    => matrix.transform_brush_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn transform_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        match self.matrix_at_time(time) {
+            Some(matrix)    => matrix.transform_path_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn reverse_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        match self.matrix_at_time(time).and_then(|matrix| matrix.invert()) {
+            Some(matrix)    => matrix.transform_path_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotate_path_point_about_origin() {
+        let rotate  = RotateMotion::rotate_to(Duration::from_millis(100), (10.0, 10.0), 90.0);
+        let points  = vec![PathPoint::new(20.0, 10.0)];
+        let rotated = rotate.transform_path_points(Duration::from_millis(100), points.iter()).collect::<Vec<_>>();
+
+        assert!((rotated[0].x()-10.0).abs() < 0.01);
+        assert!((rotated[0].y()-20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn reverse_rotation() {
+        let rotate      = RotateMotion::rotate_to(Duration::from_millis(100), (10.0, 10.0), 45.0);
+        let points      = vec![PathPoint::new(30.0, -5.0)];
+        let rotated     = rotate.transform_path_points(Duration::from_millis(100), points.iter()).collect::<Vec<_>>();
+        let reversed    = rotate.reverse_path_points(Duration::from_millis(100), rotated.iter()).collect::<Vec<_>>();
+
+        assert!((reversed[0].x()-30.0).abs() < 0.01);
+        assert!((reversed[0].y()+5.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,121 @@
+use super::matrix::*;
+use super::transform::*;
+use super::super::path::*;
+use super::super::brush::*;
+use super::super::time_path::*;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes how an element is scaled about a pivot point over time
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScaleMotion {
+    /// The point about which this scaling is taking place
+    pub origin: (f32, f32),
+
+    /// Curve describing the scale factor (the x and y coordinates are the horizontal and vertical scale factors)
+    pub scale: TimeCurve
+}
+
+impl ScaleMotion {
+    ///
+    /// Creates a scale motion that instantaneously scales something about a pivot point
+    ///
+    pub fn scale_to(when: Duration, origin: (f32, f32), scale: (f32, f32)) -> ScaleMotion {
+        let factor = TimePoint::new(scale.0, scale.1, when);
+
+        ScaleMotion {
+            origin: origin,
+            scale:  TimeCurve::new(factor, factor)
+        }
+    }
+
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the scale curve for this motion
+    ///
+    #[inline]
+    pub fn set_scale(&mut self, new_scale: TimeCurve) {
+        self.scale = new_scale;
+    }
+
+    ///
+    /// Returns the transformation matrix for this motion at a particular time
+    ///
+    fn matrix_at_time(&self, time: Duration) -> Option<MotionMatrix> {
+        curve_point_at_time(&self.scale, time_to_millis(time))
+            .map(|factor| MotionMatrix::scale(factor.0, factor.1).about(self.origin))
+    }
+}
+
+impl Default for ScaleMotion {
+    ///
+    /// Creates a default scale motion
+    ///
+    fn default() -> ScaleMotion {
+        ScaleMotion {
+            origin: (0.0, 0.0),
+            scale:  TimeCurve::new(TimePoint::new(1.0, 1.0, Duration::from_millis(0)), TimePoint::new(1.0, 1.0, Duration::from_millis(0)))
+        }
+    }
+}
+
+impl MotionTransform for ScaleMotion {
+    fn range_millis(&self) -> Range<f32> {
+        curve_range_millis(&self.scale)
+    }
+
+    fn transform_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        match self.matrix_at_time(time) {
+            Some(matrix)    => matrix.transform_brush_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn reverse_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        // Scaling by 0 can't be reversed, so the points are left as they are in that case
+        match self.matrix_at_time(time).and_then(|matrix| matrix.invert()) {
+            Some(matrix)    => matrix.transform_brush_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn transform_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        match self.matrix_at_time(time) {
+            Some(matrix)    => matrix.transform_path_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn reverse_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        match self.matrix_at_time(time).and_then(|matrix| matrix.invert()) {
+            Some(matrix)    => matrix.transform_path_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scale_brush_point_about_origin() {
+        let scale   = ScaleMotion::scale_to(Duration::from_millis(100), (10.0, 10.0), (2.0, 2.0));
+        let points  = vec![BrushPoint { position: (20.0, 15.0), cp1: (20.0, 15.0), cp2: (20.0, 15.0), width: 3.0 }];
+        let scaled  = scale.transform_brush_points(Duration::from_millis(100), points.iter()).collect::<Vec<_>>();
+
+        assert!((scaled[0].position.0-30.0).abs() < 0.01);
+        assert!((scaled[0].position.1-20.0).abs() < 0.01);
+        assert!((scaled[0].width-6.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,167 @@
+use super::matrix::*;
+use super::transform::*;
+use super::super::path::*;
+use super::super::brush::*;
+use super::super::time_path::*;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Describes a general affine transformation of an element over time
+///
+/// The element is scaled, then rotated about the origin, and then the origin is moved to the position
+/// described by the translation curve.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct TransformMotion {
+    /// The pivot point for the rotation and scaling, and the point that is moved by the translation
+    pub origin: (f32, f32),
+
+    /// Curve describing where the origin moves to
+    pub translate: TimeCurve,
+
+    /// Curve describing the angle of rotation (the x coordinate is the anticlockwise angle in degrees)
+    pub rotate: TimeCurve,
+
+    /// Curve describing the horizontal and vertical scale factors
+    pub scale: TimeCurve
+}
+
+impl TransformMotion {
+    ///
+    /// Sets the origin of this motion
+    ///
+    #[inline]
+    pub fn set_origin(&mut self, new_origin: (f32, f32)) {
+        self.origin = new_origin;
+    }
+
+    ///
+    /// Sets the translation curve for this motion
+    ///
+    #[inline]
+    pub fn set_path(&mut self, new_path: TimeCurve) {
+        self.translate = new_path;
+    }
+
+    ///
+    /// Sets the rotation curve for this motion
+    ///
+    #[inline]
+    pub fn set_rotation(&mut self, new_rotation: TimeCurve) {
+        self.rotate = new_rotation;
+    }
+
+    ///
+    /// Sets the scale curve for this motion
+    ///
+    #[inline]
+    pub fn set_scale(&mut self, new_scale: TimeCurve) {
+        self.scale = new_scale;
+    }
+
+    ///
+    /// Returns the transformation matrix for this motion at a particular time
+    ///
+    /// Curves that don't cover the specified time have no effect on the transformation
+    ///
+    fn matrix_at_time(&self, time: Duration) -> MotionMatrix {
+        let millis      = time_to_millis(time);
+        let origin      = self.origin;
+
+        let scale       = curve_point_at_time(&self.scale, millis)
+            .map(|factor| MotionMatrix::scale(factor.0, factor.1))
+            .unwrap_or_else(|| MotionMatrix::identity());
+        let rotate      = curve_point_at_time(&self.rotate, millis)
+            .map(|angle| MotionMatrix::rotate(angle.0))
+            .unwrap_or_else(|| MotionMatrix::identity());
+        let position    = curve_point_at_time(&self.translate, millis)
+            .map(|position| (position.0, position.1))
+            .unwrap_or(origin);
+
+        MotionMatrix::translate(-origin.0, -origin.1)
+            .then(&scale)
+            .then(&rotate)
+            .then(&MotionMatrix::translate(position.0, position.1))
+    }
+}
+
+impl Default for TransformMotion {
+    ///
+    /// Creates a default transform motion (which has no effect until its curves are set)
+    ///
+    fn default() -> TransformMotion {
+        TransformMotion {
+            origin:     (0.0, 0.0),
+            translate:  TimeCurve { points: vec![] },
+            rotate:     TimeCurve { points: vec![] },
+            scale:      TimeCurve { points: vec![] }
+        }
+    }
+}
+
+impl MotionTransform for TransformMotion {
+    fn range_millis(&self) -> Range<f32> {
+        // The range covers all three of the curves
+        [&self.translate, &self.rotate, &self.scale].iter()
+            .filter(|curve| curve.points.len() > 0)
+            .map(|curve| curve_range_millis(curve))
+            .fold(None, |range: Option<Range<f32>>, next| {
+                match range {
+                    None        => Some(next),
+                    Some(range) => Some(range.start.min(next.start)..range.end.max(next.end))
+                }
+            })
+            .unwrap_or(0.0..0.0)
+    }
+
+    fn transform_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        self.matrix_at_time(time).transform_brush_points(points)
+    }
+
+    fn reverse_brush_points<'a, Points: 'a+Iterator<Item=&'a BrushPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=BrushPoint>> {
+        match self.matrix_at_time(time).invert() {
+            Some(matrix)    => matrix.transform_brush_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+
+    fn transform_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        self.matrix_at_time(time).transform_path_points(points)
+    }
+
+    fn reverse_path_points<'a, Points: 'a+Iterator<Item=&'a PathPoint>>(&self, time: Duration, points: Points) -> Box<dyn 'a+Iterator<Item=PathPoint>> {
+        match self.matrix_at_time(time).invert() {
+            Some(matrix)    => matrix.transform_path_points(points),
+            None            => Box::new(points.cloned())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scale_rotate_and_move() {
+        let when        = Duration::from_millis(100);
+        let mut motion  = TransformMotion::default();
+
+        motion.set_origin((10.0, 10.0));
+        motion.set_path(TimeCurve::new(TimePoint::new(50.0, 50.0, when), TimePoint::new(50.0, 50.0, when)));
+        motion.set_rotation(TimeCurve::new(TimePoint::new(90.0, 0.0, when), TimePoint::new(90.0, 0.0, when)));
+        motion.set_scale(TimeCurve::new(TimePoint::new(2.0, 2.0, when), TimePoint::new(2.0, 2.0, when)));
+
+        let points      = vec![PathPoint::new(20.0, 10.0)];
+        let transformed = motion.transform_path_points(when, points.iter()).collect::<Vec<_>>();
+
+        assert!((transformed[0].x()-50.0).abs() < 0.01);
+        assert!((transformed[0].y()-70.0).abs() < 0.01);
+
+        let reversed    = motion.reverse_path_points(when, transformed.iter()).collect::<Vec<_>>();
+
+        assert!((reversed[0].x()-20.0).abs() < 0.01);
+        assert!((reversed[0].y()-10.0).abs() < 0.01);
+    }
+}
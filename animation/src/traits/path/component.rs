@@ -3,7 +3,7 @@ use super::point::*;
 ///
 /// Represents an element of a bezier path
 ///
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub enum PathComponent {
     /// Move to point
     Move(PathPoint),
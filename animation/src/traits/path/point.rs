@@ -6,7 +6,7 @@ use std::ops::{Mul, Add, Sub};
 ///
 /// A point in a path
 ///
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
 pub struct PathPoint {
     /// X, Y coordinates of this point
     pub position: (f64, f64)
@@ -0,0 +1,33 @@
+use flo_canvas::*;
+
+///
+/// Properties describing how a text element is rendered
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct TextStyle {
+    /// The font family to use for the text (eg, 'Helvetica' or 'serif')
+    pub font_family: String,
+
+    /// The height of the font, in canvas units
+    pub font_size: f32,
+
+    /// The colour of the text
+    pub color: Color,
+
+    /// How the text is aligned relative to its position
+    pub alignment: TextAlignment
+}
+
+impl TextStyle {
+    ///
+    /// Creates a new text style with the settings at their defaults
+    ///
+    pub fn new() -> TextStyle {
+        TextStyle {
+            font_family:    "sans-serif".to_string(),
+            font_size:      24.0,
+            color:          Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            alignment:      TextAlignment::Left
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use super::time_curve::*;
+use super::time_point::*;
+use super::time_control_point::*;
+
+/// Step used when estimating the rate of change of an easing function
+const DERIVATIVE_STEP: f32 = 0.001;
+
+/// Fraction of the section that a step easing holds its initial value for
+const STEP_HOLD: f32 = 0.98;
+
+///
+/// Named easing presets that can be applied to a section of a time curve
+///
+/// An easing describes how far along a section an element has moved (its 'progress') as time passes
+/// through that section. Easings are applied by rewriting the control points of the section, so they
+/// are stored in exactly the same way as any other time curve.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Easing {
+    /// Moves at a constant rate
+    Linear,
+
+    /// Starts slowly and speeds up
+    EaseIn,
+
+    /// Starts quickly and slows down
+    EaseOut,
+
+    /// Starts and ends slowly
+    EaseInOut,
+
+    /// Moves past the end of the section before settling back
+    Overshoot,
+
+    /// Bounces against the end of the section
+    Bounce,
+
+    /// Stays at the start of the section until the very end, then jumps
+    Step
+}
+
+impl Easing {
+    ///
+    /// Returns all of the easing presets
+    ///
+    pub fn all() -> Vec<Easing> {
+        use self::Easing::*;
+
+        vec![Linear, EaseIn, EaseOut, EaseInOut, Overshoot, Bounce, Step]
+    }
+
+    ///
+    /// The progress through a section at a particular time (both values are in the range 0-1, though overshoot easings can exceed 1)
+    ///
+    pub fn progress(&self, t: f32) -> f32 {
+        use self::Easing::*;
+
+        let t = t.max(0.0).min(1.0);
+
+        match self {
+            Linear      => t,
+            EaseIn      => t*t*t,
+            EaseOut     => 1.0 - (1.0-t)*(1.0-t)*(1.0-t),
+            EaseInOut   => if t < 0.5 { 4.0*t*t*t } else { 1.0 - (2.0-2.0*t).powi(3)/2.0 },
+
+            Overshoot   => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+
+                1.0 + c3*(t-1.0).powi(3) + c1*(t-1.0).powi(2)
+            },
+
+            Bounce      => {
+                let n1 = 7.5625;
+                let d1 = 2.75;
+
+                if t < 1.0/d1 {
+                    n1*t*t
+                } else if t < 2.0/d1 {
+                    let t = t - 1.5/d1;
+                    n1*t*t + 0.75
+                } else if t < 2.5/d1 {
+                    let t = t - 2.25/d1;
+                    n1*t*t + 0.9375
+                } else {
+                    let t = t - 2.625/d1;
+                    n1*t*t + 0.984375
+                }
+            },
+
+            Step        => if t < STEP_HOLD { 0.0 } else { (t-STEP_HOLD)/(1.0-STEP_HOLD) }
+        }
+    }
+
+    ///
+    /// The times within a section where extra control points are needed to represent this easing
+    ///
+    /// These are the places where the easing function changes direction or has a corner in it.
+    ///
+    fn breakpoints(&self) -> Vec<f32> {
+        use self::Easing::*;
+
+        match self {
+            Linear | EaseIn | EaseOut   => vec![],
+            EaseInOut                   => vec![0.5],
+            Overshoot                   => vec![0.579],
+            Bounce                      => vec![1.0/2.75, 1.5/2.75, 2.0/2.75, 2.25/2.75, 2.5/2.75, 2.625/2.75],
+            Step                        => vec![STEP_HOLD]
+        }
+    }
+
+    ///
+    /// The rate of change of the progress just before a particular time
+    ///
+    fn rate_before(&self, t: f32) -> f32 {
+        if t <= 0.0 {
+            self.rate_after(t)
+        } else {
+            (self.progress(t) - self.progress(t-DERIVATIVE_STEP)) / DERIVATIVE_STEP
+        }
+    }
+
+    ///
+    /// The rate of change of the progress just after a particular time
+    ///
+    fn rate_after(&self, t: f32) -> f32 {
+        if t >= 1.0 {
+            self.rate_before(t)
+        } else {
+            (self.progress(t+DERIVATIVE_STEP) - self.progress(t)) / DERIVATIVE_STEP
+        }
+    }
+}
+
+///
+/// Evaluates a 1D cubic bezier curve
+///
+#[inline]
+fn bezier(w1: f32, w2: f32, w3: f32, w4: f32, t: f32) -> f32 {
+    let one_minus_t = 1.0-t;
+
+    one_minus_t*one_minus_t*one_minus_t*w1 + 3.0*one_minus_t*one_minus_t*t*w2 + 3.0*one_minus_t*t*t*w3 + t*t*t*w4
+}
+
+///
+/// Evaluates the derivative of a 1D cubic bezier curve
+///
+#[inline]
+fn bezier_derivative(w1: f32, w2: f32, w3: f32, w4: f32, t: f32) -> f32 {
+    let one_minus_t = 1.0-t;
+
+    3.0*one_minus_t*one_minus_t*(w2-w1) + 6.0*one_minus_t*t*(w3-w2) + 3.0*t*t*(w4-w3)
+}
+
+impl TimeCurve {
+    ///
+    /// Finds the index of the section of this curve that contains the specified time
+    ///
+    /// Section `n` runs from point `n` to point `n+1`
+    ///
+    pub fn section_at_time(&self, milliseconds: f32) -> Option<usize> {
+        (0..self.points.len().max(1)-1)
+            .filter(|index| self.points[*index].point.milliseconds() <= milliseconds && self.points[index+1].point.milliseconds() >= milliseconds)
+            .nth(0)
+    }
+
+    ///
+    /// Generates a new time curve with the specified easing applied to a section
+    ///
+    /// The path taken through space by the section is preserved but the control points are rewritten so that the
+    /// progress along that path follows the easing. Some easings need extra control points, so the section may be
+    /// subdivided (meaning that section numbers after the one being edited can change).
+    ///
+    pub fn set_section_easing(&self, section: usize, easing: Easing) -> TimeCurve {
+        if section+1 >= self.points.len() {
+            // Section does not exist
+            return self.clone();
+        }
+
+        let start       = self.points[section];
+        let end         = self.points[section+1];
+
+        // The spatial part of the section is preserved
+        let xs          = (start.point.0, start.future.0, end.past.0, end.point.0);
+        let ys          = (start.point.1, start.future.1, end.past.1, end.point.1);
+        let position    = |p: f32| (bezier(xs.0, xs.1, xs.2, xs.3, p), bezier(ys.0, ys.1, ys.2, ys.3, p));
+        let tangent     = |p: f32| (bezier_derivative(xs.0, xs.1, xs.2, xs.3, p), bezier_derivative(ys.0, ys.1, ys.2, ys.3, p));
+
+        // The timing is replaced by the easing function
+        let start_millis    = start.point.milliseconds();
+        let duration        = end.point.milliseconds() - start_millis;
+
+        let mut knots       = vec![0.0];
+        knots.extend(easing.breakpoints());
+        knots.push(1.0);
+
+        // Generate a control point at each knot (we fix up the first and last points afterwards)
+        let mut new_points  = vec![];
+
+        for (index, knot) in knots.iter().enumerate() {
+            let knot            = *knot;
+            let progress        = easing.progress(knot);
+            let (x, y)          = position(progress);
+            let (dx, dy)        = tangent(progress);
+            let millis          = start_millis + knot*duration;
+
+            // The velocity in each direction is found from the tangent of the spatial curve and the rate of change of the easing
+            let span_before     = if index > 0 { (knot - knots[index-1])/3.0 } else { 0.0 };
+            let span_after      = if index+1 < knots.len() { (knots[index+1] - knot)/3.0 } else { 0.0 };
+            let rate_before     = easing.rate_before(knot) * span_before;
+            let rate_after      = easing.rate_after(knot) * span_after;
+
+            new_points.push(TimeControlPoint {
+                past:   TimePoint(x - dx*rate_before, y - dy*rate_before, millis - span_before*duration),
+                point:  TimePoint(x, y, millis),
+                future: TimePoint(x + dx*rate_after, y + dy*rate_after, millis + span_after*duration)
+            });
+        }
+
+        // The sections on either side are unchanged
+        let last_index                  = new_points.len()-1;
+        new_points[0].past              = start.past;
+        new_points[0].point             = start.point;
+        new_points[last_index].point    = end.point;
+        new_points[last_index].future   = end.future;
+
+        // Replace the section in the curve
+        let mut points = self.points[0..section].to_vec();
+        points.extend(new_points);
+        points.extend(self.points[(section+2)..].iter().cloned());
+
+        TimeCurve { points }
+    }
+
+    ///
+    /// Returns a set of samples describing how progress changes with time through a section of this curve
+    ///
+    /// The result is a list of `(time, progress)` pairs, where time runs from 0 to 1 through the section, and progress
+    /// is the distance along the straight line between the start and end of the section (0 at the start and 1 at
+    /// the end, though it can be outside this range if the curve overshoots)
+    ///
+    pub fn section_progress(&self, section: usize, num_samples: usize) -> Vec<(f32, f32)> {
+        if section+1 >= self.points.len() || num_samples < 2 {
+            return vec![];
+        }
+
+        let start           = self.points[section];
+        let end             = self.points[section+1];
+
+        let start_millis    = start.point.milliseconds();
+        let duration        = end.point.milliseconds() - start_millis;
+        let (dx, dy)        = (end.point.0 - start.point.0, end.point.1 - start.point.1);
+        let length_sq       = dx*dx + dy*dy;
+
+        (0..num_samples)
+            .map(|sample| (sample as f32) / ((num_samples-1) as f32))
+            .map(|pos| {
+                let x       = bezier(start.point.0, start.future.0, end.past.0, end.point.0, pos);
+                let y       = bezier(start.point.1, start.future.1, end.past.1, end.point.1, pos);
+                let millis  = bezier(start.point.2, start.future.2, end.past.2, end.point.2, pos);
+
+                let time        = if duration > 0.0 { (millis - start_millis)/duration } else { pos };
+                let progress    = if length_sq > 0.0 { ((x - start.point.0)*dx + (y - start.point.1)*dy)/length_sq } else { 0.0 };
+
+                (time, progress)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::time::Duration;
+
+    fn line() -> TimeCurve {
+        TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 0.0, Duration::from_millis(1000)))
+    }
+
+    #[test]
+    fn easings_start_at_0_and_end_at_1() {
+        for easing in Easing::all() {
+            assert!(easing.progress(0.0).abs() < 0.001);
+            assert!((easing.progress(1.0)-1.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn find_section_at_time() {
+        let curve = line().set_point_at_time(Duration::from_millis(500), (50.0, 0.0));
+
+        assert!(curve.section_at_time(250.0) == Some(0));
+        assert!(curve.section_at_time(750.0) == Some(1));
+        assert!(curve.section_at_time(1500.0) == None);
+    }
+
+    #[test]
+    fn ease_in_is_slow_at_the_start() {
+        let curve   = line().set_section_easing(0, Easing::EaseIn);
+        let point   = curve.point_at_time(500.0).unwrap();
+
+        assert!(curve.points.len() == 2);
+        assert!(point.0 < 25.0);
+        assert!(point.0 > 5.0);
+    }
+
+    #[test]
+    fn ease_out_is_fast_at_the_start() {
+        let curve   = line().set_section_easing(0, Easing::EaseOut);
+        let point   = curve.point_at_time(500.0).unwrap();
+
+        assert!(point.0 > 75.0);
+    }
+
+    #[test]
+    fn overshoot_goes_past_the_end() {
+        let curve   = line().set_section_easing(0, Easing::Overshoot);
+        let point   = curve.point_at_time(579.0).unwrap();
+
+        assert!(point.0 > 105.0);
+        assert!((curve.points.last().unwrap().point.0 - 100.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn bounce_hits_the_end_several_times() {
+        let curve   = line().set_section_easing(0, Easing::Bounce);
+
+        assert!(curve.points.len() == 8);
+        assert!((curve.point_at_time(1000.0/2.75).unwrap().0 - 100.0).abs() < 0.1);
+        assert!((curve.point_at_time(2000.0/2.75).unwrap().0 - 100.0).abs() < 0.1);
+        assert!(curve.point_at_time(1500.0/2.75).unwrap().0 < 90.0);
+    }
+
+    #[test]
+    fn step_holds_until_the_end() {
+        let curve   = line().set_section_easing(0, Easing::Step);
+
+        assert!(curve.point_at_time(900.0).unwrap().0.abs() < 0.1);
+        assert!((curve.point_at_time(1000.0).unwrap().0 - 100.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn easing_preserves_other_sections() {
+        let curve   = line().set_point_at_time(Duration::from_millis(500), (50.0, 0.0));
+        let eased   = curve.set_section_easing(0, Easing::EaseInOut);
+
+        assert!(eased.points.len() == 4);
+        assert!(eased.points[3] == curve.points[2]);
+        assert!(eased.points[2].future == curve.points[1].future);
+    }
+
+    #[test]
+    fn progress_of_linear_section() {
+        let progress = line().section_progress(0, 5);
+
+        assert!(progress.len() == 5);
+        for (time, progress) in progress {
+            assert!((time-progress).abs() < 0.01);
+        }
+    }
+}
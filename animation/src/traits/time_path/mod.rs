@@ -3,9 +3,11 @@ mod time_control_point;
 mod time_curve;
 mod convert;
 mod edit;
+mod easing;
 
 pub use self::time_point::*;
 pub use self::time_control_point::*;
 pub use self::time_curve::*;
 pub use self::convert::*;
 pub use self::edit::*;
+pub use self::easing::*;
@@ -8,7 +8,7 @@ use super::time_point::*;
 /// when considering an individual point we have control points for both how we arrive there and
 /// how we leave.
 ///
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TimeControlPoint {
     /// The point that this represents
     pub point: TimePoint,
@@ -11,7 +11,7 @@ const DELTA: f32 = 0.1;
 ///
 /// Represents a curve through time
 ///
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct TimeCurve {
     /// The points on this curves
     pub points: Vec<TimeControlPoint>
@@ -92,15 +92,117 @@ impl GroupElement {
         }
     }
 
+    ///
+    /// Returns the paths generated by each element in this group along with the properties that apply to them
+    ///
+    /// Properties are updated by each element in turn, so any brush definitions or properties in the group apply
+    /// to the elements that follow them. Elements that do not generate a path are left out of the result.
+    ///
+    fn element_paths(&self, properties: &VectorProperties) -> Vec<(Arc<VectorProperties>, Vec<Path>)> {
+        let mut properties      = Arc::new(properties.clone());
+        let mut element_paths   = vec![];
+
+        for elem in self.grouped_elements.iter() {
+            properties = elem.update_properties(properties);
+
+            if let Some(path) = elem.to_path(&properties) {
+                if path.len() > 0 {
+                    let path = path_remove_interior_points::<_, Path>(&path, 0.01);
+                    element_paths.push((Arc::clone(&properties), path));
+                }
+            }
+        }
+
+        element_paths
+    }
+
+    ///
+    /// Returns the subtracted path for this element, along with the properties of the first element that it should be rendered with
+    ///
+    fn subtracted_path(&self, properties: &VectorProperties) -> Option<(Arc<VectorProperties>, Vec<Path>)> {
+        let mut element_paths                   = self.element_paths(properties).into_iter();
+        let (first_properties, first_path)      = element_paths.next()?;
+
+        // Everything after the first element is combined into a single path to subtract
+        let subtract_paths = element_paths.map(|(_properties, path)| path).collect::<Vec<_>>();
+
+        if subtract_paths.len() > 0 {
+            let subtract_path: Vec<Path> = path_add_chain(&subtract_paths, 0.01);
+            Some((first_properties, path_sub(&first_path, &subtract_path, 0.01)))
+        } else {
+            Some((first_properties, first_path))
+        }
+    }
+
+    ///
+    /// Returns the paths for the elements following the mask in a 'masked' or 'inverted mask' group
+    ///
+    /// The first element is the mask: masked groups keep the parts of the following elements that are inside it, and
+    /// inverted mask groups keep the parts that are outside it.
+    ///
+    fn masked_paths(&self, properties: &VectorProperties) -> Vec<(Arc<VectorProperties>, Vec<Path>)> {
+        let mut element_paths   = self.element_paths(properties).into_iter();
+        let mask                = match element_paths.next() {
+            Some((_mask_properties, mask))  => mask,
+            None                            => { return vec![]; }
+        };
+        let inverted            = self.group_type == GroupType::InvertedMask;
+
+        element_paths
+            .map(|(properties, path)| {
+                let path = if inverted {
+                    path_sub::<_, _, _, Path>(&path, &mask, 0.01)
+                } else {
+                    path_intersect::<_, _, _, Path>(&path, &mask, 0.01)
+                };
+
+                (properties, path)
+            })
+            .filter(|(_properties, path)| path.len() > 0)
+            .collect()
+    }
+
+    ///
+    /// Renders a set of paths using the brush from the specified properties
+    ///
+    fn render_paths(gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, paths: &Vec<Path>) {
+        gc.draw_list(properties.brush.prepare_to_render(&properties.brush_properties));
+        paths.iter()
+            .for_each(|path| gc.draw_list(properties.brush.render_path(&properties.brush_properties, path)));
+    }
+
     ///
     /// Renders the contents of this group in 'added' mode
     ///
     fn render_added(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties) {
         let paths = self.added_path(properties);
 
-        gc.draw_list(properties.brush.prepare_to_render(&properties.brush_properties));
-        paths.into_iter()
-            .for_each(|path| gc.draw_list(properties.brush.render_path(&properties.brush_properties, &path)));
+        Self::render_paths(gc, properties, &paths);
+    }
+
+    ///
+    /// Renders the contents of this group in 'subtracted' mode
+    ///
+    fn render_subtracted(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties) {
+        if let Some((first_properties, paths)) = self.subtracted_path(properties) {
+            Self::render_paths(gc, &*first_properties, &paths);
+        }
+    }
+
+    ///
+    /// Renders the contents of this group in 'masked' or 'inverted mask' mode
+    ///
+    fn render_masked(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties) {
+        for (element_properties, paths) in self.masked_paths(properties) {
+            Self::render_paths(gc, &*element_properties, &paths);
+        }
+    }
+
+    ///
+    /// Retrieves the type of this group
+    ///
+    pub fn group_type(&self) -> GroupType {
+        self.group_type
     }
 
     ///
@@ -124,8 +226,11 @@ impl VectorElement for GroupElement {
     ///
     fn to_path(&self, properties: &VectorProperties) -> Option<Vec<Path>> {
         match self.group_type {
-            GroupType::Normal   => Some(self.grouped_elements.iter().flat_map(|elem| elem.to_path(properties)).flatten().collect()),
-            GroupType::Added    => Some(self.added_path(properties))
+            GroupType::Normal       => Some(self.grouped_elements.iter().flat_map(|elem| elem.to_path(properties)).flatten().collect()),
+            GroupType::Added        => Some(self.added_path(properties)),
+            GroupType::Subtracted   => Some(self.subtracted_path(properties).map(|(_properties, path)| path).unwrap_or_else(|| vec![])),
+            GroupType::Masked       |
+            GroupType::InvertedMask => Some(self.masked_paths(properties).into_iter().flat_map(|(_properties, path)| path).collect())
         }
     }
 
@@ -134,8 +239,11 @@ impl VectorElement for GroupElement {
     ///
     fn render(&self, gc: &mut dyn GraphicsPrimitives, properties: &VectorProperties, when: Duration) {
         match self.group_type {
-            GroupType::Normal   => self.render_normal(gc, properties, when),
-            GroupType::Added    => self.render_added(gc, properties)
+            GroupType::Normal       => self.render_normal(gc, properties, when),
+            GroupType::Added        => self.render_added(gc, properties),
+            GroupType::Subtracted   => self.render_subtracted(gc, properties),
+            GroupType::Masked       |
+            GroupType::InvertedMask => self.render_masked(gc, properties)
         }
     }
 
@@ -175,3 +283,67 @@ impl VectorElement for GroupElement {
         Vector::Group(self.clone())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::super::*;
+
+    fn square(id: i64, x1: f32, y1: f32, x2: f32, y2: f32) -> Vector {
+        let path = Path::from_elements(vec![
+            PathComponent::Move(PathPoint::from((x1, y1))),
+            PathComponent::Line(PathPoint::from((x2, y1))),
+            PathComponent::Line(PathPoint::from((x2, y2))),
+            PathComponent::Line(PathPoint::from((x1, y2))),
+            PathComponent::Line(PathPoint::from((x1, y1))),
+            PathComponent::Close
+        ]);
+        let brush               = Arc::new(BrushDefinitionElement::new(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw));
+        let brush_properties    = Arc::new(BrushPropertiesElement::new(ElementId::Unassigned, BrushProperties::new()));
+
+        Vector::Path(PathElement::new(ElementId::Assigned(id), path, brush, brush_properties))
+    }
+
+    fn bounds_for_group(group_type: GroupType) -> Rect {
+        let group   = GroupElement::new(ElementId::Assigned(0), group_type, Arc::new(vec![
+            square(1, 0.0, 0.0, 10.0, 10.0),
+            square(2, 5.0, -5.0, 15.0, 15.0)
+        ]));
+        let paths   = group.to_path(&VectorProperties::default()).unwrap();
+
+        assert!(paths.len() > 0);
+        paths.iter()
+            .map(|path| path.bounding_box())
+            .fold(Rect::empty(), |bounds, path_bounds| bounds.union(path_bounds))
+    }
+
+    #[test]
+    fn subtracted_group_cuts_from_first_element() {
+        let bounds = bounds_for_group(GroupType::Subtracted);
+
+        assert!((bounds.x1-0.0).abs() < 0.1);
+        assert!((bounds.x2-5.0).abs() < 0.1);
+        assert!((bounds.y1-0.0).abs() < 0.1);
+        assert!((bounds.y2-10.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn masked_group_keeps_inside_of_first_element() {
+        let bounds = bounds_for_group(GroupType::Masked);
+
+        assert!((bounds.x1-5.0).abs() < 0.1);
+        assert!((bounds.x2-10.0).abs() < 0.1);
+        assert!((bounds.y1-0.0).abs() < 0.1);
+        assert!((bounds.y2-10.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn inverted_mask_group_keeps_outside_of_first_element() {
+        let bounds = bounds_for_group(GroupType::InvertedMask);
+
+        assert!((bounds.x1-5.0).abs() < 0.1);
+        assert!((bounds.x2-15.0).abs() < 0.1);
+        assert!((bounds.y1-(-5.0)).abs() < 0.1);
+        assert!((bounds.y2-15.0).abs() < 0.1);
+    }
+}
@@ -1,7 +1,7 @@
 ///
 /// How a set of elements in a group are combined
 ///
-#[derive(Clone, Copy, Debug, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GroupType {
     /// Elements are just rendered one after the other
     Normal,
@@ -9,7 +9,6 @@ pub enum GroupType {
     /// Elements are added together (the path properties of the first element are used for all elements)
     Added,
 
-    /*
     /// Elements after the first element are subtracted from the first element
     Subtracted,
 
@@ -18,5 +17,4 @@ pub enum GroupType {
 
     /// The first element is subtracted from future elements
     InvertedMask
-    */
 }
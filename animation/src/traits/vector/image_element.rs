@@ -0,0 +1,233 @@
+use super::vector::*;
+use super::element::*;
+use super::properties::*;
+use super::control_point::*;
+use super::super::path::*;
+use super::super::edit::*;
+use super::super::motion::*;
+
+use flo_canvas::*;
+
+use std::time::Duration;
+
+///
+/// Element representing a bitmap image
+///
+/// The image is drawn into the unit square, which the transform maps onto the frame. The image's
+/// bottom-left corner is at (0,0) and its top-right corner is at (1,1) before the transform is applied.
+///
+#[derive(Clone, Debug)]
+pub struct ImageElement {
+    /// The ID of this element
+    id: ElementId,
+
+    /// The image data to display
+    image: CanvasImage,
+
+    /// Maps the unit square onto the location of the image in the frame
+    transform: Transform2D
+}
+
+impl ImageElement {
+    ///
+    /// Creates a new image element
+    ///
+    pub fn new(id: ElementId, image: CanvasImage, transform: Transform2D) -> ImageElement {
+        ImageElement {
+            id,
+            image,
+            transform
+        }
+    }
+
+    ///
+    /// The image displayed by this element
+    ///
+    pub fn image(&self) -> &CanvasImage {
+        &self.image
+    }
+
+    ///
+    /// The transform that maps the unit square onto the location of this image
+    ///
+    pub fn transform(&self) -> Transform2D {
+        self.transform
+    }
+
+    ///
+    /// Returns the corners of this image: bottom-left, bottom-right, top-right and top-left
+    ///
+    pub fn corners(&self) -> [(f32, f32); 4] {
+        [
+            self.transform.transform_point(0.0, 0.0),
+            self.transform.transform_point(1.0, 0.0),
+            self.transform.transform_point(1.0, 1.0),
+            self.transform.transform_point(0.0, 1.0)
+        ]
+    }
+
+    ///
+    /// Creates the transform that maps the unit square so that its origin and the ends of its x and y axes are at the specified points
+    ///
+    pub fn transform_from_axes(origin: (f32, f32), x_axis: (f32, f32), y_axis: (f32, f32)) -> Transform2D {
+        let (ox, oy) = origin;
+        let (xx, xy) = (x_axis.0-ox, x_axis.1-oy);
+        let (yx, yy) = (y_axis.0-ox, y_axis.1-oy);
+
+        Transform2D((xx, yx, ox), (xy, yy, oy), (0.0, 0.0, 1.0))
+    }
+}
+
+impl VectorElement for ImageElement {
+    ///
+    /// The ID of this element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    /// Images are represented by their outline
+    ///
+    fn to_path(&self, _properties: &VectorProperties) -> Option<Vec<Path>> {
+        let [(x1, y1), (x2, y2), (x3, y3), (x4, y4)] = self.corners();
+
+        Some(vec![Path::from_elements(vec![
+            PathComponent::Move(PathPoint::new(x1, y1)),
+            PathComponent::Line(PathPoint::new(x2, y2)),
+            PathComponent::Line(PathPoint::new(x3, y3)),
+            PathComponent::Line(PathPoint::new(x4, y4)),
+            PathComponent::Line(PathPoint::new(x1, y1)),
+            PathComponent::Close
+        ])])
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    fn render(&self, gc: &mut dyn GraphicsPrimitives, _properties: &VectorProperties, _when: Duration) {
+        gc.draw_image(&self.image, self.transform);
+    }
+
+    ///
+    /// Returns a new element that is this element transformed along a motion at a particular moment
+    /// in time.
+    ///
+    /// The motion is applied to the origin and the axes of the image, so the result is always a
+    /// parallelogram.
+    ///
+    fn motion_transform(&self, motion: &Motion, when: Duration) -> Vector {
+        let [origin, x_axis, _, y_axis] = self.corners();
+        let points                      = vec![origin, x_axis, y_axis].into_iter()
+            .map(|(x, y)| PathPoint::new(x, y))
+            .collect::<Vec<_>>();
+        let transformed                 = motion.transform_path_points(when, points.iter())
+            .map(|point| (point.x(), point.y()))
+            .collect::<Vec<_>>();
+
+        let transform = if transformed.len() == 3 {
+            Self::transform_from_axes(transformed[0], transformed[1], transformed[2])
+        } else {
+            self.transform
+        };
+
+        Vector::Image(ImageElement {
+            id:         self.id,
+            image:      self.image.clone(),
+            transform:  transform
+        })
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self) -> Vec<ControlPoint> {
+        self.corners().iter()
+            .map(|(x, y)| ControlPoint::BezierPoint(*x, *y))
+            .collect()
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    /// The vector here specifies the updated position for each control point in control_points. The
+    /// image is always a parallelogram, so the top-right corner is ignored.
+    ///
+    fn with_adjusted_control_points(&self, new_positions: Vec<(f32, f32)>) -> Vector {
+        let transform = if new_positions.len() == 4 {
+            Self::transform_from_axes(new_positions[0], new_positions[1], new_positions[3])
+        } else {
+            self.transform
+        };
+
+        Vector::Image(ImageElement {
+            id:         self.id,
+            image:      self.image.clone(),
+            transform:  transform
+        })
+    }
+}
+
+impl Into<Vector> for ImageElement {
+    #[inline]
+    fn into(self) -> Vector {
+        Vector::Image(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::*;
+
+    fn test_image() -> CanvasImage {
+        CanvasImage {
+            format: ImageFormat::Png,
+            width:  4,
+            height: 2,
+            data:   Arc::new(vec![1, 2, 3])
+        }
+    }
+
+    #[test]
+    fn image_outline_is_transformed_unit_square() {
+        let transform   = Transform2D::translate(10.0, 20.0) * Transform2D::scale(40.0, 20.0);
+        let image       = ImageElement::new(ElementId::Assigned(1), test_image(), transform);
+        let paths       = image.to_path(&VectorProperties::default()).unwrap();
+
+        assert!(paths.len() == 1);
+
+        let bounds      = Rect::from(&paths[0]);
+        assert!((bounds.x1-10.0).abs() < 0.01);
+        assert!((bounds.x2-50.0).abs() < 0.01);
+        assert!((bounds.y1-20.0).abs() < 0.01);
+        assert!((bounds.y2-40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn render_image_draws_with_transform() {
+        let transform   = Transform2D::scale(40.0, 20.0);
+        let image       = ImageElement::new(ElementId::Assigned(1), test_image(), transform);
+        let mut drawing: Vec<Draw> = vec![];
+
+        image.render(&mut drawing, &VectorProperties::default(), Duration::from_millis(0));
+
+        assert!(drawing == vec![Draw::DrawImage(test_image(), transform)]);
+    }
+
+    #[test]
+    fn moving_corners_updates_transform() {
+        let image       = ImageElement::new(ElementId::Assigned(1), test_image(), Transform2D::identity());
+        let moved       = image.with_adjusted_control_points(vec![(10.0, 10.0), (30.0, 10.0), (30.0, 50.0), (10.0, 50.0)]);
+
+        let corners     = match moved {
+            Vector::Image(moved)    => moved.corners(),
+            _                       => panic!("Not an image")
+        };
+
+        assert!(corners == [(10.0, 10.0), (30.0, 10.0), (30.0, 50.0), (10.0, 50.0)]);
+    }
+}
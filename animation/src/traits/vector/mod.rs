@@ -4,6 +4,8 @@ mod element;
 mod group_type;
 mod vector_type;
 mod path_element;
+mod text_element;
+mod image_element;
 mod control_point;
 mod brush_element;
 mod group_element;
@@ -18,6 +20,8 @@ pub use self::element::*;
 pub use self::group_type::*;
 pub use self::vector_type::*;
 pub use self::path_element::*;
+pub use self::text_element::*;
+pub use self::image_element::*;
 pub use self::control_point::*;
 pub use self::brush_element::*;
 pub use self::group_element::*;
@@ -0,0 +1,248 @@
+use super::vector::*;
+use super::element::*;
+use super::properties::*;
+use super::control_point::*;
+use super::super::path::*;
+use super::super::edit::*;
+use super::super::motion::*;
+use super::super::text_style::*;
+
+use flo_canvas::*;
+
+use std::iter;
+use std::time::Duration;
+
+/// Distance between the baselines of each line of text, as a multiple of the font size
+const LINE_HEIGHT: f32 = 1.2;
+
+/// Approximate width of a character, as a multiple of the font size
+const CHARACTER_WIDTH: f32 = 0.6;
+
+/// Approximate height of the tallest glyphs above the baseline, as a multiple of the font size
+const ASCENT: f32 = 0.8;
+
+/// Approximate depth of the lowest glyphs below the baseline, as a multiple of the font size
+const DESCENT: f32 = 0.25;
+
+///
+/// Element representing some text
+///
+#[derive(Clone, Debug)]
+pub struct TextElement {
+    /// The ID of this element
+    id: ElementId,
+
+    /// Where the baseline of the first line of text is anchored
+    position: (f32, f32),
+
+    /// The text to display (each line of text is separated by a newline)
+    text: String,
+
+    /// How the text is rendered
+    style: TextStyle
+}
+
+impl TextElement {
+    ///
+    /// Creates a new text element
+    ///
+    pub fn new(id: ElementId, position: (f32, f32), text: String, style: TextStyle) -> TextElement {
+        TextElement {
+            id,
+            position,
+            text,
+            style
+        }
+    }
+
+    ///
+    /// The position of the baseline of the first line of text in this element
+    ///
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    ///
+    /// The text displayed by this element
+    ///
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    ///
+    /// The style used to render this element
+    ///
+    pub fn style(&self) -> &TextStyle {
+        &self.style
+    }
+
+    ///
+    /// Returns the baseline position of each line of text in this element
+    ///
+    fn lines<'a>(&'a self) -> impl 'a+Iterator<Item=(&'a str, f32, f32)> {
+        let (x, y)      = self.position;
+        let line_height = self.style.font_size * LINE_HEIGHT;
+
+        self.text.split('\n')
+            .enumerate()
+            .map(move |(line_num, line)| (line, x, y - (line_num as f32)*line_height))
+    }
+}
+
+impl VectorElement for TextElement {
+    ///
+    /// The ID of this element
+    ///
+    fn id(&self) -> ElementId {
+        self.id
+    }
+
+    ///
+    /// Retrieves the paths for this element, if there are any
+    ///
+    /// Fonts are supplied by the UI so the exact glyph outlines aren't known here: instead, this
+    /// returns an approximate bounding box for each line of text.
+    ///
+    fn to_path(&self, _properties: &VectorProperties) -> Option<Vec<Path>> {
+        let font_size = self.style.font_size;
+
+        let paths = self.lines()
+            .filter(|(line, _, _)| line.len() > 0)
+            .map(|(line, x, y)| {
+                // Estimate the width of the line and work out where it starts
+                let width   = (line.chars().count() as f32) * font_size * CHARACTER_WIDTH;
+                let x1      = match self.style.alignment {
+                    TextAlignment::Left     => x,
+                    TextAlignment::Center   => x - width/2.0,
+                    TextAlignment::Right    => x - width
+                };
+                let x2      = x1 + width;
+                let y1      = y - font_size*DESCENT;
+                let y2      = y + font_size*ASCENT;
+
+                Path::from_elements(vec![
+                    PathComponent::Move(PathPoint::new(x1, y1)),
+                    PathComponent::Line(PathPoint::new(x2, y1)),
+                    PathComponent::Line(PathPoint::new(x2, y2)),
+                    PathComponent::Line(PathPoint::new(x1, y2)),
+                    PathComponent::Line(PathPoint::new(x1, y1)),
+                    PathComponent::Close
+                ])
+            })
+            .collect();
+
+        Some(paths)
+    }
+
+    ///
+    /// Renders this vector element
+    ///
+    fn render(&self, gc: &mut dyn GraphicsPrimitives, _properties: &VectorProperties, _when: Duration) {
+        gc.fill_color(self.style.color);
+        gc.font_family(&self.style.font_family);
+        gc.font_size(self.style.font_size);
+        gc.text_alignment(self.style.alignment);
+
+        for (line, x, y) in self.lines() {
+            gc.fill_text(line, x, y);
+        }
+    }
+
+    ///
+    /// Returns a new element that is this element transformed along a motion at a particular moment
+    /// in time.
+    ///
+    /// Text is always drawn upright, so only the position of the text is changed by the motion.
+    ///
+    fn motion_transform(&self, motion: &Motion, when: Duration) -> Vector {
+        let (x, y)              = self.position;
+        let position            = PathPoint::new(x, y);
+        let mut transformed     = motion.transform_path_points(when, iter::once(&position));
+        let transformed         = transformed.next().unwrap_or(position);
+
+        Vector::Text(TextElement {
+            id:         self.id,
+            position:   (transformed.x(), transformed.y()),
+            text:       self.text.clone(),
+            style:      self.style.clone()
+        })
+    }
+
+    ///
+    /// Fetches the control points for this element
+    ///
+    fn control_points(&self) -> Vec<ControlPoint> {
+        let (x, y) = self.position;
+
+        vec![ControlPoint::BezierPoint(x, y)]
+    }
+
+    ///
+    /// Creates a new vector element from this one with the control points updated to the specified set of new values
+    ///
+    /// The vector here specifies the updated position for each control point in control_points
+    ///
+    fn with_adjusted_control_points(&self, new_positions: Vec<(f32, f32)>) -> Vector {
+        let position = new_positions.into_iter().next().unwrap_or(self.position);
+
+        Vector::Text(TextElement {
+            id:         self.id,
+            position:   position,
+            text:       self.text.clone(),
+            style:      self.style.clone()
+        })
+    }
+}
+
+impl Into<Vector> for TextElement {
+    #[inline]
+    fn into(self) -> Vector {
+        Vector::Text(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn text_outline_is_box_around_line() {
+        let style   = TextStyle { font_size: 10.0, ..TextStyle::new() };
+        let text    = TextElement::new(ElementId::Assigned(1), (100.0, 200.0), "Title".to_string(), style);
+        let paths   = text.to_path(&VectorProperties::default()).unwrap();
+
+        assert!(paths.len() == 1);
+
+        let bounds  = Rect::from(&paths[0]);
+        assert!((bounds.x1-100.0).abs() < 0.01);
+        assert!((bounds.x2-130.0).abs() < 0.01);
+        assert!((bounds.y1-197.5).abs() < 0.01);
+        assert!((bounds.y2-208.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn each_line_has_an_outline() {
+        let style   = TextStyle { font_size: 10.0, alignment: TextAlignment::Center, ..TextStyle::new() };
+        let text    = TextElement::new(ElementId::Assigned(1), (0.0, 0.0), "Speech\nbubble".to_string(), style);
+        let paths   = text.to_path(&VectorProperties::default()).unwrap();
+
+        assert!(paths.len() == 2);
+
+        let first   = Rect::from(&paths[0]);
+        let second  = Rect::from(&paths[1]);
+        assert!((first.x1+18.0).abs() < 0.01);
+        assert!((first.x2-18.0).abs() < 0.01);
+        assert!((second.y2-first.y2+12.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn render_text_draws_each_line() {
+        let text        = TextElement::new(ElementId::Assigned(1), (0.0, 0.0), "One\nTwo".to_string(), TextStyle::new());
+        let mut drawing: Vec<Draw> = vec![];
+
+        text.render(&mut drawing, &VectorProperties::default(), Duration::from_millis(0));
+
+        assert!(drawing.contains(&Draw::FillText("One".to_string(), 0.0, 0.0)));
+        assert!(drawing.contains(&Draw::FillText("Two".to_string(), 0.0, -24.0*1.2)));
+    }
+}
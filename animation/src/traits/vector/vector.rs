@@ -1,5 +1,7 @@
 use super::element::*;
 use super::path_element::*;
+use super::text_element::*;
+use super::image_element::*;
 use super::brush_element::*;
 use super::group_element::*;
 use super::motion_element::*;
@@ -34,7 +36,13 @@ pub enum Vector {
     Motion(MotionElement),
 
     /// Element describing a group (with optional cache and path combining operation)
-    Group(GroupElement)
+    Group(GroupElement),
+
+    /// Text element
+    Text(TextElement),
+
+    /// Bitmap image element
+    Image(ImageElement)
 }
 
 impl Vector {
@@ -83,7 +91,9 @@ impl Deref for Vector {
 
             Path(elem)                      => elem,
             Motion(elem)                    => elem,
-            Group(elem)                     => elem
+            Group(elem)                     => elem,
+            Text(elem)                      => elem,
+            Image(elem)                     => elem
         }
     }
 }
@@ -21,7 +21,13 @@ pub enum VectorType {
     Motion,
 
     /// Group of other vector elements
-    Group
+    Group,
+
+    /// Vector element representing some text
+    Text,
+
+    /// Vector element representing a bitmap image
+    Image
 }
 
 impl From<&Vector> for VectorType {
@@ -35,7 +41,9 @@ impl From<&Vector> for VectorType {
             BrushStroke(_)                  => VectorType::BrushStroke,
             Path(_)                         => VectorType::Path,
             Motion(_)                       => VectorType::Motion,
-            Group(_)                        => VectorType::Group
+            Group(_)                        => VectorType::Group,
+            Text(_)                         => VectorType::Text,
+            Image(_)                        => VectorType::Image
         }
     }
 }
@@ -0,0 +1,151 @@
+use super::draw::*;
+
+/// Width of each character cell, as a multiple of the font size (this leaves a one-column gap between glyphs)
+const CELL_WIDTH: f32 = 0.6;
+
+/// Size of each block in a glyph, as a multiple of the font size
+const BLOCK_SIZE: f32 = 0.1;
+
+/// Glyph that's drawn for characters that aren't in the font
+const MISSING_GLYPH: [u8; 5] = [0x7f, 0x41, 0x41, 0x41, 0x7f];
+
+///
+/// 5x7 glyphs for the printable ASCII characters, starting at ' '
+///
+/// Each glyph is made up of 5 columns: the least significant bit is the top row of the column
+/// and bit 6 is the row that sits on the baseline.
+///
+const GLYPHS: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], [0x00, 0x00, 0x5f, 0x00, 0x00], [0x00, 0x07, 0x00, 0x07, 0x00], [0x14, 0x7f, 0x14, 0x7f, 0x14],
+    [0x24, 0x2a, 0x7f, 0x2a, 0x12], [0x23, 0x13, 0x08, 0x64, 0x62], [0x36, 0x49, 0x56, 0x20, 0x50], [0x00, 0x00, 0x07, 0x00, 0x00],
+    [0x00, 0x1c, 0x22, 0x41, 0x00], [0x00, 0x41, 0x22, 0x1c, 0x00], [0x2a, 0x1c, 0x7f, 0x1c, 0x2a], [0x08, 0x08, 0x3e, 0x08, 0x08],
+    [0x00, 0x50, 0x30, 0x00, 0x00], [0x08, 0x08, 0x08, 0x08, 0x08], [0x00, 0x60, 0x60, 0x00, 0x00], [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3e, 0x51, 0x49, 0x45, 0x3e], [0x00, 0x42, 0x7f, 0x40, 0x00], [0x42, 0x61, 0x51, 0x49, 0x46], [0x21, 0x41, 0x45, 0x4b, 0x31],
+    [0x18, 0x14, 0x12, 0x7f, 0x10], [0x27, 0x45, 0x45, 0x45, 0x39], [0x3c, 0x4a, 0x49, 0x49, 0x30], [0x01, 0x71, 0x09, 0x05, 0x03],
+    [0x36, 0x49, 0x49, 0x49, 0x36], [0x06, 0x49, 0x49, 0x29, 0x1e], [0x00, 0x36, 0x36, 0x00, 0x00], [0x00, 0x56, 0x36, 0x00, 0x00],
+    [0x08, 0x14, 0x22, 0x41, 0x00], [0x14, 0x14, 0x14, 0x14, 0x14], [0x00, 0x41, 0x22, 0x14, 0x08], [0x02, 0x01, 0x51, 0x09, 0x06],
+    [0x32, 0x49, 0x79, 0x41, 0x3e], [0x7e, 0x11, 0x11, 0x11, 0x7e], [0x7f, 0x49, 0x49, 0x49, 0x36], [0x3e, 0x41, 0x41, 0x41, 0x22],
+    [0x7f, 0x41, 0x41, 0x22, 0x1c], [0x7f, 0x49, 0x49, 0x49, 0x41], [0x7f, 0x09, 0x09, 0x09, 0x01], [0x3e, 0x41, 0x49, 0x49, 0x7a],
+    [0x7f, 0x08, 0x08, 0x08, 0x7f], [0x00, 0x41, 0x7f, 0x41, 0x00], [0x20, 0x40, 0x41, 0x3f, 0x01], [0x7f, 0x08, 0x14, 0x22, 0x41],
+    [0x7f, 0x40, 0x40, 0x40, 0x40], [0x7f, 0x02, 0x0c, 0x02, 0x7f], [0x7f, 0x04, 0x08, 0x10, 0x7f], [0x3e, 0x41, 0x41, 0x41, 0x3e],
+    [0x7f, 0x09, 0x09, 0x09, 0x06], [0x3e, 0x41, 0x51, 0x21, 0x5e], [0x7f, 0x09, 0x19, 0x29, 0x46], [0x46, 0x49, 0x49, 0x49, 0x31],
+    [0x01, 0x01, 0x7f, 0x01, 0x01], [0x3f, 0x40, 0x40, 0x40, 0x3f], [0x1f, 0x20, 0x40, 0x20, 0x1f], [0x3f, 0x40, 0x38, 0x40, 0x3f],
+    [0x63, 0x14, 0x08, 0x14, 0x63], [0x07, 0x08, 0x70, 0x08, 0x07], [0x61, 0x51, 0x49, 0x45, 0x43], [0x00, 0x7f, 0x41, 0x41, 0x00],
+    [0x02, 0x04, 0x08, 0x10, 0x20], [0x00, 0x41, 0x41, 0x7f, 0x00], [0x04, 0x02, 0x01, 0x02, 0x04], [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x01, 0x02, 0x04, 0x00], [0x20, 0x54, 0x54, 0x54, 0x78], [0x7f, 0x48, 0x44, 0x44, 0x38], [0x38, 0x44, 0x44, 0x44, 0x20],
+    [0x38, 0x44, 0x44, 0x48, 0x7f], [0x38, 0x54, 0x54, 0x54, 0x18], [0x08, 0x7e, 0x09, 0x01, 0x02], [0x0c, 0x52, 0x52, 0x52, 0x3e],
+    [0x7f, 0x08, 0x04, 0x04, 0x78], [0x00, 0x44, 0x7d, 0x40, 0x00], [0x20, 0x40, 0x44, 0x3d, 0x00], [0x7f, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7f, 0x40, 0x00], [0x7c, 0x04, 0x18, 0x04, 0x78], [0x7c, 0x08, 0x04, 0x04, 0x78], [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0x7c, 0x14, 0x14, 0x14, 0x08], [0x08, 0x14, 0x14, 0x18, 0x7c], [0x7c, 0x08, 0x04, 0x04, 0x08], [0x48, 0x54, 0x54, 0x54, 0x20],
+    [0x04, 0x3f, 0x44, 0x40, 0x20], [0x3c, 0x40, 0x40, 0x20, 0x7c], [0x1c, 0x20, 0x40, 0x20, 0x1c], [0x3c, 0x40, 0x30, 0x40, 0x3c],
+    [0x44, 0x28, 0x10, 0x28, 0x44], [0x0c, 0x50, 0x50, 0x50, 0x3c], [0x44, 0x64, 0x54, 0x4c, 0x44], [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x7f, 0x00, 0x00], [0x00, 0x41, 0x36, 0x08, 0x00], [0x08, 0x04, 0x08, 0x10, 0x08]
+];
+
+///
+/// Retrieves the glyph for a character
+///
+fn glyph_for_char(c: char) -> [u8; 5] {
+    let code = c as u32;
+
+    if code >= 0x20 && code < 0x7f {
+        GLYPHS[(code - 0x20) as usize]
+    } else if c.is_whitespace() {
+        GLYPHS[0]
+    } else {
+        MISSING_GLYPH
+    }
+}
+
+///
+/// The width of a line of text when it's drawn using the block font
+///
+pub fn block_font_text_width(text: &str, font_size: f32) -> f32 {
+    (text.chars().count() as f32) * font_size * CELL_WIDTH
+}
+
+///
+/// Generates the path instructions for a line of text using a simple built-in block font
+///
+/// This is used by canvas implementations that can't load fonts to render `FillText` instructions,
+/// by filling the path that this returns. The characters are the same size as the estimate used
+/// for the bounding box of text elements (0.6 times the font size across, with capital letters
+/// 0.7 times the font size high). `(x, y)` is the position of the start of the baseline, adjusted
+/// by the alignment.
+///
+pub fn block_font_text_path(text: &str, x: f32, y: f32, font_size: f32, alignment: TextAlignment) -> Vec<Draw> {
+    let width   = block_font_text_width(text, font_size);
+    let block   = font_size * BLOCK_SIZE;
+    let start_x = match alignment {
+        TextAlignment::Left     => x,
+        TextAlignment::Center   => x - width/2.0,
+        TextAlignment::Right    => x - width
+    };
+
+    let mut path = vec![];
+
+    for (char_num, c) in text.chars().enumerate() {
+        let glyph       = glyph_for_char(c);
+        let char_x      = start_x + (char_num as f32) * font_size * CELL_WIDTH;
+
+        for (column_num, column) in glyph.iter().enumerate() {
+            let x1 = char_x + (column_num as f32) * block;
+            let x2 = x1 + block;
+
+            // Runs of set bits are drawn as a single rectangle
+            let mut row = 0;
+            while row < 7 {
+                if column & (1<<row) == 0 {
+                    row += 1;
+                    continue;
+                }
+
+                let run_start = row;
+                while row < 7 && column & (1<<row) != 0 {
+                    row += 1;
+                }
+
+                // Row 6 sits on the baseline
+                let y1 = y + ((7-row) as f32) * block;
+                let y2 = y + ((7-run_start) as f32) * block;
+
+                path.extend(vec![
+                    Draw::Move(x1, y1),
+                    Draw::Line(x2, y1),
+                    Draw::Line(x2, y2),
+                    Draw::Line(x1, y2),
+                    Draw::ClosePath
+                ]);
+            }
+        }
+    }
+
+    path
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn space_has_no_path() {
+        assert!(block_font_text_path(" ", 0.0, 0.0, 10.0, TextAlignment::Left).len() == 0);
+    }
+
+    #[test]
+    fn vertical_bar_is_one_rectangle() {
+        let path = block_font_text_path("|", 0.0, 0.0, 10.0, TextAlignment::Left);
+
+        assert!(path == vec![Draw::Move(2.0, 0.0), Draw::Line(3.0, 0.0), Draw::Line(3.0, 7.0), Draw::Line(2.0, 7.0), Draw::ClosePath]);
+    }
+
+    #[test]
+    fn right_aligned_text_ends_at_position() {
+        let path = block_font_text_path("|", 0.0, 0.0, 10.0, TextAlignment::Right);
+
+        match path[0] {
+            Draw::Move(x, y)    => { assert!((x - -4.0).abs() < 0.001); assert!(y == 0.0); },
+            _                   => { assert!(false); }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use super::gc::*;
 use super::draw::*;
 use super::color::*;
+use super::image::*;
+use super::gradient::*;
 use super::transform2d::*;
 
 use std::collections::vec_deque::*;
@@ -92,6 +94,7 @@ impl CanvasCore {
                 match drawing {
                     &(_, Draw::ClearCanvas)         => true,
                     &(_, Draw::LayerBlend(_, _))    => true,
+                    &(_, Draw::LayerAlpha(_, _))    => true,
                     &(layer, _)                     => layer != layer_id
                 }
             })
@@ -121,12 +124,12 @@ impl CanvasCore {
                     new_drawing = vec![];
 
                     // Start the new drawing with the 'clear' command
-                    self.drawing_since_last_clear.push((0, *draw));
+                    self.drawing_since_last_clear.push((0, draw.clone()));
                 },
 
                 &Draw::Restore => {
                     // Have to push the restore in case it can't be cleared
-                    self.drawing_since_last_clear.push((self.current_layer, *draw));
+                    self.drawing_since_last_clear.push((self.current_layer, draw.clone()));
 
                     // On a 'restore' command we clear out everything since the 'store' if we can (so we don't build a backlog)
                     self.rewind_to_last_store();
@@ -139,13 +142,13 @@ impl CanvasCore {
                         self.drawing_since_last_clear.pop();
                     } else {
                         // Something else: the free becomes part of the drawing log (this is often inefficient)
-                        self.drawing_since_last_clear.push((self.current_layer, *draw));
+                        self.drawing_since_last_clear.push((self.current_layer, draw.clone()));
                     }
                 },
 
                 &Draw::Layer(new_layer) => {
                     self.current_layer = new_layer;
-                    self.drawing_since_last_clear.push((new_layer, *draw));
+                    self.drawing_since_last_clear.push((new_layer, draw.clone()));
                 },
 
                 &Draw::ClearLayer => {
@@ -156,11 +159,11 @@ impl CanvasCore {
                 },
 
                 // Default is to add to the current drawing
-                _ => self.drawing_since_last_clear.push((self.current_layer, *draw))
+                _ => self.drawing_since_last_clear.push((self.current_layer, draw.clone()))
             }
 
             // Send everything to the streams
-            new_drawing.push(*draw);
+            new_drawing.push(draw.clone());
         });
 
         // Send the new drawing commands to the streams
@@ -168,7 +171,7 @@ impl CanvasCore {
 
         for stream_index in 0..self.pending_streams.len() {
             // Send commands to this stream
-            if !self.pending_streams[stream_index].send_drawing(new_drawing.iter().map(|draw| *draw), clear_pending) {
+            if !self.pending_streams[stream_index].send_drawing(new_drawing.iter().map(|draw| draw.clone()), clear_pending) {
                 // If it returns false then the stream has been dropped and we should remove it from this object
                 to_remove.push(stream_index);
             }
@@ -234,7 +237,7 @@ impl Canvas {
         let add_stream = Arc::clone(&new_stream);
         self.core.sync(move |core| {
             // Send the data we've received since the last clear
-            add_stream.send_drawing(core.drawing_since_last_clear.iter().map(|&(_, draw)| draw), true);
+            add_stream.send_drawing(core.drawing_since_last_clear.iter().map(|&(_, ref draw)| draw.clone()), true);
 
             // Store the stream in the core so future notifications get sent there
             core.pending_streams.push(add_stream);
@@ -248,7 +251,7 @@ impl Canvas {
     /// Retrieves the list of drawing actions in this canvas
     ///
     pub fn get_drawing(&self) -> Vec<Draw> {
-        self.core.sync(|core| core.drawing_since_last_clear.iter().map(|&(_, draw)| draw.clone()).collect())
+        self.core.sync(|core| core.drawing_since_last_clear.iter().map(|&(_, ref draw)| draw.clone()).collect())
     }
 }
 
@@ -289,6 +292,7 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn dash_length(&mut self, length: f32)          { self.pending.push(Draw::DashLength(length)); }
     fn dash_offset(&mut self, offset: f32)          { self.pending.push(Draw::DashOffset(offset)); }
     fn fill_color(&mut self, col: Color)            { self.pending.push(Draw::FillColor(col)); }
+    fn fill_gradient(&mut self, gradient: &Gradient) { self.pending.push(Draw::FillGradient(gradient.clone())); }
     fn stroke_color(&mut self, col: Color)          { self.pending.push(Draw::StrokeColor(col)); }
     fn blend_mode(&mut self, mode: BlendMode)       { self.pending.push(Draw::BlendMode(mode)); }
     fn identity_transform(&mut self)                { self.pending.push(Draw::IdentityTransform); }
@@ -305,7 +309,13 @@ impl<'a> GraphicsContext for CoreContext<'a> {
     fn clear_canvas(&mut self)                      { self.pending.push(Draw::ClearCanvas); }
     fn layer(&mut self, layer_id: u32)              { self.pending.push(Draw::Layer(layer_id)); }
     fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode) { self.pending.push(Draw::LayerBlend(layer_id, blend_mode)); }
+    fn layer_alpha(&mut self, layer_id: u32, alpha: f32)    { self.pending.push(Draw::LayerAlpha(layer_id, alpha)); }
     fn clear_layer(&mut self)                       { self.pending.push(Draw::ClearLayer); }
+    fn font_family(&mut self, family: &str)         { self.pending.push(Draw::FontFamily(family.to_string())); }
+    fn font_size(&mut self, size: f32)              { self.pending.push(Draw::FontSize(size)); }
+    fn text_alignment(&mut self, alignment: TextAlignment) { self.pending.push(Draw::TextAlignment(alignment)); }
+    fn fill_text(&mut self, text: &str, x: f32, y: f32) { self.pending.push(Draw::FillText(text.to_string(), x, y)); }
+    fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D) { self.pending.push(Draw::DrawImage(image.clone(), transform)); }
 
     fn draw(&mut self, d: Draw)                     { self.pending.push(d); }
     fn draw_list<'b>(&'b mut self, drawing: Box<dyn 'b+Iterator<Item=Draw>>) {
@@ -1,5 +1,7 @@
 use super::draw::*;
 use super::color::*;
+use super::image::*;
+use super::gradient::*;
 use super::transform2d::*;
 
 use futures::*;
@@ -7,6 +9,7 @@ use futures::stream;
 use futures::task;
 
 use std::mem;
+use std::sync::*;
 use std::str::*;
 use std::result::Result;
 
@@ -23,6 +26,8 @@ enum DecoderState {
     Color,                          // 'C'
     Transform,                      // 'T'
     State,                          // 'Z'
+    Text,                           // 'X'
+    Image,                          // 'I'
 
     Move(String),                   // m (x, y)
     Line(String),                   // l (x, y)
@@ -38,6 +43,7 @@ enum DecoderState {
 
     ColorStroke(String),            // 'Cs' (r, g, b, a)
     ColorFill(String),              // 'Cf' (r, g, b, a)
+    ColorFillGradient(String),      // 'Cg' (gradient)
 
     BlendMode(String),              // 'M' (mode)
 
@@ -47,6 +53,14 @@ enum DecoderState {
 
     NewLayer(String),               // 'Nl' (id)
     NewLayerBlend(String),          // 'Nb' (id, mode)
+    NewLayerAlpha(String),          // 'Na' (id, alpha)
+
+    TextFontFamily(String),         // 'Xf' (family)
+    TextFontSize(String),           // 'Xs' (size)
+    TextAlignment(String),          // 'Xa' (alignment)
+    TextFill(String),               // 'Xt' (x, y, text)
+
+    ImageDraw(String),              // 'Id' (transform, format, width, height, data)
 }
 
 ///
@@ -107,6 +121,8 @@ impl CanvasDecoder {
             Color                           => Self::decode_color(next_chr)?,
             Transform                       => Self::decode_transform(next_chr)?,
             State                           => Self::decode_state(next_chr)?,
+            Text                            => Self::decode_text(next_chr)?,
+            Image                           => Self::decode_image(next_chr)?,
 
             Move(param)                     => Self::decode_move(next_chr, param)?,
             Line(param)                     => Self::decode_line(next_chr, param)?,
@@ -122,6 +138,7 @@ impl CanvasDecoder {
 
             ColorStroke(param)              => Self::decode_color_stroke(next_chr, param)?,
             ColorFill(param)                => Self::decode_color_fill(next_chr, param)?,
+            ColorFillGradient(param)        => Self::decode_fill_gradient(next_chr, param)?,
 
             BlendMode(param)                => Self::decode_blend_mode(next_chr, param)?,
 
@@ -130,7 +147,15 @@ impl CanvasDecoder {
             TransformMultiply(param)        => Self::decode_transform_multiply(next_chr, param)?,
 
             NewLayer(param)                 => Self::decode_new_layer(next_chr, param)?,
-            NewLayerBlend(param)            => Self::decode_new_layer_blend(next_chr, param)?
+            NewLayerBlend(param)            => Self::decode_new_layer_blend(next_chr, param)?,
+            NewLayerAlpha(param)            => Self::decode_new_layer_alpha(next_chr, param)?,
+
+            TextFontFamily(param)           => Self::decode_font_family(next_chr, param)?,
+            TextFontSize(param)             => Self::decode_font_size(next_chr, param)?,
+            TextAlignment(param)            => Self::decode_text_alignment(next_chr, param)?,
+            TextFill(param)                 => Self::decode_fill_text(next_chr, param)?,
+
+            ImageDraw(param)                => Self::decode_draw_image(next_chr, param)?
         };
 
         self.state = next_state;
@@ -152,6 +177,8 @@ impl CanvasDecoder {
             'C' => Ok((DecoderState::Color, None)),
             'T' => Ok((DecoderState::Transform, None)),
             'Z' => Ok((DecoderState::State, None)),
+            'X' => Ok((DecoderState::Text, None)),
+            'I' => Ok((DecoderState::Image, None)),
 
             // Single character commands
             '.' => Ok((DecoderState::None, Some(Draw::ClosePath))),
@@ -180,6 +207,7 @@ impl CanvasDecoder {
 
             'l'     => Ok((DecoderState::NewLayer(String::new()), None)),
             'b'     => Ok((DecoderState::NewLayerBlend(String::new()), None)),
+            'a'     => Ok((DecoderState::NewLayerAlpha(String::new()), None)),
 
             _       => Err(DecoderError::InvalidCharacter(next_chr))
         }
@@ -214,6 +242,7 @@ impl CanvasDecoder {
         match next_chr {
             's'     => Ok((DecoderState::ColorStroke(String::new()), None)),
             'f'     => Ok((DecoderState::ColorFill(String::new()), None)),
+            'g'     => Ok((DecoderState::ColorFillGradient(String::new()), None)),
 
             _       => Err(DecoderError::InvalidCharacter(next_chr))
         }
@@ -244,6 +273,27 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_text(next_chr: char) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Matched 'X' so far
+        match next_chr {
+            'f'     => Ok((DecoderState::TextFontFamily(String::new()), None)),
+            's'     => Ok((DecoderState::TextFontSize(String::new()), None)),
+            'a'     => Ok((DecoderState::TextAlignment(String::new()), None)),
+            't'     => Ok((DecoderState::TextFill(String::new()), None)),
+
+            _       => Err(DecoderError::InvalidCharacter(next_chr))
+        }
+    }
+
+    #[inline] fn decode_image(next_chr: char) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Matched 'I' so far
+        match next_chr {
+            'd'     => Ok((DecoderState::ImageDraw(String::new()), None)),
+
+            _       => Err(DecoderError::InvalidCharacter(next_chr))
+        }
+    }
+
     #[inline] fn decode_line_width_pixels(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         if param.len() < 5 {
             param.push(next_chr);
@@ -407,6 +457,48 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_fill_gradient(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Gradient type and coordinates, the number of stops, then the stops (31 characters each)
+        param.push(next_chr);
+
+        let header_len = match param.chars().next() {
+            Some('L')   => 25,
+            Some('R')   => 19,
+            Some(other) => { return Err(DecoderError::InvalidCharacter(other)); },
+            None        => { return Err(DecoderError::MissingCharacter); }
+        };
+
+        if param.len() < header_len + 6 {
+            return Ok((DecoderState::ColorFillGradient(param), None));
+        }
+
+        let num_stops = Self::decode_u32(&mut param[header_len..].chars())? as usize;
+        if param.len() < header_len + 6 + num_stops*31 {
+            return Ok((DecoderState::ColorFillGradient(param), None));
+        }
+
+        let mut param   = param.chars();
+        let gradient    = match param.next() {
+            Some('L')   => {
+                let start   = (Self::decode_f32(&mut param)?, Self::decode_f32(&mut param)?);
+                let end     = (Self::decode_f32(&mut param)?, Self::decode_f32(&mut param)?);
+                let stops   = Self::decode_gradient_stops(&mut param)?;
+
+                Gradient::Linear(start, end, stops)
+            },
+
+            _           => {
+                let center  = (Self::decode_f32(&mut param)?, Self::decode_f32(&mut param)?);
+                let radius  = Self::decode_f32(&mut param)?;
+                let stops   = Self::decode_gradient_stops(&mut param)?;
+
+                Gradient::Radial(center, radius, stops)
+            }
+        };
+
+        Ok((DecoderState::None, Some(Draw::FillGradient(gradient))))
+    }
+
     #[inline] fn decode_blend_mode(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         if param.len() < 1 {
             param.push(next_chr);
@@ -491,6 +583,200 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_new_layer_alpha(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 11 {
+            param.push(next_chr);
+            Ok((DecoderState::NewLayerAlpha(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let layer_id    = Self::decode_u32(&mut param)?;
+            let alpha       = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::LayerAlpha(layer_id, alpha))))
+        }
+    }
+
+    #[inline] fn decode_font_family(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        param.push(next_chr);
+
+        if Self::is_string_complete(&param)? {
+            let mut param   = param.chars();
+            let family      = Self::decode_string(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FontFamily(family))))
+        } else {
+            Ok((DecoderState::TextFontFamily(param), None))
+        }
+    }
+
+    #[inline] fn decode_font_size(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 5 {
+            param.push(next_chr);
+            Ok((DecoderState::TextFontSize(param), None))
+        } else {
+            param.push(next_chr);
+            let mut param = param.chars();
+            Ok((DecoderState::None, Some(Draw::FontSize(Self::decode_f32(&mut param)?))))
+        }
+    }
+
+    #[inline] fn decode_text_alignment(next_chr: char, _param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        match next_chr {
+            'L' => Ok((DecoderState::None, Some(Draw::TextAlignment(TextAlignment::Left)))),
+            'C' => Ok((DecoderState::None, Some(Draw::TextAlignment(TextAlignment::Center)))),
+            'R' => Ok((DecoderState::None, Some(Draw::TextAlignment(TextAlignment::Right)))),
+
+            _ => Err(DecoderError::InvalidCharacter(next_chr))
+        }
+    }
+
+    #[inline] fn decode_fill_text(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        param.push(next_chr);
+
+        if param.len() > 12 && Self::is_string_complete(&param[12..])? {
+            let mut param   = param.chars();
+            let x           = Self::decode_f32(&mut param)?;
+            let y           = Self::decode_f32(&mut param)?;
+            let text        = Self::decode_string(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::FillText(text, x, y))))
+        } else {
+            Ok((DecoderState::TextFill(param), None))
+        }
+    }
+
+    #[inline] fn decode_draw_image(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        // Transform (54 characters), format (1 character), width, height and then the data
+        param.push(next_chr);
+
+        if param.len() > 67 && Self::is_bytes_complete(&param[67..])? {
+            let mut param   = param.chars();
+
+            let mut matrix  = [0.0; 9];
+            for entry in 0..9 {
+                matrix[entry] = Self::decode_f32(&mut param)?;
+            }
+            let transform   = Transform2D((matrix[0], matrix[1], matrix[2]), (matrix[3], matrix[4], matrix[5]), (matrix[6], matrix[7], matrix[8]));
+
+            let format      = match param.next() {
+                Some('P')   => ImageFormat::Png,
+                Some('J')   => ImageFormat::Jpeg,
+                Some(other) => { return Err(DecoderError::InvalidCharacter(other)); },
+                None        => { return Err(DecoderError::BadNumber); }
+            };
+            let width       = Self::decode_u32(&mut param)?;
+            let height      = Self::decode_u32(&mut param)?;
+            let data        = Self::decode_bytes(&mut param)?;
+
+            let image       = CanvasImage { format: format, width: width, height: height, data: Arc::new(data) };
+
+            Ok((DecoderState::None, Some(Draw::DrawImage(image, transform))))
+        } else {
+            Ok((DecoderState::ImageDraw(param), None))
+        }
+    }
+
+    ///
+    /// Returns true if the specified parameter contains a complete byte array (a length followed by the bytes in base64)
+    ///
+    fn is_bytes_complete(param: &str) -> Result<bool, DecoderError> {
+        if param.len() < 6 {
+            Ok(false)
+        } else {
+            let length = Self::decode_u32(&mut param.chars())? as usize;
+            Ok(param.len() >= 6 + (length*4 + 2)/3)
+        }
+    }
+
+    ///
+    /// Consumes a length and the base64 characters that follow it to decode an array of bytes
+    ///
+    fn decode_bytes(param: &mut Chars) -> Result<Vec<u8>, DecoderError> {
+        let length      = Self::decode_u32(param)? as usize;
+        let mut result  = Vec::with_capacity(length);
+
+        while result.len() < length {
+            // Each group of 4 characters encodes 3 bytes (the last group may be shorter)
+            let remaining   = length - result.len();
+            let num_chars   = if remaining >= 3 { 4 } else { remaining + 1 };
+            let mut group   = 0u32;
+
+            for index in 0..4 {
+                let value = if index < num_chars { Self::decode_base64(param.next().ok_or(DecoderError::BadNumber)?)? } else { 0 };
+                group = (group << 6) | (value as u32);
+            }
+
+            result.push((group >> 16) as u8);
+            if remaining > 1 { result.push((group >> 8) as u8); }
+            if remaining > 2 { result.push(group as u8); }
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Returns true if the specified parameter contains a complete string (a length followed by that many characters)
+    ///
+    fn is_string_complete(param: &str) -> Result<bool, DecoderError> {
+        if param.len() < 6 {
+            Ok(false)
+        } else {
+            let length = Self::decode_u32(&mut param.chars())? as usize;
+            Ok(param.len() >= 6 + length*6)
+        }
+    }
+
+    ///
+    /// Consumes a length and that many characters (6 characters each) to decode a string
+    ///
+    fn decode_string(param: &mut Chars) -> Result<String, DecoderError> {
+        let length      = Self::decode_u32(param)?;
+        let mut result  = String::new();
+
+        for _ in 0..length {
+            let code_point = Self::decode_u32(param)?;
+            result.push(char::from_u32(code_point).ok_or(DecoderError::BadNumber)?);
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Consumes a count and that many gradient stops (31 characters each)
+    ///
+    fn decode_gradient_stops(param: &mut Chars) -> Result<Vec<GradientStop>, DecoderError> {
+        let num_stops   = Self::decode_u32(param)?;
+        let mut stops   = vec![];
+
+        for _ in 0..num_stops {
+            let offset  = Self::decode_f32(param)?;
+            let color   = Self::decode_color_only(param)?;
+
+            stops.push(GradientStop { offset: offset, color: color });
+        }
+
+        Ok(stops)
+    }
+
+    ///
+    /// Consumes 25 characters to decode a colour
+    ///
+    fn decode_color_only(param: &mut Chars) -> Result<Color, DecoderError> {
+        let col_type    = param.next();
+        let r           = Self::decode_f32(param)?;
+        let g           = Self::decode_f32(param)?;
+        let b           = Self::decode_f32(param)?;
+        let a           = Self::decode_f32(param)?;
+
+        if col_type != Some('R') {
+            Err(DecoderError::UnknownColorType)?;
+        }
+
+        Ok(Color::Rgba(r, g, b, a))
+    }
+
     ///
     /// Consumes 2 characters to decode a blend mode
     ///
@@ -754,6 +1040,28 @@ mod test {
         check_round_trip_single(Draw::FillColor(Color::Rgba(0.2, 0.3, 0.4, 0.5)));
     }
 
+    #[test]
+    fn decode_fill_linear_gradient() {
+        check_round_trip_single(Draw::FillGradient(Gradient::Linear((10.0, 20.0), (30.0, 40.0), vec![
+            GradientStop::new(0.0, Color::Rgba(0.1, 0.2, 0.3, 0.4)),
+            GradientStop::new(0.6, Color::Rgba(0.5, 0.6, 0.7, 0.8)),
+            GradientStop::new(1.0, Color::Rgba(0.9, 1.0, 0.0, 1.0))
+        ])));
+    }
+
+    #[test]
+    fn decode_fill_radial_gradient() {
+        check_round_trip_single(Draw::FillGradient(Gradient::Radial((10.0, 20.0), 50.0, vec![
+            GradientStop::new(0.0, Color::Rgba(0.1, 0.2, 0.3, 0.4)),
+            GradientStop::new(1.0, Color::Rgba(0.5, 0.6, 0.7, 0.8))
+        ])));
+    }
+
+    #[test]
+    fn decode_gradient_without_stops() {
+        check_round_trip_single(Draw::FillGradient(Gradient::Radial((10.0, 20.0), 50.0, vec![])));
+    }
+
     #[test]
     fn decode_blend_mode() {
         check_round_trip_single(Draw::BlendMode(BlendMode::Lighten));
@@ -829,11 +1137,51 @@ mod test {
         check_round_trip_single(Draw::LayerBlend(76, BlendMode::Lighten))
     }
 
+    #[test]
+    fn decode_layer_alpha() {
+        check_round_trip_single(Draw::LayerAlpha(76, 0.5))
+    }
+
     #[test]
     fn decode_clear_layer() {
         check_round_trip_single(Draw::ClearLayer);
     }
 
+    #[test]
+    fn decode_font_family() {
+        check_round_trip_single(Draw::FontFamily("Helvetica".to_string()));
+    }
+
+    #[test]
+    fn decode_empty_font_family() {
+        check_round_trip_single(Draw::FontFamily("".to_string()));
+    }
+
+    #[test]
+    fn decode_font_size() {
+        check_round_trip_single(Draw::FontSize(24.0));
+    }
+
+    #[test]
+    fn decode_text_alignment() {
+        check_round_trip_single(Draw::TextAlignment(TextAlignment::Right));
+    }
+
+    #[test]
+    fn decode_fill_text() {
+        check_round_trip_single(Draw::FillText("Hello, wörld ✓".to_string(), 10.0, 20.0));
+    }
+
+    #[test]
+    fn decode_draw_image() {
+        for length in 0..7 {
+            let data    = (0..length).map(|byte| (byte * 97) as u8).collect::<Vec<_>>();
+            let image   = CanvasImage { format: ImageFormat::Jpeg, width: 640, height: 480, data: Arc::new(data) };
+
+            check_round_trip_single(Draw::DrawImage(image, Transform2D::translate(10.0, 20.0) * Transform2D::scale(640.0, 480.0)));
+        }
+    }
+
     #[test]
     fn will_accept_newlines() {
         let mut decoder = CanvasDecoder::new();
@@ -884,6 +1232,10 @@ mod test {
             Draw::ClearCanvas,
             Draw::Layer(21),
             Draw::ClearLayer,
+            Draw::FontFamily("serif".to_string()),
+            Draw::FontSize(12.0),
+            Draw::TextAlignment(TextAlignment::Center),
+            Draw::FillText("Text".to_string(), 1.0, 2.0),
             Draw::NewPath
         ]);
     }
@@ -921,6 +1273,10 @@ mod test {
             Draw::ClearCanvas,
             Draw::Layer(21),
             Draw::ClearLayer,
+            Draw::FontFamily("serif".to_string()),
+            Draw::FontSize(12.0),
+            Draw::TextAlignment(TextAlignment::Center),
+            Draw::FillText("Text".to_string(), 1.0, 2.0),
             Draw::NewPath
         ];
         let mut encoded = String::new();
@@ -4,6 +4,8 @@
 
 use super::transform2d::*;
 use super::color::*;
+use super::image::*;
+use super::gradient::*;
 
 ///
 /// Possible way to join lines
@@ -46,9 +48,19 @@ pub enum BlendMode {
 }
 
 ///
-/// Instructions for drawing to a canvas
+/// How text is aligned relative to the point where it is drawn
 ///
 #[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right
+}
+
+///
+/// Instructions for drawing to a canvas
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Draw {
     /// Begins a new path
     NewPath,
@@ -95,6 +107,9 @@ pub enum Draw {
     /// Set the fill color
     FillColor(Color),
 
+    /// Fill future paths with a gradient instead of a solid colour (until the next FillColor or FillGradient instruction)
+    FillGradient(Gradient),
+
     /// Set the line color
     StrokeColor(Color),
 
@@ -155,6 +170,28 @@ pub enum Draw {
     /// Sets how a particular layer is blended with the underlying layer
     LayerBlend(u32, BlendMode),
 
+    /// Sets the opacity of a particular layer (between 0.0 and 1.0) when it is blended with the underlying layer
+    LayerAlpha(u32, f32),
+
     /// Clears the current layer
-    ClearLayer
+    ClearLayer,
+
+    /// Sets the font family to use for future text (eg, 'Helvetica' or 'serif')
+    FontFamily(String),
+
+    /// Sets the height of the font to use for future text, in canvas units
+    FontSize(f32),
+
+    /// Sets how future text is aligned relative to the point where it is drawn
+    TextAlignment(TextAlignment),
+
+    /// Fills a single line of text using the current fill colour, with its baseline starting at the specified point
+    ///
+    /// Glyphs are drawn upright in canvas coordinates (the tops of the glyphs point towards increasing y values)
+    FillText(String, f32, f32),
+
+    /// Draws an image, using the transform to map the unit square onto the canvas
+    ///
+    /// (0, 0) is the bottom-left corner of the image and (1, 1) is the top-right corner
+    DrawImage(CanvasImage, Transform2D)
 }
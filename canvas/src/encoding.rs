@@ -1,5 +1,7 @@
 use super::draw::*;
 use super::color::*;
+use super::image::*;
+use super::gradient::*;
 use super::transform2d::*;
 
 ///
@@ -49,6 +51,33 @@ impl CanvasEncoding<String> for f32 {
     }
 }
 
+impl<'a> CanvasEncoding<String> for &'a str {
+    #[inline]
+    fn encode_canvas(&self, append_to: &mut String) {
+        // Strings are encoded as a character count followed by the code point of each character
+        (self.chars().count() as u32).encode_canvas(append_to);
+        self.chars().for_each(|chr| (chr as u32).encode_canvas(append_to));
+    }
+}
+
+impl<'a> CanvasEncoding<String> for &'a [u8] {
+    fn encode_canvas(&self, append_to: &mut String) {
+        // Byte arrays are encoded as a length followed by the bytes in base64 (without padding)
+        (self.len() as u32).encode_canvas(append_to);
+
+        for chunk in self.chunks(3) {
+            let b0 = chunk[0] as usize;
+            let b1 = chunk.get(1).cloned().unwrap_or(0) as usize;
+            let b2 = chunk.get(2).cloned().unwrap_or(0) as usize;
+
+            append_to.push(ENCODING_CHAR_SET[b0 >> 2]);
+            append_to.push(ENCODING_CHAR_SET[((b0 & 0x3) << 4) | (b1 >> 4)]);
+            if chunk.len() > 1 { append_to.push(ENCODING_CHAR_SET[((b1 & 0xf) << 2) | (b2 >> 6)]); }
+            if chunk.len() > 2 { append_to.push(ENCODING_CHAR_SET[b2 & 0x3f]); }
+        }
+    }
+}
+
 //
 // Some convenience encodings for implementing the main canvas encoding
 //
@@ -150,6 +179,55 @@ impl CanvasEncoding<String> for BlendMode {
     }
 }
 
+impl CanvasEncoding<String> for TextAlignment {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::TextAlignment::*;
+
+        match self {
+            &Left   => 'L',
+            &Center => 'C',
+            &Right  => 'R'
+        }.encode_canvas(append_to)
+    }
+}
+
+impl CanvasEncoding<String> for ImageFormat {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::ImageFormat::*;
+
+        match self {
+            &Png    => 'P',
+            &Jpeg   => 'J'
+        }.encode_canvas(append_to)
+    }
+}
+
+impl CanvasEncoding<String> for CanvasImage {
+    fn encode_canvas(&self, append_to: &mut String) {
+        (self.format, self.width, self.height, &self.data[..]).encode_canvas(append_to)
+    }
+}
+
+impl CanvasEncoding<String> for GradientStop {
+    fn encode_canvas(&self, append_to: &mut String) {
+        (self.offset, self.color).encode_canvas(append_to)
+    }
+}
+
+impl CanvasEncoding<String> for Gradient {
+    fn encode_canvas(&self, append_to: &mut String) {
+        use self::Gradient::*;
+
+        // The type and coordinates of the gradient, followed by the number of stops and the stops themselves
+        let stops = match self {
+            &Linear(start, end, ref stops)      => { ('L', start, end, stops.len() as u32).encode_canvas(append_to); stops },
+            &Radial(center, radius, ref stops)  => { ('R', center, radius, stops.len() as u32).encode_canvas(append_to); stops }
+        };
+
+        stops.iter().for_each(|stop| stop.encode_canvas(append_to));
+    }
+}
+
 impl CanvasEncoding<String> for Transform2D {
     fn encode_canvas(&self, append_to: &mut String) {
         let Transform2D(a, b, c) = *self;
@@ -180,6 +258,7 @@ impl CanvasEncoding<String> for Draw {
             &DashOffset(offset)                     => ('D', 'o', offset).encode_canvas(append_to),
             &StrokeColor(col)                       => ('C', 's', col).encode_canvas(append_to),
             &FillColor(col)                         => ('C', 'f', col).encode_canvas(append_to),
+            &FillGradient(ref gradient)             => { ('C', 'g').encode_canvas(append_to); gradient.encode_canvas(append_to) }
             &BlendMode(mode)                        => ('M', mode).encode_canvas(append_to),
             &IdentityTransform                      => ('T', 'i').encode_canvas(append_to),
             &CanvasHeight(height)                   => ('T', 'h', height).encode_canvas(append_to),
@@ -195,7 +274,13 @@ impl CanvasEncoding<String> for Draw {
             &ClearCanvas                            => ('N', 'A').encode_canvas(append_to),
             &Layer(layer_id)                        => ('N', 'l', layer_id).encode_canvas(append_to),
             &LayerBlend(layer_id, blend_mode)       => ('N', 'b', layer_id, blend_mode).encode_canvas(append_to),
-            &ClearLayer                             => ('N', 'C').encode_canvas(append_to)
+            &LayerAlpha(layer_id, alpha)            => ('N', 'a', layer_id, alpha).encode_canvas(append_to),
+            &ClearLayer                             => ('N', 'C').encode_canvas(append_to),
+            &FontFamily(ref family)                 => ('X', 'f', family.as_str()).encode_canvas(append_to),
+            &FontSize(size)                         => ('X', 's', size).encode_canvas(append_to),
+            &TextAlignment(alignment)               => ('X', 'a', alignment).encode_canvas(append_to),
+            &FillText(ref text, x, y)               => ('X', 't', x, y, text.as_str()).encode_canvas(append_to),
+            &DrawImage(ref image, transform)        => { ('I', 'd', transform).encode_canvas(append_to); image.encode_canvas(append_to) }
         }
     }
 }
@@ -210,6 +295,8 @@ impl CanvasEncoding<String> for Vec<Draw> {
 mod test {
     use super::*;
 
+    use std::sync::*;
+
     #[test]
     fn can_encode_u32() {
         let test_number: u32 = 0xabcd1234;
@@ -269,6 +356,8 @@ mod test {
     #[test]
     fn can_encode_fillcolor() { assert!(&encode_draw(Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 1.0))) == "CfRAAAg/AAAAg/AAAAg/AAAAg/A") }
     #[test]
+    fn can_encode_fillgradient() { assert!(&encode_draw(Draw::FillGradient(Gradient::Linear((0.0, 0.0), (1.0, 0.0), vec![GradientStop::new(0.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))]))) == "CgLAAAAAAAAAAAAAAAg/AAAAAAABAAAAAAAAAAARAAAg/AAAAg/AAAAg/AAAAg/A") }
+    #[test]
     fn can_encode_blendmode() { assert!(&encode_draw(Draw::BlendMode(BlendMode::SourceOver)) == "MSV") }
     #[test]
     fn can_encode_identity_transform() { assert!(&encode_draw(Draw::IdentityTransform) == "Ti") }
@@ -294,4 +383,17 @@ mod test {
     fn can_encode_layer() { assert!(&encode_draw(Draw::Layer(2)) == "NlCAAAAA") }
     #[test]
     fn can_encode_clearlayer() { assert!(&encode_draw(Draw::ClearLayer) == "NC") }
+    #[test]
+    fn can_encode_fontfamily() { assert!(&encode_draw(Draw::FontFamily("A".to_string())) == "XfBAAAAABBAAAA") }
+    #[test]
+    fn can_encode_fontsize() { assert!(&encode_draw(Draw::FontSize(20.0)) == "XsAAAoBB") }
+    #[test]
+    fn can_encode_textalignment() { assert!(&encode_draw(Draw::TextAlignment(TextAlignment::Center)) == "XaC") }
+    #[test]
+    fn can_encode_filltext() { assert!(&encode_draw(Draw::FillText("A".to_string(), 20.0, 20.0)) == "XtAAAoBBAAAoBBBAAAAABBAAAA") }
+    #[test]
+    fn can_encode_drawimage() {
+        let image = CanvasImage { format: ImageFormat::Png, width: 1, height: 2, data: Arc::new(b"Man".to_vec()) };
+        assert!(&encode_draw(Draw::DrawImage(image, Transform2D::identity())) == "IdAAAg/AAAAAAAAAAAAAAAAAAAAAAg/AAAAAAAAAAAAAAAAAAAAAAg/APBAAAAACAAAAADAAAAATWFu")
+    }
 }
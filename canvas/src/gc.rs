@@ -1,5 +1,7 @@
 use super::draw::*;
 use super::color::*;
+use super::image::*;
+use super::gradient::*;
 use super::transform2d::*;
 
 use curves::*;
@@ -27,6 +29,7 @@ pub trait GraphicsContext {
     fn dash_length(&mut self, length: f32);
     fn dash_offset(&mut self, offset: f32);
     fn fill_color(&mut self, col: Color);
+    fn fill_gradient(&mut self, gradient: &Gradient);
     fn stroke_color(&mut self, col: Color);
     fn blend_mode(&mut self, mode: BlendMode);
     fn identity_transform(&mut self);
@@ -43,7 +46,13 @@ pub trait GraphicsContext {
     fn clear_canvas(&mut self);
     fn layer(&mut self, layer_id: u32);
     fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode);
+    fn layer_alpha(&mut self, layer_id: u32, alpha: f32);
     fn clear_layer(&mut self);
+    fn font_family(&mut self, family: &str);
+    fn font_size(&mut self, size: f32);
+    fn text_alignment(&mut self, alignment: TextAlignment);
+    fn fill_text(&mut self, text: &str, x: f32, y: f32);
+    fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D);
 
     fn draw(&mut self, d: Draw) {
         use self::Draw::*;
@@ -64,6 +73,7 @@ pub trait GraphicsContext {
             DashLength(dash_length)                     => self.dash_length(dash_length),
             DashOffset(dash_offset)                     => self.dash_offset(dash_offset),
             FillColor(col)                              => self.fill_color(col),
+            FillGradient(gradient)                      => self.fill_gradient(&gradient),
             StrokeColor(col)                            => self.stroke_color(col),
             BlendMode(blendmode)                        => self.blend_mode(blendmode),
             IdentityTransform                           => self.identity_transform(),
@@ -80,7 +90,13 @@ pub trait GraphicsContext {
             ClearCanvas                                 => self.clear_canvas(),
             Layer(layer_id)                             => self.layer(layer_id),
             LayerBlend(layer_id, blend_mode)            => self.layer_blend(layer_id, blend_mode),
-            ClearLayer                                  => self.clear_layer()
+            LayerAlpha(layer_id, alpha)                 => self.layer_alpha(layer_id, alpha),
+            ClearLayer                                  => self.clear_layer(),
+            FontFamily(family)                          => self.font_family(&family),
+            FontSize(size)                              => self.font_size(size),
+            TextAlignment(alignment)                    => self.text_alignment(alignment),
+            FillText(text, x, y)                        => self.fill_text(&text, x, y),
+            DrawImage(image, transform)                 => self.draw_image(&image, transform)
         }
     }
 
@@ -192,6 +208,7 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn dash_length(&mut self, length: f32)                                    { self.push(Draw::DashLength(length)); }
     #[inline] fn dash_offset(&mut self, offset: f32)                                    { self.push(Draw::DashOffset(offset)); }
     #[inline] fn fill_color(&mut self, col: Color)                                      { self.push(Draw::FillColor(col)); }
+    #[inline] fn fill_gradient(&mut self, gradient: &Gradient)                          { self.push(Draw::FillGradient(gradient.clone())); }
     #[inline] fn stroke_color(&mut self, col: Color)                                    { self.push(Draw::StrokeColor(col)); }
     #[inline] fn blend_mode(&mut self, mode: BlendMode)                                 { self.push(Draw::BlendMode(mode)); }
     #[inline] fn identity_transform(&mut self)                                          { self.push(Draw::IdentityTransform); }
@@ -208,7 +225,13 @@ impl GraphicsContext for Vec<Draw> {
     #[inline] fn clear_canvas(&mut self)                                                { self.push(Draw::ClearCanvas); }
     #[inline] fn layer(&mut self, layer_id: u32)                                        { self.push(Draw::Layer(layer_id)); }
     #[inline] fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode)           { self.push(Draw::LayerBlend(layer_id, blend_mode)); }
+    #[inline] fn layer_alpha(&mut self, layer_id: u32, alpha: f32)                      { self.push(Draw::LayerAlpha(layer_id, alpha)); }
     #[inline] fn clear_layer(&mut self)                                                 { self.push(Draw::ClearLayer); }
+    #[inline] fn font_family(&mut self, family: &str)                                   { self.push(Draw::FontFamily(family.to_string())); }
+    #[inline] fn font_size(&mut self, size: f32)                                        { self.push(Draw::FontSize(size)); }
+    #[inline] fn text_alignment(&mut self, alignment: TextAlignment)                    { self.push(Draw::TextAlignment(alignment)); }
+    #[inline] fn fill_text(&mut self, text: &str, x: f32, y: f32)                       { self.push(Draw::FillText(text.to_string(), x, y)); }
+    #[inline] fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D)     { self.push(Draw::DrawImage(image.clone(), transform)); }
 
     #[inline]
     fn draw(&mut self, d: Draw) {
@@ -0,0 +1,197 @@
+use super::color::*;
+
+///
+/// A colour at a particular position along a gradient
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct GradientStop {
+    /// The position of this stop, from 0.0 (the start of the gradient) to 1.0 (the end)
+    pub offset: f32,
+
+    /// The colour at this stop
+    pub color: Color
+}
+
+///
+/// A gradient that can be used to fill a path
+///
+/// The coordinates of a gradient are in canvas units, and are transformed along with the path
+/// that is filled with it.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Gradient {
+    /// Gradient that changes colour along the line between the start and end points
+    Linear((f32, f32), (f32, f32), Vec<GradientStop>),
+
+    /// Gradient that changes colour moving out from a center point to a radius
+    Radial((f32, f32), f32, Vec<GradientStop>)
+}
+
+impl GradientStop {
+    ///
+    /// Creates a new gradient stop
+    ///
+    pub fn new(offset: f32, color: Color) -> GradientStop {
+        GradientStop {
+            offset: offset,
+            color:  color
+        }
+    }
+}
+
+impl Gradient {
+    ///
+    /// The colour stops for this gradient
+    ///
+    pub fn stops(&self) -> &Vec<GradientStop> {
+        match self {
+            Gradient::Linear(_, _, stops)   => stops,
+            Gradient::Radial(_, _, stops)   => stops
+        }
+    }
+
+    ///
+    /// The colour at the start of this gradient
+    ///
+    pub fn start_color(&self) -> Color {
+        self.stops().first().map(|stop| stop.color).unwrap_or(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+    }
+
+    ///
+    /// The colour at the end of this gradient
+    ///
+    pub fn end_color(&self) -> Color {
+        self.stops().last().map(|stop| stop.color).unwrap_or(Color::Rgba(0.0, 0.0, 0.0, 0.0))
+    }
+
+    ///
+    /// Returns a solid colour that approximates this gradient (for renderers that can't draw gradients)
+    ///
+    pub fn average_color(&self) -> Color {
+        let stops = self.stops();
+
+        if stops.len() == 0 {
+            return Color::Rgba(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let (r, g, b, a) = stops.iter()
+            .map(|stop| stop.color.to_rgba_components())
+            .fold((0.0, 0.0, 0.0, 0.0), |(r1, g1, b1, a1), (r2, g2, b2, a2)| (r1+r2, g1+g2, b1+b2, a1+a2));
+        let count = stops.len() as f32;
+
+        Color::Rgba(r/count, g/count, b/count, a/count)
+    }
+
+    ///
+    /// Returns the offset along this gradient of a point (0.0 is the start of the gradient and 1.0 is the end)
+    ///
+    pub fn offset_for_point(&self, x: f32, y: f32) -> f32 {
+        match *self {
+            Gradient::Linear((x1, y1), (x2, y2), _) => {
+                // Project the point onto the line between the start and end points
+                let (dx, dy)        = (x2-x1, y2-y1);
+                let length_squared  = dx*dx + dy*dy;
+
+                if length_squared == 0.0 { 0.0 } else { ((x-x1)*dx + (y-y1)*dy) / length_squared }
+            },
+
+            Gradient::Radial((cx, cy), radius, _)   => {
+                let (dx, dy) = (x-cx, y-cy);
+
+                if radius == 0.0 { 0.0 } else { (dx*dx + dy*dy).sqrt() / radius }
+            }
+        }
+    }
+
+    ///
+    /// Returns the colour at a particular offset along this gradient
+    ///
+    /// Offsets before the first stop or after the last stop use the colour of that stop.
+    ///
+    pub fn color_at_offset(&self, offset: f32) -> Color {
+        let stops = self.stops();
+
+        if stops.len() == 0 {
+            return Color::Rgba(0.0, 0.0, 0.0, 0.0);
+        }
+
+        if offset <= stops[0].offset {
+            return stops[0].color;
+        }
+
+        for pair in stops.windows(2) {
+            let (start, end) = (&pair[0], &pair[1]);
+
+            if offset <= end.offset {
+                // Interpolate between the two stops
+                let range           = end.offset - start.offset;
+                let ratio           = if range <= 0.0 { 1.0 } else { (offset - start.offset) / range };
+                let (r1, g1, b1, a1) = start.color.to_rgba_components();
+                let (r2, g2, b2, a2) = end.color.to_rgba_components();
+
+                return Color::Rgba(r1 + (r2-r1)*ratio, g1 + (g2-g1)*ratio, b1 + (b2-b1)*ratio, a1 + (a2-a1)*ratio);
+            }
+        }
+
+        self.end_color()
+    }
+
+    ///
+    /// Returns the colour of this gradient at a particular point
+    ///
+    pub fn color_at_point(&self, x: f32, y: f32) -> Color {
+        self.color_at_offset(self.offset_for_point(x, y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn average_of_black_and_white_is_grey() {
+        let gradient = Gradient::Linear((0.0, 0.0), (100.0, 0.0), vec![
+            GradientStop::new(0.0, Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))
+        ]);
+
+        assert!(gradient.average_color() == Color::Rgba(0.5, 0.5, 0.5, 1.0));
+    }
+
+    #[test]
+    fn start_and_end_colors() {
+        let gradient = Gradient::Radial((0.0, 0.0), 10.0, vec![
+            GradientStop::new(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(0.5, Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))
+        ]);
+
+        assert!(gradient.start_color() == Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        assert!(gradient.end_color() == Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn linear_gradient_color_at_point() {
+        let gradient = Gradient::Linear((0.0, 0.0), (10.0, 0.0), vec![
+            GradientStop::new(0.0, Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))
+        ]);
+
+        assert!(gradient.color_at_point(5.0, 3.0) == Color::Rgba(0.5, 0.5, 0.5, 1.0));
+        assert!(gradient.color_at_point(-5.0, 0.0) == Color::Rgba(0.0, 0.0, 0.0, 1.0));
+        assert!(gradient.color_at_point(20.0, 0.0) == Color::Rgba(1.0, 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn radial_gradient_color_at_point() {
+        let gradient = Gradient::Radial((0.0, 0.0), 10.0, vec![
+            GradientStop::new(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(0.5, Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))
+        ]);
+
+        assert!(gradient.color_at_point(0.0, 0.0) == Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        assert!(gradient.color_at_point(0.0, -5.0) == Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        assert!(gradient.color_at_point(30.0, 40.0) == Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,171 @@
+use std::sync::*;
+
+///
+/// The formats that the data for a canvas image can be encoded in
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ImageFormat {
+    Png,
+    Jpeg
+}
+
+///
+/// An image that can be drawn on a canvas
+///
+/// Images are stored in their encoded form and are decoded by the renderer. The data is shared
+/// between copies of the image so they can be drawn repeatedly without being copied.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct CanvasImage {
+    /// The format that the image data is encoded in
+    pub format: ImageFormat,
+
+    /// The width of the image in pixels
+    pub width: u32,
+
+    /// The height of the image in pixels
+    pub height: u32,
+
+    /// The encoded image data
+    pub data: Arc<Vec<u8>>
+}
+
+impl ImageFormat {
+    ///
+    /// The MIME type for data in this format
+    ///
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Png    => "image/png",
+            ImageFormat::Jpeg   => "image/jpeg"
+        }
+    }
+}
+
+impl CanvasImage {
+    ///
+    /// Creates an image from some PNG or JPEG data, reading the format and the size of the image from its header
+    ///
+    /// Returns None if the data is not in a supported format.
+    ///
+    pub fn from_data(data: Vec<u8>) -> Option<CanvasImage> {
+        let (format, (width, height)) = if let Some(size) = png_size(&data) {
+            (ImageFormat::Png, size)
+        } else if let Some(size) = jpeg_size(&data) {
+            (ImageFormat::Jpeg, size)
+        } else {
+            return None;
+        };
+
+        Some(CanvasImage {
+            format: format,
+            width:  width,
+            height: height,
+            data:   Arc::new(data)
+        })
+    }
+}
+
+///
+/// Reads a big-endian u16 from a byte slice
+///
+fn read_u16(data: &[u8], pos: usize) -> Option<u32> {
+    if pos + 2 <= data.len() {
+        Some(((data[pos] as u32) << 8) | (data[pos+1] as u32))
+    } else {
+        None
+    }
+}
+
+///
+/// Reads a big-endian u32 from a byte slice
+///
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some((read_u16(data, pos)? << 16) | read_u16(data, pos+2)?)
+}
+
+///
+/// Reads the size of a PNG image from its header
+///
+fn png_size(data: &[u8]) -> Option<(u32, u32)> {
+    const PNG_SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    // The IHDR chunk must follow the signature
+    if data.len() < 24 || data[0..8] != PNG_SIGNATURE || &data[12..16] != b"IHDR" {
+        return None;
+    }
+
+    Some((read_u32(data, 16)?, read_u32(data, 20)?))
+}
+
+///
+/// Reads the size of a JPEG image from its start of frame segment
+///
+fn jpeg_size(data: &[u8]) -> Option<(u32, u32)> {
+    if data.len() < 4 || data[0] != 0xff || data[1] != 0xd8 {
+        return None;
+    }
+
+    let mut pos = 2;
+
+    loop {
+        // Segments start with a marker, which may be padded with extra 0xff bytes
+        if *data.get(pos)? != 0xff { return None; }
+        while *data.get(pos+1)? == 0xff { pos += 1; }
+
+        let marker = data[pos+1];
+
+        match marker {
+            // Markers without a length
+            0x01 | 0xd0..=0xd7  => { pos += 2; },
+
+            // Start of frame markers (0xc4, 0xc8 and 0xcc are other segment types)
+            0xc0..=0xcf if marker != 0xc4 && marker != 0xc8 && marker != 0xcc => {
+                let height  = read_u16(data, pos+5)?;
+                let width   = read_u16(data, pos+7)?;
+
+                return Some((width, height));
+            },
+
+            // End of image or start of scan before the frame header means the file is invalid
+            0xd9 | 0xda         => { return None; },
+
+            // Other segments are skipped
+            _                   => { pos += 2 + (read_u16(data, pos+2)? as usize); }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_png_size() {
+        let header = vec![137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 0, 1, 0, 0, 0, 0, 200, 8, 6, 0, 0, 0];
+        let image  = CanvasImage::from_data(header).unwrap();
+
+        assert!(image.format == ImageFormat::Png);
+        assert!(image.width == 256);
+        assert!(image.height == 200);
+    }
+
+    #[test]
+    fn read_jpeg_size() {
+        let header = vec![
+            0xff, 0xd8,
+            0xff, 0xe0, 0x00, 0x04, 0x4a, 0x46,
+            0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0x2c, 0x02, 0x80, 0x03
+        ];
+        let image  = CanvasImage::from_data(header).unwrap();
+
+        assert!(image.format == ImageFormat::Jpeg);
+        assert!(image.width == 640);
+        assert!(image.height == 300);
+    }
+
+    #[test]
+    fn reject_unknown_format() {
+        assert!(CanvasImage::from_data(b"GIF89a".to_vec()).is_none());
+    }
+}
@@ -14,15 +14,21 @@ extern crate hsluv;
 mod gc;
 mod draw;
 mod color;
+mod image;
+mod gradient;
 mod canvas;
 mod encoding;
 mod decoding;
 mod transform2d;
+mod block_font;
 
 pub use self::gc::*;
 pub use self::draw::*;
 pub use self::color::*;
+pub use self::image::*;
+pub use self::gradient::*;
 pub use self::canvas::*;
 pub use self::encoding::*;
 pub use self::decoding::*;
 pub use self::transform2d::*;
+pub use self::block_font::*;
@@ -1,3 +1,5 @@
+use std::ops::Mul;
+
 ///
 /// Represents a 2D affine transformation matrix
 ///
@@ -12,4 +14,147 @@ impl Transform2D {
     pub fn translate(x: f32, y: f32) -> Transform2D {
         Transform2D((1.0, 0.0, x), (0.0, 1.0, y), (0.0, 0.0, 1.0))
     }
+
+    ///
+    /// Creates a transformation that scales about the origin by the specified factors along the x and y axes
+    ///
+    pub fn scale(x: f32, y: f32) -> Transform2D {
+        Transform2D((x, 0.0, 0.0), (0.0, y, 0.0), (0.0, 0.0, 1.0))
+    }
+
+    ///
+    /// Creates a transformation that rotates anticlockwise about the origin by an angle in radians
+    ///
+    pub fn rotate(radians: f32) -> Transform2D {
+        let (sin, cos) = radians.sin_cos();
+
+        Transform2D((cos, -sin, 0.0), (sin, cos, 0.0), (0.0, 0.0, 1.0))
+    }
+
+    ///
+    /// Creates a transformation that skews points by moving them along the x axis by `x` times their y coordinate
+    /// and along the y axis by `y` times their x coordinate
+    ///
+    pub fn skew(x: f32, y: f32) -> Transform2D {
+        Transform2D((1.0, x, 0.0), (y, 1.0, 0.0), (0.0, 0.0, 1.0))
+    }
+
+    ///
+    /// Applies this transformation to a point
+    ///
+    pub fn transform_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let Transform2D(a, b, _c) = *self;
+
+        (a.0*x + a.1*y + a.2, b.0*x + b.1*y + b.2)
+    }
+
+    ///
+    /// Returns the factor by which this transformation scales distances along the x axis
+    ///
+    /// (This is the scale factor used for line widths)
+    ///
+    pub fn scale_factor(&self) -> f32 {
+        let Transform2D(a, b, _c) = *self;
+        let scale = (a.0*a.0 + b.0*b.0).sqrt();
+
+        if scale == 0.0 { 1.0 } else { scale }
+    }
+
+    ///
+    /// Returns the inverse of this transformation, if it has one
+    ///
+    pub fn invert(&self) -> Option<Transform2D> {
+        let Transform2D(a, b, _c) = *self;
+        let determinant = a.0*b.1 - a.1*b.0;
+
+        if determinant == 0.0 {
+            None
+        } else {
+            let inv_det = 1.0/determinant;
+
+            let xx =  b.1 * inv_det;
+            let xy = -a.1 * inv_det;
+            let yx = -b.0 * inv_det;
+            let yy =  a.0 * inv_det;
+
+            Some(Transform2D(
+                (xx, xy, -(xx*a.2 + xy*b.2)),
+                (yx, yy, -(yx*a.2 + yy*b.2)),
+                (0.0, 0.0, 1.0)
+            ))
+        }
+    }
+}
+
+impl Mul<Transform2D> for Transform2D {
+    type Output = Transform2D;
+
+    ///
+    /// Combines two transformations (the transformation on the right is applied first)
+    ///
+    fn mul(self, other: Transform2D) -> Transform2D {
+        let Transform2D(a1, b1, c1) = self;
+        let Transform2D(a2, b2, c2) = other;
+
+        let row = |(x, y, z): (f32, f32, f32)| {
+            (
+                x*a2.0 + y*b2.0 + z*c2.0,
+                x*a2.1 + y*b2.1 + z*c2.1,
+                x*a2.2 + y*b2.2 + z*c2.2
+            )
+        };
+
+        Transform2D(row(a1), row(b1), row(c1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::f32;
+
+    #[test]
+    fn translate_point() {
+        let (x, y) = Transform2D::translate(10.0, 20.0).transform_point(1.0, 2.0);
+
+        assert!((x-11.0).abs() < 0.001);
+        assert!((y-22.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn multiply_applies_right_hand_side_first() {
+        let transform   = Transform2D::translate(10.0, 0.0) * Transform2D::scale(2.0, 2.0);
+        let (x, y)      = transform.transform_point(1.0, 1.0);
+
+        assert!((x-12.0).abs() < 0.001);
+        assert!((y-2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn rotate_point() {
+        let (x, y) = Transform2D::rotate(f32::consts::PI/2.0).transform_point(1.0, 0.0);
+
+        assert!(x.abs() < 0.001);
+        assert!((y-1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn skew_point() {
+        let (x, y) = Transform2D::skew(0.5, 0.0).transform_point(1.0, 2.0);
+
+        assert!((x-2.0).abs() < 0.001);
+        assert!((y-2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn invert_transform() {
+        let transform   = Transform2D::translate(10.0, 5.0) * Transform2D::scale(2.0, -4.0);
+        let inverse     = transform.invert().unwrap();
+        let (x, y)      = transform.transform_point(3.0, 7.0);
+        let (x, y)      = inverse.transform_point(x, y);
+
+        assert!((x-3.0).abs() < 0.001);
+        assert!((y-7.0).abs() < 0.001);
+    }
 }
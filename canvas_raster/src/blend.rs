@@ -0,0 +1,101 @@
+use flo_canvas::*;
+
+///
+/// A pixel with premultiplied alpha (red, green, blue, alpha)
+///
+pub type Pixel = [f32; 4];
+
+///
+/// Blends a source pixel onto a destination pixel (both pixels have premultiplied alpha)
+///
+pub fn blend_pixel(blend_mode: BlendMode, src: Pixel, dst: Pixel) -> Pixel {
+    use self::BlendMode::*;
+
+    let (sa, da) = (src[3], dst[3]);
+
+    // Applies a function to each of the colour components, with the alpha component computed separately
+    let per_component = |alpha: f32, component: &dyn Fn(f32, f32) -> f32| {
+        [component(src[0], dst[0]), component(src[1], dst[1]), component(src[2], dst[2]), alpha]
+    };
+
+    // Porter-Duff operations can be applied to the alpha component in the same way as the colour components
+    let porter_duff = |src_factor: f32, dst_factor: f32| {
+        [src[0]*src_factor + dst[0]*dst_factor, src[1]*src_factor + dst[1]*dst_factor, src[2]*src_factor + dst[2]*dst_factor, sa*src_factor + da*dst_factor]
+    };
+
+    let union_alpha = sa + da - sa*da;
+
+    match blend_mode {
+        SourceOver      => porter_duff(1.0, 1.0-sa),
+        SourceIn        => porter_duff(da, 0.0),
+        SourceOut       => porter_duff(1.0-da, 0.0),
+        DestinationOver => porter_duff(1.0-da, 1.0),
+        DestinationIn   => porter_duff(0.0, sa),
+        DestinationOut  => porter_duff(0.0, 1.0-sa),
+        SourceAtop      => porter_duff(da, 1.0-sa),
+        DestinationAtop => porter_duff(1.0-da, sa),
+
+        Multiply        => per_component(union_alpha, &|s, d| s*d + s*(1.0-da) + d*(1.0-sa)),
+        Screen          => per_component(union_alpha, &|s, d| s + d - s*d),
+        Darken          => per_component(union_alpha, &|s, d| (s*da).min(d*sa) + s*(1.0-da) + d*(1.0-sa)),
+        Lighten         => per_component(union_alpha, &|s, d| (s*da).max(d*sa) + s*(1.0-da) + d*(1.0-sa))
+    }
+}
+
+///
+/// Blends a source pixel onto a destination pixel, where the source only partially covers the destination
+///
+/// Pixels outside of the shape being drawn are left unaltered, even for the blend modes that would otherwise
+/// affect the whole destination (eg, SourceIn).
+///
+#[inline]
+pub fn blend_pixel_with_coverage(blend_mode: BlendMode, src: Pixel, dst: Pixel, coverage: f32) -> Pixel {
+    if coverage <= 0.0 {
+        dst
+    } else if coverage >= 1.0 {
+        blend_pixel(blend_mode, src, dst)
+    } else {
+        let blended = blend_pixel(blend_mode, src, dst);
+
+        [
+            dst[0] + (blended[0]-dst[0])*coverage,
+            dst[1] + (blended[1]-dst[1])*coverage,
+            dst[2] + (blended[2]-dst[2])*coverage,
+            dst[3] + (blended[3]-dst[3])*coverage
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close_to(a: Pixel, b: Pixel) -> bool {
+        a.iter().zip(b.iter()).all(|(a, b)| (a-b).abs() < 0.001)
+    }
+
+    #[test]
+    fn source_over_opaque() {
+        assert!(close_to(blend_pixel(BlendMode::SourceOver, [1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]), [1.0, 0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn source_over_translucent() {
+        assert!(close_to(blend_pixel(BlendMode::SourceOver, [0.5, 0.0, 0.0, 0.5], [0.0, 1.0, 0.0, 1.0]), [0.5, 0.5, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn destination_out_erases() {
+        assert!(close_to(blend_pixel(BlendMode::DestinationOut, [0.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]), [0.0, 0.0, 0.0, 0.0]));
+    }
+
+    #[test]
+    fn multiply_darkens() {
+        assert!(close_to(blend_pixel(BlendMode::Multiply, [0.5, 0.5, 0.5, 1.0], [1.0, 0.5, 0.0, 1.0]), [0.5, 0.25, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn partial_coverage_of_destination_in() {
+        assert!(close_to(blend_pixel_with_coverage(BlendMode::DestinationIn, [0.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 1.0], 0.5), [0.0, 0.5, 0.0, 0.5]));
+    }
+}
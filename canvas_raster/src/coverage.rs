@@ -0,0 +1,205 @@
+///
+/// Accumulates the signed area covered by a set of closed polygons, producing an anti-aliased coverage mask
+///
+/// Coverage is computed using the non-zero winding rule (overlapping areas with the same winding direction
+/// are not counted twice, so polygons can be combined by giving them the same orientation).
+///
+pub struct CoverageAccumulator {
+    /// Width of the mask in pixels
+    width: usize,
+
+    /// Height of the mask in pixels
+    height: usize,
+
+    /// The signed area changes for each row (each row has width+2 entries so that edges at the right-hand side have somewhere to go)
+    accumulation: Vec<f32>
+}
+
+impl CoverageAccumulator {
+    ///
+    /// Creates a new coverage accumulator for a mask of the specified size
+    ///
+    pub fn new(width: usize, height: usize) -> CoverageAccumulator {
+        CoverageAccumulator {
+            width:          width,
+            height:         height,
+            accumulation:   vec![0.0; (width+2)*height]
+        }
+    }
+
+    ///
+    /// Adds a polygon to this accumulator (the polygon is implicitly closed)
+    ///
+    pub fn add_polygon(&mut self, points: &[(f32, f32)]) {
+        if points.len() < 2 { return; }
+
+        for index in 0..points.len() {
+            let next = if index+1 < points.len() { index+1 } else { 0 };
+            self.add_line(points[index], points[next]);
+        }
+    }
+
+    ///
+    /// Adds a single edge to this accumulator
+    ///
+    fn add_line(&mut self, p0: (f32, f32), p1: (f32, f32)) {
+        if p0.1 == p1.1 { return; }
+        if !p0.0.is_finite() || !p0.1.is_finite() || !p1.0.is_finite() || !p1.1.is_finite() { return; }
+
+        // Always process edges from top to bottom, remembering the direction
+        let (direction, p0, p1) = if p0.1 < p1.1 { (1.0, p0, p1) } else { (-1.0, p1, p0) };
+
+        let height      = self.height as f32;
+        let width       = self.width as f32;
+        let stride      = self.width+2;
+        let dxdy        = (p1.0-p0.0)/(p1.1-p0.1);
+
+        // Clip to the top and bottom of the mask
+        let y_start     = p0.1.max(0.0);
+        let y_end       = p1.1.min(height);
+        if y_start >= y_end { return; }
+
+        let mut x       = p0.0 + dxdy*(y_start-p0.1);
+        let clamp_x     = |x: f32| x.max(0.0).min(width);
+
+        for y in (y_start as usize)..(y_end.ceil() as usize) {
+            let line_start  = y*stride;
+            let dy          = ((y+1) as f32).min(y_end) - (y as f32).max(y_start);
+            let x_next      = x + dxdy*dy;
+            let d           = dy*direction;
+
+            // Area to the left of the canvas is treated as being at x=0, and area to the right of the canvas is ignored
+            let (cx, cx_next)   = (clamp_x(x), clamp_x(x_next));
+            let (x0, x1)        = if cx < cx_next { (cx, cx_next) } else { (cx_next, cx) };
+
+            let x0_floor    = x0.floor();
+            let x0i         = x0_floor as usize;
+            let x1_ceil     = x1.ceil();
+            let x1i         = x1_ceil as usize;
+
+            if x1i <= x0i+1 {
+                // Edge is within a single pixel
+                let x_mid = 0.5*(cx+cx_next) - x0_floor;
+
+                self.accumulation[line_start+x0i]   += d - d*x_mid;
+                self.accumulation[line_start+x0i+1] += d*x_mid;
+            } else {
+                // Edge crosses several pixels
+                let s       = (x1-x0).recip();
+                let x0f     = x0 - x0_floor;
+                let a0      = 0.5*s*(1.0-x0f)*(1.0-x0f);
+                let x1f     = x1 - x1_ceil + 1.0;
+                let am      = 0.5*s*x1f*x1f;
+
+                self.accumulation[line_start+x0i] += d*a0;
+
+                if x1i == x0i+2 {
+                    self.accumulation[line_start+x0i+1] += d*(1.0-a0-am);
+                } else {
+                    let a1 = s*(1.5-x0f);
+                    self.accumulation[line_start+x0i+1] += d*(a1-a0);
+
+                    for xi in (x0i+2)..(x1i-1) {
+                        self.accumulation[line_start+xi] += d*s;
+                    }
+
+                    let a2 = a1 + ((x1i-x0i-3) as f32)*s;
+                    self.accumulation[line_start+x1i-1] += d*(1.0-a2-am);
+                }
+
+                self.accumulation[line_start+x1i] += d*am;
+            }
+
+            x = x_next;
+        }
+    }
+
+    ///
+    /// Converts the accumulated areas into a coverage mask (one value between 0 and 1 per pixel)
+    ///
+    pub fn to_coverage(&self) -> Vec<f32> {
+        let stride          = self.width+2;
+        let mut coverage    = vec![0.0; self.width*self.height];
+
+        for y in 0..self.height {
+            let mut total = 0.0;
+
+            for x in 0..self.width {
+                total += self.accumulation[y*stride + x];
+                coverage[y*self.width + x] = total.abs().min(1.0);
+            }
+        }
+
+        coverage
+    }
+}
+
+///
+/// Returns the signed area of a polygon
+///
+pub fn signed_area(points: &[(f32, f32)]) -> f32 {
+    let mut area = 0.0;
+
+    for index in 0..points.len() {
+        let next        = if index+1 < points.len() { index+1 } else { 0 };
+        let (x1, y1)    = points[index];
+        let (x2, y2)    = points[next];
+
+        area += x1*y2 - x2*y1;
+    }
+
+    area * 0.5
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn square_is_fully_covered() {
+        let mut accumulator = CoverageAccumulator::new(10, 10);
+        accumulator.add_polygon(&[(2.0, 2.0), (6.0, 2.0), (6.0, 6.0), (2.0, 6.0)]);
+
+        let coverage = accumulator.to_coverage();
+
+        assert!(coverage[3*10 + 3] == 1.0);
+        assert!(coverage[2*10 + 2] == 1.0);
+        assert!(coverage[1*10 + 1] == 0.0);
+        assert!(coverage[6*10 + 6] == 0.0);
+        assert!(coverage[3*10 + 6] == 0.0);
+    }
+
+    #[test]
+    fn half_pixel_is_half_covered() {
+        let mut accumulator = CoverageAccumulator::new(4, 4);
+        accumulator.add_polygon(&[(0.0, 0.0), (1.5, 0.0), (1.5, 1.0), (0.0, 1.0)]);
+
+        let coverage = accumulator.to_coverage();
+
+        assert!((coverage[0]-1.0).abs() < 0.001);
+        assert!((coverage[1]-0.5).abs() < 0.001);
+        assert!(coverage[2] == 0.0);
+    }
+
+    #[test]
+    fn shape_outside_mask_is_clipped() {
+        let mut accumulator = CoverageAccumulator::new(4, 4);
+        accumulator.add_polygon(&[(-10.0, -10.0), (10.0, -10.0), (10.0, 10.0), (-10.0, 10.0)]);
+
+        let coverage = accumulator.to_coverage();
+
+        assert!(coverage.iter().all(|pixel| (pixel-1.0).abs() < 0.001));
+    }
+
+    #[test]
+    fn opposite_windings_cancel() {
+        let mut accumulator = CoverageAccumulator::new(10, 10);
+        accumulator.add_polygon(&[(0.0, 0.0), (8.0, 0.0), (8.0, 8.0), (0.0, 8.0)]);
+        accumulator.add_polygon(&[(2.0, 2.0), (2.0, 6.0), (6.0, 6.0), (6.0, 2.0)]);
+
+        let coverage = accumulator.to_coverage();
+
+        assert!(coverage[1*10 + 1] == 1.0);
+        assert!(coverage[4*10 + 4] == 0.0);
+    }
+}
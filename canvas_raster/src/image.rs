@@ -0,0 +1,141 @@
+use super::blend::*;
+
+use flo_canvas::*;
+use png;
+use jpeg_decoder;
+
+///
+/// An image that has been decoded into pixels (with premultiplied alpha)
+///
+pub struct DecodedImage {
+    /// The width of the image in pixels
+    width: usize,
+
+    /// The height of the image in pixels
+    height: usize,
+
+    /// The pixels in this image, ordered from the top row to the bottom
+    pixels: Vec<Pixel>
+}
+
+impl DecodedImage {
+    ///
+    /// Decodes a canvas image, returning None if the image data is invalid
+    ///
+    pub fn decode(image: &CanvasImage) -> Option<DecodedImage> {
+        let decoded = match image.format {
+            ImageFormat::Png    => Self::decode_png(&image.data)?,
+            ImageFormat::Jpeg   => Self::decode_jpeg(&image.data)?
+        };
+
+        if decoded.width == 0 || decoded.height == 0 || decoded.pixels.len() < decoded.width*decoded.height {
+            None
+        } else {
+            Some(decoded)
+        }
+    }
+
+    ///
+    /// Creates an image from 8-bit samples with the specified number of channels
+    ///
+    /// Channels are grey, grey + alpha, RGB or RGBA: the samples don't have premultiplied alpha.
+    ///
+    fn from_samples(width: usize, height: usize, channels: usize, samples: &[u8]) -> DecodedImage {
+        let to_float    = |sample: u8| (sample as f32)/255.0;
+        let pixels      = samples.chunks(channels)
+            .map(|sample| {
+                let (r, g, b, a) = match channels {
+                    1 => (sample[0], sample[0], sample[0], 255),
+                    2 => (sample[0], sample[0], sample[0], sample[1]),
+                    3 => (sample[0], sample[1], sample[2], 255),
+                    _ => (sample[0], sample[1], sample[2], sample[3])
+                };
+                let a = to_float(a);
+
+                [to_float(r)*a, to_float(g)*a, to_float(b)*a, a]
+            })
+            .collect();
+
+        DecodedImage {
+            width:  width,
+            height: height,
+            pixels: pixels
+        }
+    }
+
+    ///
+    /// Decodes a PNG image
+    ///
+    fn decode_png(data: &[u8]) -> Option<DecodedImage> {
+        // Palettes and low bit depths are expanded to 8-bit grey or RGB values
+        let mut decoder = png::Decoder::new(data);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+
+        let mut reader  = decoder.read_info().ok()?;
+        let mut buffer  = vec![0; reader.output_buffer_size()];
+        let info        = reader.next_frame(&mut buffer).ok()?;
+
+        let channels    = match info.color_type {
+            png::ColorType::Grayscale       => 1,
+            png::ColorType::GrayscaleAlpha  => 2,
+            png::ColorType::Rgb             => 3,
+            png::ColorType::Rgba            => 4,
+            png::ColorType::Indexed         => { return None; }
+        };
+
+        // Rows may be padded, so each row is read separately
+        let (width, height) = (info.width as usize, info.height as usize);
+        let samples         = buffer.chunks(info.line_size)
+            .take(height)
+            .flat_map(|row| row[0..width*channels].iter().cloned())
+            .collect::<Vec<_>>();
+
+        Some(Self::from_samples(width, height, channels, &samples))
+    }
+
+    ///
+    /// Decodes a JPEG image
+    ///
+    fn decode_jpeg(data: &[u8]) -> Option<DecodedImage> {
+        let mut decoder = jpeg_decoder::Decoder::new(data);
+        let samples     = decoder.decode().ok()?;
+        let info        = decoder.info()?;
+        let width       = info.width as usize;
+        let height      = info.height as usize;
+
+        match info.pixel_format {
+            jpeg_decoder::PixelFormat::L8       => Some(Self::from_samples(width, height, 1, &samples)),
+            jpeg_decoder::PixelFormat::RGB24    => Some(Self::from_samples(width, height, 3, &samples)),
+
+            jpeg_decoder::PixelFormat::L16      => {
+                // 16-bit samples are big-endian: the most significant byte is used
+                let samples = samples.chunks(2).map(|sample| sample[0]).collect::<Vec<_>>();
+                Some(Self::from_samples(width, height, 1, &samples))
+            },
+
+            jpeg_decoder::PixelFormat::CMYK32   => {
+                let samples = samples.chunks(4)
+                    .flat_map(|cmyk| {
+                        let k = 255 - (cmyk[3] as u32);
+                        vec![cmyk[0], cmyk[1], cmyk[2]].into_iter().map(move |c| (((255 - (c as u32)) * k) / 255) as u8)
+                    })
+                    .collect::<Vec<_>>();
+                Some(Self::from_samples(width, height, 3, &samples))
+            }
+        }
+    }
+
+    ///
+    /// Reads the pixel nearest to a point in the unit square (where (0,0) is the bottom-left corner of the image and (1,1) is the top-right)
+    ///
+    pub fn sample(&self, x: f32, y: f32) -> Pixel {
+        if x < 0.0 || y < 0.0 || x > 1.0 || y > 1.0 || !x.is_finite() || !y.is_finite() {
+            return [0.0, 0.0, 0.0, 0.0];
+        }
+
+        let pixel_x = ((x * (self.width as f32)) as usize).min(self.width-1);
+        let pixel_y = (((1.0-y) * (self.height as f32)) as usize).min(self.height-1);
+
+        self.pixels[pixel_y*self.width + pixel_x]
+    }
+}
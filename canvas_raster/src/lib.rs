@@ -0,0 +1,21 @@
+//!
+//! A pure Rust software rasterizer for flo_canvas drawing instructions
+//!
+//! This renders a set of `Draw` instructions to an RGBA pixel buffer without needing any
+//! UI toolkit, which makes it suitable for generating thumbnails, exporting animations and
+//! running rendering tests on machines without a display.
+//!
+#![warn(bare_trait_objects)]
+
+extern crate flo_canvas;
+extern crate png;
+extern crate jpeg_decoder;
+
+mod path;
+mod coverage;
+mod stroke;
+mod blend;
+mod image;
+mod rasterizer;
+
+pub use self::rasterizer::*;
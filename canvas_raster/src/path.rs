@@ -0,0 +1,142 @@
+///
+/// A section of a path, made up of straight lines in device coordinates
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct SubPath {
+    /// The points making up this subpath
+    pub points: Vec<(f32, f32)>,
+
+    /// True if this subpath was closed with ClosePath
+    pub closed: bool
+}
+
+///
+/// A path that has been transformed to device coordinates and flattened into straight lines
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct RasterPath {
+    /// The subpaths making up this path
+    pub subpaths: Vec<SubPath>
+}
+
+impl RasterPath {
+    ///
+    /// Creates a new, empty path
+    ///
+    pub fn new() -> RasterPath {
+        RasterPath {
+            subpaths: vec![]
+        }
+    }
+
+    ///
+    /// Returns the last point in this path, if there is one
+    ///
+    fn last_point(&self) -> Option<(f32, f32)> {
+        self.subpaths.last().and_then(|subpath| subpath.points.last().cloned())
+    }
+
+    ///
+    /// Starts a new subpath at the specified point
+    ///
+    pub fn move_to(&mut self, point: (f32, f32)) {
+        self.subpaths.push(SubPath { points: vec![point], closed: false });
+    }
+
+    ///
+    /// Adds a line to the current subpath
+    ///
+    pub fn line_to(&mut self, point: (f32, f32)) {
+        let needs_new_subpath = self.subpaths.last().map(|subpath| subpath.closed).unwrap_or(true);
+
+        if needs_new_subpath {
+            // Lines after a close start a new subpath at the start of the previous one
+            let start = self.subpaths.last().map(|subpath| subpath.points[0]).unwrap_or(point);
+            self.move_to(start);
+        }
+
+        self.subpaths.last_mut().unwrap().points.push(point);
+    }
+
+    ///
+    /// Adds a bezier curve to the current subpath (as a set of line segments)
+    ///
+    pub fn bezier_curve_to(&mut self, cp1: (f32, f32), cp2: (f32, f32), end: (f32, f32)) {
+        let start = self.last_point().unwrap_or(cp1);
+
+        for point in flatten_bezier(start, cp1, cp2, end) {
+            self.line_to(point);
+        }
+    }
+
+    ///
+    /// Closes the current subpath
+    ///
+    pub fn close_path(&mut self) {
+        if let Some(subpath) = self.subpaths.last_mut() {
+            subpath.closed = true;
+        }
+    }
+}
+
+///
+/// Returns the distance between two points
+///
+pub fn distance(p1: (f32, f32), p2: (f32, f32)) -> f32 {
+    let (dx, dy) = (p2.0-p1.0, p2.1-p1.1);
+
+    (dx*dx + dy*dy).sqrt()
+}
+
+///
+/// Converts a bezier curve to a set of points that approximate it with straight lines
+///
+/// The start point is not included in the result
+///
+pub fn flatten_bezier(start: (f32, f32), cp1: (f32, f32), cp2: (f32, f32), end: (f32, f32)) -> Vec<(f32, f32)> {
+    // The length of the control polygon is an upper bound on the length of the curve
+    let control_length  = distance(start, cp1) + distance(cp1, cp2) + distance(cp2, end);
+    let num_segments    = ((control_length / 2.0).ceil() as usize).max(1).min(256);
+
+    (1..=num_segments)
+        .map(|segment| {
+            let t   = (segment as f32) / (num_segments as f32);
+            let mt  = 1.0-t;
+
+            let a   = mt*mt*mt;
+            let b   = 3.0*mt*mt*t;
+            let c   = 3.0*mt*t*t;
+            let d   = t*t*t;
+
+            (a*start.0 + b*cp1.0 + c*cp2.0 + d*end.0, a*start.1 + b*cp1.1 + c*cp2.1 + d*end.1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn flattened_bezier_ends_at_end_point() {
+        let points  = flatten_bezier((0.0, 0.0), (10.0, 20.0), (30.0, 20.0), (40.0, 0.0));
+        let last    = *points.last().unwrap();
+
+        assert!(points.len() > 1);
+        assert!(distance(last, (40.0, 0.0)) < 0.001);
+    }
+
+    #[test]
+    fn line_after_close_starts_new_subpath() {
+        let mut path = RasterPath::new();
+
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        path.line_to((10.0, 10.0));
+        path.close_path();
+        path.line_to((20.0, 20.0));
+
+        assert!(path.subpaths.len() == 2);
+        assert!(path.subpaths[1].points == vec![(0.0, 0.0), (20.0, 20.0)]);
+    }
+}
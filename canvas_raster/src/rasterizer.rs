@@ -0,0 +1,1032 @@
+use super::path::*;
+use super::blend::*;
+use super::stroke::*;
+use super::coverage::*;
+use super::image::*;
+
+use flo_canvas::*;
+
+use std::mem;
+use std::iter;
+use std::sync::*;
+use std::collections::HashMap;
+
+///
+/// The drawing state for the rasterizer (this is what's saved by PushState)
+///
+#[derive(Clone)]
+struct RasterState {
+    /// The current transformation matrix (maps canvas coordinates to pixels)
+    transform: Transform2D,
+
+    /// The current path, in pixel coordinates
+    path: RasterPath,
+
+    /// The width of the line used for strokes (before transformation)
+    line_width: f32,
+
+    /// How lines are joined
+    line_join: LineJoin,
+
+    /// How lines are capped
+    line_cap: LineCap,
+
+    /// The current dash pattern (before transformation)
+    dash_pattern: Vec<f32>,
+
+    /// The offset of the current dash pattern
+    dash_offset: f32,
+
+    /// The colour to use for fills
+    fill_color: Color,
+
+    /// The gradient to use for fills, if one has been set (with the transformation from pixels to gradient coordinates)
+    fill_gradient: Option<(Gradient, Transform2D)>,
+
+    /// The colour to use for strokes
+    stroke_color: Color,
+
+    /// How new shapes are blended with the existing layer contents
+    blend_mode: BlendMode,
+
+    /// The size of the font used for text
+    font_size: f32,
+
+    /// How text is aligned relative to the point where it's drawn
+    text_alignment: TextAlignment,
+
+    /// The coverage mask for the current clipping region, if there is one
+    clip: Option<Arc<Vec<f32>>>
+}
+
+///
+/// A layer in the rasterizer
+///
+struct RasterLayer {
+    /// The pixels in this layer (with premultiplied alpha)
+    pixels: Vec<Pixel>,
+
+    /// How this layer is blended with the layers underneath it
+    blend_mode: BlendMode,
+
+    /// The opacity of this layer
+    alpha: f32,
+
+    /// The pixels saved by the last 'Store' operation
+    stored: Option<Vec<Pixel>>
+}
+
+///
+/// A graphics context that renders drawing instructions to a pixel buffer in memory
+///
+/// The coordinate scheme matches the other canvas implementations: before any transformations are
+/// applied, (0,0) is the center of the canvas and the top of the canvas is at y=1.
+///
+pub struct CanvasRasterizer {
+    /// The width of the image in pixels
+    width: usize,
+
+    /// The height of the image in pixels
+    height: usize,
+
+    /// The transformation used by IdentityTransform
+    initial_transform: Transform2D,
+
+    /// The current drawing state
+    state: RasterState,
+
+    /// States stored by PushState
+    saved_states: Vec<RasterState>,
+
+    /// The layers in this canvas
+    layers: HashMap<u32, RasterLayer>,
+
+    /// The currently selected layer
+    current_layer: u32
+}
+
+impl RasterState {
+    ///
+    /// Creates the default drawing state
+    ///
+    fn new(initial_transform: Transform2D) -> RasterState {
+        RasterState {
+            transform:      initial_transform,
+            path:           RasterPath::new(),
+            line_width:     1.0,
+            line_join:      LineJoin::Round,
+            line_cap:       LineCap::Butt,
+            dash_pattern:   vec![],
+            dash_offset:    0.0,
+            fill_color:     Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill_gradient:  None,
+            stroke_color:   Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            blend_mode:     BlendMode::SourceOver,
+            font_size:      12.0,
+            text_alignment: TextAlignment::Left,
+            clip:           None
+        }
+    }
+}
+
+impl RasterLayer {
+    ///
+    /// Creates a new transparent layer
+    ///
+    fn new(num_pixels: usize) -> RasterLayer {
+        RasterLayer {
+            pixels:     vec![[0.0, 0.0, 0.0, 0.0]; num_pixels],
+            blend_mode: BlendMode::SourceOver,
+            alpha:      1.0,
+            stored:     None
+        }
+    }
+}
+
+///
+/// Converts a colour to a pixel with premultiplied alpha
+///
+fn color_to_pixel(color: &Color) -> Pixel {
+    let (r, g, b, a)    = color.to_rgba_components();
+    let clamp           = |c: f32| c.max(0.0).min(1.0);
+    let a               = clamp(a);
+
+    [clamp(r)*a, clamp(g)*a, clamp(b)*a, a]
+}
+
+impl CanvasRasterizer {
+    ///
+    /// Creates a new rasterizer that will generate an image of the specified size
+    ///
+    pub fn new(width: usize, height: usize) -> CanvasRasterizer {
+        let (w, h)              = (width as f32, height as f32);
+        let initial_transform   = Transform2D::translate(w/2.0, h/2.0) * Transform2D::scale(h/2.0, -h/2.0);
+
+        CanvasRasterizer {
+            width:              width,
+            height:             height,
+            initial_transform:  initial_transform,
+            state:              RasterState::new(initial_transform),
+            saved_states:       vec![],
+            layers:             HashMap::new(),
+            current_layer:      0
+        }
+    }
+
+    ///
+    /// The width of the image generated by this rasterizer
+    ///
+    pub fn width(&self) -> usize { self.width }
+
+    ///
+    /// The height of the image generated by this rasterizer
+    ///
+    pub fn height(&self) -> usize { self.height }
+
+    ///
+    /// Combines the layers of this canvas into a single set of pixels (with premultiplied alpha)
+    ///
+    fn composite_layers(&self) -> Vec<Pixel> {
+        let mut result = vec![[0.0, 0.0, 0.0, 0.0]; self.width*self.height];
+
+        // Sort the layers by ID
+        let mut layer_ids = self.layers.keys().cloned().collect::<Vec<_>>();
+        layer_ids.sort();
+
+        // Blend each layer in turn
+        for layer_id in layer_ids {
+            let layer = &self.layers[&layer_id];
+
+            // The opacity of a layer works the same way as partial coverage
+            for (dst, src) in result.iter_mut().zip(layer.pixels.iter()) {
+                *dst = blend_pixel_with_coverage(layer.blend_mode, *src, *dst, layer.alpha);
+            }
+        }
+
+        result
+    }
+
+    ///
+    /// Returns the image generated by this rasterizer as RGBA bytes (without premultiplied alpha)
+    ///
+    /// The rows are ordered from the top of the image to the bottom.
+    ///
+    pub fn to_rgba_bytes(&self) -> Vec<u8> {
+        let pixels      = self.composite_layers();
+        let mut bytes   = Vec::with_capacity(pixels.len()*4);
+        let to_byte     = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u8;
+
+        for [r, g, b, a] in pixels.into_iter() {
+            if a <= 0.0 {
+                bytes.extend([0, 0, 0, 0].iter());
+            } else {
+                bytes.extend([to_byte(r/a), to_byte(g/a), to_byte(b/a), to_byte(a)].iter());
+            }
+        }
+
+        bytes
+    }
+
+    ///
+    /// Retrieves the layer that's currently being drawn on
+    ///
+    fn current_layer_mut(&mut self) -> &mut RasterLayer {
+        let num_pixels = self.width*self.height;
+
+        self.layers.entry(self.current_layer)
+            .or_insert_with(|| RasterLayer::new(num_pixels))
+    }
+
+    ///
+    /// Computes the coverage of a set of polygons, combined with the current clipping region
+    ///
+    fn coverage_for_polygons<'a, PolygonIter: Iterator<Item=&'a Vec<(f32, f32)>>>(&self, polygons: PolygonIter) -> Vec<f32> {
+        let mut accumulator = CoverageAccumulator::new(self.width, self.height);
+
+        for polygon in polygons {
+            accumulator.add_polygon(polygon);
+        }
+
+        let mut coverage = accumulator.to_coverage();
+
+        if let Some(ref clip) = self.state.clip {
+            for (pixel, clip) in coverage.iter_mut().zip(clip.iter()) {
+                *pixel *= clip;
+            }
+        }
+
+        coverage
+    }
+
+    ///
+    /// Computes the coverage of the current path
+    ///
+    fn path_coverage(&self) -> Vec<f32> {
+        self.coverage_for_polygons(self.state.path.subpaths.iter().map(|subpath| &subpath.points))
+    }
+
+    ///
+    /// Draws a colour on the current layer using a coverage mask
+    ///
+    fn draw_coverage(&mut self, color: Color, coverage: Vec<f32>) {
+        let pixel = color_to_pixel(&color);
+
+        self.draw_coverage_with(coverage, |_x, _y| pixel);
+    }
+
+    ///
+    /// Draws on the current layer using a coverage mask, using a function to generate the colour of each pixel
+    ///
+    /// The function is called with the coordinates of the center of each pixel that's covered.
+    ///
+    fn draw_coverage_with<PixelFn: Fn(f32, f32) -> Pixel>(&mut self, coverage: Vec<f32>, pixel_fn: PixelFn) {
+        let width       = self.width;
+        let blend_mode  = self.state.blend_mode;
+        let layer       = self.current_layer_mut();
+
+        for (index, (dst, coverage)) in layer.pixels.iter_mut().zip(coverage.into_iter()).enumerate() {
+            if coverage <= 0.0 { continue; }
+
+            let (x, y)  = ((index % width) as f32 + 0.5, (index / width) as f32 + 0.5);
+            *dst        = blend_pixel_with_coverage(blend_mode, pixel_fn(x, y), *dst, coverage);
+        }
+    }
+
+    ///
+    /// Fills the current path
+    ///
+    fn fill(&mut self) {
+        let coverage    = self.path_coverage();
+
+        match self.state.fill_gradient.clone() {
+            None                            => {
+                let color = self.state.fill_color;
+                self.draw_coverage(color, coverage);
+            },
+
+            Some((gradient, from_pixels))   => {
+                // Gradients are evaluated at the center of each pixel
+                self.draw_coverage_with(coverage, |x, y| {
+                    let (x, y) = from_pixels.transform_point(x, y);
+                    color_to_pixel(&gradient.color_at_point(x, y))
+                });
+            }
+        }
+    }
+
+    ///
+    /// Sets the gradient used for fills (the gradient coordinates are transformed by the current transformation)
+    ///
+    fn set_fill_gradient(&mut self, gradient: Gradient) {
+        self.state.fill_color       = gradient.average_color();
+        self.state.fill_gradient    = self.state.transform.invert().map(|from_pixels| (gradient, from_pixels));
+    }
+
+    ///
+    /// Strokes the current path
+    ///
+    fn stroke(&mut self) {
+        let scale       = self.state.transform.scale_factor();
+        let options     = StrokeOptions {
+            width:          self.state.line_width * scale,
+            join:           self.state.line_join,
+            cap:            self.state.line_cap,
+            dash_pattern:   self.state.dash_pattern.iter().map(|length| length * scale).collect(),
+            dash_offset:    self.state.dash_offset * scale
+        };
+
+        let polygons    = stroke_path(&self.state.path, &options);
+        let coverage    = self.coverage_for_polygons(polygons.iter());
+        let color       = self.state.stroke_color;
+
+        self.draw_coverage(color, coverage);
+    }
+
+    ///
+    /// Fills a line of text
+    ///
+    /// There's no font support in the rasterizer, so this uses the built-in block font from flo_canvas
+    /// (the font family is ignored)
+    ///
+    fn fill_text(&mut self, text: &str, x: f32, y: f32) {
+        let text_path   = block_font_text_path(text, x, y, self.state.font_size, self.state.text_alignment);
+        let old_path    = mem::replace(&mut self.state.path, RasterPath::new());
+
+        text_path.into_iter().for_each(|draw| self.draw_instruction(draw));
+        self.fill();
+
+        self.state.path = old_path;
+    }
+
+    ///
+    /// Draws an image, using a transform that maps the unit square onto the canvas
+    ///
+    fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D) {
+        let image       = match DecodedImage::decode(image) { Some(image) => image, None => { return; } };
+        let to_pixels   = self.state.transform * transform;
+        let from_pixels = match to_pixels.invert() { Some(transform) => transform, None => { return; } };
+
+        // The image covers the unit square after it's transformed
+        let corners     = vec![
+            to_pixels.transform_point(0.0, 0.0),
+            to_pixels.transform_point(1.0, 0.0),
+            to_pixels.transform_point(1.0, 1.0),
+            to_pixels.transform_point(0.0, 1.0)
+        ];
+        let coverage    = self.coverage_for_polygons(iter::once(&corners));
+
+        self.draw_coverage_with(coverage, |x, y| {
+            let (x, y) = from_pixels.transform_point(x, y);
+            image.sample(x, y)
+        });
+    }
+
+    ///
+    /// Sets the clipping region to the intersection of the current clipping region and the current path
+    ///
+    fn clip(&mut self) {
+        let coverage        = self.path_coverage();
+
+        self.state.clip     = Some(Arc::new(coverage));
+        self.state.path     = RasterPath::new();
+    }
+
+    ///
+    /// Restores the current layer from the stored pixels (only within the clipping region, if there is one)
+    ///
+    fn restore(&mut self) {
+        let clip    = self.state.clip.clone();
+        let layer   = self.current_layer_mut();
+
+        if let Some(ref stored) = layer.stored {
+            match clip {
+                None        => { layer.pixels = stored.clone(); },
+                Some(clip)  => {
+                    for ((dst, src), coverage) in layer.pixels.iter_mut().zip(stored.iter()).zip(clip.iter()) {
+                        for component in 0..4 {
+                            dst[component] += (src[component]-dst[component]) * coverage;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    ///
+    /// Computes the transformation to apply for a particular canvas height
+    ///
+    fn height_transform(height: f32) -> Transform2D {
+        let mut ratio_x = 2.0/height;
+        let ratio_y     = ratio_x;
+
+        if height < 0.0 {
+            ratio_x = -ratio_x;
+        }
+
+        Transform2D::scale(ratio_x, ratio_y)
+    }
+
+    ///
+    /// Computes a transformation that moves a particular region to the center of the canvas
+    ///
+    fn center_transform(&self, minx: f32, miny: f32, maxx: f32, maxy: f32) -> Transform2D {
+        let Transform2D(a, b, _c) = self.state.transform;
+
+        // Get the current scaling of this canvas
+        let mut xscale = (a.0*a.0 + b.0*b.0).sqrt();
+        let mut yscale = (a.1*a.1 + b.1*b.1).sqrt();
+        if xscale == 0.0 { xscale = 1.0; }
+        if yscale == 0.0 { yscale = 1.0; }
+
+        // Current X, Y coordinates (centered)
+        let cur_x = (a.2-((self.width as f32)/2.0))/xscale;
+        let cur_y = (b.2-((self.height as f32)/2.0))/yscale;
+
+        // New center coordinates
+        let center_x = (minx+maxx)/2.0;
+        let center_y = (miny+maxy)/2.0;
+
+        // Compute the offsets and transform the canvas
+        Transform2D::translate(cur_x - center_x, cur_y - center_y)
+    }
+
+    ///
+    /// Transforms a point to pixel coordinates
+    ///
+    #[inline]
+    fn to_pixels(&self, x: f32, y: f32) -> (f32, f32) {
+        self.state.transform.transform_point(x, y)
+    }
+
+    ///
+    /// Performs a drawing instruction on this canvas
+    ///
+    fn draw_instruction(&mut self, drawing: Draw) {
+        use self::Draw::*;
+
+        match drawing {
+            NewPath                                     => { self.state.path = RasterPath::new(); },
+            Move(x, y)                                  => { let point = self.to_pixels(x, y); self.state.path.move_to(point); },
+            Line(x, y)                                  => { let point = self.to_pixels(x, y); self.state.path.line_to(point); },
+            BezierCurve((x, y), (cx1, cy1), (cx2, cy2)) => { let (end, cp1, cp2) = (self.to_pixels(x, y), self.to_pixels(cx1, cy1), self.to_pixels(cx2, cy2)); self.state.path.bezier_curve_to(cp1, cp2, end); },
+            ClosePath                                   => { self.state.path.close_path(); },
+            Fill                                        => { self.fill(); },
+            Stroke                                      => { self.stroke(); },
+            LineWidth(width)                            => { self.state.line_width = width; },
+            LineWidthPixels(pixels)                     => { self.state.line_width = pixels / self.state.transform.scale_factor(); },
+            LineJoin(join)                              => { self.state.line_join = join; },
+            LineCap(cap)                                => { self.state.line_cap = cap; },
+            NewDashPattern                              => { self.state.dash_pattern = vec![]; },
+            DashLength(length)                          => { self.state.dash_pattern.push(length); },
+            DashOffset(offset)                          => { self.state.dash_offset = offset; },
+            FillColor(color)                            => { self.state.fill_color = color; self.state.fill_gradient = None; },
+            FillGradient(gradient)                      => { self.set_fill_gradient(gradient); },
+            StrokeColor(color)                          => { self.state.stroke_color = color; },
+            BlendMode(blend_mode)                       => { self.state.blend_mode = blend_mode; },
+            IdentityTransform                           => { self.state.transform = self.initial_transform; },
+            CanvasHeight(height)                        => { self.state.transform = self.initial_transform * Self::height_transform(height); },
+            CenterRegion((minx, miny), (maxx, maxy))    => { self.state.transform = self.state.transform * self.center_transform(minx, miny, maxx, maxy); },
+            MultiplyTransform(transform)                => { self.state.transform = self.state.transform * transform; },
+            Unclip                                      => { self.state.clip = None; },
+            Clip                                        => { self.clip(); },
+            Store                                       => { let layer = self.current_layer_mut(); layer.stored = Some(layer.pixels.clone()); },
+            Restore                                     => { self.restore(); },
+            FreeStoredBuffer                            => { self.current_layer_mut().stored = None; },
+            PushState                                   => { self.saved_states.push(self.state.clone()); },
+            PopState                                    => { if let Some(state) = self.saved_states.pop() { self.state = state; } },
+
+            ClearCanvas                                 => {
+                self.state          = RasterState::new(self.initial_transform);
+                self.saved_states   = vec![];
+                self.layers         = HashMap::new();
+                self.current_layer  = 0;
+            },
+
+            Layer(layer_id)                             => {
+                // Changing layer clears the clipping region
+                self.current_layer  = layer_id;
+                self.state.clip     = None;
+                self.current_layer_mut();
+            },
+
+            LayerBlend(layer_id, blend_mode)            => {
+                let num_pixels = self.width*self.height;
+                self.layers.entry(layer_id).or_insert_with(|| RasterLayer::new(num_pixels)).blend_mode = blend_mode;
+            },
+
+            LayerAlpha(layer_id, alpha)                 => {
+                let num_pixels = self.width*self.height;
+                self.layers.entry(layer_id).or_insert_with(|| RasterLayer::new(num_pixels)).alpha = alpha.max(0.0).min(1.0);
+            },
+
+            ClearLayer                                  => {
+                let num_pixels  = self.width*self.height;
+                let layer       = self.current_layer_mut();
+
+                *layer          = RasterLayer::new(num_pixels);
+            },
+
+            FontFamily(_)                               => { },
+            FontSize(size)                              => { self.state.font_size = size; },
+            TextAlignment(alignment)                    => { self.state.text_alignment = alignment; },
+            FillText(text, x, y)                        => { self.fill_text(&text, x, y); },
+            DrawImage(image, transform)                 => { self.draw_image(&image, transform); }
+        }
+    }
+}
+
+impl GraphicsContext for CanvasRasterizer {
+    fn new_path(&mut self)                                                      { self.draw(Draw::NewPath); }
+    fn move_to(&mut self, x: f32, y: f32)                                       { self.draw(Draw::Move(x, y)); }
+    fn line_to(&mut self, x: f32, y: f32)                                       { self.draw(Draw::Line(x, y)); }
+    fn bezier_curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) { self.draw(Draw::BezierCurve((x1, y1), (x2, y2), (x3, y3))); }
+    fn close_path(&mut self)                                                    { self.draw(Draw::ClosePath); }
+    fn fill(&mut self)                                                          { self.draw(Draw::Fill); }
+    fn stroke(&mut self)                                                        { self.draw(Draw::Stroke); }
+    fn line_width(&mut self, width: f32)                                        { self.draw(Draw::LineWidth(width)); }
+    fn line_width_pixels(&mut self, width: f32)                                 { self.draw(Draw::LineWidthPixels(width)); }
+    fn line_join(&mut self, join: LineJoin)                                     { self.draw(Draw::LineJoin(join)); }
+    fn line_cap(&mut self, cap: LineCap)                                        { self.draw(Draw::LineCap(cap)); }
+    fn new_dash_pattern(&mut self)                                              { self.draw(Draw::NewDashPattern); }
+    fn dash_length(&mut self, length: f32)                                      { self.draw(Draw::DashLength(length)); }
+    fn dash_offset(&mut self, offset: f32)                                      { self.draw(Draw::DashOffset(offset)); }
+    fn fill_color(&mut self, col: Color)                                        { self.draw(Draw::FillColor(col)); }
+    fn fill_gradient(&mut self, gradient: &Gradient)                            { self.draw(Draw::FillGradient(gradient.clone())); }
+    fn stroke_color(&mut self, col: Color)                                      { self.draw(Draw::StrokeColor(col)); }
+    fn blend_mode(&mut self, mode: BlendMode)                                   { self.draw(Draw::BlendMode(mode)); }
+    fn identity_transform(&mut self)                                            { self.draw(Draw::IdentityTransform); }
+    fn canvas_height(&mut self, height: f32)                                    { self.draw(Draw::CanvasHeight(height)); }
+    fn center_region(&mut self, minx: f32, miny: f32, maxx: f32, maxy: f32)     { self.draw(Draw::CenterRegion((minx, miny), (maxx, maxy))); }
+    fn transform(&mut self, transform: Transform2D)                             { self.draw(Draw::MultiplyTransform(transform)); }
+    fn unclip(&mut self)                                                        { self.draw(Draw::Unclip); }
+    fn clip(&mut self)                                                          { self.draw(Draw::Clip); }
+    fn store(&mut self)                                                         { self.draw(Draw::Store); }
+    fn restore(&mut self)                                                       { self.draw(Draw::Restore); }
+    fn free_stored_buffer(&mut self)                                            { self.draw(Draw::FreeStoredBuffer); }
+    fn push_state(&mut self)                                                    { self.draw(Draw::PushState); }
+    fn pop_state(&mut self)                                                     { self.draw(Draw::PopState); }
+    fn clear_canvas(&mut self)                                                  { self.draw(Draw::ClearCanvas); }
+    fn layer(&mut self, layer_id: u32)                                          { self.draw(Draw::Layer(layer_id)); }
+    fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode)             { self.draw(Draw::LayerBlend(layer_id, blend_mode)); }
+    fn layer_alpha(&mut self, layer_id: u32, alpha: f32)                        { self.draw(Draw::LayerAlpha(layer_id, alpha)); }
+    fn clear_layer(&mut self)                                                   { self.draw(Draw::ClearLayer); }
+    fn font_family(&mut self, family: &str)                                     { self.draw(Draw::FontFamily(family.to_string())); }
+    fn font_size(&mut self, size: f32)                                          { self.draw(Draw::FontSize(size)); }
+    fn text_alignment(&mut self, alignment: TextAlignment)                      { self.draw(Draw::TextAlignment(alignment)); }
+    fn fill_text(&mut self, text: &str, x: f32, y: f32)                         { self.draw(Draw::FillText(text.to_string(), x, y)); }
+    fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D)       { self.draw(Draw::DrawImage(image.clone(), transform)); }
+
+    fn draw(&mut self, d: Draw) {
+        self.draw_instruction(d);
+    }
+}
+
+impl GraphicsPrimitives for CanvasRasterizer {
+
+}
+
+///
+/// Renders a set of drawing instructions to an image of the specified size, returning the RGBA bytes for the image
+///
+pub fn rasterize_drawing<DrawIter: IntoIterator<Item=Draw>>(width: usize, height: usize, drawing: DrawIter) -> Vec<u8> {
+    let mut rasterizer = CanvasRasterizer::new(width, height);
+
+    for instruction in drawing {
+        rasterizer.draw(instruction);
+    }
+
+    rasterizer.to_rgba_bytes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use png;
+
+    ///
+    /// Creates a rasterizer where canvas coordinates match the pixel coordinates (with y=0 at the bottom)
+    ///
+    fn rasterizer() -> CanvasRasterizer {
+        let mut rasterizer = CanvasRasterizer::new(10, 10);
+
+        rasterizer.canvas_height(10.0);
+        rasterizer.center_region(0.0, 0.0, 10.0, 10.0);
+
+        rasterizer
+    }
+
+    ///
+    /// Reads a pixel from a RGBA buffer generated for the test rasterizer (with y=0 at the top)
+    ///
+    fn pixel(bytes: &Vec<u8>, x: usize, y: usize) -> [u8; 4] {
+        let pos = (y*10 + x)*4;
+        [bytes[pos], bytes[pos+1], bytes[pos+2], bytes[pos+3]]
+    }
+
+    #[test]
+    fn empty_canvas_is_transparent() {
+        let bytes = CanvasRasterizer::new(10, 10).to_rgba_bytes();
+
+        assert!(bytes.len() == 400);
+        assert!(bytes.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn fill_rectangle() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        rasterizer.new_path();
+        rasterizer.rect(2.0, 2.0, 6.0, 6.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        // y axis is flipped in the output image
+        assert!(pixel(&bytes, 3, 6) == [255, 0, 0, 255]);
+        assert!(pixel(&bytes, 3, 2) == [0, 0, 0, 0]);
+        assert!(pixel(&bytes, 7, 6) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn fill_with_identity_transform() {
+        let mut rasterizer = CanvasRasterizer::new(10, 10);
+
+        rasterizer.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 1.0, 1.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        // (0,0) is the center of the canvas and (1,1) is the top-right corner
+        assert!(pixel(&bytes, 7, 2) == [0, 0, 255, 255]);
+        assert!(pixel(&bytes, 2, 7) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn stroke_line() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.stroke_color(Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        rasterizer.line_width(2.0);
+        rasterizer.new_path();
+        rasterizer.move_to(1.0, 5.0);
+        rasterizer.line_to(9.0, 5.0);
+        rasterizer.stroke();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 5, 4) == [0, 255, 0, 255]);
+        assert!(pixel(&bytes, 5, 5) == [0, 255, 0, 255]);
+        assert!(pixel(&bytes, 5, 2) == [0, 0, 0, 0]);
+        assert!(pixel(&bytes, 0, 4) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn dashed_line_has_gaps() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.line_width(2.0);
+        rasterizer.new_dash_pattern();
+        rasterizer.dash_length(2.0);
+        rasterizer.dash_length(2.0);
+        rasterizer.new_path();
+        rasterizer.move_to(0.0, 5.0);
+        rasterizer.line_to(10.0, 5.0);
+        rasterizer.stroke();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 0, 4)[3] == 255);
+        assert!(pixel(&bytes, 1, 4)[3] == 255);
+        assert!(pixel(&bytes, 2, 4)[3] == 0);
+        assert!(pixel(&bytes, 3, 4)[3] == 0);
+        assert!(pixel(&bytes, 4, 4)[3] == 255);
+    }
+
+    #[test]
+    fn erase_with_destination_out() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        rasterizer.blend_mode(BlendMode::DestinationOut);
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5)[3] == 0);
+        assert!(pixel(&bytes, 7, 5)[3] == 255);
+    }
+
+    #[test]
+    fn clip_restricts_fill() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.clip();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5)[3] == 255);
+        assert!(pixel(&bytes, 7, 5)[3] == 0);
+    }
+
+    #[test]
+    fn unclip_removes_clip() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.clip();
+        rasterizer.unclip();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 7, 5)[3] == 255);
+    }
+
+    #[test]
+    fn store_and_restore() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.fill();
+        rasterizer.store();
+
+        rasterizer.new_path();
+        rasterizer.rect(5.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+        rasterizer.restore();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5)[3] == 255);
+        assert!(pixel(&bytes, 7, 5)[3] == 0);
+    }
+
+    #[test]
+    fn restore_within_clip() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.store();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.clip();
+        rasterizer.restore();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5)[3] == 0);
+        assert!(pixel(&bytes, 7, 5)[3] == 255);
+    }
+
+    #[test]
+    fn pop_state_restores_color() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        rasterizer.push_state();
+        rasterizer.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+        rasterizer.pop_state();
+
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 5, 5) == [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn layers_are_composited_in_order() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.layer(2);
+        rasterizer.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.fill();
+
+        rasterizer.layer(1);
+        rasterizer.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5) == [0, 0, 255, 255]);
+        assert!(pixel(&bytes, 7, 5) == [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn layer_blend_erases_lower_layers() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.layer(0);
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        rasterizer.layer_blend(1, BlendMode::DestinationOut);
+        rasterizer.layer(1);
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5)[3] == 0);
+        assert!(pixel(&bytes, 7, 5)[3] == 255);
+    }
+
+    #[test]
+    fn layer_alpha_makes_layer_translucent() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.layer_alpha(1, 0.5);
+        rasterizer.layer(1);
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!((pixel(&bytes, 5, 5)[3] as i32 - 128).abs() <= 1);
+    }
+
+    #[test]
+    fn clear_layer_only_affects_current_layer() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.layer(0);
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 5.0, 10.0);
+        rasterizer.fill();
+
+        rasterizer.layer(1);
+        rasterizer.new_path();
+        rasterizer.rect(5.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+        rasterizer.clear_layer();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 2, 5)[3] == 255);
+        assert!(pixel(&bytes, 7, 5)[3] == 0);
+    }
+
+    #[test]
+    fn fill_linear_gradient() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.fill_gradient(&Gradient::Linear((0.0, 0.0), (10.0, 0.0), vec![
+            GradientStop::new(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))
+        ]));
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        // Colour changes from red on the left to blue on the right
+        assert!(pixel(&bytes, 0, 5) == [242, 0, 13, 255]);
+        assert!(pixel(&bytes, 9, 5) == [13, 0, 242, 255]);
+        assert!(pixel(&bytes, 2, 0) == pixel(&bytes, 2, 9));
+    }
+
+    #[test]
+    fn fill_color_replaces_gradient() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.fill_gradient(&Gradient::Radial((5.0, 5.0), 5.0, vec![
+            GradientStop::new(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))
+        ]));
+        rasterizer.fill_color(Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        rasterizer.new_path();
+        rasterizer.rect(0.0, 0.0, 10.0, 10.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 5, 5) == [0, 255, 0, 255]);
+    }
+
+    #[test]
+    fn fill_text_draws_glyphs() {
+        let mut rasterizer = rasterizer();
+
+        rasterizer.fill_color(Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        rasterizer.font_size(10.0);
+        rasterizer.new_path();
+        rasterizer.rect(8.0, 8.0, 10.0, 10.0);
+        rasterizer.fill_text("|", 0.0, 0.0);
+        rasterizer.fill();
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        // The vertical bar glyph covers x=2 to x=3 and y=0 to y=7
+        assert!(pixel(&bytes, 2, 6) == [0, 255, 0, 255]);
+        assert!(pixel(&bytes, 2, 3) == [0, 255, 0, 255]);
+        assert!(pixel(&bytes, 5, 6) == [0, 0, 0, 0]);
+        assert!(pixel(&bytes, 2, 1) == [0, 0, 0, 0]);
+
+        // Drawing text leaves the current path alone
+        assert!(pixel(&bytes, 9, 0) == [0, 255, 0, 255]);
+    }
+
+    ///
+    /// Encodes some RGBA pixels as a PNG image
+    ///
+    fn png_image(width: u32, height: u32, rgba: &[u8]) -> CanvasImage {
+        let mut data = vec![];
+
+        {
+            let mut encoder = png::Encoder::new(&mut data, width, height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+
+            let mut writer = encoder.write_header().unwrap();
+            writer.write_image_data(rgba).unwrap();
+        }
+
+        CanvasImage::from_data(data).unwrap()
+    }
+
+    #[test]
+    fn draw_png_image() {
+        let mut rasterizer = rasterizer();
+
+        // Red and green on the top row, blue and white on the bottom row
+        let image = png_image(2, 2, &[
+            255, 0, 0, 255,     0, 255, 0, 255,
+            0, 0, 255, 255,     255, 255, 255, 255
+        ]);
+        rasterizer.draw_image(&image, Transform2D::translate(2.0, 2.0) * Transform2D::scale(6.0, 6.0));
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(pixel(&bytes, 3, 3) == [255, 0, 0, 255]);
+        assert!(pixel(&bytes, 6, 3) == [0, 255, 0, 255]);
+        assert!(pixel(&bytes, 3, 6) == [0, 0, 255, 255]);
+        assert!(pixel(&bytes, 6, 6) == [255, 255, 255, 255]);
+        assert!(pixel(&bytes, 0, 0) == [0, 0, 0, 0]);
+        assert!(pixel(&bytes, 9, 9) == [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn invalid_image_is_not_drawn() {
+        let mut rasterizer = rasterizer();
+
+        let mut image   = png_image(1, 1, &[255, 0, 0, 255]);
+        image.data      = Arc::new(image.data[0..30].to_vec());
+        rasterizer.draw_image(&image, Transform2D::scale(10.0, 10.0));
+
+        let bytes = rasterizer.to_rgba_bytes();
+
+        assert!(bytes.iter().all(|byte| *byte == 0));
+    }
+
+    #[test]
+    fn rasterize_draw_list() {
+        let bytes = rasterize_drawing(10, 10, vec![
+            Draw::CanvasHeight(10.0),
+            Draw::CenterRegion((0.0, 0.0), (10.0, 10.0)),
+            Draw::FillColor(Color::Rgba(1.0, 1.0, 1.0, 0.5)),
+            Draw::NewPath,
+            Draw::Move(0.0, 0.0),
+            Draw::Line(10.0, 0.0),
+            Draw::Line(10.0, 10.0),
+            Draw::Line(0.0, 10.0),
+            Draw::ClosePath,
+            Draw::Fill
+        ]);
+
+        assert!(pixel(&bytes, 5, 5) == [255, 255, 255, 128]);
+    }
+}
@@ -0,0 +1,359 @@
+use super::path::*;
+use super::coverage::*;
+
+use flo_canvas::*;
+
+use std::f32;
+
+///
+/// Describes how a path should be stroked (all distances are in device coordinates)
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct StrokeOptions {
+    /// The width of the line
+    pub width: f32,
+
+    /// How lines are joined
+    pub join: LineJoin,
+
+    /// How lines are capped
+    pub cap: LineCap,
+
+    /// The dash pattern (empty for a solid line)
+    pub dash_pattern: Vec<f32>,
+
+    /// The offset into the dash pattern for the start of each subpath
+    pub dash_offset: f32
+}
+
+/// The ratio of the miter length to the line width where miter joins are replaced with bevels (this is the same default as cairo)
+const MITER_LIMIT: f32 = 10.0;
+
+///
+/// Returns a polygon approximating a circle
+///
+fn circle(center: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+    let num_points = ((radius * 2.0).ceil() as usize).max(8).min(64);
+
+    (0..num_points)
+        .map(|point| {
+            let angle = (point as f32) / (num_points as f32) * 2.0 * f32::consts::PI;
+            (center.0 + radius*angle.cos(), center.1 + radius*angle.sin())
+        })
+        .collect()
+}
+
+///
+/// Returns the unit direction and the unit normal of a line
+///
+fn direction_and_normal(p1: (f32, f32), p2: (f32, f32)) -> ((f32, f32), (f32, f32)) {
+    let length      = distance(p1, p2);
+    let direction   = ((p2.0-p1.0)/length, (p2.1-p1.1)/length);
+
+    (direction, (-direction.1, direction.0))
+}
+
+///
+/// Removes any repeated points from a line
+///
+fn remove_repeated_points(points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+    let mut result: Vec<(f32, f32)> = vec![];
+
+    for point in points.iter() {
+        if result.last() != Some(point) {
+            result.push(*point);
+        }
+    }
+
+    result
+}
+
+///
+/// Splits a line into a set of dashes
+///
+fn dash_line(points: &[(f32, f32)], pattern: &[f32], offset: f32) -> Vec<Vec<(f32, f32)>> {
+    // Odd patterns are repeated so that dashes alternate between 'on' and 'off'
+    let pattern = if pattern.len()%2 == 1 { pattern.iter().chain(pattern.iter()).cloned().collect::<Vec<_>>() } else { pattern.to_vec() };
+    let total   = pattern.iter().map(|length| length.max(0.0)).sum::<f32>();
+
+    if total <= 0.0 || points.len() < 2 {
+        return vec![points.to_vec()];
+    }
+
+    // Find where in the pattern the line starts
+    let mut offset          = offset % total;
+    if offset < 0.0 { offset += total; }
+
+    let mut dash_index      = 0;
+    while offset >= pattern[dash_index].max(0.0) {
+        offset      -= pattern[dash_index].max(0.0);
+        dash_index  = (dash_index+1) % pattern.len();
+    }
+    let mut dash_remaining  = pattern[dash_index].max(0.0) - offset;
+
+    // Walk the line generating dashes
+    let mut dashes          = vec![];
+    let mut current_dash    = if dash_index%2 == 0 { Some(vec![points[0]]) } else { None };
+
+    for segment in points.windows(2) {
+        let (start, end)        = (segment[0], segment[1]);
+        let segment_length      = distance(start, end);
+        let mut pos             = 0.0;
+
+        while segment_length - pos > dash_remaining {
+            // The current dash ends within this segment
+            pos         += dash_remaining;
+            let t       = pos / segment_length;
+            let point   = (start.0 + (end.0-start.0)*t, start.1 + (end.1-start.1)*t);
+
+            match current_dash.take() {
+                Some(mut dash)  => { dash.push(point); dashes.push(dash); },
+                None            => { current_dash = Some(vec![point]); }
+            }
+
+            dash_index      = (dash_index+1) % pattern.len();
+            dash_remaining  = pattern[dash_index].max(0.0);
+        }
+
+        dash_remaining -= segment_length - pos;
+        if let Some(ref mut dash) = current_dash {
+            dash.push(end);
+        }
+    }
+
+    if let Some(dash) = current_dash {
+        dashes.push(dash);
+    }
+
+    dashes
+}
+
+///
+/// Generates the polygons for the join at a point between two lines
+///
+fn join_polygons(point: (f32, f32), before: (f32, f32), after: (f32, f32), options: &StrokeOptions, polygons: &mut Vec<Vec<(f32, f32)>>) {
+    let half_width          = options.width/2.0;
+    let (dir1, normal1)     = direction_and_normal(before, point);
+    let (dir2, normal2)     = direction_and_normal(point, after);
+
+    // The join is on the outside of the turn
+    let cross               = dir1.0*dir2.1 - dir1.1*dir2.0;
+    let side                = if cross > 0.0 { -1.0 } else { 1.0 };
+    let offset1             = (point.0 + normal1.0*half_width*side, point.1 + normal1.1*half_width*side);
+    let offset2             = (point.0 + normal2.0*half_width*side, point.1 + normal2.1*half_width*side);
+
+    match options.join {
+        LineJoin::Round => { polygons.push(circle(point, half_width)); },
+        LineJoin::Bevel => { polygons.push(vec![point, offset1, offset2]); },
+
+        LineJoin::Miter => {
+            // The miter length is half_width / cos(theta/2), where theta is the angle between the normals
+            let cos_theta       = normal1.0*normal2.0 + normal1.1*normal2.1;
+            let cos_half_theta  = ((1.0 + cos_theta)/2.0).max(0.0).sqrt();
+
+            if cos_half_theta <= 0.0 || 1.0/cos_half_theta > MITER_LIMIT {
+                // Too sharp: use a bevel join
+                polygons.push(vec![point, offset1, offset2]);
+            } else {
+                let miter_length    = half_width / cos_half_theta;
+                let miter_dir       = (normal1.0+normal2.0, normal1.1+normal2.1);
+                let miter_dir_len   = (miter_dir.0*miter_dir.0 + miter_dir.1*miter_dir.1).sqrt();
+
+                if miter_dir_len <= 0.0 {
+                    polygons.push(vec![point, offset1, offset2]);
+                } else {
+                    let tip = (point.0 + miter_dir.0/miter_dir_len*miter_length*side, point.1 + miter_dir.1/miter_dir_len*miter_length*side);
+                    polygons.push(vec![point, offset1, tip, offset2]);
+                }
+            }
+        }
+    }
+}
+
+///
+/// Generates the polygon for a cap at the end of a line (direction points away from the line)
+///
+fn cap_polygons(point: (f32, f32), direction: (f32, f32), options: &StrokeOptions, polygons: &mut Vec<Vec<(f32, f32)>>) {
+    let half_width  = options.width/2.0;
+    let normal      = (-direction.1, direction.0);
+
+    match options.cap {
+        LineCap::Butt   => { },
+        LineCap::Round  => { polygons.push(circle(point, half_width)); },
+        LineCap::Square => {
+            let extended = (point.0 + direction.0*half_width, point.1 + direction.1*half_width);
+
+            polygons.push(vec![
+                (point.0 + normal.0*half_width, point.1 + normal.1*half_width),
+                (extended.0 + normal.0*half_width, extended.1 + normal.1*half_width),
+                (extended.0 - normal.0*half_width, extended.1 - normal.1*half_width),
+                (point.0 - normal.0*half_width, point.1 - normal.1*half_width)
+            ]);
+        }
+    }
+}
+
+///
+/// Generates the polygons that make up the stroke of a single line
+///
+fn stroke_line(points: &[(f32, f32)], closed: bool, options: &StrokeOptions, polygons: &mut Vec<Vec<(f32, f32)>>) {
+    let mut points = remove_repeated_points(points);
+    if closed && points.len() > 2 && points.first() == points.last() {
+        points.pop();
+    }
+
+    let half_width = options.width/2.0;
+
+    if points.len() == 1 {
+        // Zero-length lines only generate a round cap
+        if options.cap == LineCap::Round && !closed {
+            polygons.push(circle(points[0], half_width));
+        }
+        return;
+    }
+
+    // Generate a rectangle for each segment
+    let num_segments = if closed { points.len() } else { points.len()-1 };
+
+    for segment in 0..num_segments {
+        let start           = points[segment];
+        let end             = points[(segment+1)%points.len()];
+        let (_, normal)     = direction_and_normal(start, end);
+
+        polygons.push(vec![
+            (start.0 + normal.0*half_width, start.1 + normal.1*half_width),
+            (end.0 + normal.0*half_width, end.1 + normal.1*half_width),
+            (end.0 - normal.0*half_width, end.1 - normal.1*half_width),
+            (start.0 - normal.0*half_width, start.1 - normal.1*half_width)
+        ]);
+    }
+
+    // Join the segments
+    if closed {
+        for index in 0..points.len() {
+            let before  = points[(index+points.len()-1)%points.len()];
+            let after   = points[(index+1)%points.len()];
+
+            join_polygons(points[index], before, after, options, polygons);
+        }
+    } else {
+        for index in 1..(points.len()-1) {
+            join_polygons(points[index], points[index-1], points[index+1], options, polygons);
+        }
+
+        // Cap the ends
+        let (start_dir, _)  = direction_and_normal(points[1], points[0]);
+        let (end_dir, _)    = direction_and_normal(points[points.len()-2], points[points.len()-1]);
+
+        cap_polygons(points[0], start_dir, options, polygons);
+        cap_polygons(points[points.len()-1], end_dir, options, polygons);
+    }
+}
+
+///
+/// Converts a path into a set of polygons representing its stroke
+///
+/// The polygons all have the same orientation so they can be combined using the non-zero winding rule.
+///
+pub fn stroke_path(path: &RasterPath, options: &StrokeOptions) -> Vec<Vec<(f32, f32)>> {
+    let mut polygons = vec![];
+
+    if options.width <= 0.0 {
+        return polygons;
+    }
+
+    for subpath in path.subpaths.iter() {
+        if subpath.points.len() == 0 { continue; }
+
+        if options.dash_pattern.len() == 0 {
+            stroke_line(&subpath.points, subpath.closed, options, &mut polygons);
+        } else {
+            // Dashed lines follow the closing line of the subpath too
+            let mut points = subpath.points.clone();
+            if subpath.closed { points.push(points[0]); }
+
+            for dash in dash_line(&points, &options.dash_pattern, options.dash_offset) {
+                stroke_line(&dash, false, options, &mut polygons);
+            }
+        }
+    }
+
+    // Give all of the polygons a consistent orientation
+    for polygon in polygons.iter_mut() {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+
+    polygons
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn options(cap: LineCap, dash_pattern: Vec<f32>) -> StrokeOptions {
+        StrokeOptions {
+            width:          2.0,
+            join:           LineJoin::Miter,
+            cap:            cap,
+            dash_pattern:   dash_pattern,
+            dash_offset:    0.0
+        }
+    }
+
+    #[test]
+    fn dash_simple_line() {
+        let dashes = dash_line(&[(0.0, 0.0), (10.0, 0.0)], &[2.0, 3.0], 0.0);
+
+        assert!(dashes == vec![
+            vec![(0.0, 0.0), (2.0, 0.0)],
+            vec![(5.0, 0.0), (7.0, 0.0)]
+        ]);
+    }
+
+    #[test]
+    fn dash_with_offset() {
+        let dashes = dash_line(&[(0.0, 0.0), (10.0, 0.0)], &[2.0, 3.0], 3.0);
+
+        assert!(dashes == vec![
+            vec![(2.0, 0.0), (4.0, 0.0)],
+            vec![(7.0, 0.0), (9.0, 0.0)]
+        ]);
+    }
+
+    #[test]
+    fn dash_follows_corners() {
+        let dashes = dash_line(&[(0.0, 0.0), (2.0, 0.0), (2.0, 4.0)], &[3.0, 1.0], 0.0);
+
+        assert!(dashes == vec![
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 1.0)],
+            vec![(2.0, 2.0), (2.0, 4.0)]
+        ]);
+    }
+
+    #[test]
+    fn polygons_have_positive_area() {
+        let mut path = RasterPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        path.line_to((10.0, 10.0));
+        path.line_to((0.0, 5.0));
+
+        let polygons = stroke_path(&path, &options(LineCap::Square, vec![]));
+
+        assert!(polygons.len() > 0);
+        assert!(polygons.iter().all(|polygon| signed_area(polygon) >= 0.0));
+    }
+
+    #[test]
+    fn butt_caps_generate_no_polygons() {
+        let mut path = RasterPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+
+        let polygons = stroke_path(&path, &options(LineCap::Butt, vec![]));
+
+        assert!(polygons.len() == 1);
+    }
+}
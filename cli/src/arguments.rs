@@ -0,0 +1,125 @@
+use flo_canvas::*;
+use flo_anim_sqlite::*;
+
+use std::path::Path;
+use std::time::Duration;
+
+///
+/// Opens an existing animation file
+///
+pub fn open_animation(path: &str) -> Result<SqliteAnimation, String> {
+    if !Path::new(path).exists() {
+        return Err(format!("{}: file not found", path));
+    }
+
+    SqliteAnimation::open_file(path)
+        .map_err(|err| format!("{}: could not open animation ({:?})", path, err))
+}
+
+///
+/// Parses a size in the form 'WIDTHxHEIGHT'
+///
+pub fn parse_size(size: &str) -> Result<(usize, usize), String> {
+    let parts = size.split(|c| c == 'x' || c == 'X').collect::<Vec<_>>();
+
+    match parts.as_slice() {
+        [width, height] => {
+            let width   = width.trim().parse::<usize>().map_err(|_| format!("'{}' is not a valid size (expected WIDTHxHEIGHT)", size))?;
+            let height  = height.trim().parse::<usize>().map_err(|_| format!("'{}' is not a valid size (expected WIDTHxHEIGHT)", size))?;
+
+            if width == 0 || height == 0 {
+                Err(format!("'{}' is not a valid size (width and height must be greater than 0)", size))
+            } else {
+                Ok((width, height))
+            }
+        },
+
+        _ => Err(format!("'{}' is not a valid size (expected WIDTHxHEIGHT)", size))
+    }
+}
+
+///
+/// Parses a time in seconds
+///
+pub fn parse_time(time: &str) -> Result<Duration, String> {
+    let seconds = time.trim().parse::<f64>().map_err(|_| format!("'{}' is not a valid time in seconds", time))?;
+
+    if seconds < 0.0 || !seconds.is_finite() {
+        Err(format!("'{}' is not a valid time in seconds", time))
+    } else {
+        Ok(Duration::from_nanos((seconds * 1_000_000_000.0).round() as u64))
+    }
+}
+
+///
+/// Parses a colour in the form '#rrggbb' or '#rrggbbaa'
+///
+pub fn parse_color(color: &str) -> Result<Color, String> {
+    let hex     = color.trim().trim_start_matches('#');
+    let invalid = || format!("'{}' is not a valid colour (expected #rrggbb or #rrggbbaa)", color);
+
+    if (hex.len() != 6 && hex.len() != 8) || !hex.chars().all(|c| c.is_digit(16)) {
+        return Err(invalid());
+    }
+
+    let component = |index: usize| u8::from_str_radix(&hex[index*2..index*2+2], 16).map(|c| (c as f32)/255.0).map_err(|_| invalid());
+
+    let alpha = if hex.len() == 8 { component(3)? } else { 1.0 };
+
+    Ok(Color::Rgba(component(0)?, component(1)?, component(2)?, alpha))
+}
+
+///
+/// Parses a count of items (such as a number of edits)
+///
+pub fn parse_count(count: &str) -> Result<usize, String> {
+    count.trim().parse::<usize>().map_err(|_| format!("'{}' is not a valid number", count))
+}
+
+///
+/// Formats a time in seconds for display
+///
+pub fn format_time(time: Duration) -> String {
+    format!("{}.{:03}s", time.as_secs(), time.subsec_millis())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_valid_size() {
+        assert!(parse_size("640x480") == Ok((640, 480)));
+    }
+
+    #[test]
+    fn parse_invalid_size() {
+        assert!(parse_size("640").is_err());
+        assert!(parse_size("0x480").is_err());
+    }
+
+    #[test]
+    fn parse_fractional_time() {
+        assert!(parse_time("1.5") == Ok(Duration::from_millis(1500)));
+        assert!(parse_time("-1").is_err());
+    }
+
+    #[test]
+    fn parse_colour_with_alpha() {
+        assert!(parse_color("#ff000080") == Ok(Color::Rgba(1.0, 0.0, 0.0, 128.0/255.0)));
+        assert!(parse_color("#00ff00") == Ok(Color::Rgba(0.0, 1.0, 0.0, 1.0)));
+        assert!(parse_color("red").is_err());
+    }
+
+    #[test]
+    fn parse_valid_count() {
+        assert!(parse_count("42") == Ok(42));
+        assert!(parse_count("-1").is_err());
+    }
+
+    #[test]
+    fn format_time_as_seconds() {
+        assert!(format_time(Duration::from_millis(1500)) == "1.500s");
+        assert!(format_time(Duration::from_millis(41)) == "0.041s");
+    }
+}
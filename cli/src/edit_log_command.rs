@@ -0,0 +1,139 @@
+use super::arguments::*;
+
+use flo_animation::*;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use futures::Stream;
+use serde_json;
+
+///
+/// Creates the definition of the edit-log subcommand
+///
+pub fn edit_log_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("edit-log")
+        .about("Writes the edit log of an animation to standard output as JSON")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to read")
+            .required(true)
+            .index(1))
+        .arg(Arg::with_name("start")
+            .long("start")
+            .takes_value(true)
+            .value_name("EDIT")
+            .help("The index of the first edit to write (defaults to 0)"))
+        .arg(Arg::with_name("count")
+            .long("count")
+            .short("n")
+            .takes_value(true)
+            .value_name("EDITS")
+            .help("The maximum number of edits to write (defaults to the rest of the log)"))
+        .arg(Arg::with_name("pretty")
+            .long("pretty")
+            .short("p")
+            .help("Formats the JSON so it's easier to read"))
+}
+
+///
+/// Reads a range of edits from an animation
+///
+pub fn read_edits(animation: &dyn Animation, start: usize, count: Option<usize>) -> Result<Vec<AnimationEdit>, String> {
+    let num_edits   = animation.get_num_edits();
+    let start       = start.min(num_edits);
+    let end         = count.map(|count| (start+count).min(num_edits)).unwrap_or(num_edits);
+
+    animation.read_edit_log(start..end)
+        .wait()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "Could not read the edit log".to_string())
+}
+
+///
+/// Runs the edit-log subcommand
+///
+pub fn run_edit_log(matches: &ArgMatches) -> Result<(), String> {
+    let input       = matches.value_of("INPUT").unwrap();
+    let start       = matches.value_of("start").map(parse_count).unwrap_or(Ok(0))?;
+    let count       = matches.value_of("count").map(parse_count).map_or(Ok(None), |count| count.map(Some))?;
+
+    let animation   = open_animation(input)?;
+    let edits       = read_edits(&animation, start, count).map_err(|err| format!("{}: {}", input, err))?;
+
+    let json        = if matches.is_present("pretty") {
+        serde_json::to_string_pretty(&edits)
+    } else {
+        serde_json::to_string(&edits)
+    };
+
+    println!("{}", json.map_err(|err| format!("{}: could not write the edit log ({})", input, err))?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use flo_anim_sqlite::*;
+
+    use std::sync::*;
+    use std::time::Duration;
+
+    #[test]
+    fn read_range_of_edits() {
+        let animation = SqliteAnimation::new_in_memory();
+        animation.perform_edits(vec![
+            AnimationEdit::SetSize(800.0, 600.0),
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+        ]);
+
+        assert!(read_edits(&animation, 0, None).unwrap().len() == 3);
+        assert!(read_edits(&animation, 1, Some(1)).unwrap() == vec![AnimationEdit::AddNewLayer(2)]);
+        assert!(read_edits(&animation, 5, Some(10)).unwrap().len() == 0);
+    }
+
+    #[test]
+    fn read_element_and_motion_edits() {
+        let when        = Duration::from_millis(0);
+        let motion_path = TimeCurve::new(TimePoint::new(0.0, 0.0, when), TimePoint::new(100.0, 50.0, Duration::from_millis(1000)));
+        let square      = Arc::new(vec![
+            PathComponent::Move(PathPoint::new(10.0, 10.0)),
+            PathComponent::Line(PathPoint::new(20.0, 10.0)),
+            PathComponent::Line(PathPoint::new(20.0, 20.0)),
+            PathComponent::Close
+        ]);
+
+        let animation = SqliteAnimation::new_in_memory();
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(when)),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::CreatePath(ElementId::Assigned(100), square.clone()))),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetControlPoints(vec![(5.0, 5.0), (25.0, 5.0), (25.0, 25.0)])),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetPath(square)),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Order(ElementOrdering::ToTop)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetType(MotionType::Translate)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetOrigin(15.0, 15.0)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetPath(motion_path.clone())),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::AddAttachment(ElementId::Assigned(200))),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)
+        ]);
+
+        let edits = read_edits(&animation, 8, None).unwrap();
+
+        assert!(edits == vec![
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetType(MotionType::Translate)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetOrigin(15.0, 15.0)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetPath(motion_path)),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::AddAttachment(ElementId::Assigned(200))),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)
+        ]);
+
+        // The whole log can be written out as JSON
+        assert!(read_edits(&animation, 0, None).unwrap().len() == 14);
+        assert!(serde_json::to_string(&read_edits(&animation, 0, None).unwrap()).is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+use super::arguments::*;
+
+use flo_export::*;
+use flo_animation::*;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use std::fs;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+
+///
+/// The file formats that the export command can generate
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ExportFormat {
+    /// A directory of PNG files, one per frame
+    PngSequence,
+
+    /// An animated GIF file
+    Gif,
+
+    /// An animated PNG file
+    Apng,
+
+    /// A directory of SVG files, one per frame
+    SvgSequence,
+
+    /// An animated SVG file
+    Svg
+}
+
+impl ExportFormat {
+    ///
+    /// Finds the export format with a particular name
+    ///
+    fn from_name(name: &str) -> Option<ExportFormat> {
+        use self::ExportFormat::*;
+
+        match name {
+            "png-sequence"  => Some(PngSequence),
+            "gif"           => Some(Gif),
+            "apng"          => Some(Apng),
+            "svg-sequence"  => Some(SvgSequence),
+            "svg"           => Some(Svg),
+            _               => None
+        }
+    }
+
+    ///
+    /// Chooses an export format from the name of the output file
+    ///
+    fn from_output_path(path: &Path) -> ExportFormat {
+        use self::ExportFormat::*;
+
+        let extension = path.extension().and_then(|extension| extension.to_str()).map(|extension| extension.to_lowercase());
+
+        match extension.as_ref().map(|extension| extension.as_str()) {
+            Some("gif")             => Gif,
+            Some("png")             |
+            Some("apng")            => Apng,
+            Some("svg")             => Svg,
+            _                       => PngSequence
+        }
+    }
+}
+
+///
+/// Creates the definition of the export subcommand
+///
+pub fn export_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("export")
+        .about("Exports an animation as a PNG sequence, animated GIF, APNG or SVG")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to export")
+            .required(true)
+            .index(1))
+        .arg(Arg::with_name("OUTPUT")
+            .help("The file or directory to write (the format is chosen from the extension if --format is not specified)")
+            .required(true)
+            .index(2))
+        .arg(Arg::with_name("format")
+            .long("format")
+            .short("f")
+            .takes_value(true)
+            .possible_values(&["png-sequence", "gif", "apng", "svg-sequence", "svg"])
+            .help("The format of the output"))
+        .arg(Arg::with_name("size")
+            .long("size")
+            .short("s")
+            .takes_value(true)
+            .value_name("WIDTHxHEIGHT")
+            .help("The size of the output in pixels (defaults to the size of the animation)"))
+        .arg(Arg::with_name("start")
+            .long("start")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .help("The time of the first frame to export"))
+        .arg(Arg::with_name("end")
+            .long("end")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .help("The time where the export should stop"))
+        .arg(Arg::with_name("background")
+            .long("background")
+            .short("b")
+            .takes_value(true)
+            .value_name("COLOUR")
+            .help("The background colour, as #rrggbb or #rrggbbaa (defaults to transparent)"))
+}
+
+///
+/// Runs the export subcommand
+///
+pub fn run_export(matches: &ArgMatches) -> Result<(), String> {
+    use self::ExportFormat::*;
+
+    let input       = matches.value_of("INPUT").unwrap();
+    let output      = Path::new(matches.value_of("OUTPUT").unwrap());
+    let format      = matches.value_of("format")
+        .and_then(ExportFormat::from_name)
+        .unwrap_or_else(|| ExportFormat::from_output_path(output));
+
+    // Read the options
+    let animation   = open_animation(input)?;
+    let mut options = RasterExportOptions::default();
+
+    if let Some(size) = matches.value_of("size") {
+        options.size = Some(parse_size(size)?);
+    }
+
+    if let Some(background) = matches.value_of("background") {
+        options.background = Some(parse_color(background)?);
+    }
+
+    if matches.is_present("start") || matches.is_present("end") {
+        let start   = matches.value_of("start").map(parse_time).unwrap_or(Ok(Duration::from_millis(0)))?;
+        let end     = matches.value_of("end").map(parse_time).unwrap_or_else(|| Ok(animation.duration()))?;
+
+        if end <= start {
+            return Err("The end time must be after the start time".to_string());
+        }
+
+        options.time_range = Some(start..end);
+    }
+
+    if (format == Svg || format == SvgSequence) && (options.size.is_some() || options.time_range.is_some() || options.background.is_some()) {
+        return Err("The --size, --start, --end and --background options are not supported for SVG output".to_string());
+    }
+
+    // Perform the export
+    let output_error = |err| format!("{}: {}", output.display(), err);
+
+    match format {
+        PngSequence => {
+            let frames = export_png_sequence(&animation, output, &options).map_err(output_error)?;
+            println!("Wrote {} frames to {}", frames.len(), output.display());
+        },
+
+        SvgSequence => {
+            let frames = export_svg_frames(&animation, output).map_err(output_error)?;
+            println!("Wrote {} frames to {}", frames.len(), output.display());
+        },
+
+        Gif         => {
+            let file = BufWriter::new(fs::File::create(output).map_err(output_error)?);
+            write_gif(&animation, file, &options).map_err(output_error)?;
+        },
+
+        Apng        => {
+            let file = BufWriter::new(fs::File::create(output).map_err(output_error)?);
+            write_apng(&animation, file, &options).map_err(output_error)?;
+        },
+
+        Svg         => {
+            fs::write(output, animation_to_animated_svg(&animation)).map_err(output_error)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_from_extension() {
+        assert!(ExportFormat::from_output_path(Path::new("test.gif")) == ExportFormat::Gif);
+        assert!(ExportFormat::from_output_path(Path::new("test.PNG")) == ExportFormat::Apng);
+        assert!(ExportFormat::from_output_path(Path::new("test.svg")) == ExportFormat::Svg);
+        assert!(ExportFormat::from_output_path(Path::new("frames")) == ExportFormat::PngSequence);
+    }
+}
@@ -0,0 +1,77 @@
+use super::arguments::*;
+
+use flo_export::*;
+use flo_animation::*;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use std::time::Duration;
+
+///
+/// Creates the definition of the info subcommand
+///
+pub fn info_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("info")
+        .about("Displays the size, duration, layers and keyframes of an animation")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to describe")
+            .required(true)
+            .index(1))
+}
+
+///
+/// Describes the keyframes in a layer
+///
+fn describe_key_frames(key_frames: &[Duration]) -> String {
+    match key_frames.len() {
+        0 => "no keyframes".to_string(),
+        1 => format!("1 keyframe ({})", format_time(key_frames[0])),
+        n => format!("{} keyframes ({})", n, key_frames.iter().map(|time| format_time(*time)).collect::<Vec<_>>().join(", "))
+    }
+}
+
+///
+/// Runs the info subcommand
+///
+pub fn run_info(matches: &ArgMatches) -> Result<(), String> {
+    let input           = matches.value_of("INPUT").unwrap();
+    let animation       = open_animation(input)?;
+
+    let (width, height) = animation.size();
+    let frame_length    = animation.frame_length();
+    let num_frames      = frame_times(&animation).len();
+    let fps             = if frame_length.as_nanos() > 0 { 1_000_000_000.0 / (frame_length.as_nanos() as f64) } else { 0.0 };
+    let layer_ids       = layer_ids_in_group_order(&animation);
+
+    println!("File:         {}", input);
+    println!("Size:         {}x{}", width, height);
+    println!("Duration:     {} ({} frames at {:.2}fps)", format_time(animation.duration()), num_frames, fps);
+    println!("Edit log:     {} edits", animation.get_num_edits());
+    println!("Layers:       {}", layer_ids.len());
+
+    for layer_id in layer_ids {
+        let layer       = animation.get_layer_with_id(layer_id).ok_or_else(|| format!("{}: layer {} could not be read", input, layer_id))?;
+        let name        = layer.name().map(|name| format!(" \"{}\"", name)).unwrap_or_default();
+        let key_frames  = layer.get_key_frames().collect::<Vec<_>>();
+        let group       = layer.parent_group().map(|group_id| format!(" (in group {})", group_id)).unwrap_or_default();
+
+        if layer.as_group_layer().is_some() {
+            println!("  Group {}{}{}", layer_id, name, group);
+        } else {
+            println!("  Layer {}{}{}: {}", layer_id, name, group, describe_key_frames(&key_frames));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn describe_layer_key_frames() {
+        assert!(describe_key_frames(&[]) == "no keyframes");
+        assert!(describe_key_frames(&[Duration::from_millis(0), Duration::from_millis(500)]) == "2 keyframes (0.000s, 0.500s)");
+    }
+}
@@ -0,0 +1,65 @@
+//!
+//! # FlowBetween command-line tool
+//!
+//! Performs operations on FlowBetween animation files without needing to start the editor
+//!
+#![warn(bare_trait_objects)]
+
+extern crate flo_canvas;
+extern crate flo_animation;
+extern crate flo_anim_sqlite;
+extern crate flo_export;
+extern crate futures;
+extern crate serde_json;
+
+#[macro_use] extern crate clap;
+
+mod arguments;
+mod export_command;
+mod info_command;
+mod edit_log_command;
+mod render_command;
+mod maintenance_command;
+mod validate_command;
+
+use self::export_command::*;
+use self::info_command::*;
+use self::edit_log_command::*;
+use self::render_command::*;
+use self::maintenance_command::*;
+use self::validate_command::*;
+
+use clap::{App, AppSettings};
+
+use std::process;
+
+fn main() {
+    let matches = App::new("flo-cli")
+        .version(crate_version!())
+        .about("Performs operations on FlowBetween animation files")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(info_subcommand())
+        .subcommand(edit_log_subcommand())
+        .subcommand(render_subcommand())
+        .subcommand(export_subcommand())
+        .subcommand(compact_subcommand())
+        .subcommand(upgrade_subcommand())
+        .subcommand(validate_subcommand())
+        .get_matches();
+
+    let result = match matches.subcommand() {
+        ("info", Some(info_matches))            => run_info(info_matches),
+        ("edit-log", Some(edit_log_matches))    => run_edit_log(edit_log_matches),
+        ("render", Some(render_matches))        => run_render(render_matches),
+        ("export", Some(export_matches))        => run_export(export_matches),
+        ("compact", Some(compact_matches))      => run_compact(compact_matches),
+        ("upgrade", Some(upgrade_matches))      => run_upgrade(upgrade_matches),
+        ("validate", Some(validate_matches))    => run_validate(validate_matches),
+        _                                       => Ok(())
+    };
+
+    if let Err(message) = result {
+        eprintln!("flo-cli: {}", message);
+        process::exit(1);
+    }
+}
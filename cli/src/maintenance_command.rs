@@ -0,0 +1,102 @@
+use super::arguments::*;
+
+use flo_anim_sqlite::*;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use std::fs;
+use std::path::Path;
+
+///
+/// Retrieves the size of a file in bytes
+///
+fn file_size(path: &str) -> Result<u64, String> {
+    fs::metadata(path)
+        .map(|metadata| metadata.len())
+        .map_err(|err| format!("{}: {}", path, err))
+}
+
+///
+/// Creates the definition of the compact subcommand
+///
+pub fn compact_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("compact")
+        .about("Discards cached data and old history from an animation file and reduces the space it uses on disk")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to compact")
+            .required(true)
+            .index(1))
+        .arg(Arg::with_name("keep-edits")
+            .long("keep-edits")
+            .short("k")
+            .takes_value(true)
+            .value_name("EDITS")
+            .help("The number of recent edits to keep in the edit log (defaults to keeping the whole edit log)"))
+}
+
+///
+/// Creates the definition of the upgrade subcommand
+///
+pub fn upgrade_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("upgrade")
+        .about("Upgrades an animation file created by an older version of FlowBetween to the latest file format")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to upgrade")
+            .required(true)
+            .index(1))
+        .arg(Arg::with_name("dry-run")
+            .long("dry-run")
+            .help("Lists the upgrades that would be applied without changing the file"))
+}
+
+///
+/// Runs the compact subcommand
+///
+pub fn run_compact(matches: &ArgMatches) -> Result<(), String> {
+    let input           = matches.value_of("INPUT").unwrap();
+    let policy          = match matches.value_of("keep-edits") {
+        Some(keep_edits)    => CompactionPolicy::KeepLastEdits(parse_count(keep_edits)?),
+        None                => CompactionPolicy::KeepAllEdits
+    };
+    let original_size   = file_size(input)?;
+
+    let num_discarded   = {
+        let animation = open_animation(input)?;
+        animation.compact(policy).map_err(|err| format!("{}: could not compact animation ({:?})", input, err))?
+    };
+
+    let compacted_size  = file_size(input)?;
+    println!("{}: {} bytes -> {} bytes ({} edits removed from the edit log)", input, original_size, compacted_size, num_discarded);
+
+    Ok(())
+}
+
+///
+/// Runs the upgrade subcommand
+///
+pub fn run_upgrade(matches: &ArgMatches) -> Result<(), String> {
+    let input       = matches.value_of("INPUT").unwrap();
+
+    if !Path::new(input).exists() {
+        return Err(format!("{}: file not found", input));
+    }
+
+    let upgrades    = SqliteAnimation::pending_upgrades(input).map_err(|err| format!("{}: cannot upgrade animation ({:?})", input, err))?;
+
+    if upgrades.len() == 0 {
+        println!("{}: already up to date", input);
+        return Ok(());
+    }
+
+    let dry_run     = matches.is_present("dry-run");
+
+    if !dry_run {
+        SqliteAnimation::upgrade_file(input).map_err(|err| format!("{}: upgrade failed ({:?})", input, err))?;
+    }
+
+    for upgrade in upgrades {
+        println!("{}: {} {}", input, if dry_run { "would apply" } else { "applied" }, upgrade);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use super::arguments::*;
+
+use flo_export::*;
+use flo_animation::*;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use std::fs;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+
+///
+/// Returns true if a frame should be rendered as an SVG file rather than a PNG file
+///
+fn is_svg_output(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase() == "svg")
+        .unwrap_or(false)
+}
+
+///
+/// Creates the definition of the render subcommand
+///
+pub fn render_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("render")
+        .about("Renders a single frame of an animation as a PNG or SVG file")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to render")
+            .required(true)
+            .index(1))
+        .arg(Arg::with_name("OUTPUT")
+            .help("The file to write (an SVG file is written if this has the .svg extension, otherwise a PNG file)")
+            .required(true)
+            .index(2))
+        .arg(Arg::with_name("time")
+            .long("time")
+            .short("t")
+            .takes_value(true)
+            .value_name("SECONDS")
+            .help("The time of the frame to render (defaults to the first frame)"))
+        .arg(Arg::with_name("size")
+            .long("size")
+            .short("s")
+            .takes_value(true)
+            .value_name("WIDTHxHEIGHT")
+            .help("The size of the output in pixels (defaults to the size of the animation)"))
+        .arg(Arg::with_name("background")
+            .long("background")
+            .short("b")
+            .takes_value(true)
+            .value_name("COLOUR")
+            .help("The background colour, as #rrggbb or #rrggbbaa (defaults to transparent)"))
+}
+
+///
+/// Runs the render subcommand
+///
+pub fn run_render(matches: &ArgMatches) -> Result<(), String> {
+    let input       = matches.value_of("INPUT").unwrap();
+    let output      = Path::new(matches.value_of("OUTPUT").unwrap());
+    let when        = matches.value_of("time").map(parse_time).unwrap_or(Ok(Duration::from_millis(0)))?;
+
+    // Read the options
+    let animation   = open_animation(input)?;
+    let mut options = RasterExportOptions::default();
+
+    if when > animation.duration() {
+        return Err(format!("The time {} is after the end of the animation ({})", format_time(when), format_time(animation.duration())));
+    }
+
+    if let Some(size) = matches.value_of("size") {
+        options.size = Some(parse_size(size)?);
+    }
+
+    if let Some(background) = matches.value_of("background") {
+        options.background = Some(parse_color(background)?);
+    }
+
+    // Render the frame
+    let output_error = |err| format!("{}: {}", output.display(), err);
+
+    if is_svg_output(output) {
+        if options.size.is_some() || options.background.is_some() {
+            return Err("The --size and --background options are not supported for SVG output".to_string());
+        }
+
+        fs::write(output, frame_to_svg(&animation, when)).map_err(output_error)?;
+    } else {
+        let (width, height) = options.output_size(&animation);
+        let pixels          = render_frame_rgba(&animation, when, &options);
+        let file            = BufWriter::new(fs::File::create(output).map_err(output_error)?);
+
+        write_png(file, width, height, &pixels).map_err(output_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn svg_output_from_extension() {
+        assert!(is_svg_output(Path::new("frame.svg")));
+        assert!(is_svg_output(Path::new("frame.SVG")));
+        assert!(!is_svg_output(Path::new("frame.png")));
+        assert!(!is_svg_output(Path::new("frame")));
+    }
+}
@@ -0,0 +1,157 @@
+use super::arguments::*;
+use super::edit_log_command::*;
+
+use flo_canvas::*;
+use flo_animation::*;
+use flo_anim_sqlite::*;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+
+use std::path::Path;
+use std::panic;
+
+///
+/// Creates the definition of the validate subcommand
+///
+pub fn validate_subcommand<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("validate")
+        .about("Checks an animation file for corruption, exiting with an error if any problems are found")
+        .arg(Arg::with_name("INPUT")
+            .help("The animation file to check")
+            .required(true)
+            .index(1))
+}
+
+///
+/// Checks that the contents of an animation can be read, returning a description of any problems that were found
+///
+pub fn validate_animation(animation: &SqliteAnimation) -> Vec<String> {
+    let mut problems = vec![];
+
+    // Check the database itself
+    match animation.check_integrity() {
+        Ok(database_problems)   => problems.extend(database_problems),
+        Err(err)                => problems.push(format!("Could not check the integrity of the file ({:?})", err))
+    }
+
+    // Every edit in the log should be readable
+    let num_edits = animation.get_num_edits();
+    match read_edits(animation, 0, None) {
+        Ok(ref edits) if edits.len() == num_edits   => { },
+        Ok(edits)                                   => problems.push(format!("The edit log should contain {} edits but only {} could be read", num_edits, edits.len())),
+        Err(err)                                    => problems.push(err)
+    }
+
+    // Every keyframe in every layer should render
+    for layer_id in animation.get_layer_ids() {
+        let layer = match animation.get_layer_with_id(layer_id) {
+            Some(layer) => layer,
+            None        => { problems.push(format!("Layer {} could not be read", layer_id)); continue; }
+        };
+
+        for key_frame in layer.get_key_frames() {
+            let rendered = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                let mut drawing: Vec<Draw> = vec![];
+                layer.get_frame_at_time(key_frame).render_to(&mut drawing);
+            }));
+
+            if rendered.is_err() {
+                problems.push(format!("Layer {}: the keyframe at {} could not be rendered", layer_id, format_time(key_frame)));
+            }
+        }
+    }
+
+    // Any errors that occurred while reading the animation are also problems
+    if let Some(err) = animation.retrieve_and_clear_error() {
+        problems.push(format!("Error while reading the animation ({:?})", err));
+    }
+
+    problems
+}
+
+///
+/// Runs the validate subcommand
+///
+pub fn run_validate(matches: &ArgMatches) -> Result<(), String> {
+    let input       = matches.value_of("INPUT").unwrap();
+
+    if !Path::new(input).exists() {
+        return Err(format!("{}: file not found", input));
+    }
+
+    // Opening a file that needs upgrading would change it, so these are reported instead
+    let upgrades    = SqliteAnimation::pending_upgrades(input).map_err(|err| format!("{}: unsupported file ({:?})", input, err))?;
+    if upgrades.len() > 0 {
+        return Err(format!("{}: the file needs to be upgraded before it can be validated ({})", input, upgrades.join(", ")));
+    }
+
+    let animation   = open_animation(input)?;
+    let problems    = validate_animation(&animation);
+
+    if problems.len() == 0 {
+        println!("{}: ok", input);
+        Ok(())
+    } else {
+        for problem in problems.iter() {
+            println!("{}: {}", input, problem);
+        }
+
+        Err(format!("{}: found {} problems", input, problems.len()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::*;
+    use std::time::Duration;
+
+    #[test]
+    fn new_animation_is_valid() {
+        let animation = SqliteAnimation::new_in_memory();
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000)))
+        ]);
+
+        assert!(validate_animation(&animation).len() == 0);
+    }
+
+    #[test]
+    fn animation_with_element_and_motion_edits_is_valid() {
+        let when        = Duration::from_millis(0);
+        let motion_path = TimeCurve::new(TimePoint::new(0.0, 0.0, when), TimePoint::new(100.0, 50.0, Duration::from_millis(1000)));
+
+        let animation = SqliteAnimation::new_in_memory();
+        animation.perform_edits(vec![
+            AnimationEdit::AddNewLayer(2),
+            AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(when)),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(20.0, 10.0)),
+                PathComponent::Line(PathPoint::new(20.0, 20.0)),
+                PathComponent::Close
+            ])))),
+            AnimationEdit::Layer(2, LayerEdit::Path(when, PathEdit::CreatePath(ElementId::Assigned(101), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(30.0, 30.0)),
+                PathComponent::Line(PathPoint::new(40.0, 30.0)),
+                PathComponent::Line(PathPoint::new(40.0, 40.0)),
+                PathComponent::Close
+            ])))),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetControlPoints(vec![(5.0, 5.0), (25.0, 5.0), (25.0, 25.0)])),
+            AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::Order(ElementOrdering::ToBottom)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::Create),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetType(MotionType::Translate)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetOrigin(15.0, 15.0)),
+            AnimationEdit::Motion(ElementId::Assigned(200), MotionEdit::SetPath(motion_path)),
+            AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::AddAttachment(ElementId::Assigned(200))),
+            AnimationEdit::Element(vec![ElementId::Assigned(101)], ElementEdit::DetachFromFrame)
+        ]);
+
+        assert!(validate_animation(&animation).len() == 0);
+    }
+}
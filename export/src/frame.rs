@@ -0,0 +1,111 @@
+use flo_canvas::*;
+use flo_animation::*;
+
+use std::time::Duration;
+use std::ops::Range;
+use std::collections::HashMap;
+
+///
+/// Converts a duration to nanoseconds
+///
+fn to_nanos(when: Duration) -> u64 {
+    when.as_secs() * 1_000_000_000 + (when.subsec_nanos() as u64)
+}
+
+///
+/// Returns the times of the frames that start in a particular time range
+///
+pub fn frame_times_in_range(frame_length: Duration, range: Range<Duration>) -> Vec<Duration> {
+    let frame_nanos = to_nanos(frame_length);
+    let start_nanos = to_nanos(range.start);
+    let end_nanos   = to_nanos(range.end);
+
+    if frame_nanos == 0 || end_nanos <= start_nanos {
+        // No frames if the frame length is 0 or the range is empty
+        vec![]
+    } else {
+        // Count the frames that start before the end of the range
+        let num_frames = (end_nanos - start_nanos + frame_nanos - 1) / frame_nanos;
+
+        (0..num_frames)
+            .map(|frame_num| range.start + Duration::from_nanos(frame_num * frame_nanos))
+            .collect()
+    }
+}
+
+///
+/// Returns the times of each of the frames in an animation
+///
+pub fn frame_times(animation: &dyn Animation) -> Vec<Duration> {
+    frame_times_in_range(animation.frame_length(), Duration::from_millis(0)..animation.duration())
+}
+
+///
+/// Renders the frame of an animation at a particular time to a graphics context
+///
+/// The animation is scaled to fill the canvas: animation coordinates run from (0,0) at the bottom-left
+/// to (width, height) at the top-right. Each animation layer is rendered to its own canvas layer, starting
+/// from layer 1 (layer 0 is left free for a background). The layers in a group are all rendered to the
+/// same canvas layer so that the opacity and blend mode of the group are applied to them as a unit.
+///
+pub fn render_animation_frame(animation: &dyn Animation, when: Duration, gc: &mut dyn GraphicsPrimitives) {
+    let (width, height) = animation.size();
+
+    // Set up the coordinate scheme
+    gc.canvas_height(height as f32);
+    gc.center_region(0.0, 0.0, width as f32, height as f32);
+
+    // Maps the layers that are composited as a unit to the canvas layer they're drawn into
+    let mut canvas_layers = HashMap::new();
+
+    // Render each visible layer in order (layers in groups follow the group that contains them)
+    for layer_id in layer_ids_in_group_order(animation) {
+        if let Some(layer) = animation.get_layer_with_id(layer_id) {
+            let (visible, opacity, blend_mode) = composited_layer_appearance(animation, &*layer);
+            if !visible { continue; }
+
+            // The first layer that's drawn for each group sets up its canvas layer
+            let composited_id   = composited_layer_id(animation, &*layer);
+            let next_layer_id   = (canvas_layers.len() + 1) as u32;
+            let canvas_layer_id = *canvas_layers.entry(composited_id).or_insert_with(|| {
+                gc.layer_blend(next_layer_id, blend_mode);
+                gc.layer_alpha(next_layer_id, opacity as f32);
+
+                next_layer_id
+            });
+
+            let frame           = layer.get_frame_at_time(when);
+
+            gc.layer(canvas_layer_id);
+            frame.render_to(gc);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn frames_in_one_second() {
+        let frames = frame_times_in_range(Duration::from_millis(100), Duration::from_millis(0)..Duration::from_millis(1000));
+
+        assert!(frames.len() == 10);
+        assert!(frames[0] == Duration::from_millis(0));
+        assert!(frames[9] == Duration::from_millis(900));
+    }
+
+    #[test]
+    fn partial_frame_at_end() {
+        let frames = frame_times_in_range(Duration::from_millis(100), Duration::from_millis(250)..Duration::from_millis(500));
+
+        assert!(frames == vec![Duration::from_millis(250), Duration::from_millis(350), Duration::from_millis(450)]);
+    }
+
+    #[test]
+    fn no_frames_for_zero_frame_length() {
+        let frames = frame_times_in_range(Duration::from_millis(0), Duration::from_millis(0)..Duration::from_millis(1000));
+
+        assert!(frames.len() == 0);
+    }
+}
@@ -0,0 +1,18 @@
+//!
+//! Library for exporting FlowBetween animations to other file formats
+//!
+#![warn(bare_trait_objects)]
+
+extern crate flo_canvas;
+extern crate flo_animation;
+extern crate flo_canvas_raster;
+extern crate png;
+extern crate gif;
+
+mod frame;
+mod svg;
+mod raster;
+
+pub use self::frame::*;
+pub use self::svg::*;
+pub use self::raster::*;
@@ -0,0 +1,75 @@
+use super::options::*;
+use super::raster_frame::*;
+
+use flo_animation::*;
+use gif;
+
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+///
+/// Converts a time to a number of hundredths of a second (the unit used for delays in GIF files)
+///
+fn to_centiseconds(when: Duration) -> u64 {
+    (when.as_secs() * 100) + (((when.subsec_nanos() as u64) + 5_000_000) / 10_000_000)
+}
+
+///
+/// Converts a GIF encoding error to an IO error
+///
+fn gif_error(error: gif::EncodingError) -> io::Error {
+    match error {
+        gif::EncodingError::Io(io_error)    => io_error,
+        other                               => io::Error::new(io::ErrorKind::InvalidData, other.to_string())
+    }
+}
+
+///
+/// Writes an animation to a target as an animated GIF file
+///
+/// The animation will loop forever when played back. As GIF files only support delays in hundredths
+/// of a second, the delay for each frame is chosen so that the frames stay in sync with the animation.
+///
+pub fn write_gif<Target: Write>(animation: &dyn Animation, target: Target, options: &RasterExportOptions) -> io::Result<()> {
+    let (width, height) = options.output_size(animation);
+    let frame_times     = options.frame_times(animation);
+    let frame_length    = animation.frame_length();
+
+    if frame_times.len() == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "There are no frames to export"));
+    }
+
+    if width > (u16::max_value() as usize) || height > (u16::max_value() as usize) {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Image is too large to export as a GIF"));
+    }
+
+    // Set up the encoder
+    let mut encoder = gif::Encoder::new(target, width as u16, height as u16, &[]).map_err(gif_error)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+
+    // Write the frames
+    for when in frame_times {
+        let mut pixels  = render_frame_rgba(animation, when, options);
+        let mut frame   = gif::Frame::from_rgba_speed(width as u16, height as u16, &mut pixels, 10);
+
+        frame.delay     = (to_centiseconds(when + frame_length) - to_centiseconds(when)).min(u16::max_value() as u64) as u16;
+        frame.dispose   = gif::DisposalMethod::Background;
+
+        encoder.write_frame(&frame).map_err(gif_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn centiseconds_round_to_nearest() {
+        assert!(to_centiseconds(Duration::from_millis(33)) == 3);
+        assert!(to_centiseconds(Duration::from_millis(67)) == 7);
+        assert!(to_centiseconds(Duration::from_millis(1005)) == 101);
+    }
+}
@@ -0,0 +1,9 @@
+mod options;
+mod raster_frame;
+mod png_export;
+mod gif_export;
+
+pub use self::options::*;
+pub use self::raster_frame::*;
+pub use self::png_export::*;
+pub use self::gif_export::*;
@@ -0,0 +1,60 @@
+use super::super::frame::*;
+
+use flo_canvas::*;
+use flo_animation::*;
+
+use std::ops::Range;
+use std::time::Duration;
+
+///
+/// Options that control how an animation is exported as a set of bitmap images
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct RasterExportOptions {
+    /// The size of the images to generate in pixels (or None to use the size of the animation)
+    pub size: Option<(usize, usize)>,
+
+    /// The time range to export (or None to export the whole animation)
+    pub time_range: Option<Range<Duration>>,
+
+    /// The colour to use for the background (or None to leave the background transparent)
+    pub background: Option<Color>
+}
+
+impl Default for RasterExportOptions {
+    fn default() -> RasterExportOptions {
+        RasterExportOptions {
+            size:       None,
+            time_range: None,
+            background: None
+        }
+    }
+}
+
+impl RasterExportOptions {
+    ///
+    /// Returns the size of the images that will be generated for an animation
+    ///
+    pub fn output_size(&self, animation: &dyn Animation) -> (usize, usize) {
+        match self.size {
+            Some(size)  => size,
+            None        => {
+                let (width, height) = animation.size();
+                (width.round().max(1.0) as usize, height.round().max(1.0) as usize)
+            }
+        }
+    }
+
+    ///
+    /// Returns the times of the frames that will be exported from an animation
+    ///
+    pub fn frame_times(&self, animation: &dyn Animation) -> Vec<Duration> {
+        let duration    = animation.duration();
+        let range       = match self.time_range {
+            Some(ref range) => range.start.min(duration)..range.end.min(duration),
+            None            => Duration::from_millis(0)..duration
+        };
+
+        frame_times_in_range(animation.frame_length(), range)
+    }
+}
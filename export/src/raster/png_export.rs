@@ -0,0 +1,134 @@
+use super::options::*;
+use super::raster_frame::*;
+
+use flo_animation::*;
+use png;
+
+use std::io;
+use std::fs;
+use std::io::{Write, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+///
+/// Creates a PNG encoder for an 8-bit RGBA image
+///
+fn rgba_encoder<Target: Write>(target: Target, width: usize, height: usize) -> png::Encoder<'static, Target> {
+    let mut encoder = png::Encoder::new(target, width as u32, height as u32);
+
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    encoder
+}
+
+///
+/// Returns the delay for each frame of an animated PNG as a fraction of a second
+///
+fn frame_delay_fraction(frame_length: Duration) -> (u16, u16) {
+    let nanos               = frame_length.as_secs() * 1_000_000_000 + (frame_length.subsec_nanos() as u64);
+    if nanos == 0 { return (0, 1); }
+
+    let frames_per_second   = 1_000_000_000.0 / (nanos as f64);
+
+    if (frames_per_second - frames_per_second.round()).abs() < 0.01 && frames_per_second.round() <= (u16::max_value() as f64) {
+        // Frame rate is a whole number of frames per second, which we can represent exactly
+        (1, frames_per_second.round() as u16)
+    } else {
+        // Round to the nearest millisecond
+        let millis = ((nanos as f64) / 1_000_000.0).round().max(1.0).min(u16::max_value() as f64);
+        (millis as u16, 1000)
+    }
+}
+
+///
+/// Writes a single image to a target as a PNG file
+///
+/// The pixels are supplied as 8-bit RGBA values, with rows ordered from top to bottom
+///
+pub fn write_png<Target: Write>(target: Target, width: usize, height: usize, pixels: &[u8]) -> io::Result<()> {
+    let mut writer = rgba_encoder(target, width, height).write_header()?;
+    writer.write_image_data(pixels)?;
+
+    Ok(())
+}
+
+///
+/// Writes every frame of an animation to a directory as a sequence of PNG files
+///
+/// The files are named `frame-00000.png`, `frame-00001.png`, etc. Returns the paths of the files that were written.
+///
+pub fn export_png_sequence(animation: &dyn Animation, directory: &Path, options: &RasterExportOptions) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(directory)?;
+
+    let (width, height) = options.output_size(animation);
+    let mut written     = vec![];
+
+    for (frame_num, when) in options.frame_times(animation).into_iter().enumerate() {
+        let path    = directory.join(format!("frame-{:05}.png", frame_num));
+        let pixels  = render_frame_rgba(animation, when, options);
+        let file    = BufWriter::new(fs::File::create(&path)?);
+
+        write_png(file, width, height, &pixels)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+///
+/// Writes an animation to a target as an animated PNG file
+///
+/// The animation will loop forever when played back.
+///
+pub fn write_apng<Target: Write>(animation: &dyn Animation, target: Target, options: &RasterExportOptions) -> io::Result<()> {
+    let (width, height) = options.output_size(animation);
+    let frame_times     = options.frame_times(animation);
+
+    if frame_times.len() == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "There are no frames to export"));
+    }
+
+    // Set up the encoder
+    let (delay_num, delay_den)  = frame_delay_fraction(animation.frame_length());
+    let mut encoder             = rgba_encoder(target, width, height);
+
+    encoder.set_animated(frame_times.len() as u32, 0)?;
+    encoder.set_frame_delay(delay_num, delay_den)?;
+
+    // Write the frames
+    let mut writer = encoder.write_header()?;
+
+    for when in frame_times {
+        let pixels = render_frame_rgba(animation, when, options);
+        writer.write_image_data(&pixels)?;
+    }
+
+    writer.finish()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn whole_frames_per_second_delay() {
+        assert!(frame_delay_fraction(Duration::from_nanos(1_000_000_000/30)) == (1, 30));
+        assert!(frame_delay_fraction(Duration::from_millis(40)) == (1, 25));
+    }
+
+    #[test]
+    fn fractional_frames_per_second_delay() {
+        assert!(frame_delay_fraction(Duration::from_millis(70)) == (70, 1000));
+    }
+
+    #[test]
+    fn write_png_signature() {
+        let mut output = vec![];
+        write_png(&mut output, 2, 2, &[255; 16]).unwrap();
+
+        assert!(&output[0..8] == &[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+    }
+}
@@ -0,0 +1,74 @@
+use super::options::*;
+use super::super::frame::*;
+
+use flo_canvas::*;
+use flo_animation::*;
+use flo_canvas_raster::*;
+
+use std::time::Duration;
+
+///
+/// Blends a set of RGBA pixels (without premultiplied alpha) over a background colour
+///
+pub fn apply_background(pixels: &mut [u8], background: &Color) {
+    let (bg_r, bg_g, bg_b, bg_a) = background.to_rgba_components();
+    let background  = [bg_r, bg_g, bg_b];
+    let bg_a        = bg_a.max(0.0).min(1.0);
+
+    for pixel in pixels.chunks_mut(4) {
+        let src_a   = (pixel[3] as f32)/255.0;
+        let out_a   = src_a + bg_a*(1.0-src_a);
+
+        if out_a <= 0.0 {
+            continue;
+        }
+
+        for component in 0..3 {
+            let src             = (pixel[component] as f32)/255.0;
+            let bg              = background[component].max(0.0).min(1.0);
+            let out             = (src*src_a + bg*bg_a*(1.0-src_a)) / out_a;
+
+            pixel[component]    = (out*255.0).round() as u8;
+        }
+
+        pixel[3] = (out_a*255.0).round() as u8;
+    }
+}
+
+///
+/// Renders a frame of an animation to a set of RGBA pixels, using the specified export options
+///
+pub fn render_frame_rgba(animation: &dyn Animation, when: Duration, options: &RasterExportOptions) -> Vec<u8> {
+    let (width, height) = options.output_size(animation);
+    let mut rasterizer  = CanvasRasterizer::new(width, height);
+
+    render_animation_frame(animation, when, &mut rasterizer);
+
+    let mut pixels = rasterizer.to_rgba_bytes();
+    if let Some(ref background) = options.background {
+        apply_background(&mut pixels, background);
+    }
+
+    pixels
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn background_fills_transparent_pixels() {
+        let mut pixels = vec![0, 0, 0, 0, 255, 0, 0, 255];
+        apply_background(&mut pixels, &Color::Rgba(0.0, 0.0, 1.0, 1.0));
+
+        assert!(pixels == vec![0, 0, 255, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn background_blends_with_translucent_pixels() {
+        let mut pixels = vec![255, 255, 255, 128];
+        apply_background(&mut pixels, &Color::Rgba(0.0, 0.0, 0.0, 1.0));
+
+        assert!(pixels == vec![128, 128, 128, 255]);
+    }
+}
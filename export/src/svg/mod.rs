@@ -0,0 +1,5 @@
+mod svg_canvas;
+mod svg_export;
+
+pub use self::svg_canvas::*;
+pub use self::svg_export::*;
@@ -0,0 +1,1010 @@
+use flo_canvas::*;
+
+use std::fmt::Write;
+use std::collections::HashMap;
+
+///
+/// The drawing state for an SVG canvas (this is what's saved by PushState)
+///
+#[derive(Clone)]
+struct SvgState {
+    /// The current transformation matrix
+    transform: Transform2D,
+
+    /// The current path, in SVG path syntax (coordinates have already been transformed to the output coordinate scheme)
+    path: String,
+
+    /// The width of the line used for strokes (before transformation)
+    line_width: f32,
+
+    /// How lines are joined
+    line_join: LineJoin,
+
+    /// How lines are capped
+    line_cap: LineCap,
+
+    /// The current dash pattern (before transformation)
+    dash_pattern: Vec<f32>,
+
+    /// The offset of the current dash pattern
+    dash_offset: f32,
+
+    /// The colour to use for fills
+    fill_color: Color,
+
+    /// The gradient to use for fills, if one has been set (with the ID of its definition and the transform that was active when it was set)
+    fill_gradient: Option<(String, Gradient, Transform2D)>,
+
+    /// The colour to use for strokes
+    stroke_color: Color,
+
+    /// How new elements are blended with the existing layer contents
+    blend_mode: BlendMode,
+
+    /// The font family used for text
+    font_family: String,
+
+    /// The height of the font used for text (before transformation)
+    font_size: f32,
+
+    /// How text is aligned relative to where it's drawn
+    text_alignment: TextAlignment,
+
+    /// The ID of the clipping path definition, if there's a clipping path
+    clip: Option<String>
+}
+
+///
+/// A layer in an SVG canvas
+///
+struct SvgLayer {
+    /// The SVG elements that make up this layer
+    content: String,
+
+    /// How this layer is blended with the layers underneath it
+    blend_mode: BlendMode,
+
+    /// The opacity of this layer
+    alpha: f32,
+
+    /// The content saved by the last 'Store' operation
+    stored: Option<String>
+}
+
+///
+/// A graphics context that generates an SVG document from the drawing instructions sent to it
+///
+/// The coordinate scheme matches the other canvas implementations: before any transformations are
+/// applied, (0,0) is the center of the canvas and the top of the canvas is at y=1.
+///
+pub struct SvgCanvas {
+    /// The width of the document in pixels
+    width: f32,
+
+    /// The height of the document in pixels
+    height: f32,
+
+    /// Prefix added to the IDs of any definitions
+    id_prefix: String,
+
+    /// The ID to assign to the next definition
+    next_id: usize,
+
+    /// The transformation used by IdentityTransform
+    initial_transform: Transform2D,
+
+    /// The current drawing state
+    state: SvgState,
+
+    /// States stored by PushState
+    saved_states: Vec<SvgState>,
+
+    /// The definitions section of the document (clipping paths, masks, etc)
+    defs: String,
+
+    /// The layers in this canvas
+    layers: HashMap<u32, SvgLayer>,
+
+    /// The currently selected layer
+    current_layer: u32
+}
+
+impl SvgLayer {
+    ///
+    /// Creates a new empty layer
+    ///
+    fn new() -> SvgLayer {
+        SvgLayer {
+            content:    String::new(),
+            blend_mode: BlendMode::SourceOver,
+            alpha:      1.0,
+            stored:     None
+        }
+    }
+}
+
+impl SvgState {
+    ///
+    /// Creates the default drawing state
+    ///
+    fn new(initial_transform: Transform2D) -> SvgState {
+        SvgState {
+            transform:      initial_transform,
+            path:           String::new(),
+            line_width:     1.0,
+            line_join:      LineJoin::Round,
+            line_cap:       LineCap::Butt,
+            dash_pattern:   vec![],
+            dash_offset:    0.0,
+            fill_color:     Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill_gradient:  None,
+            stroke_color:   Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            blend_mode:     BlendMode::SourceOver,
+            font_family:    "sans-serif".to_string(),
+            font_size:      12.0,
+            text_alignment: TextAlignment::Left,
+            clip:           None
+        }
+    }
+}
+
+///
+/// Formats a number for an SVG document
+///
+fn num(value: f32) -> String {
+    let rounded = (value * 1000.0).round() / 1000.0;
+
+    if rounded == 0.0 {
+        // Avoid '-0'
+        "0".to_string()
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+///
+/// Converts a colour to an SVG colour and opacity
+///
+fn svg_color(color: &Color) -> (String, f32) {
+    let (r, g, b, a)    = color.to_rgba_components();
+    let component       = |c: f32| (c.max(0.0).min(1.0) * 255.0).round() as u32;
+
+    (format!("#{:02x}{:02x}{:02x}", component(r), component(g), component(b)), a.max(0.0).min(1.0))
+}
+
+///
+/// Escapes a string so it can be used as XML text or in an attribute
+///
+fn xml_escape(text: &str) -> String {
+    let mut result = String::new();
+
+    for chr in text.chars() {
+        match chr {
+            '&'     => result.push_str("&amp;"),
+            '<'     => result.push_str("&lt;"),
+            '>'     => result.push_str("&gt;"),
+            '"'     => result.push_str("&quot;"),
+            '\''    => result.push_str("&apos;"),
+            other   => result.push(other)
+        }
+    }
+
+    result
+}
+
+///
+/// Encodes some data as base64 (for use in data URLs)
+///
+fn base64(data: &[u8]) -> String {
+    const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::new();
+
+    for chunk in data.chunks(3) {
+        let group = ((chunk[0] as usize) << 16) | ((chunk.get(1).cloned().unwrap_or(0) as usize) << 8) | (chunk.get(2).cloned().unwrap_or(0) as usize);
+
+        result.push(BASE64_CHARS[(group >> 18) & 0x3f] as char);
+        result.push(BASE64_CHARS[(group >> 12) & 0x3f] as char);
+        result.push(if chunk.len() > 1 { BASE64_CHARS[(group >> 6) & 0x3f] as char } else { '=' });
+        result.push(if chunk.len() > 2 { BASE64_CHARS[group & 0x3f] as char } else { '=' });
+    }
+
+    result
+}
+
+///
+/// Returns the CSS mix-blend-mode equivalent to a blend mode, if there is one
+///
+fn css_blend_mode(blend_mode: BlendMode) -> Option<&'static str> {
+    match blend_mode {
+        BlendMode::Multiply => Some("multiply"),
+        BlendMode::Screen   => Some("screen"),
+        BlendMode::Darken   => Some("darken"),
+        BlendMode::Lighten  => Some("lighten"),
+        _                   => None
+    }
+}
+
+impl SvgCanvas {
+    ///
+    /// Creates a new SVG canvas with the specified size in pixels
+    ///
+    pub fn new(width: f32, height: f32) -> SvgCanvas {
+        Self::with_id_prefix(width, height, "flo-")
+    }
+
+    ///
+    /// Creates a new SVG canvas that prefixes the IDs of its definitions with a particular string
+    ///
+    /// (This is useful when combining the output of several canvases into one document)
+    ///
+    pub fn with_id_prefix(width: f32, height: f32, id_prefix: &str) -> SvgCanvas {
+        let initial_transform = Transform2D::translate(width/2.0, height/2.0) * Transform2D::scale(height/2.0, -height/2.0);
+
+        SvgCanvas {
+            width:              width,
+            height:             height,
+            id_prefix:          id_prefix.to_string(),
+            next_id:            0,
+            initial_transform:  initial_transform,
+            state:              SvgState::new(initial_transform),
+            saved_states:       vec![],
+            defs:               String::new(),
+            layers:             HashMap::new(),
+            current_layer:      0
+        }
+    }
+
+    ///
+    /// Returns the definitions and the layer content generated for this canvas
+    ///
+    /// This is suitable for embedding in another SVG document
+    ///
+    pub fn to_svg_fragment(&self) -> String {
+        let mut defs    = self.defs.clone();
+        let mut content = String::new();
+
+        // Sort the layers by ID
+        let mut layer_ids = self.layers.keys().cloned().collect::<Vec<_>>();
+        layer_ids.sort();
+
+        // Composite the layers in order
+        let mut filter_id = None;
+
+        for layer_id in layer_ids {
+            let layer = &self.layers[&layer_id];
+            if layer.content.len() == 0 { continue; }
+
+            let layer_content = if layer.alpha < 1.0 {
+                format!("<g style=\"isolation:isolate;opacity:{}\">{}</g>", num(layer.alpha.max(0.0)), layer.content)
+            } else {
+                format!("<g style=\"isolation:isolate\">{}</g>", layer.content)
+            };
+
+            match layer.blend_mode {
+                BlendMode::DestinationOut | BlendMode::DestinationIn => {
+                    // Need a filter that turns the layer into a solid colour with the same alpha values
+                    let filter = filter_id.get_or_insert_with(|| {
+                        let id = format!("{}layer-alpha", self.id_prefix);
+                        write!(defs, "<filter id=\"{}\"><feColorMatrix type=\"matrix\" values=\"0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0\"/></filter>", id).unwrap();
+                        id
+                    });
+
+                    // Mask out the existing content using the layer
+                    let mask_id = format!("{}layer-mask-{}", self.id_prefix, layer_id);
+                    if layer.blend_mode == BlendMode::DestinationOut {
+                        // Black (alpha-only) layer over a white background
+                        write!(defs, "<mask id=\"{}\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"{}\" height=\"{}\"><rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/><g filter=\"url(#{})\">{}</g></mask>",
+                            mask_id, num(self.width), num(self.height), num(self.width), num(self.height), filter, layer_content).unwrap();
+                    } else {
+                        // Inverted (white) layer over a black background
+                        write!(defs, "<mask id=\"{}\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"{}\" height=\"{}\"><rect width=\"{}\" height=\"{}\" fill=\"#000000\"/><g filter=\"url(#{})\" style=\"filter:invert(100%)\">{}</g></mask>",
+                            mask_id, num(self.width), num(self.height), num(self.width), num(self.height), filter, layer_content).unwrap();
+                    }
+
+                    content = format!("<g mask=\"url(#{})\">{}</g>", mask_id, content);
+                },
+
+                other => {
+                    if let Some(css_mode) = css_blend_mode(other) {
+                        write!(content, "<g style=\"mix-blend-mode:{}\">{}</g>", css_mode, layer_content).unwrap();
+                    } else {
+                        // Porter-Duff modes other than DestinationOut/In aren't supported for layers: treat as SourceOver
+                        content.push_str(&layer_content);
+                    }
+                }
+            }
+        }
+
+        // Generate the fragment
+        if defs.len() > 0 {
+            format!("<defs>{}</defs>{}", defs, content)
+        } else {
+            content
+        }
+    }
+
+    ///
+    /// Returns the SVG document generated by the drawing instructions sent to this canvas
+    ///
+    pub fn to_svg(&self) -> String {
+        format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"1.1\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">{}</svg>\n",
+            num(self.width), num(self.height), num(self.width), num(self.height), self.to_svg_fragment())
+    }
+
+    ///
+    /// Assigns a new ID for a definition
+    ///
+    fn new_id(&mut self, kind: &str) -> String {
+        let id = format!("{}{}-{}", self.id_prefix, kind, self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    ///
+    /// Retrieves the layer that's currently being drawn on
+    ///
+    fn current_layer_mut(&mut self) -> &mut SvgLayer {
+        self.layers.entry(self.current_layer)
+            .or_insert_with(|| SvgLayer::new())
+    }
+
+    ///
+    /// Adds a point to the current path
+    ///
+    fn add_point(&mut self, command: &str, points: &[(f32, f32)]) {
+        let transform = self.state.transform;
+
+        self.state.path.push_str(command);
+        for (x, y) in points.iter() {
+            let (x, y) = transform.transform_point(*x, *y);
+            write!(self.state.path, "{} {} ", num(x), num(y)).unwrap();
+        }
+    }
+
+    ///
+    /// Generates the attributes describing the clipping path for a new element
+    ///
+    fn clip_attribute(&self) -> String {
+        match self.state.clip {
+            Some(ref clip)  => format!(" clip-path=\"url(#{})\"", clip),
+            None            => String::new()
+        }
+    }
+
+    ///
+    /// Creates a path element that fills the current path
+    ///
+    fn fill_element(&self, color: &Color) -> String {
+        let (color, opacity) = svg_color(color);
+
+        format!("<path d=\"{}\" fill=\"{}\" fill-opacity=\"{}\"{}/>", self.state.path.trim(), color, num(opacity), self.clip_attribute())
+    }
+
+    ///
+    /// Creates a path element that fills the current path with a gradient definition
+    ///
+    fn gradient_fill_element(&self, gradient_id: &str) -> String {
+        format!("<path d=\"{}\" fill=\"url(#{})\"{}/>", self.state.path.trim(), gradient_id, self.clip_attribute())
+    }
+
+    ///
+    /// Adds a definition for a gradient using the specified transform, and returns its ID
+    ///
+    fn define_gradient(&mut self, gradient: &Gradient, transform: Transform2D) -> String {
+        let gradient_id             = self.new_id("gradient");
+        let Transform2D(a, b, _c)   = transform;
+        let matrix                  = format!("matrix({} {} {} {} {} {})", num(a.0), num(b.0), num(a.1), num(b.1), num(a.2), num(b.2));
+
+        match gradient {
+            Gradient::Linear((x1, y1), (x2, y2), _)   => write!(self.defs, "<linearGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" gradientTransform=\"{}\" x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\">",
+                gradient_id, matrix, num(*x1), num(*y1), num(*x2), num(*y2)).unwrap(),
+            Gradient::Radial((x, y), radius, _)     => write!(self.defs, "<radialGradient id=\"{}\" gradientUnits=\"userSpaceOnUse\" gradientTransform=\"{}\" cx=\"{}\" cy=\"{}\" r=\"{}\">",
+                gradient_id, matrix, num(*x), num(*y), num(*radius)).unwrap()
+        }
+
+        for stop in gradient.stops().iter() {
+            let (color, opacity) = svg_color(&stop.color);
+            write!(self.defs, "<stop offset=\"{}\" stop-color=\"{}\" stop-opacity=\"{}\"/>", num(stop.offset.max(0.0).min(1.0)), color, num(opacity)).unwrap();
+        }
+
+        match gradient {
+            Gradient::Linear(_, _, _)   => self.defs.push_str("</linearGradient>"),
+            Gradient::Radial(_, _, _)   => self.defs.push_str("</radialGradient>")
+        }
+
+        gradient_id
+    }
+
+    ///
+    /// Adds a definition for the gradient used to generate a mask for a gradient fill, and returns its ID
+    ///
+    /// Mask opacity is determined by luminance, so the stops are black for DestinationOut and white for
+    /// DestinationIn, with the opacity of the original stops.
+    ///
+    fn define_mask_gradient(&mut self, gradient: &Gradient, transform: Transform2D) -> String {
+        let mask_value  = if self.state.blend_mode == BlendMode::DestinationOut { 0.0 } else { 1.0 };
+        let stops       = gradient.stops().iter()
+            .map(|stop| GradientStop::new(stop.offset, Color::Rgba(mask_value, mask_value, mask_value, stop.color.to_rgba_components().3)))
+            .collect();
+
+        let mask_gradient = match gradient {
+            Gradient::Linear(start, end, _)     => Gradient::Linear(*start, *end, stops),
+            Gradient::Radial(center, radius, _) => Gradient::Radial(*center, *radius, stops)
+        };
+
+        self.define_gradient(&mask_gradient, transform)
+    }
+
+    ///
+    /// Creates a path element that strokes the current path
+    ///
+    fn stroke_element(&self, color: &Color) -> String {
+        let (color, opacity)    = svg_color(color);
+        let scale               = self.state.transform.scale_factor();
+
+        let join = match self.state.line_join {
+            LineJoin::Miter => "miter",
+            LineJoin::Round => "round",
+            LineJoin::Bevel => "bevel"
+        };
+        let cap = match self.state.line_cap {
+            LineCap::Butt   => "butt",
+            LineCap::Round  => "round",
+            LineCap::Square => "square"
+        };
+
+        let mut element = format!("<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-opacity=\"{}\" stroke-width=\"{}\" stroke-linejoin=\"{}\" stroke-linecap=\"{}\"",
+            self.state.path.trim(), color, num(opacity), num(self.state.line_width * scale), join, cap);
+
+        if self.state.dash_pattern.len() > 0 {
+            let dashes = self.state.dash_pattern.iter().map(|dash| num(dash * scale)).collect::<Vec<_>>();
+            write!(element, " stroke-dasharray=\"{}\" stroke-dashoffset=\"{}\"", dashes.join(" "), num(self.state.dash_offset * scale)).unwrap();
+        }
+
+        element.push_str(&self.clip_attribute());
+        element.push_str("/>");
+        element
+    }
+
+    ///
+    /// Creates a text element that fills some text at the specified position
+    ///
+    fn text_element(&self, color: &Color, text: &str, x: f32, y: f32) -> String {
+        let (color, opacity) = svg_color(color);
+
+        // Canvas coordinates have y pointing upwards, so the text needs to be flipped to appear upright
+        let Transform2D(a, b, _c) = self.state.transform * Transform2D::translate(x, y) * Transform2D::scale(1.0, -1.0);
+
+        let anchor = match self.state.text_alignment {
+            TextAlignment::Left     => "start",
+            TextAlignment::Center   => "middle",
+            TextAlignment::Right    => "end"
+        };
+
+        format!("<text transform=\"matrix({} {} {} {} {} {})\" font-family=\"{}\" font-size=\"{}\" text-anchor=\"{}\" fill=\"{}\" fill-opacity=\"{}\"{}>{}</text>",
+            num(a.0), num(b.0), num(a.1), num(b.1), num(a.2), num(b.2),
+            xml_escape(&self.state.font_family), num(self.state.font_size), anchor, color, num(opacity), self.clip_attribute(), xml_escape(text))
+    }
+
+    ///
+    /// Generates an image element
+    ///
+    fn image_element(&self, image: &CanvasImage, transform: Transform2D) -> String {
+        // The image is drawn in the unit square, flipped so that its bottom-left corner is at the origin
+        let Transform2D(a, b, _c) = self.state.transform * transform * Transform2D::translate(0.0, 1.0) * Transform2D::scale(1.0, -1.0);
+
+        format!("<image transform=\"matrix({} {} {} {} {} {})\" width=\"1\" height=\"1\" preserveAspectRatio=\"none\" xlink:href=\"data:{};base64,{}\"{}/>",
+            num(a.0), num(b.0), num(a.1), num(b.1), num(a.2), num(b.2),
+            image.format.mime_type(), base64(&image.data), self.clip_attribute())
+    }
+
+    ///
+    /// Adds an element to the current layer using the current blend mode
+    ///
+    /// The element is generated by a function that takes the colour to use: Porter-Duff blend modes
+    /// are implemented using masks, which need the element to be generated in a different colour.
+    ///
+    fn add_element<MakeElement: Fn(&SvgCanvas, &Color) -> String>(&mut self, color: Color, make_element: MakeElement) {
+        let blend_mode = self.state.blend_mode;
+
+        match blend_mode {
+            BlendMode::DestinationOut | BlendMode::DestinationIn => {
+                // Mask out the existing layer content using this element (mask opacity is determined by luminance)
+                let alpha                   = color.to_rgba_components().3;
+                let (background, mask_color) = if blend_mode == BlendMode::DestinationOut {
+                    ("#ffffff", Color::Rgba(0.0, 0.0, 0.0, alpha))
+                } else {
+                    ("#000000", Color::Rgba(1.0, 1.0, 1.0, alpha))
+                };
+
+                let mask_id     = self.new_id("mask");
+                let element     = make_element(self, &mask_color);
+                let (w, h)      = (num(self.width), num(self.height));
+
+                write!(self.defs, "<mask id=\"{}\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"{}\" height=\"{}\"><rect width=\"{}\" height=\"{}\" fill=\"{}\"/>{}</mask>",
+                    mask_id, w, h, w, h, background, element).unwrap();
+
+                let layer       = self.current_layer_mut();
+                layer.content   = format!("<g mask=\"url(#{})\">{}</g>", mask_id, layer.content);
+            },
+
+            BlendMode::DestinationOver => {
+                // New element goes underneath the existing content
+                let element     = make_element(self, &color);
+                let layer       = self.current_layer_mut();
+                layer.content   = format!("{}{}", element, layer.content);
+            },
+
+            other => {
+                let element = make_element(self, &color);
+
+                // SourceIn, SourceOut, SourceAtop and DestinationAtop have no SVG equivalent and are treated as SourceOver
+                let element = match css_blend_mode(other) {
+                    Some(css_mode)  => format!("<g style=\"mix-blend-mode:{}\">{}</g>", css_mode, element),
+                    None            => element
+                };
+
+                self.current_layer_mut().content.push_str(&element);
+            }
+        }
+    }
+
+    ///
+    /// Computes the transformation to apply for a particular canvas height
+    ///
+    fn height_transform(height: f32) -> Transform2D {
+        let mut ratio_x = 2.0/height;
+        let ratio_y     = ratio_x;
+
+        if height < 0.0 {
+            ratio_x = -ratio_x;
+        }
+
+        Transform2D::scale(ratio_x, ratio_y)
+    }
+
+    ///
+    /// Computes a transformation that moves a particular region to the center of the canvas
+    ///
+    fn center_transform(&self, minx: f32, miny: f32, maxx: f32, maxy: f32) -> Transform2D {
+        let Transform2D(a, b, _c) = self.state.transform;
+
+        // Get the current scaling of this canvas
+        let mut xscale = (a.0*a.0 + b.0*b.0).sqrt();
+        let mut yscale = (a.1*a.1 + b.1*b.1).sqrt();
+        if xscale == 0.0 { xscale = 1.0; }
+        if yscale == 0.0 { yscale = 1.0; }
+
+        // Current X, Y coordinates (centered)
+        let cur_x = (a.2-(self.width/2.0))/xscale;
+        let cur_y = (b.2-(self.height/2.0))/yscale;
+
+        // New center coordinates
+        let center_x = (minx+maxx)/2.0;
+        let center_y = (miny+maxy)/2.0;
+
+        // Compute the offsets and transform the canvas
+        Transform2D::translate(cur_x - center_x, cur_y - center_y)
+    }
+
+    ///
+    /// Clears all of the layers and resets the state of this canvas
+    ///
+    fn clear_all(&mut self) {
+        self.state          = SvgState::new(self.initial_transform);
+        self.saved_states   = vec![];
+        self.defs           = String::new();
+        self.layers         = HashMap::new();
+        self.current_layer  = 0;
+    }
+
+    ///
+    /// Performs a drawing instruction on this canvas
+    ///
+    fn draw_instruction(&mut self, drawing: Draw) {
+        use self::Draw::*;
+
+        match drawing {
+            NewPath                                     => { self.state.path = String::new(); },
+            Move(x, y)                                  => { self.add_point("M", &[(x, y)]); },
+            Line(x, y)                                  => { self.add_point("L", &[(x, y)]); },
+            BezierCurve((x, y), (cx1, cy1), (cx2, cy2)) => { self.add_point("C", &[(cx1, cy1), (cx2, cy2), (x, y)]); },
+            ClosePath                                   => { self.state.path.push_str("Z "); },
+            Fill                                        => {
+                let color       = self.state.fill_color;
+                let gradient    = self.state.fill_gradient.clone();
+
+                let is_mask     = match self.state.blend_mode {
+                    ::flo_canvas::BlendMode::DestinationOut | ::flo_canvas::BlendMode::DestinationIn    => true,
+                    _                                                                                   => false
+                };
+
+                match (is_mask, gradient) {
+                    (_, None)                                   => { self.add_element(color, |canvas, color| canvas.fill_element(color)); },
+                    (false, Some((gradient_id, _, _)))          => { self.add_element(color, move |canvas, _color| canvas.gradient_fill_element(&gradient_id)); },
+
+                    (true, Some((_, gradient, transform)))      => {
+                        // Masks use a gradient with the same opacity as the fill gradient, in the colour of the mask
+                        let mask_gradient_id = self.define_mask_gradient(&gradient, transform);
+                        self.add_element(color, move |canvas, _color| canvas.gradient_fill_element(&mask_gradient_id));
+                    }
+                }
+            },
+            Stroke                                      => { let color = self.state.stroke_color; self.add_element(color, |canvas, color| canvas.stroke_element(color)); },
+            LineWidth(width)                            => { self.state.line_width = width; },
+            LineWidthPixels(pixels)                     => { self.state.line_width = pixels / self.state.transform.scale_factor(); },
+            LineJoin(join)                              => { self.state.line_join = join; },
+            LineCap(cap)                                => { self.state.line_cap = cap; },
+            NewDashPattern                              => { self.state.dash_pattern = vec![]; },
+            DashLength(length)                          => { self.state.dash_pattern.push(length); },
+            DashOffset(offset)                          => { self.state.dash_offset = offset; },
+            FillColor(color)                            => { self.state.fill_color = color; self.state.fill_gradient = None; },
+
+            FillGradient(gradient)                      => {
+                let transform               = self.state.transform;
+                let gradient_id             = self.define_gradient(&gradient, transform);
+
+                self.state.fill_color       = gradient.average_color();
+                self.state.fill_gradient    = Some((gradient_id, gradient, transform));
+            },
+
+            StrokeColor(color)                          => { self.state.stroke_color = color; },
+            BlendMode(blend_mode)                       => { self.state.blend_mode = blend_mode; },
+            IdentityTransform                           => { self.state.transform = self.initial_transform; },
+            CanvasHeight(height)                        => { self.state.transform = self.initial_transform * Self::height_transform(height); },
+            CenterRegion((minx, miny), (maxx, maxy))    => { self.state.transform = self.state.transform * self.center_transform(minx, miny, maxx, maxy); },
+            MultiplyTransform(transform)                => { self.state.transform = self.state.transform * transform; },
+            Unclip                                      => { self.state.clip = None; },
+
+            Clip                                        => {
+                // Clipping paths are intersected with the existing clipping path
+                let clip_id     = self.new_id("clip");
+                let parent_clip = self.clip_attribute();
+
+                write!(self.defs, "<clipPath id=\"{}\" clipPathUnits=\"userSpaceOnUse\"{}><path d=\"{}\"/></clipPath>", clip_id, parent_clip, self.state.path.trim()).unwrap();
+
+                self.state.clip = Some(clip_id);
+                self.state.path = String::new();
+            },
+
+            Store                                       => {
+                let layer       = self.current_layer_mut();
+                layer.stored    = Some(layer.content.clone());
+            },
+
+            Restore                                     => {
+                let clip = self.state.clip.clone();
+
+                match clip {
+                    None        => {
+                        // Restore the whole layer
+                        let layer = self.current_layer_mut();
+                        if let Some(ref stored) = layer.stored {
+                            layer.content = stored.clone();
+                        }
+                    },
+
+                    Some(clip)  => {
+                        // Restore only the region inside the clipping path
+                        if self.current_layer_mut().stored.is_some() {
+                            let mask_id = self.new_id("mask");
+                            let (w, h)  = (num(self.width), num(self.height));
+
+                            write!(self.defs, "<mask id=\"{}\" maskUnits=\"userSpaceOnUse\" x=\"0\" y=\"0\" width=\"{}\" height=\"{}\"><rect width=\"{}\" height=\"{}\" fill=\"#ffffff\"/><rect width=\"{}\" height=\"{}\" fill=\"#000000\" clip-path=\"url(#{})\"/></mask>",
+                                mask_id, w, h, w, h, w, h, clip).unwrap();
+
+                            let layer       = self.current_layer_mut();
+                            let stored      = layer.stored.clone().unwrap();
+                            layer.content   = format!("<g mask=\"url(#{})\">{}</g><g clip-path=\"url(#{})\">{}</g>", mask_id, layer.content, clip, stored);
+                        }
+                    }
+                }
+            },
+
+            FreeStoredBuffer                            => { self.current_layer_mut().stored = None; },
+            PushState                                   => { self.saved_states.push(self.state.clone()); },
+            PopState                                    => { if let Some(state) = self.saved_states.pop() { self.state = state; } },
+            ClearCanvas                                 => { self.clear_all(); },
+            Layer(layer_id)                             => { self.current_layer = layer_id; self.current_layer_mut(); },
+            LayerBlend(layer_id, blend_mode)            => { self.layers.entry(layer_id).or_insert_with(|| SvgLayer::new()).blend_mode = blend_mode; },
+            LayerAlpha(layer_id, alpha)                 => { self.layers.entry(layer_id).or_insert_with(|| SvgLayer::new()).alpha = alpha; },
+            ClearLayer                                  => { let layer = self.current_layer_mut(); layer.content = String::new(); },
+            FontFamily(family)                          => { self.state.font_family = family; },
+            FontSize(size)                              => { self.state.font_size = size; },
+            TextAlignment(alignment)                    => { self.state.text_alignment = alignment; },
+            FillText(text, x, y)                        => { let color = self.state.fill_color; self.add_element(color, move |canvas, color| canvas.text_element(color, &text, x, y)); },
+            DrawImage(image, transform)                 => { self.add_element(Color::Rgba(1.0, 1.0, 1.0, 1.0), move |canvas, _color| canvas.image_element(&image, transform)); }
+        }
+    }
+}
+
+impl GraphicsContext for SvgCanvas {
+    fn new_path(&mut self)                                                      { self.draw(Draw::NewPath); }
+    fn move_to(&mut self, x: f32, y: f32)                                       { self.draw(Draw::Move(x, y)); }
+    fn line_to(&mut self, x: f32, y: f32)                                       { self.draw(Draw::Line(x, y)); }
+    fn bezier_curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32) { self.draw(Draw::BezierCurve((x1, y1), (x2, y2), (x3, y3))); }
+    fn close_path(&mut self)                                                    { self.draw(Draw::ClosePath); }
+    fn fill(&mut self)                                                          { self.draw(Draw::Fill); }
+    fn stroke(&mut self)                                                        { self.draw(Draw::Stroke); }
+    fn line_width(&mut self, width: f32)                                        { self.draw(Draw::LineWidth(width)); }
+    fn line_width_pixels(&mut self, width: f32)                                 { self.draw(Draw::LineWidthPixels(width)); }
+    fn line_join(&mut self, join: LineJoin)                                     { self.draw(Draw::LineJoin(join)); }
+    fn line_cap(&mut self, cap: LineCap)                                        { self.draw(Draw::LineCap(cap)); }
+    fn new_dash_pattern(&mut self)                                              { self.draw(Draw::NewDashPattern); }
+    fn dash_length(&mut self, length: f32)                                      { self.draw(Draw::DashLength(length)); }
+    fn dash_offset(&mut self, offset: f32)                                      { self.draw(Draw::DashOffset(offset)); }
+    fn fill_color(&mut self, col: Color)                                        { self.draw(Draw::FillColor(col)); }
+    fn fill_gradient(&mut self, gradient: &Gradient)                            { self.draw(Draw::FillGradient(gradient.clone())); }
+    fn stroke_color(&mut self, col: Color)                                      { self.draw(Draw::StrokeColor(col)); }
+    fn blend_mode(&mut self, mode: BlendMode)                                   { self.draw(Draw::BlendMode(mode)); }
+    fn identity_transform(&mut self)                                            { self.draw(Draw::IdentityTransform); }
+    fn canvas_height(&mut self, height: f32)                                    { self.draw(Draw::CanvasHeight(height)); }
+    fn center_region(&mut self, minx: f32, miny: f32, maxx: f32, maxy: f32)     { self.draw(Draw::CenterRegion((minx, miny), (maxx, maxy))); }
+    fn transform(&mut self, transform: Transform2D)                             { self.draw(Draw::MultiplyTransform(transform)); }
+    fn unclip(&mut self)                                                        { self.draw(Draw::Unclip); }
+    fn clip(&mut self)                                                          { self.draw(Draw::Clip); }
+    fn store(&mut self)                                                         { self.draw(Draw::Store); }
+    fn restore(&mut self)                                                       { self.draw(Draw::Restore); }
+    fn free_stored_buffer(&mut self)                                            { self.draw(Draw::FreeStoredBuffer); }
+    fn push_state(&mut self)                                                    { self.draw(Draw::PushState); }
+    fn pop_state(&mut self)                                                     { self.draw(Draw::PopState); }
+    fn clear_canvas(&mut self)                                                  { self.draw(Draw::ClearCanvas); }
+    fn layer(&mut self, layer_id: u32)                                          { self.draw(Draw::Layer(layer_id)); }
+    fn layer_blend(&mut self, layer_id: u32, blend_mode: BlendMode)             { self.draw(Draw::LayerBlend(layer_id, blend_mode)); }
+    fn layer_alpha(&mut self, layer_id: u32, alpha: f32)                        { self.draw(Draw::LayerAlpha(layer_id, alpha)); }
+    fn clear_layer(&mut self)                                                   { self.draw(Draw::ClearLayer); }
+    fn font_family(&mut self, family: &str)                                     { self.draw(Draw::FontFamily(family.to_string())); }
+    fn font_size(&mut self, size: f32)                                          { self.draw(Draw::FontSize(size)); }
+    fn text_alignment(&mut self, alignment: TextAlignment)                      { self.draw(Draw::TextAlignment(alignment)); }
+    fn fill_text(&mut self, text: &str, x: f32, y: f32)                         { self.draw(Draw::FillText(text.to_string(), x, y)); }
+    fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D)       { self.draw(Draw::DrawImage(image.clone(), transform)); }
+
+    fn draw(&mut self, d: Draw) {
+        self.draw_instruction(d);
+    }
+}
+
+impl GraphicsPrimitives for SvgCanvas {
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::*;
+
+    #[test]
+    fn empty_document() {
+        let canvas  = SvgCanvas::new(800.0, 600.0);
+        let svg     = canvas.to_svg();
+
+        assert!(svg.contains("width=\"800\" height=\"600\""));
+        assert!(!svg.contains("<path"));
+    }
+
+    #[test]
+    fn fill_rectangle() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.canvas_height(100.0);
+        canvas.center_region(0.0, 0.0, 100.0, 100.0);
+        canvas.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        canvas.new_path();
+        canvas.rect(10.0, 10.0, 20.0, 20.0);
+        canvas.fill();
+
+        let svg = canvas.to_svg();
+
+        // y axis is flipped in SVG coordinates
+        assert!(svg.contains("<path d=\"M10 90 L10 80 L20 80 L20 90 L10 90 Z\" fill=\"#ff0000\" fill-opacity=\"1\"/>"));
+    }
+
+    #[test]
+    fn fill_linear_gradient() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.canvas_height(100.0);
+        canvas.center_region(0.0, 0.0, 100.0, 100.0);
+        canvas.fill_gradient(&Gradient::Linear((10.0, 10.0), (30.0, 10.0), vec![
+            GradientStop::new(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(0.0, 0.0, 1.0, 0.5))
+        ]));
+        canvas.new_path();
+        canvas.rect(10.0, 10.0, 20.0, 20.0);
+        canvas.fill();
+
+        let svg = canvas.to_svg();
+
+        assert!(svg.contains("<linearGradient id=\"flo-gradient-0\" gradientUnits=\"userSpaceOnUse\" gradientTransform=\"matrix(1 0 0 -1 0 100)\" x1=\"10\" y1=\"10\" x2=\"30\" y2=\"10\"><stop offset=\"0\" stop-color=\"#ff0000\" stop-opacity=\"1\"/><stop offset=\"1\" stop-color=\"#0000ff\" stop-opacity=\"0.5\"/></linearGradient>"));
+        assert!(svg.contains("<path d=\"M10 90 L10 80 L20 80 L20 90 L10 90 Z\" fill=\"url(#flo-gradient-0)\"/>"));
+    }
+
+    #[test]
+    fn fill_text() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.canvas_height(100.0);
+        canvas.center_region(0.0, 0.0, 100.0, 100.0);
+        canvas.font_family("serif");
+        canvas.font_size(10.0);
+        canvas.text_alignment(TextAlignment::Center);
+        canvas.fill_text("A & B", 50.0, 20.0);
+
+        let svg = canvas.to_svg();
+
+        // Text is flipped so it's upright in SVG coordinates
+        assert!(svg.contains("<text transform=\"matrix(1 0 0 1 50 80)\" font-family=\"serif\" font-size=\"10\" text-anchor=\"middle\" fill=\"#000000\" fill-opacity=\"1\">A &amp; B</text>"));
+    }
+
+    #[test]
+    fn draw_image() {
+        let mut canvas  = SvgCanvas::new(100.0, 100.0);
+        let image       = CanvasImage { format: ImageFormat::Png, width: 3, height: 4, data: Arc::new(b"Man".to_vec()) };
+
+        canvas.canvas_height(100.0);
+        canvas.center_region(0.0, 0.0, 100.0, 100.0);
+        canvas.draw_image(&image, Transform2D::translate(10.0, 20.0) * Transform2D::scale(30.0, 40.0));
+
+        let svg = canvas.to_svg();
+
+        // Image is flipped so it's upright in SVG coordinates
+        assert!(svg.contains("<image transform=\"matrix(30 0 0 40 10 40)\" width=\"1\" height=\"1\" preserveAspectRatio=\"none\" xlink:href=\"data:image/png;base64,TWFu\"/>"));
+    }
+
+    #[test]
+    fn stroke_width_is_scaled() {
+        let mut canvas = SvgCanvas::new(200.0, 200.0);
+
+        canvas.canvas_height(100.0);
+        canvas.line_width(3.0);
+        canvas.new_path();
+        canvas.move_to(0.0, 0.0);
+        canvas.line_to(10.0, 0.0);
+        canvas.stroke();
+
+        let svg = canvas.to_svg();
+
+        assert!(svg.contains("stroke-width=\"6\""));
+        assert!(svg.contains("d=\"M100 100 L120 100\""));
+    }
+
+    #[test]
+    fn line_width_pixels_is_unscaled() {
+        let mut canvas = SvgCanvas::new(200.0, 200.0);
+
+        canvas.canvas_height(100.0);
+        canvas.line_width_pixels(1.0);
+        canvas.new_path();
+        canvas.move_to(0.0, 0.0);
+        canvas.line_to(10.0, 0.0);
+        canvas.stroke();
+
+        assert!(canvas.to_svg().contains("stroke-width=\"1\""));
+    }
+
+    #[test]
+    fn dash_pattern_is_written() {
+        let mut canvas = SvgCanvas::new(200.0, 200.0);
+
+        canvas.canvas_height(200.0);
+        canvas.new_dash_pattern();
+        canvas.dash_length(4.0);
+        canvas.dash_length(2.0);
+        canvas.new_path();
+        canvas.move_to(0.0, 0.0);
+        canvas.line_to(10.0, 0.0);
+        canvas.stroke();
+
+        assert!(canvas.to_svg().contains("stroke-dasharray=\"4 2\""));
+    }
+
+    #[test]
+    fn erase_uses_mask() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.new_path();
+        canvas.rect(-0.5, -0.5, 0.5, 0.5);
+        canvas.fill();
+
+        canvas.blend_mode(BlendMode::DestinationOut);
+        canvas.new_path();
+        canvas.rect(-0.1, -0.1, 0.1, 0.1);
+        canvas.fill();
+
+        let svg = canvas.to_svg();
+
+        assert!(svg.contains("<mask id=\"flo-mask-0\""));
+        assert!(svg.contains("<g mask=\"url(#flo-mask-0)\"><path"));
+    }
+
+    #[test]
+    fn erase_with_gradient_uses_mask_gradient() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.blend_mode(BlendMode::DestinationOut);
+        canvas.fill_gradient(&Gradient::Linear((0.0, 0.0), (1.0, 0.0), vec![
+            GradientStop::new(0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            GradientStop::new(1.0, Color::Rgba(0.0, 0.0, 1.0, 0.5))
+        ]));
+        canvas.new_path();
+        canvas.rect(-0.1, -0.1, 0.1, 0.1);
+        canvas.fill();
+
+        let svg = canvas.to_svg();
+
+        // The mask is black where the gradient is opaque
+        assert!(svg.contains("<stop offset=\"0\" stop-color=\"#000000\" stop-opacity=\"1\"/><stop offset=\"1\" stop-color=\"#000000\" stop-opacity=\"0.5\"/>"));
+        assert!(svg.contains("fill=\"url(#flo-gradient-1)\""));
+        assert!(svg.contains("<mask id=\"flo-mask-2\""));
+    }
+
+    #[test]
+    fn clip_generates_clip_path() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.new_path();
+        canvas.rect(-0.5, -0.5, 0.5, 0.5);
+        canvas.clip();
+        canvas.new_path();
+        canvas.rect(-1.0, -1.0, 1.0, 1.0);
+        canvas.fill();
+
+        let svg = canvas.to_svg();
+
+        assert!(svg.contains("<clipPath id=\"flo-clip-0\""));
+        assert!(svg.contains("clip-path=\"url(#flo-clip-0)\"/>"));
+    }
+
+    #[test]
+    fn pop_state_restores_colour() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.fill_color(Color::Rgba(0.0, 1.0, 0.0, 1.0));
+        canvas.push_state();
+        canvas.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+        canvas.pop_state();
+        canvas.new_path();
+        canvas.rect(-0.5, -0.5, 0.5, 0.5);
+        canvas.fill();
+
+        assert!(canvas.to_svg().contains("fill=\"#00ff00\""));
+    }
+
+    #[test]
+    fn layers_are_ordered() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.layer(2);
+        canvas.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+        canvas.new_path();
+        canvas.rect(-0.5, -0.5, 0.5, 0.5);
+        canvas.fill();
+
+        canvas.layer(1);
+        canvas.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        canvas.new_path();
+        canvas.rect(-0.5, -0.5, 0.5, 0.5);
+        canvas.fill();
+
+        let svg     = canvas.to_svg();
+        let red     = svg.find("#ff0000").unwrap();
+        let blue    = svg.find("#0000ff").unwrap();
+
+        assert!(red < blue);
+    }
+
+    #[test]
+    fn layer_alpha_sets_opacity() {
+        let mut canvas = SvgCanvas::new(100.0, 100.0);
+
+        canvas.layer(1);
+        canvas.layer_alpha(1, 0.5);
+        canvas.new_path();
+        canvas.rect(-0.5, -0.5, 0.5, 0.5);
+        canvas.fill();
+
+        assert!(canvas.to_svg().contains("<g style=\"isolation:isolate;opacity:0.5\">"));
+    }
+}
@@ -0,0 +1,111 @@
+use super::svg_canvas::*;
+use super::super::frame::*;
+
+use flo_animation::*;
+
+use std::io;
+use std::fs;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+///
+/// Converts a duration to seconds
+///
+fn to_seconds(when: Duration) -> f64 {
+    (when.as_secs() as f64) + (when.subsec_nanos() as f64)/1_000_000_000.0
+}
+
+///
+/// Renders a single frame of an animation as an SVG document
+///
+pub fn frame_to_svg(animation: &dyn Animation, when: Duration) -> String {
+    let (width, height) = animation.size();
+    let mut canvas      = SvgCanvas::new(width as f32, height as f32);
+
+    render_animation_frame(animation, when, &mut canvas);
+
+    canvas.to_svg()
+}
+
+///
+/// Writes every frame of an animation to a directory as a sequence of SVG files
+///
+/// The files are named `frame-00000.svg`, `frame-00001.svg`, etc. Returns the paths of the files that were written.
+///
+pub fn export_svg_frames(animation: &dyn Animation, directory: &Path) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(directory)?;
+
+    let mut written = vec![];
+    for (frame_num, when) in frame_times(animation).into_iter().enumerate() {
+        let path = directory.join(format!("frame-{:05}.svg", frame_num));
+
+        fs::write(&path, frame_to_svg(animation, when))?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+///
+/// Generates a single animated SVG document containing every frame of an animation
+///
+/// Each frame is rendered as a group that is made visible at the appropriate time using SMIL animation.
+/// Consecutive frames that are identical are combined into a single group. The animation loops forever.
+///
+pub fn animation_to_animated_svg(animation: &dyn Animation) -> String {
+    let (width, height) = animation.size();
+    let frame_length    = animation.frame_length();
+    let total_length    = to_seconds(animation.duration());
+
+    // Render each frame, combining any identical consecutive frames (the fragment and the time range where it's visible)
+    let mut frames: Vec<(String, Duration, Duration)> = vec![];
+
+    for when in frame_times(animation) {
+        let mut canvas = SvgCanvas::with_id_prefix(width as f32, height as f32, "frame-");
+        render_animation_frame(animation, when, &mut canvas);
+
+        let fragment    = canvas.to_svg_fragment();
+        let end_time    = (when + frame_length).min(animation.duration());
+
+        match frames.last_mut() {
+            Some(ref mut last) if last.0 == fragment    => { last.2 = end_time; }
+            _                                           => { frames.push((fragment, when, end_time)); }
+        }
+    }
+
+    // Generate the document
+    let mut svg = format!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"1.1\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">",
+        width, height, width, height);
+
+    let single_frame = frames.len() <= 1;
+    for (frame_num, (fragment, start, end)) in frames.into_iter().enumerate() {
+        // Give each frame unique IDs
+        let fragment = fragment
+            .replace("id=\"frame-", &format!("id=\"frame{}-", frame_num))
+            .replace("url(#frame-", &format!("url(#frame{}-", frame_num));
+
+        if single_frame || total_length <= 0.0 {
+            // Only one frame, so it's always visible
+            write!(svg, "<g>{}</g>", fragment).unwrap();
+        } else {
+            // Frame is visible between the start and end times
+            let start           = to_seconds(start) / total_length;
+            let end             = to_seconds(end) / total_length;
+
+            let (values, times) = if start <= 0.0 {
+                ("visible;hidden".to_string(), format!("0;{}", end))
+            } else if end >= 1.0 {
+                ("hidden;visible".to_string(), format!("0;{}", start))
+            } else {
+                ("hidden;visible;hidden".to_string(), format!("0;{};{}", start, end))
+            };
+
+            write!(svg, "<g visibility=\"hidden\"><animate attributeName=\"visibility\" values=\"{}\" keyTimes=\"{}\" dur=\"{}s\" calcMode=\"discrete\" repeatCount=\"indefinite\"/>{}</g>",
+                values, times, total_length, fragment).unwrap();
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,193 @@
+extern crate flo_export;
+extern crate flo_canvas;
+extern crate flo_animation;
+extern crate flo_anim_sqlite;
+extern crate futures;
+extern crate png;
+
+use flo_export::*;
+use flo_canvas::*;
+use flo_animation::*;
+use flo_anim_sqlite::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+fn square(x: f32, y: f32) -> Arc<Vec<PathComponent>> {
+    Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x, y)),
+        PathComponent::Line(PathPoint::new(x+10.0, y)),
+        PathComponent::Line(PathPoint::new(x+10.0, y+10.0)),
+        PathComponent::Line(PathPoint::new(x, y+10.0)),
+        PathComponent::Close
+    ])
+}
+
+///
+/// Creates a 2x2 PNG image with a cyan top row and a yellow bottom row
+///
+fn cyan_and_yellow_image() -> CanvasImage {
+    let mut data = vec![];
+
+    {
+        let mut encoder = png::Encoder::new(&mut data, 2, 2);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[
+            0, 255, 255, 255,   0, 255, 255, 255,
+            255, 255, 0, 255,   255, 255, 0, 255
+        ]).unwrap();
+    }
+
+    CanvasImage::from_data(data).unwrap()
+}
+
+///
+/// Creates a 100x100 animation with a brush stroke, a group of two paths, some text and an image on its first frame
+///
+fn create_animation() -> SqliteAnimation {
+    let animation   = SqliteAnimation::new_in_memory();
+    let zero        = Duration::from_millis(0);
+    let ink         = BrushDefinition::Ink(InkDefinition::default());
+    let red         = BrushProperties { color: Color::Rgba(1.0, 0.0, 0.0, 1.0), ..BrushProperties::new() };
+    let green       = BrushProperties { color: Color::Rgba(0.0, 1.0, 0.0, 1.0), ..BrushProperties::new() };
+    let blue_text   = TextStyle { font_size: 20.0, color: Color::Rgba(0.0, 0.0, 1.0, 1.0), ..TextStyle::new() };
+
+    // Horizontal brush stroke along y=85
+    let stroke      = (0..90).map(|x| RawPoint { position: (5.0 + (x as f32), 85.0), pressure: 1.0, tilt: (0.0, 0.0) }).collect::<Vec<_>>();
+
+    executor::spawn(animation.edit()).wait_send(vec![
+        AnimationEdit::SetSize(100.0, 100.0),
+        AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+        AnimationEdit::SetDuration(Duration::from_millis(100)),
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(zero)),
+
+        AnimationEdit::Layer(1, LayerEdit::Paint(zero, PaintEdit::SelectBrush(ElementId::Unassigned, ink.clone(), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(zero, PaintEdit::BrushProperties(ElementId::Unassigned, red))),
+        AnimationEdit::Layer(1, LayerEdit::Paint(zero, PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(stroke)))),
+
+        AnimationEdit::Layer(1, LayerEdit::Path(zero, PathEdit::SelectBrush(ElementId::Unassigned, ink, BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(zero, PathEdit::BrushProperties(ElementId::Unassigned, green))),
+        AnimationEdit::Layer(1, LayerEdit::Path(zero, PathEdit::CreatePath(ElementId::Assigned(100), square(60.0, 60.0)))),
+        AnimationEdit::Layer(1, LayerEdit::Path(zero, PathEdit::CreatePath(ElementId::Assigned(101), square(80.0, 40.0)))),
+        AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(102), GroupType::Normal)),
+
+        AnimationEdit::Layer(1, LayerEdit::Text(zero, TextEdit::CreateText(ElementId::Unassigned, blue_text, (20.0, 20.0), "|".to_string()))),
+        AnimationEdit::Layer(1, LayerEdit::Image(zero, ImageEdit::CreateImage(ElementId::Unassigned, cyan_and_yellow_image(), Transform2D::translate(60.0, 10.0) * Transform2D::scale(20.0, 20.0))))
+    ]).unwrap();
+
+    animation
+}
+
+///
+/// Reads a pixel from a 100x100 RGBA image using animation coordinates (where y=0 is at the bottom of the image)
+///
+fn pixel_at(pixels: &Vec<u8>, x: usize, y: usize) -> [u8; 4] {
+    let pos = ((99-y)*100 + x)*4;
+    [pixels[pos], pixels[pos+1], pixels[pos+2], pixels[pos+3]]
+}
+
+#[test]
+fn render_each_element_type() {
+    let animation   = create_animation();
+    let pixels      = render_frame_rgba(&animation, Duration::from_millis(0), &RasterExportOptions::default());
+
+    assert!(pixels.len() == 100*100*4);
+
+    // Brush stroke
+    let stroke = pixel_at(&pixels, 70, 85);
+    assert!(stroke[0] > 200 && stroke[1] < 50 && stroke[2] < 50 && stroke[3] > 200);
+
+    // Both paths in the group
+    assert!(pixel_at(&pixels, 65, 65) == [0, 255, 0, 255]);
+    assert!(pixel_at(&pixels, 85, 45) == [0, 255, 0, 255]);
+
+    // Text (the '|' character is drawn as a bar from x=24 to x=26)
+    assert!(pixel_at(&pixels, 24, 27) == [0, 0, 255, 255]);
+
+    // Image (cyan at the top and yellow at the bottom)
+    assert!(pixel_at(&pixels, 70, 25) == [0, 255, 255, 255]);
+    assert!(pixel_at(&pixels, 70, 15) == [255, 255, 0, 255]);
+
+    // Background is left transparent
+    assert!(pixel_at(&pixels, 40, 50) == [0, 0, 0, 0]);
+    assert!(pixel_at(&pixels, 5, 5) == [0, 0, 0, 0]);
+}
+
+#[test]
+fn render_with_background_and_size() {
+    let animation   = create_animation();
+    let options     = RasterExportOptions { size: Some((50, 50)), background: Some(Color::Rgba(1.0, 1.0, 1.0, 1.0)), ..RasterExportOptions::default() };
+    let pixels      = render_frame_rgba(&animation, Duration::from_millis(0), &options);
+
+    assert!(pixels.len() == 50*50*4);
+
+    // The output is scaled down: the background is white and the first path is still green
+    let pixel = |x: usize, y: usize| { let pos = ((49-y)*50 + x)*4; [pixels[pos], pixels[pos+1], pixels[pos+2], pixels[pos+3]] };
+    assert!(pixel(20, 25) == [255, 255, 255, 255]);
+    assert!(pixel(32, 32) == [0, 255, 0, 255]);
+}
+
+#[test]
+fn export_with_no_frames_is_rejected() {
+    let animation   = SqliteAnimation::new_in_memory();
+    let options     = RasterExportOptions { time_range: Some(Duration::from_millis(1000)..Duration::from_millis(1000)), ..RasterExportOptions::default() };
+
+    let mut gif     = vec![];
+    let mut apng    = vec![];
+
+    assert!(write_gif(&animation, &mut gif, &options).unwrap_err().kind() == std::io::ErrorKind::InvalidInput);
+    assert!(write_apng(&animation, &mut apng, &options).unwrap_err().kind() == std::io::ErrorKind::InvalidInput);
+    assert!(gif.len() == 0);
+}
+
+#[test]
+fn render_group_as_a_unit() {
+    let animation   = SqliteAnimation::new_in_memory();
+    let zero        = Duration::from_millis(0);
+    let ink         = BrushDefinition::Ink(InkDefinition::default());
+    let red         = BrushProperties { color: Color::Rgba(1.0, 0.0, 0.0, 1.0), ..BrushProperties::new() };
+    let blue        = BrushProperties { color: Color::Rgba(0.0, 0.0, 1.0, 1.0), ..BrushProperties::new() };
+    let big_square  = |x: f32, y: f32| Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x, y)),
+        PathComponent::Line(PathPoint::new(x+40.0, y)),
+        PathComponent::Line(PathPoint::new(x+40.0, y+40.0)),
+        PathComponent::Line(PathPoint::new(x, y+40.0)),
+        PathComponent::Close
+    ]);
+
+    // A half-transparent group containing two layers with overlapping squares
+    executor::spawn(animation.edit()).wait_send(vec![
+        AnimationEdit::SetSize(100.0, 100.0),
+        AnimationEdit::AddNewGroupLayer(1),
+        AnimationEdit::AddNewLayer(2),
+        AnimationEdit::AddNewLayer(3),
+        AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(3, LayerEdit::SetParentGroup(Some(1))),
+        AnimationEdit::Layer(1, LayerEdit::SetOpacity(0.5)),
+        AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(zero)),
+        AnimationEdit::Layer(3, LayerEdit::AddKeyFrame(zero)),
+
+        AnimationEdit::Layer(2, LayerEdit::Path(zero, PathEdit::SelectBrush(ElementId::Unassigned, ink.clone(), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(2, LayerEdit::Path(zero, PathEdit::BrushProperties(ElementId::Unassigned, red))),
+        AnimationEdit::Layer(2, LayerEdit::Path(zero, PathEdit::CreatePath(ElementId::Unassigned, big_square(20.0, 20.0)))),
+
+        AnimationEdit::Layer(3, LayerEdit::Path(zero, PathEdit::SelectBrush(ElementId::Unassigned, ink, BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(3, LayerEdit::Path(zero, PathEdit::BrushProperties(ElementId::Unassigned, blue))),
+        AnimationEdit::Layer(3, LayerEdit::Path(zero, PathEdit::CreatePath(ElementId::Unassigned, big_square(40.0, 40.0))))
+    ]).unwrap();
+
+    let pixels      = render_frame_rgba(&animation, zero, &RasterExportOptions::default());
+
+    // Each square is drawn at the opacity of the group
+    assert!(pixel_at(&pixels, 30, 30) == [255, 0, 0, 128]);
+    assert!(pixel_at(&pixels, 70, 70) == [0, 0, 255, 128]);
+
+    // Where they overlap, the blue square hides the red one before the group's opacity is applied
+    assert!(pixel_at(&pixels, 50, 50) == [0, 0, 255, 128]);
+}
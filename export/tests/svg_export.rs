@@ -0,0 +1,107 @@
+extern crate flo_export;
+extern crate flo_animation;
+extern crate flo_anim_sqlite;
+extern crate futures;
+
+use flo_export::*;
+use flo_animation::*;
+use flo_anim_sqlite::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+fn square(x: f32, y: f32) -> Arc<Vec<PathComponent>> {
+    Arc::new(vec![
+        PathComponent::Move(PathPoint::new(x, y)),
+        PathComponent::Line(PathPoint::new(x+10.0, y)),
+        PathComponent::Line(PathPoint::new(x+10.0, y+10.0)),
+        PathComponent::Line(PathPoint::new(x, y+10.0)),
+        PathComponent::Close
+    ])
+}
+
+///
+/// Creates a 400ms animation with 100ms frames, with a keyframe at 0ms and another at 200ms
+///
+fn create_animation() -> SqliteAnimation {
+    let animation = SqliteAnimation::new_in_memory();
+
+    executor::spawn(animation.edit()).wait_send(vec![
+        AnimationEdit::SetSize(100.0, 100.0),
+        AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+        AnimationEdit::SetDuration(Duration::from_millis(400)),
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(200))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Unassigned, square(10.0, 10.0)))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(200), PathEdit::CreatePath(ElementId::Unassigned, square(50.0, 50.0))))
+    ]).unwrap();
+
+    animation
+}
+
+#[test]
+fn frame_is_svg_document() {
+    let animation   = create_animation();
+    let svg         = frame_to_svg(&animation, Duration::from_millis(0));
+
+    assert!(svg.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<svg "));
+    assert!(svg.contains("width=\"100\" height=\"100\" viewBox=\"0 0 100 100\""));
+    assert!(svg.contains("<path"));
+    assert!(svg.ends_with("</svg>\n"));
+
+    // Not an animated document
+    assert!(!svg.contains("<animate"));
+}
+
+#[test]
+fn frames_on_same_keyframe_are_identical() {
+    let animation = create_animation();
+
+    assert!(frame_to_svg(&animation, Duration::from_millis(0)) == frame_to_svg(&animation, Duration::from_millis(100)));
+    assert!(frame_to_svg(&animation, Duration::from_millis(0)) != frame_to_svg(&animation, Duration::from_millis(200)));
+}
+
+#[test]
+fn animated_svg_shows_each_keyframe_in_turn() {
+    let animation   = create_animation();
+    let svg         = animation_to_animated_svg(&animation);
+
+    // The four frames are combined into one group per keyframe
+    assert!(svg.matches("<g visibility=\"hidden\">").count() == 2);
+    assert!(svg.matches("<animate ").count() == 2);
+
+    // The first keyframe is visible for the first half of the animation, and the second for the rest
+    assert!(svg.contains("<animate attributeName=\"visibility\" values=\"visible;hidden\" keyTimes=\"0;0.5\" dur=\"0.4s\" calcMode=\"discrete\" repeatCount=\"indefinite\"/>"));
+    assert!(svg.contains("<animate attributeName=\"visibility\" values=\"hidden;visible\" keyTimes=\"0;0.5\" dur=\"0.4s\" calcMode=\"discrete\" repeatCount=\"indefinite\"/>"));
+
+    let first_frame     = svg.find("values=\"visible;hidden\"").unwrap();
+    let second_frame    = svg.find("values=\"hidden;visible\"").unwrap();
+    assert!(first_frame < second_frame);
+
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn animated_svg_with_one_keyframe_is_not_animated() {
+    let animation = SqliteAnimation::new_in_memory();
+
+    executor::spawn(animation.edit()).wait_send(vec![
+        AnimationEdit::SetFrameLength(Duration::from_millis(100)),
+        AnimationEdit::SetDuration(Duration::from_millis(400)),
+        AnimationEdit::AddNewLayer(1),
+        AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Unassigned, square(10.0, 10.0))))
+    ]).unwrap();
+
+    let svg = animation_to_animated_svg(&animation);
+
+    assert!(svg.matches("<g>").count() >= 1);
+    assert!(!svg.contains("<animate"));
+}
@@ -6,7 +6,7 @@ use flo_binding::*;
 use flo_animation::*;
 
 use std::sync::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 ///
 /// Represents a layer in the current frame
@@ -15,9 +15,24 @@ struct FrameLayer {
     /// The ID of the layer to draw on the canvas
     layer_id:           u32,
 
+    /// The ID of the animation layer that this layer is composited with (layers in the same group share a canvas layer)
+    composited_id:      u64,
+
+    /// The order that this layer is drawn in, relative to the other layers in the frame
+    draw_order:         usize,
+
     /// The frame data for this layer
     layer_frame:        Arc<dyn Frame>,
 
+    /// Whether or not this layer should be drawn
+    visible:            bool,
+
+    /// The opacity of this layer
+    opacity:            f64,
+
+    /// The blend mode to use for this layer
+    blend_mode:         BlendMode,
+
     /// The brush that was last used for this layer
     active_brush:       Option<(BrushDefinition, BrushDrawingStyle)>,
 
@@ -169,6 +184,12 @@ impl CanvasRenderer {
                     gc.layer_blend(canvas_layer, blend_style);
                 },
 
+                LayerAlpha(overlay_layer, alpha) => {
+                    // Pick the layer from the canvas
+                    let canvas_layer = *overlay.layers.entry(overlay_layer).or_insert_with(|| next_free_layer());
+                    gc.layer_alpha(canvas_layer, alpha);
+                },
+
                 unchanged => gc.draw(unchanged)
             }
         }
@@ -188,17 +209,29 @@ impl CanvasRenderer {
             // If there are any overlays, they get invalidated when we add this frame
             self.invalidate_overlay_layers();
 
-            // The layer ID comes from the number of layers we've currently got loaded (this layer will be rendered on top of all others)
+            // Layers in the same group share a canvas layer, otherwise the layer is rendered on top of all the others
             let animation_layer_id      = model.layer_id;
-            let canvas_layer_id         = (self.frame_layers.len() as u32) + 1;
+            let composited_id           = model.composited_layer_id.get();
+            let draw_order              = self.frame_layers.len();
+            let canvas_layer_id         = self.frame_layers.values()
+                .filter(|frame_layer| frame_layer.composited_id == composited_id)
+                .map(|frame_layer| frame_layer.layer_id)
+                .nth(0)
+                .unwrap_or_else(|| self.frame_layers.values().map(|frame_layer| frame_layer.layer_id).max().unwrap_or(0) + 1);
 
             // Get the frame for this time
             let layer_frame             = frame;
+            let (visible, opacity, blend_mode) = model.appearance.get();
 
             // Store this layer in the hashmap with its layer ID
             self.frame_layers.insert(animation_layer_id, FrameLayer {
                 layer_id:           canvas_layer_id,
+                composited_id:      composited_id,
+                draw_order:         draw_order,
                 layer_frame:        layer_frame,
+                visible:            visible,
+                opacity:            opacity,
+                blend_mode:         blend_mode,
                 active_brush:       None,
                 active_properties:  None
             });
@@ -258,8 +291,23 @@ impl CanvasRenderer {
 
         // Draw the active set of layers
         canvas.draw(move |gc| {
+            // Layers that share a canvas layer need to be drawn in order
+            let mut layers = self.frame_layers.values().collect::<Vec<_>>();
+            layers.sort_by_key(|layer| layer.draw_order);
+
+            // Set up each canvas layer (hidden layers are left transparent so any annotations made to them aren't visible either)
+            let visible_layers = layers.iter()
+                .filter(|layer| layer.visible)
+                .map(|layer| layer.layer_id)
+                .collect::<HashSet<_>>();
+
+            for layer in layers.iter() {
+                gc.layer_blend(layer.layer_id, layer.blend_mode);
+                gc.layer_alpha(layer.layer_id, if visible_layers.contains(&layer.layer_id) { layer.opacity as f32 } else { 0.0 });
+            }
+
             // Draw the layers
-            for layer in self.frame_layers.values() {
+            for layer in layers.iter().filter(|layer| layer.visible) {
                 gc.layer(layer.layer_id);
                 layer.layer_frame.render_to(gc);
             }
@@ -49,6 +49,9 @@ pub struct CanvasTools<Anim: Animation+EditableAnimation> {
     /// The brush properties that have been set
     brush_properties: BrushProperties,
 
+    /// The stabiliser settings that have been set
+    stabiliser: StabiliserSettings,
+
     /// Runs commands for the active tool
     tool_runner: ToolRunner<Anim>
 }
@@ -76,7 +79,8 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
             active_tool:        None,
             tool_runner:        tool_runner,
             brush_definition:   (BrushDefinition::Simple, BrushDrawingStyle::Draw),
-            brush_properties:   BrushProperties::new()
+            brush_properties:   BrushProperties::new(),
+            stabiliser:         StabiliserSettings::default()
         }
     }
 
@@ -190,8 +194,8 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
             }
         }
 
-        // Commit any animation edits that the tool produced
-        if animation_edits.len() > 0 {
+        // Commit any animation edits that the tool produced (these are a single transaction, so they're all rejected if any of them would change a locked layer)
+        if animation_edits.len() > 0 && !animation_edits.iter().any(|edit| self.is_locked_layer_edit(edit)) {
             self.edit_sink.wait_send(animation_edits).unwrap();
         }
 
@@ -206,6 +210,50 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
         }
     }
 
+    ///
+    /// True if the specified layer is locked, hidden or a group (so the tools should not be able to draw on it)
+    ///
+    fn is_layer_locked(&self, layer_id: u64) -> bool {
+        self.animation.timeline().layers.get()
+            .iter()
+            .filter(|layer| layer.id == layer_id)
+            .any(|layer| layer.is_group || layer.locked.get() || !layer.visible.get())
+    }
+
+    ///
+    /// True if the specified edit would change a layer that is locked
+    ///
+    fn is_locked_layer_edit(&self, edit: &AnimationEdit) -> bool {
+        match edit {
+            AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(_))       |
+            AnimationEdit::Layer(layer_id, LayerEdit::RemoveKeyFrame(_))    |
+            AnimationEdit::Layer(layer_id, LayerEdit::Paint(_, _))          |
+            AnimationEdit::Layer(layer_id, LayerEdit::Path(_, _))           |
+            AnimationEdit::Layer(layer_id, LayerEdit::Text(_, _))           |
+            AnimationEdit::Layer(layer_id, LayerEdit::Image(_, _))          => self.is_layer_locked(*layer_id),
+
+            // Element edits are blocked if any of the elements are on a locked layer
+            AnimationEdit::Element(element_ids, _)                          => self.is_locked_element(element_ids),
+
+            // Motions are blocked if they move any elements on a locked layer
+            AnimationEdit::Motion(motion_id, _)                             => {
+                let element_ids = self.animation.motion().get_elements_for_motion(*motion_id);
+                self.is_locked_element(&element_ids)
+            },
+
+            _                                                               => false
+        }
+    }
+
+    ///
+    /// True if any of the specified elements are on a locked layer (in any keyframe)
+    ///
+    fn is_locked_element(&self, element_ids: &Vec<ElementId>) -> bool {
+        element_ids.iter()
+            .filter_map(|element_id| self.animation.layer_for_element(*element_id))
+            .any(|layer_id| self.is_layer_locked(layer_id))
+    }
+
     ///
     /// True if we need to update the brush definition before drawing
     ///
@@ -233,6 +281,7 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
                 let mut preview = BrushPreview::new();
                 preview.set_brush_properties(&self.brush_properties);
                 preview.select_brush(&self.brush_definition.0, self.brush_definition.1);
+                preview.set_stabiliser(self.stabiliser);
 
                 self.preview = Some(preview);
             },
@@ -241,7 +290,13 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
             BrushPreviewAction::Layer(layer_id)                 => { self.preview_layer = Some(layer_id); },
             BrushPreviewAction::BrushDefinition(defn, style)    => { self.brush_definition = (defn.clone(), style); self.preview.as_mut().map(move |preview| preview.select_brush(&defn, style)); },
             BrushPreviewAction::BrushProperties(props)          => { self.brush_properties = props; self.preview.as_mut().map(move |preview| preview.set_brush_properties(&props)); },
-            BrushPreviewAction::AddPoint(point)                 => { self.preview.as_mut().map(move |preview| preview.continue_brush_stroke(point)); },
+            BrushPreviewAction::Stabiliser(settings)            => { self.stabiliser = settings; self.preview.as_mut().map(move |preview| preview.set_stabiliser(settings)); },
+            BrushPreviewAction::AddPoint(point)                 => {
+                // Locked layers can't be drawn on
+                if self.preview_layer.map(|layer_id| self.is_layer_locked(layer_id)).unwrap_or(false) { return; }
+                self.preview.as_mut().map(move |preview| preview.continue_brush_stroke(point));
+            },
+            BrushPreviewAction::FinishStroke                    => { self.preview.as_mut().map(|preview| preview.finish_brush_stroke()); },
             BrushPreviewAction::Commit                          => { self.commit_brush_preview(canvas, renderer) },
             BrushPreviewAction::CommitAsPath                    => { self.commit_brush_preview_as_path(canvas, renderer) }
             BrushPreviewAction::CombineCollidingElements        => { self.combine_colliding_elements() }
@@ -320,6 +375,9 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
     fn commit_brush_preview(&mut self, canvas: &BindingCanvas, renderer: &mut CanvasRenderer) {
         // We take the preview here (so there's no preview after this)
         if let (Some(mut preview), Some(preview_layer)) = (self.preview.take(), self.preview_layer) {
+            // Nothing is committed to a locked layer
+            if self.is_layer_locked(preview_layer) { return; }
+
             let mut need_brush  = self.need_brush_definition(preview_layer, renderer);
             let mut need_props  = self.need_brush_properties(preview_layer, renderer);
 
@@ -351,6 +409,9 @@ impl<Anim: 'static+Animation+EditableAnimation> CanvasTools<Anim> {
     fn commit_brush_preview_as_path(&mut self, canvas: &BindingCanvas, renderer: &mut CanvasRenderer) {
         // Take the brush preview and commit
         if let (Some(mut preview), Some(preview_layer)) = (self.preview.take(), self.preview_layer) {
+            // Nothing is committed to a locked layer
+            if self.is_layer_locked(preview_layer) { return; }
+
             let mut need_brush  = self.need_brush_definition(preview_layer, renderer);
             let mut need_props  = self.need_brush_properties(preview_layer, renderer);
             let current_time    = self.current_time.get();
@@ -53,7 +53,7 @@ impl OnionSkinRenderer {
 
                         iter::once(Draw::NewPath)
                             .chain(drawing.iter()
-                                .map(|draw| *draw))
+                                .map(|draw| draw.clone()))
                             .chain(vec![
                                 Draw::FillColor(color),
                                 Draw::Fill,
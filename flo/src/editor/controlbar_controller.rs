@@ -1,4 +1,5 @@
 use super::keyframe_controls_controller::*;
+use super::undo_controls_controller::*;
 use super::super::model::*;
 
 use flo_ui::*;
@@ -15,7 +16,10 @@ pub struct ControlBarController<Anim: 'static+Animation+EditableAnimation> {
     ui: BindRef<Control>,
 
     /// The keyframe controls controller
-    keyframe_controls: Arc<KeyFrameControlsController<Anim>>
+    keyframe_controls: Arc<KeyFrameControlsController<Anim>>,
+
+    /// The undo controls controller
+    undo_controls: Arc<UndoControlsController<Anim>>
 }
 
 impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
@@ -29,11 +33,14 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
         // Create the subcontrollers
         let keyframe_controls   = KeyFrameControlsController::new(model);
         let keyframe_controls   = Arc::new(keyframe_controls);
+        let undo_controls       = UndoControlsController::new(model);
+        let undo_controls       = Arc::new(undo_controls);
 
         // Build the controller itself
         ControlBarController {
             ui:                 ui,
-            keyframe_controls:  keyframe_controls
+            keyframe_controls:  keyframe_controls,
+            undo_controls:      undo_controls
         }
     }
 
@@ -46,6 +53,11 @@ impl<Anim: 'static+Animation+EditableAnimation> ControlBarController<Anim> {
             .with(Bounds::fill_all())
             .with(ControlAttribute::Padding((0, 2), (0, 2)))
             .with(vec![
+                Control::empty()
+                    .with(Bounds::next_horiz(32.0)),
+                Control::container()
+                    .with_controller("UndoControls")
+                    .with(Bounds::next_horiz(96.0)),
                 Control::empty()
                     .with(Bounds::stretch_horiz(1.0)),
                 Control::container()
@@ -69,6 +81,7 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for ControlBarControl
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         match id {
             "KeyFrameControls"  => Some(self.keyframe_controls.clone()),
+            "UndoControls"      => Some(self.undo_controls.clone()),
 
             _                   => None
         }
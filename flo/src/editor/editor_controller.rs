@@ -9,10 +9,16 @@ use super::super::style::*;
 use flo_ui::*;
 use flo_ui_files::ui::*;
 use flo_binding::*;
+use flo_canvas::{CanvasImage, Transform2D};
 use flo_animation::*;
 
+use desync::*;
+use futures::*;
+use futures::executor;
+use futures::executor::Spawn;
+
 use std::sync::*;
-use std::marker::PhantomData;
+use std::time::Duration;
 use std::collections::HashMap;
 
 use serde_json;
@@ -26,15 +32,40 @@ enum SubController {
     Toolbox
 }
 
+/// The keyboard shortcuts for choosing tools, as the key and the name of the tool that it selects
+const TOOL_SHORTCUTS: [(char, &str); 12] = [
+    ('v', "Select"),
+    ('a', "Adjust"),
+    ('q', "Transform"),
+    ('h', "Pan"),
+    ('b', "Ink"),
+    ('e', "Eraser"),
+    ('g', "Flood Fill"),
+    ('t', "Text"),
+    ('r', "Rectangle"),
+    ('o', "Ellipse"),
+    ('p', "Polygon"),
+    ('l', "Line")
+];
+
+/// Prefix for the actions that select a tool from a keyboard shortcut
+const SHORTCUT_TOOL_PREFIX: &str = "ShortcutTool:";
+
 ///
 /// The editor controller manages the editing of a single file
 ///
 pub struct EditorController<Anim: Animation> {
-    /// Phantom data so we can have the animation type
-    anim: PhantomData<Anim>,
+    /// The model that the keyboard shortcuts act upon
+    anim_model: FloModel<Anim>,
+
+    /// The animation editing stream where the keyboard shortcuts will send updates
+    edit: Desync<Spawn<Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send>>>,
+
+    /// The message describing why the last image import failed, if it did
+    import_error: Binding<Option<String>>,
 
     /// The main editor UI
-    ui: Binding<Control>,
+    ui: BindRef<Control>,
 
     /// The subcontrollers for this editor
     subcontrollers: HashMap<SubController, Arc<dyn Controller>>
@@ -59,8 +90,11 @@ impl<Anim: 'static+Animation+EditableAnimation> EditorController<Anim> {
         let timeline    = Arc::new(TimelineController::new(&animation));
         let toolbox     = Arc::new(ToolboxController::new(&animation));
         let control_bar = Arc::new(ControlBarController::new(&animation));
+        let edit        = Desync::new(executor::spawn(animation.edit()));
 
-        let ui          = bind(Self::ui());
+        let import_error    = bind(None);
+        let error_message   = import_error.clone();
+        let ui              = BindRef::from(computed(move || Self::ui(error_message.get())));
         let mut subcontrollers: HashMap<SubController, Arc<dyn Controller>> = HashMap::new();
 
         subcontrollers.insert(SubController::Canvas,        canvas);
@@ -70,7 +104,9 @@ impl<Anim: 'static+Animation+EditableAnimation> EditorController<Anim> {
         subcontrollers.insert(SubController::ControlBar,    control_bar);
 
         EditorController {
-            anim:           PhantomData,
+            anim_model:     animation,
+            edit:           edit,
+            import_error:   import_error,
             ui:             ui,
             subcontrollers: subcontrollers,
         }
@@ -127,6 +163,8 @@ impl<Anim: 'static+Animation+EditableAnimation> EditorController<Anim> {
     ///
     /// Creates the canvas control
     ///
+    /// Images can be imported by dropping them onto the canvas.
+    ///
     pub fn canvas() -> Control {
         use self::Position::*;
 
@@ -137,9 +175,23 @@ impl<Anim: 'static+Animation+EditableAnimation> EditorController<Anim> {
                 x2: Stretch(1.0),
                 y2: End
             })
+            .with((ActionTrigger::DropFile, "ImportImage"))
             .with_controller(&serde_json::to_string(&SubController::Canvas).unwrap())
     }
 
+    ///
+    /// Creates the bar that reports why an image couldn't be imported (clicking it dismisses it)
+    ///
+    pub fn import_error_bar(message: String) -> Control {
+        Control::label()
+            .with(message)
+            .with(Bounds::next_vert(24.0))
+            .with(Appearance::Background(IMPORT_ERROR_BACKGROUND))
+            .with(Font::Size(12.0))
+            .with(TextAlign::Center)
+            .with((ActionTrigger::Click, "DismissImportError"))
+    }
+
     ///
     /// Creates the control bar control
     ///
@@ -153,32 +205,159 @@ impl<Anim: 'static+Animation+EditableAnimation> EditorController<Anim> {
     }
 
     ///
-    /// Creates the UI tree for this controller
+    /// Adds the standard editor keyboard shortcuts to a control
     ///
-    pub fn ui() -> Control {
+    fn with_shortcuts(control: Control) -> Control {
+        use self::Key::*;
+
+        let control = TOOL_SHORTCUTS.iter()
+            .fold(control, |control, (key, tool_name)| control.with((ActionTrigger::KeyDown(Character(*key), vec![]), format!("{}{}", SHORTCUT_TOOL_PREFIX, tool_name))));
+
+        control
+            .with((ActionTrigger::KeyDown(Delete, vec![]), "DeleteSelection"))
+            .with((ActionTrigger::KeyDown(Backspace, vec![]), "DeleteSelection"))
+            .with((ActionTrigger::KeyDown(ArrowLeft, vec![]), "PreviousFrame"))
+            .with((ActionTrigger::KeyDown(ArrowRight, vec![]), "NextFrame"))
+            .with((ActionTrigger::KeyDown(Home, vec![]), "FirstFrame"))
+            .with((ActionTrigger::KeyDown(Character('z'), vec![Modifier::Control]), "Undo"))
+            .with((ActionTrigger::KeyDown(Character('z'), vec![Modifier::Control, Modifier::Shift]), "Redo"))
+            .with((ActionTrigger::KeyDown(Character('y'), vec![Modifier::Control]), "Redo"))
+    }
+
+    ///
+    /// Removes the selected elements from the current frame
+    ///
+    /// Elements on locked layers are not removed, and stay selected.
+    ///
+    fn delete_selection(&self) {
+        let selection   = self.anim_model.selection().selection_in_order.get();
+        let anim_model  = &self.anim_model;
+        let is_locked   = |element_id: ElementId| anim_model.layer_for_element(element_id)
+            .and_then(|layer_id| anim_model.get_layer_with_id(layer_id))
+            .map(|layer| layer.is_locked())
+            .unwrap_or(false);
+
+        let (locked, to_delete): (Vec<ElementId>, Vec<ElementId>) = selection.iter().cloned().partition(|element_id| is_locked(*element_id));
+
+        if to_delete.len() > 0 {
+            self.edit.sync(move |animation| {
+                animation.wait_send(vec![
+                    AnimationEdit::Element(to_delete, ElementEdit::Delete)
+                ]).ok();
+            });
+
+            self.anim_model.selection().clear_selection();
+            locked.into_iter().for_each(|element_id| self.anim_model.selection().select(element_id));
+            self.anim_model.timeline().invalidate_canvas();
+        }
+    }
+
+    ///
+    /// Moves the current time by the specified number of frames
+    ///
+    fn step_frames(&self, num_frames: i32) {
+        let timeline        = self.anim_model.timeline();
+        let frame_duration  = timeline.frame_duration.get();
+        let current_time    = timeline.current_time.get();
+
+        let new_time        = if num_frames >= 0 {
+            current_time + frame_duration * (num_frames as u32)
+        } else {
+            current_time.checked_sub(frame_duration * (-num_frames as u32)).unwrap_or(Duration::from_millis(0))
+        };
+
+        timeline.current_time.set(new_time);
+    }
+
+    ///
+    /// Places a PNG or JPEG image on the current keyframe of the selected layer, returning a message describing the problem if it can't be imported
+    ///
+    /// The image is centred in the frame, and is scaled down if it's too large to fit.
+    ///
+    fn import_image(&self, name: &str, data: Vec<u8>) -> Result<(), String> {
+        let selected_layer  = self.anim_model.timeline().selected_vector_layer.get();
+        let current_time    = self.anim_model.timeline().current_time.get();
+
+        let layer_id        = selected_layer.ok_or_else(|| format!("Could not import {}: select a layer that can be drawn on first", name))?;
+        let image           = CanvasImage::from_data(data).ok_or_else(|| format!("Could not import {}: only PNG and JPEG images are supported", name))?;
+
+        if self.anim_model.timeline().layers.get().iter().any(|layer| layer.id == layer_id && layer.locked.get()) {
+            return Err(format!("Could not import {}: the selected layer is locked", name));
+        }
+
+        // Add a keyframe if there's nothing to place the image in (or if new keyframes are created when drawing)
+        let frame_size          = self.anim_model.size();
+        let transform           = Self::centred_image_transform(&image, (frame_size.0 as f32, frame_size.1 as f32));
+        let frame               = self.anim_model.frame();
+        let (previous, _next)   = frame.previous_and_next_keyframe.get();
+        let add_keyframe        = !frame.keyframe_selected.get() && (frame.create_keyframe_on_draw.get() || previous.is_none());
+
+        let mut edits           = vec![];
+        if add_keyframe {
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(current_time)));
+        }
+        edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Image(current_time, ImageEdit::CreateImage(ElementId::Unassigned, image, transform))));
+
+        self.edit.sync(move |animation| animation.wait_send(edits))
+            .map_err(|_| format!("Could not import {}: the animation could not be edited", name))?;
+
+        self.anim_model.timeline().invalidate_canvas();
+        if add_keyframe {
+            self.anim_model.timeline().update_keyframe_bindings();
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the transform that draws an image at its natural size in the middle of a frame (shrinking it if it doesn't fit)
+    ///
+    fn centred_image_transform(image: &CanvasImage, frame_size: (f32, f32)) -> Transform2D {
+        let (frame_width, frame_height) = frame_size;
+        let image_width                 = (image.width as f32).max(1.0);
+        let image_height                = (image.height as f32).max(1.0);
+
+        let scale                       = (frame_width/image_width).min(frame_height/image_height).min(1.0);
+        let (width, height)             = (image_width*scale, image_height*scale);
+        let (x, y)                      = ((frame_width-width)/2.0, (frame_height-height)/2.0);
+
+        Transform2D::translate(x, y) * Transform2D::scale(width, height)
+    }
+
+    ///
+    /// Creates the UI tree for this controller (along with a message describing why an image couldn't be imported, if there is one)
+    ///
+    pub fn ui(import_error: Option<String>) -> Control {
         use self::Position::*;
 
         let menu_bar    = Self::menu_bar();
+        let error_bar   = import_error.map(|message| Self::import_error_bar(message));
         let timeline    = Self::timeline();
         let toolbar     = Self::toolbox();
         let canvas      = Self::canvas();
         let control_bar = Self::control_bar();
 
-        Control::container()
-            .with(Bounds::fill_all())
-            .with(vec![
-                menu_bar,
-                Control::container()
-                    .with((vec![toolbar, canvas],
-                        Bounds { x1: Start, y1: After, x2: End, y2: Stretch(1.0) })),
-                Control::empty()
-                    .with(Bounds::next_vert(1.0))
-                    .with(Appearance::Background(TIMESCALE_BORDER)),
-                control_bar,
-                Control::empty()
-                    .with(Bounds::next_vert(1.0))
-                    .with(Appearance::Background(TIMESCALE_LAYERS)),
-                timeline])
+        // The error bar appears underneath the menu bar when there's an error to report
+        let mut controls = vec![menu_bar];
+        controls.extend(error_bar);
+        controls.extend(vec![
+            Control::container()
+                .with((vec![toolbar, canvas],
+                    Bounds { x1: Start, y1: After, x2: End, y2: Stretch(1.0) })),
+            Control::empty()
+                .with(Bounds::next_vert(1.0))
+                .with(Appearance::Background(TIMESCALE_BORDER)),
+            control_bar,
+            Control::empty()
+                .with(Bounds::next_vert(1.0))
+                .with(Appearance::Background(TIMESCALE_LAYERS)),
+            timeline]);
+
+        let root        = Control::container()
+            .with(Bounds::fill_all());
+
+        Self::with_shortcuts(root)
+            .with(controls)
     }
 }
 
@@ -196,6 +375,34 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for EditorController<
             None
         }
     }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        match action_id {
+            "DeleteSelection"   => self.delete_selection(),
+            "PreviousFrame"     => self.step_frames(-1),
+            "NextFrame"         => self.step_frames(1),
+            "FirstFrame"        => self.anim_model.timeline().current_time.set(Duration::from_millis(0)),
+            "Undo"              => self.anim_model.undo(),
+            "Redo"              => self.anim_model.redo(),
+
+            "ImportImage"       => {
+                // The parameter is the file that the user dropped onto the canvas
+                if let ActionParameter::File(name, data) = action_parameter {
+                    let result = self.import_image(name, data.clone());
+                    self.import_error.set(result.err());
+                }
+            },
+
+            "DismissImportError" => self.import_error.set(None),
+
+            _ => {
+                if action_id.starts_with(SHORTCUT_TOOL_PREFIX) {
+                    // Keyboard shortcut for choosing a tool
+                    self.anim_model.tools().choose_tool_with_name(&action_id[SHORTCUT_TOOL_PREFIX.len()..]);
+                }
+            }
+        }
+    }
 }
 
 impl<Anim: FileAnimation+EditableAnimation+'static> FileController for EditorController<Anim> {
@@ -216,3 +423,113 @@ impl<Anim: Animation> PartialEq for EditorController<Anim> {
         false
     }
 }
+
+#[cfg(test)]
+mod test {
+    extern crate flo_anim_sqlite;
+
+    use super::*;
+    use self::flo_anim_sqlite::*;
+    use flo_canvas::ImageFormat;
+
+    /// A PNG file containing a 2x1 image
+    const SMALL_PNG: [u8; 72] = [
+        0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x7b, 0x40, 0xe8,
+        0xdd, 0x00, 0x00, 0x00, 0x0f, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9c, 0x63, 0xf8, 0xcf, 0xc0, 0xc0,
+        0xf0, 0x9f, 0x01, 0x00, 0x07, 0xff, 0x01, 0xff, 0x01, 0x7f, 0x89, 0xa7, 0x00, 0x00, 0x00, 0x00,
+        0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82
+    ];
+
+    fn controller_with_layer() -> EditorController<SqliteAnimation> {
+        let controller = EditorController::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(controller.anim_model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(1),
+                AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+            ]).unwrap();
+        }
+        controller.anim_model.timeline().selected_layer.set(Some(1));
+
+        controller
+    }
+
+    #[test]
+    fn import_png_image() {
+        let controller = controller_with_layer();
+
+        controller.action("ImportImage", &ActionParameter::File("small.png".to_string(), SMALL_PNG.to_vec()));
+        assert!(controller.import_error.get() == None);
+
+        // The image should be placed on the keyframe of the selected layer
+        let frame   = controller.anim_model.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+        let images  = frame.vector_elements().unwrap()
+            .filter_map(|element| match element { Vector::Image(image) => Some(image), _ => None })
+            .collect::<Vec<_>>();
+
+        assert!(images.len() == 1);
+        assert!(images[0].image().format == ImageFormat::Png);
+        assert!(images[0].image().width == 2);
+        assert!(images[0].image().height == 1);
+        assert!(*images[0].image().data == SMALL_PNG.to_vec());
+    }
+
+    #[test]
+    fn import_unsupported_file_reports_error() {
+        let controller = controller_with_layer();
+
+        controller.action("ImportImage", &ActionParameter::File("notes.txt".to_string(), "Not an image".as_bytes().to_vec()));
+        assert!(controller.import_error.get().is_some());
+
+        let frame = controller.anim_model.get_layer_with_id(1).unwrap().get_frame_at_time(Duration::from_millis(0));
+        assert!(frame.vector_elements().unwrap().count() == 0);
+
+        controller.action("DismissImportError", &ActionParameter::None);
+        assert!(controller.import_error.get() == None);
+    }
+
+    #[test]
+    fn delete_leaves_elements_on_locked_layers() {
+        let controller = controller_with_layer();
+
+        {
+            let mut edit_log = executor::spawn(controller.anim_model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                    PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                    PathComponent::Line(PathPoint::new(20.0, 10.0)),
+                    PathComponent::Close
+                ])))),
+                AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(0), PathEdit::CreatePath(ElementId::Assigned(200), Arc::new(vec![
+                    PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                    PathComponent::Line(PathPoint::new(20.0, 10.0)),
+                    PathComponent::Close
+                ])))),
+                AnimationEdit::Layer(1, LayerEdit::SetLocked(true))
+            ]).unwrap();
+        }
+
+        controller.anim_model.selection().select(ElementId::Assigned(100));
+        controller.anim_model.selection().select(ElementId::Assigned(200));
+        controller.action("DeleteSelection", &ActionParameter::None);
+
+        // Only the element on the unlocked layer is deleted
+        let element_exists = |layer_id, element_id| controller.anim_model.get_layer_with_id(layer_id).unwrap()
+            .get_frame_at_time(Duration::from_millis(0))
+            .element_with_id(element_id)
+            .is_some();
+
+        assert!(element_exists(1, ElementId::Assigned(100)));
+        assert!(!element_exists(2, ElementId::Assigned(200)));
+        assert!(controller.anim_model.selection().selected_elements.get().len() == 1);
+        assert!(controller.anim_model.selection().selected_elements.get().contains(&ElementId::Assigned(100)));
+    }
+}
@@ -38,8 +38,8 @@ pub struct KeyFrameControlsController<Anim: 'static+Animation+EditableAnimation>
     /// The current frame binding
     current_time: Binding<Duration>,
 
-    // The currently selected layer ID
-    selected_layer: Binding<Option<u64>>,
+    // The currently selected layer ID (None if the selected layer is a group, as groups can't have keyframes)
+    selected_layer: BindRef<Option<u64>>,
 
     /// The edit sink for the animation
     edit_sink: Desync<Spawn<Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send>>>,
@@ -58,7 +58,7 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
         let onion_skin  = model.onion_skin();
         let view_model  = Arc::new(DynamicViewModel::new());
 
-        let selected_layer          = timeline.selected_layer.clone();
+        let selected_layer          = timeline.selected_vector_layer.clone();
         let create_keyframe_on_draw = frame.create_keyframe_on_draw.clone();
         let show_onion_skins        = onion_skin.show_onion_skins.clone();
         let keyframe_selected       = frame.keyframe_selected.clone();
@@ -86,7 +86,7 @@ impl<Anim: 'static+Animation+EditableAnimation> KeyFrameControlsController<Anim>
             onion_skin:     onion_skin.clone(),
             timeline:       timeline.clone(),
             current_time:   timeline.current_time.clone(),
-            selected_layer: timeline.selected_layer.clone(),
+            selected_layer: timeline.selected_vector_layer.clone(),
             edit_sink:      Desync::new(edit_sink),
             debug_model:    model.clone()
         }
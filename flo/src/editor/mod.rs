@@ -6,6 +6,7 @@ mod timeline_layer_list_controller;
 mod timeline_layer_controls_controller;
 mod controlbar_controller;
 mod keyframe_controls_controller;
+mod undo_controls_controller;
 mod toolbox_controller;
 
 pub use self::editor_controller::*;
@@ -166,7 +166,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
 
         let duration                    = BindRef::new(&anim_model.timeline().duration);
         let frame_duration              = BindRef::new(&anim_model.timeline().frame_duration);
-        let layers                      = BindRef::new(&anim_model.timeline().layers);
+        let layers                      = BindRef::new(&anim_model.timeline().displayed_layers);
 
         let virtual_scale_control       = virtual_scale.control();
         let virtual_keyframes_control   = virtual_keyframes.control();
@@ -191,7 +191,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
     ///
     /// Creates the user interface for the timeline
     ///
-    fn ui(layers: BindRef<Vec<LayerModel>>, duration: BindRef<Duration>, frame_duration: BindRef<Duration>, virtual_scale_control: BindRef<Control>, virtual_keyframes_control: BindRef<Control>, canvases: Arc<ResourceManager<BindingCanvas>>, onion_skin: &OnionSkinModel<Anim>) -> BindRef<Control> {
+    fn ui(layers: BindRef<Vec<(usize, LayerModel)>>, duration: BindRef<Duration>, frame_duration: BindRef<Duration>, virtual_scale_control: BindRef<Control>, virtual_keyframes_control: BindRef<Control>, canvases: Arc<ResourceManager<BindingCanvas>>, onion_skin: &OnionSkinModel<Anim>) -> BindRef<Control> {
         let timescale_indicator         = BindingCanvas::with_drawing(Self::draw_frame_indicator);
         let timescale_indicator         = canvases.register(timescale_indicator);
 
@@ -376,7 +376,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
             let start_tick  = start_tick.max(0.0) as u32;
             let end_tick    = end_tick.max(0.0) as u32;
             let keyframes   = timeline.get_keyframe_binding(start_tick..end_tick);
-            let layers      = BindRef::new(&timeline.layers);
+            let layers      = BindRef::new(&timeline.displayed_layers);
 
             // Generate the drawing function for this part of the canvas
             Box::new(move |gc| {
@@ -388,7 +388,7 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineController<Anim> {
 
                 let index_for_layer = layers.iter()
                     .enumerate()
-                    .map(|(index, layer)| (layer.id, index))
+                    .map(|(index, (_depth, layer))| (layer.id, index))
                     .collect::<HashMap<_, _>>();
 
                 // Center the drawing region
@@ -2,6 +2,7 @@ use super::super::style::*;
 use super::super::model::*;
 
 use flo_ui::*;
+use flo_canvas::*;
 use flo_binding::*;
 use flo_animation::*;
 
@@ -10,6 +11,9 @@ use futures::*;
 use futures::executor;
 use futures::executor::Spawn;
 
+/// The blend modes that can be picked for a layer, in the order they're cycled through
+const LAYER_BLEND_MODES: [BlendMode; 5] = [BlendMode::SourceOver, BlendMode::Multiply, BlendMode::Screen, BlendMode::Darken, BlendMode::Lighten];
+
 ///
 /// Controller that provides controls for adding/deleting/editing layers (generally displayed above the main layer list)
 ///
@@ -32,10 +36,10 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineLayerControlsController<
     /// Creates a new timeline layer controls controller
     ///
     pub fn new(model: &FloModel<Anim>) -> TimelineLayerControlsController<Anim> {
-        let ui          = Self::ui();
+        let timeline    = model.timeline().clone();
+        let ui          = Self::ui(&timeline);
         let edit        = executor::spawn(model.edit());
         let animation   = Box::new(model.clone());
-        let timeline    = model.timeline().clone();
 
         TimelineLayerControlsController {
             ui:         ui,
@@ -45,12 +49,59 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineLayerControlsController<
         }
     }
 
+    ///
+    /// Returns the layer model for the currently selected layer
+    ///
+    fn selected_layer(layers: &BindRef<Vec<LayerModel>>, selected_layer: &Binding<Option<u64>>) -> Option<LayerModel> {
+        let selected_layer = selected_layer.get();
+
+        layers.get()
+            .into_iter()
+            .filter(|layer| Some(layer.id) == selected_layer)
+            .nth(0)
+    }
+
+    ///
+    /// Returns the group that new layers should be created in
+    ///
+    /// New layers go in the selected group, or in the same group as the selected layer if it is not a group.
+    ///
+    fn group_for_new_layers(layers: &BindRef<Vec<LayerModel>>, selected_layer: &Binding<Option<u64>>) -> Option<u64> {
+        Self::selected_layer(layers, selected_layer)
+            .and_then(|layer| if layer.is_group { Some(layer.id) } else { layer.parent_group.get() })
+    }
+
+    ///
+    /// Returns the name to display for a blend mode
+    ///
+    fn blend_mode_name(blend_mode: BlendMode) -> &'static str {
+        use self::BlendMode::*;
+
+        match blend_mode {
+            SourceOver      => "Normal",
+            Multiply        => "Multiply",
+            Screen          => "Screen",
+            Darken          => "Darken",
+            Lighten         => "Lighten",
+            _               => "Custom"
+        }
+    }
+
     ///
     /// Creates the UI for the layer controls controller
     ///
-    fn ui() -> BindRef<Control> {
+    fn ui(timeline: &TimelineModel<Anim>) -> BindRef<Control> {
+        let layers          = timeline.layers.clone();
+        let selected_layer  = timeline.selected_layer.clone();
+
         // Create the UI
         let ui = computed(move || {
+            // The opacity and blend mode controls apply to the selected layer
+            let selected_layer  = Self::selected_layer(&layers, &selected_layer);
+            let has_selection   = selected_layer.is_some();
+            let opacity         = selected_layer.as_ref().map(|layer| layer.opacity.get()).unwrap_or(1.0);
+            let blend_mode      = selected_layer.as_ref().map(|layer| layer.blend_mode.get()).unwrap_or(BlendMode::SourceOver);
+
             Control::container()
                 .with(Bounds::fill_all())
                 .with(vec![
@@ -59,11 +110,31 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineLayerControlsController<
                         .with(Font::Weight(FontWeight::ExtraBold))
                         .with(ControlAttribute::Padding((4, 2), (4, 2)))
                         .with(vec![
+                            Control::button()
+                                .with(Bounds::next_horiz(56.0))
+                                .with(State::Enabled(Property::Bool(has_selection)))
+                                .with((ActionTrigger::Click, "CycleBlendMode"))
+                                .with(vec![
+                                    Control::label()
+                                        .with(Bounds::fill_all())
+                                        .with(TextAlign::Center)
+                                        .with(Font::Size(10.0))
+                                        .with(Font::Weight(FontWeight::Normal))
+                                        .with(Self::blend_mode_name(blend_mode))
+                                ]),
+                            Control::empty()
+                                .with(Bounds::next_horiz(4.0)),
+                            Control::slider()
+                                .with(State::Range((0.0.to_property(), 1.0.to_property())))
+                                .with(State::Value(opacity.to_property()))
+                                .with(State::Enabled(Property::Bool(has_selection)))
+                                .with(Bounds::next_horiz(48.0))
+                                .with((ActionTrigger::SetValue, "SetOpacity")),
                             Control::empty()
                                 .with(Bounds::stretch_horiz(1.0)),
                             Control::container()
                                 .with(Hint::Class("button-group".to_string()))
-                                .with(Bounds::next_horiz(36.0))
+                                .with(Bounds::next_horiz(54.0))
                                 .with(vec![
                                     Control::button()
                                         .with(Bounds::next_horiz(18.0))
@@ -74,6 +145,15 @@ impl<Anim: 'static+Animation+EditableAnimation> TimelineLayerControlsController<
                                                 .with(TextAlign::Center)
                                                 .with("+")
                                         ]),
+                                    Control::button()
+                                        .with(Bounds::next_horiz(18.0))
+                                        .with((ActionTrigger::Click, "AddNewGroup"))
+                                        .with(vec![
+                                            Control::label()
+                                                .with(Bounds::fill_all())
+                                                .with(TextAlign::Center)
+                                                .with("\u{25a4}")
+                                        ]),
                                     Control::button()
                                         .with(Bounds::next_horiz(18.0))
                                         .with((ActionTrigger::Click, "RemoveLayer"))
@@ -102,17 +182,55 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for TimelineLayerCont
         BindRef::clone(&self.ui)
     }
 
-    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
         match action_id {
+            "SetOpacity" => {
+                if let (ActionParameter::Value(PropertyValue::Float(opacity)), Some(layer_id)) = (action_parameter, self.timeline.selected_layer.get()) {
+                    let opacity = opacity.max(0.0).min(1.0);
+
+                    self.edit.sync(|animation| {
+                        animation.wait_send(vec![
+                            AnimationEdit::Layer(layer_id, LayerEdit::SetOpacity(opacity))
+                        ])
+                    }).unwrap();
+
+                    self.timeline.invalidate_canvas();
+                }
+            },
+
+            "CycleBlendMode" => {
+                if let Some(layer) = Self::selected_layer(&self.timeline.layers, &self.timeline.selected_layer) {
+                    // Move to the next blend mode in the list (or back to normal if the layer has some other blend mode)
+                    let current_mode    = layer.blend_mode.get();
+                    let next_index      = LAYER_BLEND_MODES.iter()
+                        .position(|mode| *mode == current_mode)
+                        .map(|index| (index+1) % LAYER_BLEND_MODES.len())
+                        .unwrap_or(0);
+                    let next_mode       = LAYER_BLEND_MODES[next_index];
+
+                    self.edit.sync(|animation| {
+                        animation.wait_send(vec![
+                            AnimationEdit::Layer(layer.id, LayerEdit::SetBlendMode(next_mode))
+                        ])
+                    }).unwrap();
+
+                    self.timeline.invalidate_canvas();
+                }
+            },
+
             "AddNewLayer" => {
                 // Pick a layer ID for the new layer
                 let new_layer_id = self.animation.get_layer_ids().into_iter().max().unwrap_or(0) + 1;
+                let parent_group = Self::group_for_new_layers(&self.timeline.layers, &self.timeline.selected_layer);
 
                 // Send to the animation
+                let mut edits = vec![AnimationEdit::AddNewLayer(new_layer_id)];
+                if parent_group.is_some() {
+                    edits.push(AnimationEdit::Layer(new_layer_id, LayerEdit::SetParentGroup(parent_group)));
+                }
+
                 self.edit.sync(|animation| {
-                    animation.wait_send(vec![
-                        AnimationEdit::AddNewLayer(new_layer_id),
-                    ])
+                    animation.wait_send(edits)
                 }).unwrap();
 
                 // Select the new layer
@@ -123,6 +241,35 @@ impl<Anim: 'static+Animation+EditableAnimation> Controller for TimelineLayerCont
                 self.timeline.invalidate_canvas();
             },
 
+            "AddNewGroup" => {
+                // The new group is created alongside the selected layer, which is moved into it
+                let new_group_id    = self.animation.get_layer_ids().into_iter().max().unwrap_or(0) + 1;
+                let selected_layer  = Self::selected_layer(&self.timeline.layers, &self.timeline.selected_layer);
+
+                let mut edits       = vec![AnimationEdit::AddNewGroupLayer(new_group_id)];
+                if let Some(ref selected_layer) = selected_layer {
+                    let parent_group = selected_layer.parent_group.get();
+
+                    if parent_group.is_some() {
+                        edits.push(AnimationEdit::Layer(new_group_id, LayerEdit::SetParentGroup(parent_group)));
+                    }
+                    edits.push(AnimationEdit::Layer(selected_layer.id, LayerEdit::SetParentGroup(Some(new_group_id))));
+                }
+
+                // Send to the animation
+                self.edit.sync(|animation| {
+                    animation.wait_send(edits)
+                }).unwrap();
+
+                // Groups can't be drawn on, so the layer that was moved into the group stays selected (the new group is only selected if it's empty)
+                let new_selection = selected_layer.map(|layer| layer.id).unwrap_or(new_group_id);
+                self.timeline.selected_layer.set(Some(new_selection));
+
+                // Update the model
+                self.timeline.update_keyframe_bindings();
+                self.timeline.invalidate_canvas();
+            },
+
             "RemoveLayer" => {
                 // This will remove the selected layer
                 let layer_to_remove = self.timeline.selected_layer.get();
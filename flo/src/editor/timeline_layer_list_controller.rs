@@ -24,6 +24,9 @@ pub struct TimelineLayerListController {
     /// The currently selected layer
     selected_layer_id: Binding<Option<u64>>,
 
+    /// The layers in the timeline
+    layers: BindRef<Vec<LayerModel>>,
+
     /// The layer whose name is being edited
     editing_layer_id: Binding<Option<u64>>
 }
@@ -35,6 +38,7 @@ impl TimelineLayerListController {
     pub fn new<Anim: 'static+Animation+EditableAnimation>(model: &FloModel<Anim>) -> TimelineLayerListController {
         // Create the UI from the model
         let selected_layer_id   = model.timeline().selected_layer.clone();
+        let layers              = model.timeline().layers.clone();
         let editing_layer_id    = bind(None);
         let ui                  = Self::ui(model, BindRef::from(editing_layer_id.clone()));
 
@@ -44,6 +48,7 @@ impl TimelineLayerListController {
             ui:                 ui,
             edit_sink:          Desync::new(edit_sink),
             selected_layer_id:  selected_layer_id,
+            layers:             layers,
             editing_layer_id:   editing_layer_id
         }
     }
@@ -51,9 +56,12 @@ impl TimelineLayerListController {
     ///
     /// Creates a control from a layer model
     ///
-    fn layer_label(model: &LayerModel, selected_layer_id: Option<u64>, editing_layer_id: Option<u64>) -> Control {
+    fn layer_label(model: &LayerModel, depth: usize, selected_layer_id: Option<u64>, editing_layer_id: Option<u64>) -> Control {
         let name        = model.name.get();
         let layer_id    = model.id;
+        let visible     = model.visible.get();
+        let locked      = model.locked.get();
+        let collapsed   = model.collapsed.get();
 
         let is_selected = Some(layer_id) == selected_layer_id;
         let is_editing  = Some(layer_id) == editing_layer_id;
@@ -64,10 +72,42 @@ impl TimelineLayerListController {
             .with(ControlAttribute::Padding((4, 1), (1, 1)))
             .with(Appearance::Background(background))
             .with(vec![
+                Control::button()
+                    .with(Bounds::next_horiz(16.0))
+                    .with(State::Selected(Property::Bool(visible)))
+                    .with((ActionTrigger::Click, format!("ToggleVisible-{}", layer_id)))
+                    .with(vec![
+                        Control::label()
+                            .with(Bounds::fill_all())
+                            .with(TextAlign::Center)
+                            .with(if visible { "\u{25c9}" } else { "\u{25cb}" })
+                    ]),
+                Control::button()
+                    .with(Bounds::next_horiz(16.0))
+                    .with(State::Selected(Property::Bool(locked)))
+                    .with((ActionTrigger::Click, format!("ToggleLocked-{}", layer_id)))
+                    .with(vec![
+                        Control::label()
+                            .with(Bounds::fill_all())
+                            .with(TextAlign::Center)
+                            .with(if locked { "\u{25a3}" } else { "\u{25a1}" })
+                    ]),
                 Control::empty()
-                    .with(Bounds::next_horiz(20.0)),
-                Control::empty()
-                    .with(Bounds::next_horiz(2.0)),
+                    .with(Bounds::next_horiz(2.0 + (depth as f32)*12.0)),
+                if model.is_group {
+                    Control::button()
+                        .with(Bounds::next_horiz(12.0))
+                        .with((ActionTrigger::Click, format!("ToggleCollapsed-{}", layer_id)))
+                        .with(vec![
+                            Control::label()
+                                .with(Bounds::fill_all())
+                                .with(TextAlign::Center)
+                                .with(if collapsed { "\u{25b8}" } else { "\u{25be}" })
+                        ])
+                } else {
+                    Control::empty()
+                        .with(Bounds::next_horiz(12.0))
+                },
                 if is_editing {
                     Control::text_box()
                         .with(name)
@@ -93,7 +133,7 @@ impl TimelineLayerListController {
     ///
     fn ui<Anim: 'static+Animation>(model: &FloModel<Anim>, editing_layer_id: BindRef<Option<u64>>) -> BindRef<Control> {
         // Extract the bindings we're going to use from the model
-        let layers          = model.timeline().layers.clone();
+        let layers          = model.timeline().displayed_layers.clone();
         let selected_layer  = model.timeline().selected_layer.clone();
 
         // Generate the UI
@@ -105,9 +145,9 @@ impl TimelineLayerListController {
 
             // Each layer creates a control
             let layer_controls = layers.into_iter()
-                .flat_map(|layer_model| {
+                .flat_map(|(depth, layer_model)| {
                     // The layer is a simple label
-                    let label = Self::layer_label(&layer_model, selected_layer, editing_layer);
+                    let label = Self::layer_label(&layer_model, depth, selected_layer, editing_layer);
 
                     // Each layer is followed by a divider
                     let divider = Control::empty()
@@ -135,6 +175,14 @@ impl Controller for TimelineLayerListController {
     }
 
     fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        // Finds the layer model for the layer ID at the end of an action ID
+        let layer_for_action = |prefix: &str| {
+            let (_, layer_id)   = action_id.split_at(prefix.len());
+            let layer_id        = u64::from_str_radix(layer_id, 10).unwrap();
+
+            self.layers.get().into_iter().filter(|layer| layer.id == layer_id).nth(0)
+        };
+
         match action_id {
             "CancelEditingLayer" => self.editing_layer_id.set(None),
 
@@ -169,6 +217,34 @@ impl Controller for TimelineLayerListController {
 
                     // Update the model
                     self.editing_layer_id.set(Some(layer_id));
+                } else if action_id.starts_with("ToggleVisible-") {
+                    // Show or hide the layer
+                    if let Some(layer) = layer_for_action("ToggleVisible-") {
+                        let (layer_id, visible) = (layer.id, layer.visible.get());
+
+                        self.edit_sink.desync(move |edit_sink| {
+                            edit_sink.wait_send(vec![
+                                AnimationEdit::Layer(layer_id, LayerEdit::SetVisible(!visible))
+                            ]).ok();
+                        });
+                    }
+                } else if action_id.starts_with("ToggleCollapsed-") {
+                    // Show or hide the layers in a group (this only affects the timeline, so isn't an edit)
+                    if let Some(layer) = layer_for_action("ToggleCollapsed-") {
+                        let collapsed = layer.collapsed.get();
+                        layer.collapsed.set(!collapsed);
+                    }
+                } else if action_id.starts_with("ToggleLocked-") {
+                    // Lock or unlock the layer
+                    if let Some(layer) = layer_for_action("ToggleLocked-") {
+                        let (layer_id, locked) = (layer.id, layer.locked.get());
+
+                        self.edit_sink.desync(move |edit_sink| {
+                            edit_sink.wait_send(vec![
+                                AnimationEdit::Layer(layer_id, LayerEdit::SetLocked(!locked))
+                            ]).ok();
+                        });
+                    }
                 }
             }
         }
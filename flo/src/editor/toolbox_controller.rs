@@ -95,22 +95,38 @@ impl<Anim: 'static+EditableAnimation+Animation> ToolboxController<Anim> {
         // Load the tool images
         let select      = images.register(svg_static(include_bytes!("../../svg/tools/select.svg")));
         let adjust      = images.register(svg_static(include_bytes!("../../svg/tools/adjust.svg")));
+        let transform   = images.register(svg_static(include_bytes!("../../svg/tools/transform.svg")));
         let pan         = images.register(svg_static(include_bytes!("../../svg/tools/pan.svg")));
 
         let pencil      = images.register(svg_static(include_bytes!("../../svg/tools/pencil.svg")));
         let ink         = images.register(svg_static(include_bytes!("../../svg/tools/ink.svg")));
+        let calligraphy = images.register(svg_static(include_bytes!("../../svg/tools/calligraphy.svg")));
         let eraser      = images.register(svg_static(include_bytes!("../../svg/tools/eraser.svg")));
         let floodfill   = images.register(svg_static(include_bytes!("../../svg/tools/floodfill.svg")));
+        let text        = images.register(svg_static(include_bytes!("../../svg/tools/text.svg")));
+
+        let rectangle   = images.register(svg_static(include_bytes!("../../svg/tools/rectangle.svg")));
+        let ellipse     = images.register(svg_static(include_bytes!("../../svg/tools/ellipse.svg")));
+        let polygon     = images.register(svg_static(include_bytes!("../../svg/tools/polygon.svg")));
+        let line        = images.register(svg_static(include_bytes!("../../svg/tools/line.svg")));
 
         // Assign names to them
         images.assign_name(&select, "select");
         images.assign_name(&adjust, "adjust");
+        images.assign_name(&transform, "transform");
         images.assign_name(&pan, "pan");
 
         images.assign_name(&pencil, "pencil");
         images.assign_name(&ink, "ink");
+        images.assign_name(&calligraphy, "calligraphy");
         images.assign_name(&eraser, "eraser");
         images.assign_name(&floodfill, "floodfill");
+        images.assign_name(&text, "text");
+
+        images.assign_name(&rectangle, "rectangle");
+        images.assign_name(&ellipse, "ellipse");
+        images.assign_name(&polygon, "polygon");
+        images.assign_name(&line, "line");
 
         images
     }
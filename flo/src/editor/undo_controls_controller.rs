@@ -0,0 +1,104 @@
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+
+///
+/// Provides the buttons for undoing and redoing edits
+///
+/// (These are the actions that are usually bound to Ctrl+Z and Ctrl+Shift+Z)
+///
+pub struct UndoControlsController<Anim: 'static+Animation+EditableAnimation> {
+    /// The UI for this controller
+    ui: BindRef<Control>,
+
+    /// The view model for this controller
+    view_model: Arc<DynamicViewModel>,
+
+    /// The model that we'll undo or redo edits for
+    model: FloModel<Anim>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> UndoControlsController<Anim> {
+    ///
+    /// Creates a new undo controls controller
+    ///
+    pub fn new(model: &FloModel<Anim>) -> UndoControlsController<Anim> {
+        // Create the viewmodel
+        let view_model  = Arc::new(DynamicViewModel::new());
+        let can_undo    = model.undo_history().can_undo.clone();
+        let can_redo    = model.undo_history().can_redo.clone();
+
+        view_model.set_computed("CanUndo", move || PropertyValue::Bool(can_undo.get()));
+        view_model.set_computed("CanRedo", move || PropertyValue::Bool(can_redo.get()));
+
+        // Create the UI
+        let ui          = Self::ui();
+
+        UndoControlsController {
+            ui:         ui,
+            view_model: view_model,
+            model:      model.clone()
+        }
+    }
+
+    ///
+    /// Creates the UI for this controller
+    ///
+    fn ui() -> BindRef<Control> {
+        let ui = Control::container()
+            .with(vec![
+                Control::empty()
+                    .with(Bounds::next_horiz(3.0)),
+                Control::container()
+                    .with(Hint::Class("button-group".to_string()))
+                    .with(vec![
+                        Control::button()
+                            .with(vec![Control::label().with("Undo").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(ControlAttribute::Padding((9, 4), (4, 4)))
+                            .with(State::Enabled(Property::bound("CanUndo")))
+                            .with((ActionTrigger::Click, "Undo"))
+                            .with(Bounds::next_horiz(44.0)),
+
+                        Control::button()
+                            .with(vec![Control::label().with("Redo").with(TextAlign::Center).with(Bounds::fill_all())])
+                            .with(ControlAttribute::Padding((4, 4), (9, 4)))
+                            .with(State::Enabled(Property::bound("CanRedo")))
+                            .with((ActionTrigger::Click, "Redo"))
+                            .with(Bounds::next_horiz(44.0)),
+                    ])
+                    .with(Bounds::next_horiz(44.0*2.0)),
+                Control::empty()
+                    .with(Bounds::next_horiz(3.0)),
+                Control::empty()
+                    .with(Appearance::Background(TIMESCALE_LAYERS))
+                    .with(Bounds::next_horiz(1.0))
+            ])
+            .with(Bounds::fill_all());
+
+        BindRef::from(bind(ui))
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> Controller for UndoControlsController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::clone(&self.ui)
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        match action_id {
+            "Undo"  => { self.model.undo(); },
+            "Redo"  => { self.model.redo(); },
+
+            _       => { }
+        }
+    }
+}
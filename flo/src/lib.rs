@@ -7,6 +7,8 @@ extern crate flo_canvas;
 extern crate flo_binding;
 extern crate flo_ui_files;
 extern crate flo_animation;
+extern crate flo_import;
+extern crate flo_export;
 
 extern crate desync;
 extern crate serde;
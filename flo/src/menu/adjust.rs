@@ -1,31 +1,79 @@
 use super::controls;
+use super::super::model::*;
 
 use flo_ui::*;
+use flo_canvas::*;
 use flo_binding::*;
+use flo_animation::*;
+
+use desync::*;
+use futures::*;
+use futures::executor;
+use futures::executor::Spawn;
+
+use std::sync::*;
+use std::time::Duration;
+use std::collections::HashSet;
+
+/// Width of the easing graph in the menu
+const GRAPH_WIDTH: f32 = 40.0;
+
+/// Height of the easing graph in the menu
+const GRAPH_HEIGHT: f32 = 32.0 - 6.0;
 
 ///
 /// The menu controller for the adjust tool
 ///
-pub struct AdjustMenuController {
+pub struct AdjustMenuController<Anim: Animation> {
+    /// The animation editing stream where this will send updates
+    edit: Desync<Spawn<Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send>>>,
+
+    /// The model for the animation (used for finding the motions attached to the selected elements)
+    flo_model: FloModel<Anim>,
+
+    /// The canvases for this menu
+    canvases: Arc<ResourceManager<BindingCanvas>>,
+
+    /// The UI for this menu
     ui: BindRef<Control>
 }
 
-impl AdjustMenuController {
+impl<Anim: 'static+EditableAnimation+Animation> AdjustMenuController<Anim> {
     ///
     /// Creates a new adjust menu controller
     ///
-    pub fn new() -> AdjustMenuController {
-        let ui = Self::ui();
+    pub fn new(flo_model: &FloModel<Anim>) -> AdjustMenuController<Anim> {
+        let edit        = Desync::new(executor::spawn(flo_model.edit()));
+        let flo_model   = flo_model.clone();
+
+        // Create the canvases
+        let canvases        = Arc::new(ResourceManager::new());
+
+        let easing_graph    = Self::easing_graph(&flo_model);
+        let easing_graph    = canvases.register(easing_graph);
+        canvases.assign_name(&easing_graph, "EasingGraph");
+
+        let ui = Self::ui(easing_graph);
 
         AdjustMenuController {
-            ui: ui
+            edit:       edit,
+            flo_model:  flo_model,
+            canvases:   canvases,
+            ui:         ui
         }
     }
 
     ///
     /// Creates the UI for the adjust menu controller
     ///
-    fn ui() -> BindRef<Control> {
+    fn ui(easing_graph: Resource<BindingCanvas>) -> BindRef<Control> {
+        let easing_button = |label: &str, action: &str, width: f32| {
+            Control::button()
+                .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+                .with((ActionTrigger::Click, action))
+                .with(Bounds::next_horiz(width))
+        };
+
         let ui = bind(Control::container()
                     .with(Bounds::fill_all())
                     .with(ControlAttribute::Padding((0, 3), (0, 3)))
@@ -37,16 +85,151 @@ impl AdjustMenuController {
                             .with(FontWeight::Light)
                             .with(TextAlign::Right)
                             .with(Font::Size(14.0))
-                            .with(Bounds::next_horiz(48.0))
+                            .with(Bounds::next_horiz(48.0)),
+
+                        controls::divider(),
+
+                        Control::label()
+                            .with("Easing:")
+                            .with(TextAlign::Right)
+                            .with(Font::Size(13.0))
+                            .with(Bounds::next_horiz(48.0)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(6.0)),
+                        Control::canvas()
+                            .with(easing_graph)
+                            .with(Bounds::next_horiz(GRAPH_WIDTH)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(6.0)),
+                        Control::container()
+                            .with(Hint::Class("button-group".to_string()))
+                            .with(ControlAttribute::Padding((0,2), (0,2)))
+                            .with(Font::Size(9.0))
+                            .with(Bounds::next_horiz(36.0*7.0))
+                            .with(vec![
+                                easing_button("Linear", "EaseLinear", 36.0),
+                                easing_button("In", "EaseIn", 36.0),
+                                easing_button("Out", "EaseOut", 36.0),
+                                easing_button("In/Out", "EaseInOut", 36.0),
+                                easing_button("Over", "EaseOvershoot", 36.0),
+                                easing_button("Bounce", "EaseBounce", 36.0),
+                                easing_button("Step", "EaseStep", 36.0)
+                            ])
                     ])
             );
 
         BindRef::from(ui)
     }
+
+    ///
+    /// Finds the progress samples for the section of the first motion curve attached to the selection at the specified time
+    ///
+    fn selected_section_progress(flo_model: &FloModel<Anim>, selection: &Arc<HashSet<ElementId>>, when: Duration) -> Vec<(f32, f32)> {
+        let when_millis = to_millis(when) as f32;
+
+        selection.iter()
+            .flat_map(|element_id| flo_model.motion().get_motions_for_element(*element_id))
+            .filter_map(|motion_id| flo_model.motion().get_motion(motion_id))
+            .filter_map(|motion| {
+                let curve = motion.path().or(motion.rotation()).or(motion.scale())?;
+                curve.section_at_time(when_millis).map(|section| curve.section_progress(section, 32))
+            })
+            .nth(0)
+            .unwrap_or_else(|| vec![])
+    }
+
+    ///
+    /// Creates the graph showing the easing of the motion attached to the selected elements at the current time
+    ///
+    fn easing_graph(flo_model: &FloModel<Anim>) -> BindingCanvas {
+        let selection       = flo_model.selection().selected_elements.clone();
+        let current_time    = flo_model.timeline().current_time.clone();
+        let invalidated     = flo_model.timeline().canvas_invalidation_count.clone();
+        let flo_model       = flo_model.clone();
+
+        BindingCanvas::with_drawing(move |gc| {
+            // Redraw whenever the selection, time or animation changes
+            let selection   = selection.get();
+            let when        = current_time.get();
+            invalidated.get();
+
+            // Canvas height should match the control height
+            gc.canvas_height(GRAPH_HEIGHT);
+            gc.center_region(-GRAPH_WIDTH/2.0, -GRAPH_HEIGHT/2.0, GRAPH_WIDTH/2.0, GRAPH_HEIGHT/2.0);
+
+            // Clear the background
+            gc.layer(0);
+            gc.fill_color(Color::Rgba(0.9, 0.9, 0.9, 1.0));
+            gc.rect(-GRAPH_WIDTH/2.0, -GRAPH_HEIGHT/2.0, GRAPH_WIDTH/2.0, GRAPH_HEIGHT/2.0);
+            gc.fill();
+
+            // Draw the progress through the section against time
+            let progress        = Self::selected_section_progress(&flo_model, &selection, when);
+            let (left, right)   = (-GRAPH_WIDTH/2.0 + 3.0, GRAPH_WIDTH/2.0 - 3.0);
+            let (bottom, top)   = (-GRAPH_HEIGHT/2.0 + 6.0, GRAPH_HEIGHT/2.0 - 6.0);
+            let to_graph        = |(time, progress): (f32, f32)| (left + time*(right-left), bottom + progress*(top-bottom));
+
+            // Lines marking the start and end of the section
+            gc.new_path();
+            gc.move_to(left, bottom);
+            gc.line_to(right, bottom);
+            gc.move_to(left, top);
+            gc.line_to(right, top);
+            gc.line_width(0.5);
+            gc.stroke_color(Color::Rgba(0.6, 0.6, 0.6, 1.0));
+            gc.stroke();
+
+            if progress.len() > 1 {
+                let (x, y) = to_graph(progress[0]);
+
+                gc.new_path();
+                gc.move_to(x, y);
+                for point in progress.into_iter().skip(1) {
+                    let (x, y) = to_graph(point);
+                    gc.line_to(x, y);
+                }
+
+                gc.line_width(1.5);
+                gc.stroke_color(Color::Rgba(0.1, 0.3, 0.7, 1.0));
+                gc.stroke();
+            }
+        })
+    }
 }
 
-impl Controller for AdjustMenuController {
+impl<Anim: 'static+EditableAnimation+Animation> Controller for AdjustMenuController<Anim> {
     fn ui(&self) -> BindRef<Control> {
         self.ui.clone()
     }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(self.canvases.clone())
+    }
+
+    fn action(&self, action_id: &str, _action_parameter: &ActionParameter) {
+        let easing = match action_id {
+            "EaseLinear"    => Easing::Linear,
+            "EaseIn"        => Easing::EaseIn,
+            "EaseOut"       => Easing::EaseOut,
+            "EaseInOut"     => Easing::EaseInOut,
+            "EaseOvershoot" => Easing::Overshoot,
+            "EaseBounce"    => Easing::Bounce,
+            "EaseStep"      => Easing::Step,
+
+            _               => { return; }
+        };
+
+        // Apply the easing to the motions attached to the selected elements at the current time
+        let selection   = self.flo_model.selection().selected_elements.get().iter().cloned().collect();
+        let when        = self.flo_model.timeline().current_time.get();
+        let edits       = MotionEditAction::SetEasing(selection, when, easing).to_animation_edits(&self.flo_model);
+
+        if edits.len() > 0 {
+            self.edit.sync(move |animation| {
+                animation.wait_send(edits).ok();
+            });
+
+            self.flo_model.timeline().invalidate_canvas();
+        }
+    }
 }
@@ -0,0 +1,519 @@
+use super::ink::*;
+use super::controls;
+use super::super::color::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+use flo_animation::brushes::*;
+
+use std::f32;
+use std::sync::*;
+
+///
+/// Controller used for the calligraphy tool
+///
+pub struct CalligraphyMenuController {
+    size:               Binding<f32>,
+    opacity:            Binding<f32>,
+    nib_angle:          Binding<f32>,
+    nib_aspect_ratio:   Binding<f32>,
+    tilt_influence:     Binding<f32>,
+    modification_mode:  Binding<BrushModificationMode>,
+    representation:     Binding<BrushRepresentation>,
+
+    canvases:           Arc<ResourceManager<BindingCanvas>>,
+    images:             Arc<ResourceManager<Image>>,
+    ui:                 BindRef<Control>,
+    view_model:         Arc<DynamicViewModel>,
+
+    color_picker_open:  Binding<bool>,
+    color_picker:       Arc<PopupController<ColorPickerController>>
+}
+
+impl CalligraphyMenuController {
+    ///
+    /// The image resources for the calligraphy menu controller
+    ///
+    fn images() -> ResourceManager<Image> {
+        let images = ResourceManager::new();
+
+        let additive_mode           = images.register(svg_static(include_bytes!("../../svg/brush_modes/additive.svg")));
+        let individual_mode         = images.register(svg_static(include_bytes!("../../svg/brush_modes/individual.svg")));
+        let path_editing            = images.register(svg_static(include_bytes!("../../svg/brush_modes/path_editing.svg")));
+        let brush_stroke            = images.register(svg_static(include_bytes!("../../svg/brush_modes/brush_stroke.svg")));
+
+        images.assign_name(&additive_mode,          "additive_mode");
+        images.assign_name(&individual_mode,        "individual_mode");
+        images.assign_name(&path_editing,           "path_editing");
+        images.assign_name(&brush_stroke,           "brush_stroke");
+
+        images
+    }
+
+    ///
+    /// Creates a new calligraphy menu controller
+    ///
+    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, nib_angle: &Binding<f32>, nib_aspect_ratio: &Binding<f32>, tilt_influence: &Binding<f32>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> CalligraphyMenuController {
+        // Set up the view model
+        let view_model = Arc::new(DynamicViewModel::new());
+
+        let vm_size                 = size.clone();
+        let vm_opacity              = opacity.clone();
+        let vm_nib_angle            = nib_angle.clone();
+        let vm_nib_aspect_ratio     = nib_aspect_ratio.clone();
+        let vm_tilt_influence       = tilt_influence.clone();
+
+        view_model.set_computed("Size", move || PropertyValue::Float(vm_size.get() as f64));
+        view_model.set_computed("Opacity", move || PropertyValue::Float(vm_opacity.get() as f64));
+        view_model.set_computed("NibAngle", move || PropertyValue::Float(vm_nib_angle.get() as f64));
+        view_model.set_computed("NibAspectRatio", move || PropertyValue::Float(vm_nib_aspect_ratio.get() as f64));
+        view_model.set_computed("TiltInfluence", move || PropertyValue::Float(vm_tilt_influence.get() as f64));
+
+        view_model.set_property("EditSize", PropertyValue::Bool(false));
+        view_model.set_property("EditOpacity", PropertyValue::Bool(false));
+
+        // Create the colour picker popup
+        let color_picker_open   = Binding::new(false);
+        let color_picker        = ColorPickerController::new(colour);
+        let color_picker        = PopupController::new(color_picker, &color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+
+        let vm_color_picker_open = color_picker_open.clone();
+        view_model.set_computed("ColorPickerOpen", move || PropertyValue::Bool(vm_color_picker_open.get()));
+
+        // Images
+        let images                  = Arc::new(Self::images());
+
+        // Create the canvases
+        let canvases                = Arc::new(ResourceManager::new());
+
+        let brush_preview           = Self::brush_preview(size, opacity, colour, nib_angle, nib_aspect_ratio);
+        let brush_preview           = canvases.register(brush_preview);
+        canvases.assign_name(&brush_preview, "BrushPreview");
+
+        let nib_preview             = Self::nib_preview(nib_angle, nib_aspect_ratio);
+        let nib_preview             = canvases.register(nib_preview);
+        canvases.assign_name(&nib_preview, "NibPreview");
+
+        let size_preview            = InkMenuController::size_preview(size, 32.0 - 6.0);
+        let size_preview            = canvases.register(size_preview);
+        canvases.assign_name(&size_preview, "SizePreview");
+
+        let size_preview_large      = InkMenuController::size_preview(size, 100.0);
+        let size_preview_large      = canvases.register(size_preview_large);
+        canvases.assign_name(&size_preview_large, "SizePreview2");
+
+        let opacity_preview         = InkMenuController::opacity_preview(opacity, 32.0-6.0);
+        let opacity_preview         = canvases.register(opacity_preview);
+        canvases.assign_name(&opacity_preview, "OpacityPreview");
+
+        let opacity_preview_large   = InkMenuController::opacity_preview(opacity, 84.0);
+        let opacity_preview_large   = canvases.register(opacity_preview_large);
+        canvases.assign_name(&opacity_preview_large, "OpacityPreview2");
+
+        let colour_preview          = InkMenuController::colour_preview(colour);
+        let colour_preview          = canvases.register(colour_preview);
+        canvases.assign_name(&colour_preview, "ColourPreview");
+
+        // Generate the UI
+        let ui = Self::ui(&canvases, &images, modification_mode, representation);
+
+        // Finalize the control
+        CalligraphyMenuController {
+            size:               size.clone(),
+            opacity:            opacity.clone(),
+            nib_angle:          nib_angle.clone(),
+            nib_aspect_ratio:   nib_aspect_ratio.clone(),
+            tilt_influence:     tilt_influence.clone(),
+            modification_mode:  modification_mode.clone(),
+            representation:     representation.clone(),
+
+            canvases:           canvases,
+            images:             images,
+            ui:                 ui,
+            view_model:         view_model,
+
+            color_picker_open:  color_picker_open,
+            color_picker:       Arc::new(color_picker)
+        }
+    }
+
+    ///
+    /// Creates the UI for the calligraphy menu bar
+    ///
+    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> BindRef<Control> {
+        // Model
+        let modification_mode           = modification_mode.clone();
+        let representation              = representation.clone();
+
+        // Fetch the image resources
+        let additive_mode               = images.get_named_resource("additive_mode");
+        let individual_mode             = images.get_named_resource("individual_mode");
+        let path_editing_mode           = images.get_named_resource("path_editing");
+        let brush_stroke_mode           = images.get_named_resource("brush_stroke");
+
+        // ... and the canvas resources
+        let brush_preview               = canvases.get_named_resource("BrushPreview");
+        let nib_preview                 = canvases.get_named_resource("NibPreview");
+        let size_preview                = canvases.get_named_resource("SizePreview");
+        let size_preview_large          = canvases.get_named_resource("SizePreview2");
+        let opacity_preview             = canvases.get_named_resource("OpacityPreview");
+        let opacity_preview_large       = canvases.get_named_resource("OpacityPreview2");
+        let colour_preview              = canvases.get_named_resource("ColourPreview");
+
+        // Generate the UI control
+        let ui = computed(move || {
+            let modification_icon   = match modification_mode.get() {
+                BrushModificationMode::Additive     => additive_mode.clone(),
+                BrushModificationMode::Individual   => individual_mode.clone()
+            };
+            let representation_icon = match representation.get() {
+                BrushRepresentation::BrushStroke    => brush_stroke_mode.clone(),
+                BrushRepresentation::Path           => path_editing_mode.clone()
+            };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Nib:")
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(14.0))
+                        .with(Bounds::next_horiz(32.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0)),
+                    Control::canvas()
+                        .with(brush_preview.clone())
+                        .with(Bounds::next_horiz(64.0)),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Color:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(colour_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("ColorPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowColorPopup"))
+                        .with_controller("ColorPopup"),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Size:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(36.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 50.0.to_property())))
+                        .with(State::Value(Property::Bind("Size".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeSizeEdit".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeSizeSet".to_string())),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(size_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(vec![
+                            Control::popup()
+                                .with(Popup::IsOpen(Property::Bind("EditSize".to_string())))
+                                .with(Popup::Direction(PopupDirection::Below))
+                                .with(Popup::Size(100, 100))
+                                .with(Popup::Offset(14))
+                                .with(ControlAttribute::ZIndex(1000))
+                                .with(vec![
+                                    Control::canvas()
+                                        .with(Bounds::fill_all())
+                                        .with(size_preview_large.clone())
+                                ])
+                        ]),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Opacity:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(56.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 1.0.to_property())))
+                        .with(State::Value(Property::Bind("Opacity".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeOpacityEdit".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeOpacitySet".to_string())),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(opacity_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(vec![
+                            Control::popup()
+                                .with(Popup::IsOpen(Property::Bind("EditOpacity".to_string())))
+                                .with(Popup::Direction(PopupDirection::Below))
+                                .with(Popup::Size(100, 100))
+                                .with(Popup::Offset(14))
+                                .with(ControlAttribute::ZIndex(1000))
+                                .with(ControlAttribute::Padding((8, 8), (8, 8)))
+                                .with(vec![
+                                    Control::canvas()
+                                        .with(Bounds::fill_all())
+                                        .with(opacity_preview_large.clone())
+                                ])
+                        ]),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Angle:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(44.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 180.0.to_property())))
+                        .with(State::Value(Property::Bind("NibAngle".to_string())))
+                        .with(Bounds::next_horiz(80.0))
+                        .with((ActionTrigger::EditValue, "ChangeNibAngle".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeNibAngle".to_string())),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(nib_preview.clone())
+                        .with(Bounds::next_horiz(32.0)),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Thickness:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(64.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 1.0.to_property())))
+                        .with(State::Value(Property::Bind("NibAspectRatio".to_string())))
+                        .with(Bounds::next_horiz(64.0))
+                        .with((ActionTrigger::EditValue, "ChangeNibAspectRatio".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeNibAspectRatio".to_string())),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Tilt:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(32.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 1.0.to_property())))
+                        .with(State::Value(Property::Bind("TiltInfluence".to_string())))
+                        .with(Bounds::next_horiz(64.0))
+                        .with((ActionTrigger::EditValue, "ChangeTiltInfluence".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeTiltInfluence".to_string())),
+
+                    controls::divider(),
+
+                    Control::empty()
+                        .with(Bounds::next_horiz(20.0))
+                        .with(modification_icon)
+                        .with((ActionTrigger::Click, "NextModificationMode")),
+                    Control::empty()
+                        .with(Bounds::next_horiz(4.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(20.0))
+                        .with(representation_icon)
+                        .with((ActionTrigger::Click, "NextBrushRepresentation"))
+                ])
+        });
+
+        BindRef::from(ui)
+    }
+
+    ///
+    /// Creates the nib preview canvas (shows the shape and angle of the nib)
+    ///
+    pub fn nib_preview(nib_angle: &Binding<f32>, nib_aspect_ratio: &Binding<f32>) -> BindingCanvas {
+        let nib_angle           = nib_angle.clone();
+        let nib_aspect_ratio    = nib_aspect_ratio.clone();
+        let control_height      = 32.0 - 6.0;
+
+        BindingCanvas::with_drawing(move |gc| {
+            let nib_length      = (control_height - 8.0)/2.0;
+            let nib_thickness   = nib_length * nib_aspect_ratio.get().max(0.1).min(1.0);
+            let angle           = nib_angle.get().to_radians();
+            let (sin, cos)      = (angle.sin(), angle.cos());
+
+            gc.canvas_height(control_height);
+            gc.fill_color(Color::Rgba(0.8, 0.8, 0.8, 1.0));
+
+            // Draw the nib as an ellipse rotated to the nib angle
+            gc.new_path();
+            for step in 0..33 {
+                let t       = (step as f32)/32.0 * f32::consts::PI * 2.0;
+                let (x, y)  = (t.cos()*nib_length, t.sin()*nib_thickness);
+                let (x, y)  = (x*cos - y*sin, x*sin + y*cos);
+
+                if step == 0 { gc.move_to(x, y); } else { gc.line_to(x, y); }
+            }
+            gc.close_path();
+            gc.fill();
+        })
+    }
+
+    ///
+    /// Creates the brush preview canvas
+    ///
+    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>, nib_angle: &Binding<f32>, nib_aspect_ratio: &Binding<f32>) -> BindingCanvas {
+        let size                = size.clone();
+        let opacity             = opacity.clone();
+        let color               = color.clone();
+        let nib_angle           = nib_angle.clone();
+        let nib_aspect_ratio    = nib_aspect_ratio.clone();
+
+        let control_height  = 32.0 - 6.0;
+        let control_width   = 64.0;
+        let preview_width   = control_width - 8.0;
+        let preview_height  = control_height - 12.0;
+
+        BindingCanvas::with_drawing(move |gc| {
+            // Canvas height should match the control height
+            gc.canvas_height(control_height);
+            gc.center_region(-control_width/2.0, -control_height/2.0, control_width/2.0, control_height/2.0);
+
+            // Clear the background
+            gc.fill_color(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+            gc.rect(-control_width/2.0, -control_height/2.0, control_width/2.0, control_height/2.0);
+            gc.fill();
+
+            // Create a calligraphy brush (the tilt is not used for the preview)
+            let brush = CalligraphyBrush::new(&CalligraphyDefinition {
+                nib_angle:          nib_angle.get(),
+                nib_aspect_ratio:   nib_aspect_ratio.get(),
+                ..CalligraphyDefinition::default()
+            }, BrushDrawingStyle::Draw);
+
+            // Render a test brush stroke
+            let mut points = vec![];
+            for point in 0..100 {
+                let point   = (point as f32)/100.0;
+                let offset  = -(point*f32::consts::PI*1.5).cos();
+
+                points.push(RawPoint {
+                    position:   (point*preview_width-(preview_width/2.0), offset*preview_height/2.0),
+                    tilt:       (0.0, 0.0),
+                    pressure:   1.0
+                })
+            }
+
+            // Create the properties
+            let brush_properties = BrushProperties {
+                size:       size.get(),
+                opacity:    opacity.get(),
+                color:      color.get(),
+                gradient:   None
+            };
+
+            let points = brush.brush_points_for_raw_points(&points);
+
+            gc.draw_list(brush.prepare_to_render(&brush_properties));
+            gc.draw_list(brush.render_brush(&brush_properties, &points));
+        })
+    }
+}
+
+impl Controller for CalligraphyMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "ColorPopup"        => Some(self.color_picker.clone()),
+            _                   => None
+        }
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(Arc::clone(&self.canvases))
+    }
+
+    fn get_image_resources(&self) -> Option<Arc<ResourceManager<Image>>> {
+        Some(Arc::clone(&self.images))
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            ("ChangeSizeEdit", &Value(PropertyValue::Float(new_size))) => {
+                // User has dragged the 'size' property
+                self.size.set(new_size as f32);
+                self.view_model.set_property("EditSize", PropertyValue::Bool(true));
+            },
+
+            ("ChangeSizeSet", &Value(PropertyValue::Float(new_size))) => {
+                // User has dragged the 'size' property
+                self.size.set(new_size as f32);
+                self.view_model.set_property("EditSize", PropertyValue::Bool(false));
+            },
+
+            ("ChangeOpacityEdit", &Value(PropertyValue::Float(new_opacity))) => {
+                // User has dragged the 'opacity' property
+                self.opacity.set(new_opacity as f32);
+                self.view_model.set_property("EditOpacity", PropertyValue::Bool(true));
+            },
+
+            ("ChangeOpacitySet", &Value(PropertyValue::Float(new_opacity))) => {
+                // User has dragged the 'opacity' property
+                self.opacity.set(new_opacity as f32);
+                self.view_model.set_property("EditOpacity", PropertyValue::Bool(false));
+            },
+
+            ("ChangeNibAngle", &Value(PropertyValue::Float(new_angle))) => {
+                // User has dragged the nib angle
+                self.nib_angle.set(new_angle as f32);
+            },
+
+            ("ChangeNibAspectRatio", &Value(PropertyValue::Float(new_aspect_ratio))) => {
+                // User has dragged the nib thickness
+                self.nib_aspect_ratio.set(new_aspect_ratio as f32);
+            },
+
+            ("ChangeTiltInfluence", &Value(PropertyValue::Float(new_tilt_influence))) => {
+                // User has dragged the tilt influence
+                self.tilt_influence.set(new_tilt_influence as f32);
+            },
+
+            ("ShowColorPopup", _) => {
+                // User has clicked the colour icon
+                self.color_picker_open.set(true)
+            },
+
+            ("NextModificationMode", _) => {
+                self.modification_mode.set(match self.modification_mode.get() {
+                    BrushModificationMode::Additive     => BrushModificationMode::Individual,
+                    BrushModificationMode::Individual   => BrushModificationMode::Additive
+                });
+            },
+
+            ("NextBrushRepresentation", _) => {
+                self.representation.set(match self.representation.get() {
+                    BrushRepresentation::Path           => BrushRepresentation::BrushStroke,
+                    BrushRepresentation::BrushStroke    => BrushRepresentation::Path
+                });
+            },
+
+            _ => ()
+        }
+    }
+}
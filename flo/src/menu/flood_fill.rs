@@ -1,5 +1,6 @@
 use super::controls;
 use super::super::color::*;
+use super::super::model::*;
 
 use flo_ui::*;
 use flo_canvas::*;
@@ -12,20 +13,25 @@ use std::sync::*;
 ///
 pub struct FloodFillMenuController {
     opacity:            Binding<f32>,
+    fill_style:         Binding<FillStyle>,
+    gradient_angle:     Binding<f32>,
 
     canvases:           Arc<ResourceManager<BindingCanvas>>,
     ui:                 BindRef<Control>,
     view_model:         Arc<DynamicViewModel>,
 
     color_picker_open:  Binding<bool>,
-    color_picker:       Arc<PopupController<ColorPickerController>>
+    color_picker:       Arc<PopupController<ColorPickerController>>,
+
+    end_color_picker_open:  Binding<bool>,
+    end_color_picker:       Arc<PopupController<ColorPickerController>>
 }
 
 impl FloodFillMenuController {
     ///
     /// Creates a new flood fill menu controller
     ///
-    pub fn new(color: Binding<Color>, opacity: Binding<f32>) -> FloodFillMenuController {
+    pub fn new(color: Binding<Color>, opacity: Binding<f32>, fill_style: Binding<FillStyle>, end_color: Binding<Color>, gradient_angle: Binding<f32>) -> FloodFillMenuController {
         // Create the canvases
         let canvases = Arc::new(ResourceManager::new());
 
@@ -37,27 +43,50 @@ impl FloodFillMenuController {
             .with_size(&(500, 124));
         let color_picker            = Arc::new(color_picker);
 
+        // Colour picker for the end of the gradient
+        let end_color_picker_open   = bind(false);
+        let end_color_picker        = ColorPickerController::new(&end_color);
+        let end_color_picker        = PopupController::new(end_color_picker, &end_color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+        let end_color_picker        = Arc::new(end_color_picker);
+
         // Create the viewmodel
-        let vm_opacity              = Binding::clone(&opacity);
-        let vm_color_picker_open    = Binding::clone(&color_picker_open);
-        let view_model              = Arc::new(DynamicViewModel::new());
+        let vm_opacity                  = Binding::clone(&opacity);
+        let vm_color_picker_open        = Binding::clone(&color_picker_open);
+        let vm_end_color_picker_open    = Binding::clone(&end_color_picker_open);
+        let vm_fill_solid               = Binding::clone(&fill_style);
+        let vm_fill_linear              = Binding::clone(&fill_style);
+        let vm_fill_radial              = Binding::clone(&fill_style);
+        let vm_gradient_angle           = Binding::clone(&gradient_angle);
+        let view_model                  = Arc::new(DynamicViewModel::new());
 
         view_model.set_computed("Opacity", move || PropertyValue::Float(vm_opacity.get() as f64));
         view_model.set_property("EditOpacity", PropertyValue::Bool(false));
         view_model.set_computed("ColorPickerOpen", move || PropertyValue::Bool(vm_color_picker_open.get()));
+        view_model.set_computed("EndColorPickerOpen", move || PropertyValue::Bool(vm_end_color_picker_open.get()));
+        view_model.set_computed("FillSolidSelected", move || PropertyValue::Bool(vm_fill_solid.get() == FillStyle::Solid));
+        view_model.set_computed("FillLinearSelected", move || PropertyValue::Bool(vm_fill_linear.get() == FillStyle::LinearGradient));
+        view_model.set_computed("FillRadialSelected", move || PropertyValue::Bool(vm_fill_radial.get() == FillStyle::RadialGradient));
+        view_model.set_computed("GradientAngle", move || PropertyValue::Float(vm_gradient_angle.get() as f64));
 
         // Build the UI
-        let ui = Self::ui(BindRef::from(color.clone()), BindRef::from(opacity.clone()), Arc::clone(&canvases));
+        let ui = Self::ui(BindRef::from(color.clone()), BindRef::from(opacity.clone()), BindRef::from(fill_style.clone()), BindRef::from(end_color.clone()), Arc::clone(&canvases));
 
         FloodFillMenuController {
             opacity:            opacity,
+            fill_style:         fill_style,
+            gradient_angle:     gradient_angle,
 
             canvases:           canvases,
             ui:                 ui,
             view_model:         view_model,
 
             color_picker_open:  color_picker_open,
-            color_picker:       color_picker
+            color_picker:       color_picker,
+
+            end_color_picker_open:  end_color_picker_open,
+            end_color_picker:       end_color_picker
         }
     }
 
@@ -104,18 +133,60 @@ impl FloodFillMenuController {
     ///
     /// Creates the UI for this menu
     ///
-    fn ui(color: BindRef<Color>, opacity: BindRef<f32>, canvases: Arc<ResourceManager<BindingCanvas>>) -> BindRef<Control> {
+    fn ui(color: BindRef<Color>, opacity: BindRef<f32>, fill_style: BindRef<FillStyle>, end_color: BindRef<Color>, canvases: Arc<ResourceManager<BindingCanvas>>) -> BindRef<Control> {
         // Create the canvases
         let color_preview           = Self::color_preview(color);
+        let end_color_preview       = Self::color_preview(end_color);
         let opacity_preview         = Self::opacity_preview(opacity.clone(), 32.0-6.0);
         let opacity_preview_large   = Self::opacity_preview(opacity, 100.0);
 
         let color_preview           = canvases.register(color_preview);
+        let end_color_preview       = canvases.register(end_color_preview);
         let opacity_preview         = canvases.register(opacity_preview);
         let opacity_preview_large   = canvases.register(opacity_preview_large);
 
         // Generate the UI
-        let ui = computed(move ||
+        let ui = computed(move || {
+            // The end colour is only shown when filling with a gradient
+            let end_color_controls = if fill_style.get() != FillStyle::Solid {
+                vec![
+                    Control::empty().with(Bounds::next_horiz(8.0)),
+                    Control::label()
+                        .with("To:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(24.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(end_color_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("EndColorPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowEndColorPopup"))
+                        .with_controller("EndColorPopup")
+                ]
+            } else {
+                vec![]
+            };
+
+            // Radial gradients have no direction, so the angle is only shown for linear gradients
+            let angle_controls = if fill_style.get() == FillStyle::LinearGradient {
+                vec![
+                    Control::empty().with(Bounds::next_horiz(8.0)),
+                    Control::label()
+                        .with("Angle:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 360.0.to_property())))
+                        .with(State::Value(Property::Bind("GradientAngle".to_string())))
+                        .with(Bounds::next_horiz(72.0))
+                        .with((ActionTrigger::EditValue, "ChangeGradientAngle".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeGradientAngle".to_string()))
+                ]
+            } else {
+                vec![]
+            };
+
             Control::container()
                 .with(Bounds::fill_all())
                 .with(ControlAttribute::Padding((0, 3), (0, 3)))
@@ -145,6 +216,39 @@ impl FloodFillMenuController {
 
                     controls::divider(),
 
+                    Control::label()
+                        .with("Fill:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(32.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Font::Size(9.0))
+                        .with(Bounds::next_horiz(132.0))
+                        .with(vec![
+                            Control::button()
+                                .with(vec![Control::label().with("Solid").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::bound("FillSolidSelected")))
+                                .with((ActionTrigger::Click, "FillSolid"))
+                                .with(Bounds::next_horiz(44.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Linear").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::bound("FillLinearSelected")))
+                                .with((ActionTrigger::Click, "FillLinear"))
+                                .with(Bounds::next_horiz(44.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Radial").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::bound("FillRadialSelected")))
+                                .with((ActionTrigger::Click, "FillRadial"))
+                                .with(Bounds::next_horiz(44.0))
+                        ])
+                ].into_iter()
+                .chain(end_color_controls)
+                .chain(angle_controls)
+                .chain(vec![
+                    controls::divider(),
+
                     Control::label()
                         .with("Opacity:")
                         .with(TextAlign::Right)
@@ -175,7 +279,8 @@ impl FloodFillMenuController {
                                 ])
                         ])
                 ])
-            );
+                .collect::<Vec<_>>())
+        });
 
         BindRef::from(ui)
     }
@@ -193,6 +298,7 @@ impl Controller for FloodFillMenuController {
     fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
         match id {
             "ColorPopup"        => Some(self.color_picker.clone()),
+            "EndColorPopup"     => Some(self.end_color_picker.clone()),
             _                   => None
         }
     }
@@ -217,10 +323,24 @@ impl Controller for FloodFillMenuController {
                 self.view_model.set_property("EditOpacity", PropertyValue::Bool(false));
             },
 
+            ("ChangeGradientAngle", &Value(PropertyValue::Float(new_angle))) => {
+                // User has dragged the gradient angle slider
+                self.gradient_angle.set(new_angle as f32);
+            },
+
             ("ShowColorPopup", _) => {
                 // User has clicked the colour icon
                 self.color_picker_open.set(true)
-            }
+            },
+
+            ("ShowEndColorPopup", _) => {
+                // User has clicked the gradient end colour icon
+                self.end_color_picker_open.set(true)
+            },
+
+            ("FillSolid", _)    => { self.fill_style.set(FillStyle::Solid); },
+            ("FillLinear", _)   => { self.fill_style.set(FillStyle::LinearGradient); },
+            ("FillRadial", _)   => { self.fill_style.set(FillStyle::RadialGradient); }
 
             _ => ()
         }
@@ -12,23 +12,37 @@ use flo_animation::brushes::*;
 use std::f32;
 use std::sync::*;
 
+/// Size of the pressure curve editor
+const PRESSURE_EDITOR_SIZE: f32 = 140.0;
+
+/// Space between the edge of the pressure curve editor and the graph
+const PRESSURE_EDITOR_MARGIN: f32 = 10.0;
+
 ///
 /// Controller used for the ink tool
 ///
 pub struct InkMenuController {
-    size:               Binding<f32>,
-    opacity:            Binding<f32>,
-    modification_mode:  Binding<BrushModificationMode>,
-    representation:     Binding<BrushRepresentation>,
-    brush_panel_open:   Binding<bool>,
-
-    canvases:           Arc<ResourceManager<BindingCanvas>>,
-    images:             Arc<ResourceManager<Image>>,
-    ui:                 BindRef<Control>,
-    view_model:         Arc<DynamicViewModel>,
-
-    color_picker_open:  Binding<bool>,
-    color_picker:       Arc<PopupController<ColorPickerController>>
+    size:                   Binding<f32>,
+    opacity:                Binding<f32>,
+    modification_mode:      Binding<BrushModificationMode>,
+    representation:         Binding<BrushRepresentation>,
+    stabiliser_mode:        Binding<StabiliserMode>,
+    stabiliser_catch_up:    Binding<bool>,
+    string_length:          Binding<f32>,
+    average_samples:        Binding<usize>,
+    pressure_curve:         Binding<PressureCurve>,
+    pressure_source:        Binding<PressureSource>,
+    brush_panel_open:       Binding<bool>,
+    pressure_panel_open:    Binding<bool>,
+    pressure_drag_point:    Binding<Option<usize>>,
+
+    canvases:               Arc<ResourceManager<BindingCanvas>>,
+    images:                 Arc<ResourceManager<Image>>,
+    ui:                     BindRef<Control>,
+    view_model:             Arc<DynamicViewModel>,
+
+    color_picker_open:      Binding<bool>,
+    color_picker:           Arc<PopupController<ColorPickerController>>
 }
 
 impl InkMenuController {
@@ -64,21 +78,34 @@ impl InkMenuController {
     ///
     /// Creates a new ink menu controller
     ///
-    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> InkMenuController {
+    pub fn new(size: &Binding<f32>, opacity: &Binding<f32>, colour: &Binding<Color>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>, stabiliser_mode: &Binding<StabiliserMode>, stabiliser_catch_up: &Binding<bool>, string_length: &Binding<f32>, average_samples: &Binding<usize>, pressure_curve: &Binding<PressureCurve>, pressure_source: &Binding<PressureSource>) -> InkMenuController {
         // Set up the view model
         let view_model = Arc::new(DynamicViewModel::new());
 
         let vm_size                 = size.clone();
         let vm_opacity              = opacity.clone();
         let brush_panel_open        = bind(false);
+        let pressure_panel_open     = bind(false);
 
         view_model.set_computed("Size", move || PropertyValue::Float(vm_size.get() as f64));
         view_model.set_computed("Opacity", move || PropertyValue::Float(vm_opacity.get() as f64));
 
+        // The stabiliser strength is the string length or the number of samples, depending on the stabiliser mode
+        let vm_stabiliser_mode      = stabiliser_mode.clone();
+        let vm_string_length        = string_length.clone();
+        let vm_average_samples      = average_samples.clone();
+        view_model.set_computed("StabiliserStrength", move || PropertyValue::Float(match vm_stabiliser_mode.get() {
+            StabiliserMode::Off                 => 0.0,
+            StabiliserMode::PulledString(_)     => vm_string_length.get() as f64,
+            StabiliserMode::MovingAverage(_)    => vm_average_samples.get() as f64
+        }));
+
         view_model.set_property("EditSize", PropertyValue::Bool(false));
         view_model.set_property("EditOpacity", PropertyValue::Bool(false));
         let edit_brush_properties   = brush_panel_open.clone();
         view_model.set_computed("EditBrushProperties", move || PropertyValue::Bool(edit_brush_properties.get()));
+        let edit_pressure_curve     = pressure_panel_open.clone();
+        view_model.set_computed("EditPressureCurve", move || PropertyValue::Bool(edit_pressure_curve.get()));
 
         // Create the colour picker popup
         let color_picker_open   = Binding::new(false);
@@ -96,7 +123,7 @@ impl InkMenuController {
         // Create the canvases
         let canvases                = Arc::new(ResourceManager::new());
 
-        let brush_preview           = Self::brush_preview(size, opacity, colour);
+        let brush_preview           = Self::brush_preview(size, opacity, colour, pressure_curve, pressure_source);
         let brush_preview           = canvases.register(brush_preview);
         canvases.assign_name(&brush_preview, "BrushPreview");
 
@@ -120,35 +147,56 @@ impl InkMenuController {
         let colour_preview          = canvases.register(colour_preview);
         canvases.assign_name(&colour_preview, "ColourPreview");
 
+        let pressure_preview        = Self::pressure_curve_preview(pressure_curve, 32.0-6.0, 4.0, false);
+        let pressure_preview        = canvases.register(pressure_preview);
+        canvases.assign_name(&pressure_preview, "PressurePreview");
+
+        let pressure_editor         = Self::pressure_curve_preview(pressure_curve, PRESSURE_EDITOR_SIZE, PRESSURE_EDITOR_MARGIN, true);
+        let pressure_editor         = canvases.register(pressure_editor);
+        canvases.assign_name(&pressure_editor, "PressureEditor");
+
         // Generate the UI
-        let ui = Self::ui(&canvases, &images, &brush_panel_open, modification_mode, representation);
+        let ui = Self::ui(&canvases, &images, &brush_panel_open, &pressure_panel_open, modification_mode, representation, stabiliser_mode, stabiliser_catch_up, pressure_curve, pressure_source);
 
         // Finalize the control
         InkMenuController {
-            size:               size.clone(),
-            opacity:            opacity.clone(),
-            modification_mode:  modification_mode.clone(),
-            representation:     representation.clone(),
-            brush_panel_open:   brush_panel_open,
-
-            canvases:           canvases,
-            images:             images,
-            ui:                 ui,
-            view_model:         view_model,
-
-            color_picker_open:  color_picker_open,
-            color_picker:       Arc::new(color_picker)
+            size:                   size.clone(),
+            opacity:                opacity.clone(),
+            modification_mode:      modification_mode.clone(),
+            representation:         representation.clone(),
+            stabiliser_mode:        stabiliser_mode.clone(),
+            stabiliser_catch_up:    stabiliser_catch_up.clone(),
+            string_length:          string_length.clone(),
+            average_samples:        average_samples.clone(),
+            pressure_curve:         pressure_curve.clone(),
+            pressure_source:        pressure_source.clone(),
+            brush_panel_open:       brush_panel_open,
+            pressure_panel_open:    pressure_panel_open,
+            pressure_drag_point:    bind(None),
+
+            canvases:               canvases,
+            images:                 images,
+            ui:                     ui,
+            view_model:             view_model,
+
+            color_picker_open:      color_picker_open,
+            color_picker:           Arc::new(color_picker)
         }
     }
 
     ///
     /// Creates the UI for the ink menu bar
     ///
-    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, brush_panel_open: &Binding<bool>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>) -> BindRef<Control> {
+    fn ui(canvases: &ResourceManager<BindingCanvas>, images: &ResourceManager<Image>, brush_panel_open: &Binding<bool>, pressure_panel_open: &Binding<bool>, modification_mode: &Binding<BrushModificationMode>, representation: &Binding<BrushRepresentation>, stabiliser_mode: &Binding<StabiliserMode>, stabiliser_catch_up: &Binding<bool>, pressure_curve: &Binding<PressureCurve>, pressure_source: &Binding<PressureSource>) -> BindRef<Control> {
         // Model
         let modification_mode           = modification_mode.clone();
         let representation              = representation.clone();
+        let stabiliser_mode             = stabiliser_mode.clone();
+        let stabiliser_catch_up         = stabiliser_catch_up.clone();
+        let pressure_curve              = pressure_curve.clone();
+        let pressure_source             = pressure_source.clone();
         let brush_panel_open            = brush_panel_open.clone();
+        let pressure_panel_open         = pressure_panel_open.clone();
 
         // Fetch the image resources
         let brush_settings_background   = images.get_named_resource("brush_settings");
@@ -168,12 +216,19 @@ impl InkMenuController {
         let opacity_preview             = canvases.get_named_resource("OpacityPreview");
         let opacity_preview_large       = canvases.get_named_resource("OpacityPreview2");
         let colour_preview              = canvases.get_named_resource("ColourPreview");
+        let pressure_preview            = canvases.get_named_resource("PressurePreview");
+        let pressure_editor             = canvases.get_named_resource("PressureEditor");
 
         // Generate the UI control
         let ui = computed(move || {
             let modification_mode   = modification_mode.get();
             let representation      = representation.get();
+            let stabiliser_mode     = stabiliser_mode.get();
+            let stabiliser_catch_up = stabiliser_catch_up.get();
+            let pressure_curve      = pressure_curve.get();
+            let pressure_source     = pressure_source.get();
             let brush_panel_open    = brush_panel_open.get();
+            let pressure_panel_open = pressure_panel_open.get();
 
             let modification_icon   = match modification_mode {
                 BrushModificationMode::Additive     => additive_mode.clone(),
@@ -197,6 +252,31 @@ impl InkMenuController {
                 BrushRepresentation::BrushStroke    => "Keep brush strokes",
                 BrushRepresentation::Path           => "Convert to paths"
             };
+            let stabiliser_text     = match stabiliser_mode {
+                StabiliserMode::Off                 => "No stabiliser",
+                StabiliserMode::PulledString(_)     => "Pull brush on a string",
+                StabiliserMode::MovingAverage(_)    => "Average recent points"
+            };
+            let (strength_text, strength_range) = match stabiliser_mode {
+                StabiliserMode::Off                 => ("Strength:", (1.0, 100.0)),
+                StabiliserMode::PulledString(_)     => ("Length:", (1.0, 100.0)),
+                StabiliserMode::MovingAverage(_)    => ("Samples:", (2.0, 32.0))
+            };
+            let catch_up_text       = if stabiliser_catch_up {
+                "Catch up when finished"
+            } else {
+                "Stop where the brush is"
+            };
+            let pressure_curve_text = match pressure_curve {
+                PressureCurve::Linear               => "Linear response",
+                PressureCurve::Soft                 => "Soft response",
+                PressureCurve::Firm                 => "Firm response",
+                PressureCurve::Bezier(_, _)         => "Custom curve"
+            };
+            let pressure_source_text = match pressure_source {
+                PressureSource::Device              => "Use pen pressure",
+                PressureSource::Velocity            => "Simulate from speed"
+            };
 
             Control::container()
                 .with(Bounds::fill_all())
@@ -295,6 +375,69 @@ impl InkMenuController {
 
                     controls::divider(),
 
+                    Control::label()
+                        .with("Pressure:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(60.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(pressure_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("EditPressureCurve".to_string())))
+                        .with(if !pressure_panel_open { (ActionTrigger::Click, "ShowPressureCurvePopup") } else { (ActionTrigger::Click, "HidePressureCurvePopup") })
+                        .with(if pressure_panel_open { vec![
+                            Control::popup()
+                                .with(Popup::Direction(PopupDirection::Below))
+                                .with(Popup::Size(160, 214))
+                                .with(Popup::Offset(14))
+                                .with(ControlAttribute::ZIndex(1000))
+                                .with(Popup::IsOpen(Property::Bind("EditPressureCurve".to_string())))
+                                .with((ActionTrigger::Dismiss, "HidePressureCurvePopup"))
+                                .with(vec![
+                                    Control::container()
+                                        .with(Bounds::fill_all())
+                                        .with(ControlAttribute::Padding((10, 0), (10, 0)))
+                                        .with(vec![
+                                            Control::empty()
+                                                .with(Bounds::next_vert(10.0)),
+                                            Control::canvas()
+                                                .with(Bounds::next_vert(PRESSURE_EDITOR_SIZE))
+                                                .with(pressure_editor.clone())
+                                                .with((ActionTrigger::Drag, "DragPressureCurve")),
+                                            Control::empty()
+                                                .with(Bounds::next_vert(6.0)),
+                                            Control::empty()
+                                                .with(Bounds::next_vert(26.0))
+                                                .with(combo_picker.clone())
+                                                .with((ActionTrigger::Click, "NextPressureCurve"))
+                                                .with(ControlAttribute::Padding((24, 4), (24, 4)))
+                                                .with(vec![
+                                                    Control::label()
+                                                        .with(Bounds::fill_horiz())
+                                                        .with(Font::Size(11.0))
+                                                        .with(pressure_curve_text)
+                                                ]),
+                                            Control::empty()
+                                                .with(Bounds::next_vert(3.0)),
+                                            Control::empty()
+                                                .with(Bounds::next_vert(26.0))
+                                                .with(combo_picker.clone())
+                                                .with((ActionTrigger::Click, "TogglePressureSource"))
+                                                .with(ControlAttribute::Padding((24, 4), (24, 4)))
+                                                .with(vec![
+                                                    Control::label()
+                                                        .with(Bounds::fill_horiz())
+                                                        .with(Font::Size(11.0))
+                                                        .with(pressure_source_text)
+                                                ]),
+                                            Control::empty()
+                                                .with(Bounds::next_vert(3.0)),
+                                        ])
+                                ])
+                        ] } else { vec![] }),
+
+                    controls::divider(),
+
                     Control::empty()
                         .with(Bounds::next_horiz(4.0)),
                     Control::container()
@@ -308,7 +451,7 @@ impl InkMenuController {
                                 .with(if brush_panel_open { vec![
                                     Control::popup()
                                         .with(Popup::Direction(PopupDirection::Below))
-                                        .with(Popup::Size(220, 131))
+                                        .with(Popup::Size(220, 218))
                                         .with(Popup::Offset(14))
                                         .with(ControlAttribute::ZIndex(1000))
                                         .with(Popup::IsOpen(Property::Bind("EditBrushProperties".to_string())))
@@ -374,6 +517,50 @@ impl InkMenuController {
                                                         ]),
                                                     Control::empty()
                                                         .with(Bounds::next_vert(3.0)),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(26.0))
+                                                        .with(combo_picker.clone())
+                                                        .with((ActionTrigger::Click, "NextStabiliserMode"))
+                                                        .with(ControlAttribute::Padding((48, 4), (24, 4)))
+                                                        .with(vec![
+                                                            Control::label()
+                                                                .with(Bounds::fill_horiz())
+                                                                .with(Font::Size(11.0))
+                                                                .with(stabiliser_text)
+                                                        ]),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
+                                                    Control::container()
+                                                        .with(Bounds::next_vert(26.0))
+                                                        .with(ControlAttribute::Padding((48, 4), (24, 4)))
+                                                        .with(vec![
+                                                            Control::label()
+                                                                .with(Bounds::next_horiz(52.0))
+                                                                .with(Font::Size(11.0))
+                                                                .with(strength_text),
+                                                            Control::slider()
+                                                                .with(Bounds::fill_horiz())
+                                                                .with(State::Enabled(Property::Bool(stabiliser_mode != StabiliserMode::Off)))
+                                                                .with(State::Range((strength_range.0.to_property(), strength_range.1.to_property())))
+                                                                .with(State::Value(Property::Bind("StabiliserStrength".to_string())))
+                                                                .with((ActionTrigger::EditValue, "ChangeStabiliserStrength".to_string()))
+                                                                .with((ActionTrigger::SetValue, "ChangeStabiliserStrength".to_string()))
+                                                        ]),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(26.0))
+                                                        .with(combo_picker.clone())
+                                                        .with((ActionTrigger::Click, "ToggleStabiliserCatchUp"))
+                                                        .with(ControlAttribute::Padding((48, 4), (24, 4)))
+                                                        .with(vec![
+                                                            Control::label()
+                                                                .with(Bounds::fill_horiz())
+                                                                .with(Font::Size(11.0))
+                                                                .with(catch_up_text)
+                                                        ]),
+                                                    Control::empty()
+                                                        .with(Bounds::next_vert(3.0)),
                                                 ])
                                         ]),
                                 ] } else { vec![] }),
@@ -456,13 +643,88 @@ impl InkMenuController {
         })
     }
 
+    ///
+    /// Creates a canvas showing the pressure curve (optionally with handles for the control points so it can be edited)
+    ///
+    pub fn pressure_curve_preview(pressure_curve: &Binding<PressureCurve>, control_height: f32, margin: f32, show_handles: bool) -> BindingCanvas {
+        let pressure_curve  = pressure_curve.clone();
+        let graph_size      = control_height - margin*2.0;
+
+        BindingCanvas::with_drawing(move |gc| {
+            let ((cp1_x, cp1_y), (cp2_x, cp2_y)) = pressure_curve.get().control_points();
+            let to_canvas = |x: f32, y: f32| (margin + x*graph_size, margin + y*graph_size);
+
+            gc.canvas_height(control_height);
+            gc.center_region(0.0, 0.0, control_height, control_height);
+
+            // Background and the linear response for comparison
+            gc.fill_color(Color::Rgba(0.1, 0.1, 0.1, 1.0));
+            gc.new_path();
+            gc.rect(margin, margin, margin+graph_size, margin+graph_size);
+            gc.fill();
+
+            gc.line_width_pixels(1.0);
+            gc.stroke_color(Color::Rgba(0.4, 0.4, 0.4, 1.0));
+            gc.new_path();
+            gc.move_to(margin, margin);
+            gc.line_to(margin+graph_size, margin+graph_size);
+            gc.stroke();
+
+            // The pressure curve itself
+            let (start_x, start_y)  = to_canvas(0.0, 0.0);
+            let (end_x, end_y)      = to_canvas(1.0, 1.0);
+            let (cp1_x, cp1_y)      = to_canvas(cp1_x, cp1_y);
+            let (cp2_x, cp2_y)      = to_canvas(cp2_x, cp2_y);
+
+            gc.line_width_pixels(2.0);
+            gc.stroke_color(Color::Rgba(0.8, 0.8, 0.8, 1.0));
+            gc.new_path();
+            gc.move_to(start_x, start_y);
+            gc.bezier_curve_to(end_x, end_y, cp1_x, cp1_y, cp2_x, cp2_y);
+            gc.stroke();
+
+            if show_handles {
+                // Lines from the end points to the control points
+                gc.line_width_pixels(1.0);
+                gc.stroke_color(Color::Rgba(0.6, 0.7, 1.0, 1.0));
+                gc.new_path();
+                gc.move_to(start_x, start_y);
+                gc.line_to(cp1_x, cp1_y);
+                gc.move_to(end_x, end_y);
+                gc.line_to(cp2_x, cp2_y);
+                gc.stroke();
+
+                // The handles for the control points
+                gc.fill_color(Color::Rgba(0.6, 0.7, 1.0, 1.0));
+                for (x, y) in vec![(cp1_x, cp1_y), (cp2_x, cp2_y)] {
+                    gc.new_path();
+                    gc.circle(x, y, 4.0);
+                    gc.fill();
+                }
+            }
+        })
+    }
+
+    ///
+    /// Converts a position in the pressure curve editor control to a point on the pressure curve graph
+    ///
+    fn pressure_curve_point(x: f32, y: f32) -> (f32, f32) {
+        let graph_size  = PRESSURE_EDITOR_SIZE - PRESSURE_EDITOR_MARGIN*2.0;
+        let x           = (x - PRESSURE_EDITOR_MARGIN) / graph_size;
+        let y           = 1.0 - (y - PRESSURE_EDITOR_MARGIN) / graph_size;
+
+        (x.max(0.0).min(1.0), y.max(0.0).min(1.0))
+    }
+
     ///
     /// Creates the brush preview canvas
     ///
-    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>) -> BindingCanvas {
-        let size    = size.clone();
-        let opacity = opacity.clone();
-        let color   = color.clone();
+    pub fn brush_preview(size: &Binding<f32>, opacity: &Binding<f32>, color: &Binding<Color>, pressure_curve: &Binding<PressureCurve>, pressure_source: &Binding<PressureSource>) -> BindingCanvas {
+        let size            = size.clone();
+        let opacity         = opacity.clone();
+        let color           = color.clone();
+        let pressure_curve  = pressure_curve.clone();
+        let pressure_source = pressure_source.clone();
 
         let control_height  = 32.0 - 6.0;
         let control_width   = 64.0;
@@ -480,7 +742,12 @@ impl InkMenuController {
             gc.fill();
 
             // Create an ink brush
-            let brush = InkBrush::new(&InkDefinition::default(), BrushDrawingStyle::Draw);
+            let definition  = InkDefinition {
+                pressure_curve:     pressure_curve.get(),
+                pressure_source:    pressure_source.get(),
+                ..InkDefinition::default()
+            };
+            let brush       = InkBrush::new(&definition, BrushDrawingStyle::Draw);
 
             // Render a test brush stroke
             let mut points = vec![];
@@ -499,7 +766,8 @@ impl InkMenuController {
             let brush_properties = BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                gradient:   None
             };
 
             let points = brush.brush_points_for_raw_points(&points);
@@ -591,6 +859,78 @@ impl Controller for InkMenuController {
                 });
             },
 
+            ("NextStabiliserMode", _) => {
+                self.stabiliser_mode.set(match self.stabiliser_mode.get() {
+                    StabiliserMode::Off                 => StabiliserMode::PulledString(self.string_length.get()),
+                    StabiliserMode::PulledString(_)     => StabiliserMode::MovingAverage(self.average_samples.get()),
+                    StabiliserMode::MovingAverage(_)    => StabiliserMode::Off
+                });
+            },
+
+            ("ChangeStabiliserStrength", &Value(PropertyValue::Float(new_strength))) => {
+                // User has dragged the stabiliser strength slider
+                match self.stabiliser_mode.get() {
+                    StabiliserMode::Off                 => { },
+                    StabiliserMode::PulledString(_)     => { self.string_length.set((new_strength as f32).max(1.0)); },
+                    StabiliserMode::MovingAverage(_)    => { self.average_samples.set((new_strength.round() as usize).max(1)); }
+                }
+            },
+
+            ("ToggleStabiliserCatchUp", _) => {
+                self.stabiliser_catch_up.set(!self.stabiliser_catch_up.get());
+            },
+
+            ("ShowPressureCurvePopup", _) => {
+                // User has clicked the pressure curve preview
+                self.pressure_panel_open.set(true);
+            },
+
+            ("HidePressureCurvePopup", _) => {
+                // User has dismissed the pressure curve editor
+                self.pressure_panel_open.set(false);
+                self.pressure_drag_point.set(None);
+            },
+
+            ("NextPressureCurve", _) => {
+                self.pressure_curve.set(match self.pressure_curve.get() {
+                    PressureCurve::Linear       => PressureCurve::Soft,
+                    PressureCurve::Soft         => PressureCurve::Firm,
+                    PressureCurve::Firm         => PressureCurve::Linear,
+                    PressureCurve::Bezier(_, _) => PressureCurve::Linear
+                });
+            },
+
+            ("TogglePressureSource", _) => {
+                self.pressure_source.set(match self.pressure_source.get() {
+                    PressureSource::Device      => PressureSource::Velocity,
+                    PressureSource::Velocity    => PressureSource::Device
+                });
+            },
+
+            ("DragPressureCurve", &Drag(drag_action, (start_x, start_y), (x, y))) => {
+                let (cp1, cp2) = self.pressure_curve.get().control_points();
+
+                // Pick the control point nearest to where the drag started
+                if drag_action == DragAction::Start {
+                    let start       = Self::pressure_curve_point(start_x, start_y);
+                    let distance    = |(x, y): (f32, f32)| (x-start.0)*(x-start.0) + (y-start.1)*(y-start.1);
+
+                    self.pressure_drag_point.set(Some(if distance(cp1) <= distance(cp2) { 0 } else { 1 }));
+                }
+
+                // Move the control point to the drag position (this turns presets into a custom curve)
+                let position = Self::pressure_curve_point(x, y);
+                match self.pressure_drag_point.get() {
+                    Some(0) => self.pressure_curve.set(PressureCurve::Bezier(position, cp2)),
+                    Some(_) => self.pressure_curve.set(PressureCurve::Bezier(cp1, position)),
+                    None    => ()
+                }
+
+                if drag_action == DragAction::Finish || drag_action == DragAction::Cancel {
+                    self.pressure_drag_point.set(None);
+                }
+            },
+
             _ => ()
         }
     }
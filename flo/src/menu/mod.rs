@@ -1,14 +1,22 @@
 mod controls;
 mod empty;
 mod ink;
+mod calligraphy;
 mod eraser;
 mod flood_fill;
+mod shape;
+mod text;
 mod select;
 mod adjust;
+mod transform;
 
 pub use self::empty::*;
 pub use self::ink::*;
+pub use self::calligraphy::*;
 pub use self::eraser::*;
 pub use self::flood_fill::*;
+pub use self::shape::*;
+pub use self::text::*;
 pub use self::select::*;
 pub use self::adjust::*;
+pub use self::transform::*;
@@ -5,6 +5,7 @@ use super::super::standard_tools::*;
 use flo_ui::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_import::*;
 
 use desync::*;
 use futures::*;
@@ -30,6 +31,12 @@ pub struct SelectMenuController<Anim: Animation> {
     /// The timeline model for the animation
     timeline: TimelineModel<Anim>,
 
+    /// The model for the animation (used for assigning IDs to new groups and selecting them)
+    flo_model: FloModel<Anim>,
+
+    /// The message describing why the last paste failed, if it did
+    paste_error: Binding<Option<String>>,
+
     // The UI for this control
     ui: BindRef<Control>
 }
@@ -39,25 +46,29 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
     /// Creates a new select menu controller
     ///
     pub fn new(flo_model: &FloModel<Anim>, tool_model: &SelectToolModel) -> SelectMenuController<Anim> {
-        let ui                  = Self::ui(tool_model);
+        let paste_error         = bind(None);
+        let ui                  = Self::ui(tool_model, BindRef::new(&paste_error));
         let edit                = Desync::new(executor::spawn(flo_model.edit()));
         let selected            = flo_model.selection().selected_elements.clone();
         let selection_in_order  = flo_model.selection().selection_in_order.clone();
         let timeline            = flo_model.timeline().clone();
+        let flo_model           = flo_model.clone();
 
         SelectMenuController {
             ui:                 ui,
             edit:               edit,
             selected:           selected,
             selection_in_order: selection_in_order,
-            timeline:           timeline
+            timeline:           timeline,
+            flo_model:          flo_model,
+            paste_error:        paste_error
         }
     }
 
     ///
     /// Creates the UI for the select menu controller
     ///
-    fn ui(tool_model: &SelectToolModel) -> BindRef<Control> {
+    fn ui(tool_model: &SelectToolModel, paste_error: BindRef<Option<String>>) -> BindRef<Control> {
         let anything_selected   = tool_model.anything_selected.clone();
         let num_selected        = tool_model.num_elements_selected.clone();
 
@@ -109,12 +120,59 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                                     .with(Font::Size(10.0))
                                     .with((ActionTrigger::Click, "MoveToBack"))
                                     .with(Bounds::next_horiz(20.0))
+                            ]),
+
+                        controls::divider(),
+
+                        Control::label()
+                            .with("Combine:")
+                            .with(TextAlign::Right)
+                            .with(Font::Size(13.0))
+                            .with(Bounds::next_horiz(56.0)),
+                        Control::empty()
+                            .with(Bounds::next_horiz(4.0)),
+                        Control::container()
+                            .with(Hint::Class("button-group".to_string()))
+                            .with(ControlAttribute::Padding((0,2), (0,2)))
+                            .with(Font::Size(9.0))
+                            .with(Bounds::next_horiz(176.0))
+                            .with(vec![
+                                Control::button()
+                                    .with(vec![Control::label().with("Subtract").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "GroupSubtract"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Mask").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "GroupMask"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Invert").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "GroupInvertedMask"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Ungroup").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "Ungroup"))
+                                    .with(Bounds::next_horiz(44.0))
                             ])
                     ]
                 } else {
                     vec![]
                 };
 
+                // Reason the last paste failed
+                let paste_error_controls = match paste_error.get() {
+                    Some(message)   => vec![
+                        Control::empty()
+                            .with(Bounds::next_horiz(4.0)),
+                        Control::label()
+                            .with(message)
+                            .with(TextAlign::Left)
+                            .with(Font::Size(11.0))
+                            .with(Bounds::next_horiz(200.0))
+                    ],
+                    None            => vec![]
+                };
+
                 // Build the control
                 Control::container()
                     .with(Bounds::fill_all())
@@ -138,14 +196,49 @@ impl<Anim: 'static+EditableAnimation+Animation> SelectMenuController<Anim> {
                             .with(TextAlign::Left)
                             .with(Font::Size(12.0))
                             .with(Bounds::next_horiz(56.0)),
+
+                        controls::divider(),
+
+                        Control::container()
+                            .with(Hint::Class("button-group".to_string()))
+                            .with(ControlAttribute::Padding((0,2), (0,2)))
+                            .with(Font::Size(9.0))
+                            .with(Bounds::next_horiz(132.0))
+                            .with(vec![
+                                Control::button()
+                                    .with(vec![Control::label().with("Cut").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "Cut"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Copy").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "Copy"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Paste").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "Paste"))
+                                    .with(Bounds::next_horiz(44.0))
+                            ]),
                     ]
                     .into_iter()
+                    .chain(paste_error_controls)
                     .chain(selection_controls)
                     .collect::<Vec<_>>())
             });
 
         BindRef::from(ui)
     }
+
+    ///
+    /// Describes why pasting failed
+    ///
+    fn paste_error_message(error: SvgImportError) -> String {
+        match error {
+            SvgImportError::InvalidXml(_)       => String::from("Could not paste: clipboard is not valid SVG"),
+            SvgImportError::NotSvg(_)           => String::from("Could not paste: clipboard is not an SVG document"),
+            SvgImportError::NoSuchLayer(_)      => String::from("Could not paste: layer not found"),
+            SvgImportError::EditFailed          => String::from("Could not paste: the animation rejected the pasted elements")
+        }
+    }
 }
 
 impl<Anim: 'static+EditableAnimation+Animation> Controller for SelectMenuController<Anim> {
@@ -183,6 +276,50 @@ impl<Anim: 'static+EditableAnimation+Animation> Controller for SelectMenuControl
                 self.timeline.invalidate_canvas();
             },
 
+            "GroupSubtract" | "GroupMask" | "GroupInvertedMask" => {
+                // The selection is in back-to-front order, so the rearmost element is the one that is subtracted from or used as the mask
+                let selection   = self.selection_in_order.get();
+                let group_type  = match action_id {
+                    "GroupSubtract"     => GroupType::Subtracted,
+                    "GroupMask"         => GroupType::Masked,
+                    _                   => GroupType::InvertedMask
+                };
+
+                if selection.len() < 2 {
+                    return;
+                }
+
+                // Assign the group an ID so we can select it once it's created
+                let group_id    = self.flo_model.motion().assign_element_id();
+
+                self.edit.sync(move |animation| {
+                    animation.wait_send(vec![
+                            AnimationEdit::Element(selection.iter().cloned().collect(), ElementEdit::Group(group_id, group_type))
+                        ]).ok();
+                    });
+
+                self.flo_model.selection().clear_selection();
+                self.flo_model.selection().select(group_id);
+                self.timeline.invalidate_canvas();
+            },
+
+            "Cut"   => { self.paste_error.set(None); self.flo_model.cut_selection(); },
+            "Copy"  => { self.paste_error.set(None); self.flo_model.copy_selection(); },
+            "Paste" => { self.paste_error.set(self.flo_model.paste().err().map(Self::paste_error_message)); },
+
+            "Ungroup" => {
+                let selection = self.selection_in_order.get();
+
+                self.edit.sync(move |animation| {
+                    animation.wait_send(vec![
+                            AnimationEdit::Element(selection.iter().cloned().collect(), ElementEdit::Ungroup)
+                        ]).ok();
+                    });
+
+                self.flo_model.selection().clear_selection();
+                self.timeline.invalidate_canvas();
+            },
+
             _ => { }
         }
     }
@@ -0,0 +1,365 @@
+use super::controls;
+use super::super::color::*;
+use super::super::model::*;
+use super::super::standard_tools::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+
+use std::sync::*;
+
+/// The smallest number of sides that can be chosen for a polygon
+const MIN_POLYGON_SIDES: u32 = 3;
+
+/// The largest number of sides that can be chosen for a polygon
+const MAX_POLYGON_SIDES: u32 = 12;
+
+///
+/// Menu controller used for the shape tools
+///
+pub struct ShapeMenuController {
+    opacity:                    Binding<f32>,
+    stroke_width:               Binding<f32>,
+    shape_style:                Binding<ShapeStyle>,
+    num_sides:                  Binding<u32>,
+
+    canvases:                   Arc<ResourceManager<BindingCanvas>>,
+    ui:                         BindRef<Control>,
+    view_model:                 Arc<DynamicViewModel>,
+
+    fill_color_picker_open:     Binding<bool>,
+    fill_color_picker:          Arc<PopupController<ColorPickerController>>,
+
+    stroke_color_picker_open:   Binding<bool>,
+    stroke_color_picker:        Arc<PopupController<ColorPickerController>>
+}
+
+impl ShapeMenuController {
+    ///
+    /// Creates a new shape menu controller
+    ///
+    pub fn new(kind: ShapeKind, model: &ShapeModel) -> ShapeMenuController {
+        // Create the canvases
+        let canvases = Arc::new(ResourceManager::new());
+
+        // Colour picker for the inside of the shape
+        let fill_color_picker_open      = bind(false);
+        let fill_color_picker           = ColorPickerController::new(&model.fill_color);
+        let fill_color_picker           = PopupController::new(fill_color_picker, &fill_color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+        let fill_color_picker           = Arc::new(fill_color_picker);
+
+        // Colour picker for the outline of the shape
+        let stroke_color_picker_open    = bind(false);
+        let stroke_color_picker         = ColorPickerController::new(&model.stroke_color);
+        let stroke_color_picker         = PopupController::new(stroke_color_picker, &stroke_color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+        let stroke_color_picker         = Arc::new(stroke_color_picker);
+
+        // Create the viewmodel
+        let vm_opacity                  = Binding::clone(&model.opacity);
+        let vm_stroke_width             = Binding::clone(&model.stroke_width);
+        let vm_num_sides                = Binding::clone(&model.num_sides);
+        let vm_fill_color_picker_open   = Binding::clone(&fill_color_picker_open);
+        let vm_stroke_color_picker_open = Binding::clone(&stroke_color_picker_open);
+        let vm_style_fill               = Binding::clone(&model.shape_style);
+        let vm_style_stroke             = Binding::clone(&model.shape_style);
+        let vm_style_both               = Binding::clone(&model.shape_style);
+        let view_model                  = Arc::new(DynamicViewModel::new());
+
+        view_model.set_computed("Opacity", move || PropertyValue::Float(vm_opacity.get() as f64));
+        view_model.set_computed("StrokeWidth", move || PropertyValue::Float(vm_stroke_width.get() as f64));
+        view_model.set_computed("Sides", move || PropertyValue::Float(vm_num_sides.get() as f64));
+        view_model.set_property("EditOpacity", PropertyValue::Bool(false));
+        view_model.set_computed("FillColorPickerOpen", move || PropertyValue::Bool(vm_fill_color_picker_open.get()));
+        view_model.set_computed("StrokeColorPickerOpen", move || PropertyValue::Bool(vm_stroke_color_picker_open.get()));
+        view_model.set_computed("StyleFillSelected", move || PropertyValue::Bool(vm_style_fill.get() == ShapeStyle::Fill));
+        view_model.set_computed("StyleStrokeSelected", move || PropertyValue::Bool(vm_style_stroke.get() == ShapeStyle::Stroke));
+        view_model.set_computed("StyleBothSelected", move || PropertyValue::Bool(vm_style_both.get() == ShapeStyle::FillAndStroke));
+
+        // Build the UI
+        let ui = Self::ui(kind, model, Arc::clone(&canvases));
+
+        ShapeMenuController {
+            opacity:                    model.opacity.clone(),
+            stroke_width:               model.stroke_width.clone(),
+            shape_style:                model.shape_style.clone(),
+            num_sides:                  model.num_sides.clone(),
+
+            canvases:                   canvases,
+            ui:                         ui,
+            view_model:                 view_model,
+
+            fill_color_picker_open:     fill_color_picker_open,
+            fill_color_picker:          fill_color_picker,
+
+            stroke_color_picker_open:   stroke_color_picker_open,
+            stroke_color_picker:        stroke_color_picker
+        }
+    }
+
+    ///
+    /// Creates the UI for this menu
+    ///
+    fn ui(kind: ShapeKind, model: &ShapeModel, canvases: Arc<ResourceManager<BindingCanvas>>) -> BindRef<Control> {
+        // Create the canvases
+        let fill_color_preview      = FloodFillMenuController::color_preview(BindRef::from(model.fill_color.clone()));
+        let stroke_color_preview    = FloodFillMenuController::color_preview(BindRef::from(model.stroke_color.clone()));
+        let opacity_preview         = FloodFillMenuController::opacity_preview(BindRef::from(model.opacity.clone()), 32.0-6.0);
+        let opacity_preview_large   = FloodFillMenuController::opacity_preview(BindRef::from(model.opacity.clone()), 100.0);
+
+        let fill_color_preview      = canvases.register(fill_color_preview);
+        let stroke_color_preview    = canvases.register(stroke_color_preview);
+        let opacity_preview         = canvases.register(opacity_preview);
+        let opacity_preview_large   = canvases.register(opacity_preview_large);
+
+        let shape_style             = BindRef::from(model.shape_style.clone());
+        let num_sides               = BindRef::from(model.num_sides.clone());
+
+        // Generate the UI
+        let ui = computed(move || {
+            // Lines are only ever stroked, so they have no style or fill settings
+            let (fills, strokes) = match kind {
+                ShapeKind::Line => (false, true),
+                _               => (shape_style.get().fills(), shape_style.get().strokes())
+            };
+
+            let style_controls = if kind != ShapeKind::Line {
+                vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Style:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0,2), (0,2)))
+                        .with(Font::Size(9.0))
+                        .with(Bounds::next_horiz(132.0))
+                        .with(vec![
+                            Control::button()
+                                .with(vec![Control::label().with("Fill").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::bound("StyleFillSelected")))
+                                .with((ActionTrigger::Click, "StyleFill"))
+                                .with(Bounds::next_horiz(44.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Stroke").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::bound("StyleStrokeSelected")))
+                                .with((ActionTrigger::Click, "StyleStroke"))
+                                .with(Bounds::next_horiz(44.0)),
+                            Control::button()
+                                .with(vec![Control::label().with("Both").with(TextAlign::Center).with(Bounds::fill_all())])
+                                .with(State::Selected(Property::bound("StyleBothSelected")))
+                                .with((ActionTrigger::Click, "StyleBoth"))
+                                .with(Bounds::next_horiz(44.0))
+                        ])
+                ]
+            } else {
+                vec![]
+            };
+
+            let fill_controls = if fills {
+                vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Fill:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(32.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(fill_color_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("FillColorPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowFillColorPopup"))
+                        .with_controller("FillColorPopup")
+                ]
+            } else {
+                vec![]
+            };
+
+            let stroke_controls = if strokes {
+                vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Stroke:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(48.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(stroke_color_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("StrokeColorPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowStrokeColorPopup"))
+                        .with_controller("StrokeColorPopup"),
+                    Control::empty().with(Bounds::next_horiz(8.0)),
+                    Control::label()
+                        .with("Width:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 50.0.to_property())))
+                        .with(State::Value(Property::Bind("StrokeWidth".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeStrokeWidth".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeStrokeWidth".to_string()))
+                ]
+            } else {
+                vec![]
+            };
+
+            let sides_controls = if kind == ShapeKind::Polygon {
+                vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Sides:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range(((MIN_POLYGON_SIDES as f64).to_property(), (MAX_POLYGON_SIDES as f64).to_property())))
+                        .with(State::Value(Property::Bind("Sides".to_string())))
+                        .with(Bounds::next_horiz(72.0))
+                        .with((ActionTrigger::EditValue, "ChangeSides".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeSides".to_string())),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::label()
+                        .with(format!("{}", num_sides.get()))
+                        .with(Bounds::next_horiz(20.0))
+                ]
+            } else {
+                vec![]
+            };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with(format!("{}:", kind.name()))
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(14.0))
+                        .with(Bounds::next_horiz(72.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0))
+                ].into_iter()
+                .chain(style_controls)
+                .chain(fill_controls)
+                .chain(stroke_controls)
+                .chain(sides_controls)
+                .chain(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Opacity:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(56.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((0.0.to_property(), 1.0.to_property())))
+                        .with(State::Value(Property::Bind("Opacity".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeOpacityEdit".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeOpacitySet".to_string())),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(opacity_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(vec![
+                            Control::popup()
+                                .with(Popup::IsOpen(Property::Bind("EditOpacity".to_string())))
+                                .with(Popup::Direction(PopupDirection::Below))
+                                .with(Popup::Size(100, 100))
+                                .with(Popup::Offset(14))
+                                .with(ControlAttribute::ZIndex(1000))
+                                .with(ControlAttribute::Padding((8, 8), (8, 8)))
+                                .with(vec![
+                                    Control::canvas()
+                                        .with(Bounds::fill_all())
+                                        .with(opacity_preview_large.clone())
+                                ])
+                        ])
+                ])
+                .collect::<Vec<_>>())
+        });
+
+        BindRef::from(ui)
+    }
+}
+
+impl Controller for ShapeMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "FillColorPopup"    => Some(self.fill_color_picker.clone()),
+            "StrokeColorPopup"  => Some(self.stroke_color_picker.clone()),
+            _                   => None
+        }
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(self.canvases.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            ("ChangeOpacityEdit", &Value(PropertyValue::Float(new_opacity))) => {
+                // User is dragging the 'opacity' property
+                self.opacity.set(new_opacity as f32);
+                self.view_model.set_property("EditOpacity", PropertyValue::Bool(true));
+            },
+
+            ("ChangeOpacitySet", &Value(PropertyValue::Float(new_opacity))) => {
+                // User has finished dragging the 'opacity' property
+                self.opacity.set(new_opacity as f32);
+                self.view_model.set_property("EditOpacity", PropertyValue::Bool(false));
+            },
+
+            ("ChangeStrokeWidth", &Value(PropertyValue::Float(new_width))) => {
+                self.stroke_width.set(new_width as f32);
+            },
+
+            ("ChangeSides", &Value(PropertyValue::Float(new_sides))) => {
+                // Polygons always have a whole number of sides
+                let new_sides = (new_sides.round() as u32).max(MIN_POLYGON_SIDES).min(MAX_POLYGON_SIDES);
+                self.num_sides.set(new_sides);
+            },
+
+            ("ShowFillColorPopup", _) => {
+                // User has clicked the fill colour icon
+                self.fill_color_picker_open.set(true)
+            },
+
+            ("ShowStrokeColorPopup", _) => {
+                // User has clicked the stroke colour icon
+                self.stroke_color_picker_open.set(true)
+            },
+
+            ("StyleFill", _)    => { self.shape_style.set(ShapeStyle::Fill); },
+            ("StyleStroke", _)  => { self.shape_style.set(ShapeStyle::Stroke); },
+            ("StyleBoth", _)    => { self.shape_style.set(ShapeStyle::FillAndStroke); }
+
+            _ => ()
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use super::controls;
+use super::super::color::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+
+use std::sync::*;
+
+///
+/// Menu controller used for the text tool
+///
+pub struct TextMenuController {
+    text:               Binding<String>,
+    font_size:          Binding<f32>,
+    alignment:          Binding<TextAlignment>,
+
+    canvases:           Arc<ResourceManager<BindingCanvas>>,
+    ui:                 BindRef<Control>,
+    view_model:         Arc<DynamicViewModel>,
+
+    color_picker_open:  Binding<bool>,
+    color_picker:       Arc<PopupController<ColorPickerController>>
+}
+
+impl TextMenuController {
+    ///
+    /// Creates a new text menu controller
+    ///
+    pub fn new(text: Binding<String>, color: Binding<Color>, font_size: Binding<f32>, alignment: Binding<TextAlignment>) -> TextMenuController {
+        // Create the canvases
+        let canvases = Arc::new(ResourceManager::new());
+
+        // Colour picker
+        let color_picker_open       = bind(false);
+        let color_picker            = ColorPickerController::new(&color);
+        let color_picker            = PopupController::new(color_picker, &color_picker_open)
+            .with_direction(&PopupDirection::Below)
+            .with_size(&(500, 124));
+        let color_picker            = Arc::new(color_picker);
+
+        // Create the viewmodel
+        let vm_font_size            = Binding::clone(&font_size);
+        let vm_color_picker_open    = Binding::clone(&color_picker_open);
+        let view_model              = Arc::new(DynamicViewModel::new());
+
+        view_model.set_computed("FontSize", move || PropertyValue::Float(vm_font_size.get() as f64));
+        view_model.set_computed("ColorPickerOpen", move || PropertyValue::Bool(vm_color_picker_open.get()));
+
+        // Build the UI
+        let ui = Self::ui(BindRef::from(text.clone()), BindRef::from(color.clone()), BindRef::from(font_size.clone()), BindRef::from(alignment.clone()), Arc::clone(&canvases));
+
+        TextMenuController {
+            text:               text,
+            font_size:          font_size,
+            alignment:          alignment,
+
+            canvases:           canvases,
+            ui:                 ui,
+            view_model:         view_model,
+
+            color_picker_open:  color_picker_open,
+            color_picker:       color_picker
+        }
+    }
+
+    ///
+    /// Creates the colour preview canvas
+    ///
+    pub fn color_preview(colour: BindRef<Color>) -> BindingCanvas {
+        let control_height  = 32.0 - 6.0;
+
+        BindingCanvas::with_drawing(move |gc| {
+            let size = control_height - 8.0;
+
+            gc.canvas_height(control_height);
+            gc.line_width(2.0);
+            gc.stroke_color(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+            gc.fill_color(colour.get().with_alpha(1.0));
+
+            gc.new_path();
+            gc.circle(0.0, 0.0, size/2.0);
+            gc.fill();
+            gc.stroke();
+        })
+    }
+
+    ///
+    /// Creates the UI for this menu
+    ///
+    fn ui(text: BindRef<String>, color: BindRef<Color>, font_size: BindRef<f32>, alignment: BindRef<TextAlignment>, canvases: Arc<ResourceManager<BindingCanvas>>) -> BindRef<Control> {
+        // Create the canvases
+        let color_preview           = Self::color_preview(color);
+        let color_preview           = canvases.register(color_preview);
+
+        // Generate the UI
+        let ui = computed(move || {
+            let text        = text.get();
+            let font_size   = font_size.get();
+            let alignment   = alignment.get();
+
+            let align_button = |label: &str, button_alignment: TextAlignment, action: &str| {
+                Control::button()
+                    .with(vec![Control::label().with(label).with(TextAlign::Center).with(Bounds::fill_all())])
+                    .with(State::Selected(Property::Bool(alignment == button_alignment)))
+                    .with((ActionTrigger::Click, action))
+                    .with(Bounds::next_horiz(48.0))
+            };
+
+            Control::container()
+                .with(Bounds::fill_all())
+                .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                .with(vec![
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Text:")
+                        .with(FontWeight::Light)
+                        .with(TextAlign::Right)
+                        .with(Font::Size(14.0))
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty()
+                        .with(Bounds::next_horiz(8.0)),
+                    Control::text_box()
+                        .with(text)
+                        .with(Bounds::next_horiz(200.0))
+                        .with((ActionTrigger::Dismiss, "SetText"))
+                        .with((ActionTrigger::SetValue, "SetText")),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Color:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::canvas()
+                        .with(color_preview.clone())
+                        .with(Bounds::next_horiz(32.0))
+                        .with(State::Badged(Property::Bind("ColorPickerOpen".to_string())))
+                        .with((ActionTrigger::Click, "ShowColorPopup"))
+                        .with_controller("ColorPopup"),
+
+                    controls::divider(),
+
+                    Control::label()
+                        .with("Size:")
+                        .with(TextAlign::Right)
+                        .with(Bounds::next_horiz(40.0)),
+                    Control::empty().with(Bounds::next_horiz(6.0)),
+                    Control::slider()
+                        .with(State::Range((8.0.to_property(), 128.0.to_property())))
+                        .with(State::Value(Property::Bind("FontSize".to_string())))
+                        .with(Bounds::next_horiz(96.0))
+                        .with((ActionTrigger::EditValue, "ChangeFontSize".to_string()))
+                        .with((ActionTrigger::SetValue, "ChangeFontSize".to_string())),
+                    Control::empty().with(Bounds::next_horiz(4.0)),
+                    Control::label()
+                        .with(format!("{}", font_size.round()))
+                        .with(Bounds::next_horiz(32.0)),
+
+                    controls::divider(),
+
+                    Control::container()
+                        .with(Hint::Class("button-group".to_string()))
+                        .with(ControlAttribute::Padding((0, 2), (0, 2)))
+                        .with(Font::Size(10.0))
+                        .with(Bounds::next_horiz(48.0*3.0))
+                        .with(vec![
+                            align_button("Left", TextAlignment::Left, "AlignLeft"),
+                            align_button("Center", TextAlignment::Center, "AlignCenter"),
+                            align_button("Right", TextAlignment::Right, "AlignRight")
+                        ])
+                ])
+        });
+
+        BindRef::from(ui)
+    }
+}
+
+impl Controller for TextMenuController {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn get_viewmodel(&self) -> Option<Arc<dyn ViewModel>> {
+        Some(self.view_model.clone())
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match id {
+            "ColorPopup"        => Some(self.color_picker.clone()),
+            _                   => None
+        }
+    }
+
+    fn get_canvas_resources(&self) -> Option<Arc<ResourceManager<BindingCanvas>>> {
+        Some(self.canvases.clone())
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        match (action_id, action_parameter) {
+            ("SetText", Value(PropertyValue::String(new_text))) => {
+                // User has finished editing the text to add
+                self.text.set(new_text.clone());
+            },
+
+            ("ChangeFontSize", &Value(PropertyValue::Float(new_size))) => {
+                // User has dragged the 'size' slider
+                self.font_size.set(new_size as f32);
+            },
+
+            ("AlignLeft", _)        => self.alignment.set(TextAlignment::Left),
+            ("AlignCenter", _)      => self.alignment.set(TextAlignment::Center),
+            ("AlignRight", _)       => self.alignment.set(TextAlignment::Right),
+
+            ("ShowColorPopup", _) => {
+                // User has clicked the colour icon
+                self.color_picker_open.set(true)
+            }
+
+            _ => ()
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use super::controls;
+use super::super::model::*;
+use super::super::standard_tools::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use desync::*;
+use futures::*;
+use futures::executor;
+use futures::executor::Spawn;
+
+use std::sync::*;
+use std::collections::HashSet;
+
+///
+/// The menu controller for the transform tool
+///
+pub struct TransformMenuController<Anim: Animation> {
+    /// The angle to rotate the selection by, in degrees
+    rotate_degrees: Binding<f32>,
+
+    /// The horizontal scale factor, as a percentage
+    scale_width: Binding<f32>,
+
+    /// The vertical scale factor, as a percentage
+    scale_height: Binding<f32>,
+
+    /// The angle to skew the selection by, in degrees
+    skew_degrees: Binding<f32>,
+
+    /// True if changing the width also changes the height
+    proportional: Binding<bool>,
+
+    /// The point that the selection is transformed about
+    pivot: BindRef<(f32, f32)>,
+
+    /// The pivot point chosen by the user
+    custom_pivot: Binding<Option<(Arc<HashSet<ElementId>>, (f32, f32))>>,
+
+    /// The animation editing stream where this will send updates
+    edit: Desync<Spawn<Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send>>>,
+
+    /// The timeline model for the animation
+    timeline: TimelineModel<Anim>,
+
+    /// The model for the animation (used to find the selected elements)
+    flo_model: FloModel<Anim>,
+
+    // The UI for this control
+    ui: BindRef<Control>
+}
+
+impl<Anim: 'static+EditableAnimation+Animation> TransformMenuController<Anim> {
+    ///
+    /// Creates a new transform menu controller
+    ///
+    pub fn new(flo_model: &FloModel<Anim>, tool_model: &TransformModel) -> TransformMenuController<Anim> {
+        let ui          = Self::ui(tool_model);
+        let edit        = Desync::new(executor::spawn(flo_model.edit()));
+        let timeline    = flo_model.timeline().clone();
+        let flo_model   = flo_model.clone();
+
+        TransformMenuController {
+            rotate_degrees: tool_model.rotate_degrees.clone(),
+            scale_width:    tool_model.scale_width.clone(),
+            scale_height:   tool_model.scale_height.clone(),
+            skew_degrees:   tool_model.skew_degrees.clone(),
+            proportional:   tool_model.proportional.clone(),
+            pivot:          tool_model.pivot.clone(),
+            custom_pivot:   tool_model.custom_pivot.clone(),
+            ui:             ui,
+            edit:           edit,
+            timeline:       timeline,
+            flo_model:      flo_model
+        }
+    }
+
+    ///
+    /// Creates a label followed by a text box for entering a number
+    ///
+    fn number_entry(label: &str, label_width: f32, value: f32, units: &str, action: &str) -> Vec<Control> {
+        vec![
+            Control::label()
+                .with(label)
+                .with(TextAlign::Right)
+                .with(Font::Size(13.0))
+                .with(Bounds::next_horiz(label_width)),
+            Control::empty()
+                .with(Bounds::next_horiz(4.0)),
+            Control::text_box()
+                .with(format!("{}", value))
+                .with(Bounds::next_horiz(40.0))
+                .with((ActionTrigger::Dismiss, action))
+                .with((ActionTrigger::SetValue, action)),
+            Control::label()
+                .with(units)
+                .with(TextAlign::Left)
+                .with(Font::Size(13.0))
+                .with(Bounds::next_horiz(16.0))
+        ]
+    }
+
+    ///
+    /// Creates the UI for the transform menu controller
+    ///
+    fn ui(tool_model: &TransformModel) -> BindRef<Control> {
+        let rotate_degrees  = tool_model.rotate_degrees.clone();
+        let scale_width     = tool_model.scale_width.clone();
+        let scale_height    = tool_model.scale_height.clone();
+        let skew_degrees    = tool_model.skew_degrees.clone();
+        let proportional    = tool_model.proportional.clone();
+
+        let ui              =
+            computed(move || {
+                let rotate_degrees  = rotate_degrees.get();
+                let scale_width     = scale_width.get();
+                let scale_height    = scale_height.get();
+                let skew_degrees    = skew_degrees.get();
+                let proportional    = proportional.get();
+
+                Control::container()
+                    .with(Bounds::fill_all())
+                    .with(ControlAttribute::Padding((0, 3), (0, 3)))
+                    .with(vec![
+                        controls::divider(),
+
+                        Control::label()
+                            .with("Transform:")
+                            .with(FontWeight::Light)
+                            .with(TextAlign::Right)
+                            .with(Font::Size(14.0))
+                            .with(Bounds::next_horiz(72.0)),
+
+                        controls::divider()
+                    ].into_iter()
+                    .chain(Self::number_entry("Rotate:", 48.0, rotate_degrees, "°", "SetRotation"))
+                    .chain(Some(controls::divider()))
+                    .chain(Self::number_entry("W:", 20.0, scale_width, "%", "SetScaleWidth"))
+                    .chain(Self::number_entry("H:", 20.0, scale_height, "%", "SetScaleHeight"))
+                    .chain(vec![
+                        Control::empty()
+                            .with(Bounds::next_horiz(4.0)),
+                        Control::container()
+                            .with(Hint::Class("button-group".to_string()))
+                            .with(ControlAttribute::Padding((0,2), (0,2)))
+                            .with(Font::Size(9.0))
+                            .with(Bounds::next_horiz(44.0))
+                            .with(vec![
+                                Control::button()
+                                    .with(vec![Control::label().with("Lock").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with(State::Selected(Property::Bool(proportional)))
+                                    .with((ActionTrigger::Click, "ToggleProportional"))
+                                    .with(Bounds::next_horiz(44.0))
+                            ]),
+                        controls::divider()
+                    ])
+                    .chain(Self::number_entry("Skew:", 36.0, skew_degrees, "°", "SetSkew"))
+                    .chain(vec![
+                        controls::divider(),
+
+                        Control::container()
+                            .with(Hint::Class("button-group".to_string()))
+                            .with(ControlAttribute::Padding((0,2), (0,2)))
+                            .with(Font::Size(9.0))
+                            .with(Bounds::next_horiz(132.0))
+                            .with(vec![
+                                Control::button()
+                                    .with(vec![Control::label().with("Apply").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "Apply"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Reset").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "Reset"))
+                                    .with(Bounds::next_horiz(44.0)),
+                                Control::button()
+                                    .with(vec![Control::label().with("Center").with(TextAlign::Center).with(Bounds::fill_all())])
+                                    .with((ActionTrigger::Click, "CenterPivot"))
+                                    .with(Bounds::next_horiz(44.0))
+                            ])
+                    ])
+                    .collect::<Vec<_>>())
+            });
+
+        BindRef::from(ui)
+    }
+
+    ///
+    /// Sets the values in the menu back to ones that leave the selection unchanged
+    ///
+    fn reset(&self) {
+        self.rotate_degrees.set(0.0);
+        self.scale_width.set(100.0);
+        self.scale_height.set(100.0);
+        self.skew_degrees.set(0.0);
+    }
+
+    ///
+    /// Applies the transformation entered in the menu to the selection
+    ///
+    fn apply(&self) {
+        // Scale, then skew, then rotate about the pivot
+        let rotate      = Transform2D::rotate(self.rotate_degrees.get().to_radians());
+        let scale       = Transform2D::scale(self.scale_width.get()/100.0, self.scale_height.get()/100.0);
+        let skew        = Transform2D::skew(self.skew_degrees.get().to_radians().tan(), 0.0);
+        let transform   = Transform::about_point(self.pivot.get(), rotate * skew * scale);
+
+        // All of the elements are edited at once so the transformation can be undone in one step
+        let edits       = Transform::edits_for_transform(&self.flo_model, &transform);
+
+        if !edits.is_empty() {
+            self.edit.sync(move |animation| {
+                animation.wait_send(edits).ok();
+            });
+
+            self.timeline.invalidate_canvas();
+        }
+
+        self.reset();
+    }
+}
+
+impl<Anim: 'static+EditableAnimation+Animation> Controller for TransformMenuController<Anim> {
+    fn ui(&self) -> BindRef<Control> {
+        self.ui.clone()
+    }
+
+    fn action(&self, action_id: &str, action_parameter: &ActionParameter) {
+        use self::ActionParameter::*;
+
+        // Values that can't be parsed leave the current value as it is
+        let value = match action_parameter {
+            Value(PropertyValue::String(value)) => value.trim().trim_end_matches(|c| c == '%' || c == '°').parse::<f32>().ok().filter(|value| value.is_finite()),
+            _                                   => None
+        };
+
+        match (action_id, value) {
+            ("SetRotation", Some(degrees))      => { self.rotate_degrees.set(degrees); },
+            ("SetSkew", Some(degrees))          => { self.skew_degrees.set(degrees.max(-89.0).min(89.0)); },
+
+            ("SetScaleWidth", Some(percent))    => {
+                self.scale_width.set(percent);
+                if self.proportional.get() { self.scale_height.set(percent); }
+            },
+
+            ("SetScaleHeight", Some(percent))   => {
+                self.scale_height.set(percent);
+                if self.proportional.get() { self.scale_width.set(percent); }
+            },
+
+            ("ToggleProportional", _)           => { self.proportional.set(!self.proportional.get()); },
+            ("CenterPivot", _)                  => { self.custom_pivot.set(None); },
+            ("Reset", _)                        => { self.reset(); },
+            ("Apply", _)                        => { self.apply(); },
+
+            _ => { }
+        }
+    }
+}
@@ -21,3 +21,57 @@ pub enum BrushRepresentation {
     /// Brush strokes are stored directly as paths (in additive mode, things the brush stroke is added to will also be turned to paths)
     Path
 }
+
+///
+/// How the shapes created by a fill are coloured
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillStyle {
+    /// Shapes are filled with a single colour
+    Solid,
+
+    /// Shapes are filled with a gradient that runs across the shape
+    LinearGradient,
+
+    /// Shapes are filled with a gradient that runs from the center of the shape outwards
+    RadialGradient
+}
+
+///
+/// How the shapes created by the shape tools are drawn
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShapeStyle {
+    /// The inside of the shape is filled
+    Fill,
+
+    /// The outline of the shape is drawn
+    Stroke,
+
+    /// The shape is filled and its outline is drawn over the top
+    FillAndStroke
+}
+
+impl ShapeStyle {
+    ///
+    /// True if shapes drawn in this style have their inside filled
+    ///
+    pub fn fills(&self) -> bool {
+        match self {
+            ShapeStyle::Fill            => true,
+            ShapeStyle::Stroke          => false,
+            ShapeStyle::FillAndStroke   => true
+        }
+    }
+
+    ///
+    /// True if shapes drawn in this style have their outline drawn
+    ///
+    pub fn strokes(&self) -> bool {
+        match self {
+            ShapeStyle::Fill            => false,
+            ShapeStyle::Stroke          => true,
+            ShapeStyle::FillAndStroke   => true
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+use flo_import::*;
+use flo_export::*;
+
+use std::sync::*;
+use std::time::Duration;
+
+///
+/// An element that has been copied to the clipboard
+///
+/// Elements are stored along with the brush definition and properties that were in effect when they
+/// were copied, so they look the same wherever they are pasted.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ClipboardElement {
+    /// A brush stroke, with the points generated by the brush it was drawn with
+    BrushStroke(BrushDefinition, BrushDrawingStyle, BrushProperties, Arc<Vec<BrushPoint>>),
+
+    /// A path
+    Path(BrushDefinition, BrushDrawingStyle, BrushProperties, Arc<Vec<PathComponent>>),
+
+    /// A text element (style, position, text)
+    Text(TextStyle, (f32, f32), String),
+
+    /// A bitmap image
+    Image(CanvasImage, Transform2D),
+
+    /// A group of elements
+    Group(GroupType, Vec<ClipboardElement>)
+}
+
+///
+/// The contents of the clipboard
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum ClipboardContents {
+    /// Nothing has been copied
+    Empty,
+
+    /// A set of elements copied from an animation (in back-to-front order)
+    Elements(Arc<Vec<ClipboardElement>>),
+
+    /// An SVG document (usually copied from another application)
+    Svg(Arc<String>)
+}
+
+///
+/// Model representing the elements that have been copied to the clipboard
+///
+#[derive(Clone)]
+pub struct ClipboardModel {
+    /// The current contents of the clipboard
+    pub contents: BindRef<ClipboardContents>,
+
+    /// The binding for the contents (used when updating)
+    contents_binding: Binding<ClipboardContents>
+}
+
+impl ClipboardElement {
+    ///
+    /// Creates a clipboard element from an element in a frame
+    ///
+    /// Returns None for elements that can't be copied by themselves (brush definitions, brush properties and motions)
+    ///
+    pub fn from_frame_element(frame: &dyn Frame, element: &Vector) -> Option<ClipboardElement> {
+        let properties = frame.apply_properties_for_element(element, Arc::new(VectorProperties::default()));
+
+        Self::from_vector(frame, element, &*properties)
+    }
+
+    ///
+    /// Creates a clipboard element from a vector and the properties that apply to it
+    ///
+    fn from_vector(frame: &dyn Frame, element: &Vector, properties: &VectorProperties) -> Option<ClipboardElement> {
+        match element {
+            // Transformed elements are copied as they currently appear
+            Vector::Transformed(transformed)    => Self::from_vector(frame, &*transformed.transformed_vector(), properties),
+
+            Vector::BrushStroke(brush_stroke)   => {
+                let (definition, drawing_style) = properties.brush.to_definition();
+                Some(ClipboardElement::BrushStroke(definition, drawing_style, properties.brush_properties.clone(), brush_stroke.points()))
+            },
+
+            Vector::Path(path)                  => {
+                let (definition, drawing_style) = properties.brush.to_definition();
+                Some(ClipboardElement::Path(definition, drawing_style, properties.brush_properties.clone(), Arc::new(path.path().elements().collect())))
+            },
+
+            Vector::Text(text)                  => Some(ClipboardElement::Text(text.style().clone(), text.position(), text.text().to_string())),
+            Vector::Image(image)                => Some(ClipboardElement::Image(image.image().clone(), image.transform())),
+
+            Vector::Group(group)                => {
+                // Each element in the group can have its own properties
+                let group_properties    = Arc::new(properties.clone());
+                let elements            = group.elements()
+                    .filter_map(|element| {
+                        let element_properties = frame.apply_properties_for_element(element, Arc::clone(&group_properties));
+                        Self::from_vector(frame, element, &*element_properties)
+                    })
+                    .collect();
+
+                Some(ClipboardElement::Group(group.group_type(), elements))
+            },
+
+            Vector::BrushDefinition(_)          |
+            Vector::BrushProperties(_)          |
+            Vector::Motion(_)                   => None
+        }
+    }
+
+    ///
+    /// Generates the edits that will re-create this element on a layer, returning the ID of the element that will be created
+    ///
+    /// New elements are given IDs using the `assign_id` function.
+    ///
+    pub fn create_edits(&self, layer_id: u64, when: Duration, assign_id: &mut dyn FnMut() -> ElementId, edits: &mut Vec<AnimationEdit>) -> ElementId {
+        use self::ClipboardElement::*;
+
+        let element_id = assign_id();
+
+        match self {
+            BrushStroke(definition, drawing_style, properties, points)  => {
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(assign_id(), definition.clone(), *drawing_style))));
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(assign_id(), properties.clone()))));
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateBrushStroke(element_id, Arc::clone(points)))));
+            },
+
+            Path(definition, drawing_style, properties, components)     => {
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::SelectBrush(assign_id(), definition.clone(), *drawing_style))));
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::BrushProperties(assign_id(), properties.clone()))));
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::CreatePath(element_id, Arc::clone(components)))));
+            },
+
+            Text(style, position, text)                                 => {
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Text(when, TextEdit::CreateText(element_id, style.clone(), *position, text.clone()))));
+            },
+
+            Image(image, transform)                                     => {
+                edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Image(when, ImageEdit::CreateImage(element_id, image.clone(), *transform))));
+            },
+
+            Group(group_type, elements)                                 => {
+                // Create the elements in the group, then group them together
+                let grouped_ids = elements.iter()
+                    .map(|element| element.create_edits(layer_id, when, assign_id, edits))
+                    .collect();
+
+                edits.push(AnimationEdit::Element(grouped_ids, ElementEdit::Group(element_id, *group_type)));
+            }
+        }
+
+        element_id
+    }
+
+    ///
+    /// Converts this element into the vector elements needed to render it
+    ///
+    fn to_vectors(&self) -> Vec<Vector> {
+        use self::ClipboardElement::*;
+
+        match self {
+            BrushStroke(definition, drawing_style, properties, points)  => vec![
+                Vector::BrushDefinition(BrushDefinitionElement::new(ElementId::Unassigned, definition.clone(), *drawing_style)),
+                Vector::BrushProperties(BrushPropertiesElement::new(ElementId::Unassigned, properties.clone())),
+                Vector::BrushStroke(BrushElement::new(ElementId::Unassigned, Arc::clone(points)))
+            ],
+
+            Path(definition, drawing_style, properties, components)     => vec![
+                Vector::Path(PathElement::new(ElementId::Unassigned,
+                    flo_animation::Path::from_elements_arc(Arc::clone(components)),
+                    Arc::new(BrushDefinitionElement::new(ElementId::Unassigned, definition.clone(), *drawing_style)),
+                    Arc::new(BrushPropertiesElement::new(ElementId::Unassigned, properties.clone()))))
+            ],
+
+            Text(style, position, text)                                 => vec![Vector::Text(TextElement::new(ElementId::Unassigned, *position, text.clone(), style.clone()))],
+            Image(image, transform)                                     => vec![Vector::Image(ImageElement::new(ElementId::Unassigned, image.clone(), *transform))],
+
+            Group(group_type, elements)                                 => {
+                let grouped = elements.iter().flat_map(|element| element.to_vectors()).collect();
+                vec![Vector::Group(GroupElement::new(ElementId::Unassigned, *group_type, Arc::new(grouped)))]
+            }
+        }
+    }
+}
+
+impl ClipboardContents {
+    ///
+    /// Generates the edits needed to paste these contents into a layer at a particular time
+    ///
+    /// Returns the edits along with the IDs of the pasted elements. New elements are given IDs using the
+    /// `assign_id` function. The height of the frame is needed to position SVG documents.
+    ///
+    pub fn paste_edits(&self, layer_id: u64, when: Duration, frame_height: f64, assign_id: &mut dyn FnMut() -> ElementId) -> Result<(Vec<AnimationEdit>, Vec<ElementId>), SvgImportError> {
+        use self::ClipboardContents::*;
+
+        match self {
+            Empty               => Ok((vec![], vec![])),
+
+            Elements(elements)  => {
+                let mut edits   = vec![];
+                let pasted_ids  = elements.iter()
+                    .map(|element| element.create_edits(layer_id, when, assign_id, &mut edits))
+                    .collect();
+
+                Ok((edits, pasted_ids))
+            },
+
+            Svg(svg)            => {
+                let import      = svg_to_edits(&*svg, layer_id, when, frame_height)?;
+
+                // Assign the IDs now so the paths that are created can be selected
+                let edits       = import.edits.into_iter()
+                    .map(|edit| edit.assign_element_id(|| assign_id().id().unwrap()))
+                    .collect::<Vec<_>>();
+                let pasted_ids  = edits.iter()
+                    .filter_map(|edit| match edit {
+                        AnimationEdit::Layer(_, LayerEdit::Path(_, PathEdit::CreatePath(element_id, _))) => Some(*element_id),
+                        _                                                                               => None
+                    })
+                    .collect();
+
+                Ok((edits, pasted_ids))
+            }
+        }
+    }
+}
+
+impl ClipboardModel {
+    ///
+    /// Creates a new, empty clipboard
+    ///
+    pub fn new() -> ClipboardModel {
+        let contents_binding = bind(ClipboardContents::Empty);
+
+        ClipboardModel {
+            contents:           BindRef::new(&contents_binding),
+            contents_binding:   contents_binding
+        }
+    }
+
+    ///
+    /// True if there is nothing on the clipboard
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.contents.get() == ClipboardContents::Empty
+    }
+
+    ///
+    /// Removes everything from the clipboard
+    ///
+    pub fn clear(&self) {
+        self.contents_binding.set(ClipboardContents::Empty);
+    }
+
+    ///
+    /// Replaces the contents of the clipboard with a set of elements
+    ///
+    pub fn set_elements(&self, elements: Vec<ClipboardElement>) {
+        self.contents_binding.set(ClipboardContents::Elements(Arc::new(elements)));
+    }
+
+    ///
+    /// Replaces the contents of the clipboard with an SVG document
+    ///
+    /// The document is checked before it's placed on the clipboard, and the clipboard is left unchanged if it can't be read.
+    ///
+    pub fn set_svg(&self, svg: &str) -> Result<(), SvgImportError> {
+        svg_to_edits(svg, 0, Duration::from_millis(0), 0.0)?;
+
+        self.contents_binding.set(ClipboardContents::Svg(Arc::new(svg.to_string())));
+        Ok(())
+    }
+
+    ///
+    /// Renders the contents of the clipboard as an SVG document with the specified size
+    ///
+    /// Elements are positioned as they were in the frame they were copied from, so the size should usually be the size
+    /// of the animation. Returns None if the clipboard is empty.
+    ///
+    pub fn to_svg(&self, width: f64, height: f64) -> Option<String> {
+        match self.contents.get() {
+            ClipboardContents::Empty                => None,
+            ClipboardContents::Svg(svg)             => Some((*svg).clone()),
+
+            ClipboardContents::Elements(elements)   => {
+                let mut canvas      = SvgCanvas::new(width as f32, height as f32);
+                let mut properties  = Arc::new(VectorProperties::default());
+                let when            = Duration::from_millis(0);
+
+                // Use the same coordinate scheme as the animation
+                canvas.canvas_height(height as f32);
+                canvas.center_region(0.0, 0.0, width as f32, height as f32);
+
+                // Render the elements in order
+                for vector in elements.iter().flat_map(|element| element.to_vectors()) {
+                    properties = vector.update_properties(properties);
+                    properties.render(&mut canvas, vector, when);
+                }
+
+                Some(canvas.to_svg())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn test_brush_stroke() -> ClipboardElement {
+        ClipboardElement::BrushStroke(BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw, BrushProperties::new(), Arc::new(vec![
+            BrushPoint { position: (10.0, 10.0), cp1: (10.0, 10.0), cp2: (10.0, 10.0), width: 1.0 },
+            BrushPoint { position: (20.0, 5.0), cp1: (13.0, 8.0), cp2: (17.0, 6.0), width: 2.0 }
+        ]))
+    }
+
+    #[test]
+    fn clipboard_starts_empty() {
+        let clipboard = ClipboardModel::new();
+
+        assert!(clipboard.is_empty());
+        assert!(clipboard.to_svg(100.0, 100.0).is_none());
+    }
+
+    #[test]
+    fn paste_assigns_new_ids() {
+        let clipboard   = ClipboardModel::new();
+        clipboard.set_elements(vec![test_brush_stroke(), ClipboardElement::Group(GroupType::Normal, vec![test_brush_stroke(), test_brush_stroke()])]);
+
+        let mut next_id         = 100;
+        let (edits, pasted_ids) = clipboard.contents.get().paste_edits(1, Duration::from_millis(0), 1080.0, &mut || { next_id += 1; ElementId::Assigned(next_id) }).unwrap();
+
+        // Two top-level elements are pasted, and all of the edits have IDs
+        let paint_ids = edits.iter()
+            .filter_map(|edit| match edit {
+                AnimationEdit::Layer(1, LayerEdit::Paint(_, paint_edit))    => Some(paint_edit.id()),
+                _                                                           => None
+            })
+            .collect::<Vec<_>>();
+
+        assert!(pasted_ids.len() == 2);
+        assert!(edits.len() == 10);
+        assert!(paint_ids.len() == 9);
+        assert!(paint_ids.iter().all(|id| id.is_assigned()));
+
+        // The group is created after the elements it contains
+        assert!(match &edits[9] {
+            AnimationEdit::Element(grouped, ElementEdit::Group(group_id, GroupType::Normal)) => grouped.len() == 2 && *group_id == pasted_ids[1],
+            _ => false
+        });
+    }
+
+    #[test]
+    fn emit_and_paste_svg() {
+        let clipboard   = ClipboardModel::new();
+        clipboard.set_elements(vec![
+            ClipboardElement::Path(BrushDefinition::Simple, BrushDrawingStyle::Draw, BrushProperties::new(), Arc::new(vec![
+                PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 10.0)),
+                PathComponent::Line(PathPoint::new(50.0, 50.0)),
+                PathComponent::Close
+            ]))
+        ]);
+
+        let svg         = clipboard.to_svg(100.0, 100.0).unwrap();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("<path"));
+
+        // The SVG can be pasted back in as a path
+        let pasted      = ClipboardModel::new();
+        pasted.set_svg(&svg).unwrap();
+
+        let mut next_id         = 0;
+        let (_edits, pasted_ids) = pasted.contents.get().paste_edits(1, Duration::from_millis(0), 100.0, &mut || { next_id += 1; ElementId::Assigned(next_id) }).unwrap();
+
+        assert!(pasted_ids.len() == 1);
+        assert!(pasted.set_svg("not an svg document").is_err());
+    }
+}
@@ -3,10 +3,14 @@ use super::frame::*;
 use super::timeline::*;
 use super::selection::*;
 use super::onion_skin::*;
+use super::undo::*;
+use super::clipboard::*;
+use super::shared_edits::*;
 
 use flo_stream::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_import::SvgImportError;
 use futures::*;
 use futures::executor;
 use futures::executor::Spawn;
@@ -38,6 +42,12 @@ pub struct FloModel<Anim: Animation> {
     /// The onion skin model
     onion_skin: OnionSkinModel<Anim>,
 
+    /// The undo history for this animation
+    undo_history: UndoModel,
+
+    /// The clipboard used for copying and pasting elements
+    clipboard: ClipboardModel,
+
     /// The size of the animation
     pub size: BindRef<(f64, f64)>,
 
@@ -48,7 +58,30 @@ pub struct FloModel<Anim: Animation> {
     frame_edit_counter: Binding<u64>,
 
     /// Publisher where we send edits to this stream
-    edit_publisher: Arc<Desync<Spawn<Publisher<Arc<Vec<AnimationEdit>>>>>>
+    edit_publisher: Arc<Desync<Spawn<Publisher<Arc<Vec<AnimationEdit>>>>>>,
+
+    /// The edits shared with any other models that are editing the same animation
+    shared_edits: SharedEdits,
+
+    /// The ID of this model in the shared edits
+    instance_id: usize
+}
+
+///
+/// The parts of the model that are updated as a side-effect of edits to the animation
+///
+struct ModelUpdate<Anim: Animation> {
+    /// The animation that was edited
+    animation: Arc<Anim>,
+
+    /// The size of the animation
+    size_binding: Binding<(f64, f64)>,
+
+    /// The timeline model
+    timeline: TimelineModel<Anim>,
+
+    /// Counter used to set an edit ID for the frame
+    frame_edit_counter: Binding<u64>
 }
 
 impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
@@ -56,19 +89,39 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
     /// Creates a new model
     ///
     pub fn new(animation: Anim) -> FloModel<Anim> {
+        Self::new_shared(Arc::new(animation), SharedEdits::new())
+    }
+
+    ///
+    /// Creates a new model for an animation that might also be edited by other models
+    ///
+    /// Edits made by any model created with the same shared edits will update all of the others.
+    ///
+    pub fn new_shared(animation: Arc<Anim>, shared_edits: SharedEdits) -> FloModel<Anim> {
+        Self::new_with_clipboard(animation, shared_edits, ClipboardModel::new())
+    }
+
+    ///
+    /// Creates a new model that uses a particular clipboard
+    ///
+    /// Models normally have their own clipboard, so sessions can't see what was copied in other sessions. Models that
+    /// are created with the same clipboard can copy elements from one animation and paste them into another.
+    ///
+    pub fn new_with_clipboard(animation: Arc<Anim>, shared_edits: SharedEdits, clipboard: ClipboardModel) -> FloModel<Anim> {
         let mut edit_publisher  = executor::spawn(Publisher::new(10));
-        let animation           = Arc::new(animation);
         let tools               = ToolModel::new();
         let timeline            = TimelineModel::new(Arc::clone(&animation), edit_publisher.subscribe());
         let frame_edit_counter  = bind(0);
         let frame               = FrameModel::new(Arc::clone(&animation), edit_publisher.subscribe(), BindRef::new(&timeline.current_time), BindRef::new(&frame_edit_counter), BindRef::new(&timeline.selected_layer));
         let selection           = SelectionModel::new(&frame, &timeline);
-        let onion_skin          = OnionSkinModel::new(Arc::clone(&animation), &timeline);
+        let onion_skin          = OnionSkinModel::new(Arc::clone(&animation), &timeline, BindRef::new(&frame_edit_counter));
+        let undo_history        = UndoModel::new();
 
         let size_binding        = bind(animation.size());
         let edit_publisher      = Arc::new(Desync::new(edit_publisher));
+        let instance_id         = shared_edits.new_instance_id();
 
-        FloModel {
+        let model = FloModel {
             animation:          animation,
             tools:              tools,
             timeline:           timeline,
@@ -76,12 +129,51 @@ impl<Anim: EditableAnimation+Animation+'static> FloModel<Anim> {
             frame:              frame,
             selection:          selection,
             onion_skin:         onion_skin,
+            undo_history:       undo_history,
+            clipboard:          clipboard,
 
             size:               BindRef::from(size_binding.clone()),
             size_binding:       size_binding,
 
-            edit_publisher:     edit_publisher
-        }
+            edit_publisher:     edit_publisher,
+            shared_edits:       shared_edits,
+            instance_id:        instance_id
+        };
+
+        model.follow_shared_edits();
+
+        model
+    }
+
+    ///
+    /// Updates this model whenever another model sharing the same animation makes an edit
+    ///
+    fn follow_shared_edits(&self) {
+        // The edits stop being followed once the last reference to this model has been released
+        let edit_publisher  = Arc::downgrade(&self.edit_publisher);
+        let model_open      = Weak::clone(&edit_publisher);
+        let remote_edits    = self.shared_edits.subscribe_remote(self.instance_id)
+            .take_while(move |_edits| Ok(model_open.upgrade().is_some()));
+
+        // Remote edits update the model in the same way as local ones
+        let model_update    = Arc::new(Desync::new(self.model_update()));
+        let undo_history    = self.undo_history.clone();
+
+        pipe_in(model_update, remote_edits, move |model_update, edits| {
+            if let (Ok(edits), Some(edit_publisher)) = (edits, edit_publisher.upgrade()) {
+                model_update.update(&*edits);
+
+                // The local undo history was generated against the animation before these edits, so it can no longer be applied
+                undo_history.clear();
+
+                // We don't know which frames or keyframes the other model has changed
+                model_update.timeline.invalidate_canvas();
+                model_update.timeline.update_keyframe_bindings();
+
+                // Republish to the subscribers of this model
+                edit_publisher.sync(move |publisher| publisher.wait_send(edits)).unwrap();
+            }
+        });
     }
 }
 
@@ -121,6 +213,20 @@ impl<Anim: Animation+'static> FloModel<Anim> {
         &self.onion_skin
     }
 
+    ///
+    /// Retrieves the undo history for this animation
+    ///
+    pub fn undo_history(&self) -> &UndoModel {
+        &self.undo_history
+    }
+
+    ///
+    /// Retrieves the clipboard used by this animation
+    ///
+    pub fn clipboard(&self) -> &ClipboardModel {
+        &self.clipboard
+    }
+
     ///
     /// Retrieves the frame update binding for this animation
     ///
@@ -134,6 +240,17 @@ impl<Anim: Animation+'static> FloModel<Anim> {
     pub fn subscribe_edits(&self) -> impl Stream<Item=Arc<Vec<AnimationEdit>>, Error=()>+Clone+Send {
         self.edit_publisher.sync(|publisher| publisher.subscribe())
     }
+
+    ///
+    /// Returns the ID of the layer that contains the element with the specified ID
+    ///
+    /// The frames at the current time are searched first, followed by every keyframe, so elements that are on
+    /// other keyframes are found too.
+    ///
+    pub fn layer_for_element(&self, element_id: ElementId) -> Option<u64> {
+        locate_element(&*self.animation, self.timeline.current_time.get(), element_id)
+            .map(|(layer_id, _when, _frame)| layer_id)
+    }
 }
 
 // Clone because for some reason #[derive(Clone)] does something weird
@@ -147,11 +264,15 @@ impl<Anim: Animation> Clone for FloModel<Anim> {
             frame:              self.frame.clone(),
             selection:          self.selection.clone(),
             onion_skin:         self.onion_skin.clone(),
+            undo_history:       self.undo_history.clone(),
+            clipboard:          self.clipboard.clone(),
 
             size:               self.size.clone(),
             size_binding:       self.size_binding.clone(),
 
-            edit_publisher:     self.edit_publisher.clone()
+            edit_publisher:     self.edit_publisher.clone(),
+            shared_edits:       self.shared_edits.clone(),
+            instance_id:        self.instance_id
         }
     }
 }
@@ -292,76 +413,314 @@ impl<TargetSink: Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>, ProcessingFn:
     }
 }
 
-impl<Anim: 'static+Animation+EditableAnimation> EditableAnimation for FloModel<Anim> {
+impl<Anim: 'static+Animation+EditableAnimation> FloModel<Anim> {
     ///
-    /// Retrieves a sink that can be used to send edits for this animation
+    /// Undoes the most recent transaction in the undo history
     ///
-    /// Edits are supplied as groups (stored in a vec) so that it's possible to ensure that
-    /// a set of related edits are performed atomically
+    pub fn undo(&self) {
+        if let Some(undo_edits) = self.undo_history.take_undo() {
+            self.perform_edits_without_undo((*undo_edits).clone());
+        }
+    }
+
     ///
-    fn edit(&self) -> Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send> {
+    /// Redoes the most recently undone transaction
+    ///
+    pub fn redo(&self) {
+        if let Some(redo_edits) = self.undo_history.take_redo() {
+            self.perform_edits_without_undo((*redo_edits).clone());
+        }
+    }
+
+    ///
+    /// Copies the selected elements to the clipboard
+    ///
+    /// Elements are copied along with the brush that they were drawn with. Nothing is copied if the selection is empty.
+    ///
+    pub fn copy_selection(&self) {
+        let selection   = self.selection.selection_in_order.get();
+        let layer_id    = self.timeline.selected_layer.get();
+        let when        = self.timeline.current_time.get();
+        let layer       = layer_id.and_then(|layer_id| self.animation.get_layer_with_id(layer_id));
+
+        if let Some(layer) = layer {
+            let frame       = layer.get_frame_at_time(when);
+            let elements    = selection.iter()
+                .filter_map(|element_id| frame.element_with_id(*element_id))
+                .filter_map(|element| ClipboardElement::from_frame_element(&*frame, &element))
+                .collect::<Vec<_>>();
+
+            if elements.len() > 0 {
+                self.clipboard.set_elements(elements);
+            }
+        }
+    }
+
+    ///
+    /// Copies the selected elements to the clipboard and then removes them from the animation
+    ///
+    /// Nothing is cut from locked layers.
+    ///
+    pub fn cut_selection(&self) {
+        let selection = self.selection.selection_in_order.get();
+        if selection.len() == 0 {
+            return;
+        }
+
+        let layer_locked = selection.iter()
+            .filter_map(|element_id| self.layer_for_element(*element_id))
+            .any(|layer_id| self.animation.get_layer_with_id(layer_id).map(|layer| layer.is_locked()).unwrap_or(false));
+        if layer_locked {
+            return;
+        }
+
+        self.copy_selection();
+
+        let mut edit_sink = executor::spawn(self.edit());
+        edit_sink.wait_send(vec![AnimationEdit::Element(selection.iter().cloned().collect(), ElementEdit::Delete)]).unwrap();
+
+        self.selection.clear_selection();
+        self.timeline.invalidate_canvas();
+    }
+
+    ///
+    /// Pastes the contents of the clipboard into the selected layer at the current time
+    ///
+    /// The pasted elements are given new IDs and become the selection. A keyframe is created if there's no keyframe to
+    /// paste into (or if there's no keyframe at the current time and new keyframes are created when drawing). Nothing is
+    /// pasted into locked layers.
+    ///
+    pub fn paste(&self) -> Result<(), SvgImportError> {
+        let layer_id    = match self.timeline.selected_layer.get() { Some(layer_id) => layer_id, None => { return Ok(()); } };
+        let layer       = match self.animation.get_layer_with_id(layer_id) { Some(layer) => layer, None => { return Ok(()); } };
+
+        if layer.is_locked() || layer.as_vector_layer().is_none() {
+            return Ok(());
+        }
+
+        // Generate the edits for the clipboard contents, with new element IDs
+        let when                    = self.timeline.current_time.get();
+        let (_width, height)        = self.animation.size();
+        let contents                = self.clipboard.contents.get();
+        let (mut edits, pasted_ids) = contents.paste_edits(layer_id, when, height, &mut || self.animation.motion().assign_element_id())?;
+
+        if edits.len() == 0 {
+            return Ok(());
+        }
+
+        // Add a keyframe if we need one
+        let one_ms              = Duration::from_millis(1);
+        let earliest_time       = if when > one_ms { when - one_ms } else { Duration::from_millis(0) };
+        let on_keyframe         = layer.get_key_frames_during_time(earliest_time..(when + one_ms)).count() > 0;
+        let (previous, _next)   = layer.previous_and_next_key_frame(when);
+
+        if !on_keyframe && (self.frame.create_keyframe_on_draw.get() || previous.is_none()) {
+            edits.insert(0, AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(when)));
+        }
+
+        // Paste the elements as a single transaction
+        let mut edit_sink = executor::spawn(self.edit());
+        edit_sink.wait_send(edits).map_err(|_| SvgImportError::EditFailed)?;
+
+        // The pasted elements become the new selection
+        self.selection.clear_selection();
+        pasted_ids.into_iter().for_each(|element_id| self.selection.select(element_id));
+
+        self.timeline.invalidate_canvas();
+        self.timeline.update_keyframe_bindings();
+
+        Ok(())
+    }
+
+    ///
+    /// Sends a set of edits to the animation without recording them in the undo history
+    ///
+    fn perform_edits_without_undo(&self, edits: Vec<AnimationEdit>) {
+        let mut edit_sink = executor::spawn(self.edit_without_undo());
+        edit_sink.wait_send(edits).unwrap();
+
+        // Undoing or redoing can change any layer or keyframe
+        self.timeline.invalidate_canvas();
+        self.timeline.update_keyframe_bindings();
+    }
+
+    ///
+    /// Retrieves the parts of this model that are updated by edits to the animation
+    ///
+    fn model_update(&self) -> ModelUpdate<Anim> {
+        ModelUpdate {
+            animation:          Arc::clone(&self.animation),
+            size_binding:       self.size_binding.clone(),
+            timeline:           self.timeline.clone(),
+            frame_edit_counter: self.frame_edit_counter.clone()
+        }
+    }
+
+    ///
+    /// Retrieves a sink that sends edits to the animation and updates the model, without recording
+    /// them in the undo history
+    ///
+    fn edit_without_undo(&self) -> Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send> {
         // Edit the underlying animation
         let animation_edit  = self.animation.edit();
         let edit_publisher  = Arc::clone(&self.edit_publisher);
 
         // Borrow the bits of the viewmodel we can change
-        let frame_edit_counter  = self.frame_edit_counter.clone();
-        let size_binding        = self.size_binding.clone();
-        let timeline            = self.timeline.clone();
+        let model_update        = self.model_update();
+        let shared_edits        = self.shared_edits.clone();
+        let instance_id         = self.instance_id;
 
         // Pipe the edits so they modify the model as a side-effect
         let model_edit          = FloModelSink::new(animation_edit, move |edits: Arc<Vec<AnimationEdit>>| {
-            use self::AnimationEdit::*;
-            use self::LayerEdit::*;
-
             // Update the viewmodel based on the edits that are about to go through
-            let mut advance_edit_counter = false;
-
-            for edit in edits.iter() {
-                match edit {
-                    SetSize(width, height) => {
-                        size_binding.set((*width, *height));
-                        advance_edit_counter = true;
-                    },
-
-                    AddNewLayer(_)              |
-                    RemoveLayer(_)              |
-                    Element(_, _)               |
-                    Motion(_, _)                |
-                    Layer(_, Path(_, _))        |
-                    Layer(_, Paint(_, _))       => {
-                        advance_edit_counter = true;
-                    }
-
-                    Layer(_, AddKeyFrame(_))    |
-                    Layer(_, RemoveKeyFrame(_)) => {
-                        advance_edit_counter = true;
-                    },
-
-                    Layer(layer_id, SetName(new_name)) => {
-                        timeline.layers.get()
-                            .iter()
-                            .for_each(|layer| if &layer.id == layer_id { layer.name.set(new_name.clone())} );
-                        advance_edit_counter = true;
-                    },
-
-                    Layer(layer_id, SetOrdering(at_index)) => {
-                        unimplemented!("Cannot update model with layer ordering")
-                    }
+            model_update.update(&*edits);
+
+            // Publish the edits to any subscribers that there might be
+            let local_edits = Arc::clone(&edits);
+            edit_publisher.sync(move |publisher| publisher.wait_send(local_edits)).unwrap();
+
+            // Send to any other models that are editing the same animation
+            shared_edits.publish(instance_id, edits);
+        });
+
+        Box::new(model_edit)
+    }
+}
+
+impl<Anim: Animation+'static> ModelUpdate<Anim> {
+    ///
+    /// Updates the model to reflect a set of edits that have been made to the animation
+    ///
+    fn update(&self, edits: &Vec<AnimationEdit>) {
+        use self::AnimationEdit::*;
+        use self::LayerEdit::*;
+
+        let mut advance_edit_counter = false;
+
+        for edit in edits.iter() {
+            match edit {
+                SetSize(width, height) => {
+                    self.size_binding.set((*width, *height));
+                    advance_edit_counter = true;
+                },
+
+                SetDuration(duration) => {
+                    self.timeline.set_duration(*duration);
+                },
+
+                SetFrameLength(frame_length) => {
+                    self.timeline.set_frame_length(*frame_length);
+                    advance_edit_counter = true;
+                },
+
+                AddNewLayer(_)              |
+                AddNewGroupLayer(_)         |
+                Element(_, _)               |
+                Motion(_, _)                |
+                Layer(_, Path(_, _))        |
+                Layer(_, Paint(_, _))       |
+                Layer(_, Text(_, _))        |
+                Layer(_, Image(_, _))       => {
+                    advance_edit_counter = true;
+                }
+
+                RemoveLayer(layer_id) => {
+                    // The layers in a group move to the top level when the group is removed
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if layer.parent_group.get() == Some(*layer_id) { layer.parent_group.set(None) });
+                    advance_edit_counter = true;
                 }
-            }
 
-            // Advancing the frame edit counter causes any animation frames to be regenerated
-            if advance_edit_counter {
-                frame_edit_counter.set(frame_edit_counter.get()+1);
+                Layer(_, AddKeyFrame(_))    |
+                Layer(_, RemoveKeyFrame(_)) => {
+                    advance_edit_counter = true;
+                },
+
+                Layer(layer_id, SetName(new_name)) => {
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.name.set(new_name.clone())} );
+                    advance_edit_counter = true;
+                },
+
+                Layer(_, SetOrdering(_)) => {
+                    // The timeline reorders its layers from the timeline updates
+                    advance_edit_counter = true;
+                },
+
+                Layer(layer_id, SetVisible(visible)) => {
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.visible.set(*visible) });
+                    advance_edit_counter = true;
+                },
+
+                Layer(layer_id, SetLocked(locked)) => {
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.locked.set(*locked) });
+                },
+
+                Layer(layer_id, SetOpacity(opacity)) => {
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.opacity.set(*opacity) });
+                    advance_edit_counter = true;
+                },
+
+                Layer(layer_id, SetBlendMode(blend_mode)) => {
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.blend_mode.set(*blend_mode) });
+                    advance_edit_counter = true;
+                },
+
+                Layer(layer_id, SetParentGroup(_)) => {
+                    // Read the group back from the animation, as edits that would create a loop of groups are ignored
+                    let parent_group = self.animation.get_layer_with_id(*layer_id).and_then(|layer| layer.parent_group());
+
+                    self.timeline.layers.get()
+                        .iter()
+                        .for_each(|layer| if &layer.id == layer_id { layer.parent_group.set(parent_group) });
+                    advance_edit_counter = true;
+                }
             }
+        }
 
-            // Publish the edits to any subscribers that there might be
-            let edits = Arc::clone(&edits);
-            edit_publisher.sync(move |publisher| publisher.wait_send(edits)).unwrap();
+        // Advancing the frame edit counter causes any animation frames to be regenerated
+        if advance_edit_counter {
+            self.frame_edit_counter.set(self.frame_edit_counter.get()+1);
+        }
+    }
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> EditableAnimation for FloModel<Anim> {
+    ///
+    /// Retrieves a sink that can be used to send edits for this animation
+    ///
+    /// Edits are supplied as groups (stored in a vec) so that it's possible to ensure that
+    /// a set of related edits are performed atomically. Each group forms a single transaction
+    /// in the undo history.
+    ///
+    fn edit(&self) -> Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send> {
+        let animation       = Arc::clone(&self.animation);
+        let undo_history    = self.undo_history.clone();
+        let current_time    = BindRef::new(&self.timeline.current_time);
+
+        // Record each group of edits in the undo history before it's performed
+        let undoable_edit   = self.edit_without_undo().with(move |edits: Vec<AnimationEdit>| -> Result<Vec<AnimationEdit>, ()> {
+            // New elements need IDs before they're sent so that the undo history can refer to them
+            let edits = edits.into_iter()
+                .map(|edit| edit.assign_element_id(|| animation.motion().assign_element_id().id().unwrap()))
+                .collect::<Vec<_>>();
+
+            undo_history.record_edits(&*animation, current_time.get(), Arc::new(edits.clone()));
+
+            Ok(edits)
         });
 
-        Box::new(model_edit)
+        Box::new(undoable_edit)
     }
 }
 
@@ -371,6 +730,7 @@ mod test {
 
     use super::*;
     use self::flo_anim_sqlite::*;
+    use flo_canvas::BlendMode;
     use futures::executor;
 
     #[test]
@@ -391,4 +751,616 @@ mod test {
         assert!(model.size()        == (800.0, 600.0));
         assert!(model.size.get()    == (800.0, 600.0));
     }
+
+    #[test]
+    fn undo_and_redo_size_command() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        assert!(!model.undo_history().can_undo.get());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![AnimationEdit::SetSize(800.0, 600.0)]).unwrap();
+        }
+
+        assert!(model.undo_history().can_undo.get());
+        assert!(!model.undo_history().can_redo.get());
+
+        // Undoing should restore the original size
+        model.undo();
+
+        assert!(model.size()        == (1980.0, 1080.0));
+        assert!(model.size.get()    == (1980.0, 1080.0));
+        assert!(!model.undo_history().can_undo.get());
+        assert!(model.undo_history().can_redo.get());
+
+        // Redoing should put it back again
+        model.redo();
+
+        assert!(model.size()        == (800.0, 600.0));
+        assert!(model.size.get()    == (800.0, 600.0));
+        assert!(model.undo_history().can_undo.get());
+        assert!(!model.undo_history().can_redo.get());
+    }
+
+    #[test]
+    fn duration_and_frame_length_commands_update_timeline() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::SetDuration(Duration::from_secs(90)),
+                AnimationEdit::SetFrameLength(Duration::new(0, 41_666_667))
+            ]).unwrap();
+        }
+
+        assert!(model.duration()                        == Duration::from_secs(90));
+        assert!(model.frame_length()                    == Duration::new(0, 41_666_667));
+        assert!(model.timeline().duration.get()         == Duration::from_secs(90));
+        assert!(model.timeline().frame_duration.get()   == Duration::new(0, 41_666_667));
+
+        // Undoing restores the defaults
+        model.undo();
+
+        assert!(model.timeline().duration.get()         == Duration::from_secs(120));
+        assert!(model.timeline().frame_duration.get()   == Duration::new(0, 33_333_333));
+    }
+
+    #[test]
+    fn zero_frame_length_is_clamped() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::SetFrameLength(Duration::from_millis(0))
+            ]).unwrap();
+        }
+
+        assert!(model.frame_length()                    == Duration::from_nanos(1));
+        assert!(model.timeline().frame_duration.get()   == Duration::from_nanos(1));
+
+        model.timeline().set_frame_length(Duration::from_millis(0));
+        assert!(model.timeline().frame_duration.get()   == Duration::from_nanos(1));
+    }
+
+    #[test]
+    fn layer_property_edits_update_layer_model() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![AnimationEdit::AddNewLayer(2)]).unwrap();
+            edit_log.wait_send(vec![
+                AnimationEdit::Layer(2, LayerEdit::SetVisible(false)),
+                AnimationEdit::Layer(2, LayerEdit::SetLocked(true)),
+                AnimationEdit::Layer(2, LayerEdit::SetOpacity(0.5)),
+                AnimationEdit::Layer(2, LayerEdit::SetBlendMode(BlendMode::Screen))
+            ]).unwrap();
+        }
+
+        let layer = model.timeline().layers.get().into_iter().filter(|layer| layer.id == 2).nth(0).unwrap();
+        assert!(layer.visible.get() == false);
+        assert!(layer.locked.get() == true);
+        assert!(layer.opacity.get() == 0.5);
+        assert!(layer.blend_mode.get() == BlendMode::Screen);
+
+        // Undoing restores the previous properties
+        model.undo();
+
+        assert!(layer.visible.get() == true);
+        assert!(layer.locked.get() == false);
+        assert!(layer.opacity.get() == 1.0);
+        assert!(layer.blend_mode.get() == BlendMode::SourceOver);
+    }
+
+    #[test]
+    fn layers_are_displayed_after_their_group() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::AddNewLayer(3),
+                AnimationEdit::AddNewGroupLayer(4)
+            ]).unwrap();
+            edit_log.wait_send(vec![AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(4)))]).unwrap();
+        }
+
+        let displayed_layers = || model.timeline().displayed_layers.get().into_iter().map(|(depth, layer)| (depth, layer.id)).collect::<Vec<_>>();
+
+        let layer = model.timeline().layers.get().into_iter().filter(|layer| layer.id == 2).nth(0).unwrap();
+        assert!(layer.parent_group.get() == Some(4));
+        assert!(displayed_layers() == vec![(0, 3), (0, 4), (1, 2)]);
+
+        // Collapsing the group hides its layers
+        let group = model.timeline().layers.get().into_iter().filter(|layer| layer.id == 4).nth(0).unwrap();
+        assert!(group.is_group);
+
+        group.collapsed.set(true);
+        assert!(displayed_layers() == vec![(0, 3), (0, 4)]);
+
+        group.collapsed.set(false);
+        assert!(displayed_layers() == vec![(0, 3), (0, 4), (1, 2)]);
+    }
+
+    #[test]
+    fn group_layers_cannot_be_drawn_on() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::AddNewGroupLayer(3)
+            ]).unwrap();
+        }
+
+        model.timeline().selected_layer.set(Some(2));
+        assert!(model.timeline().selected_vector_layer.get() == Some(2));
+
+        model.timeline().selected_layer.set(Some(3));
+        assert!(model.timeline().selected_vector_layer.get() == None);
+    }
+
+    #[test]
+    fn undo_remove_group() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::AddNewGroupLayer(3),
+                AnimationEdit::Layer(3, LayerEdit::SetName("Group".to_string())),
+                AnimationEdit::Layer(3, LayerEdit::SetOpacity(0.5))
+            ]).unwrap();
+            edit_log.wait_send(vec![AnimationEdit::Layer(2, LayerEdit::SetParentGroup(Some(3)))]).unwrap();
+            edit_log.wait_send(vec![AnimationEdit::RemoveLayer(3)]).unwrap();
+        }
+
+        // Removing the group moves its layer to the top level
+        let layer = model.timeline().layers.get().into_iter().filter(|layer| layer.id == 2).nth(0).unwrap();
+        assert!(model.get_layer_with_id(3).is_none());
+        assert!(layer.parent_group.get() == None);
+
+        // Undoing the removal restores the group along with its properties and layers
+        model.undo();
+
+        let group = model.get_layer_with_id(3).unwrap();
+        assert!(group.as_group_layer().is_some());
+        assert!(group.name() == Some("Group".to_string()));
+        assert!(group.opacity() == 0.5);
+        assert!(model.get_layer_with_id(2).unwrap().parent_group() == Some(3));
+        assert!(layer.parent_group.get() == Some(3));
+    }
+
+    #[test]
+    fn edits_are_shared_between_models() {
+        let animation   = Arc::new(SqliteAnimation::new_in_memory());
+        let shared      = SharedEdits::new();
+        let model1      = FloModel::new_shared(Arc::clone(&animation), shared.clone());
+        let model2      = FloModel::new_shared(Arc::clone(&animation), shared.clone());
+
+        let mut model2_edits = executor::spawn(model2.subscribe_edits());
+
+        {
+            let mut edit_log = executor::spawn(model1.edit());
+            edit_log.wait_send(vec![AnimationEdit::SetSize(800.0, 600.0)]).unwrap();
+        }
+
+        // The edit made in the first model should be republished by the second
+        let edits = model2_edits.wait_stream().unwrap().unwrap();
+        assert!(*edits == vec![AnimationEdit::SetSize(800.0, 600.0)]);
+
+        // Both models should be updated
+        assert!(model1.size.get()   == (800.0, 600.0));
+        assert!(model2.size.get()   == (800.0, 600.0));
+        assert!(model2.size()       == (800.0, 600.0));
+
+        // Only the model that made the edit can undo it
+        assert!(model1.undo_history().can_undo.get());
+        assert!(!model2.undo_history().can_undo.get());
+    }
+
+    #[test]
+    fn remote_edits_clear_undo_history() {
+        let animation   = Arc::new(SqliteAnimation::new_in_memory());
+        let shared      = SharedEdits::new();
+        let model1      = FloModel::new_shared(Arc::clone(&animation), shared.clone());
+        let model2      = FloModel::new_shared(Arc::clone(&animation), shared.clone());
+
+        {
+            let mut edit_log = executor::spawn(model2.edit());
+            edit_log.wait_send(vec![AnimationEdit::SetSize(800.0, 600.0)]).unwrap();
+        }
+
+        assert!(model2.undo_history().can_undo.get());
+
+        let mut model2_edits = executor::spawn(model2.subscribe_edits());
+
+        {
+            let mut edit_log = executor::spawn(model1.edit());
+            edit_log.wait_send(vec![AnimationEdit::SetSize(640.0, 480.0)]).unwrap();
+        }
+
+        // Wait for the second model to receive the edit from the first
+        let edits = model2_edits.wait_stream().unwrap().unwrap();
+        assert!(*edits == vec![AnimationEdit::SetSize(640.0, 480.0)]);
+
+        // The second model can no longer undo its edit, as the animation has changed underneath it
+        assert!(!model2.undo_history().can_undo.get());
+        assert!(model1.undo_history().can_undo.get());
+    }
+
+    #[test]
+    fn layer_for_element_on_other_keyframe() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ]))))
+            ]).unwrap();
+        }
+
+        // The element is found even though it's not in the frame at the current time
+        model.timeline().current_time.set(Duration::from_millis(1000));
+        assert!(model.layer_for_element(ElementId::Assigned(100)) == Some(2));
+        assert!(model.layer_for_element(ElementId::Assigned(101)) == None);
+    }
+
+    #[test]
+    fn undo_brush_stroke() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new())))
+            ]).unwrap();
+            edit_log.wait_send(vec![
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ]))))
+            ]).unwrap();
+        }
+
+        let num_elements = || model.get_layer_with_id(2).unwrap()
+            .get_frame_at_time(Duration::from_millis(0))
+            .vector_elements()
+            .map(|elements| elements.count())
+            .unwrap_or(0);
+
+        assert!(num_elements() == 1);
+
+        // Undoing the brush stroke should remove it from the frame
+        model.undo();
+        assert!(num_elements() == 0);
+        assert!(model.get_layer_ids() == vec![2]);
+
+        // Redoing should restore it
+        model.redo();
+        assert!(num_elements() == 1);
+
+        // Undoing twice should remove the layer as well
+        model.undo();
+        model.undo();
+        assert!(model.get_layer_ids().len() == 0);
+    }
+
+    #[test]
+    fn undo_brush_stroke_deletes_brush_elements() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+            ]).unwrap();
+            edit_log.wait_send(vec![
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ]))))
+            ]).unwrap();
+        }
+
+        // The brush definition and properties are deleted along with the brush stroke
+        let undo_edits  = model.undo_history().take_undo().unwrap();
+        let deleted     = undo_edits.iter()
+            .filter(|edit| match edit { AnimationEdit::Element(_, ElementEdit::Delete) => true, _ => false })
+            .count();
+
+        assert!(deleted == 3);
+    }
+
+    #[test]
+    fn undo_delete_restores_element() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(101), Arc::new(vec![
+                    RawPoint::from((30.0, 10.0)),
+                    RawPoint::from((40.0, 5.0))
+                ]))))
+            ]).unwrap();
+        }
+
+        let element_ids = || model.get_layer_with_id(2).unwrap()
+            .get_frame_at_time(Duration::from_millis(0))
+            .vector_elements()
+            .map(|elements| elements.map(|element| element.id()).collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![]);
+        let points      = || model.get_layer_with_id(2).unwrap()
+            .get_frame_at_time(Duration::from_millis(0))
+            .element_with_id(ElementId::Assigned(100))
+            .map(|element| element.control_points().into_iter().map(|cp| cp.position()).collect::<Vec<_>>());
+
+        let original_points = points();
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::Delete)
+            ]).unwrap();
+        }
+
+        assert!(element_ids() == vec![ElementId::Assigned(101)]);
+
+        // Undoing the delete should put the element back where it was, with the same points
+        model.undo();
+        assert!(element_ids() == vec![ElementId::Assigned(100), ElementId::Assigned(101)]);
+        assert!(original_points.is_some());
+        assert!(points() == original_points);
+
+        // The transaction that drew the elements can still be undone
+        assert!(model.undo_history().can_undo.get());
+        model.undo();
+        assert!(element_ids().len() == 0);
+    }
+
+    #[test]
+    fn edits_that_cannot_be_undone_clear_history() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ]))))
+            ]).unwrap();
+            edit_log.wait_send(vec![AnimationEdit::SetSize(800.0, 600.0)]).unwrap();
+            assert!(model.undo_history().can_undo.get());
+
+            // There's no edit that puts an element back in a frame, so this can't be undone
+            edit_log.wait_send(vec![
+                AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::DetachFromFrame)
+            ]).unwrap();
+        }
+
+        // The earlier edits can no longer be undone either
+        assert!(!model.undo_history().can_undo.get());
+        assert!(!model.undo_history().can_redo.get());
+    }
+
+    #[test]
+    fn undo_remove_layer_restores_ordering() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::AddNewLayer(3),
+                AnimationEdit::AddNewLayer(4)
+            ]).unwrap();
+            edit_log.wait_send(vec![AnimationEdit::RemoveLayer(2)]).unwrap();
+        }
+
+        assert!(model.get_layer_ids() == vec![3, 4]);
+
+        // The layer goes back underneath the others rather than on top of them
+        model.undo();
+        assert!(model.get_layer_ids() == vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn undo_layer_ordering() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::AddNewLayer(3),
+                AnimationEdit::AddNewLayer(4)
+            ]).unwrap();
+            edit_log.wait_send(vec![AnimationEdit::Layer(4, LayerEdit::SetOrdering(0))]).unwrap();
+        }
+
+        assert!(model.get_layer_ids() == vec![4, 2, 3]);
+
+        model.undo();
+        assert!(model.get_layer_ids() == vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn undo_control_points_on_other_keyframe() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(1000))),
+                AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(1000), PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Simple, BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(1000), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Path(Duration::from_millis(1000), PathEdit::CreatePath(ElementId::Assigned(100), Arc::new(vec![
+                    PathComponent::Move(PathPoint::new(10.0, 10.0)),
+                    PathComponent::Line(PathPoint::new(20.0, 10.0)),
+                    PathComponent::Line(PathPoint::new(20.0, 20.0)),
+                    PathComponent::Close
+                ]))))
+            ]).unwrap();
+
+            // The current time is still 0, so the path isn't in the current frame
+            edit_log.wait_send(vec![
+                AnimationEdit::Element(vec![ElementId::Assigned(100)], ElementEdit::SetControlPoints(vec![(15.0, 15.0), (25.0, 15.0), (25.0, 25.0)]))
+            ]).unwrap();
+        }
+
+        let first_point = || model.get_layer_with_id(2).unwrap()
+            .get_frame_at_time(Duration::from_millis(1000))
+            .element_with_id(ElementId::Assigned(100))
+            .and_then(|element| element.control_points().first().map(|cp| cp.position()));
+
+        assert!(first_point() == Some((15.0, 15.0)));
+
+        model.undo();
+        assert!(first_point() == Some((10.0, 10.0)));
+    }
+
+    #[test]
+    fn copy_and_paste_between_animations() {
+        let clipboard   = ClipboardModel::new();
+        let model1      = FloModel::new_with_clipboard(Arc::new(SqliteAnimation::new_in_memory()), SharedEdits::new(), clipboard.clone());
+        let model2      = FloModel::new_with_clipboard(Arc::new(SqliteAnimation::new_in_memory()), SharedEdits::new(), clipboard.clone());
+
+        {
+            let mut edit_log = executor::spawn(model1.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ]))))
+            ]).unwrap();
+
+            let mut edit_log = executor::spawn(model2.edit());
+            edit_log.wait_send(vec![AnimationEdit::AddNewLayer(5)]).unwrap();
+        }
+
+        let brush_strokes = |model: &FloModel<SqliteAnimation>, layer_id| model.get_layer_with_id(layer_id).unwrap()
+            .get_frame_at_time(Duration::from_millis(0))
+            .vector_elements()
+            .map(|elements| elements.filter_map(|element| match element { Vector::BrushStroke(brush_stroke) => Some(brush_stroke), _ => None }).collect::<Vec<_>>())
+            .unwrap_or_else(|| vec![]);
+
+        // Copy the brush stroke from the first animation
+        model1.timeline().selected_layer.set(Some(2));
+        model1.selection().select(ElementId::Assigned(100));
+        model1.copy_selection();
+
+        assert!(!clipboard.is_empty());
+
+        // Paste into the second animation (which needs a new keyframe)
+        model2.timeline().selected_layer.set(Some(5));
+        model2.paste().unwrap();
+
+        let original    = brush_strokes(&model1, 2);
+        let pasted      = brush_strokes(&model2, 5);
+
+        assert!(pasted.len() == 1);
+        assert!(pasted[0].points() == original[0].points());
+        assert!(model2.get_layer_with_id(5).unwrap().get_key_frames().count() == 1);
+        assert!(model2.selection().selected_elements.get().len() == 1);
+        assert!(model2.selection().selected_elements.get().contains(&pasted[0].id()));
+
+        // The pasted brush stroke has the same brush as the original
+        let frame = model2.get_layer_with_id(5).unwrap().get_frame_at_time(Duration::from_millis(0));
+        assert!(frame.attached_elements(pasted[0].id()).len() == 2);
+
+        // Pasting into the same frame creates a copy with a different ID
+        model1.paste().unwrap();
+
+        let copied = brush_strokes(&model1, 2);
+        assert!(copied.len() == 2);
+        assert!(copied[0].id() != copied[1].id());
+
+        // Cutting removes the pasted element, and it can be pasted back again
+        model2.cut_selection();
+        assert!(brush_strokes(&model2, 5).len() == 0);
+        assert!(model2.selection().selected_elements.get().len() == 0);
+
+        model2.paste().unwrap();
+        assert!(brush_strokes(&model2, 5).len() == 1);
+        assert!(brush_strokes(&model2, 5)[0].points() == original[0].points());
+    }
+
+    #[test]
+    fn models_have_separate_clipboards() {
+        let model1 = FloModel::new(SqliteAnimation::new_in_memory());
+        let model2 = FloModel::new(SqliteAnimation::new_in_memory());
+
+        model1.clipboard().set_elements(vec![ClipboardElement::Text(TextStyle::new(), (10.0, 20.0), "Hello".to_string())]);
+
+        assert!(!model1.clipboard().is_empty());
+        assert!(model2.clipboard().is_empty());
+    }
+
+    #[test]
+    fn cut_does_not_remove_elements_from_locked_layer() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(2),
+                AnimationEdit::Layer(2, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+                AnimationEdit::Layer(2, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(100), Arc::new(vec![
+                    RawPoint::from((10.0, 10.0)),
+                    RawPoint::from((20.0, 5.0))
+                ])))),
+                AnimationEdit::Layer(2, LayerEdit::SetLocked(true))
+            ]).unwrap();
+        }
+
+        model.timeline().selected_layer.set(Some(2));
+        model.selection().select(ElementId::Assigned(100));
+        model.cut_selection();
+
+        let frame = model.get_layer_with_id(2).unwrap().get_frame_at_time(Duration::from_millis(0));
+        assert!(frame.element_with_id(ElementId::Assigned(100)).is_some());
+        assert!(model.selection().selected_elements.get().len() == 1);
+    }
 }
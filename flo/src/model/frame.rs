@@ -1,5 +1,6 @@
 use flo_stream::*;
 use flo_binding::*;
+use flo_canvas::*;
 use flo_animation::*;
 use flo_curves::bezier::path::path_contains_point;
 
@@ -41,6 +42,12 @@ pub struct FrameLayerModel {
 
     /// The current frmae for this layer
     pub frame: BindRef<Option<Arc<dyn Frame>>>,
+
+    /// The ID of the layer that this layer is composited with (the outermost group that contains it, or the layer itself)
+    pub composited_layer_id: BindRef<u64>,
+
+    /// The visibility of this layer, and the opacity and blend mode to use for the canvas layer it's drawn into
+    pub appearance: BindRef<(bool, f64, BlendMode)>
 }
 
 ///
@@ -95,8 +102,8 @@ impl FrameModel {
             // We bind to the update so this invalidates whenever the update list changes
             animation_update.get();
 
-            // Refresh the frames from the animation
-            let layer_ids = animation.get_layer_ids();
+            // Refresh the frames from the animation (the layers in each group follow the group)
+            let layer_ids = layer_ids_in_group_order(&*animation);
 
             // Remove layers that aren't in use any more
             let deleted_layers: Vec<_> = layer_ids
@@ -117,7 +124,11 @@ impl FrameModel {
                         let layer_id            = *layer_id;
                         let when                = BindRef::clone(&when);
                         let frame_animation     = Arc::clone(&animation);
+                        let layer_animation     = Arc::clone(&animation);
+                        let group_animation     = Arc::clone(&animation);
                         let animation_update    = animation_update.clone();
+                        let layer_update        = animation_update.clone();
+                        let group_update        = animation_update.clone();
 
                         let frame_binding       = ComputedBinding::new_in_context(move || {
                             // Binds to the animation update...
@@ -131,12 +142,34 @@ impl FrameModel {
                                 .map(|layer| layer.get_frame_at_time(when))
                         });
 
+                        let composited_binding  = ComputedBinding::new_in_context(move || {
+                            group_update.get();
+
+                            // Layers in a group are drawn together so the group can be composited as a unit
+                            group_animation.get_layer_with_id(layer_id)
+                                .map(|layer| composited_layer_id(&*group_animation, &*layer))
+                                .unwrap_or(layer_id)
+                        });
+
+                        let appearance_binding  = ComputedBinding::new_in_context(move || {
+                            layer_update.get();
+
+                            // Layers share the visibility and opacity of the groups that contain them
+                            layer_animation.get_layer_with_id(layer_id)
+                                .map(|layer| composited_layer_appearance(&*layer_animation, &*layer))
+                                .unwrap_or((true, 1.0, BlendMode::SourceOver))
+                        });
+
                         // Add a frame layer model for this frame
                         let frame           = BindRef::new(&frame_binding);
+                        let composited_id   = BindRef::new(&composited_binding);
+                        let appearance      = BindRef::new(&appearance_binding);
 
                         vacant.insert(FrameLayerModel {
-                            layer_id:       layer_id,
-                            frame:          frame,
+                            layer_id:               layer_id,
+                            frame:                  frame,
+                            composited_layer_id:    composited_id,
+                            appearance:             appearance
                         });
                     }
                 }
@@ -1,4 +1,5 @@
 use flo_binding::*;
+use flo_canvas::*;
 use flo_animation::*;
 
 ///
@@ -10,7 +11,28 @@ pub struct LayerModel {
     pub id: u64,
 
     /// The name of this layer
-    pub name: Binding<String>
+    pub name: Binding<String>,
+
+    /// True if this layer is rendered
+    pub visible: Binding<bool>,
+
+    /// True if this layer is locked against editing
+    pub locked: Binding<bool>,
+
+    /// The opacity of this layer (0.0-1.0)
+    pub opacity: Binding<f64>,
+
+    /// The blend mode used when rendering this layer
+    pub blend_mode: Binding<BlendMode>,
+
+    /// True if this is a group layer (not a binding as the type of a layer never changes)
+    pub is_group: bool,
+
+    /// The ID of the group that contains this layer
+    pub parent_group: Binding<Option<u64>>,
+
+    /// True if this is a group whose layers are hidden in the timeline
+    pub collapsed: Binding<bool>
 }
 
 impl PartialEq for LayerModel {
@@ -22,8 +44,15 @@ impl PartialEq for LayerModel {
 impl LayerModel {
     pub fn new<'a>(layer: &'a dyn Layer) -> LayerModel {
         LayerModel {
-            id:     layer.id(),
-            name:   bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id())))
+            id:             layer.id(),
+            name:           bind(layer.name().unwrap_or_else(|| format!("Layer {}", layer.id()))),
+            visible:        bind(layer.is_visible()),
+            locked:         bind(layer.is_locked()),
+            opacity:        bind(layer.opacity()),
+            blend_mode:     bind(layer.blend_mode()),
+            is_group:       layer.as_group_layer().is_some(),
+            parent_group:   bind(layer.parent_group()),
+            collapsed:      bind(false)
         }
     }
 }
@@ -7,8 +7,11 @@ mod keyframe;
 mod frame;
 mod selection;
 mod shared_model;
+mod shared_edits;
 mod onion_skin;
 mod brush_settings;
+mod undo;
+mod clipboard;
 
 pub use self::flo_model::*;
 pub use self::timeline::*;
@@ -18,5 +21,8 @@ pub use self::keyframe::*;
 pub use self::frame::*;
 pub use self::selection::*;
 pub use self::shared_model::*;
+pub use self::shared_edits::*;
 pub use self::onion_skin::*;
 pub use self::brush_settings::*;
+pub use self::undo::*;
+pub use self::clipboard::*;
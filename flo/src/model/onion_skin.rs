@@ -65,7 +65,9 @@ impl<Anim: 'static+Animation> OnionSkinModel<Anim> {
     ///
     /// Creates a new onion skin model
     ///
-    pub fn new(animation: Arc<Anim>, timeline: &TimelineModel<Anim>) -> OnionSkinModel<Anim> {
+    /// The onion skins are refreshed whenever the animation update counter changes (ie, when the animation is edited)
+    ///
+    pub fn new(animation: Arc<Anim>, timeline: &TimelineModel<Anim>, animation_update: BindRef<u64>) -> OnionSkinModel<Anim> {
         // Create the basic bindings
         let future_color        = Binding::new(ONIONSKIN_FUTURE);
         let past_color          = Binding::new(ONIONSKIN_PAST);
@@ -75,7 +77,7 @@ impl<Anim: 'static+Animation> OnionSkinModel<Anim> {
 
         // Create the derived bindings
         let onion_skin_times    = Self::onion_skin_times(timeline, BindRef::from(&show_onion_skins), BindRef::from(&frames_before), BindRef::from(&frames_after));
-        let onion_skins         = Self::onion_skins(Arc::clone(&animation), BindRef::from(&timeline.selected_layer), BindRef::clone(&onion_skin_times));
+        let onion_skins         = Self::onion_skins(Arc::clone(&animation), BindRef::from(&timeline.selected_layer), BindRef::clone(&onion_skin_times), animation_update);
 
         OnionSkinModel {
             future_color:       future_color,
@@ -143,13 +145,13 @@ impl<Anim: 'static+Animation> OnionSkinModel<Anim> {
     ///
     /// Returns a binding for the set of drawing actions to draw the current set of onion skins
     ///
-    fn onion_skins(animation: Arc<Anim>, selected_layer: BindRef<Option<u64>>, onion_skin_times: BindRef<Vec<OnionSkinTime>>) -> BindRef<Vec<(OnionSkinTime, Arc<Vec<Draw>>)>> {
-        // Take a stream of updates from the onion skin times
-        let onion_skin_times        = computed(move || (selected_layer.get(), onion_skin_times.get()));
+    fn onion_skins(animation: Arc<Anim>, selected_layer: BindRef<Option<u64>>, onion_skin_times: BindRef<Vec<OnionSkinTime>>, animation_update: BindRef<u64>) -> BindRef<Vec<(OnionSkinTime, Arc<Vec<Draw>>)>> {
+        // Take a stream of updates from the onion skin times (which also updates when the animation is edited)
+        let onion_skin_times        = computed(move || (selected_layer.get(), onion_skin_times.get(), animation_update.get()));
         let onion_skin_time_stream  = follow(onion_skin_times);
 
         // Then, every time the set of times change, request the cached drawings from the animation
-        let mut fetching_onion_skins = onion_skin_time_stream.map(move |(selected_layer, onion_skin_times)| {
+        let mut fetching_onion_skins = onion_skin_time_stream.map(move |(selected_layer, onion_skin_times, _animation_update)| {
             let animation = Arc::clone(&animation);
             selected_layer.map(move |selected_layer| {
                 // Fetch the layer
@@ -0,0 +1,58 @@
+use flo_stream::*;
+use flo_animation::*;
+
+use futures::*;
+use futures::executor;
+use futures::executor::Spawn;
+use desync::*;
+
+use std::sync::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+///
+/// Broadcasts the edits made by each of the models editing a single animation to all of the others
+///
+#[derive(Clone)]
+pub struct SharedEdits {
+    /// The ID that will be assigned to the next model that uses these shared edits
+    next_instance_id: Arc<AtomicUsize>,
+
+    /// Publisher where the edits are sent, along with the ID of the model that made them (this never blocks: subscribers
+    /// that fall too far behind lose their oldest edits rather than holding up the model that's making them)
+    publisher: Arc<Desync<Spawn<ExpiringPublisher<(usize, Arc<Vec<AnimationEdit>>)>>>>
+}
+
+impl SharedEdits {
+    ///
+    /// Creates a new set of shared edits
+    ///
+    pub fn new() -> SharedEdits {
+        SharedEdits {
+            next_instance_id:   Arc::new(AtomicUsize::new(0)),
+            publisher:          Arc::new(Desync::new(executor::spawn(ExpiringPublisher::new(100))))
+        }
+    }
+
+    ///
+    /// Assigns an ID to a model that will be sending and receiving edits
+    ///
+    pub fn new_instance_id(&self) -> usize {
+        self.next_instance_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    ///
+    /// Sends edits made by a particular model to all of the other models
+    ///
+    pub fn publish(&self, instance_id: usize, edits: Arc<Vec<AnimationEdit>>) {
+        self.publisher.sync(move |publisher| publisher.wait_send((instance_id, edits))).unwrap();
+    }
+
+    ///
+    /// Returns a stream of the edits made by every model except the one with the specified ID
+    ///
+    pub fn subscribe_remote(&self, instance_id: usize) -> impl Stream<Item=Arc<Vec<AnimationEdit>>, Error=()>+Send {
+        self.publisher.sync(|publisher| publisher.subscribe())
+            .filter(move |(source_id, _edits)| *source_id != instance_id)
+            .map(|(_source_id, edits)| edits)
+    }
+}
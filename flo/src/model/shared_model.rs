@@ -1,23 +1,24 @@
 use super::flo_model::*;
+use super::shared_edits::*;
 
 use flo_animation::*;
 use flo_ui_files::*;
 
-use std::marker::PhantomData;
-use std::path::{Path, PathBuf};
+use std::sync::*;
+use std::path::Path;
 
 ///
 /// Represents the file model for FlowBetween animations
 ///
 pub struct FloSharedModel<Anim> {
-    /// The path where the animation can be opened from
-    path: PathBuf,
+    /// The animation that is shared between all of the instances of this file
+    animation: Arc<Anim>,
 
-    anim: PhantomData<Anim>
+    /// The edits that are broadcast between all of the instances of this file
+    shared_edits: SharedEdits
 }
 
 impl<Anim: EditableAnimation+FileAnimation+'static> FileModel for FloSharedModel<Anim> {
-    // TODO: we should probably actually share the file between instances :-)
     type InstanceModel = FloModel<Anim>;
 
     ///
@@ -26,17 +27,17 @@ impl<Anim: EditableAnimation+FileAnimation+'static> FileModel for FloSharedModel
     ///
     fn open(path: &Path) -> FloSharedModel<Anim> {
         FloSharedModel {
-            path: PathBuf::from(path),
-            anim: PhantomData
+            animation:      Arc::new(Anim::open(path)),
+            shared_edits:   SharedEdits::new()
         }
     }
 
     ///
     /// Creates a new instance model from the shared model. This is used for a single session.
     ///
+    /// Every instance edits the same animation, and edits made in one instance are sent to all of the others.
+    ///
     fn new_instance(&self) -> FloModel<Anim> {
-        let animation = Anim::open(self.path.as_path());
-
-        FloModel::new(animation)
+        FloModel::new_shared(Arc::clone(&self.animation), self.shared_edits.clone())
     }
 }
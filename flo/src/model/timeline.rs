@@ -32,9 +32,17 @@ pub struct TimelineModel<Anim: Animation> {
     /// The layers in the timeline
     pub layers: BindRef<Vec<LayerModel>>,
 
+    /// The layers as they're displayed in the timeline, along with how deeply they're nested in groups
+    ///
+    /// The layers in each group follow the group that contains them, and the layers in collapsed groups are left out.
+    pub displayed_layers: BindRef<Vec<(usize, LayerModel)>>,
+
     /// The ID of the layer currently selected for editing
     pub selected_layer: Binding<Option<u64>>,
 
+    /// The ID of the selected layer if it's one that can be drawn on (None if there's no selection or a group layer is selected)
+    pub selected_vector_layer: BindRef<Option<u64>>,
+
     /// The number of times the canvas has been invalidated
     pub canvas_invalidation_count: Binding<u64>,
 
@@ -50,7 +58,9 @@ impl<Anim: Animation> Clone for TimelineModel<Anim> {
             frame_duration:             Binding::clone(&self.frame_duration),
             duration:                   Binding::clone(&self.duration),
             layers:                     BindRef::clone(&self.layers),
+            displayed_layers:           BindRef::clone(&self.displayed_layers),
             selected_layer:             Binding::clone(&self.selected_layer),
+            selected_vector_layer:      BindRef::clone(&self.selected_vector_layer),
             canvas_invalidation_count:  Binding::clone(&self.canvas_invalidation_count),
             keyframes:                  Arc::clone(&self.keyframes)
         }
@@ -68,8 +78,23 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
         // Create the layers binding
         let layers = Self::layers_binding(&animation, edits);
 
+        // The displayed layers are ordered by group
+        let group_layers        = BindRef::clone(&layers);
+        let displayed_layers    = BindRef::from(computed(move || Self::layers_in_group_order(&group_layers.get())));
+
         // Initial selected layer is the first in the list
-        let selected_layer = animation.get_layer_ids().into_iter().nth(0);
+        let selected_layer = bind(animation.get_layer_ids().into_iter().nth(0));
+
+        // Group layers have no frames of their own, so they can't be drawn on
+        let vector_layers           = BindRef::clone(&layers);
+        let vector_selected_layer   = Binding::clone(&selected_layer);
+        let selected_vector_layer   = BindRef::from(computed(move || {
+            let selected_layer = vector_selected_layer.get();
+            vector_layers.get().into_iter()
+                .filter(|layer| Some(layer.id) == selected_layer && !layer.is_group)
+                .map(|layer| layer.id)
+                .nth(0)
+        }));
 
         // Read the animation properties
         let duration        = animation.duration();
@@ -82,7 +107,9 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
             duration:                   bind(duration),
             frame_duration:             bind(frame_duration),
             layers:                     layers,
-            selected_layer:             bind(selected_layer),
+            displayed_layers:           displayed_layers,
+            selected_layer:             selected_layer,
+            selected_vector_layer:      selected_vector_layer,
             canvas_invalidation_count:  bind(0),
             keyframes:                  Arc::new(Mutex::new(HashMap::new()))
         }
@@ -106,6 +133,45 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
         layers
     }
 
+    ///
+    /// Orders a list of layers so that the layers in each group follow the group, leaving out the layers in collapsed groups
+    ///
+    /// The result contains the depth of each layer in the groups along with its model.
+    ///
+    fn layers_in_group_order(layers: &Vec<LayerModel>) -> Vec<(usize, LayerModel)> {
+        let known_layers    = layers.iter().map(|layer| layer.id).collect::<HashSet<_>>();
+
+        // Find the layers in each group (layers whose group has been removed are at the top level)
+        let mut top_level   = vec![];
+        let mut children    = HashMap::new();
+
+        for layer in layers.iter() {
+            match layer.parent_group.get() {
+                Some(parent_id) if known_layers.contains(&parent_id)    => children.entry(parent_id).or_insert_with(|| vec![]).push(layer.clone()),
+                _                                                       => top_level.push(layer.clone())
+            }
+        }
+
+        // Visit the layers depth-first, skipping over the contents of collapsed groups
+        let mut result      = vec![];
+        let mut visited     = HashSet::new();
+        let mut to_visit    = top_level.into_iter().rev().map(|layer| (0, layer)).collect::<Vec<_>>();
+
+        while let Some((depth, layer)) = to_visit.pop() {
+            if !visited.insert(layer.id) { continue; }
+
+            if !layer.collapsed.get() {
+                if let Some(child_layers) = children.get(&layer.id) {
+                    to_visit.extend(child_layers.iter().rev().map(|child| (depth+1, child.clone())));
+                }
+            }
+
+            result.push((depth, layer));
+        }
+
+        result
+    }
+
     ///
     /// Returns a binding for the layers in an animation
     ///
@@ -143,6 +209,12 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
                     layers.retain(|model| model.id != layer_id)
                 },
 
+                SetLayerOrdering(_, _) => {
+                    // Sort the layers into the order that the animation now reports
+                    let layer_ids = animation.get_layer_ids();
+                    layers.sort_by_key(|model| layer_ids.iter().position(|layer_id| *layer_id == model.id));
+                },
+
                 _ => { }
             }
 
@@ -172,6 +244,28 @@ impl<Anim: Animation+'static> TimelineModel<Anim> {
         }
     }
 
+    ///
+    /// Updates the duration of the timeline (keeping the current time within the animation)
+    ///
+    pub fn set_duration(&self, duration: Duration) {
+        self.duration.set(duration);
+
+        if self.current_time.get() > duration {
+            self.current_time.set(duration);
+        }
+    }
+
+    ///
+    /// Updates the length of a frame in the timeline
+    ///
+    pub fn set_frame_length(&self, frame_length: Duration) {
+        // Frames always last for at least a nanosecond, so the frame for a time can always be calculated
+        self.frame_duration.set(frame_length.max(Duration::from_nanos(1)));
+
+        // Keyframes stay at the same time but can move to a different frame
+        self.update_keyframe_bindings();
+    }
+
     ///
     /// Causes the canvas to be invalidated
     ///
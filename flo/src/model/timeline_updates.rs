@@ -12,6 +12,7 @@ use std::time::Duration;
 pub enum TimelineModelUpdate {
     AddNewLayer(u64),
     RemoveLayer(u64),
+    SetLayerOrdering(u64, u32),
     AddKeyFrame(u64, Duration),
     RemoveKeyFrame(u64, Duration)
 }
@@ -24,10 +25,11 @@ impl TimelineModelUpdate {
         use self::TimelineModelUpdate::*;
 
         match self {
-            AddNewLayer(_)  |
-            RemoveLayer(_)  =>  true,
+            AddNewLayer(_)          |
+            RemoveLayer(_)          |
+            SetLayerOrdering(_, _)  =>  true,
 
-            _               => false
+            _                       => false
         }
     }
 }
@@ -45,7 +47,9 @@ pub fn get_timeline_updates<EditStream: Stream<Item=Arc<Vec<AnimationEdit>>, Err
                 .filter_map(|animation_edit| {
                     match animation_edit {
                         AddNewLayer(layer_id)                   => Some(TimelineModelUpdate::AddNewLayer(*layer_id)),
+                        AddNewGroupLayer(layer_id)              => Some(TimelineModelUpdate::AddNewLayer(*layer_id)),
                         RemoveLayer(layer_id)                   => Some(TimelineModelUpdate::RemoveLayer(*layer_id)),
+                        Layer(layer_id, SetOrdering(at_index))  => Some(TimelineModelUpdate::SetLayerOrdering(*layer_id, *at_index)),
                         Layer(layer_id, AddKeyFrame(when))      => Some(TimelineModelUpdate::AddKeyFrame(*layer_id, *when)),
                         Layer(layer_id, RemoveKeyFrame(when))   => Some(TimelineModelUpdate::RemoveKeyFrame(*layer_id, *when)),
 
@@ -0,0 +1,765 @@
+use flo_binding::*;
+use flo_animation::*;
+
+use std::sync::*;
+use std::time::Duration;
+use std::collections::HashSet;
+
+/// The maximum number of transactions that are kept in the undo history
+const MAX_UNDO_TRANSACTIONS: usize = 200;
+
+///
+/// A transaction in the undo history
+///
+/// A transaction is a group of edits that were sent to the animation together, along with the edits that
+/// will reverse their effects.
+///
+#[derive(Clone)]
+struct UndoTransaction {
+    /// The edits that were performed in this transaction (which will be re-sent by a redo)
+    edits: Arc<Vec<AnimationEdit>>,
+
+    /// The edits that will reverse this transaction
+    undo_edits: Arc<Vec<AnimationEdit>>
+}
+
+///
+/// The undo and redo stacks
+///
+struct UndoHistory {
+    /// Transactions that can be undone (most recent last)
+    undo: Vec<UndoTransaction>,
+
+    /// Transactions that have been undone and can be redone (most recently undone last)
+    redo: Vec<UndoTransaction>
+}
+
+///
+/// Model representing the undo history of an animation
+///
+#[derive(Clone)]
+pub struct UndoModel {
+    /// The undo and redo stacks
+    history: Arc<Mutex<UndoHistory>>,
+
+    /// Binding that is true when there's an edit to undo
+    can_undo_binding: Binding<bool>,
+
+    /// Binding that is true when there's an edit to redo
+    can_redo_binding: Binding<bool>,
+
+    /// True if there is a transaction that can be undone
+    pub can_undo: BindRef<bool>,
+
+    /// True if there is a transaction that can be redone
+    pub can_redo: BindRef<bool>
+}
+
+impl UndoModel {
+    ///
+    /// Creates a new undo model with an empty history
+    ///
+    pub fn new() -> UndoModel {
+        let history             = UndoHistory { undo: vec![], redo: vec![] };
+        let can_undo_binding    = bind(false);
+        let can_redo_binding    = bind(false);
+
+        UndoModel {
+            history:            Arc::new(Mutex::new(history)),
+            can_undo:           BindRef::from(can_undo_binding.clone()),
+            can_redo:           BindRef::from(can_redo_binding.clone()),
+            can_undo_binding:   can_undo_binding,
+            can_redo_binding:   can_redo_binding
+        }
+    }
+
+    ///
+    /// Records a set of edits that are about to be sent to an animation as a single transaction
+    ///
+    /// This must be called before the edits are performed, as the animation is inspected to determine how to
+    /// reverse them. Elements that are being edited are looked for in the frames at the current time first, and then in
+    /// every keyframe. Edits must already have their element IDs assigned. If the edits cannot be reversed, the history is
+    /// cleared, as the earlier transactions would no longer undo cleanly once these edits have been made.
+    ///
+    pub fn record_edits<Anim: Animation>(&self, animation: &Anim, when: Duration, edits: Arc<Vec<AnimationEdit>>) {
+        // Work out how to reverse these edits (the reversed edits need to be applied in reverse order)
+        let undo_edits = edits.iter()
+            .map(|edit| undo_edit(animation, when, edit))
+            .collect::<Option<Vec<_>>>()
+            .map(|undo_edits| undo_edits.into_iter().rev().flatten().collect::<Vec<_>>());
+
+        {
+            let mut history = self.history.lock().unwrap();
+
+            // Any new edit makes it impossible to redo the edits that were previously undone
+            history.redo = vec![];
+
+            if let Some(undo_edits) = undo_edits {
+                // Add a new transaction to the undo history
+                history.undo.push(UndoTransaction {
+                    edits:      edits,
+                    undo_edits: Arc::new(undo_edits)
+                });
+
+                // Limit the length of the history
+                if history.undo.len() > MAX_UNDO_TRANSACTIONS {
+                    let excess = history.undo.len() - MAX_UNDO_TRANSACTIONS;
+                    history.undo.drain(0..excess);
+                }
+            } else {
+                // The earlier transactions were generated against the animation before these edits, so they can't be undone either
+                history.undo = vec![];
+            }
+        }
+
+        self.update_bindings();
+    }
+
+    ///
+    /// Removes the most recent transaction from the undo stack, moving it to the redo stack and returning the
+    /// edits that need to be performed in order to undo it
+    ///
+    pub fn take_undo(&self) -> Option<Arc<Vec<AnimationEdit>>> {
+        let undo_edits = {
+            let mut history = self.history.lock().unwrap();
+
+            history.undo.pop()
+                .map(|transaction| {
+                    let undo_edits = Arc::clone(&transaction.undo_edits);
+                    history.redo.push(transaction);
+                    undo_edits
+                })
+        };
+
+        self.update_bindings();
+        undo_edits
+    }
+
+    ///
+    /// Removes the most recently undone transaction from the redo stack, moving it back to the undo stack and
+    /// returning the edits that need to be performed in order to redo it
+    ///
+    pub fn take_redo(&self) -> Option<Arc<Vec<AnimationEdit>>> {
+        let redo_edits = {
+            let mut history = self.history.lock().unwrap();
+
+            history.redo.pop()
+                .map(|transaction| {
+                    let redo_edits = Arc::clone(&transaction.edits);
+                    history.undo.push(transaction);
+                    redo_edits
+                })
+        };
+
+        self.update_bindings();
+        redo_edits
+    }
+
+    ///
+    /// Removes everything from the undo history
+    ///
+    pub fn clear(&self) {
+        {
+            let mut history = self.history.lock().unwrap();
+
+            history.undo = vec![];
+            history.redo = vec![];
+        }
+
+        self.update_bindings();
+    }
+
+    ///
+    /// Updates the can_undo and can_redo bindings to match the history
+    ///
+    fn update_bindings(&self) {
+        let (can_undo, can_redo) = {
+            let history = self.history.lock().unwrap();
+            (!history.undo.is_empty(), !history.redo.is_empty())
+        };
+
+        self.can_undo_binding.set(can_undo);
+        self.can_redo_binding.set(can_redo);
+    }
+}
+
+///
+/// Finds the layer and frame that contain the element with the specified ID, along with the time of that frame
+///
+/// The frames at the specified time are searched first, followed by the keyframes of every layer, so elements
+/// that are on other keyframes can be found too.
+///
+pub fn locate_element<Anim: Animation>(animation: &Anim, when: Duration, element_id: ElementId) -> Option<(u64, Duration, Arc<dyn Frame>)> {
+    let layers = animation.get_layer_ids()
+        .into_iter()
+        .filter_map(|layer_id| animation.get_layer_with_id(layer_id))
+        .collect::<Vec<_>>();
+
+    // Elements are usually edited in the current frame
+    let current_frame = layers.iter()
+        .map(|layer| (layer.id(), when, layer.get_frame_at_time(when)))
+        .filter(|(_layer_id, _when, frame)| frame.element_with_id(element_id).is_some())
+        .nth(0);
+
+    if current_frame.is_some() {
+        return current_frame;
+    }
+
+    // ... but can be on any keyframe
+    layers.iter()
+        .flat_map(|layer| layer.get_key_frames().map(move |keyframe| (layer.id(), keyframe, layer.get_frame_at_time(keyframe))))
+        .filter(|(_layer_id, _when, frame)| frame.element_with_id(element_id).is_some())
+        .nth(0)
+}
+
+///
+/// Finds the frame that contains the element with the specified ID
+///
+fn frame_for_element<Anim: Animation>(animation: &Anim, when: Duration, element_id: ElementId) -> Option<Arc<dyn Frame>> {
+    locate_element(animation, when, element_id).map(|(_layer_id, _when, frame)| frame)
+}
+
+///
+/// Finds the element with the specified ID in the frames at the specified time
+///
+fn find_element<Anim: Animation>(animation: &Anim, when: Duration, element_id: ElementId) -> Option<Vector> {
+    frame_for_element(animation, when, element_id)
+        .and_then(|frame| frame.element_with_id(element_id))
+        .map(|element| element.original_without_transformations())
+}
+
+///
+/// Returns true if the specified element currently has an attachment
+///
+fn has_attachment<Anim: Animation>(animation: &Anim, when: Duration, element_id: ElementId, attachment: ElementId) -> bool {
+    frame_for_element(animation, when, element_id)
+        .map(|frame| frame.attached_elements(element_id).into_iter().any(|(attached_id, _type)| attached_id == attachment))
+        .unwrap_or(false)
+}
+
+///
+/// Returns the edits that will reverse the effects of an edit, or None if the edit can't be reversed
+///
+fn undo_edit<Anim: Animation>(animation: &Anim, when: Duration, edit: &AnimationEdit) -> Option<Vec<AnimationEdit>> {
+    use self::AnimationEdit::*;
+
+    match edit {
+        SetSize(_, _)                       => {
+            let (width, height) = animation.size();
+            Some(vec![SetSize(width, height)])
+        },
+
+        SetDuration(_)                      => Some(vec![SetDuration(animation.duration())]),
+        SetFrameLength(_)                   => Some(vec![SetFrameLength(animation.frame_length())]),
+
+        AddNewLayer(layer_id)               |
+        AddNewGroupLayer(layer_id)          => {
+            if animation.get_layer_with_id(*layer_id).is_some() {
+                // Adding a layer that already exists has no effect
+                Some(vec![])
+            } else {
+                Some(vec![RemoveLayer(*layer_id)])
+            }
+        },
+
+        RemoveLayer(layer_id)               => {
+            match animation.get_layer_with_id(*layer_id) {
+                // Removing a layer that doesn't exist has no effect
+                None                                                => Some(vec![]),
+
+                // Groups have no content of their own, so they can be recreated along with the layers they contain
+                Some(ref layer) if layer.as_group_layer().is_some() => Some(restore_group_layer(animation, &**layer)),
+
+                // Vector layers are recreated along with their keyframes and the elements they contain
+                Some(ref layer)                                     => restore_vector_layer(animation, &**layer)
+            }
+        },
+
+        Layer(layer_id, layer_edit)         => undo_layer_edit(animation, *layer_id, layer_edit),
+        Element(element_ids, element_edit)  => undo_element_edit(animation, when, element_ids, element_edit),
+        Motion(motion_id, motion_edit)      => undo_motion_edit(animation, *motion_id, motion_edit)
+    }
+}
+
+///
+/// Returns the edits that will restore the properties of a layer after it has been recreated
+///
+fn restore_layer_properties<Anim: Animation>(animation: &Anim, layer: &dyn Layer) -> Vec<AnimationEdit> {
+    let layer_id    = layer.id();
+    let mut edits   = vec![
+        AnimationEdit::Layer(layer_id, LayerEdit::SetVisible(layer.is_visible())),
+        AnimationEdit::Layer(layer_id, LayerEdit::SetLocked(layer.is_locked())),
+        AnimationEdit::Layer(layer_id, LayerEdit::SetOpacity(layer.opacity())),
+        AnimationEdit::Layer(layer_id, LayerEdit::SetBlendMode(layer.blend_mode())),
+        AnimationEdit::Layer(layer_id, LayerEdit::SetParentGroup(layer.parent_group()))
+    ];
+
+    if let Some(name) = layer.name() {
+        edits.push(AnimationEdit::Layer(layer_id, LayerEdit::SetName(name)));
+    }
+
+    edits.extend(restore_layer_ordering(animation, layer_id));
+
+    edits
+}
+
+///
+/// Returns the edit that will move a layer back to its current position in the layer ordering
+///
+fn restore_layer_ordering<Anim: Animation>(animation: &Anim, layer_id: u64) -> Option<AnimationEdit> {
+    animation.get_layer_ids()
+        .into_iter()
+        .position(|other_id| other_id == layer_id)
+        .map(|index| AnimationEdit::Layer(layer_id, LayerEdit::SetOrdering(index as u32)))
+}
+
+///
+/// Returns the edits that will recreate a group layer after it has been removed
+///
+fn restore_group_layer<Anim: Animation>(animation: &Anim, group: &dyn Layer) -> Vec<AnimationEdit> {
+    let group_id        = group.id();
+    let child_layer_ids = group.as_group_layer().map(|group| group.child_layer_ids()).unwrap_or_else(|| vec![]);
+
+    let mut edits       = vec![AnimationEdit::AddNewGroupLayer(group_id)];
+
+    edits.extend(restore_layer_properties(animation, group));
+    edits.extend(child_layer_ids.into_iter().map(|child_id| AnimationEdit::Layer(child_id, LayerEdit::SetParentGroup(Some(group_id)))));
+
+    edits
+}
+
+///
+/// Returns the edits that will recreate a vector layer and its content after it has been removed, or None if some
+/// of its content can't be recreated
+///
+fn restore_vector_layer<Anim: Animation>(animation: &Anim, layer: &dyn Layer) -> Option<Vec<AnimationEdit>> {
+    let layer_id    = layer.id();
+    let mut edits   = vec![AnimationEdit::AddNewLayer(layer_id)];
+
+    edits.extend(restore_layer_properties(animation, layer));
+
+    for keyframe in layer.get_key_frames() {
+        edits.push(AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(keyframe)));
+        restore_frame_elements(&*layer.get_frame_at_time(keyframe), layer_id, keyframe, &mut edits)?;
+    }
+
+    Some(edits)
+}
+
+///
+/// Generates the edits that recreate all of the elements in a frame (from back to front, so they end up in the same order)
+///
+fn restore_frame_elements(frame: &dyn Frame, layer_id: u64, when: Duration, edits: &mut Vec<AnimationEdit>) -> Option<()> {
+    for element in frame.vector_elements()? {
+        match element {
+            // Brush definitions, brush properties and motions are attached to the elements that are drawn rather than drawn themselves
+            Vector::BrushDefinition(_) | Vector::BrushProperties(_) | Vector::Motion(_) => { },
+
+            element                                                                     => {
+                let properties = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+                restore_element(frame, layer_id, when, &element, properties, edits)?;
+            }
+        }
+    }
+
+    Some(())
+}
+
+///
+/// Generates the edits that will recreate an element (with its original ID) after it has been removed from a frame
+///
+/// The element is recreated in front of the other elements in the frame, with a new copy of the brush it was drawn with.
+/// Returns None if the element can't be recreated.
+///
+fn restore_element(frame: &dyn Frame, layer_id: u64, when: Duration, element: &Vector, properties: Arc<VectorProperties>, edits: &mut Vec<AnimationEdit>) -> Option<()> {
+    let element     = element.original_without_transformations();
+    let element_id  = element.id();
+
+    if !element_id.is_assigned() {
+        return None;
+    }
+
+    match element {
+        Vector::BrushStroke(brush_stroke)   => {
+            let (definition, drawing_style) = properties.brush.to_definition();
+
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::SelectBrush(ElementId::Unassigned, definition, drawing_style))));
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::BrushProperties(ElementId::Unassigned, properties.brush_properties.clone()))));
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Paint(when, PaintEdit::CreateBrushStroke(element_id, brush_stroke.points()))));
+        },
+
+        Vector::Path(path)                  => {
+            let (definition, drawing_style) = properties.brush.to_definition();
+
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::SelectBrush(ElementId::Unassigned, definition, drawing_style))));
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::BrushProperties(ElementId::Unassigned, properties.brush_properties.clone()))));
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Path(when, PathEdit::CreatePath(element_id, Arc::new(path.path().elements().collect())))));
+        },
+
+        Vector::Text(text)                  => {
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Text(when, TextEdit::CreateText(element_id, text.style().clone(), text.position(), text.text().to_string()))));
+        },
+
+        Vector::Image(image)                => {
+            edits.push(AnimationEdit::Layer(layer_id, LayerEdit::Image(when, ImageEdit::CreateImage(element_id, image.image().clone(), image.transform()))));
+        },
+
+        Vector::Group(group)                => {
+            // Recreate the members of the group (which are deleted along with it), then group them again
+            let mut member_ids = vec![];
+
+            for member in group.elements() {
+                match member {
+                    Vector::BrushDefinition(_) | Vector::BrushProperties(_) | Vector::Motion(_) => { },
+
+                    member                                                                      => {
+                        let member_properties = frame.apply_properties_for_element(member, Arc::clone(&properties));
+
+                        restore_element(frame, layer_id, when, member, member_properties, edits)?;
+                        member_ids.push(member.id());
+                    }
+                }
+            }
+
+            edits.push(AnimationEdit::Element(member_ids, ElementEdit::Group(element_id, group.group_type())));
+        },
+
+        Vector::Transformed(_)              |
+        Vector::BrushDefinition(_)          |
+        Vector::BrushProperties(_)          |
+        Vector::Motion(_)                   => { return None; }
+    }
+
+    // Any motions that were attached to the element need to be attached again
+    let motions = frame.attached_elements(element_id)
+        .into_iter()
+        .filter(|(_attached_id, attached_type)| *attached_type == VectorType::Motion)
+        .map(|(motion_id, _attached_type)| AnimationEdit::Element(vec![element_id], ElementEdit::AddAttachment(motion_id)));
+    edits.extend(motions);
+
+    Some(())
+}
+
+///
+/// Returns the edits that will reverse the effects of a layer edit
+///
+fn undo_layer_edit<Anim: Animation>(animation: &Anim, layer_id: u64, edit: &LayerEdit) -> Option<Vec<AnimationEdit>> {
+    use self::LayerEdit::*;
+
+    match edit {
+        // Brush strokes, paths, text and images can be undone by deleting the element that was created
+        Paint(_, PaintEdit::BrushStroke(element_id, _))         |
+        Paint(_, PaintEdit::CreateBrushStroke(element_id, _))   |
+        Path(_, PathEdit::CreatePath(element_id, _))            |
+        Text(_, TextEdit::CreateText(element_id, _, _, _))      |
+        Image(_, ImageEdit::CreateImage(element_id, _, _))      => {
+            if element_id.is_assigned() {
+                Some(vec![AnimationEdit::Element(vec![*element_id], ElementEdit::Delete)])
+            } else {
+                None
+            }
+        },
+
+        // Brush selections create elements that the following brush strokes are attached to, which are deleted too
+        // (if the animation assigned their IDs, they're left behind: they aren't drawn so this only wastes some space)
+        Paint(_, PaintEdit::SelectBrush(element_id, _, _))      |
+        Paint(_, PaintEdit::BrushProperties(element_id, _))     |
+        Path(_, PathEdit::SelectBrush(element_id, _, _))        |
+        Path(_, PathEdit::BrushProperties(element_id, _))       => {
+            if element_id.is_assigned() {
+                Some(vec![AnimationEdit::Element(vec![*element_id], ElementEdit::Delete)])
+            } else {
+                Some(vec![])
+            }
+        },
+
+        // Elements are deleted before the layer they're on is removed, so only the edits to the layer itself need to find it
+        other                                                   => undo_layer_property_edit(animation, layer_id, other)
+    }
+}
+
+///
+/// Returns the edits that will reverse the effects of an edit to the keyframes or properties of a layer
+///
+fn undo_layer_property_edit<Anim: Animation>(animation: &Anim, layer_id: u64, edit: &LayerEdit) -> Option<Vec<AnimationEdit>> {
+    use self::LayerEdit::*;
+
+    let layer = match animation.get_layer_with_id(layer_id) {
+        Some(layer) => layer,
+
+        // Either this edit has no effect or the layer is being created in the same transaction (and will be removed when it's undone)
+        None        => { return Some(vec![]); }
+    };
+
+    match edit {
+        // Edits that create elements are undone by undo_layer_edit
+        Paint(_, _) | Path(_, _) | Text(_, _) | Image(_, _)    => Some(vec![]),
+
+        AddKeyFrame(when)                               => {
+            if layer.get_key_frames_during_time(*when..(*when + Duration::from_micros(1))).any(|keyframe| keyframe == *when) {
+                // The keyframe already exists
+                Some(vec![])
+            } else {
+                Some(vec![AnimationEdit::Layer(layer_id, RemoveKeyFrame(*when))])
+            }
+        },
+
+        RemoveKeyFrame(when)                            => {
+            if !layer.get_key_frames_during_time(*when..(*when + Duration::from_micros(1))).any(|keyframe| keyframe == *when) {
+                // No keyframe to remove
+                Some(vec![])
+            } else {
+                // Add the keyframe back again along with its content
+                let frame       = layer.get_frame_at_time(*when);
+                let mut restore = vec![AnimationEdit::Layer(layer_id, AddKeyFrame(*when))];
+
+                restore_frame_elements(&*frame, layer_id, *when, &mut restore)?;
+
+                Some(restore)
+            }
+        },
+
+        SetName(_)                                      => {
+            let old_name = layer.name().unwrap_or_else(|| format!("Layer {}", layer_id));
+            Some(vec![AnimationEdit::Layer(layer_id, SetName(old_name))])
+        },
+
+        SetOrdering(_)                                  => Some(restore_layer_ordering(animation, layer_id).into_iter().collect()),
+
+        SetVisible(_)                                   => Some(vec![AnimationEdit::Layer(layer_id, SetVisible(layer.is_visible()))]),
+        SetLocked(_)                                    => Some(vec![AnimationEdit::Layer(layer_id, SetLocked(layer.is_locked()))]),
+        SetOpacity(_)                                   => Some(vec![AnimationEdit::Layer(layer_id, SetOpacity(layer.opacity()))]),
+        SetBlendMode(_)                                 => Some(vec![AnimationEdit::Layer(layer_id, SetBlendMode(layer.blend_mode()))]),
+        SetParentGroup(_)                               => Some(vec![AnimationEdit::Layer(layer_id, SetParentGroup(layer.parent_group()))])
+    }
+}
+
+///
+/// Returns the edits that will reverse the effects of an element edit
+///
+fn undo_element_edit<Anim: Animation>(animation: &Anim, when: Duration, element_ids: &Vec<ElementId>, edit: &ElementEdit) -> Option<Vec<AnimationEdit>> {
+    use self::ElementEdit::*;
+
+    match edit {
+        AddAttachment(attachment)           => {
+            let newly_attached = element_ids.iter()
+                .filter(|element_id| !has_attachment(animation, when, **element_id, *attachment))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if newly_attached.len() > 0 {
+                Some(vec![AnimationEdit::Element(newly_attached, RemoveAttachment(*attachment))])
+            } else {
+                Some(vec![])
+            }
+        },
+
+        RemoveAttachment(attachment)        => {
+            let detached = element_ids.iter()
+                .filter(|element_id| has_attachment(animation, when, **element_id, *attachment))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            if detached.len() > 0 {
+                Some(vec![AnimationEdit::Element(detached, AddAttachment(*attachment))])
+            } else {
+                Some(vec![])
+            }
+        },
+
+        SetControlPoints(_) | SetPath(_)    => {
+            // Restore the elements to their original shape
+            Some(element_ids.iter()
+                .filter_map(|element_id| find_element(animation, when, *element_id))
+                .map(|element| {
+                    match element {
+                        Vector::Path(path)  => AnimationEdit::Element(vec![path.id()], SetPath(Arc::new(path.path().elements().collect()))),
+                        other               => AnimationEdit::Element(vec![other.id()], SetControlPoints(other.control_points().iter().map(|cp| cp.position()).collect()))
+                    }
+                })
+                .collect())
+        },
+
+        Order(ordering)                     => undo_ordering(animation, when, element_ids, *ordering),
+
+        // Deleted elements are recreated with the same ID
+        Delete                              => restore_deleted_elements(animation, when, element_ids),
+
+        // There's no edit that puts an element back in a frame once it's been detached
+        DetachFromFrame                     => None,
+
+        // New groups can be undone by ungrouping them again
+        Group(group_id, _group_type)        => {
+            if group_id.is_assigned() {
+                Some(vec![AnimationEdit::Element(vec![*group_id], Ungroup)])
+            } else {
+                None
+            }
+        },
+
+        Ungroup                             => {
+            // Regroup the elements that were in each group (the brush properties in the group are attachments of the grouped elements, so aren't included)
+            Some(element_ids.iter()
+                .filter_map(|element_id| find_element(animation, when, *element_id))
+                .filter_map(|element| {
+                    match element {
+                        Vector::Group(group)    => {
+                            let members = group.elements()
+                                .filter(|member| match member { Vector::BrushDefinition(_) | Vector::BrushProperties(_) => false, _ => true })
+                                .map(|member| member.id())
+                                .collect();
+
+                            Some(AnimationEdit::Element(members, Group(group.id(), group.group_type())))
+                        },
+
+                        _                       => None
+                    }
+                })
+                .collect())
+        }
+    }
+}
+
+///
+/// Returns the edits that will recreate a set of elements after they've been deleted
+///
+/// Elements that can't be found have already been deleted, so are skipped
+///
+fn restore_deleted_elements<Anim: Animation>(animation: &Anim, when: Duration, element_ids: &Vec<ElementId>) -> Option<Vec<AnimationEdit>> {
+    let deleted     = element_ids.iter().cloned().collect::<HashSet<_>>();
+    let mut found   = vec![];
+
+    for element_id in deleted.iter() {
+        if let Some((layer_id, frame_time, frame)) = locate_element(animation, when, *element_id) {
+            // Work out where the element is in the frame, and how many of the elements that remain are in front of it
+            let order       = frame.vector_elements()?.map(|element| element.id()).collect::<Vec<_>>();
+            let index       = order.iter().position(|id| id == element_id)?;
+            let in_front    = order[(index+1)..].iter().filter(|id| !deleted.contains(id)).count();
+
+            found.push((layer_id, frame_time, frame, *element_id, index, in_front));
+        }
+    }
+
+    // Recreate the elements from back to front: each one is restored in front of the others and then moved back into place
+    found.sort_by_key(|(layer_id, frame_time, _frame, _element_id, index, _in_front)| (*layer_id, *frame_time, *index));
+
+    let mut restore = vec![];
+    for (layer_id, frame_time, frame, element_id, _index, in_front) in found {
+        let element     = frame.element_with_id(element_id)?;
+        let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+
+        restore_element(&*frame, layer_id, frame_time, &element, properties, &mut restore)?;
+        restore.extend((0..in_front).map(|_| AnimationEdit::Element(vec![element_id], ElementEdit::Order(ElementOrdering::Behind))));
+    }
+
+    Some(restore)
+}
+
+///
+/// Returns the edits that will reverse the effects of reordering a set of elements
+///
+fn undo_ordering<Anim: Animation>(animation: &Anim, when: Duration, element_ids: &Vec<ElementId>, ordering: ElementOrdering) -> Option<Vec<AnimationEdit>> {
+    use self::ElementOrdering::*;
+
+    if element_ids.len() == 0 {
+        return Some(vec![]);
+    }
+
+    // Fetch the current ordering of the elements in the frame
+    let frame       = frame_for_element(animation, when, element_ids[0])?;
+    let mut order   = frame.vector_elements()?.map(|element| element.id()).collect::<Vec<_>>();
+    let mut undo    = vec![];
+
+    // Elements are moved one at a time: track where each one ends up and how to move it back
+    for element_id in element_ids.iter() {
+        let index       = order.iter().position(|id| id == element_id)?;
+        let last_index  = order.len()-1;
+        let new_index   = match ordering {
+            InFront     => if index < last_index { index+1 } else { index },
+            Behind      => if index > 0 { index-1 } else { index },
+            ToTop       => last_index,
+            ToBottom    => 0,
+            Before(_)   => { return None; }
+        };
+
+        let moved = order.remove(index);
+        order.insert(new_index, moved);
+
+        if new_index > index {
+            undo.extend((index..new_index).map(|_| AnimationEdit::Element(vec![*element_id], ElementEdit::Order(Behind))));
+        } else {
+            undo.extend((new_index..index).map(|_| AnimationEdit::Element(vec![*element_id], ElementEdit::Order(InFront))));
+        }
+    }
+
+    // Moves need to be reversed in the opposite order
+    undo.reverse();
+    Some(undo)
+}
+
+///
+/// Returns the edits that will reverse the effects of a motion edit
+///
+fn undo_motion_edit<Anim: Animation>(animation: &Anim, motion_id: ElementId, edit: &MotionEdit) -> Option<Vec<AnimationEdit>> {
+    use self::MotionEdit::*;
+
+    let motion = animation.motion().get_motion(motion_id);
+
+    match (edit, motion) {
+        (Create, None)                              => Some(vec![AnimationEdit::Motion(motion_id, Delete)]),
+
+        // Creating a motion that already exists leaves it alone, but put its properties back in case it was reset
+        (Create, Some(motion))                      => Some(restore_motion_properties(motion_id, &motion)),
+
+        (Delete, None)                              => Some(vec![]),
+        (Delete, Some(motion))                      => {
+            // Recreate the motion and reattach it to its elements
+            let mut recreate = vec![AnimationEdit::Motion(motion_id, Create)];
+            recreate.extend(restore_motion_properties(motion_id, &motion));
+
+            let attached_to = animation.motion().get_elements_for_motion(motion_id);
+            if attached_to.len() > 0 {
+                recreate.push(AnimationEdit::Element(attached_to, ElementEdit::AddAttachment(motion_id)));
+            }
+
+            Some(recreate)
+        },
+
+        (SetType(_), Some(motion))                  => Some(vec![AnimationEdit::Motion(motion_id, SetType(motion.motion_type()))]),
+        (SetOrigin(_, _), Some(motion))             => Some(motion.origin().map(|origin| vec![AnimationEdit::Motion(motion_id, SetOrigin(origin.0, origin.1))]).unwrap_or(vec![])),
+        (SetPath(_), Some(motion))                  => Some(motion.path().map(|path| vec![AnimationEdit::Motion(motion_id, SetPath(path.clone()))]).unwrap_or(vec![])),
+        (SetRotation(_), Some(motion))              => Some(motion.rotation().map(|rotation| vec![AnimationEdit::Motion(motion_id, SetRotation(rotation.clone()))]).unwrap_or(vec![])),
+        (SetScale(_), Some(motion))                 => Some(motion.scale().map(|scale| vec![AnimationEdit::Motion(motion_id, SetScale(scale.clone()))]).unwrap_or(vec![])),
+
+        // Motion types that don't use their origin or curves need no undo, and motions that don't exist yet are created in the same transaction
+        (SetOrigin(_, _), None)                     => Some(vec![]),
+        (SetPath(_), None)                          => Some(vec![]),
+        (SetRotation(_), None)                      => Some(vec![]),
+        (SetScale(_), None)                         => Some(vec![]),
+        (SetType(_), None)                          => Some(vec![])
+    }
+}
+
+///
+/// Returns the edits that will set the properties of a motion back to their current values
+///
+fn restore_motion_properties(motion_id: ElementId, motion: &Motion) -> Vec<AnimationEdit> {
+    use self::MotionEdit::*;
+
+    let mut restore = vec![AnimationEdit::Motion(motion_id, SetType(motion.motion_type()))];
+
+    if let Some(origin) = motion.origin() {
+        restore.push(AnimationEdit::Motion(motion_id, SetOrigin(origin.0, origin.1)));
+    }
+    if let Some(path) = motion.path() {
+        restore.push(AnimationEdit::Motion(motion_id, SetPath(path.clone())));
+    }
+    if let Some(rotation) = motion.rotation() {
+        restore.push(AnimationEdit::Motion(motion_id, SetRotation(rotation.clone())));
+    }
+    if let Some(scale) = motion.scale() {
+        restore.push(AnimationEdit::Motion(motion_id, SetScale(scale.clone())));
+    }
+
+    restore
+}
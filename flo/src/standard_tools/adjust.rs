@@ -529,7 +529,7 @@ impl Adjust {
     }
 }
 
-impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
+impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Adjust {
     type ToolData   = AdjustData;
     type Model      = ();
 
@@ -539,8 +539,8 @@ impl<Anim: 'static+Animation> Tool<Anim> for Adjust {
 
     fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> () { }
 
-    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(AdjustMenuController::new()))
+    fn create_menu_controller(&self, flo_model: Arc<FloModel<Anim>>, _tool_model: &()) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(AdjustMenuController::new(&*flo_model)))
     }
 
     ///
@@ -0,0 +1,142 @@
+use super::ink::*;
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+use std::sync::*;
+
+///
+/// The calligraphy UI model
+///
+pub struct CalligraphyModel {
+    /// The size, colour and other settings shared with the ink tool
+    pub ink: InkModel,
+
+    /// The angle of the nib (degrees)
+    pub nib_angle: Binding<f32>,
+
+    /// The thickness of the nib relative to its width (0-1)
+    pub nib_aspect_ratio: Binding<f32>,
+
+    /// How much the tilt of the pen turns the nib (0-1)
+    pub tilt_influence: Binding<f32>,
+
+    /// The brush definition for the current settings
+    pub brush_definition: BindRef<BrushDefinition>
+}
+
+impl CalligraphyModel {
+    ///
+    /// Creates a new calligraphy model with the default settings
+    ///
+    pub fn new() -> CalligraphyModel {
+        let defaults            = CalligraphyDefinition::default();
+
+        let ink                 = InkModel::new();
+        let nib_angle           = bind(defaults.nib_angle);
+        let nib_aspect_ratio    = bind(defaults.nib_aspect_ratio);
+        let tilt_influence      = bind(defaults.tilt_influence);
+
+        ink.size.set(defaults.max_width);
+
+        let brush_definition    = Self::brush_definition(nib_angle.clone(), nib_aspect_ratio.clone(), tilt_influence.clone());
+
+        CalligraphyModel {
+            ink:                ink,
+            nib_angle:          nib_angle,
+            nib_aspect_ratio:   nib_aspect_ratio,
+            tilt_influence:     tilt_influence,
+            brush_definition:   brush_definition
+        }
+    }
+
+    ///
+    /// Creates the brush definition from the model bindings
+    ///
+    fn brush_definition(nib_angle: Binding<f32>, nib_aspect_ratio: Binding<f32>, tilt_influence: Binding<f32>) -> BindRef<BrushDefinition> {
+        let brush_definition = computed(move || {
+            BrushDefinition::Calligraphy(CalligraphyDefinition {
+                nib_angle:          nib_angle.get(),
+                nib_aspect_ratio:   nib_aspect_ratio.get(),
+                tilt_influence:     tilt_influence.get(),
+                ..CalligraphyDefinition::default()
+            })
+        });
+
+        BindRef::from(brush_definition)
+    }
+}
+
+///
+/// The Calligraphy tool (draws with a flat nib)
+///
+pub struct Calligraphy {
+    ink: Ink
+}
+
+impl Calligraphy {
+    ///
+    /// Creates a new instance of the Calligraphy tool
+    ///
+    pub fn new() -> Calligraphy {
+        Calligraphy {
+            ink: Ink::new()
+        }
+    }
+}
+
+impl<Anim: Animation+'static> Tool<Anim> for Calligraphy {
+    type ToolData   = InkData;
+    type Model      = CalligraphyModel;
+
+    fn tool_name(&self) -> String { "Calligraphy".to_string() }
+
+    fn image_name(&self) -> String { "calligraphy".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> CalligraphyModel {
+        CalligraphyModel::new()
+    }
+
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &CalligraphyModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(CalligraphyMenuController::new(&tool_model.ink.size, &tool_model.ink.opacity, &tool_model.ink.color,
+            &tool_model.nib_angle, &tool_model.nib_aspect_ratio, &tool_model.tilt_influence,
+            &tool_model.ink.modification_mode, &tool_model.ink.representation)))
+    }
+
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &CalligraphyModel) -> Box<dyn Stream<Item=ToolAction<InkData>, Error=()>+Send> {
+        // Fetch the brush properties
+        let brush_definition    = tool_model.brush_definition.clone();
+        let brush_properties    = tool_model.ink.brush_properties.clone();
+        let selected_layer      = flo_model.timeline().selected_layer.clone();
+        let representation      = tool_model.ink.representation.clone();
+        let modification_mode   = tool_model.ink.modification_mode.clone();
+        let stabiliser          = tool_model.ink.stabiliser.clone();
+
+        // Create a computed binding that generates the data for the brush
+        let ink_data            = computed(move || {
+            InkData {
+                brush:              brush_definition.get(),
+                brush_properties:   brush_properties.get(),
+                selected_layer:     selected_layer.get().unwrap_or(0),
+                representation:     representation.get(),
+                modification_mode:  modification_mode.get(),
+                stabiliser:         stabiliser.get()
+            }
+        });
+
+        // Turn the computed values into a stream and update the brush whenever the values change
+        Box::new(follow(ink_data).map(|ink_data| ToolAction::Data(ink_data)))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<InkData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<InkData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<InkData>>> {
+        let ink: &dyn Tool<Anim, ToolData=InkData, Model=InkModel> = &self.ink;
+
+        // Painting works exactly as for the ink tool: the brush definition in the tool data takes care of the nib
+        ink.actions_for_input(flo_model, data, input)
+    }
+}
@@ -62,6 +62,7 @@ impl<Anim: Animation+'static> Tool<Anim> for Eraser {
         // Fetch the brush properties
         let brush_properties    = tool_model.brush_properties.clone();
         let selected_layer      = flo_model.timeline().selected_layer.clone();
+        let stabiliser          = tool_model.stabiliser.clone();
 
         // Create a computed binding that generates the data for the brush
         let ink_data            = computed(move || {
@@ -70,7 +71,8 @@ impl<Anim: Animation+'static> Tool<Anim> for Eraser {
                 brush_properties:   brush_properties.get(),
                 selected_layer:     selected_layer.get().unwrap_or(0),
                 modification_mode:  BrushModificationMode::Individual,
-                representation:     BrushRepresentation::BrushStroke
+                representation:     BrushRepresentation::BrushStroke,
+                stabiliser:         stabiliser.get()
             }
         });
 
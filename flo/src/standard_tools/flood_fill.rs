@@ -23,7 +23,16 @@ pub struct FloodFillModel {
     pub opacity: Binding<f32>,
 
     /// The color of the next flood fill that will be added
-    pub color: Binding<Color>
+    pub color: Binding<Color>,
+
+    /// Whether the next flood fill is a solid colour or a gradient
+    pub fill_style: Binding<FillStyle>,
+
+    /// The colour at the end of the gradient, when the fill style is a gradient
+    pub end_color: Binding<Color>,
+
+    /// The angle of linear gradients, in degrees
+    pub gradient_angle: Binding<f32>
 }
 
 ///
@@ -48,8 +57,11 @@ impl FloodFillModel {
     ///
     pub fn new() -> FloodFillModel {
         FloodFillModel {
-            opacity:    bind(1.0),
-            color:      bind(Color::Rgba(0.0, 0.6, 0.35, 1.0))
+            opacity:        bind(1.0),
+            color:          bind(Color::Rgba(0.0, 0.6, 0.35, 1.0)),
+            fill_style:     bind(FillStyle::Solid),
+            end_color:      bind(Color::Rgba(1.0, 1.0, 1.0, 1.0)),
+            gradient_angle: bind(90.0)
         }
     }
 }
@@ -129,21 +141,34 @@ impl<Anim: 'static+Animation> Tool<Anim> for FloodFill {
     }
 
     fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &FloodFillModel) -> Option<Arc<dyn Controller>> {
-        let color   = tool_model.color.clone();
-        let opacity = tool_model.opacity.clone();
+        let color       = tool_model.color.clone();
+        let opacity     = tool_model.opacity.clone();
+        let fill_style  = tool_model.fill_style.clone();
+        let end_color   = tool_model.end_color.clone();
+        let angle       = tool_model.gradient_angle.clone();
 
-        Some(Arc::new(FloodFillMenuController::new(color, opacity)))
+        Some(Arc::new(FloodFillMenuController::new(color, opacity, fill_style, end_color, angle)))
     }
 
     fn actions_for_model(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &FloodFillModel) -> Box<dyn Stream<Item=ToolAction<FloodFillData>, Error=()>+Send> {
         // Compute brush properties from the model
         let color               = tool_model.color.clone();
         let opacity             = tool_model.opacity.clone();
+        let fill_style          = tool_model.fill_style.clone();
+        let end_color           = tool_model.end_color.clone();
+        let gradient_angle      = tool_model.gradient_angle.clone();
         let brush_properties    = computed(move || {
+            let gradient = match fill_style.get() {
+                FillStyle::Solid            => None,
+                FillStyle::LinearGradient   => Some(BrushGradient::Linear(end_color.get(), gradient_angle.get())),
+                FillStyle::RadialGradient   => Some(BrushGradient::Radial(end_color.get()))
+            };
+
             BrushProperties {
                 size:       1.0,
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                gradient:   gradient
             }
         });
 
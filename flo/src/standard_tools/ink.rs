@@ -6,10 +6,17 @@ use flo_ui::*;
 use flo_canvas::*;
 use flo_binding::*;
 use flo_animation::*;
+use flo_animation::brushes::*;
 
 use futures::*;
 use std::sync::*;
 
+/// Default length of the string used when the stabiliser is set to the pulled string mode
+const DEFAULT_STABILISER_STRING_LENGTH: f32 = 20.0;
+
+/// Default number of points averaged when the stabiliser is set to the moving average mode
+const DEFAULT_STABILISER_AVERAGE_SAMPLES: usize = 8;
+
 ///
 /// Data for the ink brush
 ///
@@ -19,7 +26,8 @@ pub struct InkData {
     pub brush_properties:   BrushProperties,
     pub selected_layer:     u64,
     pub representation:     BrushRepresentation,
-    pub modification_mode:  BrushModificationMode
+    pub modification_mode:  BrushModificationMode,
+    pub stabiliser:         StabiliserSettings
 }
 
 ///
@@ -42,7 +50,31 @@ pub struct InkModel {
     pub modification_mode: Binding<BrushModificationMode>,
 
     /// The way new brush strokes are represented
-    pub representation: Binding<BrushRepresentation>
+    pub representation: Binding<BrushRepresentation>,
+
+    /// How the stabiliser smooths out brush strokes
+    pub stabiliser_mode: Binding<StabiliserMode>,
+
+    /// Whether or not the stabiliser catches up with the pointer when a brush stroke is finished
+    pub stabiliser_catch_up: Binding<bool>,
+
+    /// The length of the string (in canvas units) when the stabiliser pulls the brush on a string
+    pub stabiliser_string_length: Binding<f32>,
+
+    /// The number of points averaged when the stabiliser averages recent points
+    pub stabiliser_average_samples: Binding<usize>,
+
+    /// The stabiliser settings for the current model
+    pub stabiliser: BindRef<StabiliserSettings>,
+
+    /// How the pressure of the pen is mapped to the width of the brush stroke
+    pub pressure_curve: Binding<PressureCurve>,
+
+    /// Where the pressure for brush strokes comes from
+    pub pressure_source: Binding<PressureSource>,
+
+    /// The brush definition for the current model
+    pub brush_definition: BindRef<BrushDefinition>
 }
 
 ///
@@ -69,16 +101,32 @@ impl InkModel {
         let color               = bind(Color::Hsluv(0.0, 100.0, 0.0, 1.0));
         let modification_mode   = bind(BrushModificationMode::Individual);
         let representation      = bind(BrushRepresentation::BrushStroke);
+        let stabiliser_mode     = bind(StabiliserSettings::default().mode);
+        let stabiliser_catch_up = bind(StabiliserSettings::default().catch_up_on_finish);
+        let string_length       = bind(DEFAULT_STABILISER_STRING_LENGTH);
+        let average_samples     = bind(DEFAULT_STABILISER_AVERAGE_SAMPLES);
+        let pressure_curve      = bind(PressureCurve::Linear);
+        let pressure_source     = bind(PressureSource::Device);
 
         let brush_properties    = Self::brush_properties(size.clone(), opacity.clone(), color.clone());
+        let stabiliser          = Self::stabiliser(stabiliser_mode.clone(), stabiliser_catch_up.clone(), string_length.clone(), average_samples.clone());
+        let brush_definition    = Self::brush_definition(pressure_curve.clone(), pressure_source.clone());
 
         InkModel {
-            size:               size,
-            opacity:            opacity,
-            color:              color,
-            brush_properties:   brush_properties,
-            modification_mode:  modification_mode,
-            representation:     representation
+            size:                       size,
+            opacity:                    opacity,
+            color:                      color,
+            brush_properties:           brush_properties,
+            modification_mode:          modification_mode,
+            representation:             representation,
+            stabiliser_mode:            stabiliser_mode,
+            stabiliser_catch_up:        stabiliser_catch_up,
+            stabiliser_string_length:   string_length,
+            stabiliser_average_samples: average_samples,
+            stabiliser:                 stabiliser,
+            pressure_curve:             pressure_curve,
+            pressure_source:            pressure_source,
+            brush_definition:           brush_definition
         }
     }
 
@@ -90,12 +138,50 @@ impl InkModel {
             BrushProperties {
                 size:       size.get(),
                 opacity:    opacity.get(),
-                color:      color.get()
+                color:      color.get(),
+                gradient:   None
             }
         });
 
         BindRef::from(brush_properties)
     }
+
+    ///
+    /// Creates the stabiliser settings from the model bindings
+    ///
+    /// The string length and number of samples for the stabiliser mode are taken from the string length and average samples settings
+    ///
+    fn stabiliser(mode: Binding<StabiliserMode>, catch_up_on_finish: Binding<bool>, string_length: Binding<f32>, average_samples: Binding<usize>) -> BindRef<StabiliserSettings> {
+        let stabiliser = computed(move || {
+            let mode = match mode.get() {
+                StabiliserMode::Off                 => StabiliserMode::Off,
+                StabiliserMode::PulledString(_)     => StabiliserMode::PulledString(string_length.get()),
+                StabiliserMode::MovingAverage(_)    => StabiliserMode::MovingAverage(average_samples.get())
+            };
+
+            StabiliserSettings {
+                mode:               mode,
+                catch_up_on_finish: catch_up_on_finish.get()
+            }
+        });
+
+        BindRef::from(stabiliser)
+    }
+
+    ///
+    /// Creates the brush definition from the model bindings
+    ///
+    fn brush_definition(pressure_curve: Binding<PressureCurve>, pressure_source: Binding<PressureSource>) -> BindRef<BrushDefinition> {
+        let brush_definition = computed(move || {
+            BrushDefinition::Ink(InkDefinition {
+                pressure_curve:     pressure_curve.get(),
+                pressure_source:    pressure_source.get(),
+                ..InkDefinition::default()
+            })
+        });
+
+        BindRef::from(brush_definition)
+    }
 }
 
 impl<Anim: Animation+'static> Tool<Anim> for Ink {
@@ -123,7 +209,7 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
     /// Creates the menu controller for this tool (or None if this tool has no menu controller)
     ///
     fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &InkModel) -> Option<Arc<dyn Controller>> {
-        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color, &tool_model.modification_mode, &tool_model.representation)))
+        Some(Arc::new(InkMenuController::new(&tool_model.size, &tool_model.opacity, &tool_model.color, &tool_model.modification_mode, &tool_model.representation, &tool_model.stabiliser_mode, &tool_model.stabiliser_catch_up, &tool_model.stabiliser_string_length, &tool_model.stabiliser_average_samples, &tool_model.pressure_curve, &tool_model.pressure_source)))
     }
 
     ///
@@ -135,15 +221,18 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
         let selected_layer      = flo_model.timeline().selected_layer.clone();
         let representation      = tool_model.representation.clone();
         let modification_mode   = tool_model.modification_mode.clone();
+        let stabiliser          = tool_model.stabiliser.clone();
+        let brush_definition    = tool_model.brush_definition.clone();
 
         // Create a computed binding that generates the data for the brush
         let ink_data            = computed(move || {
             InkData {
-                brush:              BrushDefinition::Ink(InkDefinition::default()),
+                brush:              brush_definition.get(),
                 brush_properties:   brush_properties.get(),
                 selected_layer:     selected_layer.get().unwrap_or(0),
                 representation:     representation.get(),
-                modification_mode:  modification_mode.get()
+                modification_mode:  modification_mode.get(),
+                stabiliser:         stabiliser.get()
             }
         });
 
@@ -169,7 +258,8 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
                     BrushPreview(Layer(ink_data.selected_layer)),
                     BrushPreview(Clear),                // Clear on the new layer
                     BrushPreview(BrushDefinition(ink_data.brush.clone(), BrushDrawingStyle::Draw)),
-                    BrushPreview(BrushProperties(ink_data.brush_properties.clone()))
+                    BrushPreview(BrushProperties(ink_data.brush_properties.clone())),
+                    BrushPreview(Stabiliser(ink_data.stabiliser))
                 ],
 
                 PaintDevice(_device)            => vec![
@@ -194,10 +284,10 @@ impl<Anim: Animation+'static> Tool<Anim> for Ink {
                             let representation      = data.as_ref().map(|data| data.representation).unwrap_or(BrushRepresentation::BrushStroke);
                             let modification_mode   = data.as_ref().map(|data| data.modification_mode).unwrap_or(BrushModificationMode::Individual);
 
-                            // Update the preview according to the modification mode
+                            // Let the stabiliser catch up, then update the preview according to the modification mode
                             let update = match modification_mode {
-                                BrushModificationMode::Additive     => vec![BrushPreview(CombineCollidingElements)],
-                                BrushModificationMode::Individual   => vec![]
+                                BrushModificationMode::Additive     => vec![BrushPreview(FinishStroke), BrushPreview(CombineCollidingElements)],
+                                BrushModificationMode::Individual   => vec![BrushPreview(FinishStroke)]
                             };
 
                             // After the update, commit according to the final representation
@@ -1,17 +1,25 @@
 mod select;
 mod select_tool_model;
 mod adjust;
+mod transform;
 mod pan;
 mod ink;
+mod calligraphy;
 mod eraser;
 mod flood_fill;
+mod shape;
+mod text;
 mod tool_sets;
 
 pub use self::select::*;
 pub use self::select_tool_model::*;
 pub use self::adjust::*;
+pub use self::transform::*;
 pub use self::pan::*;
 pub use self::ink::*;
+pub use self::calligraphy::*;
 pub use self::eraser::*;
 pub use self::flood_fill::*;
+pub use self::shape::*;
+pub use self::text::*;
 pub use self::tool_sets::*;
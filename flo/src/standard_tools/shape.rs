@@ -0,0 +1,740 @@
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+
+use std::f32;
+use std::sync::*;
+use std::time::Duration;
+
+/// The constant used to approximate a quarter of an ellipse with a bezier curve
+const ELLIPSE_KAPPA: f32 = 0.5522847;
+
+/// The angle that lines snap to when the constrain modifier is held down
+const LINE_SNAP_ANGLE: f32 = f32::consts::PI / 4.0;
+
+/// The minimum size of a drag that will create a shape (smaller drags are treated as clicks and ignored)
+const MIN_SHAPE_SIZE: f32 = 1.0;
+
+///
+/// The kinds of shape that can be drawn by the shape tool
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ShapeKind {
+    Rectangle,
+    Ellipse,
+    Polygon,
+    Line
+}
+
+impl ShapeKind {
+    ///
+    /// The name of the tool that draws this kind of shape
+    ///
+    pub fn name(&self) -> &'static str {
+        use self::ShapeKind::*;
+
+        match self {
+            Rectangle   => "Rectangle",
+            Ellipse     => "Ellipse",
+            Polygon     => "Polygon",
+            Line        => "Line"
+        }
+    }
+
+    ///
+    /// The name of the image used for the tool that draws this kind of shape
+    ///
+    pub fn image_name(&self) -> &'static str {
+        use self::ShapeKind::*;
+
+        match self {
+            Rectangle   => "rectangle",
+            Ellipse     => "ellipse",
+            Polygon     => "polygon",
+            Line        => "line"
+        }
+    }
+}
+
+///
+/// Model for the shape tools
+///
+pub struct ShapeModel {
+    /// The colour used to fill the inside of new shapes
+    pub fill_color: Binding<Color>,
+
+    /// The colour used to draw the outline of new shapes
+    pub stroke_color: Binding<Color>,
+
+    /// The opacity of new shapes
+    pub opacity: Binding<f32>,
+
+    /// The width of the outline of new shapes
+    pub stroke_width: Binding<f32>,
+
+    /// Whether new shapes are filled, outlined or both
+    pub shape_style: Binding<ShapeStyle>,
+
+    /// The number of sides to use for new polygons
+    pub num_sides: Binding<u32>
+}
+
+///
+/// Data for the shape tools
+///
+#[derive(Clone)]
+pub struct ShapeData {
+    /// Whether new shapes are filled, outlined or both
+    style: ShapeStyle,
+
+    /// The properties of the brush used to fill new shapes
+    fill_properties: BrushProperties,
+
+    /// The properties of the brush used to draw the outline of new shapes
+    stroke_properties: BrushProperties,
+
+    /// The width of the outline of new shapes
+    stroke_width: f32,
+
+    /// The number of sides to use for new polygons
+    num_sides: u32,
+
+    /// Where the current drag started, or None if the user is not dragging out a shape
+    drag_start: Binding<Option<(f32, f32)>>
+}
+
+///
+/// A tool for drawing geometric shapes
+///
+pub struct Shape {
+    /// The kind of shape drawn by this tool
+    kind: ShapeKind
+}
+
+impl ShapeModel {
+    ///
+    /// Creates the default shape model
+    ///
+    pub fn new() -> ShapeModel {
+        ShapeModel {
+            fill_color:     bind(Color::Rgba(0.0, 0.6, 0.35, 1.0)),
+            stroke_color:   bind(Color::Rgba(0.0, 0.0, 0.0, 1.0)),
+            opacity:        bind(1.0),
+            stroke_width:   bind(4.0),
+            shape_style:    bind(ShapeStyle::FillAndStroke),
+            num_sides:      bind(5)
+        }
+    }
+}
+
+impl Shape {
+    ///
+    /// Creates a shape tool that draws the specified kind of shape
+    ///
+    pub fn new(kind: ShapeKind) -> Shape {
+        Shape {
+            kind: kind
+        }
+    }
+
+    ///
+    /// Creates a tool that draws rectangles
+    ///
+    pub fn rectangle() -> Shape { Shape::new(ShapeKind::Rectangle) }
+
+    ///
+    /// Creates a tool that draws ellipses
+    ///
+    pub fn ellipse() -> Shape { Shape::new(ShapeKind::Ellipse) }
+
+    ///
+    /// Creates a tool that draws regular polygons
+    ///
+    pub fn polygon() -> Shape { Shape::new(ShapeKind::Polygon) }
+
+    ///
+    /// Creates a tool that draws straight lines
+    ///
+    pub fn line() -> Shape { Shape::new(ShapeKind::Line) }
+
+    ///
+    /// Works out the two points that define a shape dragged from `start` to `end`
+    ///
+    /// Holding shift constrains rectangles, ellipses and polygons to squares and circles and
+    /// snaps lines to multiples of 45 degrees. Holding alt draws the shape outwards from the
+    /// point where the drag started.
+    ///
+    fn drag_points(kind: ShapeKind, start: (f32, f32), end: (f32, f32), modifiers: &PaintModifiers) -> ((f32, f32), (f32, f32)) {
+        let mut dx = end.0 - start.0;
+        let mut dy = end.1 - start.1;
+
+        if modifiers.shift {
+            match kind {
+                ShapeKind::Line => {
+                    // Snap the line to the nearest 45 degrees
+                    let length  = (dx*dx + dy*dy).sqrt();
+                    let angle   = (dy.atan2(dx) / LINE_SNAP_ANGLE).round() * LINE_SNAP_ANGLE;
+
+                    dx = length * angle.cos();
+                    dy = length * angle.sin();
+                },
+
+                _ => {
+                    // Use the same size in both directions
+                    let size = dx.abs().max(dy.abs());
+
+                    dx = size * dx.signum();
+                    dy = size * dy.signum();
+                }
+            }
+        }
+
+        if modifiers.alt {
+            ((start.0-dx, start.1-dy), (start.0+dx, start.1+dy))
+        } else {
+            (start, (start.0+dx, start.1+dy))
+        }
+    }
+
+    ///
+    /// Creates the outline of a shape defined by two points, grown outwards by the specified offset
+    ///
+    /// A negative offset shrinks the shape, and the result is empty if the shape shrinks away to nothing.
+    /// Lines are outlined as a rectangle around the line, which is empty if the offset is not positive.
+    ///
+    fn outline(kind: ShapeKind, (p1, p2): ((f32, f32), (f32, f32)), num_sides: u32, offset: f32) -> Vec<PathComponent> {
+        let center  = ((p1.0+p2.0)/2.0, (p1.1+p2.1)/2.0);
+        let radius  = ((p1.0-p2.0).abs()/2.0, (p1.1-p2.1).abs()/2.0);
+
+        match kind {
+            ShapeKind::Rectangle => {
+                let (rx, ry) = (radius.0 + offset, radius.1 + offset);
+                if rx <= 0.0 || ry <= 0.0 { return vec![]; }
+
+                let (x1, y1) = (center.0-rx, center.1-ry);
+                let (x2, y2) = (center.0+rx, center.1+ry);
+
+                vec![
+                    PathComponent::Move(PathPoint::new(x1, y1)),
+                    PathComponent::Line(PathPoint::new(x2, y1)),
+                    PathComponent::Line(PathPoint::new(x2, y2)),
+                    PathComponent::Line(PathPoint::new(x1, y2)),
+                    PathComponent::Close
+                ]
+            },
+
+            ShapeKind::Ellipse => {
+                let (rx, ry) = (radius.0 + offset, radius.1 + offset);
+                if rx <= 0.0 || ry <= 0.0 { return vec![]; }
+
+                // Four bezier curves, one for each quarter of the ellipse
+                let (cx, cy)    = center;
+                let (kx, ky)    = (rx * ELLIPSE_KAPPA, ry * ELLIPSE_KAPPA);
+                let point       = |x, y| PathPoint::new(x, y);
+
+                vec![
+                    PathComponent::Move(point(cx+rx, cy)),
+                    PathComponent::Bezier(point(cx, cy+ry), point(cx+rx, cy+ky), point(cx+kx, cy+ry)),
+                    PathComponent::Bezier(point(cx-rx, cy), point(cx-kx, cy+ry), point(cx-rx, cy+ky)),
+                    PathComponent::Bezier(point(cx, cy-ry), point(cx-rx, cy-ky), point(cx-kx, cy-ry)),
+                    PathComponent::Bezier(point(cx+rx, cy), point(cx+kx, cy-ry), point(cx+rx, cy-ky)),
+                    PathComponent::Close
+                ]
+            },
+
+            ShapeKind::Polygon => {
+                // Offsetting the vertices by this amount moves the edges of a regular polygon by the offset
+                let num_sides   = num_sides.max(3);
+                let offset      = offset / (f32::consts::PI / (num_sides as f32)).cos();
+                let (rx, ry)    = (radius.0 + offset, radius.1 + offset);
+                if rx <= 0.0 || ry <= 0.0 { return vec![]; }
+
+                // The vertices are spread around an ellipse, starting at the top
+                let vertices = (0..num_sides)
+                    .map(|side| {
+                        let angle = f32::consts::PI/2.0 + (side as f32) * 2.0 * f32::consts::PI / (num_sides as f32);
+                        PathPoint::new(center.0 + rx*angle.cos(), center.1 + ry*angle.sin())
+                    })
+                    .collect::<Vec<_>>();
+
+                Some(PathComponent::Move(vertices[0])).into_iter()
+                    .chain(vertices.into_iter().skip(1).map(|vertex| PathComponent::Line(vertex)))
+                    .chain(Some(PathComponent::Close))
+                    .collect()
+            },
+
+            ShapeKind::Line => {
+                let (dx, dy)    = (p2.0-p1.0, p2.1-p1.1);
+                let length      = (dx*dx + dy*dy).sqrt();
+                if offset <= 0.0 || length <= 0.0 { return vec![]; }
+
+                // Rectangle around the line, with square ends
+                let (nx, ny)    = (-dy/length*offset, dx/length*offset);
+
+                vec![
+                    PathComponent::Move(PathPoint::new(p1.0+nx, p1.1+ny)),
+                    PathComponent::Line(PathPoint::new(p2.0+nx, p2.1+ny)),
+                    PathComponent::Line(PathPoint::new(p2.0-nx, p2.1-ny)),
+                    PathComponent::Line(PathPoint::new(p1.0-nx, p1.1-ny)),
+                    PathComponent::Close
+                ]
+            }
+        }
+    }
+
+    ///
+    /// Reverses the direction of a closed path made up of a single subpath
+    ///
+    fn reverse_path(path: Vec<PathComponent>) -> Vec<PathComponent> {
+        let mut start       = None;
+        let mut last_point  = PathPoint::new(0.0, 0.0);
+        let mut segments    = vec![];
+
+        // Gather the segments as (start, control points, end)
+        for component in path {
+            match component {
+                PathComponent::Move(point)              => { start = Some(point); last_point = point; },
+                PathComponent::Line(point)              => { segments.push((last_point, None, point)); last_point = point; },
+                PathComponent::Bezier(point, cp1, cp2)  => { segments.push((last_point, Some((cp1, cp2)), point)); last_point = point; },
+                PathComponent::Close                    => { }
+            }
+        }
+
+        // Run through the segments backwards
+        if start.is_none() { return vec![]; }
+
+        Some(PathComponent::Move(last_point)).into_iter()
+            .chain(segments.into_iter().rev().map(|(from, control_points, _to)| {
+                match control_points {
+                    Some((cp1, cp2))    => PathComponent::Bezier(from, cp2, cp1),
+                    None                => PathComponent::Line(from)
+                }
+            }))
+            .chain(Some(PathComponent::Close))
+            .collect()
+    }
+
+    ///
+    /// Creates the paths for a shape and the brush properties to draw each one with
+    ///
+    /// Paths are always filled when they're rendered, so the outline of a shape is made from the
+    /// outside edge of the stroke followed by the inside edge running in the opposite direction,
+    /// which leaves a hole in the middle.
+    ///
+    fn shape_paths(kind: ShapeKind, points: ((f32, f32), (f32, f32)), data: &ShapeData) -> Vec<(Vec<PathComponent>, BrushProperties)> {
+        let half_width  = data.stroke_width.max(0.0) / 2.0;
+        let mut paths   = vec![];
+
+        // Lines are always stroked, other shapes use the style settings
+        let (fills, strokes) = match kind {
+            ShapeKind::Line => (false, true),
+            _               => (data.style.fills(), data.style.strokes())
+        };
+
+        if fills {
+            let fill_path = Self::outline(kind, points, data.num_sides, 0.0);
+            if fill_path.len() > 0 {
+                paths.push((fill_path, data.fill_properties.clone()));
+            }
+        }
+
+        if strokes && half_width > 0.0 {
+            let outer       = Self::outline(kind, points, data.num_sides, half_width);
+            let inner       = Self::reverse_path(Self::outline(kind, points, data.num_sides, -half_width));
+            let stroke_path = outer.into_iter().chain(inner).collect::<Vec<_>>();
+
+            if stroke_path.len() > 0 {
+                paths.push((stroke_path, data.stroke_properties.clone()));
+            }
+        }
+
+        paths
+    }
+
+    ///
+    /// True if a drag between two points is large enough to create a shape
+    ///
+    fn is_large_enough(kind: ShapeKind, (p1, p2): ((f32, f32), (f32, f32))) -> bool {
+        let (w, h) = ((p1.0-p2.0).abs(), (p1.1-p2.1).abs());
+
+        match kind {
+            ShapeKind::Line => (w*w + h*h).sqrt() >= MIN_SHAPE_SIZE,
+            _               => w >= MIN_SHAPE_SIZE && h >= MIN_SHAPE_SIZE
+        }
+    }
+
+    ///
+    /// Draws a preview of the shape the user is dragging out on the overlay
+    ///
+    fn draw_preview(&self, points: ((f32, f32), (f32, f32)), data: &ShapeData) -> Vec<Draw> {
+        let mut draw = vec![];
+
+        draw.layer(0);
+        draw.clear_layer();
+
+        // Draw the shape as it will look once it's created
+        for (path, properties) in Self::shape_paths(self.kind, points, data) {
+            let path = Path::from_elements(path);
+
+            draw.new_path();
+            draw.extend(Vec::<Draw>::from(&path));
+            draw.fill_color(properties.color.with_alpha(properties.opacity));
+            draw.fill();
+        }
+
+        // Outline the shape so it's visible against any background
+        let outline = match self.kind {
+            ShapeKind::Line => vec![PathComponent::Move(PathPoint::new((points.0).0, (points.0).1)), PathComponent::Line(PathPoint::new((points.1).0, (points.1).1))],
+            _               => Self::outline(self.kind, points, data.num_sides, 0.0)
+        };
+        let outline = Path::from_elements(outline);
+
+        draw.new_path();
+        draw.extend(Vec::<Draw>::from(&outline));
+
+        draw.stroke_color(SELECTION_OUTLINE);
+        draw.line_width_pixels(2.0);
+        draw.stroke();
+
+        draw.stroke_color(SELECTION_HIGHLIGHT);
+        draw.line_width_pixels(0.5);
+        draw.stroke();
+
+        draw
+    }
+
+    ///
+    /// Generates the edits that add a shape to the current frame
+    ///
+    fn create_shape<Anim: 'static+Animation>(&self, points: ((f32, f32), (f32, f32)), data: &ShapeData, model: &FloModel<Anim>) -> Vec<ToolAction<ShapeData>> {
+        let when        = model.timeline().current_time.get();
+        let layer       = model.timeline().selected_layer.get();
+
+        if let Some(layer) = layer {
+            let mut edits       = vec![];
+            let mut new_ids     = vec![];
+
+            // Shapes are drawn on a new keyframe if there's no keyframe at the current time and new keyframes are created when drawing
+            let add_keyframe    = if Self::need_new_keyframe(model, layer, when) {
+                vec![ToolAction::Edit(AnimationEdit::Layer(layer, LayerEdit::AddKeyFrame(when)))]
+            } else {
+                vec![]
+            };
+
+            // The shape is made up of a path for the fill and a path for the outline
+            for (path, properties) in Self::shape_paths(self.kind, points, data) {
+                let element_id = model.motion().assign_element_id();
+
+                edits.push(PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw));
+                edits.push(PathEdit::BrushProperties(ElementId::Unassigned, properties));
+                edits.push(PathEdit::CreatePath(element_id, Arc::new(path)));
+
+                new_ids.push(element_id);
+            }
+
+            // Add the paths to the layer and select them so they can be adjusted straight away
+            add_keyframe.into_iter()
+                .chain(edits.into_iter()
+                    .map(move |action| LayerEdit::Path(when, action))
+                    .map(move |action| AnimationEdit::Layer(layer, action))
+                    .map(|action| ToolAction::Edit(action)))
+                .chain(vec![
+                    ToolAction::Overlay(OverlayAction::Clear),
+                    ToolAction::ClearSelection
+                ])
+                .chain(new_ids.into_iter().map(|element_id| ToolAction::Select(element_id)))
+                .chain(vec![ToolAction::InvalidateFrame])
+                .collect()
+        } else {
+            vec![ToolAction::Overlay(OverlayAction::Clear)]
+        }
+    }
+
+    ///
+    /// True if a keyframe should be added to a layer before drawing a shape on it at the specified time
+    ///
+    fn need_new_keyframe<Anim: 'static+Animation>(model: &FloModel<Anim>, layer_id: u64, when: Duration) -> bool {
+        if !model.frame().create_keyframe_on_draw.get() {
+            return false;
+        }
+
+        // Look for a keyframe around the current time
+        let one_ms          = Duration::from_millis(1);
+        let earliest_time   = if when > one_ms { when - one_ms } else { Duration::from_millis(0) };
+        let latest_time     = when + one_ms;
+
+        model.get_layer_with_id(layer_id)
+            .map(|layer| layer.get_key_frames_during_time(earliest_time..latest_time).count() == 0)
+            .unwrap_or(false)
+    }
+
+    ///
+    /// Generates the tool actions for a painting action
+    ///
+    fn paint<Anim: 'static+Animation>(&self, painting: Painting, data: &ShapeData, model: &FloModel<Anim>) -> Vec<ToolAction<ShapeData>> {
+        match (data.drag_start.get(), painting.action) {
+            (_, PaintAction::Start) => {
+                // Start dragging out a new shape
+                data.drag_start.set(Some(painting.location));
+                vec![]
+            },
+
+            (Some(start), PaintAction::Continue) => {
+                // Preview the shape as the user drags
+                let points = Self::drag_points(self.kind, start, painting.location, &painting.modifiers);
+                vec![ToolAction::Overlay(OverlayAction::Draw(self.draw_preview(points, data)))]
+            },
+
+            (Some(start), PaintAction::Finish) => {
+                // Create the shape when the user releases the mouse
+                let points = Self::drag_points(self.kind, start, painting.location, &painting.modifiers);
+                data.drag_start.set(None);
+
+                if Self::is_large_enough(self.kind, points) {
+                    self.create_shape(points, data, model)
+                } else {
+                    vec![ToolAction::Overlay(OverlayAction::Clear)]
+                }
+            },
+
+            (_, PaintAction::Finish) |
+            (_, PaintAction::Cancel) => {
+                // Abandon the shape
+                data.drag_start.set(None);
+                vec![ToolAction::Overlay(OverlayAction::Clear)]
+            },
+
+            _ => vec![]
+        }
+    }
+}
+
+impl<Anim: 'static+Animation> Tool<Anim> for Shape {
+    type ToolData   = ShapeData;
+    type Model      = ShapeModel;
+
+    fn tool_name(&self) -> String { self.kind.name().to_string() }
+
+    fn image_name(&self) -> String { self.kind.image_name().to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> ShapeModel {
+        ShapeModel::new()
+    }
+
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &ShapeModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(ShapeMenuController::new(self.kind, tool_model)))
+    }
+
+    fn actions_for_model(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &ShapeModel) -> Box<dyn Stream<Item=ToolAction<ShapeData>, Error=()>+Send> {
+        // The drag is tracked in a binding shared by every version of the tool data
+        let drag_start      = bind(None);
+
+        // Compute the shape settings from the model
+        let fill_color      = tool_model.fill_color.clone();
+        let stroke_color    = tool_model.stroke_color.clone();
+        let opacity         = tool_model.opacity.clone();
+        let stroke_width    = tool_model.stroke_width.clone();
+        let shape_style     = tool_model.shape_style.clone();
+        let num_sides       = tool_model.num_sides.clone();
+
+        let settings        = computed(move || (fill_color.get(), stroke_color.get(), opacity.get(), stroke_width.get(), shape_style.get(), num_sides.get()));
+
+        // Update the tool data whenever the settings change
+        Box::new(follow(settings)
+            .map(move |(fill_color, stroke_color, opacity, stroke_width, shape_style, num_sides)| {
+                ToolAction::Data(ShapeData {
+                    style:              shape_style,
+                    fill_properties:    BrushProperties { size: 1.0, opacity: opacity, color: fill_color, gradient: None },
+                    stroke_properties:  BrushProperties { size: stroke_width, opacity: opacity, color: stroke_color, gradient: None },
+                    stroke_width:       stroke_width,
+                    num_sides:          num_sides,
+                    drag_start:         drag_start.clone()
+                })
+            }))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<ShapeData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<ShapeData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<ShapeData>>> {
+        let mut data    = data;
+        let mut actions = vec![];
+        let input       = ToolInput::last_paint_actions_only(input);
+
+        // Process the input
+        for input in input {
+            match input {
+                ToolInput::Data(new_data) => {
+                    // Keep tracking the data as it changes
+                    data = Some(new_data);
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let Some(data) = data.as_ref() {
+                        actions.extend(self.paint(painting, &**data, &*flo_model));
+                    }
+                },
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate flo_anim_sqlite;
+
+    use super::*;
+    use self::flo_anim_sqlite::*;
+    use futures::executor;
+
+    fn test_data(style: ShapeStyle, stroke_width: f32) -> ShapeData {
+        ShapeData {
+            style:              style,
+            fill_properties:    BrushProperties { size: 1.0, opacity: 1.0, color: Color::Rgba(1.0, 0.0, 0.0, 1.0), gradient: None },
+            stroke_properties:  BrushProperties { size: stroke_width, opacity: 1.0, color: Color::Rgba(0.0, 0.0, 0.0, 1.0), gradient: None },
+            stroke_width:       stroke_width,
+            num_sides:          6,
+            drag_start:         bind(None)
+        }
+    }
+
+    #[test]
+    fn shift_constrains_rectangle_to_square() {
+        let modifiers   = PaintModifiers { shift: true, ..PaintModifiers::none() };
+        let points      = Shape::drag_points(ShapeKind::Rectangle, (10.0, 10.0), (50.0, -20.0), &modifiers);
+
+        assert!(points == ((10.0, 10.0), (50.0, -30.0)));
+    }
+
+    #[test]
+    fn alt_draws_from_center() {
+        let modifiers   = PaintModifiers { alt: true, ..PaintModifiers::none() };
+        let points      = Shape::drag_points(ShapeKind::Ellipse, (10.0, 10.0), (30.0, 20.0), &modifiers);
+
+        assert!(points == ((-10.0, 0.0), (30.0, 20.0)));
+    }
+
+    #[test]
+    fn shift_snaps_line_to_45_degrees() {
+        let modifiers   = PaintModifiers { shift: true, ..PaintModifiers::none() };
+        let (p1, p2)    = Shape::drag_points(ShapeKind::Line, (0.0, 0.0), (100.0, 10.0), &modifiers);
+
+        assert!(p1 == (0.0, 0.0));
+        assert!((p2.1).abs() < 0.001);
+        assert!((p2.0 - (100.0f32*100.0 + 10.0*10.0).sqrt()).abs() < 0.001);
+    }
+
+    #[test]
+    fn polygon_has_one_point_per_side() {
+        let outline = Shape::outline(ShapeKind::Polygon, ((0.0, 0.0), (100.0, 100.0)), 6, 0.0);
+
+        assert!(outline.len() == 7);
+        assert!(outline[6] == PathComponent::Close);
+
+        if let PathComponent::Move(first_point) = outline[0] {
+            assert!((first_point.x() - 50.0).abs() < 0.001);
+            assert!((first_point.y() - 100.0).abs() < 0.001);
+        } else {
+            assert!(false);
+        }
+    }
+
+    #[test]
+    fn stroked_rectangle_has_hole() {
+        let paths = Shape::shape_paths(ShapeKind::Rectangle, ((0.0, 0.0), (100.0, 50.0)), &test_data(ShapeStyle::Stroke, 10.0));
+
+        assert!(paths.len() == 1);
+
+        let (stroke, _) = &paths[0];
+        assert!(stroke.len() == 10);
+        assert!(stroke[0] == PathComponent::Move(PathPoint::new(-5.0, -5.0)));
+        assert!(stroke[5] == PathComponent::Move(PathPoint::new(5.0, 45.0)));
+        assert!(stroke[6] == PathComponent::Line(PathPoint::new(95.0, 45.0)));
+    }
+
+    #[test]
+    fn thick_stroke_fills_small_rectangle() {
+        let paths = Shape::shape_paths(ShapeKind::Rectangle, ((0.0, 0.0), (4.0, 4.0)), &test_data(ShapeStyle::Stroke, 10.0));
+
+        assert!(paths.len() == 1);
+        assert!(paths[0].0.len() == 5);
+    }
+
+    #[test]
+    fn fill_and_stroke_creates_two_paths() {
+        let paths = Shape::shape_paths(ShapeKind::Ellipse, ((0.0, 0.0), (100.0, 50.0)), &test_data(ShapeStyle::FillAndStroke, 2.0));
+
+        assert!(paths.len() == 2);
+        assert!(paths[0].1.color == Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        assert!(paths[1].1.color == Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn lines_are_always_stroked() {
+        let paths = Shape::shape_paths(ShapeKind::Line, ((0.0, 0.0), (100.0, 0.0)), &test_data(ShapeStyle::Fill, 4.0));
+
+        assert!(paths.len() == 1);
+        assert!(paths[0].0[0] == PathComponent::Move(PathPoint::new(0.0, 2.0)));
+    }
+
+    fn model_with_keyframe() -> FloModel<SqliteAnimation> {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(1),
+                AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+            ]).unwrap();
+        }
+        model.timeline().selected_layer.set(Some(1));
+
+        model
+    }
+
+    fn added_keyframes(actions: &Vec<ToolAction<ShapeData>>) -> Vec<Duration> {
+        actions.iter()
+            .filter_map(|action| match action {
+                ToolAction::Edit(AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(when)))  => Some(*when),
+                _                                                                       => None
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shape_is_drawn_on_new_keyframe() {
+        let model   = model_with_keyframe();
+        let data    = test_data(ShapeStyle::Fill, 2.0);
+
+        model.timeline().current_time.set(Duration::from_millis(1000));
+        let actions = Shape::rectangle().create_shape(((10.0, 10.0), (50.0, 30.0)), &data, &model);
+
+        assert!(added_keyframes(&actions) == vec![Duration::from_millis(1000)]);
+        assert!(match &actions[0] { ToolAction::Edit(AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(_))) => true, _ => false });
+    }
+
+    #[test]
+    fn shape_is_drawn_on_existing_keyframe() {
+        let model   = model_with_keyframe();
+        let data    = test_data(ShapeStyle::Fill, 2.0);
+
+        let actions = Shape::rectangle().create_shape(((10.0, 10.0), (50.0, 30.0)), &data, &model);
+        assert!(added_keyframes(&actions).len() == 0);
+
+        // No keyframe is added when new keyframes aren't created when drawing
+        model.frame().create_keyframe_on_draw.set(false);
+        model.timeline().current_time.set(Duration::from_millis(1000));
+
+        let actions = Shape::rectangle().create_shape(((10.0, 10.0), (50.0, 30.0)), &data, &model);
+        assert!(added_keyframes(&actions).len() == 0);
+    }
+}
@@ -0,0 +1,174 @@
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+
+use std::iter;
+use std::sync::*;
+
+///
+/// Model for the text tool
+///
+pub struct TextModel {
+    /// The text that will be added when the user clicks on the canvas
+    pub text: Binding<String>,
+
+    /// The colour of the next text element that will be added
+    pub color: Binding<Color>,
+
+    /// The font size of the next text element that will be added
+    pub font_size: Binding<f32>,
+
+    /// How the next text element will be aligned relative to where the user clicks
+    pub alignment: Binding<TextAlignment>
+}
+
+///
+/// Data passed through to the text tool
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextData {
+    /// The text to add
+    pub text: String,
+
+    /// The style to use for the text
+    pub style: TextStyle
+}
+
+///
+/// A tool for adding text elements to the canvas (eg, for titles or speech bubbles)
+///
+pub struct Text {
+
+}
+
+impl TextModel {
+    ///
+    /// Creates the default text model
+    ///
+    pub fn new() -> TextModel {
+        let default_style = TextStyle::new();
+
+        TextModel {
+            text:       bind("Text".to_string()),
+            color:      bind(default_style.color),
+            font_size:  bind(default_style.font_size),
+            alignment:  bind(default_style.alignment)
+        }
+    }
+}
+
+impl Text {
+    ///
+    /// Creates a new text tool
+    ///
+    pub fn new() -> Text {
+        Text {
+        }
+    }
+
+    ///
+    /// Generates the actions to add a text element at the specified position
+    ///
+    pub fn add_text<Anim: 'static+Animation>(&self, model: Arc<FloModel<Anim>>, position: (f32, f32), data: &TextData) -> impl Iterator<Item=ToolAction<TextData>> {
+        // Get the current frame information
+        let when            = model.timeline().current_time.get();
+        let layer           = model.timeline().selected_layer.get();
+
+        match layer {
+            Some(layer) if data.text.len() > 0 => {
+                let create_text = TextEdit::CreateText(ElementId::Unassigned, data.style.clone(), position, data.text.clone());
+
+                Either::Left(vec![
+                    ToolAction::Edit(AnimationEdit::Layer(layer, LayerEdit::Text(when, create_text))),
+                    ToolAction::InvalidateFrame
+                ].into_iter())
+            },
+
+            _ => Either::Right(iter::empty())
+        }
+    }
+}
+
+impl<Anim: 'static+Animation> Tool<Anim> for Text {
+    type ToolData   = TextData;
+    type Model      = TextModel;
+
+    fn tool_name(&self) -> String { "Text".to_string() }
+
+    fn image_name(&self) -> String { "text".to_string() }
+
+    fn create_model(&self, _flo_model: Arc<FloModel<Anim>>) -> TextModel {
+        TextModel::new()
+    }
+
+    fn create_menu_controller(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &TextModel) -> Option<Arc<dyn Controller>> {
+        let text        = tool_model.text.clone();
+        let color       = tool_model.color.clone();
+        let font_size   = tool_model.font_size.clone();
+        let alignment   = tool_model.alignment.clone();
+
+        Some(Arc::new(TextMenuController::new(text, color, font_size, alignment)))
+    }
+
+    fn actions_for_model(&self, _flo_model: Arc<FloModel<Anim>>, tool_model: &TextModel) -> Box<dyn Stream<Item=ToolAction<TextData>, Error=()>+Send> {
+        // Compute the text data from the model
+        let text        = tool_model.text.clone();
+        let color       = tool_model.color.clone();
+        let font_size   = tool_model.font_size.clone();
+        let alignment   = tool_model.alignment.clone();
+
+        let text_data   = computed(move || {
+            let mut style   = TextStyle::new();
+            style.color     = color.get();
+            style.font_size = font_size.get();
+            style.alignment = alignment.get();
+
+            TextData {
+                text:   text.get(),
+                style:  style
+            }
+        });
+
+        // Turn the computed values into a stream and update the data whenever the values change
+        Box::new(follow(text_data).map(|text_data| ToolAction::Data(text_data)))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<TextData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<TextData>>>) -> Box<dyn Iterator<Item=ToolAction<TextData>>> {
+        Box::new(
+            input.flat_map(move |action| {
+                let actions : Box<dyn Iterator<Item=ToolAction<TextData>>> =
+                    match (action, data.as_ref()) {
+                        (ToolInput::Paint(painting), Some(data)) => {
+                            match painting.action {
+                                PaintAction::Finish => {
+                                    // Add the text where the user releases the mouse
+                                    Box::new(self.add_text(Arc::clone(&flo_model), painting.location, &*data))
+                                },
+
+                                _ => {
+                                    // Nothing to do for other paint actions
+                                    Box::new(vec![].into_iter())
+                                }
+                            }
+                        },
+
+                        _ => {
+                            // No action for other kinds of input input
+                            Box::new(vec![].into_iter())
+                        }
+                    };
+
+                actions
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+        )
+    }
+}
@@ -9,26 +9,40 @@ use std::sync::*;
 /// The selection toolset
 ///
 pub struct SelectionTools<Anim: 'static+Animation> {
-    select: Arc<FloTool<Anim>>,
-    adjust: Arc<FloTool<Anim>>,
-    pan:    Arc<FloTool<Anim>>
+    select:     Arc<FloTool<Anim>>,
+    adjust:     Arc<FloTool<Anim>>,
+    transform:  Arc<FloTool<Anim>>,
+    pan:        Arc<FloTool<Anim>>
 }
 
 ///
 /// The paint toolset
 ///
 pub struct PaintTools<Anim: 'static+Animation> {
-    ink:        Arc<FloTool<Anim>>,
-    eraser:     Arc<FloTool<Anim>>,
-    flood_fill: Arc<FloTool<Anim>>
+    ink:            Arc<FloTool<Anim>>,
+    calligraphy:    Arc<FloTool<Anim>>,
+    eraser:         Arc<FloTool<Anim>>,
+    flood_fill:     Arc<FloTool<Anim>>,
+    text:           Arc<FloTool<Anim>>
+}
+
+///
+/// The shape toolset
+///
+pub struct ShapeTools<Anim: 'static+Animation> {
+    rectangle:  Arc<FloTool<Anim>>,
+    ellipse:    Arc<FloTool<Anim>>,
+    polygon:    Arc<FloTool<Anim>>,
+    line:       Arc<FloTool<Anim>>
 }
 
 impl<Anim: EditableAnimation+Animation> SelectionTools<Anim> {
     pub fn new() -> SelectionTools<Anim> {
         SelectionTools {
-            select: Select::new().to_flo_tool(),
-            adjust: Adjust::new().to_flo_tool(),
-            pan:    Pan::new().to_flo_tool()
+            select:     Select::new().to_flo_tool(),
+            adjust:     Adjust::new().to_flo_tool(),
+            transform:  Transform::new().to_flo_tool(),
+            pan:        Pan::new().to_flo_tool()
         }
     }
 }
@@ -36,9 +50,22 @@ impl<Anim: EditableAnimation+Animation> SelectionTools<Anim> {
 impl<Anim: Animation> PaintTools<Anim> {
     pub fn new() -> PaintTools<Anim> {
         PaintTools {
-            ink:        Ink::new().to_flo_tool(),
-            eraser:     Eraser::new().to_flo_tool(),
-            flood_fill: FloodFill::new().to_flo_tool()
+            ink:            Ink::new().to_flo_tool(),
+            calligraphy:    Calligraphy::new().to_flo_tool(),
+            eraser:         Eraser::new().to_flo_tool(),
+            flood_fill:     FloodFill::new().to_flo_tool(),
+            text:           Text::new().to_flo_tool()
+        }
+    }
+}
+
+impl<Anim: Animation> ShapeTools<Anim> {
+    pub fn new() -> ShapeTools<Anim> {
+        ShapeTools {
+            rectangle:  Shape::rectangle().to_flo_tool(),
+            ellipse:    Shape::ellipse().to_flo_tool(),
+            polygon:    Shape::polygon().to_flo_tool(),
+            line:       Shape::line().to_flo_tool()
         }
     }
 }
@@ -50,6 +77,7 @@ impl<Anim: Animation> ToolSet<Anim> for SelectionTools<Anim> {
         vec![
             Arc::clone(&self.select),
             Arc::clone(&self.adjust),
+            Arc::clone(&self.transform),
             Arc::clone(&self.pan)
         ]
     }
@@ -61,8 +89,23 @@ impl<Anim: Animation> ToolSet<Anim> for PaintTools<Anim> {
     fn tools(&self) -> Vec<Arc<FloTool<Anim>>> {
         vec![
             Arc::clone(&self.ink),
+            Arc::clone(&self.calligraphy),
             Arc::clone(&self.eraser),
-            Arc::clone(&self.flood_fill)
+            Arc::clone(&self.flood_fill),
+            Arc::clone(&self.text)
+        ]
+    }
+}
+
+impl<Anim: Animation> ToolSet<Anim> for ShapeTools<Anim> {
+    fn set_name(&self) -> String { "Shapes".to_string() }
+
+    fn tools(&self) -> Vec<Arc<FloTool<Anim>>> {
+        vec![
+            Arc::clone(&self.rectangle),
+            Arc::clone(&self.ellipse),
+            Arc::clone(&self.polygon),
+            Arc::clone(&self.line)
         ]
     }
 }
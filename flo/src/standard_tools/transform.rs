@@ -0,0 +1,873 @@
+use super::super::menu::*;
+use super::super::tools::*;
+use super::super::model::*;
+use super::super::style::*;
+
+use flo_ui::*;
+use flo_canvas::*;
+use flo_binding::*;
+use flo_animation::*;
+
+use futures::*;
+
+use std::f32;
+use std::sync::*;
+use std::time::Duration;
+use std::collections::HashSet;
+
+/// How close (in canvas units) the user needs to click to a handle to pick it up
+const HANDLE_DISTANCE: f32 = 8.0;
+
+/// Half the width of the square drawn for a handle
+const HANDLE_SIZE: f32 = 4.0;
+
+/// How far outside the selection bounds the skew and rotate handles are drawn
+const HANDLE_OFFSET: f32 = 16.0;
+
+/// The angle that rotations snap to when shift is held down
+const ROTATE_SNAP_ANGLE: f32 = f32::consts::PI / 12.0;
+
+///
+/// The handles that can be dragged with the transform tool
+///
+/// Sides are specified as -1, 0 or 1 for the left/bottom, centre or right/top of the selection bounds
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransformHandle {
+    /// Moves the whole selection
+    Move,
+
+    /// Scales the selection from the handle at the specified side or corner
+    Scale(i32, i32),
+
+    /// Skews the selection from the handle beside the specified edge
+    Skew(i32, i32),
+
+    /// Rotates the selection about the pivot point
+    Rotate,
+
+    /// Moves the pivot point
+    Pivot
+}
+
+///
+/// The current action being performed by the transform tool
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TransformAction {
+    /// The tool is idle
+    NoAction,
+
+    /// A handle is being dragged from one point to another, with the modifier keys that are held down
+    Drag(TransformHandle, (f32, f32), (f32, f32), PaintModifiers)
+}
+
+///
+/// Model for the transform tool
+///
+pub struct TransformModel {
+    /// True if dragging a corner handle scales the selection proportionally (holding shift inverts this)
+    pub proportional: Binding<bool>,
+
+    /// The pivot point chosen by the user, along with the selection that it was chosen for
+    pub custom_pivot: Binding<Option<(Arc<HashSet<ElementId>>, (f32, f32))>>,
+
+    /// The selected elements, as they appear in the current frame, along with their properties
+    pub selection: BindRef<Arc<Vec<(Vector, Arc<VectorProperties>)>>>,
+
+    /// The bounds of the selected elements
+    pub selection_bounds: BindRef<Rect>,
+
+    /// The point that the selection rotates about
+    pub pivot: BindRef<(f32, f32)>,
+
+    /// The angle last entered in the menu, in degrees
+    pub rotate_degrees: Binding<f32>,
+
+    /// The horizontal scale factor last entered in the menu, as a percentage
+    pub scale_width: Binding<f32>,
+
+    /// The vertical scale factor last entered in the menu, as a percentage
+    pub scale_height: Binding<f32>,
+
+    /// The skew angle last entered in the menu, in degrees
+    pub skew_degrees: Binding<f32>
+}
+
+///
+/// Data for the transform tool
+///
+#[derive(Clone)]
+pub struct TransformData {
+    /// The current state of the tool
+    state: Binding<TransformAction>,
+
+    /// The currently selected elements
+    selected_elements: Arc<HashSet<ElementId>>,
+
+    /// The bounds of the selected elements
+    bounds: Rect,
+
+    /// The point that the selection rotates about
+    pivot: (f32, f32),
+
+    /// True if corner handles scale proportionally
+    proportional: bool,
+
+    /// The pivot point chosen by the user
+    custom_pivot: Binding<Option<(Arc<HashSet<ElementId>>, (f32, f32))>>
+}
+
+///
+/// The transform tool, which scales, rotates and skews the selection
+///
+pub struct Transform { }
+
+impl TransformModel {
+    ///
+    /// Creates a new transform model tracking the selection in the specified animation
+    ///
+    pub fn new<Anim: 'static+Animation>(flo_model: &FloModel<Anim>) -> TransformModel {
+        let custom_pivot: Binding<Option<(Arc<HashSet<ElementId>>, (f32, f32))>> = bind(None);
+        let selection           = Transform::visible_selection(flo_model);
+
+        // The bounds are worked out from the selected elements as they're rendered
+        let bounds_selection    = selection.clone();
+        let selection_bounds    = computed(move || {
+            bounds_selection.get().iter()
+                .flat_map(|(element, properties)| element.to_path(properties).unwrap_or_else(|| vec![]))
+                .fold(Rect::empty(), |bounds, path| bounds.union(path.bounding_box()))
+                .normalize()
+        });
+        let selection_bounds    = BindRef::new(&selection_bounds);
+
+        // The pivot is the centre of the selection unless the user has moved it
+        let pivot_selected      = flo_model.selection().selected_elements.clone();
+        let pivot_custom        = custom_pivot.clone();
+        let pivot_bounds        = selection_bounds.clone();
+        let pivot               = computed(move || {
+            let selected    = pivot_selected.get();
+            let bounds      = pivot_bounds.get();
+
+            match pivot_custom.get() {
+                Some((pivot_selection, pivot))  => if *pivot_selection == *selected { pivot } else { Transform::center(&bounds) },
+                None                            => Transform::center(&bounds)
+            }
+        });
+
+        TransformModel {
+            proportional:       bind(true),
+            custom_pivot:       custom_pivot,
+            selection:          selection,
+            selection_bounds:   selection_bounds,
+            pivot:              BindRef::new(&pivot),
+            rotate_degrees:     bind(0.0),
+            scale_width:        bind(100.0),
+            scale_height:       bind(100.0),
+            skew_degrees:       bind(0.0)
+        }
+    }
+}
+
+impl Transform {
+    ///
+    /// Creates a new transform tool
+    ///
+    pub fn new() -> Transform {
+        Transform { }
+    }
+
+    ///
+    /// Returns the centre of a rectangle
+    ///
+    fn center(bounds: &Rect) -> (f32, f32) {
+        ((bounds.x1+bounds.x2)/2.0, (bounds.y1+bounds.y2)/2.0)
+    }
+
+    ///
+    /// Returns the point on the specified side of a rectangle
+    ///
+    fn side_point(bounds: &Rect, (side_x, side_y): (i32, i32)) -> (f32, f32) {
+        let (center_x, center_y)    = Self::center(bounds);
+        let (half_w, half_h)        = ((bounds.x2-bounds.x1)/2.0, (bounds.y2-bounds.y1)/2.0);
+
+        (center_x + (side_x as f32)*half_w, center_y + (side_y as f32)*half_h)
+    }
+
+    ///
+    /// Returns a transformation that performs the specified transformation about a point instead of the origin
+    ///
+    pub fn about_point((x, y): (f32, f32), transform: Transform2D) -> Transform2D {
+        Transform2D::translate(x, y) * transform * Transform2D::translate(-x, -y)
+    }
+
+    ///
+    /// Creates a binding for the selected elements as they appear in the current frame (ie, with any motions applied)
+    ///
+    fn visible_selection<Anim: 'static+Animation>(flo_model: &FloModel<Anim>) -> BindRef<Arc<Vec<(Vector, Arc<VectorProperties>)>>> {
+        let frame               = flo_model.frame().frame.clone();
+        let selection_in_order  = flo_model.selection().selection_in_order.clone();
+
+        BindRef::new(&computed(move || {
+            let selection = selection_in_order.get();
+
+            if let Some(frame) = frame.get() {
+                let when = frame.time_index();
+
+                Arc::new(selection.iter()
+                    .filter_map(|element_id| frame.element_with_id(*element_id))
+                    .map(|element| {
+                        let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+                        let element     = (properties.transform_vector)(element, when);
+
+                        (element, properties)
+                    })
+                    .collect())
+            } else {
+                Arc::new(vec![])
+            }
+        }))
+    }
+
+    ///
+    /// Applies a transformation to the control points of an element
+    ///
+    fn transform_element(element: &Vector, transform: &Transform2D) -> Vector {
+        let new_control_points = element.control_points()
+            .into_iter()
+            .map(|cp| cp.position())
+            .map(|(x, y)| transform.transform_point(x, y))
+            .collect();
+
+        element.with_adjusted_control_points(new_control_points)
+    }
+
+    ///
+    /// Returns the edits that apply a transformation to the current selection
+    ///
+    /// The edits should be sent together so that the whole transformation is a single undoable action. Groups have no
+    /// control points of their own, so the elements inside them are transformed instead.
+    ///
+    pub fn edits_for_transform<Anim: 'static+Animation>(flo_model: &FloModel<Anim>, transform: &Transform2D) -> Vec<AnimationEdit> {
+        let frame               = flo_model.frame().frame.get();
+        let selection_in_order  = flo_model.selection().selection_in_order.get();
+
+        let frame = match frame {
+            Some(frame) => frame,
+            None        => { return vec![]; }
+        };
+        let when = frame.time_index();
+
+        let mut edits = vec![];
+
+        for element in selection_in_order.iter().filter_map(|element_id| frame.element_with_id(*element_id)) {
+            // Transform the element as it appears in the frame
+            let properties  = frame.apply_properties_for_element(&element, Arc::new(VectorProperties::default()));
+            let visible     = (properties.transform_vector)(element, when);
+
+            Self::add_edits_for_element(flo_model, &visible, transform, when, &vec![], &mut edits);
+        }
+
+        edits
+    }
+
+    ///
+    /// Adds the edits that transform an element (as it appears in the frame) to a list of edits
+    ///
+    /// The motions that apply to the groups containing the element are passed in, so that they can be reversed along
+    /// with the element's own motions. The elements in groups are transformed individually.
+    ///
+    fn add_edits_for_element<Anim: 'static+Animation>(flo_model: &FloModel<Anim>, visible: &Vector, transform: &Transform2D, when: Duration, parent_motions: &Vec<Motion>, edits: &mut Vec<AnimationEdit>) {
+        let element_id  = visible.id();
+
+        // The element's own motions are applied before the motions of the groups that contain it
+        let mut motions = flo_model.motion().get_motions_for_element(element_id)
+            .into_iter()
+            .filter_map(|id| flo_model.get_motion(id))
+            .collect::<Vec<_>>();
+        motions.extend(parent_motions.iter().cloned());
+
+        match visible {
+            Vector::Group(group)    => {
+                for grouped_element in group.elements() {
+                    Self::add_edits_for_element(flo_model, grouped_element, transform, when, &motions, edits);
+                }
+            },
+
+            element                 => {
+                if element.control_points().is_empty() {
+                    return;
+                }
+
+                let mut edited = Self::transform_element(element, transform);
+
+                // Reverse the motions to get the element as it should be stored
+                for motion in motions.into_iter().rev() {
+                    edited = edited.motion_transform(&motion.reverse(), when);
+                }
+
+                // Paths are replaced entirely, other elements have their control points moved
+                let edit = match edited {
+                    Vector::Path(ref path)  => ElementEdit::SetPath(Arc::new(path.path().elements().collect())),
+                    other                   => ElementEdit::SetControlPoints(other.control_points().into_iter().map(|cp| cp.position()).collect())
+                };
+
+                edits.push(AnimationEdit::Element(vec![element_id], edit));
+            }
+        }
+    }
+
+    ///
+    /// Works out the transformation for dragging a handle from one point to another
+    ///
+    /// Scale and skew handles work from the opposite side of the selection, or from the pivot if alt is held down.
+    /// Holding shift snaps rotations to 15 degree steps and toggles proportional scaling for the corner handles.
+    ///
+    fn drag_transform(handle: TransformHandle, from: (f32, f32), to: (f32, f32), modifiers: &PaintModifiers, bounds: &Rect, pivot: (f32, f32), proportional: bool) -> Transform2D {
+        let (dx, dy) = (to.0-from.0, to.1-from.1);
+
+        match handle {
+            TransformHandle::Move   => Transform2D::translate(dx, dy),
+            TransformHandle::Pivot  => Transform2D::identity(),
+
+            TransformHandle::Rotate => {
+                let start_angle = (from.1-pivot.1).atan2(from.0-pivot.0);
+                let end_angle   = (to.1-pivot.1).atan2(to.0-pivot.0);
+                let mut angle   = end_angle - start_angle;
+
+                if modifiers.shift {
+                    angle = (angle / ROTATE_SNAP_ANGLE).round() * ROTATE_SNAP_ANGLE;
+                }
+
+                Self::about_point(pivot, Transform2D::rotate(angle))
+            },
+
+            TransformHandle::Scale(side_x, side_y) => {
+                let origin          = if modifiers.alt { pivot } else { Self::side_point(bounds, (-side_x, -side_y)) };
+                let handle_pos      = Self::side_point(bounds, (side_x, side_y));
+                let (hx, hy)        = (handle_pos.0-origin.0, handle_pos.1-origin.1);
+                let (mx, my)        = (hx+dx, hy+dy);
+
+                let (scale_x, scale_y) = if side_x != 0 && side_y != 0 && proportional != modifiers.shift {
+                    // Project the new position onto the diagonal through the handle to scale by the same amount in both directions
+                    let length_sq   = hx*hx + hy*hy;
+                    let scale       = if length_sq > 0.0 { (mx*hx + my*hy)/length_sq } else { 1.0 };
+
+                    (scale, scale)
+                } else {
+                    let scale_x     = if side_x != 0 && hx.abs() > f32::EPSILON { mx/hx } else { 1.0 };
+                    let scale_y     = if side_y != 0 && hy.abs() > f32::EPSILON { my/hy } else { 1.0 };
+
+                    (scale_x, scale_y)
+                };
+
+                Self::about_point(origin, Transform2D::scale(scale_x, scale_y))
+            },
+
+            TransformHandle::Skew(side_x, side_y) => {
+                let origin          = if modifiers.alt { pivot } else { Self::side_point(bounds, (-side_x, -side_y)) };
+                let handle_pos      = Self::side_point(bounds, (side_x, side_y));
+
+                if side_y != 0 {
+                    // Handles above and below the selection skew horizontally
+                    let height  = handle_pos.1-origin.1;
+                    let skew_x  = if height.abs() > f32::EPSILON { dx/height } else { 0.0 };
+
+                    Self::about_point(origin, Transform2D::skew(skew_x, 0.0))
+                } else {
+                    // Handles to the left and right of the selection skew vertically
+                    let width   = handle_pos.0-origin.0;
+                    let skew_y  = if width.abs() > f32::EPSILON { dy/width } else { 0.0 };
+
+                    Self::about_point(origin, Transform2D::skew(0.0, skew_y))
+                }
+            }
+        }
+    }
+
+    ///
+    /// Returns the handles for a set of selection bounds, along with where they are
+    ///
+    fn handles(bounds: &Rect, pivot: (f32, f32)) -> Vec<(TransformHandle, (f32, f32))> {
+        let mut handles = vec![];
+
+        // The pivot is on top of everything else, so it comes first
+        handles.push((TransformHandle::Pivot, pivot));
+
+        // The rotate handle sits diagonally out from the top-right corner
+        let (right, top) = Self::side_point(bounds, (1, 1));
+        handles.push((TransformHandle::Rotate, (right + HANDLE_OFFSET, top + HANDLE_OFFSET)));
+
+        // The skew handles are just outside the middle of each edge
+        for &(side_x, side_y) in [(0, 1), (0, -1), (1, 0), (-1, 0)].iter() {
+            let (x, y) = Self::side_point(bounds, (side_x, side_y));
+            handles.push((TransformHandle::Skew(side_x, side_y), (x + (side_x as f32)*HANDLE_OFFSET, y + (side_y as f32)*HANDLE_OFFSET)));
+        }
+
+        // The scale handles are on the corners and edges of the bounds
+        for &(side_x, side_y) in [(-1, -1), (1, -1), (1, 1), (-1, 1), (0, 1), (0, -1), (1, 0), (-1, 0)].iter() {
+            handles.push((TransformHandle::Scale(side_x, side_y), Self::side_point(bounds, (side_x, side_y))));
+        }
+
+        handles
+    }
+
+    ///
+    /// Finds the handle at the specified point
+    ///
+    fn handle_at_point(bounds: &Rect, pivot: (f32, f32), point: (f32, f32)) -> Option<TransformHandle> {
+        let nearest = Self::handles(bounds, pivot).into_iter()
+            .map(|(handle, (x, y))| (handle, ((x-point.0)*(x-point.0) + (y-point.1)*(y-point.1)).sqrt()))
+            .filter(|(_handle, distance)| *distance < HANDLE_DISTANCE)
+            .fold(None, |nearest: Option<(TransformHandle, f32)>, (handle, distance)| {
+                match nearest {
+                    Some((_, nearest_distance)) if nearest_distance <= distance => nearest,
+                    _                                                           => Some((handle, distance))
+                }
+            });
+
+        match nearest {
+            Some((handle, _))                               => Some(handle),
+            None if bounds.contains(point.0, point.1)       => Some(TransformHandle::Move),
+            None                                            => None
+        }
+    }
+
+    ///
+    /// Draws the selection bounds, the handles and (while dragging) a preview of the transformed selection
+    ///
+    fn draw_overlay(state: TransformAction, bounds: Rect, pivot: (f32, f32), proportional: bool, selection: &Vec<(Vector, Arc<VectorProperties>)>) -> Vec<Draw> {
+        let mut draw = vec![];
+
+        // Clear the preview and handle layers
+        draw.layer(1);
+        draw.clear_layer();
+        draw.layer(0);
+        draw.clear_layer();
+
+        if bounds.is_zero_size() {
+            return draw;
+        }
+
+        // Work out how the selection is being transformed
+        let (transform, pivot) = match state {
+            TransformAction::Drag(TransformHandle::Pivot, _, to, _)     => (Transform2D::identity(), to),
+            TransformAction::Drag(handle, from, to, modifiers)          => (Self::drag_transform(handle, from, to, &modifiers, &bounds, pivot, proportional), pivot),
+            TransformAction::NoAction                                   => (Transform2D::identity(), pivot)
+        };
+
+        // Preview the transformed elements while the user is dragging
+        if transform != Transform2D::identity() {
+            draw.layer(1);
+            draw.new_path();
+
+            for (element, properties) in selection.iter() {
+                let transformed = Self::transform_element(element, &transform);
+
+                if let Some(paths) = transformed.to_path(properties) {
+                    for path in paths {
+                        draw.extend(path.to_drawing());
+                    }
+                }
+            }
+
+            draw.fill_color(SELECTION_FILL);
+            draw.fill();
+
+            draw.line_width_pixels(2.0);
+            draw.stroke_color(SELECTION_OUTLINE);
+            draw.stroke();
+
+            draw.line_width_pixels(0.5);
+            draw.stroke_color(SELECTION_HIGHLIGHT);
+            draw.stroke();
+
+            draw.layer(0);
+        }
+
+        // Outline the transformed bounds
+        let corners = [(-1, -1), (1, -1), (1, 1), (-1, 1)].iter()
+            .map(|side| Self::side_point(&bounds, *side))
+            .map(|(x, y)| transform.transform_point(x, y))
+            .collect::<Vec<_>>();
+
+        draw.new_path();
+        draw.move_to(corners[0].0, corners[0].1);
+        for &(x, y) in corners.iter().skip(1) {
+            draw.line_to(x, y);
+        }
+        draw.close_path();
+
+        draw.line_width_pixels(2.0);
+        draw.stroke_color(SELECTION_OUTLINE);
+        draw.stroke();
+
+        draw.line_width_pixels(0.5);
+        draw.stroke_color(SELECTION_BBOX);
+        draw.stroke();
+
+        // Draw the handles where they'll end up after the transformation
+        draw.line_width_pixels(1.0);
+        draw.stroke_color(SELECTION_OUTLINE);
+
+        for (handle, (x, y)) in Self::handles(&bounds, pivot) {
+            let (x, y) = if handle == TransformHandle::Pivot { (x, y) } else { transform.transform_point(x, y) };
+
+            draw.new_path();
+
+            match handle {
+                TransformHandle::Scale(_, _)    => {
+                    draw.rect(x-HANDLE_SIZE, y-HANDLE_SIZE, x+HANDLE_SIZE, y+HANDLE_SIZE);
+                    draw.fill_color(CP_BEZIER_CP);
+                },
+
+                TransformHandle::Skew(_, _)     => {
+                    draw.move_to(x, y-HANDLE_SIZE);
+                    draw.line_to(x+HANDLE_SIZE, y);
+                    draw.line_to(x, y+HANDLE_SIZE);
+                    draw.line_to(x-HANDLE_SIZE, y);
+                    draw.close_path();
+                    draw.fill_color(CP_BEZIER_CP);
+                },
+
+                TransformHandle::Rotate         => {
+                    draw.circle(x, y, HANDLE_SIZE);
+                    draw.fill_color(CP_BEZIER);
+                },
+
+                TransformHandle::Pivot          => {
+                    draw.circle(x, y, HANDLE_SIZE);
+                    draw.fill_color(SELECTION_HIGHLIGHT);
+                },
+
+                TransformHandle::Move           => { }
+            }
+
+            draw.fill();
+            draw.stroke();
+        }
+
+        draw
+    }
+
+    ///
+    /// Generates the tool actions for a painting action
+    ///
+    fn paint<Anim: 'static+Animation>(&self, painting: Painting, data: &TransformData, model: &FloModel<Anim>) -> Vec<ToolAction<TransformData>> {
+        match (data.state.get(), painting.action) {
+            (_, PaintAction::Start) => {
+                let handle = if data.bounds.is_zero_size() { None } else { Self::handle_at_point(&data.bounds, data.pivot, painting.location) };
+
+                if let Some(handle) = handle {
+                    // Start dragging the handle
+                    data.state.set(TransformAction::Drag(handle, painting.location, painting.location, painting.modifiers));
+                    vec![]
+                } else {
+                    // Clicking away from the selection selects the element under the pointer instead
+                    data.state.set(TransformAction::NoAction);
+
+                    // Search for an element to select, preferring elements where the point is inside the path
+                    let mut selected_element = None;
+                    for elem in model.frame().elements_at_point(painting.location) {
+                        match elem {
+                            ElementMatch::InsidePath(element) => {
+                                selected_element = Some(element);
+                                break;
+                            }
+
+                            ElementMatch::OnlyInBounds(element) => {
+                                if selected_element.is_none() { selected_element = Some(element); }
+                            }
+                        }
+                    }
+
+                    Some(ToolAction::ClearSelection).into_iter()
+                        .chain(selected_element.map(|element| ToolAction::Select(element)))
+                        .collect()
+                }
+            },
+
+            (TransformAction::Drag(handle, from, _to, _modifiers), PaintAction::Continue) => {
+                // Update the drag (the overlay will redraw to show the new transformation)
+                data.state.set(TransformAction::Drag(handle, from, painting.location, painting.modifiers));
+                vec![]
+            },
+
+            (TransformAction::Drag(TransformHandle::Pivot, _from, _to, _modifiers), PaintAction::Finish) => {
+                // Move the pivot point for the current selection
+                data.custom_pivot.set(Some((Arc::clone(&data.selected_elements), painting.location)));
+                data.state.set(TransformAction::NoAction);
+                vec![]
+            },
+
+            (TransformAction::Drag(handle, from, _to, _modifiers), PaintAction::Finish) => {
+                // Apply the transformation to the selection as a single set of edits
+                let transform = Self::drag_transform(handle, from, painting.location, &painting.modifiers, &data.bounds, data.pivot, data.proportional);
+                data.state.set(TransformAction::NoAction);
+
+                // Any pivot the user has chosen moves along with the selection
+                if let Some((pivot_selection, (x, y))) = data.custom_pivot.get() {
+                    data.custom_pivot.set(Some((pivot_selection, transform.transform_point(x, y))));
+                }
+
+                Self::edits_for_transform(model, &transform).into_iter()
+                    .map(|edit| ToolAction::Edit(edit))
+                    .chain(Some(ToolAction::InvalidateFrame))
+                    .collect()
+            },
+
+            (_, PaintAction::Finish) |
+            (_, PaintAction::Cancel) => {
+                // Abandon any drag that's in progress
+                data.state.set(TransformAction::NoAction);
+                vec![]
+            },
+
+            _ => vec![]
+        }
+    }
+}
+
+impl<Anim: 'static+EditableAnimation+Animation> Tool<Anim> for Transform {
+    type ToolData   = TransformData;
+    type Model      = TransformModel;
+
+    fn tool_name(&self) -> String { "Transform".to_string() }
+
+    fn image_name(&self) -> String { "transform".to_string() }
+
+    fn create_model(&self, flo_model: Arc<FloModel<Anim>>) -> TransformModel {
+        TransformModel::new(&*flo_model)
+    }
+
+    fn create_menu_controller(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &TransformModel) -> Option<Arc<dyn Controller>> {
+        Some(Arc::new(TransformMenuController::new(&*flo_model, tool_model)))
+    }
+
+    ///
+    /// Returns a stream containing the actions for the view and tool model for the transform tool
+    ///
+    fn actions_for_model(&self, flo_model: Arc<FloModel<Anim>>, tool_model: &TransformModel) -> Box<dyn Stream<Item=ToolAction<TransformData>, Error=()>+Send> {
+        // State is initially 'no action'
+        let transform_state     = bind(TransformAction::NoAction);
+
+        // Redraw the handles whenever the selection or the state changes
+        let draw_state          = BindRef::new(&transform_state);
+        let draw_bounds         = tool_model.selection_bounds.clone();
+        let draw_pivot          = tool_model.pivot.clone();
+        let draw_proportional   = tool_model.proportional.clone();
+        let draw_selection      = tool_model.selection.clone();
+
+        let draw_overlay        = follow(computed(move || (draw_state.get(), draw_bounds.get(), draw_pivot.get(), draw_proportional.get(), draw_selection.get())))
+            .map(|(state, bounds, pivot, proportional, selection)| {
+                ToolAction::Overlay(OverlayAction::Draw(Self::draw_overlay(state, bounds, pivot, proportional, &*selection)))
+            });
+
+        // Update the tool data whenever the selection changes
+        let selected_elements   = flo_model.selection().selected_elements.clone();
+        let bounds              = tool_model.selection_bounds.clone();
+        let pivot               = tool_model.pivot.clone();
+        let proportional        = tool_model.proportional.clone();
+        let custom_pivot        = tool_model.custom_pivot.clone();
+
+        let update_data         = follow(computed(move || (selected_elements.get(), bounds.get(), pivot.get(), proportional.get())))
+            .map(move |(selected_elements, bounds, pivot, proportional)| {
+                ToolAction::Data(TransformData {
+                    state:              transform_state.clone(),
+                    selected_elements:  selected_elements,
+                    bounds:             bounds,
+                    pivot:              pivot,
+                    proportional:       proportional,
+                    custom_pivot:       custom_pivot.clone()
+                })
+            });
+
+        Box::new(update_data.select(draw_overlay))
+    }
+
+    fn actions_for_input<'a>(&'a self, flo_model: Arc<FloModel<Anim>>, data: Option<Arc<TransformData>>, input: Box<dyn 'a+Iterator<Item=ToolInput<TransformData>>>) -> Box<dyn 'a+Iterator<Item=ToolAction<TransformData>>> {
+        let mut data    = data;
+        let mut actions = vec![];
+        let input       = ToolInput::last_paint_actions_only(input);
+
+        // Process the input
+        for input in input {
+            match input {
+                ToolInput::Data(new_data) => {
+                    // Keep tracking the data as it changes
+                    data = Some(new_data);
+                },
+
+                ToolInput::Paint(painting) => {
+                    if let Some(data) = data.as_ref() {
+                        actions.extend(self.paint(painting, &**data, &*flo_model));
+                    }
+                },
+
+                _ => ()
+            }
+        }
+
+        Box::new(actions.into_iter())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate flo_anim_sqlite;
+
+    use super::*;
+    use self::flo_anim_sqlite::*;
+    use futures::executor;
+
+    fn bounds() -> Rect {
+        Rect::with_points(0.0, 0.0, 100.0, 50.0)
+    }
+
+    fn is_near((x1, y1): (f32, f32), (x2, y2): (f32, f32)) -> bool {
+        (x1-x2).abs() < 0.001 && (y1-y2).abs() < 0.001
+    }
+
+    fn brush_stroke(element_id: i64, points: Vec<(f32, f32)>) -> Vec<AnimationEdit> {
+        vec![
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(element_id), Arc::new(points.into_iter().map(|point| RawPoint::from(point)).collect()))))
+        ]
+    }
+
+    #[test]
+    fn transform_selected_group() {
+        let model = FloModel::new(SqliteAnimation::new_in_memory());
+
+        {
+            let mut edit_log = executor::spawn(model.edit());
+            edit_log.wait_send(vec![
+                AnimationEdit::AddNewLayer(1),
+                AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))
+            ]).unwrap();
+            edit_log.wait_send(brush_stroke(100, vec![(10.0, 10.0), (20.0, 5.0)])).unwrap();
+            edit_log.wait_send(brush_stroke(101, vec![(30.0, 10.0), (40.0, 5.0)])).unwrap();
+            edit_log.wait_send(vec![
+                AnimationEdit::Element(vec![ElementId::Assigned(100), ElementId::Assigned(101)], ElementEdit::Group(ElementId::Assigned(102), GroupType::Normal))
+            ]).unwrap();
+        }
+
+        model.timeline().selected_layer.set(Some(1));
+        model.selection().select(ElementId::Assigned(102));
+
+        // Moving the group moves each of the elements inside it
+        let frame       = model.frame().frame.get().unwrap();
+        let original    = |element_id| frame.element_with_id(ElementId::Assigned(102))
+            .and_then(|group| match group { Vector::Group(group) => group.elements().filter(|element| element.id() == element_id).cloned().nth(0), _ => None })
+            .unwrap()
+            .control_points()
+            .into_iter()
+            .map(|cp| cp.position())
+            .collect::<Vec<_>>();
+
+        let edits       = Transform::edits_for_transform(&model, &Transform2D::translate(10.0, 20.0));
+
+        assert!(edits.len() == 2);
+        for (edit, element_id) in edits.iter().zip(vec![ElementId::Assigned(100), ElementId::Assigned(101)]) {
+            let expected = original(element_id).into_iter().map(|(x, y)| (x+10.0, y+20.0)).collect::<Vec<_>>();
+
+            match edit {
+                AnimationEdit::Element(ids, ElementEdit::SetControlPoints(points)) => {
+                    assert!(ids == &vec![element_id]);
+                    assert!(points.len() == expected.len());
+                    assert!(points.iter().zip(expected.iter()).all(|(point, expected)| is_near(*point, *expected)));
+                },
+
+                other => panic!("Unexpected edit: {:?}", other)
+            }
+        }
+    }
+
+    #[test]
+    fn corner_handle_scales_from_opposite_corner() {
+        let transform = Transform::drag_transform(TransformHandle::Scale(1, 1), (100.0, 50.0), (200.0, 75.0), &PaintModifiers::none(), &bounds(), (50.0, 25.0), false);
+
+        assert!(is_near(transform.transform_point(0.0, 0.0), (0.0, 0.0)));
+        assert!(is_near(transform.transform_point(100.0, 50.0), (200.0, 75.0)));
+    }
+
+    #[test]
+    fn proportional_scale_keeps_aspect_ratio() {
+        let transform = Transform::drag_transform(TransformHandle::Scale(1, 1), (100.0, 50.0), (200.0, 50.0), &PaintModifiers::none(), &bounds(), (50.0, 25.0), true);
+        let (x, y)      = transform.transform_point(100.0, 50.0);
+
+        assert!(is_near(transform.transform_point(0.0, 0.0), (0.0, 0.0)));
+        assert!(((x/y) - 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn shift_toggles_proportional_scale() {
+        let modifiers   = PaintModifiers { shift: true, ..PaintModifiers::none() };
+        let transform   = Transform::drag_transform(TransformHandle::Scale(1, 1), (100.0, 50.0), (200.0, 75.0), &modifiers, &bounds(), (50.0, 25.0), true);
+
+        assert!(is_near(transform.transform_point(100.0, 50.0), (200.0, 75.0)));
+    }
+
+    #[test]
+    fn edge_handle_scales_one_axis() {
+        let transform = Transform::drag_transform(TransformHandle::Scale(0, 1), (50.0, 50.0), (80.0, 100.0), &PaintModifiers::none(), &bounds(), (50.0, 25.0), true);
+
+        assert!(is_near(transform.transform_point(100.0, 50.0), (100.0, 100.0)));
+        assert!(is_near(transform.transform_point(100.0, 0.0), (100.0, 0.0)));
+    }
+
+    #[test]
+    fn alt_scales_about_pivot() {
+        let modifiers   = PaintModifiers { alt: true, ..PaintModifiers::none() };
+        let transform   = Transform::drag_transform(TransformHandle::Scale(1, 0), (100.0, 25.0), (150.0, 25.0), &modifiers, &bounds(), (50.0, 25.0), true);
+
+        assert!(is_near(transform.transform_point(50.0, 25.0), (50.0, 25.0)));
+        assert!(is_near(transform.transform_point(0.0, 25.0), (-50.0, 25.0)));
+    }
+
+    #[test]
+    fn rotate_about_pivot() {
+        let transform = Transform::drag_transform(TransformHandle::Rotate, (60.0, 25.0), (50.0, 35.0), &PaintModifiers::none(), &bounds(), (50.0, 25.0), true);
+
+        assert!(is_near(transform.transform_point(50.0, 25.0), (50.0, 25.0)));
+        assert!(is_near(transform.transform_point(100.0, 25.0), (50.0, 75.0)));
+    }
+
+    #[test]
+    fn shift_snaps_rotation() {
+        let modifiers   = PaintModifiers { shift: true, ..PaintModifiers::none() };
+        let transform   = Transform::drag_transform(TransformHandle::Rotate, (60.0, 25.0), (60.0, 26.0), &modifiers, &bounds(), (50.0, 25.0), true);
+
+        assert!(is_near(transform.transform_point(100.0, 25.0), (100.0, 25.0)));
+    }
+
+    #[test]
+    fn top_handle_skews_horizontally() {
+        let transform = Transform::drag_transform(TransformHandle::Skew(0, 1), (50.0, 66.0), (75.0, 66.0), &PaintModifiers::none(), &bounds(), (50.0, 25.0), true);
+
+        assert!(is_near(transform.transform_point(0.0, 0.0), (0.0, 0.0)));
+        assert!(is_near(transform.transform_point(0.0, 50.0), (25.0, 50.0)));
+    }
+
+    #[test]
+    fn pivot_takes_priority_over_move() {
+        let handle = Transform::handle_at_point(&bounds(), (50.0, 25.0), (52.0, 24.0));
+
+        assert!(handle == Some(TransformHandle::Pivot));
+    }
+
+    #[test]
+    fn inside_bounds_moves_selection() {
+        let handle = Transform::handle_at_point(&bounds(), (50.0, 25.0), (25.0, 30.0));
+
+        assert!(handle == Some(TransformHandle::Move));
+    }
+
+    #[test]
+    fn corner_picks_scale_handle() {
+        let handle = Transform::handle_at_point(&bounds(), (50.0, 25.0), (101.0, 49.0));
+
+        assert!(handle == Some(TransformHandle::Scale(1, 1)));
+    }
+}
@@ -40,4 +40,6 @@ pub const CP_LINES:                         Color = Color::Rgba(0.6, 0.6, 0.6, 0
 pub const ONIONSKIN_PAST:                   Color = Color::Rgba(0.8, 0.3, 0.3, 1.0);
 pub const ONIONSKIN_FUTURE:                 Color = Color::Rgba(0.3, 0.6, 0.8, 1.0);
 
+pub const IMPORT_ERROR_BACKGROUND:          Color = Color::Rgba(0.55, 0.20, 0.18, 1.0);
+
 pub const FILE_CHOOSER_BACKGROUND:          Color = Color::Rgba(0.165, 0.250, 0.198, 1.0);
@@ -1,4 +1,5 @@
 use flo_animation::*;
+use flo_animation::brushes::*;
 
 ///
 /// Action that updates the brush preview
@@ -20,9 +21,15 @@ pub enum BrushPreviewAction {
     /// Sets the brush properties to use for the brush preview
     BrushProperties(BrushProperties),
 
+    /// Sets how the points added to the brush preview are stabilised
+    Stabiliser(StabiliserSettings),
+
     /// Adds a raw point to the brush preview
     AddPoint(RawPoint),
 
+    /// Finishes the brush stroke in the preview (letting the stabiliser catch up with the last point that was added)
+    FinishStroke,
+
     /// If any elements overlap the brush preview, combine them into a single element
     CombineCollidingElements,
 
@@ -0,0 +1,14 @@
+//!
+//! Library for importing files in other formats into FlowBetween animations
+//!
+#![warn(bare_trait_objects)]
+
+extern crate flo_curves;
+extern crate flo_canvas;
+extern crate flo_animation;
+extern crate futures;
+extern crate roxmltree;
+
+mod svg;
+
+pub use self::svg::*;
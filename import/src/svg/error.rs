@@ -0,0 +1,27 @@
+use roxmltree;
+
+///
+/// Errors that prevent an SVG document from being imported at all
+///
+/// (Problems with individual elements are reported in the import log instead)
+///
+#[derive(Debug)]
+pub enum SvgImportError {
+    /// The document is not well-formed XML
+    InvalidXml(roxmltree::Error),
+
+    /// The root element of the document is not an `<svg>` element (the actual root element name is supplied)
+    NotSvg(String),
+
+    /// The layer that the document should be imported into does not exist
+    NoSuchLayer(u64),
+
+    /// The animation did not accept the edits
+    EditFailed
+}
+
+impl From<roxmltree::Error> for SvgImportError {
+    fn from(err: roxmltree::Error) -> SvgImportError {
+        SvgImportError::InvalidXml(err)
+    }
+}
@@ -0,0 +1,38 @@
+///
+/// Describes why part of an SVG document could not be imported exactly
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum ImportIssue {
+    /// The element is not supported (eg, filters, text or embedded images) and was left out of the import
+    UnsupportedElement,
+
+    /// The attribute (name, value) has no equivalent in FlowBetween and was ignored
+    UnsupportedAttribute(String, String),
+
+    /// The paint value could not be represented (eg, a gradient or pattern) so this part of the element was left out
+    UnsupportedPaint(String),
+
+    /// The path data contains an error: the path up to the point where the error occurs was imported
+    InvalidPathData(String),
+
+    /// A length or number could not be understood, so a default value was used instead
+    InvalidValue(String, String),
+
+    /// A `<use>` element refers to an element that does not exist or refers back to itself
+    InvalidReference(String)
+}
+
+///
+/// An entry in the log generated while importing an SVG document
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct ImportLogEntry {
+    /// The name of the element where the issue was found
+    pub element: String,
+
+    /// The value of the element's `id` attribute, if it has one
+    pub element_id: Option<String>,
+
+    /// What was wrong with the element
+    pub issue: ImportIssue
+}
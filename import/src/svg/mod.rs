@@ -0,0 +1,12 @@
+mod error;
+mod import_log;
+mod transform;
+mod path_data;
+mod paint;
+mod style;
+mod stroke;
+mod svg_import;
+
+pub use self::error::*;
+pub use self::import_log::*;
+pub use self::svg_import::*;
@@ -0,0 +1,164 @@
+///
+/// A colour from an SVG document (red, green, blue and alpha, as values from 0 to 1)
+///
+pub type SvgColor = (f32, f32, f32, f32);
+
+///
+/// The value of an SVG `fill` or `stroke` property
+///
+#[derive(Clone, PartialEq, Debug)]
+pub enum SvgPaint {
+    /// Nothing is painted
+    None,
+
+    /// A flat colour
+    Color(SvgColor),
+
+    /// The value of the `color` property
+    CurrentColor,
+
+    /// A paint server (eg a gradient or pattern) or other value that can't be represented as a flat colour
+    Unsupported(String)
+}
+
+///
+/// Parses the value of a `fill` or `stroke` property
+///
+pub fn parse_paint(paint: &str) -> SvgPaint {
+    let paint = paint.trim();
+
+    match paint {
+        "none"          => SvgPaint::None,
+        "currentColor"  => SvgPaint::CurrentColor,
+
+        _ => {
+            if paint.starts_with("url(") {
+                // Gradients and patterns may supply a fallback colour after the URL
+                let fallback = paint.find(')').map(|close_bracket| paint[(close_bracket+1)..].trim()).unwrap_or("");
+
+                match fallback {
+                    ""      => SvgPaint::Unsupported(paint.to_string()),
+                    "none"  => SvgPaint::None,
+                    _       => parse_color(fallback).map(|color| SvgPaint::Color(color)).unwrap_or_else(|| SvgPaint::Unsupported(paint.to_string()))
+                }
+            } else {
+                parse_color(paint).map(|color| SvgPaint::Color(color)).unwrap_or_else(|| SvgPaint::Unsupported(paint.to_string()))
+            }
+        }
+    }
+}
+
+///
+/// Parses a CSS colour value
+///
+pub fn parse_color(color: &str) -> Option<SvgColor> {
+    let color = color.trim();
+
+    if color.starts_with('#') {
+        // Hex colour
+        let hex     = &color[1..];
+        let digit   = |idx: usize| u8::from_str_radix(&hex[idx..idx+1], 16).ok().map(|val| val as f32);
+        let pair    = |idx: usize| u8::from_str_radix(&hex[idx..idx+2], 16).ok().map(|val| val as f32);
+
+        if !hex.is_ascii() { return None; }
+
+        match hex.len() {
+            3 => Some((digit(0)?*17.0/255.0, digit(1)?*17.0/255.0, digit(2)?*17.0/255.0, 1.0)),
+            6 => Some((pair(0)?/255.0, pair(2)?/255.0, pair(4)?/255.0, 1.0)),
+            _ => None
+        }
+    } else if color.starts_with("rgb(") || color.starts_with("rgba(") {
+        // Functional colour
+        let open_bracket    = color.find('(')?;
+        let close_bracket   = color.rfind(')')?;
+        let components: Vec<_> = color[(open_bracket+1)..close_bracket].split(',').map(|component| component.trim()).collect();
+
+        let channel = |component: &str| -> Option<f32> {
+            if component.ends_with('%') {
+                component[0..component.len()-1].trim().parse::<f32>().ok().map(|val| (val/100.0).max(0.0).min(1.0))
+            } else {
+                component.parse::<f32>().ok().map(|val| (val/255.0).max(0.0).min(1.0))
+            }
+        };
+
+        match components.len() {
+            3 => Some((channel(components[0])?, channel(components[1])?, channel(components[2])?, 1.0)),
+            4 => Some((channel(components[0])?, channel(components[1])?, channel(components[2])?, components[3].parse::<f32>().ok()?.max(0.0).min(1.0))),
+            _ => None
+        }
+    } else {
+        named_color(&color.to_ascii_lowercase())
+    }
+}
+
+///
+/// Looks up a CSS colour keyword
+///
+fn named_color(name: &str) -> Option<SvgColor> {
+    let (r, g, b) = match name {
+        "black"         => (0, 0, 0),
+        "silver"        => (192, 192, 192),
+        "gray" | "grey" => (128, 128, 128),
+        "white"         => (255, 255, 255),
+        "maroon"        => (128, 0, 0),
+        "red"           => (255, 0, 0),
+        "purple"        => (128, 0, 128),
+        "fuchsia"       => (255, 0, 255),
+        "magenta"       => (255, 0, 255),
+        "green"         => (0, 128, 0),
+        "lime"          => (0, 255, 0),
+        "olive"         => (128, 128, 0),
+        "yellow"        => (255, 255, 0),
+        "navy"          => (0, 0, 128),
+        "blue"          => (0, 0, 255),
+        "teal"          => (0, 128, 128),
+        "aqua"          => (0, 255, 255),
+        "cyan"          => (0, 255, 255),
+        "orange"        => (255, 165, 0),
+        "brown"         => (165, 42, 42),
+        "pink"          => (255, 192, 203),
+        "gold"          => (255, 215, 0),
+        "indigo"        => (75, 0, 130),
+        "violet"        => (238, 130, 238),
+        "darkgray" | "darkgrey"     => (169, 169, 169),
+        "lightgray" | "lightgrey"   => (211, 211, 211),
+
+        "transparent"   => { return Some((0.0, 0.0, 0.0, 0.0)); }
+
+        _               => { return None; }
+    };
+
+    Some((r as f32/255.0, g as f32/255.0, b as f32/255.0, 1.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_hex_colors() {
+        assert!(parse_color("#fff") == Some((1.0, 1.0, 1.0, 1.0)));
+        assert!(parse_color("#ff0000") == Some((1.0, 0.0, 0.0, 1.0)));
+        assert!(parse_color("#ff00") == None);
+    }
+
+    #[test]
+    fn parse_rgb_colors() {
+        assert!(parse_color("rgb(255, 0, 0)") == Some((1.0, 0.0, 0.0, 1.0)));
+        assert!(parse_color("rgb(100%, 0%, 50%)") == Some((1.0, 0.0, 0.5, 1.0)));
+        assert!(parse_color("rgba(0, 0, 255, 0.5)") == Some((0.0, 0.0, 1.0, 0.5)));
+    }
+
+    #[test]
+    fn parse_named_colors() {
+        assert!(parse_color("Blue") == Some((0.0, 0.0, 1.0, 1.0)));
+        assert!(parse_color("notacolor") == None);
+    }
+
+    #[test]
+    fn gradients_are_unsupported() {
+        assert!(parse_paint("url(#gradient)") == SvgPaint::Unsupported("url(#gradient)".to_string()));
+        assert!(parse_paint("url(#gradient) #000") == SvgPaint::Color((0.0, 0.0, 0.0, 1.0)));
+        assert!(parse_paint("none") == SvgPaint::None);
+    }
+}
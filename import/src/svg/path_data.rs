@@ -0,0 +1,626 @@
+use std::f64;
+use std::f64::consts::PI;
+
+///
+/// A segment of a path in SVG user coordinates (after relative coordinates and shorthand commands have been resolved)
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PathSegment {
+    /// Begins a new subpath at the specified point
+    Move((f64, f64)),
+
+    /// Straight line to the specified point
+    Line((f64, f64)),
+
+    /// Cubic bezier curve (control point 1, control point 2, end point)
+    Cubic((f64, f64), (f64, f64), (f64, f64)),
+
+    /// Closes the current subpath
+    Close
+}
+
+///
+/// Reads numbers from SVG attribute values (where numbers can be separated by whitespace, commas or just by where the next number starts)
+///
+struct NumberReader<'a> {
+    data:   &'a [u8],
+    pos:    usize
+}
+
+impl<'a> NumberReader<'a> {
+    fn new(data: &'a str) -> NumberReader<'a> {
+        NumberReader { data: data.as_bytes(), pos: 0 }
+    }
+
+    ///
+    /// Skips whitespace and at most one comma
+    ///
+    fn skip_separators(&mut self) {
+        let mut seen_comma = false;
+
+        while self.pos < self.data.len() {
+            match self.data[self.pos] {
+                b' ' | b'\t' | b'\r' | b'\n'    => { self.pos += 1; },
+                b',' if !seen_comma             => { seen_comma = true; self.pos += 1; },
+                _                               => { break; }
+            }
+        }
+    }
+
+    ///
+    /// Skips whitespace only
+    ///
+    fn skip_whitespace(&mut self) {
+        while self.pos < self.data.len() && (self.data[self.pos] as char).is_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    ///
+    /// True if everything has been read
+    ///
+    fn at_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.pos >= self.data.len()
+    }
+
+    ///
+    /// Returns the next character without consuming it
+    ///
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.data.get(self.pos).cloned()
+    }
+
+    ///
+    /// True if the next thing to read is a number
+    ///
+    fn at_number(&mut self) -> bool {
+        self.skip_separators();
+
+        match self.data.get(self.pos) {
+            Some(b'0'..=b'9') | Some(b'-') | Some(b'+') | Some(b'.')    => true,
+            _                                                           => false
+        }
+    }
+
+    ///
+    /// Reads the next number
+    ///
+    fn number(&mut self) -> Result<f64, String> {
+        self.skip_separators();
+
+        let start       = self.pos;
+        let data        = self.data;
+        let digits      = |pos: &mut usize| { while *pos < data.len() && data[*pos].is_ascii_digit() { *pos += 1; } };
+
+        if self.pos < data.len() && (data[self.pos] == b'-' || data[self.pos] == b'+') { self.pos += 1; }
+        digits(&mut self.pos);
+        if self.pos < data.len() && data[self.pos] == b'.' { self.pos += 1; digits(&mut self.pos); }
+
+        // Exponent (but not the start of a unit like 'em' or 'ex')
+        if self.pos < data.len() && (data[self.pos] == b'e' || data[self.pos] == b'E') {
+            let mut exponent_pos = self.pos + 1;
+            if exponent_pos < data.len() && (data[exponent_pos] == b'-' || data[exponent_pos] == b'+') { exponent_pos += 1; }
+
+            if exponent_pos < data.len() && data[exponent_pos].is_ascii_digit() {
+                self.pos = exponent_pos;
+                digits(&mut self.pos);
+            }
+        }
+
+        let number = String::from_utf8_lossy(&data[start..self.pos]);
+        number.parse::<f64>().map_err(|_| format!("Expected a number at '{}'", String::from_utf8_lossy(&data[start..])))
+    }
+
+    ///
+    /// Reads an arc flag (which is a single '0' or '1', which may not be separated from the following number)
+    ///
+    fn flag(&mut self) -> Result<bool, String> {
+        self.skip_separators();
+
+        match self.data.get(self.pos) {
+            Some(b'0')  => { self.pos += 1; Ok(false) },
+            Some(b'1')  => { self.pos += 1; Ok(true) },
+            _           => Err(format!("Expected an arc flag at '{}'", String::from_utf8_lossy(&self.data[self.pos..])))
+        }
+    }
+
+    ///
+    /// Reads a coordinate pair
+    ///
+    fn point(&mut self) -> Result<(f64, f64), String> {
+        let x = self.number()?;
+        let y = self.number()?;
+
+        Ok((x, y))
+    }
+
+    ///
+    /// Returns whatever is left to read
+    ///
+    fn remaining(&self) -> String {
+        String::from_utf8_lossy(&self.data[self.pos..]).to_string()
+    }
+}
+
+///
+/// Parses a list of numbers (as used by `transform`, `viewBox` and `points` attributes)
+///
+pub fn parse_number_list(list: &str) -> Result<Vec<f64>, String> {
+    let mut reader = NumberReader::new(list);
+    let mut result = vec![];
+
+    while !reader.at_end() {
+        result.push(reader.number()?);
+    }
+
+    Ok(result)
+}
+
+///
+/// Parses the value of a path's `d` attribute
+///
+/// As recommended by the SVG specification, a path with an error is imported up to the point where the
+/// error occurs, so this returns the segments read so far along with the error (if there was one).
+///
+pub fn parse_path_data(data: &str) -> (Vec<PathSegment>, Option<String>) {
+    let mut path = PathBuilder::new();
+    let error    = read_path_data(data, &mut path).err();
+
+    (path.segments, error)
+}
+
+///
+/// Reads path data into a path builder
+///
+fn read_path_data(data: &str, path: &mut PathBuilder) -> Result<(), String> {
+    let mut reader          = NumberReader::new(data);
+    let mut last_command    = None;
+
+    while !reader.at_end() {
+        // Read the command (or repeat the last command if there's just a number here)
+        let command = match reader.peek() {
+            Some(chr) if (chr as char).is_ascii_alphabetic() => {
+                reader.pos += 1;
+                chr as char
+            },
+
+            _ => {
+                match last_command {
+                    // Coordinates following a move are treated as lines
+                    Some('M')               => 'L',
+                    Some('m')               => 'l',
+                    Some('Z') | Some('z')   => { return Err(format!("Expected a command at '{}'", reader.remaining())); }
+                    Some(command)           => command,
+                    None                    => { return Err(format!("Path data must begin with a move at '{}'", reader.remaining())); }
+                }
+            }
+        };
+
+        if last_command.is_none() && command != 'M' && command != 'm' {
+            return Err(format!("Path data must begin with a move at '{}'", data));
+        }
+
+        // Relative commands are relative to the current point
+        let relative    = command.is_ascii_lowercase();
+        let (cx, cy)    = path.current;
+        let offset      = |(x, y): (f64, f64)| if relative { (x+cx, y+cy) } else { (x, y) };
+
+        match command.to_ascii_uppercase() {
+            'M' => { let pos = offset(reader.point()?); path.move_to(pos); },
+            'L' => { let pos = offset(reader.point()?); path.line_to(pos); },
+            'H' => { let x = reader.number()?; path.line_to((if relative { cx+x } else { x }, cy)); },
+            'V' => { let y = reader.number()?; path.line_to((cx, if relative { cy+y } else { y })); },
+            'C' => {
+                let cp1 = offset(reader.point()?);
+                let cp2 = offset(reader.point()?);
+                let pos = offset(reader.point()?);
+                path.curve_to(cp1, cp2, pos);
+            },
+            'S' => {
+                let cp2 = offset(reader.point()?);
+                let pos = offset(reader.point()?);
+                let cp1 = path.reflected_cubic_control_point();
+                path.curve_to(cp1, cp2, pos);
+            },
+            'Q' => {
+                let cp  = offset(reader.point()?);
+                let pos = offset(reader.point()?);
+                path.quad_to(cp, pos);
+            },
+            'T' => {
+                let pos = offset(reader.point()?);
+                let cp  = path.reflected_quad_control_point();
+                path.quad_to(cp, pos);
+            },
+            'A' => {
+                let rx          = reader.number()?;
+                let ry          = reader.number()?;
+                let rotation    = reader.number()?;
+                let large_arc   = reader.flag()?;
+                let sweep       = reader.flag()?;
+                let pos         = offset(reader.point()?);
+                path.arc_to((rx, ry), rotation, large_arc, sweep, pos);
+            },
+            'Z' => { path.close(); },
+
+            _   => { return Err(format!("Unknown path command '{}'", command)); }
+        }
+
+        last_command = Some(command);
+
+        // Commands other than close must be followed by either another command or more coordinates
+        if command != 'Z' && command != 'z' && !reader.at_end() && !reader.at_number() && !reader.peek().map(|chr| (chr as char).is_ascii_alphabetic()).unwrap_or(false) {
+            return Err(format!("Unexpected '{}'", reader.remaining()));
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// Builds up a path from SVG-style commands
+///
+pub struct PathBuilder {
+    /// The segments generated so far
+    pub segments: Vec<PathSegment>,
+
+    /// The current point
+    current: (f64, f64),
+
+    /// The start of the current subpath
+    subpath_start: (f64, f64),
+
+    /// The last cubic control point (for the 'S' command)
+    last_cubic_cp: Option<(f64, f64)>,
+
+    /// The last quadratic control point (for the 'T' command)
+    last_quad_cp: Option<(f64, f64)>
+}
+
+impl PathBuilder {
+    ///
+    /// Creates a new, empty, path builder
+    ///
+    pub fn new() -> PathBuilder {
+        PathBuilder {
+            segments:       vec![],
+            current:        (0.0, 0.0),
+            subpath_start:  (0.0, 0.0),
+            last_cubic_cp:  None,
+            last_quad_cp:   None
+        }
+    }
+
+    ///
+    /// Starts a new subpath
+    ///
+    pub fn move_to(&mut self, pos: (f64, f64)) {
+        self.segments.push(PathSegment::Move(pos));
+        self.current        = pos;
+        self.subpath_start  = pos;
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// Adds a line to the current subpath
+    ///
+    pub fn line_to(&mut self, pos: (f64, f64)) {
+        self.segments.push(PathSegment::Line(pos));
+        self.current        = pos;
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// Adds a cubic bezier curve to the current subpath
+    ///
+    pub fn curve_to(&mut self, cp1: (f64, f64), cp2: (f64, f64), pos: (f64, f64)) {
+        self.segments.push(PathSegment::Cubic(cp1, cp2, pos));
+        self.current        = pos;
+        self.last_cubic_cp  = Some(cp2);
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// Adds a quadratic bezier curve to the current subpath (as the equivalent cubic curve)
+    ///
+    pub fn quad_to(&mut self, cp: (f64, f64), pos: (f64, f64)) {
+        let (x0, y0)    = self.current;
+        let cp1         = (x0 + (cp.0-x0)*2.0/3.0, y0 + (cp.1-y0)*2.0/3.0);
+        let cp2         = (pos.0 + (cp.0-pos.0)*2.0/3.0, pos.1 + (cp.1-pos.1)*2.0/3.0);
+
+        self.segments.push(PathSegment::Cubic(cp1, cp2, pos));
+        self.current        = pos;
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = Some(cp);
+    }
+
+    ///
+    /// Closes the current subpath
+    ///
+    pub fn close(&mut self) {
+        self.segments.push(PathSegment::Close);
+        self.current        = self.subpath_start;
+        self.last_cubic_cp  = None;
+        self.last_quad_cp   = None;
+    }
+
+    ///
+    /// The first control point for a smooth cubic curve
+    ///
+    fn reflected_cubic_control_point(&self) -> (f64, f64) {
+        let (x, y) = self.current;
+        self.last_cubic_cp.map(|(cx, cy)| (2.0*x - cx, 2.0*y - cy)).unwrap_or((x, y))
+    }
+
+    ///
+    /// The control point for a smooth quadratic curve
+    ///
+    fn reflected_quad_control_point(&self) -> (f64, f64) {
+        let (x, y) = self.current;
+        self.last_quad_cp.map(|(cx, cy)| (2.0*x - cx, 2.0*y - cy)).unwrap_or((x, y))
+    }
+
+    ///
+    /// Adds an elliptical arc to the current subpath (approximated as a series of cubic curves)
+    ///
+    /// Follows the 'endpoint to center' conversion from the implementation notes in the SVG specification
+    ///
+    pub fn arc_to(&mut self, (rx, ry): (f64, f64), rotation: f64, large_arc: bool, sweep: bool, pos: (f64, f64)) {
+        let (x1, y1)    = self.current;
+        let (x2, y2)    = pos;
+        let mut rx      = rx.abs();
+        let mut ry      = ry.abs();
+
+        // Degenerate arcs are straight lines (or nothing at all)
+        if x1 == x2 && y1 == y2 { return; }
+        if rx == 0.0 || ry == 0.0 { self.line_to(pos); return; }
+
+        let phi             = rotation.to_radians();
+        let (sin_phi, cos_phi) = (phi.sin(), phi.cos());
+
+        // Compute the transformed start point
+        let dx2             = (x1 - x2) / 2.0;
+        let dy2             = (y1 - y2) / 2.0;
+        let x1p             = cos_phi*dx2 + sin_phi*dy2;
+        let y1p             = -sin_phi*dx2 + cos_phi*dy2;
+
+        // Scale up the radii if they're too small to reach the end point
+        let lambda          = (x1p*x1p)/(rx*rx) + (y1p*y1p)/(ry*ry);
+        if lambda > 1.0 {
+            rx *= lambda.sqrt();
+            ry *= lambda.sqrt();
+        }
+
+        // Compute the center
+        let numerator       = rx*rx*ry*ry - rx*rx*y1p*y1p - ry*ry*x1p*x1p;
+        let denominator     = rx*rx*y1p*y1p + ry*ry*x1p*x1p;
+        let mut coefficient = (numerator/denominator).max(0.0).sqrt();
+        if large_arc == sweep { coefficient = -coefficient; }
+
+        let cxp             = coefficient * (rx*y1p/ry);
+        let cyp             = coefficient * -(ry*x1p/rx);
+        let cx              = cos_phi*cxp - sin_phi*cyp + (x1+x2)/2.0;
+        let cy              = sin_phi*cxp + cos_phi*cyp + (y1+y2)/2.0;
+
+        // Compute the start angle and the sweep
+        let angle           = |ux: f64, uy: f64, vx: f64, vy: f64| {
+            let sign = if ux*vy - uy*vx < 0.0 { -1.0 } else { 1.0 };
+            let dot  = (ux*vx + uy*vy) / ((ux*ux + uy*uy).sqrt() * (vx*vx + vy*vy).sqrt());
+            sign * dot.max(-1.0).min(1.0).acos()
+        };
+
+        let theta1          = angle(1.0, 0.0, (x1p-cxp)/rx, (y1p-cyp)/ry);
+        let mut delta       = angle((x1p-cxp)/rx, (y1p-cyp)/ry, (-x1p-cxp)/rx, (-y1p-cyp)/ry);
+
+        if !sweep && delta > 0.0 { delta -= 2.0*PI; }
+        if sweep && delta < 0.0 { delta += 2.0*PI; }
+
+        // Split into segments of no more than 90 degrees, each of which is approximated by a cubic curve
+        let num_segments    = (delta.abs() / (PI/2.0)).ceil().max(1.0) as usize;
+        let segment_delta   = delta / (num_segments as f64);
+        let handle_length   = 4.0/3.0 * (segment_delta/4.0).tan();
+
+        let point_at        = |theta: f64| {
+            let (sin_t, cos_t) = (theta.sin(), theta.cos());
+            (cx + rx*cos_t*cos_phi - ry*sin_t*sin_phi, cy + rx*cos_t*sin_phi + ry*sin_t*cos_phi)
+        };
+        let derivative_at   = |theta: f64| {
+            let (sin_t, cos_t) = (theta.sin(), theta.cos());
+            (-rx*sin_t*cos_phi - ry*cos_t*sin_phi, -rx*sin_t*sin_phi + ry*cos_t*cos_phi)
+        };
+
+        for segment in 0..num_segments {
+            let t1          = theta1 + segment_delta*(segment as f64);
+            let t2          = t1 + segment_delta;
+
+            let p1          = point_at(t1);
+            let d1          = derivative_at(t1);
+            let d2          = derivative_at(t2);
+
+            // The final point is exactly the end point, to avoid accumulating rounding errors
+            let p2          = if segment == num_segments-1 { pos } else { point_at(t2) };
+
+            let cp1         = (p1.0 + handle_length*d1.0, p1.1 + handle_length*d1.1);
+            let cp2         = (p2.0 - handle_length*d2.0, p2.1 - handle_length*d2.1);
+
+            self.curve_to(cp1, cp2, p2);
+        }
+    }
+}
+
+///
+/// Generates the path for an SVG `<rect>` element
+///
+pub fn rect_path(x: f64, y: f64, width: f64, height: f64, rx: f64, ry: f64) -> Vec<PathSegment> {
+    let mut path    = PathBuilder::new();
+    let rx          = rx.min(width/2.0).max(0.0);
+    let ry          = ry.min(height/2.0).max(0.0);
+
+    if rx == 0.0 || ry == 0.0 {
+        path.move_to((x, y));
+        path.line_to((x+width, y));
+        path.line_to((x+width, y+height));
+        path.line_to((x, y+height));
+        path.close();
+    } else {
+        path.move_to((x+rx, y));
+        path.line_to((x+width-rx, y));
+        path.arc_to((rx, ry), 0.0, false, true, (x+width, y+ry));
+        path.line_to((x+width, y+height-ry));
+        path.arc_to((rx, ry), 0.0, false, true, (x+width-rx, y+height));
+        path.line_to((x+rx, y+height));
+        path.arc_to((rx, ry), 0.0, false, true, (x, y+height-ry));
+        path.line_to((x, y+ry));
+        path.arc_to((rx, ry), 0.0, false, true, (x+rx, y));
+        path.close();
+    }
+
+    path.segments
+}
+
+///
+/// Generates the path for an SVG `<ellipse>` or `<circle>` element
+///
+pub fn ellipse_path(cx: f64, cy: f64, rx: f64, ry: f64) -> Vec<PathSegment> {
+    let mut path = PathBuilder::new();
+
+    path.move_to((cx+rx, cy));
+    path.arc_to((rx, ry), 0.0, false, true, (cx, cy+ry));
+    path.arc_to((rx, ry), 0.0, false, true, (cx-rx, cy));
+    path.arc_to((rx, ry), 0.0, false, true, (cx, cy-ry));
+    path.arc_to((rx, ry), 0.0, false, true, (cx+rx, cy));
+    path.close();
+
+    path.segments
+}
+
+///
+/// Generates the path for an SVG `<polyline>` or `<polygon>` element
+///
+pub fn poly_path(points: &[f64], closed: bool) -> Vec<PathSegment> {
+    let mut path    = PathBuilder::new();
+    let mut points  = points.chunks(2).filter(|point| point.len() == 2);
+
+    if let Some(first) = points.next() {
+        path.move_to((first[0], first[1]));
+        points.for_each(|point| path.line_to((point[0], point[1])));
+
+        if closed { path.close(); }
+    }
+
+    path.segments
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::PathSegment::*;
+
+    fn close_to(a: (f64, f64), b: (f64, f64)) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    #[test]
+    fn parse_absolute_lines() {
+        let (path, error) = parse_path_data("M10 20 L30,40 H50 V60 Z");
+
+        assert!(error.is_none());
+        assert!(path == vec![Move((10.0, 20.0)), Line((30.0, 40.0)), Line((50.0, 40.0)), Line((50.0, 60.0)), Close]);
+    }
+
+    #[test]
+    fn parse_relative_lines_with_implicit_commands() {
+        let (path, error) = parse_path_data("m10 20 10 0 0 10 h-10z");
+
+        assert!(error.is_none());
+        assert!(path == vec![Move((10.0, 20.0)), Line((20.0, 20.0)), Line((20.0, 30.0)), Line((10.0, 30.0)), Close]);
+    }
+
+    #[test]
+    fn parse_compact_numbers() {
+        let (path, error) = parse_path_data("M.5.5l-1-1e1");
+
+        assert!(error.is_none());
+        assert!(path == vec![Move((0.5, 0.5)), Line((-0.5, -9.5))]);
+    }
+
+    #[test]
+    fn smooth_curves_reflect_control_points() {
+        let (path, error) = parse_path_data("M0 0 C0 10 10 10 10 0 S20 -10 20 0");
+
+        assert!(error.is_none());
+        assert!(path[2] == Cubic((10.0, -10.0), (20.0, -10.0), (20.0, 0.0)));
+    }
+
+    #[test]
+    fn quadratic_curves_become_cubic() {
+        let (path, error) = parse_path_data("M0 0 Q15 30 30 0");
+
+        assert!(error.is_none());
+        assert!(path[1] == Cubic((10.0, 20.0), (20.0, 20.0), (30.0, 0.0)));
+    }
+
+    #[test]
+    fn arc_ends_at_end_point() {
+        let (path, error) = parse_path_data("M0 0 A10 10 0 0 1 20 0");
+
+        assert!(error.is_none());
+        assert!(path.len() == 3);
+
+        match path[2] {
+            Cubic(_, _, end)    => assert!(close_to(end, (20.0, 0.0))),
+            _                   => assert!(false)
+        }
+
+        // Half circle through (10, 10) or (10, -10)
+        match path[1] {
+            Cubic(_, _, mid)    => assert!(close_to(mid, (10.0, 10.0)) || close_to(mid, (10.0, -10.0))),
+            _                   => assert!(false)
+        }
+    }
+
+    #[test]
+    fn arc_flags_do_not_need_separators() {
+        let (path, error) = parse_path_data("M0 0 a10 10 0 0120 0");
+
+        assert!(error.is_none());
+        assert!(path.len() == 3);
+    }
+
+    #[test]
+    fn path_with_error_is_imported_up_to_error() {
+        let (path, error) = parse_path_data("M10 10 L20 20 L30 X");
+
+        assert!(error.is_some());
+        assert!(path == vec![Move((10.0, 10.0)), Line((20.0, 20.0))]);
+    }
+
+    #[test]
+    fn path_must_start_with_move() {
+        let (path, error) = parse_path_data("L10 10");
+
+        assert!(error.is_some());
+        assert!(path.len() == 0);
+    }
+
+    #[test]
+    fn parse_numbers() {
+        assert!(parse_number_list("1, 2 3,-4.5e1").unwrap() == vec![1.0, 2.0, 3.0, -45.0]);
+        assert!(parse_number_list("1 px").is_err());
+    }
+
+    #[test]
+    fn circle_has_four_curves() {
+        let path = ellipse_path(0.0, 0.0, 10.0, 10.0);
+
+        assert!(path.len() == 6);
+        match path[1] {
+            Cubic(_, _, end)    => assert!(close_to(end, (0.0, 10.0))),
+            _                   => assert!(false)
+        }
+    }
+}
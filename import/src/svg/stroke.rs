@@ -0,0 +1,227 @@
+use super::path_data::*;
+
+use flo_curves::*;
+use flo_curves::bezier;
+
+///
+/// A piece of an outline: the start point and the segment that follows it (which is always a line or a curve)
+///
+type OutlinePiece = ((f64, f64), PathSegment);
+
+///
+/// Generates a path that outlines the stroke of a path
+///
+/// FlowBetween's path elements are always filled, so strokes from SVG documents are imported as a separate
+/// filled path. The outline uses butt caps and bevel joins.
+///
+pub fn stroke_outline(path: &[PathSegment], width: f64) -> Vec<PathSegment> {
+    let half_width  = width / 2.0;
+    let mut result  = vec![];
+
+    if half_width <= 0.0 {
+        return result;
+    }
+
+    for (pieces, closed) in subpaths(path) {
+        let reversed    = reverse_pieces(&pieces);
+        let side_a      = offset_pieces(&pieces, half_width);
+        let side_b      = offset_pieces(&reversed, half_width);
+
+        if side_a.len() == 0 || side_b.len() == 0 {
+            continue;
+        }
+
+        if closed {
+            // Closed paths have an inside and outside edge, which go in opposite directions so the middle is not filled
+            append_pieces(&mut result, &side_a, true);
+            result.push(PathSegment::Close);
+            append_pieces(&mut result, &side_b, true);
+            result.push(PathSegment::Close);
+        } else {
+            // Open paths go along one side and back along the other
+            append_pieces(&mut result, &side_a, true);
+            append_pieces(&mut result, &side_b, false);
+            result.push(PathSegment::Close);
+        }
+    }
+
+    result
+}
+
+///
+/// Splits a path into subpaths made up of lines and curves, along with whether or not the subpath is closed
+///
+fn subpaths(path: &[PathSegment]) -> Vec<(Vec<OutlinePiece>, bool)> {
+    let mut result          = vec![];
+    let mut current         = vec![];
+    let mut start_point     = (0.0, 0.0);
+    let mut last_point      = (0.0, 0.0);
+
+    for segment in path.iter() {
+        match segment {
+            PathSegment::Move(pos) => {
+                if current.len() > 0 { result.push((current, false)); }
+
+                current         = vec![];
+                start_point     = *pos;
+                last_point      = *pos;
+            },
+
+            PathSegment::Line(pos) => {
+                current.push((last_point, *segment));
+                last_point      = *pos;
+            },
+
+            PathSegment::Cubic(_, _, pos) => {
+                current.push((last_point, *segment));
+                last_point      = *pos;
+            },
+
+            PathSegment::Close => {
+                if last_point != start_point {
+                    current.push((last_point, PathSegment::Line(start_point)));
+                }
+
+                if current.len() > 0 { result.push((current, true)); }
+
+                current         = vec![];
+                last_point      = start_point;
+            }
+        }
+    }
+
+    if current.len() > 0 { result.push((current, false)); }
+
+    result
+}
+
+///
+/// Reverses the direction of a set of outline pieces
+///
+fn reverse_pieces(pieces: &[OutlinePiece]) -> Vec<OutlinePiece> {
+    pieces.iter()
+        .rev()
+        .map(|(start, segment)| match segment {
+            PathSegment::Line(end)              => (*end, PathSegment::Line(*start)),
+            PathSegment::Cubic(cp1, cp2, end)   => (*end, PathSegment::Cubic(*cp2, *cp1, *start)),
+            other                               => (*start, *other)
+        })
+        .collect()
+}
+
+///
+/// Offsets a set of outline pieces to one side by the specified distance
+///
+fn offset_pieces(pieces: &[OutlinePiece], distance: f64) -> Vec<OutlinePiece> {
+    pieces.iter()
+        .flat_map(|(start, segment)| match segment {
+            PathSegment::Line(end)              => offset_line(*start, *end, distance),
+            PathSegment::Cubic(cp1, cp2, end)   => offset_curve(*start, *cp1, *cp2, *end, distance),
+            _                                   => vec![]
+        })
+        .collect()
+}
+
+///
+/// Offsets a straight line
+///
+fn offset_line(start: (f64, f64), end: (f64, f64), distance: f64) -> Vec<OutlinePiece> {
+    let (dx, dy)    = (end.0-start.0, end.1-start.1);
+    let length      = (dx*dx + dy*dy).sqrt();
+
+    if length < 1e-9 {
+        // Zero-length lines have no direction and don't contribute to the outline
+        vec![]
+    } else {
+        let (nx, ny) = (-dy/length*distance, dx/length*distance);
+
+        vec![((start.0+nx, start.1+ny), PathSegment::Line((end.0+nx, end.1+ny)))]
+    }
+}
+
+///
+/// Offsets a bezier curve
+///
+fn offset_curve(start: (f64, f64), cp1: (f64, f64), cp2: (f64, f64), end: (f64, f64), distance: f64) -> Vec<OutlinePiece> {
+    let curve   = bezier::Curve::from_points(Coord2(start.0, start.1), (Coord2(cp1.0, cp1.1), Coord2(cp2.0, cp2.1)), Coord2(end.0, end.1));
+    let offset  = bezier::offset(&curve, distance, distance);
+
+    let pieces: Vec<_> = offset.into_iter()
+        .map(|curve| {
+            let start       = curve.start_point();
+            let (cp1, cp2)  = curve.control_points();
+            let end         = curve.end_point();
+
+            ((start.x(), start.y()), PathSegment::Cubic((cp1.x(), cp1.y()), (cp2.x(), cp2.y()), (end.x(), end.y())))
+        })
+        .collect();
+
+    let is_valid = pieces.len() > 0 && pieces.iter().all(|(start, segment)| match segment {
+        PathSegment::Cubic(cp1, cp2, end)   => [start, cp1, cp2, end].iter().all(|(x, y)| x.is_finite() && y.is_finite()),
+        _                                   => false
+    });
+
+    if is_valid {
+        pieces
+    } else {
+        // Curves where the control points are on top of the end points can have no defined normal: treat these as straight lines
+        offset_line(start, end, distance)
+    }
+}
+
+///
+/// Adds a set of offset pieces to a path, joining them together with straight lines where there are gaps
+///
+fn append_pieces(path: &mut Vec<PathSegment>, pieces: &[OutlinePiece], new_subpath: bool) {
+    let mut last_point = None;
+
+    for (start, segment) in pieces.iter() {
+        if last_point.is_none() && new_subpath {
+            path.push(PathSegment::Move(*start));
+        } else if last_point != Some(*start) {
+            path.push(PathSegment::Line(*start));
+        }
+
+        path.push(*segment);
+
+        last_point = match segment {
+            PathSegment::Line(end)          => Some(*end),
+            PathSegment::Cubic(_, _, end)   => Some(*end),
+            _                               => last_point
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::PathSegment::*;
+
+    #[test]
+    fn outline_of_line_is_rectangle() {
+        let outline = stroke_outline(&[Move((0.0, 0.0)), Line((10.0, 0.0))], 2.0);
+
+        assert!(outline == vec![
+            Move((0.0, 1.0)),
+            Line((10.0, 1.0)),
+            Line((10.0, -1.0)),
+            Line((0.0, -1.0)),
+            Close
+        ]);
+    }
+
+    #[test]
+    fn outline_of_closed_path_has_two_subpaths() {
+        let outline = stroke_outline(&[Move((0.0, 0.0)), Line((10.0, 0.0)), Line((10.0, 10.0)), Close], 2.0);
+
+        assert!(outline.iter().filter(|segment| segment == &&Close).count() == 2);
+        assert!(outline.iter().filter(|segment| match segment { Move(_) => true, _ => false }).count() == 2);
+    }
+
+    #[test]
+    fn zero_width_stroke_is_empty() {
+        let outline = stroke_outline(&[Move((0.0, 0.0)), Line((10.0, 0.0))], 0.0);
+
+        assert!(outline.len() == 0);
+    }
+}
@@ -0,0 +1,257 @@
+use super::paint::*;
+use super::import_log::*;
+
+///
+/// The style properties that affect how an SVG element is imported
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct SvgStyle {
+    /// How the inside of shapes is painted
+    pub fill: SvgPaint,
+
+    /// How the outline of shapes is painted
+    pub stroke: SvgPaint,
+
+    /// The opacity of the fill
+    pub fill_opacity: f32,
+
+    /// The opacity of the stroke
+    pub stroke_opacity: f32,
+
+    /// The width of the stroke, in user units
+    pub stroke_width: f64,
+
+    /// The opacity of this element
+    pub opacity: f32,
+
+    /// The combined opacity of the groups containing this element (FlowBetween doesn't have group opacity, so this is applied to the fill and stroke directly)
+    pub group_opacity: f32,
+
+    /// The value used for the 'currentColor' paint
+    pub color: SvgColor,
+
+    /// False if the element should not be displayed (this also hides the children of the element)
+    pub display: bool,
+
+    /// False if the element is invisible (unlike display, children can override this)
+    pub visible: bool
+}
+
+impl SvgStyle {
+    ///
+    /// The initial style for an SVG document
+    ///
+    pub fn new() -> SvgStyle {
+        SvgStyle {
+            fill:           SvgPaint::Color((0.0, 0.0, 0.0, 1.0)),
+            stroke:         SvgPaint::None,
+            fill_opacity:   1.0,
+            stroke_opacity: 1.0,
+            stroke_width:   1.0,
+            opacity:        1.0,
+            group_opacity:  1.0,
+            color:          (0.0, 0.0, 0.0, 1.0),
+            display:        true,
+            visible:        true
+        }
+    }
+
+    ///
+    /// Creates the style for a child element (ie, with the inherited properties of this style)
+    ///
+    pub fn inherit(&self) -> SvgStyle {
+        SvgStyle {
+            opacity:        1.0,
+            group_opacity:  self.effective_opacity(),
+            display:        true,
+            ..self.clone()
+        }
+    }
+
+    ///
+    /// The opacity of this element combined with the opacity of its groups
+    ///
+    pub fn effective_opacity(&self) -> f32 {
+        self.opacity * self.group_opacity
+    }
+
+    ///
+    /// Resolves a paint value to a colour
+    ///
+    pub fn color_for_paint(&self, paint: &SvgPaint) -> Option<SvgColor> {
+        match paint {
+            SvgPaint::None              => None,
+            SvgPaint::Color(color)      => Some(*color),
+            SvgPaint::CurrentColor      => Some(self.color),
+            SvgPaint::Unsupported(_)    => None
+        }
+    }
+
+    ///
+    /// Sets a style property from an attribute or a style declaration
+    ///
+    /// Properties that don't affect the import are ignored. If the property can't be represented, the issue is returned
+    /// so it can be added to the import log.
+    ///
+    pub fn set_property(&mut self, name: &str, value: &str) -> Option<ImportIssue> {
+        let value = value.trim();
+
+        // 'inherit' just leaves the inherited value in place
+        if value == "inherit" {
+            return None;
+        }
+
+        match name {
+            "fill" | "stroke" => {
+                let paint = parse_paint(value);
+
+                let issue = match &paint {
+                    SvgPaint::Unsupported(paint)    => Some(ImportIssue::UnsupportedPaint(paint.clone())),
+                    _                               => None
+                };
+
+                if name == "fill" { self.fill = paint; } else { self.stroke = paint; }
+                issue
+            },
+
+            "fill-opacity"      => match parse_opacity(value)   { Ok(opacity) => { self.fill_opacity = opacity; None },     Err(_) => Some(invalid(name, value)) },
+            "stroke-opacity"    => match parse_opacity(value)   { Ok(opacity) => { self.stroke_opacity = opacity; None },   Err(_) => Some(invalid(name, value)) },
+            "opacity"           => match parse_opacity(value)   { Ok(opacity) => { self.opacity = opacity; None },          Err(_) => Some(invalid(name, value)) },
+            "stroke-width"      => match parse_length(value)    { Ok(width) => { self.stroke_width = width; None },         Err(_) => Some(invalid(name, value)) },
+            "color"             => match parse_color(value)     { Some(color) => { self.color = color; None },              None => Some(invalid(name, value)) },
+
+            "display"           => { self.display = value != "none"; None },
+            "visibility"        => { self.visible = value == "visible"; None },
+
+            // Properties that can't be represented
+            "fill-rule" | "clip-rule" if value != "nonzero"             => Some(unsupported(name, value)),
+            "stroke-linecap" if value != "butt"                         => Some(unsupported(name, value)),
+            "stroke-linejoin" if value == "round"                       => Some(unsupported(name, value)),
+            "stroke-dasharray" if value != "none"                       => Some(unsupported(name, value)),
+            "filter" | "mask" | "clip-path" if value != "none"          => Some(unsupported(name, value)),
+            "marker-start" | "marker-mid" | "marker-end" | "marker" if value != "none" => Some(unsupported(name, value)),
+
+            _                   => None
+        }
+    }
+
+    ///
+    /// Sets the properties from the contents of a `style` attribute
+    ///
+    pub fn set_style_attribute(&mut self, style: &str) -> Vec<ImportIssue> {
+        style.split(';')
+            .filter_map(|declaration| {
+                let mut parts = declaration.splitn(2, ':');
+
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value))   => Some((name.trim(), value.trim().trim_end_matches("!important").trim())),
+                    _                           => None
+                }
+            })
+            .filter_map(|(name, value)| self.set_property(name, value))
+            .collect()
+    }
+}
+
+///
+/// Creates an 'invalid value' import issue
+///
+fn invalid(name: &str, value: &str) -> ImportIssue {
+    ImportIssue::InvalidValue(name.to_string(), value.to_string())
+}
+
+///
+/// Creates an 'unsupported attribute' import issue
+///
+fn unsupported(name: &str, value: &str) -> ImportIssue {
+    ImportIssue::UnsupportedAttribute(name.to_string(), value.to_string())
+}
+
+///
+/// Parses an opacity value (a number or a percentage)
+///
+fn parse_opacity(value: &str) -> Result<f32, ()> {
+    let opacity = if value.ends_with('%') {
+        value[0..value.len()-1].trim().parse::<f32>().map_err(|_| ())? / 100.0
+    } else {
+        value.parse::<f32>().map_err(|_| ())?
+    };
+
+    Ok(opacity.max(0.0).min(1.0))
+}
+
+///
+/// Parses an SVG length, converting it to user units (pixels)
+///
+/// Relative units (percentages, em and ex) aren't supported.
+///
+pub fn parse_length(value: &str) -> Result<f64, ()> {
+    let value       = value.trim();
+    let unit_start  = value.find(|c: char| (c.is_ascii_alphabetic() && c != 'e' && c != 'E') || c == '%')
+        .unwrap_or(value.len());
+
+    let number      = value[0..unit_start].trim().parse::<f64>().map_err(|_| ())?;
+    let scale       = match &value[unit_start..] {
+        "" | "px"   => 1.0,
+        "pt"        => 96.0/72.0,
+        "pc"        => 16.0,
+        "in"        => 96.0,
+        "cm"        => 96.0/2.54,
+        "mm"        => 96.0/25.4,
+        _           => { return Err(()); }
+    };
+
+    Ok(number * scale)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_lengths() {
+        assert!(parse_length("10") == Ok(10.0));
+        assert!(parse_length("10px") == Ok(10.0));
+        assert!(parse_length("1in") == Ok(96.0));
+        assert!(parse_length("1e1") == Ok(10.0));
+        assert!(parse_length("50%").is_err());
+        assert!(parse_length("2em").is_err());
+    }
+
+    #[test]
+    fn style_attribute_overrides_properties() {
+        let mut style = SvgStyle::new();
+
+        let issues = style.set_style_attribute("fill: #ff0000; stroke:blue;stroke-width: 2px; opacity: 0.5");
+
+        assert!(issues.len() == 0);
+        assert!(style.fill == SvgPaint::Color((1.0, 0.0, 0.0, 1.0)));
+        assert!(style.stroke == SvgPaint::Color((0.0, 0.0, 1.0, 1.0)));
+        assert!(style.stroke_width == 2.0);
+        assert!(style.opacity == 0.5);
+    }
+
+    #[test]
+    fn opacity_multiplies_with_parent() {
+        let mut parent = SvgStyle::new();
+        parent.set_property("opacity", "0.5");
+
+        let mut child = parent.inherit();
+        child.set_property("opacity", "0.5");
+
+        assert!(child.effective_opacity() == 0.25);
+    }
+
+    #[test]
+    fn unsupported_properties_are_reported() {
+        let mut style = SvgStyle::new();
+
+        let issues = style.set_style_attribute("filter:url(#blur);fill:url(#gradient);fill-rule:evenodd");
+
+        assert!(issues == vec![
+            ImportIssue::UnsupportedAttribute("filter".to_string(), "url(#blur)".to_string()),
+            ImportIssue::UnsupportedPaint("url(#gradient)".to_string()),
+            ImportIssue::UnsupportedAttribute("fill-rule".to_string(), "evenodd".to_string())
+        ]);
+    }
+}
@@ -0,0 +1,622 @@
+use super::error::*;
+use super::import_log::*;
+use super::transform::*;
+use super::path_data::*;
+use super::paint::*;
+use super::style::*;
+use super::stroke::*;
+
+use flo_canvas::*;
+use flo_animation::*;
+
+use roxmltree;
+use futures::*;
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+use std::collections::{HashMap, HashSet};
+
+/// The namespace used for SVG elements
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// The namespace used for `xlink:href` attributes
+const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+
+///
+/// The result of converting an SVG document into animation edits
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct SvgImport {
+    /// The edits that will add the contents of the document to the animation (these should be performed as a single group)
+    pub edits: Vec<AnimationEdit>,
+
+    /// The parts of the document that could not be imported exactly
+    pub log: Vec<ImportLogEntry>
+}
+
+///
+/// Converts an SVG document into the edits required to add it to a layer at a particular time
+///
+/// Every shape becomes a path element: fills become the shape's path and strokes are converted into a path
+/// that outlines the stroke. The top-left corner of the document is placed at the top-left corner of the
+/// frame, so `frame_height` should be the height of the animation.
+///
+pub fn svg_to_edits(svg: &str, layer_id: u64, when: Duration, frame_height: f64) -> Result<SvgImport, SvgImportError> {
+    let document = roxmltree::Document::parse(svg)?;
+    let root     = document.root_element();
+
+    if root.tag_name().name() != "svg" || !is_svg_namespace(root.tag_name().namespace()) {
+        return Err(SvgImportError::NotSvg(root.tag_name().name().to_string()));
+    }
+
+    // SVG's y axis points down, but FlowBetween's points up
+    let flip_y          = SvgTransform(1.0, 0.0, 0.0, -1.0, 0.0, frame_height);
+
+    let mut importer    = SvgImporter::new(&document, layer_id, when);
+    importer.import_svg_element(root, &SvgStyle::new(), &flip_y, true);
+
+    Ok(SvgImport {
+        edits:  importer.edits,
+        log:    importer.log
+    })
+}
+
+///
+/// Imports an SVG document into a layer of an animation at a particular time
+///
+/// The document is added to the keyframe at the specified time (a keyframe is created if the layer doesn't already
+/// have one at this time). All of the edits are sent to the animation as a single group, and the log of the parts
+/// of the document that could not be imported exactly is returned.
+///
+pub fn import_svg<Anim: Animation+EditableAnimation>(animation: &Anim, svg: &str, layer_id: u64, when: Duration) -> Result<Vec<ImportLogEntry>, SvgImportError> {
+    let layer           = animation.get_layer_with_id(layer_id).ok_or(SvgImportError::NoSuchLayer(layer_id))?;
+    let (_, height)     = animation.size();
+
+    let import          = svg_to_edits(svg, layer_id, when, height)?;
+    let mut edits       = import.edits;
+
+    // Add a keyframe if there isn't one already
+    let has_keyframe    = layer.get_key_frames_during_time(when..(when + Duration::from_nanos(1))).any(|keyframe| keyframe == when);
+    if !has_keyframe {
+        edits.insert(0, AnimationEdit::Layer(layer_id, LayerEdit::AddKeyFrame(when)));
+    }
+
+    // Send as a single group of edits
+    let mut edit_sink   = executor::spawn(animation.edit());
+    edit_sink.wait_send(edits).map_err(|_| SvgImportError::EditFailed)?;
+    edit_sink.wait_flush().map_err(|_| SvgImportError::EditFailed)?;
+
+    Ok(import.log)
+}
+
+///
+/// True if an element is in the SVG namespace (documents that don't declare a namespace are also accepted)
+///
+fn is_svg_namespace(namespace: Option<&str>) -> bool {
+    match namespace {
+        None                => true,
+        Some(namespace)     => namespace == SVG_NAMESPACE
+    }
+}
+
+///
+/// Converts a path in SVG coordinates into path components
+///
+fn to_path_components(path: &[PathSegment], transform: &SvgTransform) -> Vec<PathComponent> {
+    let point = |pos: &(f64, f64)| PathPoint { position: *pos };
+
+    transform.transform_path(path).iter()
+        .map(|segment| match segment {
+            PathSegment::Move(pos)              => PathComponent::Move(point(pos)),
+            PathSegment::Line(pos)              => PathComponent::Line(point(pos)),
+            PathSegment::Cubic(cp1, cp2, pos)   => PathComponent::Bezier(point(pos), point(cp1), point(cp2)),
+            PathSegment::Close                  => PathComponent::Close
+        })
+        .collect()
+}
+
+///
+/// Computes the transform that maps a viewBox onto a viewport of a particular size
+///
+fn viewport_transform(view_box: Option<&str>, preserve_aspect_ratio: Option<&str>, width: Option<f64>, height: Option<f64>) -> Result<SvgTransform, ImportIssue> {
+    let view_box = match view_box {
+        None            => { return Ok(SvgTransform::identity()); },
+        Some(view_box)  => view_box
+    };
+
+    let invalid_view_box = || ImportIssue::InvalidValue("viewBox".to_string(), view_box.to_string());
+    let view_box         = parse_number_list(view_box).map_err(|_| invalid_view_box())?;
+
+    if view_box.len() != 4 || view_box[2] <= 0.0 || view_box[3] <= 0.0 {
+        return Err(invalid_view_box());
+    }
+
+    let (min_x, min_y, view_width, view_height) = (view_box[0], view_box[1], view_box[2], view_box[3]);
+
+    // The viewport is the same size as the viewbox if no size is specified
+    let width           = width.unwrap_or(view_width);
+    let height          = height.unwrap_or(view_height);
+    let scale_x         = width / view_width;
+    let scale_y         = height / view_height;
+
+    // Default is to scale uniformly and center the view box in the viewport
+    let mut parts       = preserve_aspect_ratio.unwrap_or("").split_whitespace().filter(|part| *part != "defer");
+    let align           = parts.next().unwrap_or("xMidYMid");
+    let slice           = parts.next() == Some("slice");
+
+    if align == "none" {
+        return Ok(SvgTransform::scale(scale_x, scale_y).then_apply_to(&SvgTransform::translate(-min_x, -min_y)));
+    }
+
+    let scale           = if slice { scale_x.max(scale_y) } else { scale_x.min(scale_y) };
+    let extra_width     = width - view_width*scale;
+    let extra_height    = height - view_height*scale;
+
+    let offset_x        = if align.starts_with("xMin") { 0.0 } else if align.starts_with("xMax") { extra_width } else { extra_width/2.0 };
+    let offset_y        = if align.ends_with("YMin") { 0.0 } else if align.ends_with("YMax") { extra_height } else { extra_height/2.0 };
+
+    Ok(SvgTransform::translate(offset_x, offset_y)
+        .then_apply_to(&SvgTransform::scale(scale, scale))
+        .then_apply_to(&SvgTransform::translate(-min_x, -min_y)))
+}
+
+///
+/// Walks the elements in an SVG document and generates the edits to import it
+///
+struct SvgImporter<'a, 'input: 'a> {
+    /// The layer that the edits will be applied to
+    layer_id: u64,
+
+    /// The time of the keyframe where the elements will be added
+    when: Duration,
+
+    /// The elements in the document with IDs (used for resolving references)
+    elements_with_id: HashMap<&'a str, roxmltree::Node<'a, 'input>>,
+
+    /// The IDs of the elements currently being imported via `<use>` elements (used to detect loops)
+    using_ids: HashSet<&'a str>,
+
+    /// The edits generated so far
+    edits: Vec<AnimationEdit>,
+
+    /// The import log so far
+    log: Vec<ImportLogEntry>
+}
+
+impl<'a, 'input: 'a> SvgImporter<'a, 'input> {
+    ///
+    /// Creates a new importer for a document
+    ///
+    fn new(document: &'a roxmltree::Document<'input>, layer_id: u64, when: Duration) -> SvgImporter<'a, 'input> {
+        let elements_with_id = document.descendants()
+            .filter(|node| node.is_element())
+            .filter_map(|node| node.attribute("id").map(|id| (id, node)))
+            .collect();
+
+        SvgImporter {
+            layer_id:           layer_id,
+            when:               when,
+            elements_with_id:   elements_with_id,
+            using_ids:          HashSet::new(),
+            edits:              vec![],
+            log:                vec![]
+        }
+    }
+
+    ///
+    /// Adds an entry to the import log
+    ///
+    fn log(&mut self, node: roxmltree::Node<'a, 'input>, issue: ImportIssue) {
+        self.log.push(ImportLogEntry {
+            element:    node.tag_name().name().to_string(),
+            element_id: node.attribute("id").map(|id| id.to_string()),
+            issue:      issue
+        });
+    }
+
+    ///
+    /// Works out the style for an element from its attributes and the style of its parent
+    ///
+    fn style_for_element(&mut self, node: roxmltree::Node<'a, 'input>, parent_style: &SvgStyle) -> SvgStyle {
+        let mut style       = parent_style.inherit();
+        let mut issues      = vec![];
+
+        // Presentation attributes
+        for attribute in node.attributes() {
+            if attribute.namespace().is_none() && attribute.name() != "style" {
+                issues.extend(style.set_property(attribute.name(), attribute.value()));
+            }
+        }
+
+        // The style attribute overrides the presentation attributes
+        if let Some(style_attribute) = node.attribute("style") {
+            issues.extend(style.set_style_attribute(style_attribute));
+        }
+
+        issues.into_iter().for_each(|issue| self.log(node, issue));
+
+        style
+    }
+
+    ///
+    /// Adds the element's transform (if it has one) to a transformation matrix
+    ///
+    fn transform_for_element(&mut self, node: roxmltree::Node<'a, 'input>, transform: &SvgTransform) -> Option<SvgTransform> {
+        match node.attribute("transform").map(|transform| parse_transform(transform)) {
+            None                        => Some(*transform),
+            Some(Ok(element_transform)) => Some(transform.then_apply_to(&element_transform)),
+            Some(Err(_))                => {
+                // Elements with invalid transforms are not rendered
+                let value = node.attribute("transform").unwrap_or("").to_string();
+                self.log(node, ImportIssue::InvalidValue("transform".to_string(), value));
+                None
+            }
+        }
+    }
+
+    ///
+    /// Reads a length attribute from an element
+    ///
+    fn length(&mut self, node: roxmltree::Node<'a, 'input>, name: &str) -> Option<f64> {
+        let value = node.attribute(name)?;
+
+        match parse_length(value) {
+            Ok(length)  => Some(length),
+            Err(_)      => {
+                self.log(node, ImportIssue::InvalidValue(name.to_string(), value.to_string()));
+                None
+            }
+        }
+    }
+
+    ///
+    /// Imports an element and its children
+    ///
+    fn import_element(&mut self, node: roxmltree::Node<'a, 'input>, parent_style: &SvgStyle, transform: &SvgTransform) {
+        if !node.is_element() { return; }
+
+        // Elements in other namespaces are usually editor metadata
+        if !is_svg_namespace(node.tag_name().namespace()) { return; }
+
+        match node.tag_name().name() {
+            // Containers
+            "g" | "a" | "switch"    => self.import_group(node, parent_style, transform),
+            "svg"                   => self.import_svg_element(node, parent_style, transform, false),
+            "use"                   => self.import_use(node, parent_style, transform),
+
+            // Shapes
+            "path" | "rect" | "circle" | "ellipse" | "line" | "polyline" | "polygon" => self.import_shape(node, parent_style, transform),
+
+            // Elements that are only used by reference or don't affect the rendering
+            "defs" | "symbol" | "title" | "desc" | "metadata" | "linearGradient" | "radialGradient" | "pattern" | "clipPath" | "mask" | "marker" => { },
+
+            // Anything else (text, images, filters, stylesheets...) can't be imported
+            _ => self.log(node, ImportIssue::UnsupportedElement)
+        }
+    }
+
+    ///
+    /// Imports the children of a group element
+    ///
+    fn import_group(&mut self, node: roxmltree::Node<'a, 'input>, parent_style: &SvgStyle, transform: &SvgTransform) {
+        let style = self.style_for_element(node, parent_style);
+        if !style.display { return; }
+
+        if let Some(transform) = self.transform_for_element(node, transform) {
+            self.import_children(node, &style, &transform);
+        }
+    }
+
+    ///
+    /// Imports the children of an element
+    ///
+    fn import_children(&mut self, node: roxmltree::Node<'a, 'input>, style: &SvgStyle, transform: &SvgTransform) {
+        for child in node.children() {
+            self.import_element(child, style, transform);
+        }
+    }
+
+    ///
+    /// Imports an `<svg>` element, which establishes a new viewport
+    ///
+    fn import_svg_element(&mut self, node: roxmltree::Node<'a, 'input>, parent_style: &SvgStyle, transform: &SvgTransform, is_root: bool) {
+        let style = self.style_for_element(node, parent_style);
+        if !style.display { return; }
+
+        // Nested SVG elements can be positioned within their parent (the position of the root element is ignored)
+        let (x, y)      = if is_root { (None, None) } else { (self.length(node, "x"), self.length(node, "y")) };
+        let width       = self.length(node, "width");
+        let height      = self.length(node, "height");
+        let viewport    = viewport_transform(node.attribute("viewBox"), node.attribute("preserveAspectRatio"), width, height);
+
+        let viewport    = match viewport {
+            Ok(viewport)    => viewport,
+            Err(issue)      => { self.log(node, issue); SvgTransform::identity() }
+        };
+
+        let transform   = transform.then_apply_to(&SvgTransform::translate(x.unwrap_or(0.0), y.unwrap_or(0.0)));
+
+        if let Some(transform) = self.transform_for_element(node, &transform) {
+            self.import_children(node, &style, &transform.then_apply_to(&viewport));
+        }
+    }
+
+    ///
+    /// Imports a `<use>` element, which renders another element in the document
+    ///
+    fn import_use(&mut self, node: roxmltree::Node<'a, 'input>, parent_style: &SvgStyle, transform: &SvgTransform) {
+        let style = self.style_for_element(node, parent_style);
+        if !style.display { return; }
+
+        // Find the element that's being used
+        let href        = node.attribute((XLINK_NAMESPACE, "href")).or_else(|| node.attribute("href")).unwrap_or("");
+        let target_id   = if href.starts_with('#') { &href[1..] } else { "" };
+        let target      = self.elements_with_id.get(target_id).cloned();
+
+        let target      = match target {
+            Some(target) if !self.using_ids.contains(target_id) => target,
+            _                                                   => { self.log(node, ImportIssue::InvalidReference(href.to_string())); return; }
+        };
+
+        // The element is positioned at the x, y coordinates of the use element
+        let x           = self.length(node, "x").unwrap_or(0.0);
+        let y           = self.length(node, "y").unwrap_or(0.0);
+
+        let transform   = match self.transform_for_element(node, transform) {
+            Some(transform) => transform.then_apply_to(&SvgTransform::translate(x, y)),
+            None            => { return; }
+        };
+
+        self.using_ids.insert(target_id);
+
+        if target.tag_name().name() == "symbol" {
+            // Symbols establish a viewport in the same way as an <svg> element, with the size given by the use element
+            let symbol_style    = self.style_for_element(target, &style);
+            let width           = self.length(node, "width");
+            let height          = self.length(node, "height");
+            let viewport        = viewport_transform(target.attribute("viewBox"), target.attribute("preserveAspectRatio"), width, height);
+
+            match viewport {
+                Ok(viewport)    => self.import_children(target, &symbol_style, &transform.then_apply_to(&viewport)),
+                Err(issue)      => self.log(target, issue)
+            }
+        } else {
+            self.import_element(target, &style, &transform);
+        }
+
+        self.using_ids.remove(target_id);
+    }
+
+    ///
+    /// Imports a basic shape or a path element
+    ///
+    fn import_shape(&mut self, node: roxmltree::Node<'a, 'input>, parent_style: &SvgStyle, transform: &SvgTransform) {
+        let style = self.style_for_element(node, parent_style);
+        if !style.display || !style.visible { return; }
+
+        let transform = match self.transform_for_element(node, transform) {
+            Some(transform) => transform,
+            None            => { return; }
+        };
+
+        // Generate the path for this shape
+        let path = match node.tag_name().name() {
+            "path" => {
+                let (path, error) = parse_path_data(node.attribute("d").unwrap_or(""));
+                if let Some(error) = error { self.log(node, ImportIssue::InvalidPathData(error)); }
+
+                path
+            },
+
+            "rect" => {
+                let x           = self.length(node, "x").unwrap_or(0.0);
+                let y           = self.length(node, "y").unwrap_or(0.0);
+                let width       = self.length(node, "width").unwrap_or(0.0);
+                let height      = self.length(node, "height").unwrap_or(0.0);
+                let rx          = self.length(node, "rx");
+                let ry          = self.length(node, "ry");
+
+                // If only one radius is specified, it's used for both
+                let (rx, ry)    = (rx.or(ry).unwrap_or(0.0), ry.or(rx).unwrap_or(0.0));
+
+                if width > 0.0 && height > 0.0 { rect_path(x, y, width, height, rx, ry) } else { vec![] }
+            },
+
+            "circle" => {
+                let cx          = self.length(node, "cx").unwrap_or(0.0);
+                let cy          = self.length(node, "cy").unwrap_or(0.0);
+                let r           = self.length(node, "r").unwrap_or(0.0);
+
+                if r > 0.0 { ellipse_path(cx, cy, r, r) } else { vec![] }
+            },
+
+            "ellipse" => {
+                let cx          = self.length(node, "cx").unwrap_or(0.0);
+                let cy          = self.length(node, "cy").unwrap_or(0.0);
+                let rx          = self.length(node, "rx").unwrap_or(0.0);
+                let ry          = self.length(node, "ry").unwrap_or(0.0);
+
+                if rx > 0.0 && ry > 0.0 { ellipse_path(cx, cy, rx, ry) } else { vec![] }
+            },
+
+            "line" => {
+                let x1          = self.length(node, "x1").unwrap_or(0.0);
+                let y1          = self.length(node, "y1").unwrap_or(0.0);
+                let x2          = self.length(node, "x2").unwrap_or(0.0);
+                let y2          = self.length(node, "y2").unwrap_or(0.0);
+
+                vec![PathSegment::Move((x1, y1)), PathSegment::Line((x2, y2))]
+            },
+
+            "polyline" | "polygon" => {
+                let points = node.attribute("points").unwrap_or("");
+
+                match parse_number_list(points) {
+                    Ok(points)  => poly_path(&points, node.tag_name().name() == "polygon"),
+                    Err(_)      => { self.log(node, ImportIssue::InvalidValue("points".to_string(), points.to_string())); vec![] }
+                }
+            },
+
+            _ => vec![]
+        };
+
+        if path.len() == 0 { return; }
+
+        // Lines have no inside, so are never filled
+        let has_fill = node.tag_name().name() != "line";
+
+        if has_fill {
+            if let Some(color) = style.color_for_paint(&style.fill) {
+                let opacity = color.3 * style.fill_opacity * style.effective_opacity();
+                self.add_path(&path, &transform, color, opacity);
+            }
+        }
+
+        if let Some(color) = style.color_for_paint(&style.stroke) {
+            let opacity = color.3 * style.stroke_opacity * style.effective_opacity();
+            let outline = stroke_outline(&path, style.stroke_width);
+
+            self.add_path(&outline, &transform, color, opacity);
+        }
+    }
+
+    ///
+    /// Adds the edits to create a filled path element
+    ///
+    fn add_path(&mut self, path: &[PathSegment], transform: &SvgTransform, (r, g, b, _): SvgColor, opacity: f32) {
+        if path.len() == 0 || opacity <= 0.0 { return; }
+
+        let mut properties      = BrushProperties::new();
+        properties.color        = Color::Rgba(r, g, b, 1.0);
+        properties.opacity      = opacity;
+
+        let path_edits          = vec![
+            PathEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw),
+            PathEdit::BrushProperties(ElementId::Unassigned, properties),
+            PathEdit::CreatePath(ElementId::Unassigned, Arc::new(to_path_components(path, transform)))
+        ];
+
+        let (layer_id, when)    = (self.layer_id, self.when);
+        self.edits.extend(path_edits.into_iter()
+            .map(|edit| AnimationEdit::Layer(layer_id, LayerEdit::Path(when, edit))));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn paths(import: &SvgImport) -> Vec<Arc<Vec<PathComponent>>> {
+        import.edits.iter()
+            .filter_map(|edit| match edit {
+                AnimationEdit::Layer(_, LayerEdit::Path(_, PathEdit::CreatePath(_, path)))  => Some(Arc::clone(path)),
+                _                                                                           => None
+            })
+            .collect()
+    }
+
+    #[test]
+    fn import_rectangle() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"100\" height=\"100\"><rect x=\"10\" y=\"20\" width=\"30\" height=\"40\" fill=\"#ff0000\"/></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.log.len() == 0);
+        assert!(import.edits.len() == 3);
+        assert!(import.edits[1] == AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties {
+            size:       5.0,
+            opacity:    1.0,
+            color:      Color::Rgba(1.0, 0.0, 0.0, 1.0),
+            gradient:   None
+        }))));
+
+        // Y axis is flipped
+        assert!(*paths(&import)[0] == vec![
+            PathComponent::Move(PathPoint { position: (10.0, 80.0) }),
+            PathComponent::Line(PathPoint { position: (40.0, 80.0) }),
+            PathComponent::Line(PathPoint { position: (40.0, 40.0) }),
+            PathComponent::Line(PathPoint { position: (10.0, 40.0) }),
+            PathComponent::Close
+        ]);
+    }
+
+    #[test]
+    fn group_transforms_and_styles_are_inherited() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\"><g transform=\"translate(5 5)\" style=\"fill:blue;opacity:0.5\"><path d=\"M0 0 L10 0 L10 10 Z\"/></g></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.log.len() == 0);
+        assert!(import.edits[1] == AnimationEdit::Layer(1, LayerEdit::Path(Duration::from_millis(0), PathEdit::BrushProperties(ElementId::Unassigned, BrushProperties {
+            size:       5.0,
+            opacity:    0.5,
+            color:      Color::Rgba(0.0, 0.0, 1.0, 1.0),
+            gradient:   None
+        }))));
+        assert!(paths(&import)[0][0] == PathComponent::Move(PathPoint { position: (5.0, 95.0) }));
+    }
+
+    #[test]
+    fn strokes_become_separate_paths() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\"><rect width=\"10\" height=\"10\" fill=\"none\" stroke=\"black\" stroke-width=\"2\"/><rect width=\"10\" height=\"10\" stroke=\"black\"/></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.log.len() == 0);
+        assert!(paths(&import).len() == 3);
+    }
+
+    #[test]
+    fn view_box_scales_document() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"200\" viewBox=\"0 0 100 100\"><rect x=\"10\" y=\"10\" width=\"10\" height=\"10\"/></svg>", 1, Duration::from_millis(0), 200.0).unwrap();
+
+        assert!(paths(&import)[0][0] == PathComponent::Move(PathPoint { position: (20.0, 180.0) }));
+    }
+
+    #[test]
+    fn use_element_imports_referenced_element() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\"><defs><rect id=\"r\" width=\"10\" height=\"10\"/></defs><use xlink:href=\"#r\" x=\"20\"/><use href=\"#r\" x=\"40\"/></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.log.len() == 0);
+        assert!(paths(&import).len() == 2);
+        assert!(paths(&import)[0][0] == PathComponent::Move(PathPoint { position: (20.0, 100.0) }));
+        assert!(paths(&import)[1][0] == PathComponent::Move(PathPoint { position: (40.0, 100.0) }));
+    }
+
+    #[test]
+    fn use_loops_are_reported() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\"><g id=\"loop\"><use href=\"#loop\"/></g></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.log == vec![ImportLogEntry { element: "use".to_string(), element_id: None, issue: ImportIssue::InvalidReference("#loop".to_string()) }]);
+    }
+
+    #[test]
+    fn unsupported_elements_are_logged() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\"><text id=\"title\">Hello</text><rect width=\"10\" height=\"10\" filter=\"url(#blur)\"/><filter id=\"blur\"/></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(paths(&import).len() == 1);
+        assert!(import.log == vec![
+            ImportLogEntry { element: "text".to_string(), element_id: Some("title".to_string()), issue: ImportIssue::UnsupportedElement },
+            ImportLogEntry { element: "rect".to_string(), element_id: None, issue: ImportIssue::UnsupportedAttribute("filter".to_string(), "url(#blur)".to_string()) },
+            ImportLogEntry { element: "filter".to_string(), element_id: Some("blur".to_string()), issue: ImportIssue::UnsupportedElement }
+        ]);
+    }
+
+    #[test]
+    fn hidden_elements_are_not_imported() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\"><g display=\"none\"><rect width=\"10\" height=\"10\"/></g><rect width=\"10\" height=\"10\" visibility=\"hidden\"/></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.edits.len() == 0);
+    }
+
+    #[test]
+    fn metadata_in_other_namespaces_is_ignored() {
+        let import = svg_to_edits("<svg xmlns=\"http://www.w3.org/2000/svg\" xmlns:sodipodi=\"http://sodipodi.sourceforge.net/DTD/sodipodi-0.dtd\"><sodipodi:namedview/><rect width=\"10\" height=\"10\"/></svg>", 1, Duration::from_millis(0), 100.0).unwrap();
+
+        assert!(import.log.len() == 0);
+        assert!(paths(&import).len() == 1);
+    }
+
+    #[test]
+    fn non_svg_document_is_an_error() {
+        match svg_to_edits("<html/>", 1, Duration::from_millis(0), 100.0) {
+            Err(SvgImportError::NotSvg(name))   => assert!(name == "html"),
+            _                                   => assert!(false)
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use super::path_data::*;
+
+use std::f64;
+
+///
+/// An SVG affine transformation matrix
+///
+/// The values are in the order used by the SVG `matrix(a b c d e f)` transform, so a point
+/// `(x, y)` is transformed to `(a*x + c*y + e, b*x + d*y + f)`.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SvgTransform(pub f64, pub f64, pub f64, pub f64, pub f64, pub f64);
+
+impl SvgTransform {
+    ///
+    /// The identity transform
+    ///
+    pub fn identity() -> SvgTransform {
+        SvgTransform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)
+    }
+
+    ///
+    /// A translation
+    ///
+    pub fn translate(x: f64, y: f64) -> SvgTransform {
+        SvgTransform(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    ///
+    /// A scale about the origin
+    ///
+    pub fn scale(x: f64, y: f64) -> SvgTransform {
+        SvgTransform(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    ///
+    /// A rotation about the origin (in degrees, as for SVG)
+    ///
+    pub fn rotate(degrees: f64) -> SvgTransform {
+        let radians = degrees.to_radians();
+        let (s, c)  = (radians.sin(), radians.cos());
+
+        SvgTransform(c, s, -s, c, 0.0, 0.0)
+    }
+
+    ///
+    /// A skew along the x axis (in degrees)
+    ///
+    pub fn skew_x(degrees: f64) -> SvgTransform {
+        SvgTransform(1.0, 0.0, degrees.to_radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    ///
+    /// A skew along the y axis (in degrees)
+    ///
+    pub fn skew_y(degrees: f64) -> SvgTransform {
+        SvgTransform(1.0, degrees.to_radians().tan(), 0.0, 1.0, 0.0, 0.0)
+    }
+
+    ///
+    /// Returns the transform that applies `next` and then this transform
+    ///
+    /// This is the order used by SVG: `transform="translate(..) rotate(..)"` rotates the points first,
+    /// and a transform on a group applies after any transforms on its children.
+    ///
+    pub fn then_apply_to(&self, next: &SvgTransform) -> SvgTransform {
+        let SvgTransform(a1, b1, c1, d1, e1, f1) = *self;
+        let SvgTransform(a2, b2, c2, d2, e2, f2) = *next;
+
+        SvgTransform(
+            a1*a2 + c1*b2,
+            b1*a2 + d1*b2,
+            a1*c2 + c1*d2,
+            b1*c2 + d1*d2,
+            a1*e2 + c1*f2 + e1,
+            b1*e2 + d1*f2 + f1
+        )
+    }
+
+    ///
+    /// Transforms a point
+    ///
+    pub fn transform_point(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let SvgTransform(a, b, c, d, e, f) = *self;
+
+        (a*x + c*y + e, b*x + d*y + f)
+    }
+
+    ///
+    /// Transforms every point in a path
+    ///
+    pub fn transform_path(&self, path: &[PathSegment]) -> Vec<PathSegment> {
+        use self::PathSegment::*;
+
+        path.iter()
+            .map(|segment| match segment {
+                Move(pos)               => Move(self.transform_point(*pos)),
+                Line(pos)               => Line(self.transform_point(*pos)),
+                Cubic(cp1, cp2, pos)    => Cubic(self.transform_point(*cp1), self.transform_point(*cp2), self.transform_point(*pos)),
+                Close                   => Close
+            })
+            .collect()
+    }
+}
+
+///
+/// Parses the value of an SVG `transform` attribute
+///
+pub fn parse_transform(transform: &str) -> Result<SvgTransform, String> {
+    let mut result      = SvgTransform::identity();
+    let mut remaining   = transform.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+
+    while !remaining.is_empty() {
+        // Each transform is in the form 'name(args)'
+        let open_bracket    = remaining.find('(').ok_or_else(|| format!("Expected '(' in '{}'", remaining))?;
+        let close_bracket   = remaining.find(')').ok_or_else(|| format!("Expected ')' in '{}'", remaining))?;
+
+        if close_bracket < open_bracket {
+            return Err(format!("Unexpected ')' in '{}'", remaining));
+        }
+
+        let name            = remaining[0..open_bracket].trim();
+        let args            = parse_number_list(&remaining[(open_bracket+1)..close_bracket])?;
+
+        let next = match (name, args.len()) {
+            ("matrix", 6)       => SvgTransform(args[0], args[1], args[2], args[3], args[4], args[5]),
+            ("translate", 1)    => SvgTransform::translate(args[0], 0.0),
+            ("translate", 2)    => SvgTransform::translate(args[0], args[1]),
+            ("scale", 1)        => SvgTransform::scale(args[0], args[0]),
+            ("scale", 2)        => SvgTransform::scale(args[0], args[1]),
+            ("rotate", 1)       => SvgTransform::rotate(args[0]),
+            ("rotate", 3)       => SvgTransform::translate(args[1], args[2])
+                                    .then_apply_to(&SvgTransform::rotate(args[0]))
+                                    .then_apply_to(&SvgTransform::translate(-args[1], -args[2])),
+            ("skewX", 1)        => SvgTransform::skew_x(args[0]),
+            ("skewY", 1)        => SvgTransform::skew_y(args[0]),
+
+            _                   => { return Err(format!("Invalid transform '{}'", &remaining[0..=close_bracket])); }
+        };
+
+        result      = result.then_apply_to(&next);
+        remaining   = remaining[(close_bracket+1)..].trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn close_to(a: (f64, f64), b: (f64, f64)) -> bool {
+        (a.0-b.0).abs() < 0.0001 && (a.1-b.1).abs() < 0.0001
+    }
+
+    #[test]
+    fn transforms_apply_right_to_left() {
+        let transform = parse_transform("translate(10, 20) scale(2)").unwrap();
+
+        assert!(close_to(transform.transform_point((1.0, 1.0)), (12.0, 22.0)));
+    }
+
+    #[test]
+    fn rotate_about_point() {
+        let transform = parse_transform("rotate(90 10 10)").unwrap();
+
+        assert!(close_to(transform.transform_point((20.0, 10.0)), (10.0, 20.0)));
+    }
+
+    #[test]
+    fn matrix_transform() {
+        let transform = parse_transform("matrix(1,0,0,1,5,6)").unwrap();
+
+        assert!(close_to(transform.transform_point((1.0, 2.0)), (6.0, 8.0)));
+    }
+
+    #[test]
+    fn invalid_transform_is_an_error() {
+        assert!(parse_transform("wobble(3)").is_err());
+        assert!(parse_transform("scale(1, 2, 3)").is_err());
+    }
+}
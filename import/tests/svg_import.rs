@@ -0,0 +1,79 @@
+extern crate flo_import;
+extern crate flo_animation;
+extern crate flo_anim_sqlite;
+extern crate futures;
+
+use flo_import::*;
+use flo_animation::*;
+use flo_anim_sqlite::*;
+
+use futures::executor;
+
+use std::time::Duration;
+
+const DRAWING: &str = "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"100\">
+    <rect x=\"10\" y=\"10\" width=\"50\" height=\"20\" fill=\"red\"/>
+    <g transform=\"translate(100 50)\" fill=\"#00ff00\">
+        <circle r=\"20\" stroke=\"black\" stroke-width=\"2\"/>
+    </g>
+    <path d=\"M10 90 Q 50 60 90 90\" fill=\"none\" stroke=\"blue\" stroke-width=\"4\"/>
+    <text x=\"10\" y=\"50\">Not imported</text>
+    <rect id=\"blurred\" width=\"10\" height=\"10\" filter=\"url(#blur)\"/>
+</svg>";
+
+fn create_animation() -> SqliteAnimation {
+    let animation = SqliteAnimation::new_in_memory();
+
+    executor::spawn(animation.edit()).wait_send(vec![
+        AnimationEdit::SetSize(200.0, 100.0),
+        AnimationEdit::AddNewLayer(1)
+    ]).unwrap();
+
+    animation
+}
+
+fn path_elements<Anim: Animation>(animation: &Anim, when: Duration) -> Vec<PathElement> {
+    animation.get_layer_with_id(1).unwrap()
+        .get_frame_at_time(when)
+        .vector_elements()
+        .map(|elements| elements.filter_map(|element| match element {
+            Vector::Path(path)  => Some(path),
+            _                   => None
+        }).collect())
+        .unwrap_or(vec![])
+}
+
+#[test]
+fn import_drawing_into_layer() {
+    let animation   = create_animation();
+    let log         = import_svg(&animation, DRAWING, 1, Duration::from_millis(0)).unwrap();
+
+    // Rectangle, circle fill, circle stroke, path stroke and the blurred rectangle
+    assert!(path_elements(&animation, Duration::from_millis(0)).len() == 5);
+
+    assert!(log.len() == 2);
+    assert!(log[0] == ImportLogEntry { element: "text".to_string(), element_id: None, issue: ImportIssue::UnsupportedElement });
+    assert!(log[1] == ImportLogEntry { element: "rect".to_string(), element_id: Some("blurred".to_string()), issue: ImportIssue::UnsupportedAttribute("filter".to_string(), "url(#blur)".to_string()) });
+}
+
+#[test]
+fn import_creates_keyframe() {
+    let animation   = create_animation();
+    import_svg(&animation, DRAWING, 1, Duration::from_millis(500)).unwrap();
+
+    let layer       = animation.get_layer_with_id(1).unwrap();
+    let keyframes   = layer.get_key_frames_during_time(Duration::from_millis(0)..Duration::from_millis(1000)).collect::<Vec<_>>();
+
+    assert!(keyframes == vec![Duration::from_millis(500)]);
+    assert!(path_elements(&animation, Duration::from_millis(500)).len() == 5);
+}
+
+#[test]
+fn import_into_missing_layer_fails() {
+    let animation = create_animation();
+
+    match import_svg(&animation, DRAWING, 2, Duration::from_millis(0)) {
+        Err(SvgImportError::NoSuchLayer(2)) => { },
+        _                                   => assert!(false)
+    }
+}
@@ -0,0 +1,409 @@
+use super::error::*;
+use super::message::*;
+use super::connection::*;
+use super::element_id_range::*;
+use super::server::ELEMENT_IDS_PER_RANGE;
+
+use flo_animation::*;
+
+use futures::*;
+use futures::executor;
+use tungstenite;
+use url::Url;
+
+use std::thread;
+use std::sync::*;
+use std::sync::mpsc;
+use std::net::TcpStream;
+use std::ops::Range;
+use std::time::Duration;
+
+/// When a client has fewer than this many element IDs left, it will ask the server for more
+const LOW_ELEMENT_IDS: i64 = ELEMENT_IDS_PER_RANGE/4;
+
+///
+/// The state of a sync client
+///
+struct SyncClientState {
+    /// The element IDs that this client is currently using
+    element_ids: ElementIdRange,
+
+    /// The element IDs to use once the current range runs out
+    next_element_ids: Option<ElementIdRange>,
+
+    /// True if this client has asked the server for more element IDs
+    requested_element_ids: bool,
+
+    /// The sequence number of the last batch of edits applied to the animation
+    applied_sequence: u64,
+
+    /// The number of batches of edits that have been submitted by this client but not yet applied or rejected
+    pending_batches: usize,
+
+    /// The reasons the server gave for rejecting any edits from this client
+    rejected: Vec<String>,
+
+    /// False once the connection to the server has been closed
+    connected: bool
+}
+
+///
+/// The part of the client that is shared with the thread that runs the connection
+///
+struct SyncClientCore {
+    /// The ID the server assigned to this client
+    client_id: u64,
+
+    /// The state of this client
+    state: Mutex<SyncClientState>,
+
+    /// Notified whenever the state changes
+    state_changed: Condvar
+}
+
+///
+/// Sends edits and requests to the server
+///
+struct ServerConnection {
+    /// The core of the client
+    core: Arc<SyncClientCore>,
+
+    /// Channel used to send messages to the server (the connection closes when this is dropped)
+    server: Mutex<mpsc::Sender<SyncMessage>>
+}
+
+///
+/// A copy of an animation that's kept in sync with the other clients of a `SyncServer`
+///
+/// Edits sent to this animation are applied once the server sends them back, in the same order that
+/// every other client applies them in. Any new elements are given IDs from ranges reserved for this
+/// client before they're sent, so IDs assigned by different clients never collide.
+///
+pub struct SyncClient<Anim: Animation> {
+    /// The local copy of the animation
+    animation: Arc<Anim>,
+
+    /// The connection to the server
+    connection: Arc<ServerConnection>
+}
+
+///
+/// Sink that submits edits to the server
+///
+struct SubmitEditsSink {
+    connection: Arc<ServerConnection>
+}
+
+impl<Anim: 'static+Animation+EditableAnimation> SyncClient<Anim> {
+    ///
+    /// Connects to the sync server at the specified URL, keeping the specified animation up to date with the edits
+    /// made by all of the clients
+    ///
+    /// The animation should start in the same state as the animation on every other client (eg, empty).
+    ///
+    pub fn connect(url: &str, animation: Anim) -> Result<SyncClient<Anim>, SyncError> {
+        // Connect to the server
+        let url                 = Url::parse(url)?;
+        let host                = url.host_str().ok_or(SyncError::MissingHost)?.to_string();
+        let port                = url.port_or_known_default().unwrap_or(80);
+        let stream              = TcpStream::connect((host.as_str(), port))?;
+        let (mut socket, _)     = tungstenite::client(url, stream)?;
+
+        // The server starts by telling us who we are
+        let (client_id, element_ids) = match receive_message(&mut socket)? {
+            SyncMessage::Welcome(client_id, element_ids)    => (client_id, element_ids),
+            other                                           => { return Err(SyncError::UnexpectedMessage(format!("{:?}", other))); }
+        };
+
+        let core = Arc::new(SyncClientCore {
+            client_id:      client_id,
+            state:          Mutex::new(SyncClientState {
+                element_ids:            element_ids,
+                next_element_ids:       None,
+                requested_element_ids:  false,
+                applied_sequence:       0,
+                pending_batches:        0,
+                rejected:               vec![],
+                connected:              true
+            }),
+            state_changed:  Condvar::new()
+        });
+
+        // Run the connection on its own thread
+        let animation           = Arc::new(animation);
+        let (server, outgoing)  = mpsc::channel();
+        let connection_core     = Arc::clone(&core);
+        let connection_anim     = Arc::clone(&animation);
+
+        thread::spawn(move || {
+            let _ = run_connection(socket, outgoing, |message| connection_core.receive(&*connection_anim, message));
+
+            connection_core.state.lock().unwrap().connected = false;
+            connection_core.state_changed.notify_all();
+        });
+
+        Ok(SyncClient {
+            animation:  animation,
+            connection: Arc::new(ServerConnection {
+                core:   core,
+                server: Mutex::new(server)
+            })
+        })
+    }
+}
+
+impl<Anim: Animation> SyncClient<Anim> {
+    ///
+    /// The ID that the server assigned to this client
+    ///
+    pub fn client_id(&self) -> u64 {
+        self.connection.core.client_id
+    }
+
+    ///
+    /// True if this client is still connected to the server
+    ///
+    pub fn is_connected(&self) -> bool {
+        self.connection.core.state.lock().unwrap().connected
+    }
+
+    ///
+    /// The sequence number of the last batch of edits that was applied to the animation
+    ///
+    pub fn applied_sequence(&self) -> u64 {
+        self.connection.core.state.lock().unwrap().applied_sequence
+    }
+
+    ///
+    /// Retrieves the reasons the server has given for rejecting edits from this client
+    ///
+    pub fn rejected_edits(&self) -> Vec<String> {
+        self.connection.core.state.lock().unwrap().rejected.clone()
+    }
+
+    ///
+    /// Sends a batch of edits to the server, assigning IDs to any new elements
+    ///
+    pub fn submit(&self, edits: Vec<AnimationEdit>) -> Result<(), SyncError> {
+        self.connection.submit(edits)
+    }
+
+    ///
+    /// Waits until every batch of edits submitted by this client has been applied to the animation (or rejected),
+    /// returning the sequence number of the last batch that was applied
+    ///
+    pub fn flush(&self) -> u64 {
+        let core        = &self.connection.core;
+        let mut state   = core.state.lock().unwrap();
+
+        while state.pending_batches > 0 && state.connected {
+            state = core.state_changed.wait(state).unwrap();
+        }
+
+        state.applied_sequence
+    }
+
+    ///
+    /// Waits until the batch of edits with the specified sequence number has been applied to the animation
+    ///
+    pub fn wait_for_sequence(&self, sequence: u64) {
+        let core        = &self.connection.core;
+        let mut state   = core.state.lock().unwrap();
+
+        while state.applied_sequence < sequence && state.connected {
+            state = core.state_changed.wait(state).unwrap();
+        }
+    }
+}
+
+impl SyncClientCore {
+    ///
+    /// Processes a message from the server
+    ///
+    fn receive<Anim: Animation+EditableAnimation>(&self, animation: &Anim, message: SyncMessage) {
+        use self::SyncMessage::*;
+
+        match message {
+            Edits(edits) => {
+                // Apply the edits to our copy of the animation
+                let SequencedEdits { sequence, client_id, edits } = edits;
+                executor::spawn(animation.edit()).wait_send(edits).ok();
+
+                let mut state           = self.state.lock().unwrap();
+                state.applied_sequence  = sequence;
+                if client_id == self.client_id {
+                    state.pending_batches = state.pending_batches.saturating_sub(1);
+                }
+            },
+
+            AssignElementIds(element_ids) => {
+                let mut state                   = self.state.lock().unwrap();
+                state.next_element_ids          = Some(element_ids);
+                state.requested_element_ids     = false;
+            },
+
+            Rejected(reason) => {
+                let mut state           = self.state.lock().unwrap();
+                state.pending_batches   = state.pending_batches.saturating_sub(1);
+                state.rejected.push(reason);
+            },
+
+            // Messages that only clients send are ignored
+            Welcome(_, _)       |
+            Submit(_)           |
+            RequestElementIds   => { }
+        }
+
+        self.state_changed.notify_all();
+    }
+}
+
+impl ServerConnection {
+    ///
+    /// Sends a message to the server
+    ///
+    fn send(&self, message: SyncMessage) -> Result<(), SyncError> {
+        self.server.lock().unwrap().send(message).map_err(|_| SyncError::Disconnected)
+    }
+
+    ///
+    /// Assigns a new element ID from the ranges set aside for this client, or returns None if we've run out
+    /// of IDs and are no longer connected to the server
+    ///
+    fn assign_element_id(&self) -> Option<i64> {
+        let core        = &self.core;
+        let mut state   = core.state.lock().unwrap();
+
+        loop {
+            if let Some(element_id) = state.element_ids.take_id() {
+                // Ask for more IDs before we run out
+                if state.element_ids.len() < LOW_ELEMENT_IDS && state.next_element_ids.is_none() && !state.requested_element_ids {
+                    state.requested_element_ids = true;
+                    self.send(SyncMessage::RequestElementIds).ok();
+                }
+
+                return Some(element_id);
+            } else if let Some(next_element_ids) = state.next_element_ids.take() {
+                // Move on to the next range
+                state.element_ids = next_element_ids;
+            } else if !state.connected {
+                // No more IDs are coming
+                return None;
+            } else {
+                // Wait for the server to send more IDs
+                state = core.state_changed.wait(state).unwrap();
+            }
+        }
+    }
+
+    ///
+    /// Assigns IDs to the new elements in a set of edits and sends them to the server
+    ///
+    fn submit(&self, edits: Vec<AnimationEdit>) -> Result<(), SyncError> {
+        // Assign IDs to any new elements
+        let mut assigned = vec![];
+        for edit in edits {
+            let mut out_of_ids  = false;
+            let edit            = edit.assign_element_id(|| self.assign_element_id().unwrap_or_else(|| { out_of_ids = true; 0 }));
+
+            if out_of_ids {
+                return Err(SyncError::Disconnected);
+            }
+
+            assigned.push(edit);
+        }
+
+        // Send to the server
+        self.core.state.lock().unwrap().pending_batches += 1;
+
+        let result = self.send(SyncMessage::Submit(assigned));
+        if result.is_err() {
+            self.core.state.lock().unwrap().pending_batches -= 1;
+        }
+
+        result
+    }
+}
+
+impl Sink for SubmitEditsSink {
+    type SinkItem   = Vec<AnimationEdit>;
+    type SinkError  = ();
+
+    fn start_send(&mut self, edits: Vec<AnimationEdit>) -> StartSend<Vec<AnimationEdit>, ()> {
+        self.connection.submit(edits).map_err(|_| ())?;
+
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Poll<(), ()> {
+        Ok(Async::Ready(()))
+    }
+}
+
+impl<Anim: Animation> Animation for SyncClient<Anim> {
+    fn size(&self) -> (f64, f64) {
+        self.animation.size()
+    }
+
+    fn duration(&self) -> Duration {
+        self.animation.duration()
+    }
+
+    fn frame_length(&self) -> Duration {
+        self.animation.frame_length()
+    }
+
+    fn get_layer_ids(&self) -> Vec<u64> {
+        self.animation.get_layer_ids()
+    }
+
+    fn get_layer_with_id(&self, layer_id: u64) -> Option<Arc<dyn Layer>> {
+        self.animation.get_layer_with_id(layer_id)
+    }
+
+    fn get_num_edits(&self) -> usize {
+        self.animation.get_num_edits()
+    }
+
+    fn read_edit_log<'a>(&'a self, range: Range<usize>) -> Box<dyn 'a+Stream<Item=AnimationEdit, Error=()>> {
+        self.animation.read_edit_log(range)
+    }
+
+    fn motion<'a>(&'a self) -> &'a dyn AnimationMotion {
+        self
+    }
+}
+
+impl<Anim: Animation> AnimationMotion for SyncClient<Anim> {
+    ///
+    /// Assigns an ID from the ranges set aside for this client, so it won't be used by any other client
+    ///
+    fn assign_element_id(&self) -> ElementId {
+        self.connection.assign_element_id()
+            .map(|element_id| ElementId::Assigned(element_id))
+            .unwrap_or(ElementId::Unassigned)
+    }
+
+    fn get_motions_for_element(&self, element_id: ElementId) -> Vec<ElementId> {
+        self.animation.motion().get_motions_for_element(element_id)
+    }
+
+    fn get_elements_for_motion(&self, motion_id: ElementId) -> Vec<ElementId> {
+        self.animation.motion().get_elements_for_motion(motion_id)
+    }
+
+    fn get_motion(&self, motion_id: ElementId) -> Option<Motion> {
+        self.animation.motion().get_motion(motion_id)
+    }
+}
+
+impl<Anim: Animation> EditableAnimation for SyncClient<Anim> {
+    ///
+    /// Edits sent to this sink are sent to the server, and applied to the animation once the server sends them back
+    ///
+    fn edit(&self) -> Box<dyn Sink<SinkItem=Vec<AnimationEdit>, SinkError=()>+Send> {
+        Box::new(SubmitEditsSink {
+            connection: Arc::clone(&self.connection)
+        })
+    }
+}
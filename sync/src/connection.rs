@@ -0,0 +1,71 @@
+use super::error::*;
+use super::message::*;
+
+use tungstenite::{WebSocket, Message};
+
+use std::io;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::mpsc;
+use std::sync::mpsc::TryRecvError;
+use std::time::Duration;
+
+/// How long a connection waits for a message to arrive before checking for messages to send
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+///
+/// Sends a message to a websocket
+///
+pub fn send_message<Stream: Read+Write>(socket: &mut WebSocket<Stream>, message: &SyncMessage) -> Result<(), SyncError> {
+    socket.write_message(Message::Text(message.to_json()))?;
+
+    Ok(())
+}
+
+///
+/// Waits for the next message to arrive on a websocket
+///
+pub fn receive_message<Stream: Read+Write>(socket: &mut WebSocket<Stream>) -> Result<SyncMessage, SyncError> {
+    loop {
+        // Frames that aren't text (pings, etc) aren't part of the protocol
+        if let Message::Text(json) = socket.read_message()? {
+            return SyncMessage::from_json(&json);
+        }
+    }
+}
+
+///
+/// Runs a connection until it's closed, writing any messages sent to the `outgoing` channel to the websocket, and
+/// calling `incoming` for every message that arrives from it
+///
+/// Only a single thread can use a websocket, so this polls for incoming messages rather than blocking until one
+/// arrives. The connection is closed when the sender for the `outgoing` channel is dropped.
+///
+pub fn run_connection<IncomingFn: FnMut(SyncMessage) -> ()>(mut socket: WebSocket<TcpStream>, outgoing: mpsc::Receiver<SyncMessage>, mut incoming: IncomingFn) -> Result<(), SyncError> {
+    socket.get_mut().set_read_timeout(Some(POLL_INTERVAL))?;
+
+    loop {
+        // Send any messages that are waiting
+        loop {
+            match outgoing.try_recv() {
+                Ok(message)                     => send_message(&mut socket, &message)?,
+                Err(TryRecvError::Empty)        => break,
+                Err(TryRecvError::Disconnected) => {
+                    socket.close(None)?;
+                    return Ok(());
+                }
+            }
+        }
+
+        // Wait for the next incoming message
+        match socket.read_message() {
+            Ok(Message::Text(json))     => incoming(SyncMessage::from_json(&json)?),
+            Ok(_)                       => { },
+
+            // Timeouts just mean that no message has arrived yet
+            Err(tungstenite::Error::Io(ref err)) if err.kind() == io::ErrorKind::WouldBlock || err.kind() == io::ErrorKind::TimedOut => { },
+
+            Err(err)                    => return Err(err.into())
+        }
+    }
+}
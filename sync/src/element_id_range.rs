@@ -0,0 +1,71 @@
+///
+/// A range of element IDs that the server has set aside for a single client
+///
+/// Clients only give new elements IDs from the ranges they've been given, so elements created by
+/// different clients at the same time never end up with the same ID.
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ElementIdRange {
+    /// The next ID in this range
+    pub start: i64,
+
+    /// The ID after the last ID in this range
+    pub end: i64
+}
+
+impl ElementIdRange {
+    ///
+    /// Creates a new element ID range
+    ///
+    pub fn new(start: i64, end: i64) -> ElementIdRange {
+        ElementIdRange {
+            start:  start,
+            end:    end
+        }
+    }
+
+    ///
+    /// The number of IDs remaining in this range
+    ///
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    ///
+    /// True if there are no IDs remaining in this range
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Removes the next ID from this range, if there are any left
+    ///
+    pub fn take_id(&mut self) -> Option<i64> {
+        if self.start < self.end {
+            let id      = self.start;
+            self.start  += 1;
+
+            Some(id)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn take_all_ids() {
+        let mut range = ElementIdRange::new(10, 13);
+
+        assert!(range.len() == 3);
+        assert!(range.take_id() == Some(10));
+        assert!(range.take_id() == Some(11));
+        assert!(range.take_id() == Some(12));
+        assert!(range.take_id() == None);
+        assert!(range.is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+use tungstenite;
+use tungstenite::handshake::{HandshakeError, HandshakeRole};
+use serde_json;
+use url;
+
+use std::io;
+
+///
+/// Errors that can occur while synchronising an animation
+///
+#[derive(Debug)]
+pub enum SyncError {
+    /// The address of the server could not be parsed
+    InvalidUrl(url::ParseError),
+
+    /// The server address does not have a host name
+    MissingHost,
+
+    /// An error occurred with the network connection
+    Io(io::Error),
+
+    /// An error occurred with the websocket
+    WebSocket(tungstenite::Error),
+
+    /// The websocket handshake could not be completed
+    HandshakeInterrupted,
+
+    /// A message could not be decoded
+    InvalidMessage(serde_json::Error),
+
+    /// The server sent a message that was not expected at this point
+    UnexpectedMessage(String),
+
+    /// The connection to the server has been closed
+    Disconnected
+}
+
+impl From<url::ParseError> for SyncError {
+    fn from(err: url::ParseError) -> SyncError {
+        SyncError::InvalidUrl(err)
+    }
+}
+
+impl From<io::Error> for SyncError {
+    fn from(err: io::Error) -> SyncError {
+        SyncError::Io(err)
+    }
+}
+
+impl From<tungstenite::Error> for SyncError {
+    fn from(err: tungstenite::Error) -> SyncError {
+        SyncError::WebSocket(err)
+    }
+}
+
+impl<Role: HandshakeRole> From<HandshakeError<Role>> for SyncError {
+    fn from(err: HandshakeError<Role>) -> SyncError {
+        match err {
+            HandshakeError::Failure(err)    => SyncError::WebSocket(err),
+            HandshakeError::Interrupted(_)  => SyncError::HandshakeInterrupted
+        }
+    }
+}
+
+impl From<serde_json::Error> for SyncError {
+    fn from(err: serde_json::Error) -> SyncError {
+        SyncError::InvalidMessage(err)
+    }
+}
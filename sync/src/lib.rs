@@ -0,0 +1,39 @@
+//!
+//! Collaborative editing for FlowBetween animations
+//!
+//! A `SyncServer` shares an animation between any number of `SyncClient`s, which exchange batches of
+//! `AnimationEdit`s with it as JSON over a websocket. Each client keeps its own copy of the animation.
+//!
+//! The server decides the order of the edits: every batch it accepts is given a sequence number and
+//! sent to all of the clients (including the one that submitted it), and clients only apply edits in
+//! this order. Edits are therefore not visible in a client until the server has sent them back, but
+//! every copy of the animation ends up the same. When two clients change the same thing at the same
+//! time, the batch that reaches the server last wins.
+//!
+//! New elements need IDs that are the same in every copy of the animation, so clients assign them
+//! before submitting their edits, from ranges of IDs that the server has set aside for that client.
+//!
+#![warn(bare_trait_objects)]
+
+extern crate flo_animation;
+
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+extern crate futures;
+extern crate tungstenite;
+extern crate url;
+
+mod error;
+mod message;
+mod element_id_range;
+mod connection;
+mod server;
+mod client;
+
+pub use self::error::*;
+pub use self::message::*;
+pub use self::element_id_range::*;
+pub use self::server::*;
+pub use self::client::*;
@@ -0,0 +1,124 @@
+use super::error::*;
+use super::element_id_range::*;
+
+use flo_animation::*;
+
+use serde_json;
+
+///
+/// A batch of edits, along with its position in the order that every client applies edits in
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SequencedEdits {
+    /// The position of this batch in the sequence of edits made to the animation (the first batch is 1)
+    pub sequence: u64,
+
+    /// The ID of the client that submitted this batch
+    pub client_id: u64,
+
+    /// The edits in this batch
+    pub edits: Vec<AnimationEdit>
+}
+
+///
+/// The messages exchanged between the sync server and its clients
+///
+/// Messages are sent as JSON in websocket text frames.
+///
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum SyncMessage {
+    /// Sent by the server when a client connects, with the ID of the client and the first range of element IDs it can use
+    Welcome(u64, ElementIdRange),
+
+    /// Sent by a client with a batch of edits to make to the animation
+    Submit(Vec<AnimationEdit>),
+
+    /// Sent by the server to every client when it accepts a batch of edits
+    Edits(SequencedEdits),
+
+    /// Sent by a client when it is running out of element IDs
+    RequestElementIds,
+
+    /// Sent by the server in response to `RequestElementIds` with another range of element IDs for the client
+    AssignElementIds(ElementIdRange),
+
+    /// Sent by the server to a client when one of its batches of edits could not be accepted
+    Rejected(String)
+}
+
+impl SyncMessage {
+    ///
+    /// Encodes this message in its wire format
+    ///
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Sync messages can always be encoded")
+    }
+
+    ///
+    /// Decodes a message from its wire format
+    ///
+    pub fn from_json(json: &str) -> Result<SyncMessage, SyncError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+///
+/// True if an edit would create an element without an ID
+///
+/// The server can't accept these edits, as each client would end up assigning its own ID to the new element.
+///
+pub fn has_unassigned_element_id(edit: &AnimationEdit) -> bool {
+    if let AnimationEdit::Motion(ElementId::Unassigned, _) = edit {
+        return true;
+    }
+
+    let mut unassigned = false;
+    edit.clone().assign_element_id(|| { unassigned = true; 0 });
+
+    unassigned
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use flo_canvas::*;
+
+    use std::sync::*;
+    use std::time::Duration;
+
+    #[test]
+    fn encode_and_decode_edits() {
+        let edits = vec![
+            AnimationEdit::AddNewLayer(1),
+            AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0))),
+            AnimationEdit::Layer(1, LayerEdit::SetBlendMode(BlendMode::Multiply)),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Assigned(1), BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+            AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(2), Arc::new(vec![
+                RawPoint::from((10.0, 10.0)),
+                RawPoint::from((20.0, 5.0))
+            ])))),
+            AnimationEdit::Layer(1, LayerEdit::Text(Duration::from_millis(0), TextEdit::CreateText(ElementId::Assigned(3), TextStyle::new(), (100.0, 200.0), "Hello".to_string()))),
+            AnimationEdit::Layer(1, LayerEdit::Image(Duration::from_millis(0), ImageEdit::CreateImage(ElementId::Assigned(5), CanvasImage { format: ImageFormat::Png, width: 2, height: 1, data: Arc::new(vec![137, 80, 78, 71]) }, Transform2D::scale(20.0, 10.0)))),
+            AnimationEdit::Motion(ElementId::Assigned(4), MotionEdit::SetPath(TimeCurve::new(TimePoint::new(0.0, 0.0, Duration::from_millis(0)), TimePoint::new(100.0, 100.0, Duration::from_millis(1000))))),
+            AnimationEdit::Element(vec![ElementId::Assigned(2)], ElementEdit::AddAttachment(ElementId::Assigned(4))),
+            AnimationEdit::SetFrameLength(Duration::new(0, 41_666_667))
+        ];
+
+        let message = SyncMessage::Edits(SequencedEdits { sequence: 1, client_id: 2, edits: edits });
+        let decoded = SyncMessage::from_json(&message.to_json()).unwrap();
+
+        assert!(decoded == message);
+    }
+
+    #[test]
+    fn detect_unassigned_ids() {
+        let assigned    = AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Assigned(2), Arc::new(vec![]))));
+        let unassigned  = AnimationEdit::Layer(1, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![]))));
+
+        assert!(!has_unassigned_element_id(&assigned));
+        assert!(has_unassigned_element_id(&unassigned));
+        assert!(has_unassigned_element_id(&AnimationEdit::Motion(ElementId::Unassigned, MotionEdit::Create)));
+        assert!(!has_unassigned_element_id(&AnimationEdit::SetSize(800.0, 600.0)));
+    }
+}
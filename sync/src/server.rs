@@ -0,0 +1,205 @@
+use super::error::*;
+use super::message::*;
+use super::connection::*;
+use super::element_id_range::*;
+
+use tungstenite;
+
+use std::thread;
+use std::sync::*;
+use std::sync::mpsc;
+use std::net::{TcpListener, TcpStream, SocketAddr, ToSocketAddrs};
+use std::collections::HashMap;
+
+/// The first element ID that the server hands out (IDs below this are left for elements created before the animation was shared)
+pub const FIRST_SHARED_ELEMENT_ID: i64 = 1<<32;
+
+/// The number of element IDs that are handed to a client at once
+pub const ELEMENT_IDS_PER_RANGE: i64 = 1<<20;
+
+///
+/// The state of the sync server
+///
+struct SyncServerCore {
+    /// The ID to assign to the next client that connects
+    next_client_id: u64,
+
+    /// The start of the next range of element IDs to hand out
+    next_element_id: i64,
+
+    /// Every batch of edits that has been accepted so far, in order
+    edits: Vec<SequencedEdits>,
+
+    /// Channels for sending messages to the clients that are currently connected
+    clients: HashMap<u64, mpsc::Sender<SyncMessage>>
+}
+
+///
+/// A server that shares an animation between any number of clients
+///
+/// The server decides the order that edits are applied in: each batch of edits is numbered as it arrives
+/// and then sent to every client. Clients that connect later are sent all of the edits made so far.
+///
+pub struct SyncServer {
+    /// The address the server is listening on
+    address: SocketAddr,
+
+    /// The state of the server
+    core: Arc<Mutex<SyncServerCore>>
+}
+
+impl SyncServer {
+    ///
+    /// Creates a new sync server listening on the specified address (use port 0 to pick any free port)
+    ///
+    pub fn bind<Address: ToSocketAddrs>(address: Address) -> Result<SyncServer, SyncError> {
+        let listener    = TcpListener::bind(address)?;
+        let address     = listener.local_addr()?;
+        let core        = Arc::new(Mutex::new(SyncServerCore::new()));
+
+        // Each client is run on its own thread
+        let accept_core = Arc::clone(&core);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if let Ok(stream) = stream {
+                    let core = Arc::clone(&accept_core);
+                    thread::spawn(move || Self::run_client(core, stream));
+                }
+            }
+        });
+
+        Ok(SyncServer {
+            address:    address,
+            core:       core
+        })
+    }
+
+    ///
+    /// The address that this server is listening on
+    ///
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    ///
+    /// The URL that clients can use to connect to this server
+    ///
+    pub fn url(&self) -> String {
+        format!("ws://{}/", self.address)
+    }
+
+    ///
+    /// The sequence number of the most recent batch of edits accepted by this server (0 if no edits have been made yet)
+    ///
+    pub fn sequence(&self) -> u64 {
+        self.core.lock().unwrap().edits.len() as u64
+    }
+
+    ///
+    /// Runs the connection for a single client
+    ///
+    fn run_client(core: Arc<Mutex<SyncServerCore>>, stream: TcpStream) {
+        // Complete the websocket handshake
+        let socket = match tungstenite::accept(stream) {
+            Ok(socket)  => socket,
+            Err(_)      => { return; }
+        };
+
+        // Register the client
+        let (client, outgoing)  = mpsc::channel();
+        let client_id           = core.lock().unwrap().add_client(client);
+
+        // Process messages until the client disconnects
+        let _ = run_connection(socket, outgoing, |message| core.lock().unwrap().receive(client_id, message));
+
+        core.lock().unwrap().clients.remove(&client_id);
+    }
+}
+
+impl SyncServerCore {
+    ///
+    /// Creates the state for a server where no edits have been made yet
+    ///
+    fn new() -> SyncServerCore {
+        SyncServerCore {
+            next_client_id:     0,
+            next_element_id:    FIRST_SHARED_ELEMENT_ID,
+            edits:              vec![],
+            clients:            HashMap::new()
+        }
+    }
+
+    ///
+    /// Sets aside a new range of element IDs
+    ///
+    fn new_element_id_range(&mut self) -> ElementIdRange {
+        let range               = ElementIdRange::new(self.next_element_id, self.next_element_id + ELEMENT_IDS_PER_RANGE);
+        self.next_element_id    = range.end;
+
+        range
+    }
+
+    ///
+    /// Adds a new client, sending it its ID and the edits that have been made so far
+    ///
+    fn add_client(&mut self, client: mpsc::Sender<SyncMessage>) -> u64 {
+        let client_id           = self.next_client_id;
+        let element_ids         = self.new_element_id_range();
+        self.next_client_id     += 1;
+
+        client.send(SyncMessage::Welcome(client_id, element_ids)).ok();
+        for edits in self.edits.iter() {
+            client.send(SyncMessage::Edits(edits.clone())).ok();
+        }
+
+        self.clients.insert(client_id, client);
+
+        client_id
+    }
+
+    ///
+    /// Sends a message to a single client
+    ///
+    fn send(&self, client_id: u64, message: SyncMessage) {
+        if let Some(client) = self.clients.get(&client_id) {
+            client.send(message).ok();
+        }
+    }
+
+    ///
+    /// Processes a message from a client
+    ///
+    fn receive(&mut self, client_id: u64, message: SyncMessage) {
+        use self::SyncMessage::*;
+
+        match message {
+            Submit(edits) => {
+                if edits.iter().any(|edit| has_unassigned_element_id(edit)) {
+                    // Every client would assign a different ID to these elements
+                    self.send(client_id, Rejected("Edits must not contain unassigned element IDs".to_string()));
+                } else {
+                    // Edits are applied by every client in the order that they arrive here
+                    let edits = SequencedEdits {
+                        sequence:   self.edits.len() as u64 + 1,
+                        client_id:  client_id,
+                        edits:      edits
+                    };
+
+                    self.clients.values().for_each(|client| { client.send(Edits(edits.clone())).ok(); });
+                    self.edits.push(edits);
+                }
+            },
+
+            RequestElementIds => {
+                let element_ids = self.new_element_id_range();
+                self.send(client_id, AssignElementIds(element_ids));
+            },
+
+            // Messages that only the server sends are ignored
+            Welcome(_, _)           |
+            Edits(_)                |
+            AssignElementIds(_)     |
+            Rejected(_)             => { }
+        }
+    }
+}
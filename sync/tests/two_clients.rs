@@ -0,0 +1,129 @@
+extern crate flo_sync;
+extern crate flo_animation;
+extern crate flo_anim_sqlite;
+extern crate futures;
+
+use flo_sync::*;
+use flo_animation::*;
+use flo_anim_sqlite::*;
+
+use futures::executor;
+
+use std::sync::*;
+use std::time::Duration;
+
+fn connect(server: &SyncServer) -> SyncClient<SqliteAnimation> {
+    SyncClient::connect(&server.url(), SqliteAnimation::new_in_memory()).unwrap()
+}
+
+fn edit<Anim: EditableAnimation>(animation: &Anim, edits: Vec<AnimationEdit>) {
+    executor::spawn(animation.edit()).wait_send(edits).unwrap();
+}
+
+fn draw_line(layer_id: u64) -> Vec<AnimationEdit> {
+    vec![
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::SelectBrush(ElementId::Unassigned, BrushDefinition::Ink(InkDefinition::default()), BrushDrawingStyle::Draw))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushProperties(ElementId::Unassigned, BrushProperties::new()))),
+        AnimationEdit::Layer(layer_id, LayerEdit::Paint(Duration::from_millis(0), PaintEdit::BrushStroke(ElementId::Unassigned, Arc::new(vec![
+            RawPoint::from((10.0, 10.0)),
+            RawPoint::from((20.0, 5.0))
+        ]))))
+    ]
+}
+
+fn element_ids<Anim: Animation>(animation: &Anim, layer_id: u64) -> Vec<ElementId> {
+    animation.get_layer_with_id(layer_id).unwrap()
+        .get_frame_at_time(Duration::from_millis(0))
+        .vector_elements()
+        .map(|elements| elements.map(|element| element.id()).collect())
+        .unwrap_or(vec![])
+}
+
+#[test]
+fn edits_are_sent_to_other_clients() {
+    let server  = SyncServer::bind("127.0.0.1:0").unwrap();
+    let client1 = connect(&server);
+    let client2 = connect(&server);
+
+    assert!(client1.client_id() != client2.client_id());
+
+    edit(&client1, vec![AnimationEdit::SetSize(800.0, 600.0), AnimationEdit::AddNewLayer(1)]);
+
+    let sequence = client1.flush();
+    client2.wait_for_sequence(sequence);
+
+    assert!(sequence == 1);
+    assert!(client1.size() == (800.0, 600.0));
+    assert!(client2.size() == (800.0, 600.0));
+    assert!(client2.get_layer_ids() == vec![1]);
+}
+
+#[test]
+fn clients_assign_different_element_ids() {
+    let server  = SyncServer::bind("127.0.0.1:0").unwrap();
+    let client1 = connect(&server);
+    let client2 = connect(&server);
+
+    edit(&client1, vec![AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+    client2.wait_for_sequence(client1.flush());
+
+    // Both clients draw a line at the same time
+    edit(&client1, draw_line(1));
+    edit(&client2, draw_line(1));
+
+    let sequence = client1.flush().max(client2.flush());
+    client1.wait_for_sequence(sequence);
+    client2.wait_for_sequence(sequence);
+
+    // Both lines should be in both animations, with the same IDs
+    let client1_ids = element_ids(&client1, 1);
+    let client2_ids = element_ids(&client2, 1);
+
+    assert!(client1_ids.len() == 2);
+    assert!(client1_ids[0] != client1_ids[1]);
+    assert!(client1_ids == client2_ids);
+    assert!(client1_ids.iter().all(|id| id.id().unwrap() >= FIRST_SHARED_ELEMENT_ID));
+}
+
+#[test]
+fn conflicting_edits_are_applied_in_the_same_order() {
+    let server  = SyncServer::bind("127.0.0.1:0").unwrap();
+    let client1 = connect(&server);
+    let client2 = connect(&server);
+
+    edit(&client1, vec![AnimationEdit::AddNewLayer(1)]);
+    client2.wait_for_sequence(client1.flush());
+
+    // Both clients rename the same layer
+    edit(&client1, vec![AnimationEdit::Layer(1, LayerEdit::SetName("Client 1".to_string()))]);
+    edit(&client2, vec![AnimationEdit::Layer(1, LayerEdit::SetName("Client 2".to_string()))]);
+
+    let sequence = client1.flush().max(client2.flush());
+    client1.wait_for_sequence(sequence);
+    client2.wait_for_sequence(sequence);
+
+    // Whichever rename reached the server last wins on both clients
+    let client1_name = client1.get_layer_with_id(1).unwrap().name();
+    let client2_name = client2.get_layer_with_id(1).unwrap().name();
+
+    assert!(client1_name.is_some());
+    assert!(client1_name == client2_name);
+}
+
+#[test]
+fn late_clients_receive_earlier_edits() {
+    let server  = SyncServer::bind("127.0.0.1:0").unwrap();
+    let client1 = connect(&server);
+
+    edit(&client1, vec![AnimationEdit::AddNewLayer(1), AnimationEdit::Layer(1, LayerEdit::AddKeyFrame(Duration::from_millis(0)))]);
+    edit(&client1, draw_line(1));
+    let sequence = client1.flush();
+
+    // A client that connects now should catch up with the edits that have already been made
+    let client2 = connect(&server);
+    client2.wait_for_sequence(sequence);
+
+    assert!(server.sequence() == sequence);
+    assert!(element_ids(&client2, 1) == element_ids(&client1, 1));
+    assert!(element_ids(&client2, 1).len() == 1);
+}
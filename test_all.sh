@@ -6,10 +6,15 @@ cargo test -p flo_binding
 cargo test -p flo_stream
 cargo test -p flo_curves
 cargo test -p flo_canvas
+cargo test -p flo_canvas_raster
 cargo test -p flo_ui
 cargo test -p flo_ui_files
 cargo test -p flo_animation
 cargo test -p flo_anim_sqlite
+cargo test -p flo_export
+cargo test --manifest-path cli/Cargo.toml
+cargo test --manifest-path import/Cargo.toml
+cargo test --manifest-path sync/Cargo.toml
 cargo test -p flo_static_files --features http
 cargo test -p flo_http_ui --features http
 cargo test -p flo_http_ui_actix --features http
@@ -1,4 +1,5 @@
 use super::paint::*;
+use super::keyboard::*;
 use super::super::property::*;
 
 ///
@@ -34,7 +35,19 @@ pub enum ActionTrigger {
     CancelEdit,
 
     /// Divides a scrollable region into a grid, and generates an event whenever the region in the top-left corner changes
-    VirtualScroll(f32, f32)
+    VirtualScroll(f32, f32),
+
+    /// The user dropped a file onto this item. The parameter is `ActionParameter::File`, containing the name and contents of the file
+    DropFile,
+
+    /// Keyboard shortcut: the user pressed the specified key while holding down exactly the specified modifiers
+    ///
+    /// Shortcuts apply to the whole window rather than to the control they're attached to, so a controller
+    /// declares its shortcuts by attaching this trigger to any control in its UI (usually the root)
+    KeyDown(Key, Vec<Modifier>),
+
+    /// Keyboard shortcut: the user released the specified key while holding down exactly the specified modifiers
+    KeyUp(Key, Vec<Modifier>)
 }
 
 ///
@@ -73,5 +86,8 @@ pub enum ActionParameter {
     /// of 3, 2 in the second would indicate that the client area of the scroll
     /// region is 1536x1024 (ie, you need to draw 3 512x512 squares horizontally
     /// and 2 vertically in order to cover everything the user can currently see)
-    VirtualScroll((u32, u32), (u32, u32))
+    VirtualScroll((u32, u32), (u32, u32)),
+
+    /// A file supplied by the user, as its name and its contents
+    File(String, Vec<u8>)
 }
@@ -0,0 +1,58 @@
+///
+/// Identifies a key on the keyboard
+///
+/// Keys are identified by what they produce rather than where they are on the keyboard, so
+/// `Character` keys are always reported in lower case (shift is reported as a modifier instead).
+/// Names follow the W3C key values used by browsers.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub enum Key {
+    /// A key that generates a character (always lower case)
+    Character(char),
+
+    Enter,
+    Escape,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+
+    /// A function key (F1 is `Function(1)`)
+    Function(u8)
+}
+
+///
+/// A modifier key that can be held down while another key is pressed
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+pub enum Modifier {
+    Shift,
+    Control,
+    Alt,
+
+    /// The 'command' key on OS X or the 'windows' key elsewhere
+    Meta
+}
+
+///
+/// Returns true if two sets of modifiers contain the same keys
+///
+pub fn modifiers_match(a: &[Modifier], b: &[Modifier]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+
+    a.sort();
+    a.dedup();
+    b.sort();
+    b.dedup();
+
+    a == b
+}
@@ -5,6 +5,7 @@ mod mouse;
 mod bounds;
 mod actions;
 mod control;
+mod keyboard;
 mod modifier;
 mod position;
 mod font_attr;
@@ -22,6 +23,7 @@ pub use self::mouse::*;
 pub use self::bounds::*;
 pub use self::actions::*;
 pub use self::control::*;
+pub use self::keyboard::*;
 pub use self::modifier::*;
 pub use self::position::*;
 pub use self::font_attr::*;
@@ -1,4 +1,5 @@
 use super::mouse::*;
+use super::keyboard::*;
 
 ///
 /// The device that caused a painting event
@@ -39,6 +40,54 @@ pub enum PaintAction {
     Cancel
 }
 
+///
+/// The modifier keys that were held down during a painting event
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+pub struct PaintModifiers {
+    pub shift:      bool,
+    pub control:    bool,
+    pub alt:        bool,
+    pub meta:       bool
+}
+
+impl PaintModifiers {
+    ///
+    /// Creates a set of paint modifiers where no keys are held down
+    ///
+    pub fn none() -> PaintModifiers {
+        PaintModifiers { shift: false, control: false, alt: false, meta: false }
+    }
+
+    ///
+    /// Creates a set of paint modifiers from a list of modifier keys
+    ///
+    pub fn from_modifiers(modifiers: &[Modifier]) -> PaintModifiers {
+        PaintModifiers {
+            shift:      modifiers.contains(&Modifier::Shift),
+            control:    modifiers.contains(&Modifier::Control),
+            alt:        modifiers.contains(&Modifier::Alt),
+            meta:       modifiers.contains(&Modifier::Meta)
+        }
+    }
+
+    ///
+    /// True if the specified modifier key was held down
+    ///
+    pub fn contains(&self, modifier: Modifier) -> bool {
+        match modifier {
+            Modifier::Shift     => self.shift,
+            Modifier::Control   => self.control,
+            Modifier::Alt       => self.alt,
+            Modifier::Meta      => self.meta
+        }
+    }
+}
+
+impl Default for PaintModifiers {
+    fn default() -> PaintModifiers { PaintModifiers::none() }
+}
+
 ///
 /// Data for a painting event
 ///
@@ -60,5 +109,9 @@ pub struct Painting {
     pub tilt_x: f32,
 
     /// Y tilt (-90 to 90)
-    pub tilt_y: f32
+    pub tilt_y: f32,
+
+    /// The modifier keys that were held down (user interfaces that can't report these leave them all released)
+    #[serde(default)]
+    pub modifiers: PaintModifiers
 }
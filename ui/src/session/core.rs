@@ -138,6 +138,20 @@ impl UiSessionCore {
                     }
                 },
 
+                UiEvent::KeyDown(key, modifiers) => {
+                    self.dispatch_key(controller, |trigger| match trigger {
+                        ActionTrigger::KeyDown(trigger_key, trigger_modifiers)  => trigger_key == &key && modifiers_match(trigger_modifiers, &modifiers),
+                        _                                                       => false
+                    });
+                },
+
+                UiEvent::KeyUp(key, modifiers) => {
+                    self.dispatch_key(controller, |trigger| match trigger {
+                        ActionTrigger::KeyUp(trigger_key, trigger_modifiers)    => trigger_key == &key && modifiers_match(trigger_modifiers, &modifiers),
+                        _                                                       => false
+                    });
+                },
+
                 UiEvent::SuspendUpdates => {
                     self.suspension_count += 1;
                     self.suspend_updates.wait_send(self.suspension_count > 0).ok();
@@ -220,6 +234,36 @@ impl UiSessionCore {
         self.suspend_updates.get_mut().subscribe()
     }
 
+    ///
+    /// Dispatches a key event to every controller that has an action with a matching trigger
+    ///
+    fn dispatch_key<TriggerMatches: Fn(&ActionTrigger) -> bool>(&mut self, controller: &dyn Controller, trigger_matches: TriggerMatches) {
+        // Find the actions that match this trigger
+        let ui_tree = self.ui_tree.get();
+        let mut key_actions = vec![];
+
+        find_key_actions(&ui_tree, &mut vec![], &trigger_matches, &mut key_actions);
+
+        // Send to the controllers that own them
+        for (controller_path, action_name) in key_actions {
+            if controller_path.len() == 0 {
+                // Straight to the root controller
+                self.dispatch_action(controller, action_name, ActionParameter::None);
+            } else {
+                // Controller along a path
+                let mut controller = controller.get_subcontroller(&controller_path[0]);
+
+                for controller_name in controller_path.into_iter().skip(1) {
+                    controller = controller.map_or(None, move |ctrl| ctrl.get_subcontroller(&controller_name));
+                }
+
+                if let Some(ref controller) = controller {
+                    self.dispatch_action(&**controller, action_name, ActionParameter::None);
+                }
+            }
+        }
+    }
+
     ///
     /// Dispatches an action to a controller
     ///
@@ -246,3 +290,30 @@ impl UiSessionCore {
         self.tick.wait_send(()).ok();
     }
 }
+
+///
+/// Finds the actions in a UI tree whose triggers match a key event, along with the path to the controller that owns them
+///
+fn find_key_actions<TriggerMatches: Fn(&ActionTrigger) -> bool>(node: &Control, controller_path: &mut Vec<String>, trigger_matches: &TriggerMatches, result: &mut Vec<(Vec<String>, String)>) {
+    // Actions on this node belong to the controller that owns it
+    for (trigger, action_name) in node.attributes().filter_map(|attr| attr.action()) {
+        if trigger_matches(trigger) {
+            result.push((controller_path.clone(), action_name.clone()));
+        }
+    }
+
+    // A node's own controller supplies its subcomponents
+    if let Some(subcomponents) = node.subcomponents() {
+        if let Some(controller) = node.controller() {
+            controller_path.push(controller.to_string());
+        }
+
+        for subcomponent in subcomponents.iter() {
+            find_key_actions(subcomponent, controller_path, trigger_matches, result);
+        }
+
+        if node.controller().is_some() {
+            controller_path.pop();
+        }
+    }
+}
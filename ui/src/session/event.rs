@@ -14,6 +14,12 @@ pub enum UiEvent {
     /// Performs the specified action: parameters are the controller path, the event name and the action parameter
     Action(Vec<String>, String, ActionParameter),
 
+    /// The user pressed a key while the window was focused. This is sent as an action to any controller with a matching `KeyDown` trigger
+    KeyDown(Key, Vec<Modifier>),
+
+    /// The user released a key while the window was focused. This is sent as an action to any controller with a matching `KeyUp` trigger
+    KeyUp(Key, Vec<Modifier>),
+
     /// Sends a tick to all the controllers. If updates are suspended, ticks are only sent when they resume,
     /// and only one tick is sent regardless of how many were requested during the suspension.
     Tick
@@ -219,3 +219,59 @@ fn viewmodel_update_triggers_update() {
 */
 
 // TODO: also check we trigger an update if a canvas that's in the UI changes
+
+struct KeyTestController {
+    ui:             Binding<Control>,
+    subcontroller:  Option<Arc<KeyTestController>>,
+    actions:        Arc<Mutex<Vec<String>>>
+}
+
+impl Controller for KeyTestController {
+    fn ui(&self) -> BindRef<Control> {
+        BindRef::new(&self.ui)
+    }
+
+    fn get_subcontroller(&self, id: &str) -> Option<Arc<dyn Controller>> {
+        match (id, &self.subcontroller) {
+            ("Sub", Some(subcontroller))    => Some(subcontroller.clone()),
+            _                               => None
+        }
+    }
+
+    fn action(&self, action_id: &str, _action_data: &ActionParameter) {
+        self.actions.lock().unwrap().push(action_id.to_string());
+    }
+}
+
+#[test]
+fn key_events_are_sent_to_controller_with_matching_shortcut() {
+    let actions         = Arc::new(Mutex::new(vec![]));
+    let subcontroller   = KeyTestController {
+        ui:             bind(Control::empty()
+            .with((ActionTrigger::KeyDown(Key::Character('b'), vec![]), "SubB"))
+            .with((ActionTrigger::KeyUp(Key::Character('b'), vec![]), "SubReleaseB"))),
+        subcontroller:  None,
+        actions:        Arc::clone(&actions)
+    };
+    let controller      = Arc::new(KeyTestController {
+        ui:             bind(Control::container()
+            .with((ActionTrigger::KeyDown(Key::Character('z'), vec![Modifier::Control]), "Undo"))
+            .with((ActionTrigger::KeyDown(Key::Character('z'), vec![Modifier::Shift, Modifier::Control]), "Redo"))
+            .with(vec![Control::empty().with_controller("Sub")])),
+        subcontroller:  Some(Arc::new(subcontroller)),
+        actions:        Arc::clone(&actions)
+    });
+
+    let mut core        = super::core::UiSessionCore::new(controller.clone());
+
+    core.dispatch_event(vec![
+        UiEvent::KeyDown(Key::Character('b'), vec![]),
+        UiEvent::KeyDown(Key::Character('b'), vec![Modifier::Control]),
+        UiEvent::KeyUp(Key::Character('b'), vec![]),
+        UiEvent::KeyDown(Key::Character('z'), vec![Modifier::Control]),
+        UiEvent::KeyDown(Key::Character('z'), vec![Modifier::Control, Modifier::Shift]),
+        UiEvent::KeyDown(Key::Character('x'), vec![])
+    ], &*controller);
+
+    assert!(*actions.lock().unwrap() == vec!["SubB".to_string(), "SubReleaseB".to_string(), "Undo".to_string(), "Redo".to_string()]);
+}
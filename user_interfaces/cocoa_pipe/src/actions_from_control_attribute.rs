@@ -80,6 +80,10 @@ fn event_actions(trigger: &ActionTrigger, name: &String) -> Vec<ViewAction> {
         SetValue                        => vec![ViewAction::RequestEvent(ViewEvent::SetValue, name.clone())],
         CancelEdit                      => vec![ViewAction::RequestEvent(ViewEvent::CancelEdit, name.clone())],
         VirtualScroll(width, height)    => vec![ViewAction::RequestEvent(ViewEvent::VirtualScroll(*width as f64, *height as f64), name.clone())],
+        DropFile                        => vec![],
+
+        KeyDown(_, _)                   => vec![],
+        KeyUp(_, _)                     => vec![],
     }
 }
 
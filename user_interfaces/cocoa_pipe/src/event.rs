@@ -89,7 +89,8 @@ impl AppPainting {
             location:   (self.position_x as f32, self.position_y as f32),
             pressure:   self.pressure as f32,
             tilt_x:     self.tilt_x as f32,
-            tilt_y:     self.tilt_y as f32
+            tilt_y:     self.tilt_y as f32,
+            modifiers:  PaintModifiers::none()
         }
     }
 }
@@ -130,7 +130,7 @@ impl CanvasContext {
                 Line(x, y)                                          => { self.state.path_line(*x as CGFloat, *y as CGFloat); }
                 BezierCurve((ex, ey), (c1x, c1y), (c2x, c2y))       => { self.state.path_bezier_curve((*c1x as CGFloat, *c1y as CGFloat), (*c2x as CGFloat, *c2y as CGFloat), (*ex as CGFloat, *ey as CGFloat)); }
                 ClosePath                                           => { self.state.path_close(); }
-                Fill                                                => { self.state.load_path(); CGContextFillPath(*self.context); }
+                Fill                                                => { self.state.fill_path(); }
                 Stroke                                              => { self.state.load_path(); CGContextStrokePath(*self.context); }
                 LineWidth(width)                                    => { self.state.set_line_width(*width as CGFloat); }
                 LineWidthPixels(width_pixels)                       => {
@@ -148,6 +148,7 @@ impl CanvasContext {
                 DashLength(len)                                     => { /* TODO */ }
                 DashOffset(offset)                                  => { /* TODO */ }
                 FillColor(col)                                      => { self.state.set_fill_color(col); }
+                FillGradient(gradient)                              => { self.state.set_fill_gradient(gradient); }
                 StrokeColor(col)                                    => { self.state.set_stroke_color(col); }
                 BlendMode(blend)                                    => { self.state.set_blend_mode(blend); }
                 Unclip                                              => { self.state.unclip(); }
@@ -206,6 +207,25 @@ impl CanvasContext {
                 }
                 Layer(_layer_id)                                    => { /* Layers need to be implemented elsewhere */ }
                 LayerBlend(_layer_id, _blend)                       => { /* Layers need to be implemented elsewhere */ }
+                LayerAlpha(_layer_id, _alpha)                       => { /* Layers need to be implemented elsewhere */ }
+
+                FontFamily(family)                                  => { self.state.set_font_family(family); }
+                FontSize(size)                                      => { self.state.set_font_size(*size as CGFloat); }
+                TextAlignment(alignment)                            => { self.state.set_text_alignment(alignment); }
+                FillText(text, x, y)                                => { self.state.fill_text(text, *x as CGFloat, *y as CGFloat); }
+                DrawImage(image, transform)                         => {
+                    let Transform2D(a, b, _c)   = transform;
+                    let transform               = CGAffineTransform {
+                        a: a.0 as CGFloat,
+                        b: b.0 as CGFloat,
+                        c: a.1 as CGFloat,
+                        d: b.1 as CGFloat,
+                        tx: a.2 as CGFloat,
+                        ty: b.2 as CGFloat
+                    };
+
+                    self.state.draw_image(image, transform);
+                }
             }
         }
     }
@@ -4,6 +4,9 @@ use flo_canvas::*;
 
 use objc::rc::*;
 
+use std::ptr;
+use std::ffi::CString;
+
 ///
 /// Possible actions stored in the path for this state
 ///
@@ -22,6 +25,7 @@ enum PathAction {
 struct CanvasStateValues {
     color_space:    CFRef<CGColorSpaceRef>,
     fill_color:     CFRef<CGColorRef>,
+    fill_gradient:  Option<(CFRef<CGGradientRef>, Gradient, CGAffineTransform)>,
     stroke_color:   CFRef<CGColorRef>,
     transform:      CGAffineTransform,
     blend_mode:     CGBlendMode,
@@ -31,7 +35,10 @@ struct CanvasStateValues {
     line_width:     CGFloat,
     path:           Vec<PathAction>,
     stored_layer:   Option<StrongPtr>,
-    clip:           Option<Vec<PathAction>>
+    clip:           Option<Vec<PathAction>>,
+    font_family:    String,
+    font_size:      CGFloat,
+    text_alignment: TextAlignment
 }
 
 ///
@@ -59,6 +66,7 @@ impl CanvasState {
                 values:     CanvasStateValues {
                     color_space:    color_space,
                     fill_color:     fill_color,
+                    fill_gradient:  None,
                     stroke_color:   stroke_color,
                     transform:      transform,
                     blend_mode:     CGBlendMode::Normal,
@@ -68,7 +76,10 @@ impl CanvasState {
                     line_width:     1.0,
                     path:           vec![],
                     stored_layer:   None,
-                    clip:           None
+                    clip:           None,
+                    font_family:    "Helvetica".to_string(),
+                    font_size:      12.0,
+                    text_alignment: TextAlignment::Left
                 },
                 stack:      vec![]
             }
@@ -296,7 +307,8 @@ impl CanvasState {
             let new_color           = CFRef::from(CGColorCreate(*self.values.color_space, [r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat].as_ptr()));
 
             // Store it in this object
-            self.values.fill_color  = new_color;
+            self.values.fill_color      = new_color;
+            self.values.fill_gradient   = None;
 
             // Set in the context
             if let Some(ref context) = self.context {
@@ -305,6 +317,72 @@ impl CanvasState {
         }
     }
 
+    ///
+    /// Sets the gradient used for fills (the gradient is transformed by the current transformation)
+    ///
+    pub fn set_fill_gradient(&mut self, gradient: &Gradient) {
+        // Text is filled using a flat colour that approximates the gradient
+        self.set_fill_color(&gradient.average_color());
+
+        unsafe {
+            let stops       = gradient.stops();
+            let components  = stops.iter()
+                .flat_map(|stop| {
+                    let (r, g, b, a) = stop.color.to_rgba_components();
+                    vec![r as CGFloat, g as CGFloat, b as CGFloat, a as CGFloat]
+                })
+                .collect::<Vec<_>>();
+            let locations   = stops.iter().map(|stop| stop.offset as CGFloat).collect::<Vec<_>>();
+            let cg_gradient = CGGradientCreateWithColorComponents(*self.values.color_space, components.as_ptr(), locations.as_ptr(), stops.len());
+
+            if !cg_gradient.is_null() {
+                self.values.fill_gradient = Some((CFRef::from(cg_gradient), gradient.clone(), self.values.transform));
+            }
+        }
+    }
+
+    ///
+    /// Fills the current path using the current fill colour or gradient
+    ///
+    pub fn fill_path(&self) {
+        if let Some(ref context) = self.context {
+            unsafe {
+                match self.values.fill_gradient {
+                    None                                                        => {
+                        self.load_path();
+                        CGContextFillPath(**context);
+                    },
+
+                    Some((ref cg_gradient, ref gradient, gradient_transform))  => {
+                        // Gradients are drawn by clipping to the path
+                        CGContextSaveGState(**context);
+                        self.load_path();
+                        CGContextClip(**context);
+
+                        // The gradient uses the transformation that was set when it was chosen
+                        CGContextConcatCTM(**context, CGAffineTransformConcat(gradient_transform, CGAffineTransformInvert(self.values.transform)));
+
+                        let options = CG_GRADIENT_DRAWS_BEFORE_START_LOCATION | CG_GRADIENT_DRAWS_AFTER_END_LOCATION;
+                        match gradient {
+                            Gradient::Linear((x1, y1), (x2, y2), _) => {
+                                let start   = CGPoint { x: *x1 as CGFloat, y: *y1 as CGFloat };
+                                let end     = CGPoint { x: *x2 as CGFloat, y: *y2 as CGFloat };
+                                CGContextDrawLinearGradient(**context, **cg_gradient, start, end, options);
+                            },
+
+                            Gradient::Radial((x, y), radius, _)     => {
+                                let center  = CGPoint { x: *x as CGFloat, y: *y as CGFloat };
+                                CGContextDrawRadialGradient(**context, **cg_gradient, center, 0.0, center, *radius as CGFloat, options);
+                            }
+                        }
+
+                        CGContextRestoreGState(**context);
+                    }
+                }
+            }
+        }
+    }
+
     ///
     /// Sets the stroke color of this state
     ///
@@ -403,6 +481,87 @@ impl CanvasState {
         self.reapply_state();
     }
 
+    ///
+    /// Sets the font family used for text
+    ///
+    pub fn set_font_family(&mut self, font_family: &str) {
+        self.values.font_family = font_family.to_string();
+    }
+
+    ///
+    /// Sets the size of the font used for text
+    ///
+    pub fn set_font_size(&mut self, font_size: CGFloat) {
+        self.values.font_size = font_size;
+    }
+
+    ///
+    /// Sets how text is aligned relative to the point it's drawn at
+    ///
+    pub fn set_text_alignment(&mut self, alignment: &TextAlignment) {
+        self.values.text_alignment = *alignment;
+    }
+
+    ///
+    /// Fills some text using the current fill colour
+    ///
+    pub fn fill_text(&mut self, text: &str, x: CGFloat, y: CGFloat) {
+        if let Some(ref context) = self.context {
+            unsafe {
+                // The text functions in core graphics only support MacRoman: characters outside of ASCII are replaced
+                let text        = text.chars().map(|chr| if chr.is_ascii() { chr as u8 } else { b'?' }).collect::<Vec<_>>();
+                let font_family = CString::new(self.values.font_family.replace('\0', "")).unwrap();
+
+                CGContextSelectFont(**context, font_family.as_ptr(), self.values.font_size, CGTextEncoding::MacRoman);
+
+                // Canvas coordinates already have y pointing upwards, so the identity text matrix draws the glyphs upright
+                CGContextSetTextMatrix(**context, CGAffineTransformIdentity);
+
+                // Measure the text to work out where it should start
+                CGContextSetTextDrawingMode(**context, CGTextDrawingMode::Invisible);
+                CGContextShowTextAtPoint(**context, 0.0, 0.0, text.as_ptr() as *const _, text.len());
+                let width       = CGContextGetTextPosition(**context).x;
+
+                let x_offset    = match self.values.text_alignment {
+                    TextAlignment::Left     => 0.0,
+                    TextAlignment::Center   => -width/2.0,
+                    TextAlignment::Right    => -width
+                };
+
+                // Draw the text
+                CGContextSetTextDrawingMode(**context, CGTextDrawingMode::Fill);
+                CGContextShowTextAtPoint(**context, x + x_offset, y, text.as_ptr() as *const _, text.len());
+            }
+        }
+    }
+
+    ///
+    /// Draws an image, using a transform that maps the unit square onto the canvas
+    ///
+    pub fn draw_image(&mut self, image: &CanvasImage, transform: CGAffineTransform) {
+        if let Some(ref context) = self.context {
+            unsafe {
+                // Core Graphics decodes the image from a copy of its data
+                let data        = CFRef::from(CFDataCreate(ptr::null(), image.data.as_ptr(), image.data.len() as isize));
+                let provider    = CFRef::from(CGDataProviderCreateWithCFData(*data));
+                let cg_image    = match image.format {
+                    ImageFormat::Png    => CGImageCreateWithPNGDataProvider(*provider, ptr::null(), true, CGColorRenderingIntent::Default),
+                    ImageFormat::Jpeg   => CGImageCreateWithJPEGDataProvider(*provider, ptr::null(), true, CGColorRenderingIntent::Default)
+                };
+
+                // Images that can't be decoded are not drawn
+                if cg_image.is_null() { return; }
+                let cg_image    = CFRef::from(cg_image);
+
+                // Core graphics draws images with their bottom-left corner at the origin of the rectangle, so the unit square is drawn without flipping
+                CGContextSaveGState(**context);
+                CGContextConcatCTM(**context, transform);
+                CGContextDrawImage(**context, CGRect { origin: CGPoint { x: 0.0, y: 0.0 }, size: CGSize { width: 1.0, height: 1.0 } }, *cg_image);
+                CGContextRestoreGState(**context);
+            }
+        }
+    }
+
     ///
     /// Sets the layer that we should draw to for this context
     ///
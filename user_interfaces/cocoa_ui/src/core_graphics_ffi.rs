@@ -1,6 +1,8 @@
 //! FFI for core graphics functions
 #[cfg(target_pointer_width = "32")] use std::os::raw::c_float;
 #[cfg(target_pointer_width = "64")] use std::os::raw::c_double;
+use std::os::raw::c_char;
+use std::os::raw::c_void;
 use std::ops::Deref;
 
 #[cfg(target_pointer_width = "64")] pub type CGFloat = c_double;
@@ -9,6 +11,9 @@ use std::ops::Deref;
 #[repr(C)] pub struct __CFString { _private: [u8; 0] }
 pub type CFStringRef = *mut __CFString;
 
+#[repr(C)] pub struct __CFData { _private: [u8; 0] }
+pub type CFDataRef = *mut __CFData;
+
 #[repr(C)] pub struct CGContext {  _private: [u8; 0] }
 pub type CGContextRef = *mut CGContext;
 
@@ -21,6 +26,19 @@ pub type CGColorRef = *mut CGColor;
 #[repr(C)] pub struct CGMutablePath { _private: [u8; 0] }
 pub type CGMutablePathRef = *mut CGMutablePath;
 
+#[repr(C)] pub struct CGDataProvider { _private: [u8; 0] }
+pub type CGDataProviderRef = *mut CGDataProvider;
+
+#[repr(C)] pub struct CGImage { _private: [u8; 0] }
+pub type CGImageRef = *mut CGImage;
+
+#[repr(C)] pub struct CGGradient { _private: [u8; 0] }
+pub type CGGradientRef = *mut CGGradient;
+
+pub type CGGradientDrawingOptions = u32;
+pub const CG_GRADIENT_DRAWS_BEFORE_START_LOCATION: CGGradientDrawingOptions = 1;
+pub const CG_GRADIENT_DRAWS_AFTER_END_LOCATION: CGGradientDrawingOptions = 2;
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)] pub struct CGAffineTransform {
     pub a: CGFloat,
@@ -96,6 +114,33 @@ pub type CGMutablePathRef = *mut CGMutablePath;
     Square
 }
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)] pub enum CGTextEncoding {
+    FontSpecific,
+    MacRoman
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)] pub enum CGTextDrawingMode {
+    Fill,
+    Stroke,
+    FillStroke,
+    Invisible,
+    FillClip,
+    StrokeClip,
+    FillStrokeClip,
+    Clip
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)] pub enum CGColorRenderingIntent {
+    Default,
+    AbsoluteColorimetric,
+    RelativeColorimetric,
+    Perceptual,
+    Saturation
+}
+
 unsafe impl objc::Encode for CGPoint {
     fn encode() -> objc::Encoding {
         let encoding = format!("{{CGPoint={}{}}}",
@@ -123,6 +168,13 @@ unsafe impl objc::Encode for CGRect {
     }
 }
 
+#[link(name = "CoreFoundation", kind = "framework")]
+extern {
+    pub fn CFRetain(cf: *const c_void);
+    pub fn CFRelease(cf: *const c_void);
+    pub fn CFDataCreate(allocator: *const c_void, bytes: *const u8, length: isize) -> CFDataRef;
+}
+
 #[link(name = "CoreGraphics", kind = "framework")]
 extern {
     pub static kCGColorSpaceSRGB: CFStringRef;
@@ -143,6 +195,19 @@ extern {
     pub fn CGAffineTransformConcat(t1: CGAffineTransform, t2: CGAffineTransform) -> CGAffineTransform;
     pub fn CGPointApplyAffineTransform(CGPoint: CGPoint, t: CGAffineTransform) -> CGPoint;
 
+    pub fn CGDataProviderRetain(provider: CGDataProviderRef);
+    pub fn CGDataProviderRelease(provider: CGDataProviderRef);
+    pub fn CGDataProviderCreateWithCFData(data: CFDataRef) -> CGDataProviderRef;
+
+    pub fn CGImageRetain(image: CGImageRef);
+    pub fn CGImageRelease(image: CGImageRef);
+    pub fn CGImageCreateWithPNGDataProvider(source: CGDataProviderRef, decode: *const CGFloat, should_interpolate: bool, intent: CGColorRenderingIntent) -> CGImageRef;
+    pub fn CGImageCreateWithJPEGDataProvider(source: CGDataProviderRef, decode: *const CGFloat, should_interpolate: bool, intent: CGColorRenderingIntent) -> CGImageRef;
+
+    pub fn CGGradientRetain(gradient: CGGradientRef);
+    pub fn CGGradientRelease(gradient: CGGradientRef);
+    pub fn CGGradientCreateWithColorComponents(space: CGColorSpaceRef, components: *const CGFloat, locations: *const CGFloat, count: usize) -> CGGradientRef;
+
     pub fn CGPathCreateMutable() -> CGMutablePathRef;
     pub fn CGPathCreateMutableCopy(path: CGMutablePathRef) -> CGMutablePathRef;
     pub fn CGPathRetain(path: CGMutablePathRef);
@@ -174,6 +239,14 @@ extern {
     pub fn CGContextAddPath(ctxt: CGContextRef, path: CGMutablePathRef);
     pub fn CGContextClearRect(ctxt: CGContextRef, rect: CGRect);
     pub fn CGContextClip(ctxt: CGContextRef);
+    pub fn CGContextSelectFont(ctxt: CGContextRef, name: *const c_char, size: CGFloat, textEncoding: CGTextEncoding);
+    pub fn CGContextSetTextDrawingMode(ctxt: CGContextRef, mode: CGTextDrawingMode);
+    pub fn CGContextSetTextMatrix(ctxt: CGContextRef, t: CGAffineTransform);
+    pub fn CGContextShowTextAtPoint(ctxt: CGContextRef, x: CGFloat, y: CGFloat, string: *const c_char, length: usize);
+    pub fn CGContextGetTextPosition(ctxt: CGContextRef) -> CGPoint;
+    pub fn CGContextDrawImage(ctxt: CGContextRef, rect: CGRect, image: CGImageRef);
+    pub fn CGContextDrawLinearGradient(ctxt: CGContextRef, gradient: CGGradientRef, start_point: CGPoint, end_point: CGPoint, options: CGGradientDrawingOptions);
+    pub fn CGContextDrawRadialGradient(ctxt: CGContextRef, gradient: CGGradientRef, start_center: CGPoint, start_radius: CGFloat, end_center: CGPoint, end_radius: CGFloat, options: CGGradientDrawingOptions);
 }
 
 pub trait CFReleasable {
@@ -225,6 +298,50 @@ impl CFReleasable for CGMutablePathRef {
     }
 }
 
+impl CFReleasable for CFDataRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CFRetain(*self as *const c_void); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CFRelease(*self as *const c_void); }
+    }
+}
+
+impl CFReleasable for CGDataProviderRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CGDataProviderRetain(*self); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CGDataProviderRelease(*self); }
+    }
+}
+
+impl CFReleasable for CGImageRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CGImageRetain(*self); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CGImageRelease(*self); }
+    }
+}
+
+impl CFReleasable for CGGradientRef {
+    #[inline] fn retain(&self) -> Self {
+        unsafe { CGGradientRetain(*self); }
+        *self
+    }
+
+    #[inline] fn release(&self) {
+        unsafe { CGGradientRelease(*self); }
+    }
+}
+
 pub struct CFRef<T: CFReleasable>(T);
 
 impl<T: CFReleasable> Clone for CFRef<T> {
@@ -5,6 +5,15 @@ use flo_canvas::*;
 
 use cairo;
 use cairo::*;
+use gdk::prelude::*;
+use gdk_pixbuf;
+use gio;
+use glib;
+
+use std::sync::*;
+
+/// The number of decoded images that are kept for drawing again
+const MAX_CACHED_IMAGES: usize = 8;
 
 ///
 /// The current source colour that's set
@@ -22,7 +31,9 @@ enum ColorTarget {
 struct SavedState {
     dash_pattern:   Vec<f64>,
     stroke_color:   Color,
-    fill_color:     Color
+    fill_color:     Color,
+    fill_gradient:  Option<flo::Gradient>,
+    text_alignment: TextAlignment
 }
 
 impl SavedState {
@@ -33,7 +44,9 @@ impl SavedState {
         SavedState {
             dash_pattern:   drawing.dash_pattern.clone(),
             stroke_color:   drawing.stroke_color.clone(),
-            fill_color:     drawing.fill_color.clone()
+            fill_color:     drawing.fill_color.clone(),
+            fill_gradient:  drawing.fill_gradient.clone(),
+            text_alignment: drawing.text_alignment
         }
     }
 
@@ -44,6 +57,8 @@ impl SavedState {
         drawing.dash_pattern    = self.dash_pattern;
         drawing.stroke_color    = self.stroke_color;
         drawing.fill_color      = self.fill_color;
+        drawing.fill_gradient   = self.fill_gradient;
+        drawing.text_alignment  = self.text_alignment;
         drawing.set_color       = ColorTarget::None;
     }
 }
@@ -57,8 +72,12 @@ pub struct CairoState {
     line_join:      cairo::LineJoin,
     line_cap:       cairo::LineCap,
     fill_color:     Color,
+    fill_gradient:  Option<flo::Gradient>,
     stroke_color:   Color,
-    dash_pattern:   Vec<f64>
+    dash_pattern:   Vec<f64>,
+    font_family:    String,
+    font_size:      f64,
+    text_alignment: TextAlignment
 }
 
 ///
@@ -83,14 +102,29 @@ pub struct CairoDraw {
     /// The current fill colour
     fill_color: Color,
 
+    /// The gradient to fill with instead of the fill colour, if there is one
+    fill_gradient: Option<flo::Gradient>,
+
     /// The colour that's currently set
     set_color: ColorTarget,
 
+    /// The font family used for text
+    font_family: String,
+
+    /// The size of the font used for text
+    font_size: f64,
+
+    /// How text is aligned relative to the point it's drawn at
+    text_alignment: TextAlignment,
+
     /// The viewport for this canvas
     viewport: CanvasViewport,
 
     /// The initial translation matrix
-    initial_matrix: Matrix
+    initial_matrix: Matrix,
+
+    /// The most recently drawn images, along with their decoded pixbufs
+    images: Vec<(Arc<Vec<u8>>, gdk_pixbuf::Pixbuf)>
 }
 
 impl CairoState {
@@ -116,9 +150,14 @@ impl CairoDraw {
             dash_pattern:   vec![],
             stroke_color:   Color::Rgba(0.0, 0.0, 0.0, 1.0),
             fill_color:     Color::Rgba(0.0, 0.0, 0.0, 1.0),
+            fill_gradient:  None,
             set_color:      ColorTarget::None,
+            font_family:    "sans-serif".to_string(),
+            font_size:      12.0,
+            text_alignment: TextAlignment::Left,
             initial_matrix: Matrix::from(&viewport),
-            viewport:       viewport
+            viewport:       viewport,
+            images:         vec![]
         }
     }
 
@@ -149,6 +188,14 @@ impl CairoDraw {
     ///
     #[inline]
     fn set_color(&mut self, target: ColorTarget) {
+        // Gradients are positioned using the transform at the point they're set, so they're always set again
+        if let (ColorTarget::Fill, Some(gradient)) = (target, &self.fill_gradient) {
+            self.ctxt.set_source(&Self::get_gradient_pattern(gradient));
+            self.set_color = ColorTarget::None;
+
+            return;
+        }
+
         // Only change the colour if it's not already set
         if self.set_color != target {
             // Get the RGBA components for this target
@@ -171,7 +218,7 @@ impl CairoDraw {
     ///
     /// Converts a blend mode into an operator
     ///
-    fn get_operator(blend: flo::BlendMode) -> cairo::Operator {
+    pub fn get_operator(blend: flo::BlendMode) -> cairo::Operator {
         match blend {
             flo::BlendMode::SourceOver      => cairo::Operator::Over,
             flo::BlendMode::SourceIn        => cairo::Operator::In,
@@ -188,6 +235,35 @@ impl CairoDraw {
         }
     }
 
+    ///
+    /// Converts a Flo gradient to a Cairo pattern
+    ///
+    fn get_gradient_pattern(gradient: &flo::Gradient) -> Pattern {
+        match gradient {
+            flo::Gradient::Linear((x1, y1), (x2, y2), stops)    => {
+                let linear = LinearGradient::new(*x1 as f64, *y1 as f64, *x2 as f64, *y2 as f64);
+                Self::add_gradient_stops(&linear, stops);
+                Pattern::LinearGradient(linear)
+            },
+
+            flo::Gradient::Radial((x, y), radius, stops)        => {
+                let radial = RadialGradient::new(*x as f64, *y as f64, 0.0, *x as f64, *y as f64, *radius as f64);
+                Self::add_gradient_stops(&radial, stops);
+                Pattern::RadialGradient(radial)
+            }
+        }
+    }
+
+    ///
+    /// Adds the stops from a Flo gradient to a Cairo gradient
+    ///
+    fn add_gradient_stops<TGradient: cairo::Gradient>(gradient: &TGradient, stops: &[GradientStop]) {
+        for stop in stops {
+            let (r, g, b, a) = stop.color.to_rgba_components();
+            gradient.add_color_stop_rgba(stop.offset as f64, r as f64, g as f64, b as f64, a as f64);
+        }
+    }
+
     ///
     /// Converts a Flo Transform2D to a Cairo matrix
     ///
@@ -287,8 +363,12 @@ impl CairoDraw {
         let line_join       = self.ctxt.get_line_join();
         let line_cap        = self.ctxt.get_line_cap();
         let fill_color      = self.fill_color;
+        let fill_gradient   = self.fill_gradient.clone();
         let stroke_color    = self.stroke_color;
         let dash_pattern    = self.dash_pattern.clone();
+        let font_family     = self.font_family.clone();
+        let font_size       = self.font_size;
+        let text_alignment  = self.text_alignment;
 
         CairoState {
             transform,
@@ -296,8 +376,12 @@ impl CairoDraw {
             line_join,
             line_cap,
             fill_color,
+            fill_gradient,
             stroke_color,
-            dash_pattern
+            dash_pattern,
+            font_family,
+            font_size,
+            text_alignment
         }
     }
 
@@ -310,9 +394,93 @@ impl CairoDraw {
         self.ctxt.set_line_join(state.line_join);
         self.ctxt.set_line_cap(state.line_cap);
         self.fill_color     = state.fill_color;
+        self.fill_gradient  = state.fill_gradient.clone();
         self.stroke_color   = state.stroke_color;
         self.dash_pattern   = state.dash_pattern.clone();
         self.set_color      = ColorTarget::None;
+        self.font_family    = state.font_family.clone();
+        self.font_size      = state.font_size;
+        self.text_alignment = state.text_alignment;
+        self.update_font();
+    }
+
+    ///
+    /// Updates the font face used by the Cairo context
+    ///
+    fn update_font(&self) {
+        self.ctxt.select_font_face(&self.font_family, FontSlant::Normal, FontWeight::Normal);
+        self.ctxt.set_font_size(self.font_size);
+    }
+
+    ///
+    /// Fills some text using the current fill colour
+    ///
+    fn fill_text(&mut self, text: &str, x: f32, y: f32) {
+        self.set_color(ColorTarget::Fill);
+
+        // Offset the start of the text according to the alignment
+        let width       = self.ctxt.text_extents(text).x_advance;
+        let x_offset    = match self.text_alignment {
+            TextAlignment::Left     => 0.0,
+            TextAlignment::Center   => -width/2.0,
+            TextAlignment::Right    => -width
+        };
+
+        // Canvas coordinates have y pointing upwards, so the text is flipped to draw it upright
+        self.ctxt.save();
+        self.ctxt.translate(x as f64, y as f64);
+        self.ctxt.scale(1.0, -1.0);
+        self.ctxt.move_to(x_offset, 0.0);
+        self.ctxt.show_text(text);
+        self.ctxt.restore();
+    }
+
+    ///
+    /// Decodes the pixbuf for an image (images are usually drawn many times, so recently drawn images are kept)
+    ///
+    fn pixbuf_for_image(&mut self, image: &CanvasImage) -> Option<gdk_pixbuf::Pixbuf> {
+        if let Some((_, pixbuf)) = self.images.iter().find(|(data, _)| Arc::ptr_eq(data, &image.data) || **data == *image.data) {
+            return Some(pixbuf.clone());
+        }
+
+        // Gdk can decode both PNG and JPEG data
+        let bytes           = glib::Bytes::from_owned(image.data.to_vec());
+        let input_stream    = gio::MemoryInputStream::new_from_bytes(&bytes);
+        let not_cancellable: Option<gio::Cancellable> = None;
+        let pixbuf          = gdk_pixbuf::Pixbuf::new_from_stream(&input_stream, not_cancellable.as_ref()).ok()?;
+
+        if self.images.len() >= MAX_CACHED_IMAGES {
+            self.images.remove(0);
+        }
+        self.images.push((Arc::clone(&image.data), pixbuf.clone()));
+
+        Some(pixbuf)
+    }
+
+    ///
+    /// Draws an image, mapping the unit square to the canvas using the specified transform
+    ///
+    fn draw_image(&mut self, image: &CanvasImage, transform: Transform2D) {
+        let pixbuf = match self.pixbuf_for_image(image) {
+            Some(pixbuf)    => pixbuf,
+            None            => { return; }
+        };
+
+        let width   = pixbuf.get_width() as f64;
+        let height  = pixbuf.get_height() as f64;
+        if width <= 0.0 || height <= 0.0 { return; }
+
+        // Pixbufs have their origin at the top-left, so flip the image so its bottom-left corner is at (0, 0)
+        self.ctxt.save();
+        self.ctxt.transform(Self::get_transform(transform));
+        self.ctxt.translate(0.0, 1.0);
+        self.ctxt.scale(1.0/width, -1.0/height);
+        self.ctxt.set_source_pixbuf(&pixbuf, 0.0, 0.0);
+        self.ctxt.paint();
+        self.ctxt.restore();
+
+        // The image replaced the source colour
+        self.set_color = ColorTarget::None;
     }
 
     ///
@@ -336,7 +504,8 @@ impl CairoDraw {
             NewDashPattern                              => { self.dash_pattern = vec![]; self.ctxt.set_dash(&[], 0.0); },
             DashLength(length)                          => { self.dash_pattern.push(length as f64); self.ctxt.set_dash(&self.dash_pattern, self.ctxt.get_dash_offset()); },
             DashOffset(offset)                          => { self.ctxt.set_dash(&self.dash_pattern, offset as f64); },
-            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; },
+            FillColor(color)                            => { self.set_color = ColorTarget::None; self.fill_color = color; self.fill_gradient = None; },
+            FillGradient(gradient)                      => { self.set_color = ColorTarget::None; self.fill_gradient = Some(gradient); },
             StrokeColor(color)                          => { self.set_color = ColorTarget::None; self.stroke_color = color; },
             BlendMode(blend)                            => { self.ctxt.set_operator(Self::get_operator(blend)); },
             IdentityTransform                           => { self.ctxt.set_matrix(self.initial_matrix); },
@@ -350,6 +519,12 @@ impl CairoDraw {
             PopState                                    => { self.ctxt.restore(); self.saved_states.pop().map(|state| state.restore(self)); },
             Layer(_layer_id)                            => { /* Layers require external support */ },
             LayerBlend(_layer_id, _mode)                => { /* Layers require external support */ },
+            LayerAlpha(_layer_id, _alpha)               => { /* Layers require external support */ },
+            FontFamily(family)                          => { self.font_family = family; self.update_font(); },
+            FontSize(size)                              => { self.font_size = size as f64; self.update_font(); },
+            TextAlignment(alignment)                    => { self.text_alignment = alignment; },
+            FillText(text, x, y)                        => { self.fill_text(&text, x, y); },
+            DrawImage(image, transform)                 => { self.draw_image(&image, transform); },
 
             CanvasHeight(height)                        => {
                 let transform   = self.initial_matrix.clone();
@@ -379,7 +554,11 @@ impl CairoDraw {
                 self.stroke_color   = Color::Rgba(0.0, 0.0, 0.0, 1.0);
                 self.set_color      = ColorTarget::None;
                 self.dash_pattern   = vec![];
+                self.font_family    = "sans-serif".to_string();
+                self.font_size      = 12.0;
+                self.text_alignment = TextAlignment::Left;
 
+                self.update_font();
                 self.ctxt.set_dash(&[], 0.0);
                 self.ctxt.set_line_width(1.0);
                 self.ctxt.set_operator(Operator::Over);
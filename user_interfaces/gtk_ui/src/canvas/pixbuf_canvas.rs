@@ -16,7 +16,13 @@ struct Layer {
     stored: Option<cairo::ImageSurface>,
 
     /// Context that this surface will be drawn upon
-    context: CairoDraw
+    context: CairoDraw,
+
+    /// How this layer is blended with the layers underneath
+    blend_mode: BlendMode,
+
+    /// The opacity of this layer
+    alpha: f64
 }
 
 ///
@@ -151,6 +157,26 @@ impl PixBufCanvas {
                 let pixel_scale = self.pixel_scale;
                 let layer       = self.layers.entry(current_layer).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
                 layer.context.draw(Draw::ClearLayer);
+
+                // Clearing a layer also resets how it's blended
+                layer.blend_mode    = BlendMode::SourceOver;
+                layer.alpha         = 1.0;
+            },
+
+            Draw::LayerBlend(layer_id, blend_mode) => {
+                let viewport        = &self.viewport;
+                let pixel_scale     = self.pixel_scale;
+                let layer           = self.layers.entry(layer_id).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
+
+                layer.blend_mode    = blend_mode;
+            },
+
+            Draw::LayerAlpha(layer_id, alpha) => {
+                let viewport        = &self.viewport;
+                let pixel_scale     = self.pixel_scale;
+                let layer           = self.layers.entry(layer_id).or_insert_with(|| Self::create_layer(viewport, pixel_scale));
+
+                layer.alpha         = (alpha as f64).max(0.0).min(1.0);
             },
 
             Draw::Layer(new_layer_id) => {
@@ -217,9 +243,9 @@ impl PixBufCanvas {
             let layer_pattern = cairo::SurfacePattern::create(&layer.surface);
             layer_pattern.set_filter(cairo::Filter::Nearest);
 
-            drawable.set_operator(cairo::Operator::Over);
+            drawable.set_operator(CairoDraw::get_operator(layer.blend_mode));
             drawable.set_source(&Pattern::SurfacePattern(layer_pattern));
-            drawable.paint();
+            drawable.paint_with_alpha(layer.alpha);
         }
 
         drawable.restore();
@@ -260,7 +286,9 @@ impl PixBufCanvas {
         let new_layer = Layer {
             surface:    surface,
             context:    draw,
-            stored:     None
+            stored:     None,
+            blend_mode: BlendMode::SourceOver,
+            alpha:      1.0
         };
 
         new_layer
@@ -1,6 +1,8 @@
 use super::gtk_action::*;
 use super::gtk_event_parameter::*;
 
+use flo_ui::*;
+
 ///
 /// User interface events that can be generated by Gtk
 ///
@@ -15,6 +17,12 @@ pub enum GtkEvent {
     /// A window was closed by the user
     CloseWindow(WindowId),
 
+    /// The user pressed a key while a window was focused
+    KeyDown(WindowId, Key, Vec<Modifier>),
+
+    /// The user released a key while a window was focused
+    KeyUp(WindowId, Key, Vec<Modifier>),
+
     /// Registered event has occurred on a widget
     Event(WidgetId, String, GtkEventParameter)
 }
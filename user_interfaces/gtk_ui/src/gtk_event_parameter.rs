@@ -1,3 +1,5 @@
+use super::widgets::keyboard::*;
+
 use flo_ui::*;
 
 use glib::translate::*;
@@ -58,7 +60,8 @@ pub struct GtkPainting {
     pub position:   (f64, f64),
     pub pressure:   f64,
     pub xtilt:      f64,
-    pub ytilt:      f64
+    pub ytilt:      f64,
+    pub modifiers:  PaintModifiers
 }
 
 impl From<GtkEventParameter> for ActionParameter {
@@ -130,7 +133,8 @@ impl GtkPainting {
             position: button.get_position(),
             pressure: 1.0,
             xtilt: 0.0,
-            ytilt: 0.0
+            ytilt: 0.0,
+            modifiers: PaintModifiers::from_modifiers(&modifiers_for_state(button.get_state()))
         };
 
         // Update from the axes available from this device
@@ -152,7 +156,8 @@ impl GtkPainting {
             position: motion.get_position(),
             pressure: 1.0,
             xtilt: 0.0,
-            ytilt: 0.0
+            ytilt: 0.0,
+            modifiers: PaintModifiers::from_modifiers(&modifiers_for_state(motion.get_state()))
         };
 
         // Update from the axes available from this device
@@ -185,7 +190,8 @@ impl GtkPainting {
             location:   (x as f32, y as f32),
             pressure:   self.pressure as f32,
             tilt_x:     self.xtilt as f32,
-            tilt_y:     self.ytilt as f32
+            tilt_y:     self.ytilt as f32,
+            modifiers:  self.modifiers
         }
     }
 }
@@ -226,6 +226,8 @@ impl GtkSessionCore {
             None                                        => vec![],
             CloseWindow(_window_id)                     => vec![],
             Tick                                        => vec![ UiEvent::Tick ],
+            KeyDown(_window_id, key, modifiers)         => vec![ UiEvent::KeyDown(key, modifiers) ],
+            KeyUp(_window_id, key, modifiers)           => vec![ UiEvent::KeyUp(key, modifiers) ],
             Event(widget, event_name, parameter)        => self.controller_for_widget.get(&widget)
                 .map(|controller| vec![ UiEvent::Action((**controller).clone(), event_name, parameter.into()) ])
                 .unwrap_or(vec![])
@@ -584,7 +586,10 @@ impl GtkSessionCore {
                     CancelEdit                      => vec![ /* TODO */ ],
                     EditValue                       => vec![ RequestEvent(GtkWidgetEventType::EditValue, action_name) ],
                     SetValue                        => vec![ RequestEvent(GtkWidgetEventType::SetValue, action_name) ],
-                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ]
+                    VirtualScroll(width, height)    => vec![ RequestEvent(GtkWidgetEventType::VirtualScroll(width, height), action_name) ],
+                    DropFile                        => vec![ /* TODO */ ],
+                    KeyDown(_, _)                   => vec![ /* Keyboard events are sent by the window */ ],
+                    KeyUp(_, _)                     => vec![ /* Keyboard events are sent by the window */ ]
                 }
             })
             .collect()
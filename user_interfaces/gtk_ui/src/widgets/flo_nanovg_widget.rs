@@ -283,7 +283,7 @@ impl FloNanoVgWidget {
         let actions: Vec<_> = actions.into_iter().collect();
         if !core.needs_redraw {
             for action in actions.iter() {
-                core.layers.as_mut().map(|layers| layers.draw(*action));
+                core.layers.as_mut().map(|layers| layers.draw(action.clone()));
             }
         }
         core.canvas.write(actions);
@@ -300,7 +300,7 @@ impl GtkUiWidget for FloNanoVgWidget {
 
     fn process(&mut self, flo_gtk: &mut FloGtk, action: &GtkWidgetAction) {
         match action {
-            &GtkWidgetAction::Content(WidgetContent::Draw(ref drawing)) => self.draw(drawing.iter().map(|draw| *draw)),
+            &GtkWidgetAction::Content(WidgetContent::Draw(ref drawing)) => self.draw(drawing.iter().map(|draw| draw.clone())),
             other_action                                                => process_basic_widget_action(self, flo_gtk, other_action)
         }
     }
@@ -0,0 +1,59 @@
+use flo_ui::*;
+
+use gdk;
+use gdk::enums::key;
+
+///
+/// Converts a GDK key event into a FlowBetween key, if it's one that we can represent
+///
+pub fn key_for_event(event: &gdk::EventKey) -> Option<Key> {
+    let keyval = event.get_keyval();
+
+    match keyval {
+        key::Return | key::KP_Enter    => Some(Key::Enter),
+        key::Escape                    => Some(Key::Escape),
+        key::Tab | key::ISO_Left_Tab   => Some(Key::Tab),
+        key::BackSpace                 => Some(Key::Backspace),
+        key::Delete | key::KP_Delete   => Some(Key::Delete),
+        key::Insert                    => Some(Key::Insert),
+        key::Home                      => Some(Key::Home),
+        key::End                       => Some(Key::End),
+        key::Page_Up                   => Some(Key::PageUp),
+        key::Page_Down                 => Some(Key::PageDown),
+        key::Left                      => Some(Key::ArrowLeft),
+        key::Right                     => Some(Key::ArrowRight),
+        key::Up                        => Some(Key::ArrowUp),
+        key::Down                      => Some(Key::ArrowDown),
+
+        _ if keyval >= key::F1 && keyval <= key::F12 => Some(Key::Function((keyval - key::F1 + 1) as u8)),
+
+        _ => {
+            // Characters are always reported in lower case, with shift as a separate modifier
+            gdk::keyval_to_unicode(gdk::keyval_to_lower(keyval))
+                .filter(|chr| !chr.is_control())
+                .map(|chr| Key::Character(chr.to_lowercase().next().unwrap_or(chr)))
+        }
+    }
+}
+
+///
+/// Retrieves the modifiers that were held down for a GDK key event
+///
+pub fn modifiers_for_event(event: &gdk::EventKey) -> Vec<Modifier> {
+    modifiers_for_state(event.get_state())
+}
+
+///
+/// Retrieves the modifiers that are held down in a GDK modifier state
+///
+pub fn modifiers_for_state(state: gdk::ModifierType) -> Vec<Modifier> {
+    let mut modifiers   = vec![];
+
+    if state.contains(gdk::ModifierType::SHIFT_MASK)    { modifiers.push(Modifier::Shift); }
+    if state.contains(gdk::ModifierType::CONTROL_MASK)  { modifiers.push(Modifier::Control); }
+    if state.contains(gdk::ModifierType::MOD1_MASK)     { modifiers.push(Modifier::Alt); }
+    if state.contains(gdk::ModifierType::META_MASK)
+        || state.contains(gdk::ModifierType::SUPER_MASK) { modifiers.push(Modifier::Meta); }
+
+    modifiers
+}
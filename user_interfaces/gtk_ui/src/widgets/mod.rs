@@ -25,6 +25,7 @@ mod click;
 mod drag;
 mod paint;
 mod events;
+pub mod keyboard;
 
 pub use self::image::*;
 pub use self::window::*;
@@ -1,4 +1,5 @@
 use super::factory::*;
+use super::keyboard::*;
 use super::super::gtk_event::*;
 use super::super::gtk_thread::*;
 use super::super::gtk_action::*;
@@ -31,10 +32,39 @@ fn wire_up_window(new_window: &gtk::Window, window_id: WindowId, flo_gtk: &mut F
     let event_sink = flo_gtk.get_event_sink();
 
     // GTK events are Fn() and not FnMut() :-/ Use a cell so we can actually send events.
-    let event_sink = RefCell::new(event_sink);
+    let event_sink = Rc::new(RefCell::new(event_sink));
 
     // Send the close event when the window is closed
-    new_window.connect_hide(move |_window| { event_sink.borrow_mut().start_send(GtkEvent::CloseWindow(window_id)).unwrap(); });
+    let close_sink = Rc::clone(&event_sink);
+    new_window.connect_hide(move |_window| { close_sink.borrow_mut().start_send(GtkEvent::CloseWindow(window_id)).unwrap(); });
+
+    // Keyboard events are sent to the whole window, unless the user is typing into a text box
+    let key_down_sink = Rc::clone(&event_sink);
+    new_window.connect_key_press_event(move |window, event| {
+        if let (false, Some(key)) = (is_editing_text(window), key_for_event(event)) {
+            key_down_sink.borrow_mut().start_send(GtkEvent::KeyDown(window_id, key, modifiers_for_event(event))).unwrap();
+        }
+
+        Inhibit(false)
+    });
+
+    let key_up_sink = Rc::clone(&event_sink);
+    new_window.connect_key_release_event(move |window, event| {
+        if let (false, Some(key)) = (is_editing_text(window), key_for_event(event)) {
+            key_up_sink.borrow_mut().start_send(GtkEvent::KeyUp(window_id, key, modifiers_for_event(event))).unwrap();
+        }
+
+        Inhibit(false)
+    });
+}
+
+///
+/// True if the focused widget in a window is one that the user can type into
+///
+fn is_editing_text(window: &gtk::Window) -> bool {
+    window.get_focus()
+        .map(|focused| focused.is::<gtk::Entry>())
+        .unwrap_or(false)
 }
 
 ///
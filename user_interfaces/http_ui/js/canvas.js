@@ -161,7 +161,12 @@ let flo_canvas = (function() {
         let last_store_pos              = null;
         let layer_canvases              = null;
         let blend_for_layer             = {};
+        let alpha_for_layer             = {};
         let current_layer_id            = 0;
+        let current_font_family         = 'sans-serif';
+        let current_font_size           = 12.0;
+        let image_cache                 = {};
+        let previous_image_cache        = {};
 
         ///
         /// Sets the current transform (lack of browser support for currentTransform means we have to track this independently)
@@ -220,6 +225,27 @@ let flo_canvas = (function() {
             context.fillStyle = 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')';
         }
 
+        function fill_gradient(gradient) {
+            let style;
+
+            if (gradient.type === 'linear') {
+                style = context.createLinearGradient(gradient.x1, gradient.y1, gradient.x2, gradient.y2);
+            } else {
+                style = context.createRadialGradient(gradient.x, gradient.y, 0, gradient.x, gradient.y, gradient.radius);
+            }
+
+            gradient.stops.forEach(stop => {
+                let r = Math.floor(stop.color[0]*255.0);
+                let g = Math.floor(stop.color[1]*255.0);
+                let b = Math.floor(stop.color[2]*255.0);
+                let a = stop.color[3];
+
+                style.addColorStop(Math.min(1.0, Math.max(0.0, stop.offset)), 'rgba(' + r + ',' + g + ',' + b + ',' + a + ')');
+            });
+
+            context.fillStyle = style;
+        }
+
         function stroke_color(r, g, b, a) {
             r = Math.floor(r*255.0);
             g = Math.floor(g*255.0);
@@ -268,6 +294,74 @@ let flo_canvas = (function() {
             context.globalCompositeOperation = blend_mode;
         }
 
+        function update_font() {
+            // Generic font families can't be quoted
+            let generic_families    = [ 'serif', 'sans-serif', 'monospace', 'cursive', 'fantasy', 'system-ui' ];
+            let family              = generic_families.indexOf(current_font_family) >= 0 ? current_font_family : '"' + current_font_family.replace(/"/g, '') + '"';
+
+            context.font = current_font_size + 'px ' + family;
+        }
+
+        function font_family(family) {
+            current_font_family = family;
+            update_font();
+        }
+
+        function font_size(size) {
+            current_font_size = size;
+            update_font();
+        }
+
+        function text_alignment(alignment) {
+            context.textAlign = alignment;
+        }
+
+        function fill_text(text, x, y) {
+            // Canvas coordinates have y pointing upwards, so the text is flipped to draw it upright
+            context.save();
+            context.translate(x, y);
+            context.scale(1, -1);
+            context.fillText(text, 0, 0);
+            context.restore();
+        }
+
+        ///
+        /// Retrieves the image element for some image data (images are reused between redraws)
+        ///
+        function load_image(data_url) {
+            let image = image_cache[data_url] || previous_image_cache[data_url];
+
+            if (!image) {
+                // Images load asynchronously, so redraw the canvas once the image is available
+                image           = new Image();
+                image.onload    = () => {
+                    replay_drawing();
+                    draw_layers();
+                };
+                image.src       = data_url;
+            }
+
+            image_cache[data_url] = image;
+            return image;
+        }
+
+        function draw_image(data_url, image_transform) {
+            let image = load_image(data_url);
+
+            // Images that haven't loaded yet are drawn when the canvas is redrawn
+            if (!image.complete || image.naturalWidth === 0) {
+                return;
+            }
+
+            // The transform maps the unit square to the canvas, with the bottom-left corner of the image at the origin
+            context.save();
+            context.transform(image_transform[0], image_transform[3], image_transform[1], image_transform[4], image_transform[2], image_transform[5]);
+            context.translate(0, 1);
+            context.scale(1.0/image.naturalWidth, -1.0/image.naturalHeight);
+            context.drawImage(image, 0, 0);
+            context.restore();
+        }
+
         function identity_transform() {
             canvas_height(2.0);
         }
@@ -553,6 +647,7 @@ let flo_canvas = (function() {
 
             // Reset the blend mode
             blend_for_layer[current_layer_id] = 'source-over';
+            alpha_for_layer[current_layer_id] = 1.0;
 
             // Remove everything from the canvas that was on this layer
             for (let index=1; index<replay.length; ++index) {
@@ -571,11 +666,16 @@ let flo_canvas = (function() {
             blend_for_layer[layer_id] = blend_mode;
         }
 
+        function layer_alpha(layer_id, alpha) {
+            alpha_for_layer[layer_id] = alpha;
+        }
+
         function clear_canvas() {
             // Clear layers
             layer_canvases      = null;
             context             = canvas.getContext('2d');
             blend_for_layer     = {};
+            alpha_for_layer     = {};
             current_layer_id    = 0;
 
             // Clear
@@ -594,6 +694,13 @@ let flo_canvas = (function() {
             fill_color(0,0,0,1);
             stroke_color(0,0,0,1);
             line_width(1.0);
+            font_family('sans-serif');
+            font_size(12.0);
+            text_alignment('left');
+
+            // Keep the images from the previous drawing in case they're drawn again
+            previous_image_cache    = image_cache;
+            image_cache             = {};
         }
 
         function rewind_to_last_store() {
@@ -650,9 +757,14 @@ let flo_canvas = (function() {
 
                 // Draw each of the layers
                 Object.keys(layer_canvases).forEach(layer_id => {
-                    layer_context.globalCompositeOperation = blend_for_layer[layer_id] || 'source-over';
+                    let alpha = alpha_for_layer[layer_id];
+
+                    layer_context.globalCompositeOperation  = blend_for_layer[layer_id] || 'source-over';
+                    layer_context.globalAlpha               = (alpha === undefined) ? 1.0 : alpha;
                     layer_context.drawImage(layer_canvases[layer_id], 0,0, width,height);
                 });
+
+                layer_context.globalAlpha = 1.0;
             }
         }
 
@@ -672,6 +784,7 @@ let flo_canvas = (function() {
             dash_length:        (length)        => { replay.push([dash_length, [length], current_layer_id]);                dash_length(length);            },
             dash_offset:        (offset)        => { replay.push([dash_offset, [offset], current_layer_id]);                dash_length(offset);            },
             fill_color:         (r, g, b, a)    => { replay.push([fill_color, [r, g, b, a], current_layer_id]);             fill_color(r, g, b, a);         },
+            fill_gradient:      (gradient)      => { replay.push([fill_gradient, [gradient], current_layer_id]);            fill_gradient(gradient);        },
             stroke_color:       (r, g, b, a)    => { replay.push([stroke_color, [r, g, b, a], current_layer_id]);           stroke_color(r, g, b, a);       },
             blend_mode:         (mode)          => { replay.push([blend_mode, [mode], current_layer_id]);                   blend_mode(mode);               },
             identity_transform: ()              => { replay.push([identity_transform, [], current_layer_id]);               identity_transform();           },
@@ -687,8 +800,14 @@ let flo_canvas = (function() {
             pop_state:          ()              => { replay.push([pop_state, [], current_layer_id]);                        pop_state();                    },
             layer:              (layer_id)      => { replay.push([layer, [layer_id], layer]);                               layer(layer_id);                },
             layer_blend:        (layer_id, blend_mode) => { replay.push([layer_blend, [layer_id, blend_mode], -1]);         layer_blend(layer_id, blend_mode); },
+            layer_alpha:        (layer_id, alpha) => { replay.push([layer_alpha, [layer_id, alpha], -1]);                   layer_alpha(layer_id, alpha);   },
             clear_layer:        ()              => { replay.push([clear_layer, [], current_layer_id]);                      clear_layer();                  },
             clear_canvas:       ()              => { replay = [ [clear_canvas, [], current_layer_id] ];                     clear_canvas();                 },
+            font_family:        (family)        => { replay.push([font_family, [family], current_layer_id]);                font_family(family);            },
+            font_size:          (size)          => { replay.push([font_size, [size], current_layer_id]);                    font_size(size);                },
+            text_alignment:     (alignment)     => { replay.push([text_alignment, [alignment], current_layer_id]);          text_alignment(alignment);      },
+            fill_text:          (text, x, y)    => { replay.push([fill_text, [text, x, y], current_layer_id]);              fill_text(text, x, y);          },
+            draw_image:         (data_url, transform) => { replay.push([draw_image, [data_url, transform], current_layer_id]); draw_image(data_url, transform); },
 
             replay_drawing:     replay_drawing,
             map_coords:         map_coords,
@@ -817,15 +936,48 @@ let flo_canvas = (function() {
                 case 'A':   draw.clear_canvas();    break;
                 case 'l':   draw.layer(read_u32()); break;
                 case 'b':   draw.layer_blend(read_u32(), decode_blend_mode()); break;
+                case 'a':   draw.layer_alpha(read_u32(), read_float()); break;
                 case 'C':   draw.clear_layer();     break;
                 }
             };
 
+            ///
+            /// Decodes a gradient (used by the fill gradient operation)
+            ///
+            let decode_gradient = () => {
+                let gradient_type   = read_char();
+                let gradient;
+
+                switch (gradient_type) {
+                case 'L':   gradient = { type: 'linear', x1: read_float(), y1: read_float(), x2: read_float(), y2: read_float() }; break;
+                case 'R':   gradient = { type: 'radial', x: read_float(), y: read_float(), radius: read_float() }; break;
+                default:    throw 'Unknown gradient type: \'' + gradient_type + '\'';
+                }
+
+                let num_stops   = read_u32();
+                gradient.stops  = [];
+
+                for (let stop=0; stop<num_stops; ++stop) {
+                    let offset  = read_float();
+                    let color   = read_rgba();
+
+                    gradient.stops.push({ offset: offset, color: color });
+                }
+
+                return gradient;
+            };
+
             ///
             /// Decodes a colour operation
             ///
             let decode_color = () => {
                 let color_target    = read_char();
+
+                if (color_target === 'g') {
+                    draw.fill_gradient(decode_gradient());
+                    return;
+                }
+
                 let color           = read_rgba();
 
                 switch (color_target) {
@@ -913,6 +1065,74 @@ let flo_canvas = (function() {
                 }
             };
             
+            ///
+            /// Reads a string (a length followed by a code point for each character)
+            ///
+            let read_string = () => {
+                let length = read_u32();
+                let result = '';
+
+                for (let p=0; p<length; ++p) {
+                    result += String.fromCodePoint(read_u32());
+                }
+
+                return result;
+            };
+
+            let decode_text = () => {
+                switch (read_char()) {
+                case 'f':   draw.font_family(read_string());    break;
+                case 's':   draw.font_size(read_float());       break;
+                case 'a':
+                    switch (read_char()) {
+                    case 'L':   draw.text_alignment('left');    break;
+                    case 'C':   draw.text_alignment('center');  break;
+                    case 'R':   draw.text_alignment('right');   break;
+                    }
+                    break;
+                case 't':
+                    {
+                        let x = read_float();
+                        let y = read_float();
+                        draw.fill_text(read_string(), x, y);
+                    }
+                    break;
+                }
+            };
+
+            ///
+            /// Reads an array of bytes as a base64 string (a length followed by the base64 characters without padding)
+            ///
+            let read_base64 = () => {
+                let length      = read_u32();
+                let num_chars   = Math.floor((length*4 + 2)/3);
+                let result      = serialized_instructions.substring(pos, pos+num_chars);
+                pos += num_chars;
+
+                while ((result.length % 4) !== 0) {
+                    result += '=';
+                }
+
+                return result;
+            };
+
+            let decode_image = () => {
+                switch (read_char()) {
+                case 'd':
+                    {
+                        let transform = [ 1,0,0, 0,1,0, 0,0,1 ];
+                        for (let p=0; p<9; ++p) transform[p] = read_float();
+
+                        let mime_type = read_char() === 'J' ? 'image/jpeg' : 'image/png';
+                        read_u32();     // Width
+                        read_u32();     // Height
+
+                        draw.draw_image('data:' + mime_type + ';base64,' + read_base64(), transform);
+                    }
+                    break;
+                }
+            };
+
             let decode_dash         = () => { throw 'Not implemented'; };
             
             for(;;) {
@@ -938,6 +1158,8 @@ let flo_canvas = (function() {
                 case 'M':   decode_blend_mode();                        break;
                 case 'T':   decode_transform();                         break;
                 case 'Z':   decode_clip();                              break;
+                case 'X':   decode_text();                              break;
+                case 'I':   decode_image();                             break;
                 case 'P':   draw.push_state();                          break;
                 case 'p':   draw.pop_state();                           break;
 
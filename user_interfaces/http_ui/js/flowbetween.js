@@ -1158,6 +1158,40 @@ function flowbetween(root_node) {
         }
     };
 
+    ///
+    /// Wires up a file drop action to a node
+    ///
+    /// The contents of the file are sent along with the action (as an array of bytes)
+    ///
+    let wire_drop_file = (action_name, node, controller_path) => {
+        // Files can only be dropped onto nodes that cancel the dragover event
+        add_action_event(node, 'dragover', event => {
+            event.preventDefault();
+        }, false);
+
+        add_action_event(node, 'drop', event => {
+            let files = event.dataTransfer ? event.dataTransfer.files : null;
+            if (!files || files.length === 0) {
+                return;
+            }
+
+            event.preventDefault();
+            event.stopPropagation();
+
+            // Only the first file is sent
+            let file    = files[0];
+            let reader  = new FileReader();
+
+            reader.onload = () => {
+                let data = Array.from(new Uint8Array(reader.result));
+
+                note('Drop file ' + file.name + ' --> ' + controller_path);
+                perform_action(controller_path, action_name, { 'File': [ file.name, data ] });
+            };
+            reader.readAsArrayBuffer(file);
+        }, false);
+    };
+
     ///
     /// Wires up an action to a node
     ///
@@ -1184,6 +1218,9 @@ function flowbetween(root_node) {
         } else if (action_type === 'Drag') {
             wire_drag(action_name, node, controller_path);
 
+        } else if (action_type === 'DropFile') {
+            wire_drop_file(action_name, node, controller_path);
+
         } else if (action_type === 'Focused') {
             node.flo_was_focused = new_property_value => perform_action(controller_path, action_name, null);
 
@@ -1196,6 +1233,9 @@ function flowbetween(root_node) {
         } else if (action_type === 'CancelEdit') {
             node.flo_cancel_edit = new_property_value => perform_action(controller_path, action_name, null);
 
+        } else if (action_type['KeyDown'] || action_type['KeyUp']) {
+            // Keyboard shortcuts are sent for the whole document rather than for individual nodes
+
         } else if (action_type === 'Dismiss') {
             node.flo_dismiss = () => perform_action(controller_path, action_name, null);
 
@@ -2217,6 +2257,90 @@ function flowbetween(root_node) {
         return send_request(request);
     };
 
+    ///
+    /// Converts a browser key name into a FlowBetween key (or null if it's not a key that we support)
+    ///
+    let key_for_event = (ev) => {
+        let key = ev.key;
+
+        switch (key) {
+            case 'Enter':
+            case 'Escape':
+            case 'Tab':
+            case 'Backspace':
+            case 'Delete':
+            case 'Insert':
+            case 'Home':
+            case 'End':
+            case 'PageUp':
+            case 'PageDown':
+            case 'ArrowLeft':
+            case 'ArrowRight':
+            case 'ArrowUp':
+            case 'ArrowDown':
+                return key;
+
+            default:
+                if (/^F[0-9]+$/.test(key)) {
+                    return { 'Function': parseInt(key.substring(1)) };
+                } else if (key.length === 1) {
+                    // Characters are always sent in lower case, with shift as a separate modifier
+                    return { 'Character': key.toLowerCase() };
+                } else {
+                    return null;
+                }
+        }
+    };
+
+    ///
+    /// Retrieves the modifier keys that were held down for a key event
+    ///
+    let modifiers_for_event = (ev) => {
+        let modifiers = [];
+
+        if (ev.shiftKey)    { modifiers.push('Shift'); }
+        if (ev.ctrlKey)     { modifiers.push('Control'); }
+        if (ev.altKey)      { modifiers.push('Alt'); }
+        if (ev.metaKey)     { modifiers.push('Meta'); }
+
+        return modifiers;
+    };
+
+    ///
+    /// True if a key event is going to a node that the user can type into
+    ///
+    let is_editing_text = (ev) => {
+        let target = ev.target;
+
+        if (!target) {
+            return false;
+        }
+
+        let tag_name = (target.tagName || '').toLowerCase();
+        return tag_name === 'input' || tag_name === 'textarea' || target.isContentEditable;
+    };
+
+    ///
+    /// Sends a key event to the session (event_type is 'KeyDown' or 'KeyUp')
+    ///
+    let perform_key_event = (event_type, ev) => {
+        if (is_editing_text(ev)) {
+            return;
+        }
+
+        let key = key_for_event(ev);
+        if (key === null) {
+            return;
+        }
+
+        let key_event           = {};
+        key_event[event_type]   = [key, modifiers_for_event(ev)];
+
+        let request = make_request([ make_event(key_event) ], running_session_id);
+
+        return send_request(request);
+    };
+
     ///
     /// ===== DEBUGGING AND INTROSPECTION
     ///
@@ -2300,6 +2424,10 @@ function flowbetween(root_node) {
         passive: true
     });
 
+    // Keyboard events are sent to the session, where they're matched against the shortcuts declared by the controllers
+    document.addEventListener('keydown', ev => perform_key_event('KeyDown', ev));
+    document.addEventListener('keyup', ev => perform_key_event('KeyUp', ev));
+
     // Prepare for painting
     flo_paint.initialise(add_action_event, perform_action);
 
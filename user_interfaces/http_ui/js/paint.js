@@ -22,6 +22,18 @@ let flo_paint = (function() {
     let supports_pointer_events = 'onpointerdown' in window;
     let supports_touch_events   = 'ontouchstart' in window;
 
+    ///
+    /// Retrieves the modifier keys that were held down for an event
+    ///
+    let modifiers_for_event = (ev) => {
+        return {
+            shift:      !!ev.shiftKey,
+            control:    !!ev.ctrlKey,
+            alt:        !!ev.altKey,
+            meta:       !!ev.metaKey
+        };
+    };
+
     ///
     /// Converts a MouseEvent to a Paint object.
     ///
@@ -53,7 +65,8 @@ let flo_paint = (function() {
             location:   [x, y],
             pressure:   mouse_event.mozPressure || 0.5,
             tilt_x:     0,
-            tilt_y:     0
+            tilt_y:     0,
+            modifiers:  modifiers_for_event(mouse_event)
         };
     };
 
@@ -91,7 +104,8 @@ let flo_paint = (function() {
             location:   [x, y],
             pressure:   touch.force || 0.5,
             tilt_x:     0,
-            tilt_y:     0
+            tilt_y:     0,
+            modifiers:  modifiers_for_event(touch_event)
         };
     };
 
@@ -125,7 +139,8 @@ let flo_paint = (function() {
             location:   [x, y],
             pressure:   pointer_event.pressure,
             tilt_x:     pointer_event.tiltX,
-            tilt_y:     pointer_event.tiltY
+            tilt_y:     pointer_event.tiltY,
+            modifiers:  modifiers_for_event(pointer_event)
         };
     };
 
@@ -1,4 +1,4 @@
-use ui::ActionParameter;
+use ui::{ActionParameter, Key, Modifier};
 
 ///
 /// Represents details of an event from the browser side
@@ -20,6 +20,16 @@ pub enum Event {
     ///
     Action(Vec<String>, String, ActionParameter),
 
+    ///
+    /// The user pressed a key (parameters are the key and the modifiers that were held down)
+    ///
+    KeyDown(Key, Vec<Modifier>),
+
+    ///
+    /// The user released a key
+    ///
+    KeyUp(Key, Vec<Modifier>),
+
     ///
     /// Wait for the next resume before sending any further updates. This is used when we don't want intermediate states to be displayed to the UI during event processing
     ///
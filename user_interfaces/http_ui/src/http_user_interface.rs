@@ -61,6 +61,8 @@ impl<CoreUi: CoreUserInterface> HttpUserInterface<CoreUi> {
             Tick            => UiEvent::Tick,
             SuspendUpdates  => UiEvent::SuspendUpdates,
             ResumeUpdates   => UiEvent::ResumeUpdates,
+            KeyDown(key, modifiers) => UiEvent::KeyDown(key, modifiers),
+            KeyUp(key, modifiers)   => UiEvent::KeyUp(key, modifiers),
 
             Action(controller_path, action_name, action_parameter) => UiEvent::Action(controller_path, action_name, action_parameter)
         }
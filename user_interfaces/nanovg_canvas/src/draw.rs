@@ -3,10 +3,12 @@ use super::paint::*;
 use super::viewport::*;
 
 use flo_canvas;
-use flo_canvas::Draw;
+use flo_canvas::{Draw, Transform2D};
 use nanovg::*;
 use nanovg;
 
+use std::mem;
+
 ///
 /// Represents state associated with sending canvas drawing commands to a nanovg frame
 ///
@@ -30,7 +32,13 @@ pub struct NanoVgDrawingState {
     stroke_options: StrokeOptions,
 
     /// Current path options
-    path_options: PathOptions
+    path_options: PathOptions,
+
+    /// The size of the font used for text
+    font_size: f32,
+
+    /// How text is aligned relative to the point where it's drawn
+    text_alignment: flo_canvas::TextAlignment
 }
 
 impl NanoVgDrawingState {
@@ -45,7 +53,9 @@ impl NanoVgDrawingState {
             fill:               NanoVgPaint::Color(nanovg::Color::new(0.0, 0.0, 0.0, 1.0)),
             fill_options:       FillOptions { antialias: true },
             stroke_options:     StrokeOptions { width: 1.0, line_cap: LineCap::Butt, line_join: LineJoin::Miter, miter_limit: 16.0, antialias: true },
-            path_options:       PathOptions { clip: Clip::None, composite_operation: CompositeOperation::Basic(BasicCompositeOperation::SourceOver), alpha: 1.0, transform: Some(viewport.to_transform()) }
+            path_options:       PathOptions { clip: Clip::None, composite_operation: CompositeOperation::Basic(BasicCompositeOperation::SourceOver), alpha: 1.0, transform: Some(viewport.to_transform()) },
+            font_size:          12.0,
+            text_alignment:     flo_canvas::TextAlignment::Left
         }
     }
 
@@ -89,6 +99,27 @@ impl NanoVgDrawingState {
         self.path_options.clone());
     }
 
+    ///
+    /// Fills a line of text on the current frame
+    ///
+    /// No fonts are loaded into nanovg, so this fills the outline of the built-in block font from flo_canvas
+    ///
+    fn fill_text<'a>(&mut self, text: &str, x: f32, y: f32, frame: &Frame<'a>) {
+        let text_path = flo_canvas::block_font_text_path(text, x, y, self.font_size, self.text_alignment)
+            .into_iter()
+            .filter_map(|draw| match draw {
+                Draw::Move(x, y)    => Some(NanoVgPath::MoveTo(x, y)),
+                Draw::Line(x, y)    => Some(NanoVgPath::LineTo(x, y)),
+                Draw::ClosePath     => Some(NanoVgPath::Close),
+                _                   => None
+            })
+            .collect();
+
+        let old_path = mem::replace(&mut self.path, text_path);
+        self.fill_path(frame);
+        self.path = old_path;
+    }
+
     ///
     /// Converts a canvas blending mode into a nanovg blending mdoe
     ///
@@ -189,6 +220,28 @@ impl NanoVgDrawingState {
             .map(|transform| center * transform);
     }
 
+    ///
+    /// Draws an image, using a transform that maps the unit square onto the canvas
+    ///
+    pub fn draw_image<'a, 'b>(&self, image: &nanovg::Image<'b>, transform: Transform2D, frame: &Frame<'a>) {
+        let Transform2D(a, b, _) = transform;
+
+        // The image pattern has its origin at the top-left, so it's flipped to put the bottom-left corner at (0, 0)
+        let mut flip            = Transform::new();
+        flip.matrix             = [1.0, 0.0, 0.0, -1.0, 0.0, 1.0];
+        let mut image_transform = Transform::new();
+        image_transform.matrix  = [a.0, b.0, a.1, b.1, a.2, b.2];
+
+        let mut path_options    = self.path_options.clone();
+        path_options.transform  = path_options.transform.map(|transform| flip * image_transform * transform);
+
+        frame.path(|path| {
+            path.rect((0.0, 0.0), (1.0, 1.0));
+            path.fill(ImagePattern { image: image, origin: (0.0, 0.0), size: (1.0, 1.0), angle: 0.0, alpha: 1.0 }, FillOptions { antialias: true });
+        },
+        path_options);
+    }
+
     ///
     /// Performs a drawing action on the specified frame
     ///
@@ -211,6 +264,7 @@ impl NanoVgDrawingState {
             DashLength(len)                             => { /* Dashed paths are not supported by nanovg at the moment */ },
             DashOffset(offset)                          => { /* Dashed paths are not supported by nanovg */ },
             FillColor(col)                              => { self.fill = col.into(); },
+            FillGradient(gradient)                      => { self.fill = NanoVgPaint::from(&gradient); },
             StrokeColor(col)                            => { self.stroke = col.into(); },
             BlendMode(blend)                            => { self.path_options.composite_operation = Self::blend_mode(blend); },
             IdentityTransform                           => { self.path_options.transform = Some(self.viewport.to_transform()) },
@@ -227,7 +281,13 @@ impl NanoVgDrawingState {
             ClearCanvas                                 => { },
             Layer(layer_id)                             => { },
             LayerBlend(layer_id, mode)                  => { },
-            ClearLayer                                  => { }
+            LayerAlpha(layer_id, alpha)                 => { },
+            ClearLayer                                  => { },
+            FontFamily(family)                          => { },
+            FontSize(size)                              => { self.font_size = size; },
+            TextAlignment(alignment)                    => { self.text_alignment = alignment; },
+            FillText(text, x, y)                        => { self.fill_text(&text, x, y, frame); },
+            DrawImage(_image, _transform)               => { /* Images are loaded by the layers before the frame starts, then drawn using draw_image() */ }
         }
     }
 }
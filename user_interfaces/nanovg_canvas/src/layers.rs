@@ -146,6 +146,15 @@ impl NanoVgLayers {
         // Take the pending actions for the current layer
         mem::swap(&mut actions, &mut self.pending_for_layer);
 
+        // Images are deleted when they're dropped so they need to be loaded before the frame starts (nanovg renders when the frame finishes)
+        let images: Vec<_>  = actions.iter()
+            .filter_map(|action| match action {
+                Draw::DrawImage(image, _)   => Some(nanovg::Image::new(&layer.context).build_from_memory(&image.data).ok()),
+                _                           => None
+            })
+            .collect();
+        let mut images      = images.iter();
+
         // Set the GL viewport for drawing
         unsafe { gl::Viewport(0, 0, viewport.viewport_width, viewport.viewport_height) };
 
@@ -157,7 +166,7 @@ impl NanoVgLayers {
 
         layer.context.frame((frame_width, frame_height), scale_factor, move |frame| {
             for action in actions {
-                Self::flush_to_layer(state, action, &frame);
+                Self::flush_to_layer(state, action, &mut images, &frame);
             }
 
             state.commit(&frame);
@@ -171,8 +180,15 @@ impl NanoVgLayers {
     /// Performs a draw action on the current layer
     ///
     #[inline]
-    fn flush_to_layer<'a>(state: &mut NanoVgDrawingState, action: Draw, frame: &'a nanovg::Frame<'a>) {
+    fn flush_to_layer<'a, 'b, 'c: 'b, Images: Iterator<Item=&'b Option<nanovg::Image<'c>>>>(state: &mut NanoVgDrawingState, action: Draw, images: &mut Images, frame: &'a nanovg::Frame<'a>) {
         match action {
+            // Images were loaded before the frame started
+            Draw::DrawImage(_, transform)   => {
+                if let Some(Some(image)) = images.next() {
+                    state.draw_image(image, transform, frame);
+                }
+            },
+
             // Most actions are directly processed by the layer
             other_action => state.draw(other_action, frame)
         }
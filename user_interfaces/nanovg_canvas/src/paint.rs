@@ -17,6 +17,45 @@ impl From<flo_canvas::Color> for NanoVgPaint {
     }
 }
 
+impl<'a> From<&'a flo_canvas::Gradient> for NanoVgPaint {
+    ///
+    /// NanoVG gradients only have two colours, so this uses the first and last stops of the canvas gradient
+    ///
+    fn from(item: &flo_canvas::Gradient) -> NanoVgPaint {
+        let stops               = item.stops();
+        let first_offset        = stops.first().map(|stop| stop.offset).unwrap_or(0.0);
+        let last_offset         = stops.last().map(|stop| stop.offset).unwrap_or(1.0);
+
+        let (r, g, b, a)        = item.start_color().to_rgba_components();
+        let start_color         = Color::new(r, g, b, a);
+        let (r, g, b, a)        = item.end_color().to_rgba_components();
+        let end_color           = Color::new(r, g, b, a);
+
+        match item {
+            flo_canvas::Gradient::Linear((x1, y1), (x2, y2), _) => {
+                let (dx, dy)    = (x2-x1, y2-y1);
+
+                NanoVgPaint::Gradient(Gradient::Linear {
+                    start:          (x1 + dx*first_offset, y1 + dy*first_offset),
+                    end:            (x1 + dx*last_offset, y1 + dy*last_offset),
+                    start_color:    start_color,
+                    end_color:      end_color
+                })
+            },
+
+            flo_canvas::Gradient::Radial(center, radius, _) => {
+                NanoVgPaint::Gradient(Gradient::Radial {
+                    center:         *center,
+                    inner_radius:   radius * first_offset,
+                    outer_radius:   radius * last_offset,
+                    start_color:    start_color,
+                    end_color:      end_color
+                })
+            }
+        }
+    }
+}
+
 impl Paint for NanoVgPaint {
     #[inline]
     fn fill(&self, context: &Context) {